@@ -0,0 +1,11 @@
+//! Emits the `native_backend` cfg, set when the `native` feature is enabled
+//! outside of WebAssembly, so the sources don't repeat the whole condition.
+
+fn main() {
+    println!("cargo::rustc-check-cfg=cfg(native_backend)");
+    let native = std::env::var_os("CARGO_FEATURE_NATIVE").is_some();
+    let wasm = std::env::var("CARGO_CFG_TARGET_ARCH").is_ok_and(|arch| arch == "wasm32");
+    if native && !wasm {
+        println!("cargo::rustc-cfg=native_backend");
+    }
+}
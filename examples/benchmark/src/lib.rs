@@ -66,35 +66,16 @@ impl WebCipher {
     pub async fn encrypt(&self, input: &[u8]) -> Result<Vec<u8>, JsError> {
         use browser_crypto::algorithm::Algorithm;
 
-        // Each encryption gets its own 96-bit nonce
-        let nonce = browser_crypto::aes256gcm::Aes256Gcm::generate_nonce()
-            .map_err(|_| JsError::new("unable to generate nonce"))?;
-        let ciphertext = self
-            .0
-            .encrypt(&nonce, input)
-            .await
-            .map_err(|_| JsError::new("unable to encrypt payload"))?;
-
-        // We pack the nonce with the encrypted data
-        let mut result = nonce.to_vec();
-        result.extend(ciphertext);
-        Ok(result)
+        // Each encryption gets its own 96-bit nonce, packed with the encrypted
+        // data
+        Ok(self.0.seal(input).await?)
     }
 
     #[wasm_bindgen]
     pub async fn decrypt(&self, input: &[u8]) -> Result<Vec<u8>, JsError> {
-        use browser_crypto::algorithm::{Algorithm, Nonce};
+        use browser_crypto::algorithm::Algorithm;
 
         // First 12 bytes are our nonce
-        let Some((nonce, payload)) = input.split_at_checked(12) else {
-            return Err(JsError::new("unable to extract nonce"));
-        };
-
-        let nonce = Nonce::from_slice(nonce).map_err(|_| JsError::new("unable to parse nonce"))?;
-
-        self.0
-            .decrypt(&nonce, payload)
-            .await
-            .map_err(|_| JsError::new("unable to decrypt payload"))
+        Ok(self.0.open(input).await?)
     }
 }
@@ -0,0 +1,224 @@
+//! Compatibility with the `aead` crate
+//!
+//! The RustCrypto ciphers implement the traits of the `aead` crate, which are
+//! synchronous, while the Web Crypto API only returns promises. [`AsyncAead`]
+//! is their async analogue: the same methods, taking the same nonces and
+//! [`Payload`]s and failing with the same opaque [`aead::Error`], only to be
+//! awaited. [`AeadAdapter`] implements it, along with [`AeadCore`] and
+//! [`KeySizeUser`], for AES-GCM, so code written against the RustCrypto
+//! generics can move to the browser by adding `.await`.
+//!
+//! The errors being opaque, use [`crate::algorithm::Algorithm`] to know why an
+//! operation failed.
+//!
+//! Requires the `aead` feature.
+//!
+//! ```rust,no_run
+//! use aead::{AeadCore, Payload};
+//! use browser_crypto::aead::{Aes256GcmAdapter, AsyncAead};
+//!
+//! async fn encrypt(key: &[u8; 32]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+//!     let cipher = Aes256GcmAdapter::from_key(key.into()).await?;
+//!     let nonce = aead::Nonce::<Aes256GcmAdapter>::default(); // use a unique nonce
+//!     let payload = Payload {
+//!         msg: b"Hello World!",
+//!         aad: b"user-42",
+//!     };
+//!     // aead::Error doesn't implement std::error::Error without aead/std
+//!     let encrypted = cipher.encrypt(&nonce, payload).await;
+//!     Ok(encrypted.map_err(|_| "encryption failed")?)
+//! }
+//! ```
+//!
+//! [`aead::Error`]: ::aead::Error
+
+use ::aead::consts::{U0, U12, U16, U24, U32};
+use ::aead::{AeadCore, Buffer, KeySizeUser, Payload};
+
+use crate::aes_gcm::AesGcm;
+use crate::algorithm::Nonce;
+use crate::key::ImportKeyError;
+
+/// Async analogue of the `aead::Aead` and `aead::AeadInPlace` traits
+pub trait AsyncAead: AeadCore {
+    /// Encrypts a plaintext, or a [`Payload`] with additional data, returning
+    /// the ciphertext followed by the tag
+    ///
+    /// # Errors
+    /// `aead::Error` if the encryption fails
+    fn encrypt<'msg, 'aad>(
+        &self,
+        nonce: &::aead::Nonce<Self>,
+        plaintext: impl Into<Payload<'msg, 'aad>>,
+    ) -> impl std::future::Future<Output = ::aead::Result<Vec<u8>>>;
+
+    /// Decrypts a ciphertext followed by its tag, or a [`Payload`] with
+    /// additional data
+    ///
+    /// # Errors
+    /// `aead::Error` if the ciphertext or the additional data have been
+    /// altered, or if the key doesn't match
+    fn decrypt<'msg, 'aad>(
+        &self,
+        nonce: &::aead::Nonce<Self>,
+        ciphertext: impl Into<Payload<'msg, 'aad>>,
+    ) -> impl std::future::Future<Output = ::aead::Result<Vec<u8>>>;
+
+    /// Encrypts the content of a buffer, replaced by the ciphertext followed
+    /// by the tag
+    ///
+    /// # Errors
+    /// `aead::Error` if the encryption fails or the buffer can't grow, the
+    /// buffer being left unchanged
+    fn encrypt_in_place(
+        &self,
+        nonce: &::aead::Nonce<Self>,
+        associated_data: &[u8],
+        buffer: &mut dyn Buffer,
+    ) -> impl std::future::Future<Output = ::aead::Result<()>>;
+
+    /// Decrypts the content of a buffer, replaced by the plaintext
+    ///
+    /// # Errors
+    /// `aead::Error` if the decryption fails, the buffer being left unchanged
+    fn decrypt_in_place(
+        &self,
+        nonce: &::aead::Nonce<Self>,
+        associated_data: &[u8],
+        buffer: &mut dyn Buffer,
+    ) -> impl std::future::Future<Output = ::aead::Result<()>>;
+}
+
+/// AES-GCM cipher implementing the conventions of the `aead` crate, for a key
+/// of `KEY_BITS` bits
+///
+/// Use one of the [`Aes128GcmAdapter`], [`Aes192GcmAdapter`] or
+/// [`Aes256GcmAdapter`] aliases.
+#[derive(Debug, Clone)]
+pub struct AeadAdapter<const KEY_BITS: usize> {
+    cipher: AesGcm<KEY_BITS>,
+}
+
+/// AES-128-GCM cipher implementing the conventions of the `aead` crate
+pub type Aes128GcmAdapter = AeadAdapter<128>;
+
+/// AES-192-GCM cipher implementing the conventions of the `aead` crate
+pub type Aes192GcmAdapter = AeadAdapter<192>;
+
+/// AES-256-GCM cipher implementing the conventions of the `aead` crate
+pub type Aes256GcmAdapter = AeadAdapter<256>;
+
+impl<const KEY_BITS: usize> AeadAdapter<KEY_BITS> {
+    /// Wraps a cipher
+    pub fn new(cipher: AesGcm<KEY_BITS>) -> Self {
+        Self { cipher }
+    }
+
+    /// Wrapped cipher
+    pub fn cipher(&self) -> &AesGcm<KEY_BITS> {
+        &self.cipher
+    }
+
+    /// Consumes the adapter, returning the cipher
+    pub fn into_inner(self) -> AesGcm<KEY_BITS> {
+        self.cipher
+    }
+}
+
+impl<const KEY_BITS: usize> AeadAdapter<KEY_BITS>
+where
+    Self: KeySizeUser,
+{
+    /// Imports a raw key, the async analogue of `aead::KeyInit::new`
+    ///
+    /// # Errors
+    /// Same as [`AesGcm::from_key`]
+    pub async fn from_key(key: &::aead::Key<Self>) -> Result<Self, ImportKeyError> {
+        Ok(Self::new(AesGcm::from_key(key.as_slice()).await?))
+    }
+}
+
+impl<const KEY_BITS: usize> From<AesGcm<KEY_BITS>> for AeadAdapter<KEY_BITS> {
+    fn from(value: AesGcm<KEY_BITS>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl KeySizeUser for AeadAdapter<128> {
+    type KeySize = U16;
+}
+
+impl KeySizeUser for AeadAdapter<192> {
+    type KeySize = U24;
+}
+
+impl KeySizeUser for AeadAdapter<256> {
+    type KeySize = U32;
+}
+
+impl<const KEY_BITS: usize> AeadCore for AeadAdapter<KEY_BITS> {
+    type NonceSize = U12;
+    type TagSize = U16;
+    type CiphertextOverhead = U0;
+}
+
+impl<const KEY_BITS: usize> AsyncAead for AeadAdapter<KEY_BITS> {
+    async fn encrypt<'msg, 'aad>(
+        &self,
+        nonce: &::aead::Nonce<Self>,
+        plaintext: impl Into<Payload<'msg, 'aad>>,
+    ) -> ::aead::Result<Vec<u8>> {
+        let payload = plaintext.into();
+        let nonce = Nonce::from(<[u8; 12]>::from(*nonce));
+        self.cipher
+            .encrypt_with_aad(&nonce, payload.aad, payload.msg)
+            .await
+            .map_err(|_| ::aead::Error)
+    }
+
+    async fn decrypt<'msg, 'aad>(
+        &self,
+        nonce: &::aead::Nonce<Self>,
+        ciphertext: impl Into<Payload<'msg, 'aad>>,
+    ) -> ::aead::Result<Vec<u8>> {
+        let payload = ciphertext.into();
+        let nonce = Nonce::from(<[u8; 12]>::from(*nonce));
+        self.cipher
+            .decrypt_with_aad(&nonce, payload.aad, payload.msg)
+            .await
+            .map_err(|_| ::aead::Error)
+    }
+
+    async fn encrypt_in_place(
+        &self,
+        nonce: &::aead::Nonce<Self>,
+        associated_data: &[u8],
+        buffer: &mut dyn Buffer,
+    ) -> ::aead::Result<()> {
+        let payload = Payload {
+            msg: buffer.as_ref(),
+            aad: associated_data,
+        };
+        let ciphertext = AsyncAead::encrypt(self, nonce, payload).await?;
+        let length = buffer.len();
+        buffer.extend_from_slice(&ciphertext[length..])?;
+        buffer.as_mut()[..length].copy_from_slice(&ciphertext[..length]);
+        Ok(())
+    }
+
+    async fn decrypt_in_place(
+        &self,
+        nonce: &::aead::Nonce<Self>,
+        associated_data: &[u8],
+        buffer: &mut dyn Buffer,
+    ) -> ::aead::Result<()> {
+        let payload = Payload {
+            msg: buffer.as_ref(),
+            aad: associated_data,
+        };
+        let plaintext = AsyncAead::decrypt(self, nonce, payload).await?;
+        buffer.as_mut()[..plaintext.len()].copy_from_slice(&plaintext);
+        buffer.truncate(plaintext.len());
+        Ok(())
+    }
+}
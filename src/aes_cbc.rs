@@ -0,0 +1,176 @@
+//! AES-CBC encryption implementation
+//!
+//! AES-CBC is not an authenticated encryption mode, it's only provided for
+//! interoperability with systems still producing CBC ciphertexts. Prefer
+//! [`crate::aes_gcm`] for any new data.
+
+use crate::algorithm::{Algorithm, DecryptionError, EncryptionError, Nonce};
+use crate::context::Operation;
+use crate::input::SecretInput;
+use crate::jwk::Jwk;
+use crate::key::{ExportKeyError, ImportKeyError, WrappableKey};
+
+const NAME: &str = "AES-CBC";
+
+/// AES-CBC encryption implementation, for a key of `KEY_BITS` bits
+///
+/// Use one of the [`Aes128Cbc`], [`Aes192Cbc`] or [`Aes256Cbc`] aliases.
+///
+/// The plaintext is padded using PKCS#7 by the browser. When decrypting, an
+/// invalid padding results in a `DecryptionError::Operation`.
+#[derive(Debug, Clone)]
+pub struct AesCbc<const KEY_BITS: usize> {
+    key: web_sys::CryptoKey,
+}
+
+/// AES-128-CBC encryption implementation
+pub type Aes128Cbc = AesCbc<128>;
+
+/// AES-192-CBC encryption implementation
+///
+/// Note: Chromium based browsers don't support 192-bit AES keys, importing one
+/// will fail with `ImportKeyError::Generic`.
+pub type Aes192Cbc = AesCbc<192>;
+
+/// AES-256-CBC encryption implementation
+pub type Aes256Cbc = AesCbc<256>;
+
+/// Initialization vector for AES-CBC, 16 bytes long
+///
+/// The IV must be unpredictable, it should be generated with
+/// [`Algorithm::generate_nonce`] for every encryption.
+pub type Iv<const KEY_BITS: usize> = Nonce<AesCbc<KEY_BITS>>;
+
+impl<const KEY_BITS: usize> AesCbc<KEY_BITS> {
+    /// Creates a new AES-CBC instance from a raw key.
+    ///
+    /// # Arguments
+    /// * `data` - Raw key bytes (should be `KEY_BITS / 8` bytes, 32 bytes for
+    ///   AES-256)
+    ///
+    /// # Returns
+    /// Result containing the AesCbc instance or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::Syntax` if key usage array is empty
+    /// - `ImportKeyError::Type` if key format/data is invalid
+    /// - `ImportKeyError::InvalidKeyFormat` if provided key format is invalid
+    ///   or if the key size doesn't match `KEY_BITS`
+    pub async fn from_key(data: &(impl SecretInput + ?Sized)) -> Result<Self, ImportKeyError> {
+        let key = crate::key::import_raw_aes::<KEY_BITS>(
+            NAME,
+            data.expose_bytes(),
+            &["encrypt", "decrypt"],
+        )
+        .await?;
+        Ok(Self { key })
+    }
+
+    /// Creates a new AES-CBC instance from a JSON Web Key.
+    ///
+    /// # Arguments
+    /// * `jwk` - JSON Web Key of type "oct"
+    ///
+    /// # Returns
+    /// Result containing the AesCbc instance or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::InvalidKeyFormat` if the key is invalid, if its
+    ///   algorithm doesn't match or if the key size doesn't match `KEY_BITS`
+    pub async fn from_jwk(jwk: &Jwk) -> Result<Self, ImportKeyError> {
+        let key =
+            crate::key::import_jwk_aes::<KEY_BITS>(NAME, jwk, &["encrypt", "decrypt"]).await?;
+        Ok(Self { key })
+    }
+
+    /// Exports the key in the JSON Web Key format.
+    ///
+    /// # Returns
+    /// Result containing the JSON Web Key, of type "oct", or an ExportKeyError
+    ///
+    /// # Errors
+    /// - `ExportKeyError::InvalidAccess` if the key is not extractable, like
+    ///   derived keys
+    pub async fn to_jwk(&self) -> Result<Jwk, ExportKeyError> {
+        crate::key::export_jwk(&self.key).await
+    }
+}
+
+impl<const KEY_BITS: usize> Algorithm for AesCbc<KEY_BITS> {
+    const NAME: &'static str = NAME;
+    const NONCE_SIZE: u32 = 16;
+    const KEY_SIZE: u32 = KEY_BITS as u32 / 8;
+    type EncryptError = EncryptionError;
+    type DecryptError = DecryptionError;
+
+    async fn encrypt(&self, iv: &Nonce<Self>, payload: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let ciphertext =
+            crate::instrument::observe(Operation::Encrypt, NAME, Some(payload.len()), async {
+                let subtle = crate::subtle()?;
+                let params = web_sys::AesCbcParams::new(NAME, &iv.to_array());
+                let promise: js_sys::Promise = crate::with_bytes(payload, |plaintext| {
+                    subtle.encrypt_with_object_and_js_u8_array(&params, &self.key, plaintext)
+                })?;
+                crate::resolve::<js_sys::ArrayBuffer, EncryptionError>(promise).await
+            })
+            .await?;
+
+        Ok(crate::array_to_vec(&js_sys::Uint8Array::new(&ciphertext)))
+    }
+
+    async fn decrypt(&self, iv: &Nonce<Self>, payload: &[u8]) -> Result<Vec<u8>, DecryptionError> {
+        let clear = self.decrypt_buffer(iv, payload).await?;
+        Ok(crate::array_to_vec(&js_sys::Uint8Array::new(&clear)))
+    }
+
+    /// Decrypts data in its buffer, copying the decrypted data from the
+    /// browser directly over the encrypted data, the padding being truncated
+    async fn decrypt_in_place(
+        &self,
+        iv: &Nonce<Self>,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), DecryptionError> {
+        let clear = self.decrypt_buffer(iv, buffer).await?;
+        buffer.clear();
+        crate::extend_from_array(buffer, &js_sys::Uint8Array::new(&clear));
+        Ok(())
+    }
+}
+
+impl<const KEY_BITS: usize> AesCbc<KEY_BITS> {
+    async fn decrypt_buffer(
+        &self,
+        iv: &Nonce<Self>,
+        payload: &[u8],
+    ) -> Result<js_sys::ArrayBuffer, DecryptionError> {
+        crate::instrument::observe(Operation::Decrypt, NAME, Some(payload.len()), async {
+            let subtle = crate::subtle()?;
+            let params = web_sys::AesCbcParams::new(NAME, &iv.to_array());
+            let promise: js_sys::Promise = crate::with_bytes(payload, |payload| {
+                subtle.decrypt_with_object_and_js_u8_array(&params, &self.key, payload)
+            })?;
+            crate::resolve::<js_sys::ArrayBuffer, DecryptionError>(promise).await
+        })
+        .await
+    }
+}
+
+impl<const KEY_BITS: usize> crate::key::private::Sealed for AesCbc<KEY_BITS> {
+    fn import_algorithm() -> Result<js_sys::Object, wasm_bindgen::JsValue> {
+        crate::key::aes_algorithm(NAME, KEY_BITS)
+    }
+
+    fn usages() -> &'static [&'static str] {
+        &["encrypt", "decrypt"]
+    }
+
+    fn crypto_key(&self) -> Result<&web_sys::CryptoKey, crate::Error> {
+        Ok(&self.key)
+    }
+
+    fn from_crypto_key(key: web_sys::CryptoKey) -> Option<Self> {
+        crate::key::is_aes_key(&key, NAME, KEY_BITS).then_some(Self { key })
+    }
+}
+
+impl<const KEY_BITS: usize> WrappableKey for AesCbc<KEY_BITS> {}
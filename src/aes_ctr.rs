@@ -0,0 +1,215 @@
+//! AES-CTR encryption implementation
+//!
+//! AES-CTR is not an authenticated encryption mode, it's only provided for
+//! interoperability with consumers expecting a stream cipher. Prefer
+//! [`crate::aes_gcm`] for any new data.
+
+use crate::algorithm::{Algorithm, DecryptionError, EncryptionError, Nonce};
+use crate::context::Operation;
+use crate::input::SecretInput;
+use crate::jwk::Jwk;
+use crate::key::{ExportKeyError, ImportKeyError, WrappableKey};
+
+const NAME: &str = "AES-CTR";
+
+/// Default number of bits of the counter block used for the counter, the
+/// remaining bits being used for the nonce.
+pub const DEFAULT_COUNTER_LENGTH: u8 = 64;
+
+/// AES-CTR encryption implementation, for a key of `KEY_BITS` bits
+///
+/// Use one of the [`Aes128Ctr`], [`Aes192Ctr`] or [`Aes256Ctr`] aliases.
+///
+/// The ciphertext has the same size as the plaintext.
+#[derive(Debug, Clone)]
+pub struct AesCtr<const KEY_BITS: usize> {
+    key: web_sys::CryptoKey,
+    counter_length: u8,
+}
+
+/// AES-128-CTR encryption implementation
+pub type Aes128Ctr = AesCtr<128>;
+
+/// AES-192-CTR encryption implementation
+///
+/// Note: Chromium based browsers don't support 192-bit AES keys, importing one
+/// will fail with `ImportKeyError::Generic`.
+pub type Aes192Ctr = AesCtr<192>;
+
+/// AES-256-CTR encryption implementation
+pub type Aes256Ctr = AesCtr<256>;
+
+/// Initial counter block for AES-CTR, 16 bytes long
+///
+/// The rightmost [`AesCtr::counter_length`] bits are incremented for each
+/// block, the remaining ones are a nonce that should never be reused with the
+/// same key.
+pub type Counter<const KEY_BITS: usize> = Nonce<AesCtr<KEY_BITS>>;
+
+impl<const KEY_BITS: usize> AesCtr<KEY_BITS> {
+    /// Creates a new AES-CTR instance from a raw key.
+    ///
+    /// The counter length is set to [`DEFAULT_COUNTER_LENGTH`].
+    ///
+    /// # Arguments
+    /// * `data` - Raw key bytes (should be `KEY_BITS / 8` bytes, 32 bytes for
+    ///   AES-256)
+    ///
+    /// # Returns
+    /// Result containing the AesCtr instance or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::Syntax` if key usage array is empty
+    /// - `ImportKeyError::Type` if key format/data is invalid
+    /// - `ImportKeyError::InvalidKeyFormat` if provided key format is invalid
+    ///   or if the key size doesn't match `KEY_BITS`
+    pub async fn from_key(data: &(impl SecretInput + ?Sized)) -> Result<Self, ImportKeyError> {
+        let key = crate::key::import_raw_aes::<KEY_BITS>(
+            NAME,
+            data.expose_bytes(),
+            &["encrypt", "decrypt"],
+        )
+        .await?;
+        Ok(Self {
+            key,
+            counter_length: DEFAULT_COUNTER_LENGTH,
+        })
+    }
+
+    /// Creates a new AES-CTR instance from a JSON Web Key.
+    ///
+    /// # Arguments
+    /// * `jwk` - JSON Web Key of type "oct"
+    ///
+    /// # Returns
+    /// Result containing the AesCtr instance or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::InvalidKeyFormat` if the key is invalid, if its
+    ///   algorithm doesn't match or if the key size doesn't match `KEY_BITS`
+    pub async fn from_jwk(jwk: &Jwk) -> Result<Self, ImportKeyError> {
+        let key =
+            crate::key::import_jwk_aes::<KEY_BITS>(NAME, jwk, &["encrypt", "decrypt"]).await?;
+        Ok(Self {
+            key,
+            counter_length: DEFAULT_COUNTER_LENGTH,
+        })
+    }
+
+    /// Exports the key in the JSON Web Key format.
+    ///
+    /// # Returns
+    /// Result containing the JSON Web Key, of type "oct", or an ExportKeyError
+    ///
+    /// # Errors
+    /// - `ExportKeyError::InvalidAccess` if the key is not extractable, like
+    ///   derived keys
+    pub async fn to_jwk(&self) -> Result<Jwk, ExportKeyError> {
+        crate::key::export_jwk(&self.key).await
+    }
+
+    /// Sets the number of bits of the counter block used for the counter
+    ///
+    /// The value must be between 1 and 128, otherwise the encryption and
+    /// decryption will fail with an `Operation` error.
+    pub fn with_counter_length(mut self, counter_length: u8) -> Self {
+        self.counter_length = counter_length;
+        self
+    }
+
+    /// Number of bits of the counter block used for the counter
+    pub fn counter_length(&self) -> u8 {
+        self.counter_length
+    }
+}
+
+impl<const KEY_BITS: usize> Algorithm for AesCtr<KEY_BITS> {
+    const NAME: &'static str = NAME;
+    const NONCE_SIZE: u32 = 16;
+    const KEY_SIZE: u32 = KEY_BITS as u32 / 8;
+    type EncryptError = EncryptionError;
+    type DecryptError = DecryptionError;
+
+    async fn encrypt(
+        &self,
+        counter: &Nonce<Self>,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, EncryptionError> {
+        let ciphertext =
+            crate::instrument::observe(Operation::Encrypt, NAME, Some(payload.len()), async {
+                let subtle = crate::subtle()?;
+                let params =
+                    web_sys::AesCtrParams::new(NAME, &counter.to_array(), self.counter_length);
+                let promise: js_sys::Promise = crate::with_bytes(payload, |plaintext| {
+                    subtle.encrypt_with_object_and_js_u8_array(&params, &self.key, plaintext)
+                })?;
+                crate::resolve::<js_sys::ArrayBuffer, EncryptionError>(promise).await
+            })
+            .await?;
+
+        Ok(crate::array_to_vec(&js_sys::Uint8Array::new(&ciphertext)))
+    }
+
+    async fn decrypt(
+        &self,
+        counter: &Nonce<Self>,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, DecryptionError> {
+        let clear = self.decrypt_buffer(counter, payload).await?;
+        Ok(crate::array_to_vec(&js_sys::Uint8Array::new(&clear)))
+    }
+
+    /// Decrypts data in its buffer, copying the decrypted data from the
+    /// browser directly over the encrypted data, which has the same size
+    async fn decrypt_in_place(
+        &self,
+        counter: &Nonce<Self>,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), DecryptionError> {
+        let clear = self.decrypt_buffer(counter, buffer).await?;
+        buffer.clear();
+        crate::extend_from_array(buffer, &js_sys::Uint8Array::new(&clear));
+        Ok(())
+    }
+}
+
+impl<const KEY_BITS: usize> AesCtr<KEY_BITS> {
+    async fn decrypt_buffer(
+        &self,
+        counter: &Nonce<Self>,
+        payload: &[u8],
+    ) -> Result<js_sys::ArrayBuffer, DecryptionError> {
+        crate::instrument::observe(Operation::Decrypt, NAME, Some(payload.len()), async {
+            let subtle = crate::subtle()?;
+            let params = web_sys::AesCtrParams::new(NAME, &counter.to_array(), self.counter_length);
+            let promise: js_sys::Promise = crate::with_bytes(payload, |payload| {
+                subtle.decrypt_with_object_and_js_u8_array(&params, &self.key, payload)
+            })?;
+            crate::resolve::<js_sys::ArrayBuffer, DecryptionError>(promise).await
+        })
+        .await
+    }
+}
+
+impl<const KEY_BITS: usize> crate::key::private::Sealed for AesCtr<KEY_BITS> {
+    fn import_algorithm() -> Result<js_sys::Object, wasm_bindgen::JsValue> {
+        crate::key::aes_algorithm(NAME, KEY_BITS)
+    }
+
+    fn usages() -> &'static [&'static str] {
+        &["encrypt", "decrypt"]
+    }
+
+    fn crypto_key(&self) -> Result<&web_sys::CryptoKey, crate::Error> {
+        Ok(&self.key)
+    }
+
+    fn from_crypto_key(key: web_sys::CryptoKey) -> Option<Self> {
+        crate::key::is_aes_key(&key, NAME, KEY_BITS).then_some(Self {
+            key,
+            counter_length: DEFAULT_COUNTER_LENGTH,
+        })
+    }
+}
+
+impl<const KEY_BITS: usize> WrappableKey for AesCtr<KEY_BITS> {}
@@ -0,0 +1,759 @@
+//! AES-GCM encryption implementation
+//!
+//! The key size is part of the type, so a cipher created for 128-bit keys can't
+//! be used with a 256-bit key by mistake.
+//!
+//! The same key can also authenticate data without encrypting it, with GMAC,
+//! see [`AesGcm::authenticate`].
+//!
+//! With the `native` feature enabled, outside of WebAssembly, the key is held
+//! in memory and the data encrypted by the RustCrypto implementation, so the
+//! code using it can be unit tested or run on a server. Only the raw keys, the
+//! random keys and the 96-bit nonces are supported, the methods relying on a
+//! `CryptoKey` fail with `Error::Unsupported`.
+
+use crate::algorithm::{
+    Algorithm, DecryptionError, EncryptionError, Key, Nonce, Payload, VariableNonceSize,
+};
+use crate::context::Operation;
+use crate::derive::{DeriveError, KeyDerivation};
+use crate::input::{CryptoInput, SecretInput};
+use crate::jwk::Jwk;
+use crate::key::{CryptoKeyError, ExportKeyError, GenerateKeyError, ImportKeyError, WrappableKey};
+use crate::password::PasswordError;
+
+const NAME: &str = "AES-GCM";
+
+/// AES-GCM encryption implementation, for a key of `KEY_BITS` bits
+///
+/// Use one of the [`Aes128Gcm`], [`Aes192Gcm`] or [`Aes256Gcm`] aliases.
+#[derive(Debug, Clone)]
+pub struct AesGcm<const KEY_BITS: usize> {
+    #[cfg(not(native_backend))]
+    key: web_sys::CryptoKey,
+    #[cfg(native_backend)]
+    key: crate::native::AesGcmKey,
+}
+
+/// AES-128-GCM encryption implementation
+pub type Aes128Gcm = AesGcm<128>;
+
+/// AES-192-GCM encryption implementation
+///
+/// Note: Chromium based browsers don't support 192-bit AES keys, importing one
+/// will fail with `ImportKeyError::Generic`.
+pub type Aes192Gcm = AesGcm<192>;
+
+/// AES-256-GCM encryption implementation
+pub type Aes256Gcm = AesGcm<256>;
+
+impl<const KEY_BITS: usize> AesGcm<KEY_BITS> {
+    /// Creates a new AES-GCM instance from a raw key.
+    ///
+    /// # Arguments
+    /// * `data` - Raw key bytes (should be `KEY_BITS / 8` bytes, 32 bytes for
+    ///   AES-256)
+    ///
+    /// # Returns
+    /// Result containing the AesGcm instance or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::Syntax` if key usage array is empty
+    /// - `ImportKeyError::Type` if key format/data is invalid
+    /// - `ImportKeyError::InvalidKeyFormat` if provided key format is invalid
+    ///   or if the key size doesn't match `KEY_BITS`
+    pub async fn from_key(data: &(impl SecretInput + ?Sized)) -> Result<Self, ImportKeyError> {
+        #[cfg(native_backend)]
+        let key = crate::native::AesGcmKey::new(data.expose_bytes(), KEY_BITS)?;
+        #[cfg(not(native_backend))]
+        let key = crate::key::import_raw_aes::<KEY_BITS>(
+            NAME,
+            data.expose_bytes(),
+            &["encrypt", "decrypt"],
+        )
+        .await?;
+        Ok(Self { key })
+    }
+
+    /// Creates a new AES-GCM instance from a raw key encoded with the standard
+    /// base64 alphabet, padding being optional, as usually found in
+    /// configuration files.
+    ///
+    /// # Arguments
+    /// * `input` - Base64 encoded key of `KEY_BITS / 8` bytes
+    ///
+    /// # Returns
+    /// Result containing the AesGcm instance or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::RawKey` if the text is not base64 encoded or if the
+    ///   key size doesn't match `KEY_BITS`
+    pub async fn from_base64_key(input: &str) -> Result<Self, ImportKeyError> {
+        Self::from_key(&Key::<Self>::from_base64(input)?).await
+    }
+
+    /// Creates a new AES-GCM instance from a raw key encoded as hexadecimal,
+    /// in lowercase or uppercase.
+    ///
+    /// # Arguments
+    /// * `input` - Hexadecimal encoded key of `KEY_BITS / 8` bytes
+    ///
+    /// # Returns
+    /// Result containing the AesGcm instance or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::RawKey` if the text is not hexadecimal or if the key
+    ///   size doesn't match `KEY_BITS`
+    pub async fn from_hex_key(input: &str) -> Result<Self, ImportKeyError> {
+        Self::from_key(&Key::<Self>::from_hex(input)?).await
+    }
+
+    /// Generates a new random key, directly in the browser.
+    ///
+    /// With the `native` feature enabled, outside of WebAssembly, the key is
+    /// generated with the random number generator of the operating system.
+    ///
+    /// # Arguments
+    /// * `extractable` - Whether the key can be wrapped, with [`crate::aes_kw`]
+    ///   for example. A non extractable key never reaches the WebAssembly
+    ///   memory. It has no effect with the `native` feature, outside of
+    ///   WebAssembly, where the key can't be exported or wrapped anyway.
+    ///
+    /// # Returns
+    /// Result containing the AesGcm instance or a GenerateKeyError
+    ///
+    /// # Errors
+    /// - `GenerateKeyError::NotSupported` if the key size is not supported by
+    ///   the browser
+    pub async fn generate(extractable: bool) -> Result<Self, GenerateKeyError> {
+        #[cfg(native_backend)]
+        let key = {
+            // the key held in memory is never exported
+            let _ = extractable;
+            crate::native::AesGcmKey::generate(KEY_BITS)?
+        };
+        #[cfg(not(native_backend))]
+        let key = {
+            crate::check_target()?;
+            let algorithm = crate::key::aes_algorithm(NAME, KEY_BITS)?;
+            crate::key::generate_key(&algorithm, extractable, &["encrypt", "decrypt"]).await?
+        };
+        Ok(Self { key })
+    }
+
+    /// Derives a new AES-GCM key, like with [`crate::hkdf::Hkdf`] or
+    /// [`crate::pbkdf2::Pbkdf2`].
+    ///
+    /// The derivation happens in the browser and the derived key is not
+    /// extractable, so it never reaches the WebAssembly memory.
+    ///
+    /// # Arguments
+    /// * `derivation` - Key derivation, holding the base key and its parameters
+    ///
+    /// # Returns
+    /// Result containing the AesGcm instance or a DeriveError
+    ///
+    /// # Errors
+    /// - `DeriveError::InvalidAccess` if the base key can't derive keys
+    /// - `DeriveError::Operation` if the derivation parameters are invalid
+    /// - `DeriveError::NotSupported` with the `native` feature, outside of
+    ///   WebAssembly
+    pub async fn derive_from<D: KeyDerivation>(derivation: &D) -> Result<Self, DeriveError> {
+        #[cfg(native_backend)]
+        let result = {
+            let _ = derivation;
+            Err(DeriveError::NotSupported)
+        };
+        #[cfg(not(native_backend))]
+        let result = {
+            let params = derivation.derive_params()?;
+            crate::derive::derive_key(&params, derivation.base_key(), false).await
+        };
+        result
+    }
+}
+
+impl<const KEY_BITS: usize> AesGcm<KEY_BITS> {
+    /// Underlying CryptoKey
+    #[cfg(not(native_backend))]
+    fn web_key(&self) -> Result<&web_sys::CryptoKey, crate::Error> {
+        Ok(&self.key)
+    }
+
+    /// The native backend holds the key in memory, without a CryptoKey
+    #[cfg(native_backend)]
+    fn web_key(&self) -> Result<&web_sys::CryptoKey, crate::Error> {
+        Err(crate::Error::Unsupported)
+    }
+
+    /// Wraps a CryptoKey, already checked
+    #[cfg(not(native_backend))]
+    fn from_web_key(key: web_sys::CryptoKey) -> Result<Self, crate::Error> {
+        Ok(Self { key })
+    }
+
+    /// The native backend can't use a CryptoKey
+    #[cfg(native_backend)]
+    fn from_web_key(_key: web_sys::CryptoKey) -> Result<Self, crate::Error> {
+        Err(crate::Error::Unsupported)
+    }
+}
+
+impl<const KEY_BITS: usize> AesGcm<KEY_BITS> {
+    /// Creates a new AES-GCM instance from a JSON Web Key.
+    ///
+    /// # Arguments
+    /// * `jwk` - JSON Web Key of type "oct"
+    ///
+    /// # Returns
+    /// Result containing the AesGcm instance or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::InvalidKeyFormat` if the key is invalid, if its
+    ///   algorithm doesn't match or if the key size doesn't match `KEY_BITS`
+    /// - `ImportKeyError::Generic` with `Error::Unsupported` with the `native`
+    ///   feature, outside of WebAssembly
+    pub async fn from_jwk(jwk: &Jwk) -> Result<Self, ImportKeyError> {
+        let key =
+            crate::key::import_jwk_aes::<KEY_BITS>(NAME, jwk, &["encrypt", "decrypt"]).await?;
+        Ok(Self::from_web_key(key)?)
+    }
+
+    /// Creates a new AES-GCM instance from an existing CryptoKey, obtained
+    /// from other JavaScript code, like an IndexedDB store or a message from
+    /// another worker.
+    ///
+    /// # Arguments
+    /// * `key` - AES-GCM secret key of `KEY_BITS` bits
+    ///
+    /// # Returns
+    /// Result containing the AesGcm instance or a CryptoKeyError
+    ///
+    /// # Errors
+    /// - `CryptoKeyError::InvalidType` if the key is not a secret key
+    /// - `CryptoKeyError::InvalidAlgorithm` if the key is not an AES-GCM key
+    /// - `CryptoKeyError::InvalidLength` if the key size doesn't match
+    ///   `KEY_BITS`
+    /// - `CryptoKeyError::MissingUsage` if the key doesn't allow both the
+    ///   "encrypt" and "decrypt" usages
+    /// - `CryptoKeyError::NotSupported` with the `native` feature, outside of
+    ///   WebAssembly
+    pub fn from_crypto_key(key: web_sys::CryptoKey) -> Result<Self, CryptoKeyError> {
+        crate::key::check_aes_key(&key, NAME, KEY_BITS, &["encrypt", "decrypt"])?;
+        Self::from_web_key(key).map_err(|_| CryptoKeyError::NotSupported)
+    }
+
+    /// Underlying CryptoKey, to hand it to other JavaScript code using the Web
+    /// Crypto API, store it in IndexedDB or post it to a worker, without
+    /// exporting it.
+    ///
+    /// The key can be turned back into a cipher with
+    /// [`AesGcm::from_crypto_key`].
+    ///
+    /// # Returns
+    /// Result containing the CryptoKey or an Error
+    ///
+    /// # Errors
+    /// - `Error::Unsupported` with the `native` feature, outside of
+    ///   WebAssembly, where the key is held in memory instead of a CryptoKey
+    pub fn as_crypto_key(&self) -> Result<&web_sys::CryptoKey, crate::Error> {
+        self.web_key()
+    }
+
+    /// Exports the key in the JSON Web Key format.
+    ///
+    /// # Returns
+    /// Result containing the JSON Web Key, of type "oct", or an ExportKeyError
+    ///
+    /// # Errors
+    /// - `ExportKeyError::InvalidAccess` if the key is not extractable, like
+    ///   derived keys
+    /// - `ExportKeyError::Generic` with `Error::Unsupported` with the `native`
+    ///   feature, outside of WebAssembly
+    pub async fn to_jwk(&self) -> Result<Jwk, ExportKeyError> {
+        crate::key::export_jwk(self.web_key()?).await
+    }
+
+    /// Exports the key protected by a passphrase, to back it up.
+    ///
+    /// The key is wrapped with an AES-KW key derived from the passphrase with
+    /// PBKDF2, using [`crate::pbkdf2::Pbkdf2::DEFAULT_ITERATIONS`] iterations
+    /// and a random salt. The output embeds a version and the derivation
+    /// parameters, see [`crate::password`].
+    ///
+    /// # Arguments
+    /// * `passphrase` - Passphrase protecting the key
+    ///
+    /// # Returns
+    /// Result containing the protected key or a PasswordError
+    ///
+    /// # Errors
+    /// - `PasswordError::Wrap` if the key is not extractable
+    pub async fn export_protected(
+        &self,
+        passphrase: &(impl SecretInput + ?Sized),
+    ) -> Result<Vec<u8>, PasswordError> {
+        crate::password::wrap_key(
+            passphrase.expose_bytes(),
+            self,
+            crate::pbkdf2::Pbkdf2::DEFAULT_ITERATIONS,
+        )
+        .await
+    }
+
+    /// Imports a key exported with [`AesGcm::export_protected`].
+    ///
+    /// # Arguments
+    /// * `passphrase` - Passphrase protecting the key
+    /// * `data` - Protected key
+    /// * `extractable` - Whether the restored key can be exported again
+    ///
+    /// # Returns
+    /// Result containing the AesGcm instance or a PasswordError
+    ///
+    /// # Errors
+    /// - `PasswordError::InvalidFormat` if the data is truncated
+    /// - `PasswordError::UnsupportedVersion` if the data has an unknown version
+    /// - `PasswordError::Unwrap` if the passphrase is wrong, the data has been
+    ///   altered or the key size doesn't match `KEY_BITS`
+    pub async fn import_protected(
+        passphrase: &(impl SecretInput + ?Sized),
+        data: &[u8],
+        extractable: bool,
+    ) -> Result<Self, PasswordError> {
+        crate::password::unwrap_key(passphrase.expose_bytes(), data, extractable).await
+    }
+}
+
+impl<const KEY_BITS: usize> AesGcm<KEY_BITS> {
+    fn params(nonce: &Nonce<Self>, aad: &[u8]) -> web_sys::AesGcmParams {
+        let params = web_sys::AesGcmParams::new(NAME, &nonce.to_array());
+        if !aad.is_empty() {
+            params.set_additional_data(&js_sys::Uint8Array::from(aad));
+        }
+        params
+    }
+
+    async fn encrypt_buffer(
+        &self,
+        nonce: &Nonce<Self>,
+        aad: &[u8],
+        payload: CryptoInput<'_>,
+    ) -> Result<js_sys::ArrayBuffer, EncryptionError> {
+        let size = Some(payload.len());
+        crate::instrument::observe(Operation::Encrypt, NAME, size, async {
+            let subtle = crate::subtle()?;
+            let key = self.web_key()?;
+            let params = Self::params(nonce, aad);
+            let promise: js_sys::Promise = payload.with_array(|plaintext| {
+                subtle.encrypt_with_object_and_js_u8_array(&params, key, plaintext)
+            })?;
+            crate::resolve::<js_sys::ArrayBuffer, EncryptionError>(promise).await
+        })
+        .await
+    }
+
+    async fn decrypt_buffer(
+        &self,
+        nonce: &Nonce<Self>,
+        aad: &[u8],
+        payload: CryptoInput<'_>,
+    ) -> Result<js_sys::ArrayBuffer, DecryptionError> {
+        let size = Some(payload.len());
+        crate::instrument::observe(Operation::Decrypt, NAME, size, async {
+            let subtle = crate::subtle()?;
+            let key = self.web_key()?;
+            let params = Self::params(nonce, aad);
+            let promise: js_sys::Promise = payload.with_array(|payload| {
+                subtle.decrypt_with_object_and_js_u8_array(&params, key, payload)
+            })?;
+            crate::resolve::<js_sys::ArrayBuffer, DecryptionError>(promise).await
+        })
+        .await
+    }
+}
+
+#[cfg(not(native_backend))]
+impl<const KEY_BITS: usize> AesGcm<KEY_BITS> {
+    /// Encrypts data, authenticating some additional data that is not
+    /// encrypted
+    pub(crate) async fn encrypt_with_aad(
+        &self,
+        nonce: &Nonce<Self>,
+        aad: &[u8],
+        payload: &[u8],
+    ) -> Result<Vec<u8>, EncryptionError> {
+        let ciphertext = self.encrypt_buffer(nonce, aad, payload.into()).await?;
+        Ok(crate::array_to_vec(&js_sys::Uint8Array::new(&ciphertext)))
+    }
+
+    /// Decrypts data, checking the additional data used for encryption
+    pub(crate) async fn decrypt_with_aad(
+        &self,
+        nonce: &Nonce<Self>,
+        aad: &[u8],
+        payload: &[u8],
+    ) -> Result<Vec<u8>, DecryptionError> {
+        let clear = self.decrypt_buffer(nonce, aad, payload.into()).await?;
+        Ok(crate::array_to_vec(&js_sys::Uint8Array::new(&clear)))
+    }
+}
+
+#[cfg(native_backend)]
+impl<const KEY_BITS: usize> AesGcm<KEY_BITS> {
+    /// Encrypts data, authenticating some additional data that is not
+    /// encrypted
+    pub(crate) async fn encrypt_with_aad(
+        &self,
+        nonce: &Nonce<Self>,
+        aad: &[u8],
+        payload: &[u8],
+    ) -> Result<Vec<u8>, EncryptionError> {
+        let size = Some(payload.len());
+        crate::instrument::observe(Operation::Encrypt, NAME, size, async {
+            self.key.encrypt(nonce.as_ref(), aad, payload)
+        })
+        .await
+    }
+
+    /// Decrypts data, checking the additional data used for encryption
+    pub(crate) async fn decrypt_with_aad(
+        &self,
+        nonce: &Nonce<Self>,
+        aad: &[u8],
+        payload: &[u8],
+    ) -> Result<Vec<u8>, DecryptionError> {
+        let size = Some(payload.len());
+        crate::instrument::observe(Operation::Decrypt, NAME, size, async {
+            self.key.decrypt(nonce.as_ref(), aad, payload)
+        })
+        .await
+    }
+}
+
+impl<const KEY_BITS: usize> AesGcm<KEY_BITS> {
+    /// Encrypts data, authenticating the additional data of a [`Payload`]
+    /// without encrypting it.
+    ///
+    /// Byte slices convert into a payload without additional data, the same
+    /// as [`Algorithm::encrypt`].
+    ///
+    /// # Arguments
+    /// * `nonce` - Nonce to use for encryption
+    /// * `payload` - Data to encrypt, or a [`Payload`] with additional data
+    ///
+    /// # Returns
+    /// Result containing the encrypted bytes or an EncryptionError
+    pub async fn encrypt_payload<'a>(
+        &self,
+        nonce: &Nonce<Self>,
+        payload: impl Into<Payload<'a>>,
+    ) -> Result<Vec<u8>, EncryptionError> {
+        let payload = payload.into();
+        self.encrypt_with_aad(nonce, payload.aad, payload.msg).await
+    }
+
+    /// Decrypts data, checking the additional data of a [`Payload`] matches
+    /// the one used for encryption.
+    ///
+    /// # Arguments
+    /// * `nonce` - Nonce used for encryption
+    /// * `payload` - Encrypted data, or a [`Payload`] with additional data
+    ///
+    /// # Returns
+    /// Result containing the decrypted bytes or a DecryptionError
+    ///
+    /// # Errors
+    /// - `DecryptionError::Operation` if the data or the additional data have
+    ///   been altered, or if the key doesn't match
+    pub async fn decrypt_payload<'a>(
+        &self,
+        nonce: &Nonce<Self>,
+        payload: impl Into<Payload<'a>>,
+    ) -> Result<Vec<u8>, DecryptionError> {
+        let payload = payload.into();
+        self.decrypt_with_aad(nonce, payload.aad, payload.msg).await
+    }
+
+    /// Computes a GMAC authentication tag over some data, without encrypting
+    /// anything.
+    ///
+    /// The data is authenticated as the additional data of an empty payload,
+    /// so metadata can be integrity protected with the encryption key. The
+    /// nonce must never be reused with the same key, including for
+    /// encryption.
+    ///
+    /// # Arguments
+    /// * `nonce` - Nonce to use for the authentication
+    /// * `data` - Data to authenticate
+    ///
+    /// # Returns
+    /// Result containing the 16 bytes authentication tag or an
+    /// EncryptionError
+    pub async fn authenticate(
+        &self,
+        nonce: &Nonce<Self>,
+        data: &[u8],
+    ) -> Result<Vec<u8>, EncryptionError> {
+        self.encrypt_with_aad(nonce, data, &[]).await
+    }
+
+    /// Verifies a GMAC authentication tag produced by
+    /// [`AesGcm::authenticate`]
+    ///
+    /// The comparison is done by the browser, in constant time.
+    ///
+    /// # Arguments
+    /// * `nonce` - Nonce used for the authentication
+    /// * `tag` - Authentication tag to verify
+    /// * `data` - Data that was authenticated
+    ///
+    /// # Returns
+    /// Result containing whether the authentication tag is valid or a
+    /// DecryptionError
+    ///
+    /// # Errors
+    /// - `DecryptionError::InvalidAccess` if the key can't decrypt
+    pub async fn verify_tag(
+        &self,
+        nonce: &Nonce<Self>,
+        tag: &[u8],
+        data: &[u8],
+    ) -> Result<bool, DecryptionError> {
+        match self.decrypt_with_aad(nonce, data, tag).await {
+            Ok(_) => Ok(true),
+            Err(DecryptionError::Operation) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl<const KEY_BITS: usize> AesGcm<KEY_BITS> {
+    /// Encrypts data, returning the encrypted bytes as a JavaScript array
+    ///
+    /// The encrypted bytes are not copied into the WebAssembly memory, which
+    /// suits the values handed back to JavaScript, like the result of an
+    /// exported function.
+    ///
+    /// # Arguments
+    /// * `nonce` - Nonce to use for encryption
+    /// * `payload` - Data to encrypt, held by Rust or JavaScript
+    ///
+    /// # Returns
+    /// Result containing the encrypted bytes or an EncryptionError
+    pub async fn encrypt_js<'a>(
+        &self,
+        nonce: &Nonce<Self>,
+        payload: impl Into<CryptoInput<'a>>,
+    ) -> Result<js_sys::Uint8Array, EncryptionError> {
+        let ciphertext = self.encrypt_buffer(nonce, &[], payload.into()).await?;
+        Ok(js_sys::Uint8Array::new(&ciphertext))
+    }
+
+    /// Decrypts data, returning the decrypted bytes as a JavaScript array
+    ///
+    /// The decrypted bytes are not copied into the WebAssembly memory, which
+    /// suits the values handed back to JavaScript, like the result of an
+    /// exported function.
+    ///
+    /// # Arguments
+    /// * `nonce` - Nonce used for encryption
+    /// * `payload` - Encrypted data to decrypt, held by Rust or JavaScript
+    ///
+    /// # Returns
+    /// Result containing the decrypted bytes or a DecryptionError
+    pub async fn decrypt_js<'a>(
+        &self,
+        nonce: &Nonce<Self>,
+        payload: impl Into<CryptoInput<'a>>,
+    ) -> Result<js_sys::Uint8Array, DecryptionError> {
+        let clear = self.decrypt_buffer(nonce, &[], payload.into()).await?;
+        Ok(js_sys::Uint8Array::new(&clear))
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<const KEY_BITS: usize> AesGcm<KEY_BITS> {
+    /// Encrypts data, copying the encrypted bytes from the browser directly
+    /// into a `Bytes` buffer
+    ///
+    /// Requires the `bytes` feature.
+    ///
+    /// # Arguments
+    /// * `nonce` - Nonce to use for encryption
+    /// * `payload` - Data to encrypt, like a `Bytes` buffer
+    ///
+    /// # Returns
+    /// Result containing the encrypted bytes or an EncryptionError
+    pub async fn encrypt_bytes<'a>(
+        &self,
+        nonce: &Nonce<Self>,
+        payload: impl Into<CryptoInput<'a>>,
+    ) -> Result<bytes::Bytes, EncryptionError> {
+        let ciphertext = self.encrypt_buffer(nonce, &[], payload.into()).await?;
+        Ok(crate::array_to_bytes(&js_sys::Uint8Array::new(&ciphertext)))
+    }
+
+    /// Decrypts data, copying the decrypted bytes from the browser directly
+    /// into a `Bytes` buffer
+    ///
+    /// Requires the `bytes` feature.
+    ///
+    /// # Arguments
+    /// * `nonce` - Nonce used for encryption
+    /// * `payload` - Encrypted data to decrypt, like a `Bytes` buffer
+    ///
+    /// # Returns
+    /// Result containing the decrypted bytes or a DecryptionError
+    pub async fn decrypt_bytes<'a>(
+        &self,
+        nonce: &Nonce<Self>,
+        payload: impl Into<CryptoInput<'a>>,
+    ) -> Result<bytes::Bytes, DecryptionError> {
+        let clear = self.decrypt_buffer(nonce, &[], payload.into()).await?;
+        Ok(crate::array_to_bytes(&js_sys::Uint8Array::new(&clear)))
+    }
+}
+
+impl<const KEY_BITS: usize> Algorithm for AesGcm<KEY_BITS> {
+    const NAME: &'static str = NAME;
+    const NONCE_SIZE: u32 = 12;
+    const KEY_SIZE: u32 = KEY_BITS as u32 / 8;
+    type EncryptError = EncryptionError;
+    type DecryptError = DecryptionError;
+
+    async fn encrypt(
+        &self,
+        nonce: &Nonce<Self>,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, EncryptionError> {
+        self.encrypt_with_aad(nonce, &[], payload).await
+    }
+
+    async fn decrypt(
+        &self,
+        nonce: &Nonce<Self>,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, DecryptionError> {
+        self.decrypt_with_aad(nonce, &[], payload).await
+    }
+
+    /// Encrypts data, copying the encrypted data from the browser directly to
+    /// the end of the output buffer
+    #[cfg(not(native_backend))]
+    async fn encrypt_into(
+        &self,
+        nonce: &Nonce<Self>,
+        payload: &[u8],
+        output: &mut Vec<u8>,
+    ) -> Result<(), EncryptionError> {
+        let ciphertext = self.encrypt_buffer(nonce, &[], payload.into()).await?;
+        crate::extend_from_array(output, &js_sys::Uint8Array::new(&ciphertext));
+        Ok(())
+    }
+
+    /// Decrypts data, copying the decrypted data from the browser directly to
+    /// the end of the output buffer
+    #[cfg(not(native_backend))]
+    async fn decrypt_into(
+        &self,
+        nonce: &Nonce<Self>,
+        payload: &[u8],
+        output: &mut Vec<u8>,
+    ) -> Result<(), DecryptionError> {
+        let clear = self.decrypt_buffer(nonce, &[], payload.into()).await?;
+        crate::extend_from_array(output, &js_sys::Uint8Array::new(&clear));
+        Ok(())
+    }
+
+    /// Decrypts data in its buffer, copying the decrypted data from the
+    /// browser directly over the encrypted data
+    #[cfg(not(native_backend))]
+    async fn decrypt_in_place(
+        &self,
+        nonce: &Nonce<Self>,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), DecryptionError> {
+        let clear = self
+            .decrypt_buffer(nonce, &[], CryptoInput::Bytes(buffer))
+            .await?;
+        buffer.clear();
+        crate::extend_from_array(buffer, &js_sys::Uint8Array::new(&clear));
+        Ok(())
+    }
+}
+
+/// AES-GCM accepts initialization vectors of any length, even if 96 bits is the
+/// recommended size.
+impl<const KEY_BITS: usize> VariableNonceSize for AesGcm<KEY_BITS> {}
+
+/// The standard 96-bit nonces can't have an invalid size.
+impl<const KEY_BITS: usize> From<[u8; 12]> for Nonce<AesGcm<KEY_BITS>> {
+    fn from(value: [u8; 12]) -> Self {
+        Self::from_vec(value.to_vec())
+    }
+}
+
+impl<const KEY_BITS: usize> crate::nonce_sequence::SequentialNonce for AesGcm<KEY_BITS> {}
+
+impl<const KEY_BITS: usize> crate::key::private::Sealed for AesGcm<KEY_BITS> {
+    fn import_algorithm() -> Result<js_sys::Object, wasm_bindgen::JsValue> {
+        crate::key::aes_algorithm(NAME, KEY_BITS)
+    }
+
+    fn usages() -> &'static [&'static str] {
+        &["encrypt", "decrypt"]
+    }
+
+    fn crypto_key(&self) -> Result<&web_sys::CryptoKey, crate::Error> {
+        self.web_key()
+    }
+
+    fn from_crypto_key(key: web_sys::CryptoKey) -> Option<Self> {
+        crate::key::is_aes_key(&key, NAME, KEY_BITS)
+            .then_some(key)
+            .and_then(|key| Self::from_web_key(key).ok())
+    }
+}
+
+impl<const KEY_BITS: usize> WrappableKey for AesGcm<KEY_BITS> {}
+
+impl<const KEY_BITS: usize> crate::sealed::private::Cipher for AesGcm<KEY_BITS> {
+    const ALGORITHM: crate::sealed::SealedAlgorithm =
+        crate::sealed::SealedAlgorithm::aes_gcm(KEY_BITS);
+
+    async fn encrypt_with_aad(
+        &self,
+        nonce: &Nonce<Self>,
+        aad: &[u8],
+        payload: &[u8],
+    ) -> Result<Vec<u8>, EncryptionError> {
+        AesGcm::encrypt_with_aad(self, nonce, aad, payload).await
+    }
+
+    async fn decrypt_with_aad(
+        &self,
+        nonce: &Nonce<Self>,
+        aad: &[u8],
+        payload: &[u8],
+    ) -> Result<Vec<u8>, DecryptionError> {
+        AesGcm::decrypt_with_aad(self, nonce, aad, payload).await
+    }
+}
+
+impl<const KEY_BITS: usize> crate::sealed::SealingCipher for AesGcm<KEY_BITS> {}
+
+#[cfg(feature = "keystore")]
+impl<const KEY_BITS: usize> crate::keystore::private::Sealed for AesGcm<KEY_BITS> {
+    fn to_entry(&self) -> Result<js_sys::Object, wasm_bindgen::JsValue> {
+        crate::keystore::key_entry(self.web_key()?)
+    }
+
+    fn from_entry(entry: &wasm_bindgen::JsValue) -> Result<Self, crate::keystore::KeystoreError> {
+        let key = crate::keystore::entry_key(entry)?;
+        Ok(Self::from_crypto_key(key)?)
+    }
+}
+
+#[cfg(feature = "keystore")]
+impl<const KEY_BITS: usize> crate::keystore::StorableKey for AesGcm<KEY_BITS> {}
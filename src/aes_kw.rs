@@ -0,0 +1,140 @@
+//! AES-KW key wrapping implementation
+//!
+//! AES-KW ([RFC 3394](https://www.rfc-editor.org/rfc/rfc3394)) wraps a data
+//! encryption key (DEK) under a key encryption key (KEK), without the raw DEK
+//! bytes ever going through Rust or JavaScript.
+//!
+//! ```rust,no_run
+//! use browser_crypto::aes_gcm::Aes256Gcm;
+//! use browser_crypto::aes_kw::Aes256Kw;
+//! use browser_crypto::key::{KeyUnwrapper, KeyWrapper};
+//!
+//! async fn wrap() -> Result<(), Box<dyn std::error::Error>> {
+//!     let kek = Aes256Kw::from_key(&[0u8; 32]).await?;
+//!     let dek = Aes256Gcm::from_key(&[1u8; 32]).await?;
+//!
+//!     let wrapped = kek.wrap_key(&dek).await?;
+//!     let unwrapped: Aes256Gcm = kek.unwrap_key(&wrapped).await?;
+//!     Ok(())
+//! }
+//! ```
+
+use crate::input::SecretInput;
+use crate::jwk::Jwk;
+use crate::key::{ExportKeyError, ImportKeyError, KeyUnwrapper, KeyWrapper, WrappableKey};
+
+const NAME: &str = "AES-KW";
+
+/// AES-KW key wrapping implementation, for a key of `KEY_BITS` bits
+///
+/// Use one of the [`Aes128Kw`], [`Aes192Kw`] or [`Aes256Kw`] aliases.
+#[derive(Debug, Clone)]
+pub struct AesKw<const KEY_BITS: usize> {
+    key: web_sys::CryptoKey,
+}
+
+/// AES-128-KW key wrapping implementation
+pub type Aes128Kw = AesKw<128>;
+
+/// AES-192-KW key wrapping implementation
+///
+/// Note: Chromium based browsers don't support 192-bit AES keys, importing one
+/// will fail with `ImportKeyError::Generic`.
+pub type Aes192Kw = AesKw<192>;
+
+/// AES-256-KW key wrapping implementation
+pub type Aes256Kw = AesKw<256>;
+
+impl<const KEY_BITS: usize> AesKw<KEY_BITS> {
+    /// Size of the raw key in bytes
+    pub const KEY_SIZE: usize = KEY_BITS / 8;
+
+    /// Creates a new AES-KW instance from a raw key.
+    ///
+    /// # Arguments
+    /// * `data` - Raw key bytes (should be `KEY_BITS / 8` bytes, 32 bytes for
+    ///   AES-256)
+    ///
+    /// # Returns
+    /// Result containing the AesKw instance or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::Syntax` if key usage array is empty
+    /// - `ImportKeyError::Type` if key format/data is invalid
+    /// - `ImportKeyError::InvalidKeyFormat` if provided key format is invalid
+    ///   or if the key size doesn't match `KEY_BITS`
+    pub async fn from_key(data: &(impl SecretInput + ?Sized)) -> Result<Self, ImportKeyError> {
+        let key = crate::key::import_raw_aes::<KEY_BITS>(
+            NAME,
+            data.expose_bytes(),
+            &["wrapKey", "unwrapKey"],
+        )
+        .await?;
+        Ok(Self { key })
+    }
+
+    /// Creates a new AES-KW instance from a JSON Web Key.
+    ///
+    /// # Arguments
+    /// * `jwk` - JSON Web Key of type "oct"
+    ///
+    /// # Returns
+    /// Result containing the AesKw instance or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::InvalidKeyFormat` if the key is invalid, if its
+    ///   algorithm doesn't match or if the key size doesn't match `KEY_BITS`
+    pub async fn from_jwk(jwk: &Jwk) -> Result<Self, ImportKeyError> {
+        let key =
+            crate::key::import_jwk_aes::<KEY_BITS>(NAME, jwk, &["wrapKey", "unwrapKey"]).await?;
+        Ok(Self { key })
+    }
+
+    /// Exports the key in the JSON Web Key format.
+    ///
+    /// # Returns
+    /// Result containing the JSON Web Key, of type "oct", or an ExportKeyError
+    ///
+    /// # Errors
+    /// - `ExportKeyError::InvalidAccess` if the key is not extractable, like
+    ///   derived keys
+    pub async fn to_jwk(&self) -> Result<Jwk, ExportKeyError> {
+        crate::key::export_jwk(&self.key).await
+    }
+}
+
+impl<const KEY_BITS: usize> crate::key::private::Sealed for AesKw<KEY_BITS> {
+    fn import_algorithm() -> Result<js_sys::Object, wasm_bindgen::JsValue> {
+        crate::key::aes_algorithm(NAME, KEY_BITS)
+    }
+
+    fn usages() -> &'static [&'static str] {
+        &["wrapKey", "unwrapKey"]
+    }
+
+    fn crypto_key(&self) -> Result<&web_sys::CryptoKey, crate::Error> {
+        Ok(&self.key)
+    }
+
+    fn from_crypto_key(key: web_sys::CryptoKey) -> Option<Self> {
+        crate::key::is_aes_key(&key, NAME, KEY_BITS).then_some(Self { key })
+    }
+}
+
+impl<const KEY_BITS: usize> WrappableKey for AesKw<KEY_BITS> {}
+
+impl<const KEY_BITS: usize> crate::key::private::Wrapper for AesKw<KEY_BITS> {
+    fn wrapping_algorithm(&self) -> Result<js_sys::Object, wasm_bindgen::JsValue> {
+        let algorithm = js_sys::Object::new();
+        js_sys::Reflect::set(&algorithm, &"name".into(), &NAME.into())?;
+        Ok(algorithm)
+    }
+
+    fn wrapping_key(&self) -> &web_sys::CryptoKey {
+        &self.key
+    }
+}
+
+impl<const KEY_BITS: usize> KeyWrapper for AesKw<KEY_BITS> {}
+
+impl<const KEY_BITS: usize> KeyUnwrapper for AesKw<KEY_BITS> {}
@@ -3,6 +3,10 @@ use std::marker::PhantomData;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::DomException;
 
+use crate::encoding::EncodingError;
+use crate::input::SecretInput;
+use crate::ErrorCode;
+
 /// Errors that can occur during nonce (number used once) operations.
 ///
 /// These errors handle both Web Crypto API random generation errors and
@@ -10,6 +14,8 @@ use web_sys::DomException;
 ///
 /// See [MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/Crypto/getRandomValues#exceptions)
 #[derive(Debug, Clone, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum NonceError {
     /// Indicates that the requested nonce length exceeds the maximum allowed
     /// size.
@@ -41,6 +47,21 @@ pub enum NonceError {
     /// received=16.
     #[error("invalid nonce size provided, expected {expected}, received {received}")]
     InvalidSize { expected: u32, received: u32 },
+    /// Indicates that a nonce sequence has produced all its nonces, and would
+    /// wrap around.
+    #[error("the nonce sequence is exhausted")]
+    Exhausted,
+    /// Indicates that a nonce of variable size is empty, with
+    /// [`Nonce::from_slice_unchecked_len`].
+    #[error("the nonce is empty")]
+    Empty,
+    /// Indicates that a nonce couldn't be parsed, the text not being properly
+    /// hex or base64url encoded.
+    ///
+    /// # Fields
+    /// * `0` - The decoding error
+    #[error("invalid nonce encoding: {0}")]
+    InvalidEncoding(#[from] EncodingError),
     /// A wrapper for other types of errors that may occur during nonce
     /// operations.
     ///
@@ -50,6 +71,25 @@ pub enum NonceError {
     Generic(#[from] crate::Error),
 }
 
+impl ErrorCode for NonceError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::QuotaExceeded => "nonce.quota_exceeded",
+            Self::InvalidSize { .. } => "nonce.invalid_size",
+            Self::Exhausted => "nonce.exhausted",
+            Self::Empty => "nonce.empty",
+            Self::InvalidEncoding(..) => "nonce.invalid_encoding",
+            Self::Generic(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<NonceError> for JsValue {
+    fn from(value: NonceError) -> Self {
+        crate::js_error("NonceError", &value)
+    }
+}
+
 impl From<wasm_bindgen::JsValue> for NonceError {
     fn from(value: wasm_bindgen::JsValue) -> Self {
         if let Some(exception) = value.dyn_ref::<web_sys::DomException>() {
@@ -61,6 +101,47 @@ impl From<wasm_bindgen::JsValue> for NonceError {
     }
 }
 
+/// Errors that can occur when generating or parsing a raw [`Key`].
+#[derive(Debug, Clone, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum KeyError {
+    /// Indicates that the key doesn't have the size expected by the
+    /// algorithm.
+    ///
+    /// # Fields
+    /// * `expected` - The key size required by the algorithm
+    /// * `received` - The size of the provided key
+    #[error("invalid key size provided, expected {expected}, received {received}")]
+    InvalidSize { expected: u32, received: u32 },
+    /// Indicates that a key couldn't be parsed, the text not being properly
+    /// encoded.
+    ///
+    /// # Fields
+    /// * `0` - The decoding error
+    #[error("invalid key encoding: {0}")]
+    InvalidEncoding(#[from] EncodingError),
+    /// Indicates that the random bytes couldn't be generated.
+    #[error(transparent)]
+    Random(#[from] NonceError),
+}
+
+impl ErrorCode for KeyError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidSize { .. } => "key.invalid_size",
+            Self::InvalidEncoding(..) => "key.invalid_encoding",
+            Self::Random(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<KeyError> for JsValue {
+    fn from(value: KeyError) -> Self {
+        crate::js_error("KeyError", &value)
+    }
+}
+
 /// Errors that can occur during encryption operations.
 ///
 /// These errors map to the exceptions defined in the Web Crypto API
@@ -68,6 +149,8 @@ impl From<wasm_bindgen::JsValue> for NonceError {
 ///
 /// See [MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto/encrypt#exceptions)
 #[derive(Debug, Clone, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum EncryptionError {
     /// Indicates that the requested operation is not valid for the provided
     /// key. This typically occurs when:
@@ -83,11 +166,32 @@ pub enum EncryptionError {
     /// - There's an internal error in the cryptographic implementation
     #[error("operation failed for an operation-specific reason")]
     Operation,
+    /// Indicates that the nonce couldn't be generated, with
+    /// [`Algorithm::seal`].
+    #[error(transparent)]
+    Nonce(#[from] NonceError),
     /// A wrapper for other types of errors that may occur during encryption
     #[error(transparent)]
     Generic(#[from] crate::Error),
 }
 
+impl ErrorCode for EncryptionError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidAccess => "encryption.invalid_access",
+            Self::Operation => "encryption.operation",
+            Self::Nonce(inner) => inner.code(),
+            Self::Generic(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<EncryptionError> for JsValue {
+    fn from(value: EncryptionError) -> Self {
+        crate::js_error("EncryptionError", &value)
+    }
+}
+
 impl From<JsValue> for EncryptionError {
     fn from(value: JsValue) -> Self {
         if let Some(exception) = value.dyn_ref::<DomException>() {
@@ -112,6 +216,8 @@ impl From<JsValue> for EncryptionError {
 ///
 /// See [MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto/decrypt#exceptions)
 #[derive(Debug, Clone, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum DecryptionError {
     /// Indicates that the requested operation is not valid for the provided
     /// key. This typically occurs when:
@@ -128,11 +234,42 @@ pub enum DecryptionError {
     ///   encryption
     #[error("operation failed for an operation-specific reason")]
     Operation,
+    /// Indicates that the input is truncated, like the input of
+    /// [`Algorithm::open`] being shorter than the nonce.
+    #[error("the sealed payload is truncated")]
+    Truncated,
+    /// Indicates that the key commitment of the payload doesn't match, the
+    /// payload being encrypted with another key, see [`crate::committing`].
+    #[error("the key commitment doesn't match")]
+    KeyCommitment,
+    /// Indicates that the decrypted data is not padded, see
+    /// [`crate::padding`].
+    #[error("invalid padding")]
+    InvalidPadding,
     /// A wrapper for other types of errors that may occur during decryption
     #[error(transparent)]
     Generic(#[from] crate::Error),
 }
 
+impl ErrorCode for DecryptionError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidAccess => "decryption.invalid_access",
+            Self::Operation => "decryption.operation",
+            Self::Truncated => "decryption.truncated",
+            Self::KeyCommitment => "decryption.key_commitment",
+            Self::InvalidPadding => "decryption.invalid_padding",
+            Self::Generic(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<DecryptionError> for JsValue {
+    fn from(value: DecryptionError) -> Self {
+        crate::js_error("DecryptionError", &value)
+    }
+}
+
 impl From<JsValue> for DecryptionError {
     fn from(value: JsValue) -> Self {
         if let Some(exception) = value.dyn_ref::<DomException>() {
@@ -151,14 +288,17 @@ impl From<JsValue> for DecryptionError {
 }
 
 /// Nonce handling for cryptographic operations
+///
+/// The bytes are held by Rust, so a nonce can be sent between threads or held
+/// across awaits, the JavaScript array being only created when used.
 #[derive(Debug, Clone)]
 pub struct Nonce<A> {
-    algo: PhantomData<A>,
-    inner: js_sys::Uint8Array,
+    algo: PhantomData<fn() -> A>,
+    inner: Vec<u8>,
 }
 
-impl<A> AsRef<js_sys::Uint8Array> for Nonce<A> {
-    fn as_ref(&self) -> &js_sys::Uint8Array {
+impl<A> AsRef<[u8]> for Nonce<A> {
+    fn as_ref(&self) -> &[u8] {
         &self.inner
     }
 }
@@ -173,12 +313,9 @@ where
     /// # Returns
     /// Result containing generated Nonce or NonceError
     pub fn generate() -> Result<Nonce<A>, NonceError> {
-        let crypto = crate::crypto()?;
-        let inner = js_sys::Uint8Array::new_with_length(A::NONCE_SIZE);
-        crypto.get_random_values_with_js_u8_array(&inner)?;
         Ok(Nonce {
             algo: PhantomData,
-            inner,
+            inner: crate::random::random_bytes(A::NONCE_SIZE as usize)?,
         })
     }
 
@@ -203,25 +340,416 @@ where
         }
         Ok(Self {
             algo: PhantomData,
-            inner: js_sys::Uint8Array::from(data),
+            inner: data.to_vec(),
         })
     }
 
     pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
-        (0..self.inner.length()).map(|idx| self.inner.get_index(idx))
+        self.inner.iter().copied()
     }
 
     /// Returns nonce bytes as a vector
     pub fn to_vec(&self) -> Vec<u8> {
-        crate::array_to_vec(&self.inner)
+        self.inner.clone()
+    }
+
+    /// Encodes the nonce as lowercase hexadecimal
+    pub fn to_hex(&self) -> String {
+        crate::encoding::hex_encode(&self.inner)
+    }
+
+    /// Parses a nonce encoded as hexadecimal, in lowercase or uppercase
+    ///
+    /// # Errors
+    /// - `NonceError::InvalidEncoding` if the text is not hexadecimal
+    /// - `NonceError::InvalidSize` if the nonce doesn't have the expected size
+    pub fn from_hex(input: &str) -> Result<Self, NonceError> {
+        let bytes = crate::encoding::hex_decode(input)?;
+        Self::from_slice(&bytes)
+    }
+
+    /// Encodes the nonce with the URL and filename safe base64 alphabet,
+    /// without padding
+    pub fn to_base64url(&self) -> String {
+        crate::encoding::base64url_encode(&self.inner)
+    }
+
+    /// Parses a nonce encoded with the URL and filename safe base64 alphabet,
+    /// padding being optional
+    ///
+    /// # Errors
+    /// - `NonceError::InvalidEncoding` if the text is not base64url encoded
+    /// - `NonceError::InvalidSize` if the nonce doesn't have the expected size
+    pub fn from_base64url(input: &str) -> Result<Self, NonceError> {
+        let bytes = crate::encoding::base64url_decode(input)?;
+        Self::from_slice(&bytes)
+    }
+}
+
+impl<A: Algorithm> TryFrom<&[u8]> for Nonce<A> {
+    type Error = NonceError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_slice(value)
+    }
+}
+
+impl<A: Algorithm> TryFrom<Vec<u8>> for Nonce<A> {
+    type Error = NonceError;
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        if value.len() as u32 != A::NONCE_SIZE {
+            return Err(NonceError::InvalidSize {
+                expected: A::NONCE_SIZE,
+                received: value.len() as u32,
+            });
+        }
+        Ok(Self::from_vec(value))
+    }
+}
+
+impl<A> IntoIterator for Nonce<A> {
+    type Item = u8;
+    type IntoIter = std::vec::IntoIter<u8>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.into_iter()
+    }
+}
+
+impl<'a, A> IntoIterator for &'a Nonce<A> {
+    type Item = u8;
+    type IntoIter = std::iter::Copied<std::slice::Iter<'a, u8>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.iter().copied()
+    }
+}
+
+/// Only displays the size of the nonce, so it doesn't end up in logs by
+/// accident. Use [`Nonce::to_hex`] or [`Nonce::to_base64url`] to store it.
+impl<A> std::fmt::Display for Nonce<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Nonce([REDACTED; {} bytes])", self.inner.len())
+    }
+}
+
+impl<A> Nonce<A> {
+    /// Wraps bytes already known to have the size of the nonces of `A`
+    pub(crate) fn from_vec(inner: Vec<u8>) -> Self {
+        Self {
+            algo: PhantomData,
+            inner,
+        }
+    }
+
+    /// Reuses the nonce bytes for another algorithm, with the same nonce size
+    pub(crate) fn cast<B>(&self) -> Nonce<B> {
+        Nonce {
+            algo: PhantomData,
+            inner: self.inner.clone(),
+        }
+    }
+
+    /// Creates the JavaScript array passed to the Web Crypto API
+    pub(crate) fn to_array(&self) -> js_sys::Uint8Array {
+        js_sys::Uint8Array::from(self.inner.as_slice())
+    }
+}
+
+impl<A> Nonce<A>
+where
+    A: VariableNonceSize,
+{
+    /// Creates a nonce from existing bytes, whatever their length
+    ///
+    /// This is meant to decrypt legacy data produced with a non standard nonce
+    /// size (like 16-byte IVs for AES-GCM). New data should rely on
+    /// [`Nonce::generate`] or [`Nonce::from_slice`].
+    ///
+    /// # Arguments
+    /// * `data` - Byte slice containing nonce data
+    ///
+    /// # Returns
+    /// Result containing the Nonce wrapping the provided bytes or a NonceError
+    ///
+    /// # Errors
+    /// - `NonceError::Empty` if the slice is empty, the Web Crypto API
+    ///   rejecting empty nonces with an opaque `OperationError`
+    pub fn from_slice_unchecked_len(data: &[u8]) -> Result<Self, NonceError> {
+        if data.is_empty() {
+            return Err(NonceError::Empty);
+        }
+        Ok(Self {
+            algo: PhantomData,
+            inner: data.to_vec(),
+        })
+    }
+}
+
+/// Serialized as bytes or, for human readable formats like JSON, as a base64
+/// string.
+#[cfg(feature = "serde")]
+impl<A> serde::Serialize for Nonce<A> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&crate::encoding::base64_encode(&self.inner))
+        } else {
+            serializer.serialize_bytes(&self.inner)
+        }
+    }
+}
+
+/// Checks the size of the nonce, like [`Nonce::from_slice`].
+#[cfg(feature = "serde")]
+impl<'de, A: Algorithm> serde::Deserialize<'de> for Nonce<A> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor<A>(PhantomData<fn() -> A>);
+
+        impl<'de, A: Algorithm> serde::de::Visitor<'de> for Visitor<A> {
+            type Value = Nonce<A>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(
+                    formatter,
+                    "a nonce of {} bytes, as bytes or a base64 string",
+                    A::NONCE_SIZE
+                )
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                let bytes = crate::encoding::base64_decode(value).map_err(E::custom)?;
+                Nonce::from_slice(&bytes).map_err(E::custom)
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+                Nonce::from_slice(value).map_err(E::custom)
+            }
+
+            fn visit_seq<S: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: S,
+            ) -> Result<Self::Value, S::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(byte) = seq.next_element::<u8>()? {
+                    bytes.push(byte);
+                }
+                Nonce::from_slice(&bytes).map_err(serde::de::Error::custom)
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(Visitor(PhantomData))
+        } else {
+            deserializer.deserialize_bytes(Visitor(PhantomData))
+        }
+    }
+}
+
+/// Raw key of an algorithm, before its import
+///
+/// The bytes are checked against [`Algorithm::KEY_SIZE`] when the key is
+/// created, so a key of the wrong size is rejected before reaching the Web
+/// Crypto API. The key is imported with the `from_key` constructor of the
+/// algorithm, like [`crate::aes_gcm::AesGcm::from_key`].
+///
+/// The bytes are only exposed through [`SecretInput`], the `Debug`
+/// implementation only showing the size of the key, and the keys are compared
+/// in constant time. With the `zeroize` feature enabled, they are overwritten
+/// with zeros when the key is dropped.
+#[derive(Clone)]
+pub struct Key<A> {
+    algo: PhantomData<fn() -> A>,
+    inner: crate::SecretBytes,
+}
+
+impl<A: Algorithm> Key<A> {
+    /// Generates a new random key
+    ///
+    /// # Returns
+    /// Result containing the generated Key or a KeyError
+    pub fn generate() -> Result<Self, KeyError> {
+        Ok(Self {
+            algo: PhantomData,
+            inner: crate::random::random_bytes(A::KEY_SIZE as usize)?.into(),
+        })
+    }
+
+    /// Creates a key from existing bytes
+    ///
+    /// # Arguments
+    /// * `data` - Byte slice containing the key
+    ///
+    /// # Errors
+    /// - `KeyError::InvalidSize` if the key doesn't have the size expected by
+    ///   the algorithm
+    pub fn from_slice(data: &[u8]) -> Result<Self, KeyError> {
+        let size = data.len() as u32;
+        if size != A::KEY_SIZE {
+            return Err(KeyError::InvalidSize {
+                expected: A::KEY_SIZE,
+                received: size,
+            });
+        }
+        Ok(Self {
+            algo: PhantomData,
+            inner: data.to_vec().into(),
+        })
+    }
+
+    /// Encodes the key as lowercase hexadecimal
+    pub fn to_hex(&self) -> String {
+        crate::encoding::hex_encode(&self.inner)
+    }
+
+    /// Parses a key encoded as hexadecimal, in lowercase or uppercase
+    ///
+    /// # Errors
+    /// - `KeyError::InvalidEncoding` if the text is not hexadecimal
+    /// - `KeyError::InvalidSize` if the key doesn't have the expected size
+    pub fn from_hex(input: &str) -> Result<Self, KeyError> {
+        let bytes = crate::SecretBytes::from(crate::encoding::hex_decode(input)?);
+        Self::from_slice(&bytes)
+    }
+
+    /// Encodes the key with the standard base64 alphabet, with padding
+    pub fn to_base64(&self) -> String {
+        crate::encoding::base64_encode(&self.inner)
+    }
+
+    /// Parses a key encoded with the standard base64 alphabet, padding being
+    /// optional
+    ///
+    /// # Errors
+    /// - `KeyError::InvalidEncoding` if the text is not base64 encoded
+    /// - `KeyError::InvalidSize` if the key doesn't have the expected size
+    pub fn from_base64(input: &str) -> Result<Self, KeyError> {
+        let bytes = crate::SecretBytes::from(crate::encoding::base64_decode(input)?);
+        Self::from_slice(&bytes)
+    }
+
+    /// Encodes the key with the URL and filename safe base64 alphabet,
+    /// without padding
+    pub fn to_base64url(&self) -> String {
+        crate::encoding::base64url_encode(&self.inner)
+    }
+
+    /// Parses a key encoded with the URL and filename safe base64 alphabet,
+    /// padding being optional
+    ///
+    /// # Errors
+    /// - `KeyError::InvalidEncoding` if the text is not base64url encoded
+    /// - `KeyError::InvalidSize` if the key doesn't have the expected size
+    pub fn from_base64url(input: &str) -> Result<Self, KeyError> {
+        let bytes = crate::SecretBytes::from(crate::encoding::base64url_decode(input)?);
+        Self::from_slice(&bytes)
+    }
+}
+
+impl<A> SecretInput for Key<A> {
+    fn expose_bytes(&self) -> &[u8] {
+        &self.inner
+    }
+}
+
+impl<A: Algorithm> TryFrom<&[u8]> for Key<A> {
+    type Error = KeyError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_slice(value)
+    }
+}
+
+impl<A> PartialEq for Key<A> {
+    fn eq(&self, other: &Self) -> bool {
+        crate::util::ct_eq(&self.inner, &other.inner)
+    }
+}
+
+impl<A> Eq for Key<A> {}
+
+impl<A> std::fmt::Debug for Key<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Key([REDACTED; {} bytes])", self.inner.len())
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<A> zeroize::ZeroizeOnDrop for Key<A> {}
+
+/// Data to encrypt or decrypt, along with additional data that is
+/// authenticated but not encrypted, like the `Payload` of the `aead` crate
+///
+/// References to bytes, like slices, arrays or vectors, convert into a payload
+/// without additional data, which doesn't allocate anything, so the same call
+/// accepts both:
+/// `cipher.encrypt_payload(&nonce, b"Hello World!")` or
+/// `cipher.encrypt_payload(&nonce, Payload { msg: b"Hello World!", aad:
+/// b"user-42" })` with [`crate::aes_gcm::AesGcm::encrypt_payload`].
+///
+/// The `Debug` implementation doesn't show the message.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct Payload<'a> {
+    /// Data to encrypt or decrypt
+    pub msg: &'a [u8],
+    /// Additional data, authenticated but not encrypted, can be empty
+    pub aad: &'a [u8],
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> From<&'a T> for Payload<'a> {
+    fn from(msg: &'a T) -> Self {
+        Self {
+            msg: msg.as_ref(),
+            aad: &[],
+        }
     }
 }
 
+impl std::fmt::Debug for Payload<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Payload")
+            .field("msg", &format_args!("[REDACTED; {} bytes]", self.msg.len()))
+            .field("aad", &self.aad)
+            .finish()
+    }
+}
+
+/// Marker trait for algorithms accepting nonces of any length
+///
+/// Implementing this trait enables [`Nonce::from_slice_unchecked_len`].
+pub trait VariableNonceSize: Algorithm {}
+
+/// Maximum number of concurrent operations of [`Algorithm::seal_batch`] and
+/// [`Algorithm::open_batch`]
+pub const DEFAULT_MAX_IN_FLIGHT: usize = 64;
+
 /// Core cryptographic algorithm trait
 pub trait Algorithm: Sized {
+    /// Name of the algorithm, like "AES-GCM", reported by
+    /// [`crate::context::ContextError`]
+    const NAME: &'static str;
+
     /// Required nonce size in bytes for this algorithm
     const NONCE_SIZE: u32;
 
+    /// Size in bytes of the raw keys of this algorithm, see [`Key`]
+    const KEY_SIZE: u32;
+
+    /// Error returned when encrypting, [`EncryptionError`] for the algorithms
+    /// of this crate
+    ///
+    /// Another implementation, like a software fallback, can return its own
+    /// error type, as long as it can represent the errors of the provided
+    /// methods, like a nonce that can't be generated.
+    type EncryptError: std::error::Error + From<EncryptionError>;
+
+    /// Error returned when decrypting, [`DecryptionError`] for the algorithms
+    /// of this crate
+    ///
+    /// Another implementation can return its own error type, as long as it can
+    /// represent the errors of the provided methods, like a truncated input.
+    type DecryptError: std::error::Error + From<DecryptionError>;
+
     /// Generates a new random nonce suitable for this algorithm
     ///
     /// # Returns
@@ -252,7 +780,7 @@ pub trait Algorithm: Sized {
         &self,
         nonce: &Nonce<Self>,
         payload: &[u8],
-    ) -> impl std::future::Future<Output = Result<Vec<u8>, EncryptionError>>;
+    ) -> impl std::future::Future<Output = Result<Vec<u8>, Self::EncryptError>>;
 
     /// Decrypts data using this algorithm
     ///
@@ -271,5 +799,229 @@ pub trait Algorithm: Sized {
         &self,
         nonce: &Nonce<Self>,
         payload: &[u8],
-    ) -> impl std::future::Future<Output = Result<Vec<u8>, DecryptionError>>;
+    ) -> impl std::future::Future<Output = Result<Vec<u8>, Self::DecryptError>>;
+
+    /// Encrypts data, appending the encrypted bytes to a buffer
+    ///
+    /// Reusing the same buffer, once cleared, avoids allocating a new one for
+    /// each payload. The buffer is left unchanged on error.
+    ///
+    /// # Arguments
+    /// * `nonce` - Nonce to use for encryption
+    /// * `payload` - Data to encrypt
+    /// * `output` - Buffer receiving the encrypted bytes
+    ///
+    /// # Returns
+    /// Result containing nothing or an EncryptionError
+    ///
+    /// # Errors
+    /// Same as [`Algorithm::encrypt`]
+    fn encrypt_into(
+        &self,
+        nonce: &Nonce<Self>,
+        payload: &[u8],
+        output: &mut Vec<u8>,
+    ) -> impl std::future::Future<Output = Result<(), Self::EncryptError>> {
+        async move {
+            let encrypted = self.encrypt(nonce, payload).await?;
+            output.extend_from_slice(&encrypted);
+            Ok(())
+        }
+    }
+
+    /// Decrypts data, appending the decrypted bytes to a buffer
+    ///
+    /// Reusing the same buffer, once cleared, avoids allocating a new one for
+    /// each payload. The buffer is left unchanged on error.
+    ///
+    /// # Arguments
+    /// * `nonce` - Nonce used for encryption
+    /// * `payload` - Encrypted data to decrypt
+    /// * `output` - Buffer receiving the decrypted bytes
+    ///
+    /// # Returns
+    /// Result containing nothing or a DecryptionError
+    ///
+    /// # Errors
+    /// Same as [`Algorithm::decrypt`]
+    fn decrypt_into(
+        &self,
+        nonce: &Nonce<Self>,
+        payload: &[u8],
+        output: &mut Vec<u8>,
+    ) -> impl std::future::Future<Output = Result<(), Self::DecryptError>> {
+        async move {
+            let decrypted = self.decrypt(nonce, payload).await?;
+            output.extend_from_slice(&decrypted);
+            Ok(())
+        }
+    }
+
+    /// Decrypts data in its buffer, the encrypted bytes being replaced by the
+    /// decrypted ones, like the authentication tag being truncated
+    ///
+    /// The buffer is a `Vec` rather than a slice so it can be truncated, the
+    /// decrypted data being shorter than the encrypted one for AES-GCM and
+    /// AES-CBC. The buffer is left unchanged on error.
+    ///
+    /// The Web Crypto ciphers of this crate copy the decrypted data from the
+    /// browser directly into the buffer. The default implementation, for the
+    /// other algorithms, decrypts into a new buffer and copies it back.
+    ///
+    /// # Arguments
+    /// * `nonce` - Nonce used for encryption
+    /// * `buffer` - Encrypted data, replaced by the decrypted data
+    ///
+    /// # Returns
+    /// Result containing nothing or a DecryptionError
+    ///
+    /// # Errors
+    /// Same as [`Algorithm::decrypt`]
+    fn decrypt_in_place(
+        &self,
+        nonce: &Nonce<Self>,
+        buffer: &mut Vec<u8>,
+    ) -> impl std::future::Future<Output = Result<(), Self::DecryptError>> {
+        async move {
+            let decrypted = self.decrypt(nonce, buffer).await?;
+            buffer.clear();
+            buffer.extend_from_slice(&decrypted);
+            Ok(())
+        }
+    }
+
+    /// Encrypts data with a random nonce, prepended to the encrypted data
+    ///
+    /// # Arguments
+    /// * `payload` - Data to encrypt
+    ///
+    /// # Returns
+    /// Result containing the nonce followed by the encrypted bytes, or an
+    /// EncryptionError
+    ///
+    /// # Errors
+    /// - `EncryptionError::Nonce` if the nonce couldn't be generated
+    /// - `EncryptionError::Operation` if encryption fails for
+    ///   algorithm-specific reasons
+    fn seal(
+        &self,
+        payload: &[u8],
+    ) -> impl std::future::Future<Output = Result<Vec<u8>, Self::EncryptError>> {
+        async move {
+            let nonce = Self::generate_nonce().map_err(EncryptionError::from)?;
+            let mut output = nonce.to_vec();
+            self.encrypt_into(&nonce, payload, &mut output).await?;
+            Ok(output)
+        }
+    }
+
+    /// Decrypts data produced by [`Algorithm::seal`]
+    ///
+    /// # Arguments
+    /// * `sealed` - Nonce followed by the encrypted bytes
+    ///
+    /// # Returns
+    /// Result containing decrypted bytes or a DecryptionError
+    ///
+    /// # Errors
+    /// - `DecryptionError::Truncated` if the input is shorter than the nonce
+    /// - `DecryptionError::Operation` if decryption fails for
+    ///   algorithm-specific reasons
+    fn open(
+        &self,
+        sealed: &[u8],
+    ) -> impl std::future::Future<Output = Result<Vec<u8>, Self::DecryptError>> {
+        async move {
+            let nonce_size = Self::NONCE_SIZE as usize;
+            if sealed.len() < nonce_size {
+                return Err(DecryptionError::Truncated.into());
+            }
+            let (nonce, payload) = sealed.split_at(nonce_size);
+            let nonce = Nonce::<Self>::from_slice(nonce).map_err(|_| DecryptionError::Truncated)?;
+            self.decrypt(&nonce, payload).await
+        }
+    }
+
+    /// Encrypts several payloads concurrently, each one with its own random
+    /// nonce, like [`Algorithm::seal`]
+    ///
+    /// The Web Crypto operations are started before awaiting them, at most
+    /// [`DEFAULT_MAX_IN_FLIGHT`] at a time, which is faster than sealing the
+    /// payloads one after the other.
+    ///
+    /// # Arguments
+    /// * `payloads` - Data to encrypt
+    ///
+    /// # Returns
+    /// Result containing the sealed payloads, in the same order, or the first
+    /// EncryptionError
+    fn seal_batch(
+        &self,
+        payloads: &[&[u8]],
+    ) -> impl std::future::Future<Output = Result<Vec<Vec<u8>>, Self::EncryptError>> {
+        self.seal_batch_with_limit(payloads, DEFAULT_MAX_IN_FLIGHT)
+    }
+
+    /// Encrypts several payloads concurrently, like [`Algorithm::seal_batch`],
+    /// with at most `max_in_flight` operations at a time
+    ///
+    /// # Arguments
+    /// * `payloads` - Data to encrypt
+    /// * `max_in_flight` - Maximum number of concurrent operations, 0 being
+    ///   considered as 1
+    ///
+    /// # Returns
+    /// Result containing the sealed payloads, in the same order, or the first
+    /// EncryptionError
+    fn seal_batch_with_limit(
+        &self,
+        payloads: &[&[u8]],
+        max_in_flight: usize,
+    ) -> impl std::future::Future<Output = Result<Vec<Vec<u8>>, Self::EncryptError>> {
+        async move {
+            let futures = payloads.iter().map(|data| self.seal(data));
+            let results = crate::join::join_all(futures, max_in_flight).await;
+            results.into_iter().collect()
+        }
+    }
+
+    /// Decrypts several payloads produced by [`Algorithm::seal`] or
+    /// [`Algorithm::seal_batch`] concurrently, at most
+    /// [`DEFAULT_MAX_IN_FLIGHT`] at a time
+    ///
+    /// # Arguments
+    /// * `sealed` - Nonces followed by the encrypted bytes
+    ///
+    /// # Returns
+    /// Result containing the decrypted payloads, in the same order, or the
+    /// first DecryptionError
+    fn open_batch(
+        &self,
+        sealed: &[&[u8]],
+    ) -> impl std::future::Future<Output = Result<Vec<Vec<u8>>, Self::DecryptError>> {
+        self.open_batch_with_limit(sealed, DEFAULT_MAX_IN_FLIGHT)
+    }
+
+    /// Decrypts several payloads concurrently, like [`Algorithm::open_batch`],
+    /// with at most `max_in_flight` operations at a time
+    ///
+    /// # Arguments
+    /// * `sealed` - Nonces followed by the encrypted bytes
+    /// * `max_in_flight` - Maximum number of concurrent operations, 0 being
+    ///   considered as 1
+    ///
+    /// # Returns
+    /// Result containing the decrypted payloads, in the same order, or the
+    /// first DecryptionError
+    fn open_batch_with_limit(
+        &self,
+        sealed: &[&[u8]],
+        max_in_flight: usize,
+    ) -> impl std::future::Future<Output = Result<Vec<Vec<u8>>, Self::DecryptError>> {
+        async move {
+            let futures = sealed.iter().map(|data| self.open(data));
+            let results = crate::join::join_all(futures, max_in_flight).await;
+            results.into_iter().collect()
+        }
+    }
 }
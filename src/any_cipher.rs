@@ -0,0 +1,188 @@
+//! Built-in ciphers chosen at runtime
+//!
+//! [`AnyCipher`] holds one of the built-in AEAD ciphers, picked from the
+//! algorithm identifier recorded in a payload, like the one of
+//! [`crate::sealed::Sealed`], instead of a type parameter. Unlike a
+//! `Box<dyn DynAlgorithm>`, the cipher can be matched to get back the
+//! concrete type.
+//!
+//! ```rust,no_run
+//! use browser_crypto::any_cipher::AnyCipher;
+//! use browser_crypto::sealed::Sealed;
+//!
+//! async fn open(key: &[u8], bytes: &[u8]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+//!     let sealed = Sealed::from_bytes(bytes)?;
+//!     let cipher = AnyCipher::from_key(sealed.algorithm(), key).await?;
+//!     Ok(sealed.open_any(&cipher, b"").await?)
+//! }
+//! ```
+
+use crate::aes_gcm::{Aes128Gcm, Aes192Gcm, Aes256Gcm};
+use crate::algorithm::{DecryptionError, EncryptionError, NonceError};
+use crate::dyn_algorithm::DynAlgorithm;
+use crate::input::SecretInput;
+use crate::key::{GenerateKeyError, ImportKeyError};
+use crate::sealed::SealedAlgorithm;
+
+/// One of the built-in AEAD ciphers
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum AnyCipher {
+    /// AES-128-GCM
+    Aes128Gcm(Aes128Gcm),
+    /// AES-192-GCM
+    Aes192Gcm(Aes192Gcm),
+    /// AES-256-GCM
+    Aes256Gcm(Aes256Gcm),
+}
+
+impl AnyCipher {
+    /// Creates a cipher of an algorithm from a raw key.
+    ///
+    /// # Arguments
+    /// * `algorithm` - Algorithm of the cipher, like
+    ///   [`crate::sealed::Sealed::algorithm`]
+    /// * `data` - Raw key bytes, of the size of the algorithm
+    ///
+    /// # Returns
+    /// Result containing the cipher or an ImportKeyError
+    ///
+    /// # Errors
+    /// Same as [`crate::aes_gcm::AesGcm::from_key`]
+    pub async fn from_key(
+        algorithm: SealedAlgorithm,
+        data: &(impl SecretInput + ?Sized),
+    ) -> Result<Self, ImportKeyError> {
+        Ok(match algorithm {
+            SealedAlgorithm::Aes128Gcm => Self::Aes128Gcm(Aes128Gcm::from_key(data).await?),
+            SealedAlgorithm::Aes192Gcm => Self::Aes192Gcm(Aes192Gcm::from_key(data).await?),
+            SealedAlgorithm::Aes256Gcm => Self::Aes256Gcm(Aes256Gcm::from_key(data).await?),
+        })
+    }
+
+    /// Generates a random key for an algorithm.
+    ///
+    /// # Arguments
+    /// * `algorithm` - Algorithm of the cipher
+    /// * `extractable` - Whether the key can be wrapped
+    ///
+    /// # Returns
+    /// Result containing the cipher or a GenerateKeyError
+    ///
+    /// # Errors
+    /// Same as [`crate::aes_gcm::AesGcm::generate`]
+    pub async fn generate(
+        algorithm: SealedAlgorithm,
+        extractable: bool,
+    ) -> Result<Self, GenerateKeyError> {
+        Ok(match algorithm {
+            SealedAlgorithm::Aes128Gcm => Self::Aes128Gcm(Aes128Gcm::generate(extractable).await?),
+            SealedAlgorithm::Aes192Gcm => Self::Aes192Gcm(Aes192Gcm::generate(extractable).await?),
+            SealedAlgorithm::Aes256Gcm => Self::Aes256Gcm(Aes256Gcm::generate(extractable).await?),
+        })
+    }
+
+    /// Algorithm of the cipher, with its stable identifier
+    pub fn algorithm(&self) -> SealedAlgorithm {
+        match self {
+            Self::Aes128Gcm(_) => SealedAlgorithm::Aes128Gcm,
+            Self::Aes192Gcm(_) => SealedAlgorithm::Aes192Gcm,
+            Self::Aes256Gcm(_) => SealedAlgorithm::Aes256Gcm,
+        }
+    }
+
+    /// Cipher as a trait object
+    pub fn as_dyn(&self) -> &dyn DynAlgorithm {
+        match self {
+            Self::Aes128Gcm(inner) => inner,
+            Self::Aes192Gcm(inner) => inner,
+            Self::Aes256Gcm(inner) => inner,
+        }
+    }
+
+    /// Name of the algorithm, like "AES-GCM"
+    pub fn name(&self) -> &'static str {
+        self.as_dyn().name()
+    }
+
+    /// Required nonce size in bytes
+    pub fn nonce_size(&self) -> u32 {
+        self.as_dyn().nonce_size()
+    }
+
+    /// Size in bytes of the raw keys
+    pub fn key_size(&self) -> u32 {
+        self.as_dyn().key_size()
+    }
+
+    /// Generates a random nonce of [`AnyCipher::nonce_size`] bytes
+    ///
+    /// # Errors
+    /// - `NonceError::QuotaExceeded` if the random generator refuses the
+    ///   request
+    /// - `NonceError::Generic` if the random generator is not available
+    pub fn generate_nonce(&self) -> Result<Vec<u8>, NonceError> {
+        let mut nonce = vec![0u8; self.nonce_size() as usize];
+        crate::random::fill_random(&mut nonce)?;
+        Ok(nonce)
+    }
+
+    /// Encrypts data.
+    ///
+    /// # Arguments
+    /// * `nonce` - Nonce to use for encryption, of [`AnyCipher::nonce_size`]
+    ///   bytes
+    /// * `payload` - Data to encrypt
+    ///
+    /// # Errors
+    /// Same as [`DynAlgorithm::encrypt`]
+    pub async fn encrypt(&self, nonce: &[u8], payload: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        self.as_dyn().encrypt(nonce, payload).await
+    }
+
+    /// Decrypts data.
+    ///
+    /// # Arguments
+    /// * `nonce` - Nonce used for encryption
+    /// * `payload` - Encrypted data to decrypt
+    ///
+    /// # Errors
+    /// Same as [`DynAlgorithm::decrypt`]
+    pub async fn decrypt(&self, nonce: &[u8], payload: &[u8]) -> Result<Vec<u8>, DecryptionError> {
+        self.as_dyn().decrypt(nonce, payload).await
+    }
+
+    /// Encrypts data with a random nonce, prepended to the encrypted data.
+    ///
+    /// # Errors
+    /// Same as [`crate::algorithm::Algorithm::seal`]
+    pub async fn seal(&self, payload: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        self.as_dyn().seal(payload).await
+    }
+
+    /// Decrypts data produced by [`AnyCipher::seal`].
+    ///
+    /// # Errors
+    /// Same as [`crate::algorithm::Algorithm::open`]
+    pub async fn open(&self, sealed: &[u8]) -> Result<Vec<u8>, DecryptionError> {
+        self.as_dyn().open(sealed).await
+    }
+}
+
+impl From<Aes128Gcm> for AnyCipher {
+    fn from(value: Aes128Gcm) -> Self {
+        Self::Aes128Gcm(value)
+    }
+}
+
+impl From<Aes192Gcm> for AnyCipher {
+    fn from(value: Aes192Gcm) -> Self {
+        Self::Aes192Gcm(value)
+    }
+}
+
+impl From<Aes256Gcm> for AnyCipher {
+    fn from(value: Aes256Gcm) -> Self {
+        Self::Aes256Gcm(value)
+    }
+}
@@ -0,0 +1,181 @@
+//! Audit trail of the Web Crypto operations
+//!
+//! Regulated applications may have to keep a trail of the cryptographic
+//! operations run on the client. An application can register an
+//! [`AuditSink`], or a closure, with [`set_audit_sink`] to receive an
+//! [`AuditRecord`] after each key generation, import, export, wrapping,
+//! encryption, decryption, signature, verification, digest and derivation.
+//! The records describe the operation, the key ID, the length of the payload
+//! and the outcome, never the data nor the key material.
+//!
+//! The operations run with a [`crate::kid::Tagged`] key are recorded with its
+//! key ID, the others can be attributed a key ID with [`with_kid`]. The
+//! verification of a signature that doesn't match is a successful operation,
+//! with a `false` result.
+//!
+//! The sink is called synchronously, so it can't persist the records itself:
+//! a tamper-evident trail would, for example, chain the hash of each record
+//! into the next one and queue them for storage.
+//!
+//! The sink is registered for the current thread only: each worker registers
+//! its own.
+//!
+//! Requires the `audit` feature.
+//!
+//! ```rust,no_run
+//! use browser_crypto::audit::{self, AuditRecord};
+//!
+//! audit::set_audit_sink(|record: &AuditRecord<'_>| {
+//!     web_sys::console::log_1(
+//!         &format!(
+//!             "{} with {} by {:?}: {:?}",
+//!             record.operation, record.algorithm, record.kid, record.outcome
+//!         )
+//!         .into(),
+//!     );
+//! });
+//! ```
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+use crate::context::Operation;
+
+/// Outcome of an audited operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase", tag = "status"))]
+pub enum Outcome {
+    /// The operation succeeded
+    Success,
+    /// The operation failed
+    ///
+    /// # Fields
+    /// * `code` - Code of the error, see [`crate::ErrorCode`]
+    Failure { code: &'static str },
+}
+
+/// Record of a Web Crypto operation
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AuditRecord<'a> {
+    /// Operation that was run
+    pub operation: Operation,
+    /// Name of the algorithm, like "AES-GCM"
+    pub algorithm: &'a str,
+    /// ID of the key, for the keys attributed one
+    pub kid: Option<&'a str>,
+    /// Length of the payload in bytes, for the operations processing data
+    pub payload_len: Option<usize>,
+    /// Outcome of the operation
+    pub outcome: Outcome,
+}
+
+impl AuditRecord<'_> {
+    /// Whether the operation succeeded
+    pub fn is_success(&self) -> bool {
+        self.outcome == Outcome::Success
+    }
+}
+
+/// Receiver of the audit records
+///
+/// Implemented for the closures taking an [`AuditRecord`].
+pub trait AuditSink {
+    /// Receives the record of an operation
+    fn record(&self, record: &AuditRecord<'_>);
+}
+
+impl<F: Fn(&AuditRecord<'_>)> AuditSink for F {
+    fn record(&self, record: &AuditRecord<'_>) {
+        self(record)
+    }
+}
+
+thread_local! {
+    static SINK: RefCell<Option<Rc<dyn AuditSink>>> = const { RefCell::new(None) };
+    static KID: RefCell<Option<Rc<str>>> = const { RefCell::new(None) };
+}
+
+/// Registers the sink of the current thread, replacing the previous one
+pub fn set_audit_sink(sink: impl AuditSink + 'static) {
+    SINK.with(|current| *current.borrow_mut() = Some(Rc::new(sink)));
+}
+
+/// Unregisters the sink of the current thread
+pub fn clear_audit_sink() {
+    SINK.with(|current| *current.borrow_mut() = None);
+}
+
+/// Hands a record to the registered sink, if any
+///
+/// The sink is cloned out of the cell first, so it can register another one
+/// while recording.
+pub(crate) fn record(record: &AuditRecord<'_>) {
+    let sink = SINK.with(|current| current.borrow().clone());
+    if let Some(sink) = sink {
+        sink.record(record);
+    }
+}
+
+/// Key ID attributed to the operations being polled, if any
+pub(crate) fn current_kid() -> Option<Rc<str>> {
+    KID.with(|current| current.borrow().clone())
+}
+
+/// Attributes a key ID to the operations run by a future
+///
+/// The key ID only applies while the future is polled, so concurrent
+/// operations keep their own. The innermost key ID wins when they are nested.
+///
+/// # Arguments
+/// * `kid` - ID of the key
+/// * `future` - Operations using the key
+pub fn with_kid<F: Future>(kid: &str, future: F) -> WithKid<F> {
+    with_optional_kid(Some(kid), future)
+}
+
+/// Attributes a key ID, if any, to the operations run by a future, leaving
+/// the current one otherwise
+pub(crate) fn with_optional_kid<F: Future>(kid: Option<&str>, future: F) -> WithKid<F> {
+    WithKid {
+        kid: kid.map(Rc::from),
+        future: Box::pin(future),
+    }
+}
+
+/// Future returned by [`with_kid`]
+pub struct WithKid<F> {
+    kid: Option<Rc<str>>,
+    future: Pin<Box<F>>,
+}
+
+impl<F> std::fmt::Debug for WithKid<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WithKid").field("kid", &self.kid).finish()
+    }
+}
+
+/// Restores the previous key ID, even if the future panics
+struct Restore(Option<Rc<str>>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        KID.with(|current| *current.borrow_mut() = self.0.take());
+    }
+}
+
+impl<F: Future> Future for WithKid<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Some(kid) = self.kid.clone() else {
+            return self.future.as_mut().poll(cx);
+        };
+        let _restore = Restore(KID.with(|current| current.replace(Some(kid))));
+        self.future.as_mut().poll(cx)
+    }
+}
@@ -0,0 +1,286 @@
+//! Encryption of `Blob` and `File` objects
+//!
+//! [`encrypt_blob`] reads a blob chunk by chunk, without loading it entirely
+//! in memory, and returns a new blob with the same layout as the streams of
+//! [`crate::web_stream`]: the nonce prefix followed by the chunks encrypted
+//! with [`crate::stream`]. A `File` can be passed as a blob.
+//!
+//! The `_abortable` variants stop before the next chunk once their
+//! `AbortSignal` is aborted.
+//!
+//! [`digest_blob`] hashes a blob the same way, with the `incremental-digest`
+//! feature.
+//!
+//! Requires the `streams` feature.
+//!
+//! ```rust,no_run
+//! use browser_crypto::aes_gcm::Aes256Gcm;
+//! use browser_crypto::blob::{decrypt_blob, encrypt_blob};
+//!
+//! async fn store(blob: &web_sys::Blob) -> Result<(), Box<dyn std::error::Error>> {
+//!     let cipher = Aes256Gcm::generate(false).await?;
+//!     let encrypted = encrypt_blob(&cipher, blob).await?;
+//!     let decrypted = decrypt_blob(&cipher, &encrypted, &blob.type_()).await?;
+//!     assert_eq!(decrypted.size(), blob.size());
+//!     Ok(())
+//! }
+//! ```
+
+use js_sys::{Array, ArrayBuffer, Uint8Array};
+use web_sys::{AbortSignal, Blob};
+
+use crate::aes_gcm::Aes256Gcm;
+use crate::stream::{
+    StreamDecryptor, StreamEncryptor, StreamError, CHUNK_SIZE, PREFIX_SIZE, TAG_SIZE,
+};
+
+/// Size of the slices read when hashing a blob, 1 MiB
+#[cfg(feature = "incremental-digest")]
+const DIGEST_CHUNK_SIZE: u64 = 1024 * 1024;
+
+/// Reads a slice of a blob
+async fn read(blob: &Blob, start: u64, end: u64) -> Result<Vec<u8>, StreamError> {
+    let slice = blob.slice_with_f64_and_f64(start as f64, end as f64)?;
+    let buffer = crate::resolve::<ArrayBuffer, StreamError>(slice.array_buffer()).await?;
+    Ok(crate::array_to_vec(&Uint8Array::new(&buffer)))
+}
+
+/// Splits the content of a blob, after an offset, in chunks of `chunk_size`
+/// bytes, the last chunk being flagged
+///
+/// An empty content is made of a single empty chunk.
+fn chunks(offset: u64, size: u64, chunk_size: u64) -> impl Iterator<Item = (u64, u64, bool)> {
+    let length = size.saturating_sub(offset);
+    let count = length.div_ceil(chunk_size).max(1);
+    (0..count).map(move |index| {
+        let start = offset + index * chunk_size;
+        let end = (start + chunk_size).min(size.max(offset));
+        (start, end, index + 1 == count)
+    })
+}
+
+fn check_signal(signal: Option<&AbortSignal>) -> Result<(), StreamError> {
+    match signal {
+        Some(signal) if signal.aborted() => Err(StreamError::Aborted),
+        _ => Ok(()),
+    }
+}
+
+fn parts_blob(parts: &Array, content_type: &str) -> Result<Blob, StreamError> {
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(content_type);
+    Ok(Blob::new_with_u8_array_sequence_and_options(
+        parts, &options,
+    )?)
+}
+
+/// Encrypts the content of a blob
+///
+/// # Arguments
+/// * `cipher` - Cipher encrypting the blob
+/// * `blob` - Blob or File to encrypt
+///
+/// # Returns
+/// Result containing the encrypted blob, of type `application/octet-stream`,
+/// or a StreamError
+///
+/// # Errors
+/// - `StreamError::Generic` if the blob couldn't be read
+pub async fn encrypt_blob(cipher: &Aes256Gcm, blob: &Blob) -> Result<Blob, StreamError> {
+    encrypt_blob_with_progress(cipher, blob, |_, _| {}).await
+}
+
+/// Encrypts the content of a blob, reporting the progress after each chunk
+///
+/// # Arguments
+/// * `cipher` - Cipher encrypting the blob
+/// * `blob` - Blob or File to encrypt
+/// * `on_progress` - Called with the number of bytes of the blob encrypted so
+///   far and the size of the blob
+///
+/// # Returns
+/// Result containing the encrypted blob, of type `application/octet-stream`,
+/// or a StreamError
+///
+/// # Errors
+/// - `StreamError::Generic` if the blob couldn't be read
+pub async fn encrypt_blob_with_progress(
+    cipher: &Aes256Gcm,
+    blob: &Blob,
+    on_progress: impl FnMut(u64, u64),
+) -> Result<Blob, StreamError> {
+    encrypt(cipher, blob, None, on_progress).await
+}
+
+/// Encrypts the content of a blob until the signal is aborted, reporting the
+/// progress after each chunk
+///
+/// # Arguments
+/// * `cipher` - Cipher encrypting the blob
+/// * `blob` - Blob or File to encrypt
+/// * `signal` - Signal of an `AbortController`, checked before each chunk
+/// * `on_progress` - Called with the number of bytes of the blob encrypted so
+///   far and the size of the blob
+///
+/// # Returns
+/// Result containing the encrypted blob, of type `application/octet-stream`,
+/// or a StreamError
+///
+/// # Errors
+/// - `StreamError::Aborted` if the signal has been aborted
+/// - `StreamError::Generic` if the blob couldn't be read
+pub async fn encrypt_blob_abortable(
+    cipher: &Aes256Gcm,
+    blob: &Blob,
+    signal: &AbortSignal,
+    on_progress: impl FnMut(u64, u64),
+) -> Result<Blob, StreamError> {
+    encrypt(cipher, blob, Some(signal), on_progress).await
+}
+
+async fn encrypt(
+    cipher: &Aes256Gcm,
+    blob: &Blob,
+    signal: Option<&AbortSignal>,
+    mut on_progress: impl FnMut(u64, u64),
+) -> Result<Blob, StreamError> {
+    let mut encryptor = StreamEncryptor::new(cipher.clone())?;
+    let parts = Array::new();
+    parts.push(&Uint8Array::from(encryptor.prefix()));
+    let size = blob.size() as u64;
+    for (start, end, last) in chunks(0, size, CHUNK_SIZE as u64) {
+        check_signal(signal)?;
+        let chunk = read(blob, start, end).await?;
+        let encrypted = if last {
+            encryptor.finish(&chunk).await?
+        } else {
+            encryptor.encrypt_chunk(&chunk).await?
+        };
+        parts.push(&Uint8Array::from(encrypted.as_slice()));
+        on_progress(end, size);
+    }
+    parts_blob(&parts, "application/octet-stream")
+}
+
+/// Decrypts the content of a blob produced by [`encrypt_blob`] or by
+/// [`crate::web_stream::encrypt_transform`]
+///
+/// # Arguments
+/// * `cipher` - Cipher used to encrypt the blob
+/// * `blob` - Encrypted blob
+/// * `content_type` - MIME type of the decrypted blob, can be empty
+///
+/// # Returns
+/// Result containing the decrypted blob or a StreamError
+///
+/// # Errors
+/// - `StreamError::InvalidPrefix` if the blob is shorter than the nonce prefix
+/// - `StreamError::Decryption` if the blob has been altered or truncated
+pub async fn decrypt_blob(
+    cipher: &Aes256Gcm,
+    blob: &Blob,
+    content_type: &str,
+) -> Result<Blob, StreamError> {
+    decrypt_blob_with_progress(cipher, blob, content_type, |_, _| {}).await
+}
+
+/// Decrypts the content of a blob, reporting the progress after each chunk
+///
+/// # Arguments
+/// * `cipher` - Cipher used to encrypt the blob
+/// * `blob` - Encrypted blob
+/// * `content_type` - MIME type of the decrypted blob, can be empty
+/// * `on_progress` - Called with the number of bytes of the encrypted blob
+///   decrypted so far and the size of the encrypted blob
+///
+/// # Returns
+/// Result containing the decrypted blob or a StreamError
+///
+/// # Errors
+/// - `StreamError::InvalidPrefix` if the blob is shorter than the nonce prefix
+/// - `StreamError::Decryption` if the blob has been altered or truncated
+pub async fn decrypt_blob_with_progress(
+    cipher: &Aes256Gcm,
+    blob: &Blob,
+    content_type: &str,
+    on_progress: impl FnMut(u64, u64),
+) -> Result<Blob, StreamError> {
+    decrypt(cipher, blob, content_type, None, on_progress).await
+}
+
+/// Decrypts the content of a blob until the signal is aborted, reporting the
+/// progress after each chunk
+///
+/// # Arguments
+/// * `cipher` - Cipher used to encrypt the blob
+/// * `blob` - Encrypted blob
+/// * `content_type` - MIME type of the decrypted blob, can be empty
+/// * `signal` - Signal of an `AbortController`, checked before each chunk
+/// * `on_progress` - Called with the number of bytes of the encrypted blob
+///   decrypted so far and the size of the encrypted blob
+///
+/// # Returns
+/// Result containing the decrypted blob or a StreamError
+///
+/// # Errors
+/// - `StreamError::Aborted` if the signal has been aborted
+/// - `StreamError::InvalidPrefix` if the blob is shorter than the nonce prefix
+/// - `StreamError::Decryption` if the blob has been altered or truncated
+pub async fn decrypt_blob_abortable(
+    cipher: &Aes256Gcm,
+    blob: &Blob,
+    content_type: &str,
+    signal: &AbortSignal,
+    on_progress: impl FnMut(u64, u64),
+) -> Result<Blob, StreamError> {
+    decrypt(cipher, blob, content_type, Some(signal), on_progress).await
+}
+
+async fn decrypt(
+    cipher: &Aes256Gcm,
+    blob: &Blob,
+    content_type: &str,
+    signal: Option<&AbortSignal>,
+    mut on_progress: impl FnMut(u64, u64),
+) -> Result<Blob, StreamError> {
+    let prefix_size = PREFIX_SIZE as u64;
+    let size = blob.size() as u64;
+    let prefix = read(blob, 0, prefix_size).await?;
+    let mut decryptor = StreamDecryptor::new(cipher.clone(), &prefix)?;
+    let parts = Array::new();
+    for (start, end, last) in chunks(prefix_size, size, (CHUNK_SIZE + TAG_SIZE) as u64) {
+        check_signal(signal)?;
+        let chunk = read(blob, start, end).await?;
+        let decrypted = if last {
+            decryptor.finish(&chunk).await?
+        } else {
+            decryptor.decrypt_chunk(&chunk).await?
+        };
+        parts.push(&Uint8Array::from(decrypted.as_slice()));
+        on_progress(end, size);
+    }
+    parts_blob(&parts, content_type)
+}
+
+/// Computes the digest of the content of a blob, reading it slice by slice
+///
+/// Requires the `incremental-digest` feature.
+///
+/// # Arguments
+/// * `hash` - Hash function
+/// * `blob` - Blob or File to hash
+///
+/// # Returns
+/// Result containing the digest, the same as [`crate::hash::Hash::digest`] of
+/// the content, or a StreamError
+///
+/// # Errors
+/// - `StreamError::Generic` if the blob couldn't be read
+#[cfg(feature = "incremental-digest")]
+pub async fn digest_blob(hash: crate::hash::Hash, blob: &Blob) -> Result<Vec<u8>, StreamError> {
+    let mut hasher = hash.hasher();
+    for (start, end, _) in chunks(0, blob.size() as u64, DIGEST_CHUNK_SIZE) {
+        hasher.update(&read(blob, start, end).await?);
+    }
+    Ok(hasher.finalize())
+}
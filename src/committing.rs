@@ -0,0 +1,142 @@
+//! Key-committing AES-GCM
+//!
+//! AES-GCM is not key-committing: a ciphertext can be crafted to decrypt
+//! successfully under several keys, which enables partitioning oracle attacks
+//! on applications trying several keys, like a password list or a set of
+//! rotated keys. [`CommittingAesGcm`] derives an AES-GCM key and an HMAC
+//! commitment key from the same input key with HKDF, and appends a commitment
+//! to each ciphertext: the HMAC-SHA256 of the nonce. The commitment is checked
+//! before decrypting, so a ciphertext only decrypts with the key that produced
+//! it.
+//!
+//! The ciphertexts are 32 bytes longer than with [`crate::aes_gcm::AesGcm`]
+//! and can't be decrypted by it.
+//!
+//! ```rust,no_run
+//! use browser_crypto::algorithm::Algorithm;
+//! use browser_crypto::committing::CommittingAes256Gcm;
+//! use browser_crypto::hash::Hash;
+//! use browser_crypto::hkdf::Hkdf;
+//!
+//! async fn encrypt(master: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+//!     let hkdf = Hkdf::from_key(master, Hash::Sha256, b"some salt", b"").await?;
+//!     let cipher = CommittingAes256Gcm::derive_from(&hkdf).await?;
+//!     let encrypted = cipher.seal(b"Hello World!").await?;
+//!     assert_eq!(cipher.open(&encrypted).await?, b"Hello World!");
+//!     Ok(())
+//! }
+//! ```
+
+use crate::aes_gcm::AesGcm;
+use crate::algorithm::{Algorithm, DecryptionError, EncryptionError, Nonce};
+use crate::derive::DeriveError;
+use crate::hash::Hash;
+use crate::hkdf::Hkdf;
+use crate::hmac::Hmac;
+use crate::signature::{SignError, VerifyError};
+
+/// HKDF info of the encryption key
+const ENCRYPTION_INFO: &[u8] = b"browser-crypto committing aes-gcm encryption";
+
+/// HKDF info of the commitment key
+const COMMITMENT_INFO: &[u8] = b"browser-crypto committing aes-gcm commitment";
+
+/// Size of the commitment, with HMAC-SHA256
+pub const COMMITMENT_SIZE: usize = 32;
+
+/// AES-GCM encryption with a key commitment, for a key of `KEY_BITS` bits
+///
+/// Use one of the [`CommittingAes128Gcm`] or [`CommittingAes256Gcm`] aliases.
+#[derive(Debug, Clone)]
+pub struct CommittingAesGcm<const KEY_BITS: usize> {
+    cipher: AesGcm<KEY_BITS>,
+    commitment: Hmac,
+}
+
+/// AES-128-GCM encryption with a key commitment
+pub type CommittingAes128Gcm = CommittingAesGcm<128>;
+
+/// AES-256-GCM encryption with a key commitment
+pub type CommittingAes256Gcm = CommittingAesGcm<256>;
+
+impl<const KEY_BITS: usize> CommittingAesGcm<KEY_BITS> {
+    /// Derives the encryption key and the commitment key from an HKDF input
+    /// key, the info of the HKDF instance being replaced.
+    ///
+    /// Both keys are non extractable.
+    ///
+    /// # Arguments
+    /// * `hkdf` - HKDF instance, with the input key and the salt
+    ///
+    /// # Returns
+    /// Result containing the CommittingAesGcm instance or a DeriveError
+    pub async fn derive_from(hkdf: &Hkdf) -> Result<Self, DeriveError> {
+        let cipher = AesGcm::derive_from(&hkdf.with_info(ENCRYPTION_INFO)).await?;
+        let commitment = Hmac::derive_from(&hkdf.with_info(COMMITMENT_INFO), Hash::Sha256).await?;
+        Ok(Self { cipher, commitment })
+    }
+
+    /// Underlying AES-GCM cipher, producing ciphertexts without commitment
+    pub fn cipher(&self) -> &AesGcm<KEY_BITS> {
+        &self.cipher
+    }
+}
+
+impl<const KEY_BITS: usize> Algorithm for CommittingAesGcm<KEY_BITS> {
+    const NAME: &'static str = "Committing AES-GCM";
+    const NONCE_SIZE: u32 = 12;
+    const KEY_SIZE: u32 = KEY_BITS as u32 / 8;
+    type EncryptError = EncryptionError;
+    type DecryptError = DecryptionError;
+
+    /// Encrypts data, appending the commitment to the encrypted data
+    async fn encrypt(
+        &self,
+        nonce: &Nonce<Self>,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, EncryptionError> {
+        let mut output = self.cipher.encrypt(&nonce.cast(), payload).await?;
+        let commitment = self
+            .commitment
+            .sign(&nonce.to_vec())
+            .await
+            .map_err(|err| match err {
+                SignError::InvalidAccess => EncryptionError::InvalidAccess,
+                SignError::Generic(inner) => EncryptionError::Generic(inner),
+            })?;
+        output.extend_from_slice(&commitment);
+        Ok(output)
+    }
+
+    /// Checks the commitment before decrypting the data
+    ///
+    /// # Errors
+    /// - `DecryptionError::Truncated` if the payload is shorter than the
+    ///   commitment
+    /// - `DecryptionError::KeyCommitment` if the payload was encrypted with
+    ///   another key
+    async fn decrypt(
+        &self,
+        nonce: &Nonce<Self>,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, DecryptionError> {
+        let Some(split) = payload.len().checked_sub(COMMITMENT_SIZE) else {
+            return Err(DecryptionError::Truncated);
+        };
+        let (payload, commitment) = payload.split_at(split);
+        let valid = self
+            .commitment
+            .verify(commitment, &nonce.to_vec())
+            .await
+            .map_err(|err| match err {
+                VerifyError::InvalidAccess => DecryptionError::InvalidAccess,
+                VerifyError::Generic(inner) => DecryptionError::Generic(inner),
+            })?;
+        if !valid {
+            return Err(DecryptionError::KeyCommitment);
+        }
+        self.cipher.decrypt(&nonce.cast(), payload).await
+    }
+}
+
+impl<const KEY_BITS: usize> crate::nonce_sequence::SequentialNonce for CommittingAesGcm<KEY_BITS> {}
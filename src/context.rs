@@ -0,0 +1,156 @@
+//! Operation context of the errors
+//!
+//! The errors of the crate describe what went wrong, like
+//! `DecryptionError::Operation`, but not what the application was doing, which
+//! makes the logs of a production build hard to diagnose. [`ContextError`]
+//! wraps an error with the [`Operation`] and the algorithm that failed, and
+//! [`ErrorContext::context`] adds it to any result.
+//!
+//! The algorithms implementing [`crate::algorithm::Algorithm`] expose their
+//! name with [`crate::algorithm::Algorithm::NAME`].
+//!
+//! ```rust,no_run
+//! use browser_crypto::aes_gcm::Aes256Gcm;
+//! use browser_crypto::algorithm::{Algorithm, DecryptionError};
+//! use browser_crypto::context::{ContextError, ErrorContext, Operation};
+//!
+//! async fn decrypt(
+//!     cipher: &Aes256Gcm,
+//!     sealed: &[u8],
+//! ) -> Result<Vec<u8>, ContextError<DecryptionError>> {
+//!     // fails with "decrypt with AES-GCM failed: ..."
+//!     cipher
+//!         .open(sealed)
+//!         .await
+//!         .context(Operation::Decrypt, Aes256Gcm::NAME)
+//! }
+//! ```
+
+/// Operations of the Web Crypto API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Operation {
+    /// Encrypting data
+    Encrypt,
+    /// Decrypting data
+    Decrypt,
+    /// Signing data or computing a MAC
+    Sign,
+    /// Verifying a signature or a MAC
+    Verify,
+    /// Hashing data
+    Digest,
+    /// Generating a key or a key pair
+    GenerateKey,
+    /// Importing a key
+    ImportKey,
+    /// Exporting a key
+    ExportKey,
+    /// Deriving a key
+    DeriveKey,
+    /// Deriving bits
+    DeriveBits,
+    /// Wrapping a key
+    WrapKey,
+    /// Unwrapping a key
+    UnwrapKey,
+}
+
+impl Operation {
+    /// Name of the operation, as the method of `SubtleCrypto`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Encrypt => "encrypt",
+            Self::Decrypt => "decrypt",
+            Self::Sign => "sign",
+            Self::Verify => "verify",
+            Self::Digest => "digest",
+            Self::GenerateKey => "generateKey",
+            Self::ImportKey => "importKey",
+            Self::ExportKey => "exportKey",
+            Self::DeriveKey => "deriveKey",
+            Self::DeriveBits => "deriveBits",
+            Self::WrapKey => "wrapKey",
+            Self::UnwrapKey => "unwrapKey",
+        }
+    }
+}
+
+impl std::fmt::Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Error wrapped with the operation and the algorithm that failed
+///
+/// # Fields
+/// * `operation` - The operation that failed
+/// * `algorithm` - The name of the algorithm, like "AES-GCM"
+/// * `source` - The error of the operation
+#[derive(Debug, Clone, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[error("{operation} with {algorithm} failed: {source}")]
+pub struct ContextError<E> {
+    pub operation: Operation,
+    pub algorithm: &'static str,
+    #[source]
+    pub source: E,
+}
+
+impl<E> ContextError<E> {
+    /// Wraps an error with its context
+    pub fn new(operation: Operation, algorithm: &'static str, source: E) -> Self {
+        Self {
+            operation,
+            algorithm,
+            source,
+        }
+    }
+
+    /// Consumes the context, returning the wrapped error
+    pub fn into_inner(self) -> E {
+        self.source
+    }
+}
+
+/// Reports the code of the wrapped error
+impl<E: crate::ErrorCode> crate::ErrorCode for ContextError<E> {
+    fn code(&self) -> &'static str {
+        self.source.code()
+    }
+}
+
+/// Converts the error into a JavaScript `Error`, named after the wrapped error,
+/// with the `operation` and `algorithm` properties
+impl<E> From<ContextError<E>> for wasm_bindgen::JsValue
+where
+    E: std::fmt::Display,
+    wasm_bindgen::JsValue: From<E>,
+{
+    fn from(value: ContextError<E>) -> Self {
+        let message = value.to_string();
+        let output = Self::from(value.source);
+        let _ = js_sys::Reflect::set(&output, &"message".into(), &message.into());
+        let _ = js_sys::Reflect::set(&output, &"operation".into(), &value.operation.name().into());
+        let _ = js_sys::Reflect::set(&output, &"algorithm".into(), &value.algorithm.into());
+        output
+    }
+}
+
+/// Adds the operation context to the error of a result
+pub trait ErrorContext<T, E> {
+    /// Wraps the error, if any, with the operation and the algorithm that
+    /// failed
+    ///
+    /// # Arguments
+    /// * `operation` - The operation that failed
+    /// * `algorithm` - The name of the algorithm, like "AES-GCM"
+    fn context(self, operation: Operation, algorithm: &'static str) -> Result<T, ContextError<E>>;
+}
+
+impl<T, E> ErrorContext<T, E> for Result<T, E> {
+    fn context(self, operation: Operation, algorithm: &'static str) -> Result<T, ContextError<E>> {
+        self.map_err(|source| ContextError::new(operation, algorithm, source))
+    }
+}
@@ -0,0 +1,45 @@
+//! Elliptic curves supported by the Web Crypto API
+
+/// NIST elliptic curves, used by ECDSA and ECDH
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NamedCurve {
+    /// NIST P-256, also known as secp256r1
+    P256,
+    /// NIST P-384, also known as secp384r1
+    P384,
+    /// NIST P-521, also known as secp521r1
+    P521,
+}
+
+impl NamedCurve {
+    /// Name of the curve, as expected by the Web Crypto API
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::P256 => "P-256",
+            Self::P384 => "P-384",
+            Self::P521 => "P-521",
+        }
+    }
+
+    /// Finds a curve from its Web Crypto API name, like "P-256"
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "P-256" => Some(Self::P256),
+            "P-384" => Some(Self::P384),
+            "P-521" => Some(Self::P521),
+            _ => None,
+        }
+    }
+
+    /// Size of a field element in bytes
+    ///
+    /// ECDSA signatures are made of two field elements, and uncompressed
+    /// public keys of two field elements prefixed by one byte.
+    pub fn field_size(&self) -> usize {
+        match self {
+            Self::P256 => 32,
+            Self::P384 => 48,
+            Self::P521 => 66,
+        }
+    }
+}
@@ -0,0 +1,184 @@
+//! Minimal DER reader, checking the algorithm of SPKI and PKCS#8 keys
+//!
+//! The browser reports any issue with those keys as a `DataError`, this reads
+//! just enough of the structure to tell a malformed key apart from a key of
+//! another algorithm. Validating the key itself is left to the browser.
+
+use crate::curve::NamedCurve;
+use crate::key::{ImportKeyError, KeyFormat};
+
+const TAG_INTEGER: u8 = 0x02;
+const TAG_BIT_STRING: u8 = 0x03;
+const TAG_OCTET_STRING: u8 = 0x04;
+const TAG_OID: u8 = 0x06;
+const TAG_SEQUENCE: u8 = 0x30;
+
+/// rsaEncryption, 1.2.840.113549.1.1.1
+const RSA_ENCRYPTION: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+/// id-ecPublicKey, 1.2.840.10045.2.1
+const EC_PUBLIC_KEY: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+/// id-X25519, 1.3.101.110
+const X25519: &[u8] = &[0x2b, 0x65, 0x6e];
+/// id-Ed25519, 1.3.101.112
+const ED25519: &[u8] = &[0x2b, 0x65, 0x70];
+/// prime256v1, 1.2.840.10045.3.1.7
+const P256: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07];
+/// secp384r1, 1.3.132.0.34
+const P384: &[u8] = &[0x2b, 0x81, 0x04, 0x00, 0x22];
+/// secp521r1, 1.3.132.0.35
+const P521: &[u8] = &[0x2b, 0x81, 0x04, 0x00, 0x23];
+
+/// Algorithm a SPKI or PKCS#8 key is expected to be of
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum KeyAlgorithm {
+    Rsa,
+    Ec(NamedCurve),
+    X25519,
+    Ed25519,
+}
+
+impl KeyAlgorithm {
+    fn oid(&self) -> &'static [u8] {
+        match self {
+            Self::Rsa => RSA_ENCRYPTION,
+            Self::Ec(_) => EC_PUBLIC_KEY,
+            Self::X25519 => X25519,
+            Self::Ed25519 => ED25519,
+        }
+    }
+}
+
+fn curve_oid(curve: NamedCurve) -> &'static [u8] {
+    match curve {
+        NamedCurve::P256 => P256,
+        NamedCurve::P384 => P384,
+        NamedCurve::P521 => P521,
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Reads an element with the given tag and returns its content
+    fn read(&mut self, tag: u8) -> Option<&'a [u8]> {
+        let (&found, rest) = self.data.split_first()?;
+        if found != tag {
+            return None;
+        }
+        let (&first, mut rest) = rest.split_first()?;
+        let length = match first {
+            0x00..=0x7f => first as usize,
+            // indefinite lengths are not allowed in DER
+            0x81..=0x84 => {
+                let count = (first & 0x7f) as usize;
+                if rest.len() < count {
+                    return None;
+                }
+                let (bytes, tail) = rest.split_at(count);
+                rest = tail;
+                bytes
+                    .iter()
+                    .fold(0usize, |acc, byte| (acc << 8) | *byte as usize)
+            }
+            _ => return None,
+        };
+        if rest.len() < length {
+            return None;
+        }
+        let (content, tail) = rest.split_at(length);
+        self.data = tail;
+        Some(content)
+    }
+}
+
+/// Reads the AlgorithmIdentifier of a SPKI or PKCS#8 key, returning its
+/// object identifier and parameters
+fn algorithm_identifier(format: KeyFormat, data: &[u8]) -> Option<(&[u8], &[u8])> {
+    let mut outer = Reader::new(data);
+    let mut info = Reader::new(outer.read(TAG_SEQUENCE)?);
+    if !outer.is_empty() {
+        return None;
+    }
+    let algorithm = match format {
+        KeyFormat::Spki => {
+            let algorithm = info.read(TAG_SEQUENCE)?;
+            info.read(TAG_BIT_STRING)?;
+            if !info.is_empty() {
+                return None;
+            }
+            algorithm
+        }
+        KeyFormat::Pkcs8 => {
+            info.read(TAG_INTEGER)?;
+            let algorithm = info.read(TAG_SEQUENCE)?;
+            info.read(TAG_OCTET_STRING)?;
+            // optional attributes and public key are left to the browser
+            algorithm
+        }
+        KeyFormat::Raw => return None,
+    };
+    let mut algorithm = Reader::new(algorithm);
+    let oid = algorithm.read(TAG_OID)?;
+    Some((oid, algorithm.data))
+}
+
+/// Formats an encoded object identifier with the dotted notation
+fn oid_to_string(oid: &[u8]) -> String {
+    let mut arcs = Vec::new();
+    let mut value: u64 = 0;
+    for byte in oid {
+        value = (value << 7) | (byte & 0x7f) as u64;
+        if byte & 0x80 == 0 {
+            if arcs.is_empty() {
+                let first = (value / 40).min(2);
+                arcs.push(first);
+                arcs.push(value - first * 40);
+            } else {
+                arcs.push(value);
+            }
+            value = 0;
+        }
+    }
+    arcs.iter()
+        .map(|arc| arc.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Checks that a DER encoded SPKI or PKCS#8 key is of the expected algorithm
+///
+/// # Errors
+/// - `ImportKeyError::MalformedDer` if the structure of the key can't be read
+/// - `ImportKeyError::UnsupportedAlgorithm` if the key is of another algorithm,
+///   or on another curve
+pub(crate) fn check(
+    format: KeyFormat,
+    data: &[u8],
+    expected: KeyAlgorithm,
+) -> Result<(), ImportKeyError> {
+    let (oid, parameters) =
+        algorithm_identifier(format, data).ok_or(ImportKeyError::MalformedDer(format))?;
+    if oid != expected.oid() {
+        return Err(ImportKeyError::UnsupportedAlgorithm(oid_to_string(oid)));
+    }
+    if let KeyAlgorithm::Ec(curve) = expected {
+        let mut parameters = Reader::new(parameters);
+        let found = parameters
+            .read(TAG_OID)
+            .ok_or(ImportKeyError::MalformedDer(format))?;
+        if found != curve_oid(curve) {
+            return Err(ImportKeyError::UnsupportedAlgorithm(oid_to_string(found)));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,263 @@
+//! Key derivation errors and helpers
+//!
+//! These are shared by the derivation algorithms, like [`crate::pbkdf2`].
+//!
+//! [`derive_bits`] gives access to any derivation supported by the Web Crypto
+//! API, for the ones not having a dedicated wrapper yet.
+//!
+//! ```rust,no_run
+//! use browser_crypto::derive::{derive_bits, import_base_key};
+//! use wasm_bindgen::JsValue;
+//!
+//! async fn hkdf(secret: &[u8]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+//!     let base_key = import_base_key("HKDF", secret).await?;
+//!     let params = js_sys::Object::new();
+//!     js_sys::Reflect::set(&params, &"name".into(), &"HKDF".into()).unwrap();
+//!     js_sys::Reflect::set(&params, &"hash".into(), &"SHA-256".into()).unwrap();
+//!     js_sys::Reflect::set(
+//!         &params,
+//!         &"salt".into(),
+//!         &js_sys::Uint8Array::new_with_length(0),
+//!     )
+//!     .unwrap();
+//!     js_sys::Reflect::set(
+//!         &params,
+//!         &"info".into(),
+//!         &js_sys::Uint8Array::new_with_length(0),
+//!     )
+//!     .unwrap();
+//!     Ok(derive_bits(&params, &base_key, 256).await?)
+//! }
+//! ```
+
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::DomException;
+
+use crate::context::Operation;
+use crate::input::SecretInput;
+use crate::key::ImportKeyError;
+use crate::ErrorCode;
+
+/// Errors that can occur during key or bits derivation operations.
+///
+/// These errors map to the exceptions defined in the Web Crypto API
+/// specification for derivation operations.
+///
+/// See [MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto/deriveBits#exceptions)
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum DeriveError {
+    /// Indicates that the requested operation is not valid for the provided
+    /// key. This typically occurs when:
+    /// - The base key is not a derivation key
+    /// - The base key's usages don't include "deriveBits" or "deriveKey"
+    #[error("requested operation is not valid for the provided key")]
+    InvalidAccess,
+    /// Indicates that the derivation algorithm is not supported by the browser.
+    #[error("the algorithm is not supported")]
+    NotSupported,
+    /// Indicates that the operation failed for an algorithm-specific reason.
+    /// This can occur when:
+    /// - The requested length is not a multiple of 8
+    /// - The iteration count is zero
+    #[error("operation failed for an operation-specific reason")]
+    Operation,
+    /// A wrapper for other types of errors that may occur during derivation
+    #[error(transparent)]
+    Generic(#[from] crate::Error),
+}
+
+impl ErrorCode for DeriveError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidAccess => "derive.invalid_access",
+            Self::NotSupported => "derive.not_supported",
+            Self::Operation => "derive.operation",
+            Self::Generic(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<DeriveError> for JsValue {
+    fn from(value: DeriveError) -> Self {
+        crate::js_error("DeriveError", &value)
+    }
+}
+
+impl From<JsValue> for DeriveError {
+    fn from(value: JsValue) -> Self {
+        if let Some(exception) = value.dyn_ref::<DomException>() {
+            match exception.name().as_str() {
+                "InvalidAccessError" => {
+                    return Self::InvalidAccess;
+                }
+                "NotSupportedError" => {
+                    return Self::NotSupported;
+                }
+                "OperationError" => {
+                    return Self::Operation;
+                }
+                _ => {}
+            }
+        }
+        Self::Generic(crate::Error::from(value))
+    }
+}
+
+/// Imports raw key material as a base key for [`derive_bits`]
+///
+/// The key is not extractable and can only be used to derive bits or keys.
+///
+/// # Arguments
+/// * `name` - Name of the derivation algorithm, like "HKDF" or "PBKDF2"
+/// * `data` - Raw key material
+///
+/// # Returns
+/// Result containing the imported CryptoKey or an ImportKeyError
+///
+/// # Errors
+/// - `ImportKeyError::NotSupported` if the algorithm is not a derivation
+///   algorithm supported by the browser
+pub async fn import_base_key(
+    name: &str,
+    data: &(impl SecretInput + ?Sized),
+) -> Result<web_sys::CryptoKey, ImportKeyError> {
+    crate::key::import_base_key(name, data.expose_bytes()).await
+}
+
+/// Derives bits from a base key, with any derivation algorithm supported by the
+/// Web Crypto API
+///
+/// # Arguments
+/// * `algorithm` - Derivation algorithm parameters, like `HkdfParams`,
+///   `Pbkdf2Params` or `EcdhKeyDeriveParams`
+/// * `base_key` - Key to derive the bits from, its algorithm must match the
+///   parameters and its usages must include "deriveBits"
+/// * `length` - Number of bits to derive, should be a multiple of 8
+///
+/// # Returns
+/// Result containing the derived bytes or a DeriveError
+///
+/// # Errors
+/// - `DeriveError::InvalidAccess` if the base key can't be used with these
+///   parameters
+/// - `DeriveError::NotSupported` if the algorithm is not supported
+/// - `DeriveError::Operation` if the length is invalid for the algorithm
+pub async fn derive_bits(
+    algorithm: &js_sys::Object,
+    base_key: &web_sys::CryptoKey,
+    length: u32,
+) -> Result<Vec<u8>, DeriveError> {
+    let name = crate::instrument::algorithm_name(algorithm);
+    let bits = crate::instrument::observe(Operation::DeriveBits, &name, None, async {
+        let subtle = crate::subtle()?;
+        let promise: js_sys::Promise =
+            subtle.derive_bits_with_object(algorithm, base_key, length)?;
+        crate::resolve::<js_sys::ArrayBuffer, DeriveError>(promise).await
+    })
+    .await?;
+
+    Ok(crate::secret_array_to_vec(&js_sys::Uint8Array::new(&bits)))
+}
+
+/// Derives a secret key from a base key
+///
+/// # Arguments
+/// * `algorithm` - Derivation algorithm details
+/// * `base_key` - Key to derive the secret key from
+/// * `extractable` - Whether the derived key can be exported
+///
+/// # Returns
+/// Result containing the derived key or a DeriveError
+pub(crate) async fn derive_key<K>(
+    algorithm: &js_sys::Object,
+    base_key: &web_sys::CryptoKey,
+    extractable: bool,
+) -> Result<K, DeriveError>
+where
+    K: crate::key::private::Sealed,
+{
+    let key = derive_crypto_key(
+        algorithm,
+        base_key,
+        &K::import_algorithm()?,
+        extractable,
+        K::usages(),
+    )
+    .await?;
+
+    // the derived key type is provided by K, so it always matches
+    K::from_crypto_key(key).ok_or(DeriveError::Operation)
+}
+
+/// Derives a CryptoKey from a base key
+///
+/// # Arguments
+/// * `algorithm` - Derivation algorithm details
+/// * `base_key` - Key to derive the secret key from
+/// * `derived_algorithm` - Algorithm details of the derived key
+/// * `extractable` - Whether the derived key can be exported
+/// * `usages` - Usages of the derived key
+///
+/// # Returns
+/// Result containing the derived CryptoKey or a DeriveError
+pub(crate) async fn derive_crypto_key(
+    algorithm: &js_sys::Object,
+    base_key: &web_sys::CryptoKey,
+    derived_algorithm: &js_sys::Object,
+    extractable: bool,
+    usages: &[&str],
+) -> Result<web_sys::CryptoKey, DeriveError> {
+    let name = crate::instrument::algorithm_name(algorithm);
+    crate::instrument::observe(Operation::DeriveKey, &name, None, async {
+        let subtle = crate::subtle()?;
+        let promise: js_sys::Promise = subtle.derive_key_with_object_and_object(
+            algorithm,
+            base_key,
+            derived_algorithm,
+            extractable,
+            &crate::key::usages_array(usages),
+        )?;
+        crate::resolve::<web_sys::CryptoKey, DeriveError>(promise).await
+    })
+    .await
+}
+
+pub(crate) mod private {
+    /// Plumbing to derive keys with the Web Crypto API, not meant to be
+    /// implemented outside of this crate.
+    pub trait Sealed {
+        /// Derivation algorithm parameters
+        fn derive_params(&self) -> Result<js_sys::Object, wasm_bindgen::JsValue>;
+        /// Key to derive from
+        fn base_key(&self) -> &web_sys::CryptoKey;
+    }
+}
+
+/// Key derivation algorithms able to derive secret keys directly in the
+/// browser, like [`crate::hkdf::Hkdf`] or [`crate::pbkdf2::Pbkdf2`].
+///
+/// See [`crate::aes_gcm::AesGcm::derive_from`].
+pub trait KeyDerivation: private::Sealed {}
+
+/// Computes the shared secret of a key agreement, like ECDH or X25519
+///
+/// # Arguments
+/// * `name` - Name of the key agreement algorithm
+/// * `private_key` - Own private key, its usages must include "deriveBits"
+/// * `public_key` - Public key of the peer, of the same algorithm and curve
+/// * `length` - Size of the shared secret in bits
+///
+/// # Returns
+/// Result containing the shared secret or a DeriveError
+pub(crate) async fn key_agreement(
+    name: &str,
+    private_key: &web_sys::CryptoKey,
+    public_key: &web_sys::CryptoKey,
+    length: u32,
+) -> Result<Vec<u8>, DeriveError> {
+    let algorithm = js_sys::Object::new();
+    js_sys::Reflect::set(&algorithm, &"name".into(), &name.into())?;
+    js_sys::Reflect::set(&algorithm, &"public".into(), public_key)?;
+    derive_bits(&algorithm, private_key, length).await
+}
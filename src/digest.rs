@@ -0,0 +1,241 @@
+//! Compatibility with the `digest` crate
+//!
+//! [`BufferedDigest`] mirrors the interface of the `digest::Digest` trait, the
+//! one of the RustCrypto hash functions: it buffers the hashed data, then
+//! computes the digest with `SubtleCrypto.digest`. The Web Crypto API only
+//! returns promises, so the finalization is async and can fail, where
+//! `digest::Digest` is synchronous: moving code from `sha2::Sha256` to
+//! [`Sha256`] means changing the import and awaiting the digest.
+//!
+//! The buffering side implements the `digest` traits, like `digest::Update`,
+//! so code feeding data to a hash function through them works unchanged. To
+//! hash large inputs without holding them in memory, see the `Hasher` of the
+//! `incremental-digest` feature.
+//!
+//! Requires the `digest` feature.
+//!
+//! ```rust,no_run
+//! use browser_crypto::digest::Sha256;
+//!
+//! async fn digest() -> Result<(), browser_crypto::Error> {
+//!     let mut hasher = Sha256::new();
+//!     hasher.update(b"Hello ");
+//!     hasher.update(b"World!");
+//!     let digest = hasher.finalize().await?;
+//!     assert_eq!(digest, Sha256::digest(b"Hello World!").await?);
+//!     Ok(())
+//! }
+//! ```
+
+use std::marker::PhantomData;
+
+use ::digest::consts::{U20, U32, U48, U64};
+use ::digest::generic_array::{ArrayLength, GenericArray};
+use ::digest::{HashMarker, Output, OutputSizeUser, Reset, Update};
+
+use crate::hash::Hash;
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Hash function of a [`BufferedDigest`], not meant to be implemented outside
+/// of this crate
+pub trait HashFunction: private::Sealed {
+    /// Hash function of the Web Crypto API
+    const HASH: Hash;
+
+    /// Size of the digest in bytes
+    type OutputSize: ArrayLength<u8> + 'static;
+}
+
+/// SHA-1 hash function, see [`Hash::Sha1`]
+#[derive(Debug, Clone, Copy)]
+pub struct Sha1Function;
+
+/// SHA-256 hash function
+#[derive(Debug, Clone, Copy)]
+pub struct Sha256Function;
+
+/// SHA-384 hash function
+#[derive(Debug, Clone, Copy)]
+pub struct Sha384Function;
+
+/// SHA-512 hash function
+#[derive(Debug, Clone, Copy)]
+pub struct Sha512Function;
+
+impl private::Sealed for Sha1Function {}
+impl private::Sealed for Sha256Function {}
+impl private::Sealed for Sha384Function {}
+impl private::Sealed for Sha512Function {}
+
+impl HashFunction for Sha1Function {
+    const HASH: Hash = Hash::Sha1;
+    type OutputSize = U20;
+}
+
+impl HashFunction for Sha256Function {
+    const HASH: Hash = Hash::Sha256;
+    type OutputSize = U32;
+}
+
+impl HashFunction for Sha384Function {
+    const HASH: Hash = Hash::Sha384;
+    type OutputSize = U48;
+}
+
+impl HashFunction for Sha512Function {
+    const HASH: Hash = Hash::Sha512;
+    type OutputSize = U64;
+}
+
+/// Hash function buffering its input, computing the digest with the Web Crypto
+/// API
+///
+/// Use one of the [`Sha1`], [`Sha256`], [`Sha384`] or [`Sha512`] aliases.
+pub struct BufferedDigest<H> {
+    buffer: Vec<u8>,
+    function: PhantomData<H>,
+}
+
+/// SHA-1 hash function, only meant for interoperability with legacy systems
+pub type Sha1 = BufferedDigest<Sha1Function>;
+
+/// SHA-256 hash function
+pub type Sha256 = BufferedDigest<Sha256Function>;
+
+/// SHA-384 hash function
+pub type Sha384 = BufferedDigest<Sha384Function>;
+
+/// SHA-512 hash function
+pub type Sha512 = BufferedDigest<Sha512Function>;
+
+impl<H: HashFunction> BufferedDigest<H> {
+    /// Starts hashing
+    pub fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            function: PhantomData,
+        }
+    }
+
+    /// Starts hashing with some data
+    pub fn new_with_prefix(data: impl AsRef<[u8]>) -> Self {
+        Self::new().chain_update(data)
+    }
+
+    /// Hash function of the Web Crypto API
+    pub fn hash(&self) -> Hash {
+        H::HASH
+    }
+
+    /// Buffers some data
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.buffer.extend_from_slice(data.as_ref());
+    }
+
+    /// Buffers some data, in a chained manner
+    pub fn chain_update(mut self, data: impl AsRef<[u8]>) -> Self {
+        self.update(data);
+        self
+    }
+
+    /// Computes the digest of the buffered data
+    ///
+    /// # Returns
+    /// Result containing the digest or an Error
+    ///
+    /// # Errors
+    /// Same as [`Hash::digest`]
+    pub async fn finalize(self) -> Result<Output<Self>, crate::Error> {
+        digest::<H>(&self.buffer).await
+    }
+
+    /// Computes the digest of the buffered data, emptying the buffer
+    ///
+    /// # Errors
+    /// Same as [`Hash::digest`], the buffer being kept
+    pub async fn finalize_reset(&mut self) -> Result<Output<Self>, crate::Error> {
+        let output = digest::<H>(&self.buffer).await?;
+        self.buffer.clear();
+        Ok(output)
+    }
+
+    /// Empties the buffer
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+    }
+
+    /// Size of the digest in bytes
+    pub fn output_size() -> usize {
+        H::HASH.output_size()
+    }
+
+    /// Computes the digest of some data
+    ///
+    /// # Errors
+    /// Same as [`Hash::digest`]
+    pub async fn digest(data: impl AsRef<[u8]>) -> Result<Output<Self>, crate::Error> {
+        digest::<H>(data.as_ref()).await
+    }
+}
+
+async fn digest<H: HashFunction>(
+    data: &[u8],
+) -> Result<GenericArray<u8, H::OutputSize>, crate::Error> {
+    let output = H::HASH.digest(data).await?;
+    Ok(GenericArray::clone_from_slice(&output))
+}
+
+impl<H: HashFunction> Default for BufferedDigest<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H> Clone for BufferedDigest<H> {
+    fn clone(&self) -> Self {
+        Self {
+            buffer: self.buffer.clone(),
+            function: PhantomData,
+        }
+    }
+}
+
+impl<H: HashFunction> std::fmt::Debug for BufferedDigest<H> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BufferedDigest")
+            .field("hash", &H::HASH)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<H: HashFunction> Update for BufferedDigest<H> {
+    fn update(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+}
+
+impl<H: HashFunction> OutputSizeUser for BufferedDigest<H> {
+    type OutputSize = H::OutputSize;
+}
+
+impl<H: HashFunction> Reset for BufferedDigest<H> {
+    fn reset(&mut self) {
+        self.buffer.clear();
+    }
+}
+
+impl<H: HashFunction> HashMarker for BufferedDigest<H> {}
+
+impl<H: HashFunction> std::io::Write for BufferedDigest<H> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
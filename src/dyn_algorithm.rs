@@ -0,0 +1,148 @@
+//! Ciphers chosen at runtime
+//!
+//! [`Algorithm`] returns its futures as `impl Future` and takes nonces typed
+//! by the algorithm, so it can't be used as a trait object: the cipher has to
+//! be known at compile time. [`DynAlgorithm`] is its object safe companion,
+//! boxing the futures and taking the nonces as bytes, so an application can
+//! hold ciphers picked from its configuration as `Box<dyn DynAlgorithm>`.
+//!
+//! It's implemented by every [`Algorithm`] returning the errors of this crate,
+//! the trait objects having to share their error types. Its methods have the
+//! same names, so a concrete cipher should be used through [`Algorithm`] and
+//! only the trait objects through [`DynAlgorithm`], importing one of them in a
+//! module.
+//!
+//! The futures are not `Send`, like the promises of the Web Crypto API.
+//!
+//! ```rust,no_run
+//! use browser_crypto::aes_gcm::{Aes128Gcm, Aes256Gcm};
+//! use browser_crypto::dyn_algorithm::DynAlgorithm;
+//!
+//! async fn cipher(
+//!     name: &str,
+//!     key: &[u8],
+//! ) -> Result<Box<dyn DynAlgorithm>, Box<dyn std::error::Error>> {
+//!     Ok(match name {
+//!         "A128GCM" => Box::new(Aes128Gcm::from_key(key).await?),
+//!         _ => Box::new(Aes256Gcm::from_key(key).await?),
+//!     })
+//! }
+//!
+//! async fn encrypt(cipher: &dyn DynAlgorithm) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+//!     Ok(cipher.seal(b"Hello World!").await?)
+//! }
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::algorithm::{Algorithm, DecryptionError, EncryptionError, Nonce};
+
+/// Future returned by the methods of [`DynAlgorithm`]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// Object safe companion of [`Algorithm`]
+pub trait DynAlgorithm {
+    /// Name of the algorithm, see [`Algorithm::NAME`]
+    fn name(&self) -> &'static str;
+
+    /// Required nonce size in bytes, see [`Algorithm::NONCE_SIZE`]
+    fn nonce_size(&self) -> u32;
+
+    /// Size in bytes of the raw keys, see [`Algorithm::KEY_SIZE`]
+    fn key_size(&self) -> u32;
+
+    /// Encrypts data, see [`Algorithm::encrypt`]
+    ///
+    /// # Arguments
+    /// * `nonce` - Nonce to use for encryption, of [`DynAlgorithm::nonce_size`]
+    ///   bytes
+    /// * `payload` - Data to encrypt
+    ///
+    /// # Errors
+    /// - `EncryptionError::Nonce` if the nonce doesn't have the expected size
+    /// - Any error of [`Algorithm::encrypt`]
+    fn encrypt<'a>(
+        &'a self,
+        nonce: &'a [u8],
+        payload: &'a [u8],
+    ) -> BoxFuture<'a, Result<Vec<u8>, EncryptionError>>;
+
+    /// Decrypts data, see [`Algorithm::decrypt`]
+    ///
+    /// # Arguments
+    /// * `nonce` - Nonce used for encryption, of [`DynAlgorithm::nonce_size`]
+    ///   bytes
+    /// * `payload` - Encrypted data to decrypt
+    ///
+    /// # Errors
+    /// - `DecryptionError::Operation` if the nonce doesn't have the expected
+    ///   size
+    /// - Any error of [`Algorithm::decrypt`]
+    fn decrypt<'a>(
+        &'a self,
+        nonce: &'a [u8],
+        payload: &'a [u8],
+    ) -> BoxFuture<'a, Result<Vec<u8>, DecryptionError>>;
+
+    /// Encrypts data with a random nonce, prepended to the encrypted data,
+    /// see [`Algorithm::seal`]
+    ///
+    /// # Errors
+    /// Same as [`Algorithm::seal`]
+    fn seal<'a>(&'a self, payload: &'a [u8]) -> BoxFuture<'a, Result<Vec<u8>, EncryptionError>>;
+
+    /// Decrypts data produced by [`DynAlgorithm::seal`], see
+    /// [`Algorithm::open`]
+    ///
+    /// # Errors
+    /// Same as [`Algorithm::open`]
+    fn open<'a>(&'a self, sealed: &'a [u8]) -> BoxFuture<'a, Result<Vec<u8>, DecryptionError>>;
+}
+
+impl<A> DynAlgorithm for A
+where
+    A: Algorithm<EncryptError = EncryptionError, DecryptError = DecryptionError>,
+{
+    fn name(&self) -> &'static str {
+        A::NAME
+    }
+
+    fn nonce_size(&self) -> u32 {
+        A::NONCE_SIZE
+    }
+
+    fn key_size(&self) -> u32 {
+        A::KEY_SIZE
+    }
+
+    fn encrypt<'a>(
+        &'a self,
+        nonce: &'a [u8],
+        payload: &'a [u8],
+    ) -> BoxFuture<'a, Result<Vec<u8>, EncryptionError>> {
+        Box::pin(async move {
+            let nonce = Nonce::<A>::from_slice(nonce)?;
+            Algorithm::encrypt(self, &nonce, payload).await
+        })
+    }
+
+    fn decrypt<'a>(
+        &'a self,
+        nonce: &'a [u8],
+        payload: &'a [u8],
+    ) -> BoxFuture<'a, Result<Vec<u8>, DecryptionError>> {
+        Box::pin(async move {
+            let nonce = Nonce::<A>::from_slice(nonce).map_err(|_| DecryptionError::Operation)?;
+            Algorithm::decrypt(self, &nonce, payload).await
+        })
+    }
+
+    fn seal<'a>(&'a self, payload: &'a [u8]) -> BoxFuture<'a, Result<Vec<u8>, EncryptionError>> {
+        Box::pin(Algorithm::seal(self, payload))
+    }
+
+    fn open<'a>(&'a self, sealed: &'a [u8]) -> BoxFuture<'a, Result<Vec<u8>, DecryptionError>> {
+        Box::pin(Algorithm::open(self, sealed))
+    }
+}
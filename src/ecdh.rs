@@ -0,0 +1,269 @@
+//! ECDH key agreement implementation
+//!
+//! Both parties combine their private key with the public key of the other one
+//! to compute the same shared secret. The shared secret is the `x` coordinate
+//! of the resulting point, it's not uniformly random and should go through a
+//! key derivation like [`crate::hkdf`] before being used as a key.
+//!
+//! ```rust,no_run
+//! use browser_crypto::curve::NamedCurve;
+//! use browser_crypto::ecdh::EcdhKeyPair;
+//!
+//! async fn agree() -> Result<(), Box<dyn std::error::Error>> {
+//!     let alice = EcdhKeyPair::generate(NamedCurve::P256).await?;
+//!     let bob = EcdhKeyPair::generate(NamedCurve::P256).await?;
+//!     let left = alice.private_key.diffie_hellman(&bob.public_key).await?;
+//!     let right = bob.private_key.diffie_hellman(&alice.public_key).await?;
+//!     assert_eq!(left, right);
+//!     Ok(())
+//! }
+//! ```
+
+use crate::curve::NamedCurve;
+use crate::der::KeyAlgorithm;
+use crate::derive::DeriveError;
+use crate::input::SecretInput;
+use crate::jwk::Jwk;
+use crate::key::{ExportKeyError, GenerateKeyError, ImportKeyError, KeyFormat};
+use crate::key_pair::{AsymmetricAlgorithm, AsymmetricKey, KeyPair};
+
+const NAME: &str = "ECDH";
+
+fn import_algorithm(curve: NamedCurve) -> web_sys::EcKeyImportParams {
+    let algorithm = web_sys::EcKeyImportParams::new(NAME);
+    algorithm.set_named_curve(curve.name());
+    algorithm
+}
+
+/// ECDH algorithm, see [`EcdhKeyPair`]
+#[derive(Debug, Clone, Copy)]
+pub struct Ecdh;
+
+impl AsymmetricAlgorithm for Ecdh {
+    type PublicKey = EcdhPublicKey;
+    type PrivateKey = EcdhPrivateKey;
+
+    const PUBLIC_USAGES: &'static [&'static str] = &[];
+    const PRIVATE_USAGES: &'static [&'static str] = &["deriveBits"];
+}
+
+/// ECDH public key, shared with the peer
+#[derive(Debug, Clone)]
+pub struct EcdhPublicKey {
+    key: web_sys::CryptoKey,
+    curve: NamedCurve,
+}
+
+impl EcdhPublicKey {
+    /// Imports a public key from its raw uncompressed point.
+    ///
+    /// # Arguments
+    /// * `data` - Uncompressed point, `0x04` followed by the `x` and `y`
+    ///   coordinates
+    /// * `curve` - Curve of the key
+    ///
+    /// # Returns
+    /// Result containing the public key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::InvalidKeyFormat` if the data is not a valid point on
+    ///   the given curve
+    pub async fn from_raw(data: &[u8], curve: NamedCurve) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let key =
+            crate::key::import_key(KeyFormat::Raw, &import_algorithm(curve), data, &[]).await?;
+        Ok(Self { key, curve })
+    }
+
+    /// Imports a public key in the SubjectPublicKeyInfo format.
+    ///
+    /// # Arguments
+    /// * `data` - DER encoded SubjectPublicKeyInfo
+    /// * `curve` - Curve of the key
+    ///
+    /// # Returns
+    /// Result containing the public key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::MalformedDer` if the data is not a DER encoded
+    ///   SubjectPublicKeyInfo
+    /// - `ImportKeyError::UnsupportedAlgorithm` if the key is not an EC key on
+    ///   the given curve
+    pub async fn from_spki(data: &[u8], curve: NamedCurve) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let key = crate::key::import_der(
+            KeyFormat::Spki,
+            &import_algorithm(curve),
+            data,
+            KeyAlgorithm::Ec(curve),
+            &[],
+        )
+        .await?;
+        Ok(Self { key, curve })
+    }
+
+    /// Imports a public key in the JSON Web Key format.
+    ///
+    /// # Arguments
+    /// * `jwk` - JSON Web Key, with `kty` set to `EC` and `crv` matching the
+    ///   curve
+    /// * `curve` - Curve of the key
+    ///
+    /// # Returns
+    /// Result containing the public key or an ImportKeyError
+    pub async fn from_jwk(jwk: &Jwk, curve: NamedCurve) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let key = crate::key::import_jwk(&import_algorithm(curve), jwk, &[]).await?;
+        Ok(Self { key, curve })
+    }
+
+    /// Exports the public key as a raw uncompressed point.
+    pub async fn to_raw(&self) -> Result<Vec<u8>, ExportKeyError> {
+        crate::key::export_key(KeyFormat::Raw, &self.key).await
+    }
+
+    /// Exports the public key in the SubjectPublicKeyInfo format.
+    pub async fn to_spki(&self) -> Result<Vec<u8>, ExportKeyError> {
+        crate::key::export_key(KeyFormat::Spki, &self.key).await
+    }
+
+    /// Exports the public key in the JSON Web Key format.
+    pub async fn to_jwk(&self) -> Result<Jwk, ExportKeyError> {
+        crate::key::export_jwk(&self.key).await
+    }
+
+    /// Curve of the key
+    pub fn curve(&self) -> NamedCurve {
+        self.curve
+    }
+}
+
+impl crate::key_pair::private::Sealed for EcdhPublicKey {
+    fn crypto_key(&self) -> &web_sys::CryptoKey {
+        &self.key
+    }
+}
+
+impl AsymmetricKey for EcdhPublicKey {}
+
+/// ECDH private key, used to compute shared secrets
+#[derive(Debug, Clone)]
+pub struct EcdhPrivateKey {
+    key: web_sys::CryptoKey,
+    curve: NamedCurve,
+}
+
+impl EcdhPrivateKey {
+    /// Imports a private key in the PKCS#8 format.
+    ///
+    /// # Arguments
+    /// * `data` - DER encoded PKCS#8 private key
+    /// * `curve` - Curve of the key
+    ///
+    /// # Returns
+    /// Result containing the private key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::MalformedDer` if the data is not a DER encoded PKCS#8
+    ///   key
+    /// - `ImportKeyError::UnsupportedAlgorithm` if the key is not an EC key on
+    ///   the given curve
+    pub async fn from_pkcs8(
+        data: &(impl SecretInput + ?Sized),
+        curve: NamedCurve,
+    ) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let key = crate::key::import_der(
+            KeyFormat::Pkcs8,
+            &import_algorithm(curve),
+            data.expose_bytes(),
+            KeyAlgorithm::Ec(curve),
+            Ecdh::PRIVATE_USAGES,
+        )
+        .await?;
+        Ok(Self { key, curve })
+    }
+
+    /// Imports a private key in the JSON Web Key format.
+    ///
+    /// # Arguments
+    /// * `jwk` - JSON Web Key, with `kty` set to `EC`, `crv` matching the curve
+    ///   and `d` defined
+    /// * `curve` - Curve of the key
+    ///
+    /// # Returns
+    /// Result containing the private key or an ImportKeyError
+    pub async fn from_jwk(jwk: &Jwk, curve: NamedCurve) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let key =
+            crate::key::import_jwk(&import_algorithm(curve), jwk, Ecdh::PRIVATE_USAGES).await?;
+        Ok(Self { key, curve })
+    }
+
+    /// Exports the private key in the PKCS#8 format.
+    pub async fn to_pkcs8(&self) -> Result<Vec<u8>, ExportKeyError> {
+        crate::key::export_key(KeyFormat::Pkcs8, &self.key).await
+    }
+
+    /// Exports the private key in the JSON Web Key format.
+    pub async fn to_jwk(&self) -> Result<Jwk, ExportKeyError> {
+        crate::key::export_jwk(&self.key).await
+    }
+
+    /// Curve of the key
+    pub fn curve(&self) -> NamedCurve {
+        self.curve
+    }
+
+    /// Computes the shared secret with the public key of the peer
+    ///
+    /// # Arguments
+    /// * `public_key` - Public key of the peer, on the same curve
+    ///
+    /// # Returns
+    /// Result containing the shared secret, of the curve field size, or a
+    /// DeriveError
+    ///
+    /// # Errors
+    /// - `DeriveError::InvalidAccess` if the public key is on another curve
+    pub async fn diffie_hellman(&self, public_key: &EcdhPublicKey) -> Result<Vec<u8>, DeriveError> {
+        let length = (self.curve.field_size() * 8) as u32;
+        crate::derive::key_agreement(NAME, &self.key, &public_key.key, length).await
+    }
+}
+
+impl crate::key_pair::private::Sealed for EcdhPrivateKey {
+    fn crypto_key(&self) -> &web_sys::CryptoKey {
+        &self.key
+    }
+}
+
+impl AsymmetricKey for EcdhPrivateKey {}
+
+/// ECDH key pair
+pub type EcdhKeyPair = KeyPair<Ecdh>;
+
+impl KeyPair<Ecdh> {
+    /// Generates a new key pair.
+    ///
+    /// # Arguments
+    /// * `curve` - Curve of the keys
+    ///
+    /// # Returns
+    /// Result containing the key pair or a GenerateKeyError
+    pub async fn generate(curve: NamedCurve) -> Result<Self, GenerateKeyError> {
+        crate::check_target()?;
+        let algorithm = web_sys::EcKeyGenParams::new(NAME, curve.name());
+        let (public_key, private_key) = crate::key_pair::generate::<Ecdh>(&algorithm).await?;
+        Ok(Self::new(
+            EcdhPublicKey {
+                key: public_key,
+                curve,
+            },
+            EcdhPrivateKey {
+                key: private_key,
+                curve,
+            },
+        ))
+    }
+}
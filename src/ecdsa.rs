@@ -0,0 +1,386 @@
+//! ECDSA signature implementation
+//!
+//! Signatures are encoded as the concatenation of `r` and `s` (IEEE P1363),
+//! which is the format used by JWS (ES256, ES384 and ES512).
+//!
+//! ```rust,no_run
+//! use browser_crypto::curve::NamedCurve;
+//! use browser_crypto::ecdsa::EcdsaKeyPair;
+//! use browser_crypto::hash::Hash;
+//!
+//! async fn sign() -> Result<(), Box<dyn std::error::Error>> {
+//!     let pair = EcdsaKeyPair::generate(NamedCurve::P256, Hash::Sha256).await?;
+//!     let signature = pair.private_key.sign(b"Hello World!").await?;
+//!     assert!(pair.public_key.verify(&signature, b"Hello World!").await?);
+//!     Ok(())
+//! }
+//! ```
+
+use crate::curve::NamedCurve;
+use crate::der::KeyAlgorithm;
+use crate::hash::Hash;
+use crate::input::SecretInput;
+use crate::jwk::Jwk;
+use crate::key::{ExportKeyError, GenerateKeyError, ImportKeyError, KeyFormat};
+use crate::key_pair::{AsymmetricAlgorithm, AsymmetricKey, KeyPair};
+use crate::signature::{SignError, SignatureAlgorithm, VerifyError};
+
+const NAME: &str = "ECDSA";
+
+fn import_algorithm(curve: NamedCurve) -> web_sys::EcKeyImportParams {
+    let algorithm = web_sys::EcKeyImportParams::new(NAME);
+    algorithm.set_named_curve(curve.name());
+    algorithm
+}
+
+fn signature_algorithm(hash: Hash) -> web_sys::EcdsaParams {
+    web_sys::EcdsaParams::new(NAME, &hash.name().into())
+}
+
+/// Reads the curve of a stored key
+#[cfg(feature = "keystore")]
+fn key_curve(key: &web_sys::CryptoKey) -> Result<NamedCurve, crate::keystore::KeystoreError> {
+    crate::key::key_algorithm_member(key, "namedCurve")
+        .and_then(|value| value.as_string())
+        .and_then(|name| NamedCurve::from_name(&name))
+        .ok_or(crate::keystore::KeystoreError::InvalidEntry)
+}
+
+/// ECDSA algorithm, see [`EcdsaKeyPair`]
+#[derive(Debug, Clone, Copy)]
+pub struct Ecdsa;
+
+impl AsymmetricAlgorithm for Ecdsa {
+    type PublicKey = EcdsaPublicKey;
+    type PrivateKey = EcdsaPrivateKey;
+
+    const PUBLIC_USAGES: &'static [&'static str] = &["verify"];
+    const PRIVATE_USAGES: &'static [&'static str] = &["sign"];
+}
+
+impl SignatureAlgorithm for Ecdsa {
+    const NAME: &'static str = NAME;
+    const SIGNATURE_SIZE: Option<usize> = None;
+    type SigningKey = EcdsaPrivateKey;
+    type VerifyingKey = EcdsaPublicKey;
+
+    async fn sign(key: &EcdsaPrivateKey, payload: &[u8]) -> Result<Vec<u8>, SignError> {
+        key.sign(payload).await
+    }
+
+    async fn verify(
+        key: &EcdsaPublicKey,
+        signature: &[u8],
+        payload: &[u8],
+    ) -> Result<bool, VerifyError> {
+        key.verify(signature, payload).await
+    }
+}
+
+/// ECDSA public key, used for verification
+#[derive(Debug, Clone)]
+pub struct EcdsaPublicKey {
+    key: web_sys::CryptoKey,
+    curve: NamedCurve,
+    hash: Hash,
+}
+
+impl EcdsaPublicKey {
+    /// Imports a public key in the SubjectPublicKeyInfo format.
+    ///
+    /// # Arguments
+    /// * `data` - DER encoded SubjectPublicKeyInfo
+    /// * `curve` - Curve of the key
+    /// * `hash` - Hash function used for verifying
+    ///
+    /// # Returns
+    /// Result containing the public key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::MalformedDer` if the data is not a DER encoded
+    ///   SubjectPublicKeyInfo
+    /// - `ImportKeyError::UnsupportedAlgorithm` if the key is not an EC key on
+    ///   the given curve
+    /// - `ImportKeyError::InvalidKeyFormat` if the data is not a valid EC
+    ///   public key on the given curve
+    pub async fn from_spki(
+        data: &[u8],
+        curve: NamedCurve,
+        hash: Hash,
+    ) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let key = crate::key::import_der(
+            KeyFormat::Spki,
+            &import_algorithm(curve),
+            data,
+            KeyAlgorithm::Ec(curve),
+            &["verify"],
+        )
+        .await?;
+        Ok(Self { key, curve, hash })
+    }
+
+    /// Imports a public key from its raw uncompressed point.
+    ///
+    /// # Arguments
+    /// * `data` - Uncompressed point, `0x04` followed by the `x` and `y`
+    ///   coordinates
+    /// * `curve` - Curve of the key
+    /// * `hash` - Hash function used for verifying
+    ///
+    /// # Returns
+    /// Result containing the public key or an ImportKeyError
+    pub async fn from_raw(
+        data: &[u8],
+        curve: NamedCurve,
+        hash: Hash,
+    ) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let key =
+            crate::key::import_key(KeyFormat::Raw, &import_algorithm(curve), data, &["verify"])
+                .await?;
+        Ok(Self { key, curve, hash })
+    }
+
+    /// Imports a public key in the JSON Web Key format.
+    ///
+    /// # Arguments
+    /// * `jwk` - JSON Web Key, with `kty` set to `EC` and `crv` matching the
+    ///   curve
+    /// * `curve` - Curve of the key
+    /// * `hash` - Hash function used for verifying
+    ///
+    /// # Returns
+    /// Result containing the public key or an ImportKeyError
+    pub async fn from_jwk(
+        jwk: &Jwk,
+        curve: NamedCurve,
+        hash: Hash,
+    ) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let key = crate::key::import_jwk(&import_algorithm(curve), jwk, &["verify"]).await?;
+        Ok(Self { key, curve, hash })
+    }
+
+    /// Exports the public key in the SubjectPublicKeyInfo format.
+    pub async fn to_spki(&self) -> Result<Vec<u8>, ExportKeyError> {
+        crate::key::export_key(KeyFormat::Spki, &self.key).await
+    }
+
+    /// Exports the public key in the JSON Web Key format.
+    ///
+    /// # Returns
+    /// Result containing the JSON Web Key or an ExportKeyError
+    pub async fn to_jwk(&self) -> Result<Jwk, ExportKeyError> {
+        crate::key::export_jwk(&self.key).await
+    }
+
+    /// Exports the public key as a raw uncompressed point.
+    pub async fn to_raw(&self) -> Result<Vec<u8>, ExportKeyError> {
+        crate::key::export_key(KeyFormat::Raw, &self.key).await
+    }
+
+    /// Curve of the key
+    pub fn curve(&self) -> NamedCurve {
+        self.curve
+    }
+
+    /// Hash function used for verifying
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    /// Verifies the signature of a payload
+    ///
+    /// # Arguments
+    /// * `signature` - Signature to verify, in the IEEE P1363 format
+    /// * `payload` - Data that was signed
+    ///
+    /// # Returns
+    /// Result containing whether the signature is valid or a VerifyError
+    pub async fn verify(&self, signature: &[u8], payload: &[u8]) -> Result<bool, VerifyError> {
+        let algorithm = signature_algorithm(self.hash);
+        crate::signature::verify(&algorithm, &self.key, signature, payload).await
+    }
+}
+
+impl crate::key_pair::private::Sealed for EcdsaPublicKey {
+    fn crypto_key(&self) -> &web_sys::CryptoKey {
+        &self.key
+    }
+}
+
+impl AsymmetricKey for EcdsaPublicKey {}
+
+#[cfg(feature = "keystore")]
+impl crate::keystore::private::Sealed for EcdsaPublicKey {
+    fn to_entry(&self) -> Result<js_sys::Object, wasm_bindgen::JsValue> {
+        crate::keystore::key_hash_entry(&self.key, self.hash)
+    }
+
+    fn from_entry(entry: &wasm_bindgen::JsValue) -> Result<Self, crate::keystore::KeystoreError> {
+        let key = crate::keystore::check_entry_key(entry, "public", NAME, Ecdsa::PUBLIC_USAGES)?;
+        let curve = key_curve(&key)?;
+        let hash = crate::keystore::entry_hash(entry)?;
+        Ok(Self { key, curve, hash })
+    }
+}
+
+#[cfg(feature = "keystore")]
+impl crate::keystore::StorableKey for EcdsaPublicKey {}
+
+/// ECDSA private key, used for signing
+#[derive(Debug, Clone)]
+pub struct EcdsaPrivateKey {
+    key: web_sys::CryptoKey,
+    curve: NamedCurve,
+    hash: Hash,
+}
+
+impl EcdsaPrivateKey {
+    /// Imports a private key in the PKCS#8 format.
+    ///
+    /// # Arguments
+    /// * `data` - DER encoded PKCS#8 private key
+    /// * `curve` - Curve of the key
+    /// * `hash` - Hash function used for signing
+    ///
+    /// # Returns
+    /// Result containing the private key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::MalformedDer` if the data is not a DER encoded PKCS#8
+    ///   key
+    /// - `ImportKeyError::UnsupportedAlgorithm` if the key is not an EC key on
+    ///   the given curve
+    /// - `ImportKeyError::InvalidKeyFormat` if the data is not a valid EC
+    ///   private key on the given curve
+    pub async fn from_pkcs8(
+        data: &(impl SecretInput + ?Sized),
+        curve: NamedCurve,
+        hash: Hash,
+    ) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let key = crate::key::import_der(
+            KeyFormat::Pkcs8,
+            &import_algorithm(curve),
+            data.expose_bytes(),
+            KeyAlgorithm::Ec(curve),
+            &["sign"],
+        )
+        .await?;
+        Ok(Self { key, curve, hash })
+    }
+
+    /// Imports a private key in the JSON Web Key format.
+    ///
+    /// # Arguments
+    /// * `jwk` - JSON Web Key, with `kty` set to `EC`, `crv` matching the curve
+    ///   and `d` defined
+    /// * `curve` - Curve of the key
+    /// * `hash` - Hash function used for signing
+    ///
+    /// # Returns
+    /// Result containing the private key or an ImportKeyError
+    pub async fn from_jwk(
+        jwk: &Jwk,
+        curve: NamedCurve,
+        hash: Hash,
+    ) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let key = crate::key::import_jwk(&import_algorithm(curve), jwk, &["sign"]).await?;
+        Ok(Self { key, curve, hash })
+    }
+
+    /// Exports the private key in the PKCS#8 format.
+    pub async fn to_pkcs8(&self) -> Result<Vec<u8>, ExportKeyError> {
+        crate::key::export_key(KeyFormat::Pkcs8, &self.key).await
+    }
+
+    /// Exports the private key in the JSON Web Key format.
+    ///
+    /// # Returns
+    /// Result containing the JSON Web Key or an ExportKeyError
+    pub async fn to_jwk(&self) -> Result<Jwk, ExportKeyError> {
+        crate::key::export_jwk(&self.key).await
+    }
+
+    /// Curve of the key
+    pub fn curve(&self) -> NamedCurve {
+        self.curve
+    }
+
+    /// Hash function used for signing
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    /// Signs a payload
+    ///
+    /// # Arguments
+    /// * `payload` - Data to sign
+    ///
+    /// # Returns
+    /// Result containing the signature, in the IEEE P1363 format, or a
+    /// SignError
+    pub async fn sign(&self, payload: &[u8]) -> Result<Vec<u8>, SignError> {
+        let algorithm = signature_algorithm(self.hash);
+        crate::signature::sign(&algorithm, &self.key, payload).await
+    }
+}
+
+impl crate::key_pair::private::Sealed for EcdsaPrivateKey {
+    fn crypto_key(&self) -> &web_sys::CryptoKey {
+        &self.key
+    }
+}
+
+impl AsymmetricKey for EcdsaPrivateKey {}
+
+#[cfg(feature = "keystore")]
+impl crate::keystore::private::Sealed for EcdsaPrivateKey {
+    fn to_entry(&self) -> Result<js_sys::Object, wasm_bindgen::JsValue> {
+        crate::keystore::key_hash_entry(&self.key, self.hash)
+    }
+
+    fn from_entry(entry: &wasm_bindgen::JsValue) -> Result<Self, crate::keystore::KeystoreError> {
+        let key = crate::keystore::check_entry_key(entry, "private", NAME, Ecdsa::PRIVATE_USAGES)?;
+        let curve = key_curve(&key)?;
+        let hash = crate::keystore::entry_hash(entry)?;
+        Ok(Self { key, curve, hash })
+    }
+}
+
+#[cfg(feature = "keystore")]
+impl crate::keystore::StorableKey for EcdsaPrivateKey {}
+
+/// ECDSA key pair, the private key signs and the public key verifies
+pub type EcdsaKeyPair = KeyPair<Ecdsa>;
+
+impl KeyPair<Ecdsa> {
+    /// Generates a new key pair.
+    ///
+    /// # Arguments
+    /// * `curve` - Curve of the keys
+    /// * `hash` - Hash function used for signing and verifying
+    ///
+    /// # Returns
+    /// Result containing the key pair or a GenerateKeyError
+    pub async fn generate(curve: NamedCurve, hash: Hash) -> Result<Self, GenerateKeyError> {
+        crate::check_target()?;
+        let algorithm = web_sys::EcKeyGenParams::new(NAME, curve.name());
+        let (public_key, private_key) = crate::key_pair::generate::<Ecdsa>(&algorithm).await?;
+        Ok(Self::new(
+            EcdsaPublicKey {
+                key: public_key,
+                curve,
+                hash,
+            },
+            EcdsaPrivateKey {
+                key: private_key,
+                curve,
+                hash,
+            },
+        ))
+    }
+}
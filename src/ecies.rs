@@ -0,0 +1,167 @@
+//! Elliptic Curve Integrated Encryption Scheme (ECIES)
+//!
+//! A simpler alternative to [`crate::hpke`], to encrypt a payload for an
+//! [`EcdhPublicKey`]. Each payload gets an ephemeral ECDH key pair, the shared
+//! secret goes through HKDF-SHA256 to derive a non extractable AES-256-GCM
+//! key, used with a random nonce.
+//!
+//! The ciphertext is self-contained: the ephemeral public key (as an
+//! uncompressed point), the nonce and the encrypted payload.
+//!
+//! ```rust,no_run
+//! use browser_crypto::curve::NamedCurve;
+//! use browser_crypto::ecdh::EcdhKeyPair;
+//!
+//! async fn encrypt() -> Result<(), Box<dyn std::error::Error>> {
+//!     let pair = EcdhKeyPair::generate(NamedCurve::P256).await?;
+//!     let ciphertext = browser_crypto::ecies::encrypt(&pair.public_key, b"Hello World!").await?;
+//!     let plaintext = browser_crypto::ecies::decrypt(&pair.private_key, &ciphertext).await?;
+//!     assert_eq!(plaintext, b"Hello World!");
+//!     Ok(())
+//! }
+//! ```
+
+use wasm_bindgen::JsValue;
+
+use crate::aes_gcm::Aes256Gcm;
+use crate::algorithm::{Algorithm, DecryptionError, EncryptionError, Nonce, NonceError};
+use crate::curve::NamedCurve;
+use crate::derive::DeriveError;
+use crate::ecdh::{EcdhKeyPair, EcdhPrivateKey, EcdhPublicKey};
+use crate::hash::Hash;
+use crate::hkdf::Hkdf;
+use crate::key::{ExportKeyError, GenerateKeyError, ImportKeyError};
+use crate::ErrorCode;
+
+/// Prefix of the HKDF info, followed by the ephemeral public key
+const INFO_LABEL: &[u8] = b"browser-crypto ECIES";
+
+/// Errors that can occur when encrypting or decrypting with ECIES.
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum EciesError {
+    /// Indicates that the ciphertext is too short.
+    #[error("invalid ciphertext")]
+    InvalidCiphertext,
+    /// Indicates that the ephemeral public key of the ciphertext is not valid
+    /// on the curve of the private key.
+    #[error(transparent)]
+    Import(#[from] ImportKeyError),
+    /// Indicates that the ephemeral public key couldn't be serialized.
+    #[error(transparent)]
+    Export(#[from] ExportKeyError),
+    /// Indicates that the ephemeral key pair couldn't be generated.
+    #[error(transparent)]
+    Generate(#[from] GenerateKeyError),
+    /// Indicates that the shared secret or the encryption key couldn't be
+    /// derived.
+    #[error(transparent)]
+    Derive(#[from] DeriveError),
+    /// Indicates that the nonce couldn't be generated.
+    #[error(transparent)]
+    Nonce(#[from] NonceError),
+    /// Indicates that the payload couldn't be encrypted.
+    #[error(transparent)]
+    Encryption(#[from] EncryptionError),
+    /// Indicates that the payload couldn't be decrypted, like when the private
+    /// key doesn't match or the ciphertext has been altered.
+    #[error(transparent)]
+    Decryption(#[from] DecryptionError),
+}
+
+impl ErrorCode for EciesError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidCiphertext => "ecies.invalid_ciphertext",
+            Self::Import(inner) => inner.code(),
+            Self::Export(inner) => inner.code(),
+            Self::Generate(inner) => inner.code(),
+            Self::Derive(inner) => inner.code(),
+            Self::Nonce(inner) => inner.code(),
+            Self::Encryption(inner) => inner.code(),
+            Self::Decryption(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<EciesError> for JsValue {
+    fn from(value: EciesError) -> Self {
+        crate::js_error("EciesError", &value)
+    }
+}
+
+/// Size of an uncompressed point on the curve
+fn point_size(curve: NamedCurve) -> usize {
+    1 + 2 * curve.field_size()
+}
+
+/// Derives the AES-256-GCM key from the shared secret, bound to the ephemeral
+/// public key
+async fn derive_cipher(shared_secret: &[u8], ephemeral: &[u8]) -> Result<Aes256Gcm, EciesError> {
+    let info = [INFO_LABEL, ephemeral].concat();
+    let hkdf = Hkdf::from_key(shared_secret, Hash::Sha256, &[], &info).await?;
+    Ok(Aes256Gcm::derive_from(&hkdf).await?)
+}
+
+/// Encrypts a payload for the owner of a public key
+///
+/// # Arguments
+/// * `public_key` - Public key of the receiver
+/// * `payload` - Data to encrypt
+///
+/// # Returns
+/// Result containing the ciphertext, embedding the ephemeral public key and
+/// the nonce, or an EciesError
+pub async fn encrypt(public_key: &EcdhPublicKey, payload: &[u8]) -> Result<Vec<u8>, EciesError> {
+    let ephemeral = EcdhKeyPair::generate(public_key.curve()).await?;
+    let shared_secret =
+        crate::SecretBytes::from(ephemeral.private_key.diffie_hellman(public_key).await?);
+    let ephemeral_public_key = ephemeral.public_key.to_raw().await?;
+    let cipher = derive_cipher(&shared_secret, &ephemeral_public_key).await?;
+
+    let nonce = Aes256Gcm::generate_nonce()?;
+    let encrypted = cipher.encrypt(&nonce, payload).await?;
+
+    let mut output = Vec::with_capacity(
+        ephemeral_public_key.len() + Aes256Gcm::NONCE_SIZE as usize + encrypted.len(),
+    );
+    output.extend_from_slice(&ephemeral_public_key);
+    output.extend(nonce.iter());
+    output.extend_from_slice(&encrypted);
+    Ok(output)
+}
+
+/// Decrypts a payload encrypted with [`encrypt`]
+///
+/// # Arguments
+/// * `private_key` - Private key of the receiver
+/// * `ciphertext` - Data returned by [`encrypt`]
+///
+/// # Returns
+/// Result containing the decrypted payload or an EciesError
+///
+/// # Errors
+/// - `EciesError::InvalidCiphertext` if the ciphertext is truncated
+/// - `EciesError::Import` if the ephemeral public key is not on the curve of
+///   the private key
+/// - `EciesError::Decryption` if the private key doesn't match or the
+///   ciphertext has been altered
+pub async fn decrypt(
+    private_key: &EcdhPrivateKey,
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, EciesError> {
+    let point_size = point_size(private_key.curve());
+    let nonce_size = Aes256Gcm::NONCE_SIZE as usize;
+    if ciphertext.len() < point_size + nonce_size {
+        return Err(EciesError::InvalidCiphertext);
+    }
+    let (ephemeral_public_key, rest) = ciphertext.split_at(point_size);
+    let (nonce, encrypted) = rest.split_at(nonce_size);
+
+    let ephemeral = EcdhPublicKey::from_raw(ephemeral_public_key, private_key.curve()).await?;
+    let shared_secret = crate::SecretBytes::from(private_key.diffie_hellman(&ephemeral).await?);
+    let cipher = derive_cipher(&shared_secret, ephemeral_public_key).await?;
+
+    let nonce = Nonce::<Aes256Gcm>::from_slice(nonce)?;
+    Ok(cipher.decrypt(&nonce, encrypted).await?)
+}
@@ -0,0 +1,325 @@
+//! Ed25519 signature implementation
+//!
+//! Ed25519 is only supported by recent browsers, use [`is_supported`] to check
+//! its availability. On older browsers, the operations fail with a
+//! `NotSupported` error.
+//!
+//! ```rust,no_run
+//! use browser_crypto::ed25519::Ed25519KeyPair;
+//!
+//! async fn sign() -> Result<(), Box<dyn std::error::Error>> {
+//!     if !browser_crypto::ed25519::is_supported().await {
+//!         return Ok(());
+//!     }
+//!     let pair = Ed25519KeyPair::generate().await?;
+//!     let signature = pair.private_key.sign(b"Hello World!").await?;
+//!     assert!(pair.public_key.verify(&signature, b"Hello World!").await?);
+//!     Ok(())
+//! }
+//! ```
+
+use crate::der::KeyAlgorithm;
+use crate::input::SecretInput;
+use crate::jwk::Jwk;
+use crate::key::{ExportKeyError, GenerateKeyError, ImportKeyError, KeyFormat};
+use crate::key_pair::{AsymmetricAlgorithm, AsymmetricKey, KeyPair};
+use crate::signature::{SignError, SignatureAlgorithm, VerifyError};
+
+const NAME: &str = "Ed25519";
+
+/// Size of a public key in bytes
+pub const PUBLIC_KEY_SIZE: usize = 32;
+
+/// Size of a signature in bytes
+pub const SIGNATURE_SIZE: usize = 64;
+
+/// Public key from RFC 8032, used to check the browser support
+const PROBE_PUBLIC_KEY: [u8; PUBLIC_KEY_SIZE] = [
+    0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b, 0xfe, 0xd3, 0xc9, 0x64, 0x07, 0x3a,
+    0x0e, 0xe1, 0x72, 0xf3, 0xda, 0xa6, 0x23, 0x25, 0xaf, 0x02, 0x1a, 0x68, 0xf7, 0x07, 0x51, 0x1a,
+];
+
+fn algorithm() -> Result<js_sys::Object, wasm_bindgen::JsValue> {
+    let algorithm = js_sys::Object::new();
+    js_sys::Reflect::set(&algorithm, &"name".into(), &NAME.into())?;
+    Ok(algorithm)
+}
+
+/// Checks if the browser supports Ed25519
+///
+/// # Returns
+/// `true` if a public key could be imported, `false` otherwise
+pub async fn is_supported() -> bool {
+    Ed25519PublicKey::from_raw(&PROBE_PUBLIC_KEY).await.is_ok()
+}
+
+/// Ed25519 algorithm, see [`Ed25519KeyPair`]
+#[derive(Debug, Clone, Copy)]
+pub struct Ed25519;
+
+impl AsymmetricAlgorithm for Ed25519 {
+    type PublicKey = Ed25519PublicKey;
+    type PrivateKey = Ed25519PrivateKey;
+
+    const PUBLIC_USAGES: &'static [&'static str] = &["verify"];
+    const PRIVATE_USAGES: &'static [&'static str] = &["sign"];
+}
+
+impl SignatureAlgorithm for Ed25519 {
+    const NAME: &'static str = NAME;
+    const SIGNATURE_SIZE: Option<usize> = Some(SIGNATURE_SIZE);
+    type SigningKey = Ed25519PrivateKey;
+    type VerifyingKey = Ed25519PublicKey;
+
+    async fn sign(key: &Ed25519PrivateKey, payload: &[u8]) -> Result<Vec<u8>, SignError> {
+        key.sign(payload).await
+    }
+
+    async fn verify(
+        key: &Ed25519PublicKey,
+        signature: &[u8],
+        payload: &[u8],
+    ) -> Result<bool, VerifyError> {
+        key.verify(signature, payload).await
+    }
+}
+
+/// Ed25519 public key, used for verification
+#[derive(Debug, Clone)]
+pub struct Ed25519PublicKey {
+    key: web_sys::CryptoKey,
+}
+
+impl Ed25519PublicKey {
+    /// Imports a public key from its raw bytes.
+    ///
+    /// # Arguments
+    /// * `data` - Raw public key, 32 bytes
+    ///
+    /// # Returns
+    /// Result containing the public key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::InvalidKeyFormat` if the data is not a valid public
+    ///   key
+    /// - `ImportKeyError::NotSupported` if the browser doesn't support Ed25519
+    pub async fn from_raw(data: &[u8]) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let key = crate::key::import_key(KeyFormat::Raw, &algorithm()?, data, &["verify"]).await?;
+        Ok(Self { key })
+    }
+
+    /// Imports a public key in the SubjectPublicKeyInfo format.
+    ///
+    /// # Arguments
+    /// * `data` - DER encoded SubjectPublicKeyInfo
+    ///
+    /// # Returns
+    /// Result containing the public key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::MalformedDer` if the data is not a DER encoded
+    ///   SubjectPublicKeyInfo
+    /// - `ImportKeyError::UnsupportedAlgorithm` if the key is not an Ed25519
+    ///   key
+    /// - `ImportKeyError::NotSupported` if the browser doesn't support Ed25519
+    pub async fn from_spki(data: &[u8]) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let key = crate::key::import_der(
+            KeyFormat::Spki,
+            &algorithm()?,
+            data,
+            KeyAlgorithm::Ed25519,
+            &["verify"],
+        )
+        .await?;
+        Ok(Self { key })
+    }
+
+    /// Imports a public key in the JSON Web Key format.
+    ///
+    /// # Arguments
+    /// * `jwk` - JSON Web Key, with `kty` set to `OKP` and `crv` to `Ed25519`
+    ///
+    /// # Returns
+    /// Result containing the public key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::NotSupported` if the browser doesn't support Ed25519
+    pub async fn from_jwk(jwk: &Jwk) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let key = crate::key::import_jwk(&algorithm()?, jwk, &["verify"]).await?;
+        Ok(Self { key })
+    }
+
+    /// Exports the public key as raw bytes.
+    pub async fn to_raw(&self) -> Result<Vec<u8>, ExportKeyError> {
+        crate::key::export_key(KeyFormat::Raw, &self.key).await
+    }
+
+    /// Exports the public key in the SubjectPublicKeyInfo format.
+    pub async fn to_spki(&self) -> Result<Vec<u8>, ExportKeyError> {
+        crate::key::export_key(KeyFormat::Spki, &self.key).await
+    }
+
+    /// Exports the public key in the JSON Web Key format.
+    pub async fn to_jwk(&self) -> Result<Jwk, ExportKeyError> {
+        crate::key::export_jwk(&self.key).await
+    }
+
+    /// Verifies the signature of a payload
+    ///
+    /// # Arguments
+    /// * `signature` - Signature to verify, 64 bytes
+    /// * `payload` - Data that was signed
+    ///
+    /// # Returns
+    /// Result containing whether the signature is valid or a VerifyError
+    pub async fn verify(&self, signature: &[u8], payload: &[u8]) -> Result<bool, VerifyError> {
+        crate::signature::verify(&algorithm()?, &self.key, signature, payload).await
+    }
+}
+
+impl crate::key_pair::private::Sealed for Ed25519PublicKey {
+    fn crypto_key(&self) -> &web_sys::CryptoKey {
+        &self.key
+    }
+}
+
+impl AsymmetricKey for Ed25519PublicKey {}
+
+#[cfg(feature = "keystore")]
+impl crate::keystore::private::Sealed for Ed25519PublicKey {
+    fn to_entry(&self) -> Result<js_sys::Object, wasm_bindgen::JsValue> {
+        crate::keystore::key_entry(&self.key)
+    }
+
+    fn from_entry(entry: &wasm_bindgen::JsValue) -> Result<Self, crate::keystore::KeystoreError> {
+        let key = crate::keystore::check_entry_key(entry, "public", NAME, Ed25519::PUBLIC_USAGES)?;
+        Ok(Self { key })
+    }
+}
+
+#[cfg(feature = "keystore")]
+impl crate::keystore::StorableKey for Ed25519PublicKey {}
+
+/// Ed25519 private key, used for signing
+#[derive(Debug, Clone)]
+pub struct Ed25519PrivateKey {
+    key: web_sys::CryptoKey,
+}
+
+impl Ed25519PrivateKey {
+    /// Imports a private key in the PKCS#8 format.
+    ///
+    /// The Web Crypto API doesn't support importing raw Ed25519 private keys.
+    ///
+    /// # Arguments
+    /// * `data` - DER encoded PKCS#8 private key
+    ///
+    /// # Returns
+    /// Result containing the private key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::MalformedDer` if the data is not a DER encoded PKCS#8
+    ///   key
+    /// - `ImportKeyError::UnsupportedAlgorithm` if the key is not an Ed25519
+    ///   key
+    /// - `ImportKeyError::NotSupported` if the browser doesn't support Ed25519
+    pub async fn from_pkcs8(data: &(impl SecretInput + ?Sized)) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let key = crate::key::import_der(
+            KeyFormat::Pkcs8,
+            &algorithm()?,
+            data.expose_bytes(),
+            KeyAlgorithm::Ed25519,
+            &["sign"],
+        )
+        .await?;
+        Ok(Self { key })
+    }
+
+    /// Imports a private key in the JSON Web Key format.
+    ///
+    /// # Arguments
+    /// * `jwk` - JSON Web Key, with `kty` set to `OKP`, `crv` to `Ed25519` and
+    ///   `d` defined
+    ///
+    /// # Returns
+    /// Result containing the private key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::NotSupported` if the browser doesn't support Ed25519
+    pub async fn from_jwk(jwk: &Jwk) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let key = crate::key::import_jwk(&algorithm()?, jwk, &["sign"]).await?;
+        Ok(Self { key })
+    }
+
+    /// Exports the private key in the PKCS#8 format.
+    pub async fn to_pkcs8(&self) -> Result<Vec<u8>, ExportKeyError> {
+        crate::key::export_key(KeyFormat::Pkcs8, &self.key).await
+    }
+
+    /// Exports the private key in the JSON Web Key format.
+    pub async fn to_jwk(&self) -> Result<Jwk, ExportKeyError> {
+        crate::key::export_jwk(&self.key).await
+    }
+
+    /// Signs a payload
+    ///
+    /// # Arguments
+    /// * `payload` - Data to sign
+    ///
+    /// # Returns
+    /// Result containing the 64 bytes signature or a SignError
+    pub async fn sign(&self, payload: &[u8]) -> Result<Vec<u8>, SignError> {
+        crate::signature::sign(&algorithm()?, &self.key, payload).await
+    }
+}
+
+impl crate::key_pair::private::Sealed for Ed25519PrivateKey {
+    fn crypto_key(&self) -> &web_sys::CryptoKey {
+        &self.key
+    }
+}
+
+impl AsymmetricKey for Ed25519PrivateKey {}
+
+#[cfg(feature = "keystore")]
+impl crate::keystore::private::Sealed for Ed25519PrivateKey {
+    fn to_entry(&self) -> Result<js_sys::Object, wasm_bindgen::JsValue> {
+        crate::keystore::key_entry(&self.key)
+    }
+
+    fn from_entry(entry: &wasm_bindgen::JsValue) -> Result<Self, crate::keystore::KeystoreError> {
+        let key =
+            crate::keystore::check_entry_key(entry, "private", NAME, Ed25519::PRIVATE_USAGES)?;
+        Ok(Self { key })
+    }
+}
+
+#[cfg(feature = "keystore")]
+impl crate::keystore::StorableKey for Ed25519PrivateKey {}
+
+/// Ed25519 key pair, the private key signs and the public key verifies
+pub type Ed25519KeyPair = KeyPair<Ed25519>;
+
+impl KeyPair<Ed25519> {
+    /// Generates a new key pair.
+    ///
+    /// # Returns
+    /// Result containing the key pair or a GenerateKeyError
+    ///
+    /// # Errors
+    /// - `GenerateKeyError::NotSupported` if the browser doesn't support
+    ///   Ed25519
+    pub async fn generate() -> Result<Self, GenerateKeyError> {
+        crate::check_target()?;
+        let (public_key, private_key) = crate::key_pair::generate::<Ed25519>(&algorithm()?).await?;
+        Ok(Self::new(
+            Ed25519PublicKey { key: public_key },
+            Ed25519PrivateKey { key: private_key },
+        ))
+    }
+}
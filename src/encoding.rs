@@ -0,0 +1,192 @@
+//! Text encodings of binary data
+//!
+//! The nonces, salts, keys, JSON Web Keys and stored entries of the crate are
+//! encoded as base64, base64url or hexadecimal text. The same encoders are
+//! exposed here, so applications exchanging those values don't need another
+//! crate to produce or read them.
+//!
+//! The encoders never fail. The decoders report the first invalid character or
+//! an impossible length with an [`EncodingError`], and accept both padded and
+//! unpadded base64.
+//!
+//! ```rust
+//! use browser_crypto::encoding;
+//!
+//! let encoded = encoding::base64url_encode(b"hello");
+//! assert_eq!(encoded, "aGVsbG8");
+//! assert_eq!(encoding::base64url_decode(&encoded).unwrap(), b"hello");
+//! assert_eq!(encoding::hex_encode(b"hello"), "68656c6c6f");
+//! ```
+
+use wasm_bindgen::JsValue;
+
+use crate::ErrorCode;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+const BASE64URL_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+const HEX_ALPHABET: &[u8; 16] = b"0123456789abcdef";
+
+/// Errors that can occur when decoding text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum EncodingError {
+    /// Indicates that the text contains a character outside of the alphabet,
+    /// or a misplaced padding character.
+    ///
+    /// # Fields
+    /// * `position` - The byte offset of the first invalid character
+    #[error("invalid character at position {position}")]
+    InvalidCharacter { position: usize },
+    /// Indicates that no data could have this encoded length, like an odd
+    /// number of hexadecimal digits.
+    ///
+    /// # Fields
+    /// * `length` - The length of the text
+    #[error("invalid encoded length {length}")]
+    InvalidLength { length: usize },
+}
+
+impl ErrorCode for EncodingError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidCharacter { .. } => "encoding.invalid_character",
+            Self::InvalidLength { .. } => "encoding.invalid_length",
+        }
+    }
+}
+
+impl From<EncodingError> for JsValue {
+    fn from(value: EncodingError) -> Self {
+        crate::js_error("EncodingError", &value)
+    }
+}
+
+fn base64_value(alphabet: &[u8; 64], c: u8) -> Option<u32> {
+    match c {
+        b'A'..=b'Z' => Some((c - b'A') as u32),
+        b'a'..=b'z' => Some((c - b'a') as u32 + 26),
+        b'0'..=b'9' => Some((c - b'0') as u32 + 52),
+        _ if c == alphabet[62] => Some(62),
+        _ if c == alphabet[63] => Some(63),
+        _ => None,
+    }
+}
+
+/// Encodes bytes with the standard base64 alphabet, with padding
+pub fn base64_encode(data: &[u8]) -> String {
+    encode(BASE64_ALPHABET, data, true)
+}
+
+/// Decodes a string encoded with the standard base64 alphabet, padding being
+/// optional
+///
+/// # Errors
+/// - `EncodingError::InvalidCharacter` if the string contains any other
+///   character
+/// - `EncodingError::InvalidLength` if its length is not possible for a base64
+///   string
+pub fn base64_decode(input: &str) -> Result<Vec<u8>, EncodingError> {
+    decode(BASE64_ALPHABET, input)
+}
+
+/// Encodes bytes with the URL and filename safe base64 alphabet, without
+/// padding, as in JSON Web Keys
+pub fn base64url_encode(data: &[u8]) -> String {
+    encode(BASE64URL_ALPHABET, data, false)
+}
+
+/// Decodes a string encoded with the URL and filename safe base64 alphabet,
+/// padding being optional
+///
+/// # Errors
+/// - `EncodingError::InvalidCharacter` if the string contains any other
+///   character
+/// - `EncodingError::InvalidLength` if its length is not possible for a base64
+///   string
+pub fn base64url_decode(input: &str) -> Result<Vec<u8>, EncodingError> {
+    decode(BASE64URL_ALPHABET, input)
+}
+
+fn encode(alphabet: &[u8; 64], data: &[u8], padding: bool) -> String {
+    let mut output = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let buffer = chunk.iter().enumerate().fold(0u32, |acc, (idx, byte)| {
+            acc | (*byte as u32) << (16 - idx * 8)
+        });
+        for idx in 0..4 {
+            if idx <= chunk.len() {
+                let value = (buffer >> (18 - idx * 6)) & 0x3f;
+                output.push(alphabet[value as usize] as char);
+            } else if padding {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
+
+fn decode(alphabet: &[u8; 64], input: &str) -> Result<Vec<u8>, EncodingError> {
+    let input = input.as_bytes();
+    let unpadded = match input {
+        [rest @ .., b'=', b'='] | [rest @ .., b'='] if input.len().is_multiple_of(4) => rest,
+        _ => input,
+    };
+    if unpadded.len() % 4 == 1 {
+        return Err(EncodingError::InvalidLength {
+            length: input.len(),
+        });
+    }
+    let mut output = Vec::with_capacity(unpadded.len() * 3 / 4);
+    for (index, chunk) in unpadded.chunks(4).enumerate() {
+        let buffer = chunk.iter().enumerate().try_fold(0u32, |acc, (idx, c)| {
+            base64_value(alphabet, *c)
+                .map(|value| acc | value << (18 - idx * 6))
+                .ok_or(EncodingError::InvalidCharacter {
+                    position: index * 4 + idx,
+                })
+        })?;
+        for idx in 0..chunk.len() - 1 {
+            output.push((buffer >> (16 - idx * 8)) as u8);
+        }
+    }
+    Ok(output)
+}
+
+/// Encodes bytes as lowercase hexadecimal
+pub fn hex_encode(data: &[u8]) -> String {
+    let mut output = String::with_capacity(data.len() * 2);
+    for byte in data {
+        output.push(HEX_ALPHABET[(byte >> 4) as usize] as char);
+        output.push(HEX_ALPHABET[(byte & 0x0f) as usize] as char);
+    }
+    output
+}
+
+/// Decodes a hexadecimal string, in lowercase or uppercase
+///
+/// # Errors
+/// - `EncodingError::InvalidCharacter` if the string contains a character which
+///   is not a hexadecimal digit
+/// - `EncodingError::InvalidLength` if its length is odd
+pub fn hex_decode(input: &str) -> Result<Vec<u8>, EncodingError> {
+    let input = input.as_bytes();
+    if !input.len().is_multiple_of(2) {
+        return Err(EncodingError::InvalidLength {
+            length: input.len(),
+        });
+    }
+    let digit = |position: usize| {
+        (input[position] as char)
+            .to_digit(16)
+            .ok_or(EncodingError::InvalidCharacter { position })
+    };
+    (0..input.len())
+        .step_by(2)
+        .map(|position| Ok((digit(position)? << 4 | digit(position + 1)?) as u8))
+        .collect()
+}
@@ -0,0 +1,252 @@
+//! Envelope encryption
+//!
+//! Each payload is encrypted with its own data encryption key (DEK), generated
+//! in the browser. The DEK is then wrapped with a long lived key encryption key
+//! (KEK), like [`crate::aes_kw::AesKw`] or
+//! [`crate::rsa_oaep::RsaOaepPublicKey`], and stored next to the encrypted
+//! payload. Rotating the KEK only requires wrapping the DEKs again.
+//!
+//! When the KEK has a key ID, attached with [`crate::kid::Tagged`], the
+//! envelope records it, so the matching KEK can be picked to open it.
+//!
+//! With the `native` feature enabled, outside of WebAssembly, the envelopes can
+//! only be parsed and serialized, the keys being wrapped by the browser.
+//!
+//! ```rust,no_run
+//! use browser_crypto::aes_kw::Aes256Kw;
+//! use browser_crypto::envelope::Envelope;
+//!
+//! async fn seal() -> Result<(), Box<dyn std::error::Error>> {
+//!     let kek = Aes256Kw::from_key(&[0u8; 32]).await?;
+//!     let envelope = Envelope::seal(&kek, b"Hello World!").await?;
+//!     let bytes = envelope.to_bytes();
+//!
+//!     let envelope = Envelope::from_bytes(&bytes)?;
+//!     assert_eq!(envelope.open(&kek).await?, b"Hello World!");
+//!     Ok(())
+//! }
+//! ```
+
+use wasm_bindgen::JsValue;
+
+use crate::aes_gcm::Aes256Gcm;
+use crate::algorithm::{Algorithm, DecryptionError, EncryptionError, Nonce, NonceError};
+use crate::key::{GenerateKeyError, KeyUnwrapper, KeyWrapper, UnwrapKeyError, WrapKeyError};
+use crate::ErrorCode;
+
+/// Flag set on the wrapped key length when the envelope has a key ID
+const KID_FLAG: u16 = 0x8000;
+
+/// Errors that can occur when sealing or opening an envelope.
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum EnvelopeError {
+    /// Indicates that the serialized envelope is truncated.
+    #[error("invalid envelope")]
+    InvalidEnvelope,
+    /// Indicates that the key ID of the key encryption key is empty or longer
+    /// than 255 bytes.
+    #[error("key ID must be between 1 and 255 bytes")]
+    InvalidKid,
+    /// Indicates that the envelope was sealed with another key encryption key,
+    /// both having a key ID.
+    ///
+    /// # Fields
+    /// * `0` - The key ID recorded in the envelope
+    #[error("envelope sealed with the key {0:?}")]
+    KidMismatch(String),
+    /// Indicates that the data encryption key couldn't be generated.
+    #[error(transparent)]
+    Generate(#[from] GenerateKeyError),
+    /// Indicates that the data encryption key couldn't be wrapped.
+    #[error(transparent)]
+    Wrap(#[from] WrapKeyError),
+    /// Indicates that the data encryption key couldn't be unwrapped, like when
+    /// the key encryption key doesn't match.
+    #[error(transparent)]
+    Unwrap(#[from] UnwrapKeyError),
+    /// Indicates that the nonce couldn't be generated.
+    #[error(transparent)]
+    Nonce(#[from] NonceError),
+    /// Indicates that the payload couldn't be encrypted.
+    #[error(transparent)]
+    Encryption(#[from] EncryptionError),
+    /// Indicates that the payload couldn't be decrypted.
+    #[error(transparent)]
+    Decryption(#[from] DecryptionError),
+}
+
+impl ErrorCode for EnvelopeError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidEnvelope => "envelope.invalid_envelope",
+            Self::InvalidKid => "envelope.invalid_kid",
+            Self::KidMismatch(..) => "envelope.kid_mismatch",
+            Self::Generate(inner) => inner.code(),
+            Self::Wrap(inner) => inner.code(),
+            Self::Unwrap(inner) => inner.code(),
+            Self::Nonce(inner) => inner.code(),
+            Self::Encryption(inner) => inner.code(),
+            Self::Decryption(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<EnvelopeError> for JsValue {
+    fn from(value: EnvelopeError) -> Self {
+        crate::js_error("EnvelopeError", &value)
+    }
+}
+
+/// Payload encrypted with AES-256-GCM, along with its wrapped key
+#[derive(Debug, Clone)]
+pub struct Envelope {
+    kid: Option<String>,
+    wrapped_key: Vec<u8>,
+    nonce: Nonce<Aes256Gcm>,
+    ciphertext: Vec<u8>,
+}
+
+impl Envelope {
+    /// Encrypts a payload with a new data encryption key, wrapped with the key
+    /// encryption key.
+    ///
+    /// # Arguments
+    /// * `kek` - Key encryption key
+    /// * `payload` - Data to encrypt
+    ///
+    /// # Returns
+    /// Result containing the envelope or an EnvelopeError
+    ///
+    /// # Errors
+    /// - `EnvelopeError::InvalidKid` if the key ID of the key encryption key is
+    ///   empty or longer than 255 bytes
+    /// - `EnvelopeError::Wrap` if the key encryption key can't wrap keys
+    pub async fn seal<W: KeyWrapper>(kek: &W, payload: &[u8]) -> Result<Self, EnvelopeError> {
+        let kid = kek.kid().map(String::from);
+        if kid
+            .as_ref()
+            .is_some_and(|kid| kid.is_empty() || kid.len() > u8::MAX as usize)
+        {
+            return Err(EnvelopeError::InvalidKid);
+        }
+        // the key has to be extractable to be wrapped, it's dropped right after
+        let dek = Aes256Gcm::generate(true).await?;
+        let nonce = Aes256Gcm::generate_nonce()?;
+        let ciphertext = dek.encrypt(&nonce, payload).await?;
+        let wrapped_key = kek.wrap_key(&dek).await?;
+        Ok(Self {
+            kid,
+            wrapped_key,
+            nonce,
+            ciphertext,
+        })
+    }
+
+    /// Decrypts the payload, after unwrapping the data encryption key as a
+    /// non extractable key.
+    ///
+    /// # Arguments
+    /// * `kek` - Key encryption key used to seal the envelope
+    ///
+    /// # Returns
+    /// Result containing the decrypted payload or an EnvelopeError
+    ///
+    /// # Errors
+    /// - `EnvelopeError::KidMismatch` if the envelope and the key encryption
+    ///   key have different key IDs
+    /// - `EnvelopeError::Unwrap` if the key encryption key doesn't match
+    /// - `EnvelopeError::Decryption` if the payload has been altered
+    pub async fn open<U: KeyUnwrapper>(&self, kek: &U) -> Result<Vec<u8>, EnvelopeError> {
+        if let (Some(kid), Some(expected)) = (self.kid.as_deref(), kek.kid()) {
+            if kid != expected {
+                return Err(EnvelopeError::KidMismatch(kid.to_string()));
+            }
+        }
+        let dek: Aes256Gcm = kek.unwrap_key(&self.wrapped_key).await?;
+        Ok(dek.decrypt(&self.nonce, &self.ciphertext).await?)
+    }
+
+    /// Key ID of the key encryption key, if it had one
+    pub fn kid(&self) -> Option<&str> {
+        self.kid.as_deref()
+    }
+
+    /// Wrapped data encryption key
+    pub fn wrapped_key(&self) -> &[u8] {
+        &self.wrapped_key
+    }
+
+    /// Nonce used to encrypt the payload
+    pub fn nonce(&self) -> &Nonce<Aes256Gcm> {
+        &self.nonce
+    }
+
+    /// Encrypted payload, with its authentication tag
+    pub fn ciphertext(&self) -> &[u8] {
+        &self.ciphertext
+    }
+
+    /// Serializes the envelope: the length of the wrapped key on 2 bytes (big
+    /// endian), the wrapped key, the nonce and the encrypted payload.
+    ///
+    /// With a key ID, the highest bit of the length is set, and the length is
+    /// followed by the length of the key ID on one byte and the key ID.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let kid = self.kid.as_deref().unwrap_or_default();
+        let mut output = Vec::with_capacity(
+            3 + kid.len()
+                + self.wrapped_key.len()
+                + Aes256Gcm::NONCE_SIZE as usize
+                + self.ciphertext.len(),
+        );
+        let length = self.wrapped_key.len() as u16;
+        if kid.is_empty() {
+            output.extend_from_slice(&length.to_be_bytes());
+        } else {
+            output.extend_from_slice(&(length | KID_FLAG).to_be_bytes());
+            output.push(kid.len() as u8);
+            output.extend_from_slice(kid.as_bytes());
+        }
+        output.extend_from_slice(&self.wrapped_key);
+        output.extend(self.nonce.iter());
+        output.extend_from_slice(&self.ciphertext);
+        output
+    }
+
+    /// Deserializes an envelope produced by [`Envelope::to_bytes`]
+    ///
+    /// # Errors
+    /// - `EnvelopeError::InvalidEnvelope` if the data is truncated
+    pub fn from_bytes(data: &[u8]) -> Result<Self, EnvelopeError> {
+        let (length, rest) = data
+            .split_first_chunk::<2>()
+            .ok_or(EnvelopeError::InvalidEnvelope)?;
+        let length = u16::from_be_bytes(*length);
+        let (kid, rest) = if length & KID_FLAG == 0 {
+            (None, rest)
+        } else {
+            let (&kid_length, rest) = rest.split_first().ok_or(EnvelopeError::InvalidEnvelope)?;
+            let kid_length = kid_length as usize;
+            if kid_length == 0 || rest.len() < kid_length {
+                return Err(EnvelopeError::InvalidEnvelope);
+            }
+            let (kid, rest) = rest.split_at(kid_length);
+            let kid = std::str::from_utf8(kid).map_err(|_| EnvelopeError::InvalidEnvelope)?;
+            (Some(kid.to_string()), rest)
+        };
+        let length = (length & !KID_FLAG) as usize;
+        let nonce_size = Aes256Gcm::NONCE_SIZE as usize;
+        if rest.len() < length + nonce_size {
+            return Err(EnvelopeError::InvalidEnvelope);
+        }
+        let (wrapped_key, rest) = rest.split_at(length);
+        let (nonce, ciphertext) = rest.split_at(nonce_size);
+        Ok(Self {
+            kid,
+            wrapped_key: wrapped_key.to_vec(),
+            nonce: Nonce::from_slice(nonce)?,
+            ciphertext: ciphertext.to_vec(),
+        })
+    }
+}
@@ -0,0 +1,85 @@
+//! Hash functions supported by the Web Crypto API
+
+use crate::context::Operation;
+
+/// Hash functions, used as parameters of other algorithms like RSA-OAEP or
+/// HMAC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Hash {
+    /// SHA-1, only meant for interoperability with legacy systems as it's not
+    /// considered secure anymore.
+    Sha1,
+    /// SHA-256
+    Sha256,
+    /// SHA-384
+    Sha384,
+    /// SHA-512
+    Sha512,
+}
+
+impl Hash {
+    /// Name of the hash function, as expected by the Web Crypto API
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Sha1 => "SHA-1",
+            Self::Sha256 => "SHA-256",
+            Self::Sha384 => "SHA-384",
+            Self::Sha512 => "SHA-512",
+        }
+    }
+
+    /// Finds a hash function from its Web Crypto API name, like "SHA-256"
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "SHA-1" => Some(Self::Sha1),
+            "SHA-256" => Some(Self::Sha256),
+            "SHA-384" => Some(Self::Sha384),
+            "SHA-512" => Some(Self::Sha512),
+            _ => None,
+        }
+    }
+
+    /// Size of the digest in bytes
+    pub fn output_size(&self) -> usize {
+        match self {
+            Self::Sha1 => 20,
+            Self::Sha256 => 32,
+            Self::Sha384 => 48,
+            Self::Sha512 => 64,
+        }
+    }
+
+    /// Computes the digest of some data
+    ///
+    /// With the `native` feature enabled, outside of WebAssembly, the digest
+    /// is computed by the RustCrypto implementation.
+    ///
+    /// # Arguments
+    /// * `data` - Data to hash, held by Rust or JavaScript
+    ///
+    /// # Returns
+    /// Result containing the digest, of [`Hash::output_size`] bytes, or an
+    /// Error
+    pub async fn digest<'a>(
+        &self,
+        data: impl Into<crate::input::CryptoInput<'a>>,
+    ) -> Result<Vec<u8>, crate::Error> {
+        let data = data.into();
+        let size = Some(data.len());
+        crate::instrument::observe(Operation::Digest, self.name(), size, async {
+            #[cfg(native_backend)]
+            let digest = Ok(crate::native::digest(*self, &data.to_vec()));
+            #[cfg(not(native_backend))]
+            let digest = {
+                let subtle = crate::subtle()?;
+                let promise = data
+                    .with_array(|data| subtle.digest_with_str_and_js_u8_array(self.name(), data))?;
+                crate::resolve::<js_sys::ArrayBuffer, crate::Error>(promise)
+                    .await
+                    .map(|digest| crate::array_to_vec(&js_sys::Uint8Array::new(&digest)))
+            };
+            digest
+        })
+        .await
+    }
+}
@@ -0,0 +1,100 @@
+//! Incremental hashing of large inputs
+//!
+//! `SubtleCrypto.digest` only hashes data held entirely in memory. [`Hasher`]
+//! computes the same digests in software, chunk by chunk, so large files can
+//! be hashed without loading them at once.
+//!
+//! Requires the `incremental-digest` feature.
+//!
+//! ```rust,no_run
+//! use browser_crypto::hash::Hash;
+//!
+//! async fn digest() -> Result<(), Box<dyn std::error::Error>> {
+//!     let mut hasher = Hash::Sha256.hasher();
+//!     hasher.update(b"Hello ");
+//!     hasher.update(b"World!");
+//!     assert_eq!(
+//!         hasher.finalize(),
+//!         Hash::Sha256.digest(b"Hello World!").await?
+//!     );
+//!     Ok(())
+//! }
+//! ```
+
+use sha2::Digest;
+
+use crate::hash::Hash;
+
+#[derive(Clone)]
+enum State {
+    Sha1(sha1::Sha1),
+    Sha256(sha2::Sha256),
+    Sha384(sha2::Sha384),
+    Sha512(sha2::Sha512),
+}
+
+/// Incremental hash function, producing the same digests as
+/// [`Hash::digest`]
+#[derive(Clone)]
+pub struct Hasher {
+    hash: Hash,
+    state: State,
+}
+
+impl std::fmt::Debug for Hasher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Hasher")
+            .field("hash", &self.hash)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Hasher {
+    /// Starts hashing with a hash function
+    pub fn new(hash: Hash) -> Self {
+        let state = match hash {
+            Hash::Sha1 => State::Sha1(sha1::Sha1::new()),
+            Hash::Sha256 => State::Sha256(sha2::Sha256::new()),
+            Hash::Sha384 => State::Sha384(sha2::Sha384::new()),
+            Hash::Sha512 => State::Sha512(sha2::Sha512::new()),
+        };
+        Self { hash, state }
+    }
+
+    /// Hash function
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    /// Hashes a chunk of data
+    pub fn update(&mut self, data: &[u8]) {
+        match &mut self.state {
+            State::Sha1(inner) => inner.update(data),
+            State::Sha256(inner) => inner.update(data),
+            State::Sha384(inner) => inner.update(data),
+            State::Sha512(inner) => inner.update(data),
+        }
+    }
+
+    /// Computes the digest of all the hashed data
+    ///
+    /// # Returns
+    /// The digest, of [`Hash::output_size`] bytes
+    pub fn finalize(self) -> Vec<u8> {
+        match self.state {
+            State::Sha1(inner) => inner.finalize().to_vec(),
+            State::Sha256(inner) => inner.finalize().to_vec(),
+            State::Sha384(inner) => inner.finalize().to_vec(),
+            State::Sha512(inner) => inner.finalize().to_vec(),
+        }
+    }
+}
+
+impl Hash {
+    /// Starts hashing data incrementally
+    ///
+    /// Requires the `incremental-digest` feature.
+    pub fn hasher(&self) -> Hasher {
+        Hasher::new(*self)
+    }
+}
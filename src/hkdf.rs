@@ -0,0 +1,122 @@
+//! HKDF key derivation implementation
+//!
+//! Derives keys from a secret with a high entropy, like a shared secret or a
+//! master key. For passwords, use [`crate::pbkdf2`] instead.
+//!
+//! ```rust,no_run
+//! use browser_crypto::aes_gcm::Aes256Gcm;
+//! use browser_crypto::hash::Hash;
+//! use browser_crypto::hkdf::Hkdf;
+//!
+//! async fn from_master_key(master: &[u8]) -> Result<Aes256Gcm, Box<dyn std::error::Error>> {
+//!     let hkdf = Hkdf::from_key(master, Hash::Sha256, b"some salt", b"").await?;
+//!     Ok(Aes256Gcm::derive_from(&hkdf.with_info(b"encryption")).await?)
+//! }
+//! ```
+
+use crate::derive::{DeriveError, KeyDerivation};
+use crate::hash::Hash;
+use crate::input::SecretInput;
+use crate::key::ImportKeyError;
+
+const NAME: &str = "HKDF";
+
+/// HKDF key derivation, from an input key with a given salt and info
+#[derive(Debug, Clone)]
+pub struct Hkdf {
+    key: web_sys::CryptoKey,
+    hash: Hash,
+    salt: Vec<u8>,
+    info: Vec<u8>,
+}
+
+impl Hkdf {
+    /// Creates a new HKDF instance from an input key.
+    ///
+    /// The input key is imported as a non extractable key.
+    ///
+    /// # Arguments
+    /// * `data` - Input key material
+    /// * `hash` - Hash function used by the underlying HMAC
+    /// * `salt` - Salt, like a [`crate::salt::Salt`], can be empty
+    /// * `info` - Application specific context, can be empty
+    ///
+    /// # Returns
+    /// Result containing the Hkdf instance or an ImportKeyError
+    pub async fn from_key(
+        data: &(impl SecretInput + ?Sized),
+        hash: Hash,
+        salt: impl AsRef<[u8]>,
+        info: &[u8],
+    ) -> Result<Self, ImportKeyError> {
+        let key = crate::key::import_base_key(NAME, data.expose_bytes()).await?;
+        Ok(Self {
+            key,
+            hash,
+            salt: salt.as_ref().to_vec(),
+            info: info.to_vec(),
+        })
+    }
+
+    /// Creates a copy of this instance with a different info, to derive
+    /// several keys from the same input key.
+    pub fn with_info(&self, info: &[u8]) -> Self {
+        Self {
+            key: self.key.clone(),
+            hash: self.hash,
+            salt: self.salt.clone(),
+            info: info.to_vec(),
+        }
+    }
+
+    /// Hash function used by the underlying HMAC
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    /// Salt used for the derivation
+    pub fn salt(&self) -> &[u8] {
+        &self.salt
+    }
+
+    /// Application specific context used for the derivation
+    pub fn info(&self) -> &[u8] {
+        &self.info
+    }
+
+    fn params(&self) -> web_sys::HkdfParams {
+        web_sys::HkdfParams::new(
+            NAME,
+            &self.hash.name().into(),
+            &js_sys::Uint8Array::from(self.info.as_slice()),
+            &js_sys::Uint8Array::from(self.salt.as_slice()),
+        )
+    }
+
+    /// Derives raw bits from the input key
+    ///
+    /// # Arguments
+    /// * `length` - Number of bits to derive, should be a multiple of 8 and at
+    ///   most 255 times the hash output size
+    ///
+    /// # Returns
+    /// Result containing the derived bytes or a DeriveError
+    ///
+    /// # Errors
+    /// - `DeriveError::Operation` if the length is invalid
+    pub async fn derive_bits(&self, length: u32) -> Result<Vec<u8>, DeriveError> {
+        crate::derive::derive_bits(&self.params(), &self.key, length).await
+    }
+}
+
+impl crate::derive::private::Sealed for Hkdf {
+    fn derive_params(&self) -> Result<js_sys::Object, wasm_bindgen::JsValue> {
+        Ok(self.params().into())
+    }
+
+    fn base_key(&self) -> &web_sys::CryptoKey {
+        &self.key
+    }
+}
+
+impl KeyDerivation for Hkdf {}
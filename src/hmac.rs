@@ -0,0 +1,200 @@
+//! HMAC implementation
+//!
+//! ```rust,no_run
+//! use browser_crypto::hash::Hash;
+//! use browser_crypto::hmac::Hmac;
+//!
+//! async fn authenticate() -> Result<(), Box<dyn std::error::Error>> {
+//!     let hmac = Hmac::from_key(&[0u8; 32], Hash::Sha256).await?;
+//!     let tag = hmac.sign(b"Hello World!").await?;
+//!     assert!(hmac.verify(&tag, b"Hello World!").await?);
+//!     Ok(())
+//! }
+//! ```
+
+use crate::derive::{DeriveError, KeyDerivation};
+use crate::hash::Hash;
+use crate::input::SecretInput;
+use crate::jwk::Jwk;
+use crate::key::{ExportKeyError, GenerateKeyError, ImportKeyError, KeyFormat};
+use crate::signature::{SignError, SignatureAlgorithm, VerifyError};
+
+const NAME: &str = "HMAC";
+
+fn algorithm() -> Result<js_sys::Object, wasm_bindgen::JsValue> {
+    let algorithm = js_sys::Object::new();
+    js_sys::Reflect::set(&algorithm, &"name".into(), &NAME.into())?;
+    Ok(algorithm)
+}
+
+/// HMAC implementation, signing and verifying message authentication codes
+#[derive(Debug, Clone)]
+pub struct Hmac {
+    key: web_sys::CryptoKey,
+    hash: Hash,
+}
+
+impl Hmac {
+    /// Creates a new HMAC instance from a raw key.
+    ///
+    /// # Arguments
+    /// * `data` - Raw key bytes, it's recommended to use at least as many bytes
+    ///   as the hash output size
+    /// * `hash` - Hash function
+    ///
+    /// # Returns
+    /// Result containing the Hmac instance or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::InvalidKeyFormat` if the key is empty
+    pub async fn from_key(
+        data: &(impl SecretInput + ?Sized),
+        hash: Hash,
+    ) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let algorithm = web_sys::HmacImportParams::new(NAME, &hash.name().into());
+        let key = crate::key::import_key(
+            KeyFormat::Raw,
+            &algorithm,
+            data.expose_bytes(),
+            &["sign", "verify"],
+        )
+        .await?;
+        Ok(Self { key, hash })
+    }
+
+    /// Creates a new HMAC instance from a JSON Web Key.
+    ///
+    /// # Arguments
+    /// * `jwk` - JSON Web Key of type "oct"
+    /// * `hash` - Hash function, must match the `alg` of the JWK when defined
+    ///   (`HS256` for SHA-256)
+    ///
+    /// # Returns
+    /// Result containing the Hmac instance or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::InvalidKeyFormat` if the JWK is invalid or doesn't
+    ///   match the hash function
+    pub async fn from_jwk(jwk: &Jwk, hash: Hash) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let algorithm = web_sys::HmacImportParams::new(NAME, &hash.name().into());
+        let key = crate::key::import_jwk(&algorithm, jwk, &["sign", "verify"]).await?;
+        Ok(Self { key, hash })
+    }
+
+    /// Exports the key in the JSON Web Key format.
+    ///
+    /// # Returns
+    /// Result containing the JSON Web Key, of type "oct", or an ExportKeyError
+    pub async fn to_jwk(&self) -> Result<Jwk, ExportKeyError> {
+        crate::key::export_jwk(&self.key).await
+    }
+
+    /// Generates a new random key, of the hash block size.
+    ///
+    /// # Arguments
+    /// * `hash` - Hash function
+    ///
+    /// # Returns
+    /// Result containing the Hmac instance or a GenerateKeyError
+    pub async fn generate(hash: Hash) -> Result<Self, GenerateKeyError> {
+        crate::check_target()?;
+        let algorithm = web_sys::HmacKeyGenParams::new(NAME, &hash.name().into());
+        let key = crate::key::generate_key(&algorithm, true, &["sign", "verify"]).await?;
+        Ok(Self { key, hash })
+    }
+
+    /// Derives a non extractable key, of the hash block size, from a key
+    /// derivation like [`crate::hkdf::Hkdf`].
+    ///
+    /// # Arguments
+    /// * `derivation` - Key derivation algorithm and its parameters
+    /// * `hash` - Hash function
+    ///
+    /// # Returns
+    /// Result containing the Hmac instance or a DeriveError
+    ///
+    /// # Errors
+    /// - `DeriveError::Operation` if the derivation parameters are invalid
+    pub async fn derive_from<D: KeyDerivation>(
+        derivation: &D,
+        hash: Hash,
+    ) -> Result<Self, DeriveError> {
+        let algorithm = web_sys::HmacImportParams::new(NAME, &hash.name().into());
+        let key = crate::derive::derive_crypto_key(
+            &derivation.derive_params()?,
+            derivation.base_key(),
+            &algorithm,
+            false,
+            &["sign", "verify"],
+        )
+        .await?;
+        Ok(Self { key, hash })
+    }
+
+    /// Hash function used by this instance
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    /// Computes the authentication code of a payload
+    ///
+    /// # Arguments
+    /// * `payload` - Data to authenticate
+    ///
+    /// # Returns
+    /// Result containing the authentication code, of the hash output size, or
+    /// a SignError
+    pub async fn sign(&self, payload: &[u8]) -> Result<Vec<u8>, SignError> {
+        crate::signature::sign(&algorithm()?, &self.key, payload).await
+    }
+
+    /// Verifies the authentication code of a payload
+    ///
+    /// The comparison is done by the browser, in constant time.
+    ///
+    /// # Arguments
+    /// * `signature` - Authentication code to verify
+    /// * `payload` - Data that was authenticated
+    ///
+    /// # Returns
+    /// Result containing whether the authentication code is valid or a
+    /// VerifyError
+    pub async fn verify(&self, signature: &[u8], payload: &[u8]) -> Result<bool, VerifyError> {
+        crate::signature::verify(&algorithm()?, &self.key, signature, payload).await
+    }
+}
+
+/// The same key signs and verifies, the size of the authentication codes
+/// being the output size of its hash function.
+impl SignatureAlgorithm for Hmac {
+    const NAME: &'static str = NAME;
+    const SIGNATURE_SIZE: Option<usize> = None;
+    type SigningKey = Hmac;
+    type VerifyingKey = Hmac;
+
+    async fn sign(key: &Hmac, payload: &[u8]) -> Result<Vec<u8>, SignError> {
+        key.sign(payload).await
+    }
+
+    async fn verify(key: &Hmac, signature: &[u8], payload: &[u8]) -> Result<bool, VerifyError> {
+        key.verify(signature, payload).await
+    }
+}
+
+#[cfg(feature = "keystore")]
+impl crate::keystore::private::Sealed for Hmac {
+    fn to_entry(&self) -> Result<js_sys::Object, wasm_bindgen::JsValue> {
+        crate::keystore::key_hash_entry(&self.key, self.hash)
+    }
+
+    fn from_entry(entry: &wasm_bindgen::JsValue) -> Result<Self, crate::keystore::KeystoreError> {
+        let key = crate::keystore::check_entry_key(entry, "secret", NAME, &["sign", "verify"])?;
+        let hash = crate::keystore::entry_hash(entry)?;
+        Ok(Self { key, hash })
+    }
+}
+
+#[cfg(feature = "keystore")]
+impl crate::keystore::StorableKey for Hmac {}
@@ -0,0 +1,952 @@
+//! Hybrid Public Key Encryption (HPKE), as defined by RFC 9180
+//!
+//! A sender encrypts messages for the public key of a receiver, without any
+//! previous exchange. The sender gets an encapsulated key, to be sent along
+//! the ciphertexts, and a context to encrypt messages. The receiver opens
+//! the encapsulated key with its private key to get the matching context.
+//!
+//! The base mode and the authenticated mode, where the sender also proves the
+//! possession of its own private key, are supported. Both contexts can export
+//! secrets, to derive other keys from the same exchange.
+//!
+//! The KEMs rely on [`crate::ecdh`] and [`crate::x25519`], the KDF on
+//! [`crate::hmac`] and the AEADs on [`crate::aes_gcm`]. ChaCha20-Poly1305 is
+//! not available in the Web Crypto API.
+//!
+//! ```rust,no_run
+//! use browser_crypto::hpke::{Aead, Kdf, Kem, Suite};
+//!
+//! async fn exchange() -> Result<(), Box<dyn std::error::Error>> {
+//!     let suite = Suite::new(Kem::DhkemP256HkdfSha256, Kdf::HkdfSha256, Aead::Aes128Gcm);
+//!     let receiver = suite.generate_key_pair().await?;
+//!
+//!     let (enc, ciphertext) = suite
+//!         .seal(receiver.public_key(), b"info", b"aad", b"Hello World!")
+//!         .await?;
+//!     let plaintext = suite
+//!         .open(&enc, &receiver, b"info", b"aad", &ciphertext)
+//!         .await?;
+//!     assert_eq!(plaintext, b"Hello World!");
+//!     Ok(())
+//! }
+//! ```
+
+use wasm_bindgen::JsValue;
+
+use crate::aes_gcm::{Aes128Gcm, Aes256Gcm};
+use crate::algorithm::{Algorithm, DecryptionError, EncryptionError, Nonce, NonceError};
+use crate::curve::NamedCurve;
+use crate::derive::DeriveError;
+use crate::ecdh::{EcdhKeyPair, EcdhPrivateKey, EcdhPublicKey};
+use crate::hash::Hash;
+use crate::hmac::Hmac;
+use crate::key::{ExportKeyError, GenerateKeyError, ImportKeyError};
+use crate::signature::SignError;
+use crate::x25519::{X25519KeyPair, X25519PrivateKey, X25519PublicKey};
+use crate::ErrorCode;
+
+const VERSION_LABEL: &[u8] = b"HPKE-v1";
+
+const MODE_BASE: u8 = 0x00;
+const MODE_AUTH: u8 = 0x02;
+
+/// Size of the AEAD nonces in bytes
+const NONCE_SIZE: usize = 12;
+
+/// Errors that can occur when using HPKE.
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum HpkeError {
+    /// Indicates that a private key doesn't match the KEM of the suite.
+    #[error("the private key doesn't match the KEM")]
+    InvalidKey,
+    /// Indicates that a context encrypted or decrypted as many messages as
+    /// its sequence number allows.
+    #[error("message limit reached")]
+    MessageLimitReached,
+    /// Indicates that the requested length of an exported secret is larger
+    /// than 255 times the KDF output size.
+    #[error("invalid length for an exported secret")]
+    InvalidExportLength,
+    /// Indicates that a public or encapsulated key couldn't be imported.
+    #[error(transparent)]
+    Import(#[from] ImportKeyError),
+    /// Indicates that a public key couldn't be serialized.
+    #[error(transparent)]
+    Export(#[from] ExportKeyError),
+    /// Indicates that the ephemeral key pair couldn't be generated.
+    #[error(transparent)]
+    Generate(#[from] GenerateKeyError),
+    /// Indicates that the Diffie-Hellman exchange failed, like with a low
+    /// order X25519 public key.
+    #[error(transparent)]
+    Derive(#[from] DeriveError),
+    /// Indicates that the key schedule failed.
+    #[error(transparent)]
+    KeySchedule(#[from] SignError),
+    /// Indicates that a nonce couldn't be built.
+    #[error(transparent)]
+    Nonce(#[from] NonceError),
+    /// Indicates that the encryption failed.
+    #[error(transparent)]
+    Encryption(#[from] EncryptionError),
+    /// Indicates that the decryption failed, like when the ciphertext, the
+    /// additional data or the keys don't match.
+    #[error(transparent)]
+    Decryption(#[from] DecryptionError),
+}
+
+impl ErrorCode for HpkeError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidKey => "hpke.invalid_key",
+            Self::MessageLimitReached => "hpke.message_limit_reached",
+            Self::InvalidExportLength => "hpke.invalid_export_length",
+            Self::Import(inner) => inner.code(),
+            Self::Export(inner) => inner.code(),
+            Self::Generate(inner) => inner.code(),
+            Self::Derive(inner) => inner.code(),
+            Self::KeySchedule(inner) => inner.code(),
+            Self::Nonce(inner) => inner.code(),
+            Self::Encryption(inner) => inner.code(),
+            Self::Decryption(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<HpkeError> for JsValue {
+    fn from(value: HpkeError) -> Self {
+        crate::js_error("HpkeError", &value)
+    }
+}
+
+/// Key encapsulation mechanisms
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kem {
+    /// DHKEM(P-256, HKDF-SHA256)
+    DhkemP256HkdfSha256,
+    /// DHKEM(P-384, HKDF-SHA384)
+    DhkemP384HkdfSha384,
+    /// DHKEM(P-521, HKDF-SHA512)
+    DhkemP521HkdfSha512,
+    /// DHKEM(X25519, HKDF-SHA256), on browsers supporting X25519
+    DhkemX25519HkdfSha256,
+}
+
+impl Kem {
+    /// Identifier of the KEM, as registered by RFC 9180
+    pub fn id(&self) -> u16 {
+        match self {
+            Self::DhkemP256HkdfSha256 => 0x0010,
+            Self::DhkemP384HkdfSha384 => 0x0011,
+            Self::DhkemP521HkdfSha512 => 0x0012,
+            Self::DhkemX25519HkdfSha256 => 0x0020,
+        }
+    }
+
+    /// Size of a serialized public key, and of an encapsulated key, in bytes
+    pub fn public_key_size(&self) -> usize {
+        match self.curve() {
+            Some(curve) => 1 + 2 * curve.field_size(),
+            None => crate::x25519::PUBLIC_KEY_SIZE,
+        }
+    }
+
+    fn curve(&self) -> Option<NamedCurve> {
+        match self {
+            Self::DhkemP256HkdfSha256 => Some(NamedCurve::P256),
+            Self::DhkemP384HkdfSha384 => Some(NamedCurve::P384),
+            Self::DhkemP521HkdfSha512 => Some(NamedCurve::P521),
+            Self::DhkemX25519HkdfSha256 => None,
+        }
+    }
+
+    fn hash(&self) -> Hash {
+        match self {
+            Self::DhkemP256HkdfSha256 | Self::DhkemX25519HkdfSha256 => Hash::Sha256,
+            Self::DhkemP384HkdfSha384 => Hash::Sha384,
+            Self::DhkemP521HkdfSha512 => Hash::Sha512,
+        }
+    }
+
+    fn suite_id(&self) -> Vec<u8> {
+        [b"KEM".as_slice(), &self.id().to_be_bytes()].concat()
+    }
+
+    async fn import_public_key(&self, data: &[u8]) -> Result<PublicKey, ImportKeyError> {
+        if data.len() != self.public_key_size() {
+            return Err(ImportKeyError::InvalidKeyFormat);
+        }
+        Ok(match self.curve() {
+            Some(curve) => PublicKey::Ecdh(EcdhPublicKey::from_raw(data, curve).await?),
+            None => PublicKey::X25519(X25519PublicKey::from_raw(data).await?),
+        })
+    }
+
+    /// Computes the shared secret from the Diffie-Hellman outputs
+    async fn extract_and_expand(&self, dh: &[u8], context: &[u8]) -> Result<Vec<u8>, HpkeError> {
+        let kdf = Labeled::new(self.hash(), self.suite_id());
+        let eae_prk = crate::SecretBytes::from(kdf.extract(&[], b"eae_prk", dh).await?);
+        kdf.expand(
+            &eae_prk,
+            b"shared_secret",
+            context,
+            self.hash().output_size(),
+        )
+        .await
+    }
+}
+
+/// Key derivation functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kdf {
+    /// HKDF-SHA256
+    HkdfSha256,
+    /// HKDF-SHA384
+    HkdfSha384,
+    /// HKDF-SHA512
+    HkdfSha512,
+}
+
+impl Kdf {
+    /// Identifier of the KDF, as registered by RFC 9180
+    pub fn id(&self) -> u16 {
+        match self {
+            Self::HkdfSha256 => 0x0001,
+            Self::HkdfSha384 => 0x0002,
+            Self::HkdfSha512 => 0x0003,
+        }
+    }
+
+    fn hash(&self) -> Hash {
+        match self {
+            Self::HkdfSha256 => Hash::Sha256,
+            Self::HkdfSha384 => Hash::Sha384,
+            Self::HkdfSha512 => Hash::Sha512,
+        }
+    }
+}
+
+/// Authenticated encryption algorithms
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Aead {
+    /// AES-128-GCM
+    Aes128Gcm,
+    /// AES-256-GCM
+    Aes256Gcm,
+}
+
+impl Aead {
+    /// Identifier of the AEAD, as registered by RFC 9180
+    pub fn id(&self) -> u16 {
+        match self {
+            Self::Aes128Gcm => 0x0001,
+            Self::Aes256Gcm => 0x0002,
+        }
+    }
+
+    fn key_size(&self) -> usize {
+        match self {
+            Self::Aes128Gcm => Aes128Gcm::KEY_SIZE as usize,
+            Self::Aes256Gcm => Aes256Gcm::KEY_SIZE as usize,
+        }
+    }
+}
+
+/// Public key imported for a Diffie-Hellman exchange
+enum PublicKey {
+    Ecdh(EcdhPublicKey),
+    X25519(X25519PublicKey),
+}
+
+/// Private key of an HPKE key pair
+#[derive(Debug, Clone)]
+pub enum HpkePrivateKey {
+    /// Private key for the DHKEMs over NIST curves
+    Ecdh(EcdhPrivateKey),
+    /// Private key for DHKEM(X25519, HKDF-SHA256)
+    X25519(X25519PrivateKey),
+}
+
+impl From<EcdhPrivateKey> for HpkePrivateKey {
+    fn from(value: EcdhPrivateKey) -> Self {
+        Self::Ecdh(value)
+    }
+}
+
+impl From<X25519PrivateKey> for HpkePrivateKey {
+    fn from(value: X25519PrivateKey) -> Self {
+        Self::X25519(value)
+    }
+}
+
+impl HpkePrivateKey {
+    fn matches(&self, kem: Kem) -> bool {
+        match self {
+            Self::Ecdh(key) => kem.curve() == Some(key.curve()),
+            Self::X25519(_) => kem.curve().is_none(),
+        }
+    }
+
+    async fn diffie_hellman(&self, public_key: &PublicKey) -> Result<Vec<u8>, HpkeError> {
+        match (self, public_key) {
+            (Self::Ecdh(private), PublicKey::Ecdh(public)) => {
+                Ok(private.diffie_hellman(public).await?)
+            }
+            (Self::X25519(private), PublicKey::X25519(public)) => {
+                Ok(private.diffie_hellman(public).await?)
+            }
+            _ => Err(HpkeError::InvalidKey),
+        }
+    }
+}
+
+/// Key pair of a receiver, or of an authenticated sender
+///
+/// The public key is kept serialized, as it's part of the key schedule.
+#[derive(Debug, Clone)]
+pub struct HpkeKeyPair {
+    private_key: HpkePrivateKey,
+    public_key: Vec<u8>,
+}
+
+impl HpkeKeyPair {
+    /// Builds a key pair from a private key and its serialized public key
+    ///
+    /// Nothing checks that both keys match, a mismatching public key leads to
+    /// decryption errors.
+    ///
+    /// # Arguments
+    /// * `private_key` - ECDH or X25519 private key
+    /// * `public_key` - Serialized public key, the uncompressed point for the
+    ///   NIST curves or the raw 32 bytes for X25519
+    pub fn new(private_key: impl Into<HpkePrivateKey>, public_key: Vec<u8>) -> Self {
+        Self {
+            private_key: private_key.into(),
+            public_key,
+        }
+    }
+
+    /// Builds a key pair from an ECDH key pair
+    pub async fn from_ecdh(pair: EcdhKeyPair) -> Result<Self, ExportKeyError> {
+        let public_key = pair.public_key.to_raw().await?;
+        Ok(Self::new(pair.private_key, public_key))
+    }
+
+    /// Builds a key pair from an X25519 key pair
+    pub async fn from_x25519(pair: X25519KeyPair) -> Result<Self, ExportKeyError> {
+        let public_key = pair.public_key.to_raw().await?;
+        Ok(Self::new(pair.private_key, public_key))
+    }
+
+    /// Private key
+    pub fn private_key(&self) -> &HpkePrivateKey {
+        &self.private_key
+    }
+
+    /// Serialized public key, to share with the senders
+    pub fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+}
+
+/// Labeled HKDF of RFC 9180, built on HMAC as the Web Crypto API can't run the
+/// extract and expand steps separately
+struct Labeled {
+    hash: Hash,
+    suite_id: Vec<u8>,
+}
+
+impl Labeled {
+    fn new(hash: Hash, suite_id: Vec<u8>) -> Self {
+        Self { hash, suite_id }
+    }
+
+    async fn hmac(&self, key: &[u8], data: &[u8]) -> Result<Vec<u8>, HpkeError> {
+        // HMAC keys can't be empty, an empty salt is a string of zeros
+        let zeros = vec![0u8; self.hash.output_size()];
+        let key = if key.is_empty() { &zeros } else { key };
+        let hmac = Hmac::from_key(key, self.hash).await?;
+        Ok(hmac.sign(data).await?)
+    }
+
+    async fn extract(&self, salt: &[u8], label: &[u8], ikm: &[u8]) -> Result<Vec<u8>, HpkeError> {
+        let labeled_ikm =
+            crate::SecretBytes::from([VERSION_LABEL, &self.suite_id, label, ikm].concat());
+        self.hmac(salt, &labeled_ikm).await
+    }
+
+    async fn expand(
+        &self,
+        prk: &[u8],
+        label: &[u8],
+        info: &[u8],
+        length: usize,
+    ) -> Result<Vec<u8>, HpkeError> {
+        if length > 255 * self.hash.output_size() {
+            return Err(HpkeError::InvalidExportLength);
+        }
+        let labeled_info = [
+            &(length as u16).to_be_bytes(),
+            VERSION_LABEL,
+            &self.suite_id,
+            label,
+            info,
+        ]
+        .concat();
+        let mut output = Vec::with_capacity(length);
+        let mut block = Vec::new();
+        let mut counter = 1u8;
+        while output.len() < length {
+            let input = [block.as_slice(), &labeled_info, &[counter]].concat();
+            block = self.hmac(prk, &input).await?;
+            output.extend_from_slice(&block);
+            counter += 1;
+        }
+        output.truncate(length);
+        Ok(output)
+    }
+}
+
+/// Cipher suite, made of a KEM, a KDF and an AEAD
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Suite {
+    kem: Kem,
+    kdf: Kdf,
+    aead: Aead,
+}
+
+impl Suite {
+    /// Creates a cipher suite
+    pub const fn new(kem: Kem, kdf: Kdf, aead: Aead) -> Self {
+        Self { kem, kdf, aead }
+    }
+
+    /// Key encapsulation mechanism
+    pub fn kem(&self) -> Kem {
+        self.kem
+    }
+
+    /// Key derivation function
+    pub fn kdf(&self) -> Kdf {
+        self.kdf
+    }
+
+    /// Authenticated encryption algorithm
+    pub fn aead(&self) -> Aead {
+        self.aead
+    }
+
+    fn labeled(&self) -> Labeled {
+        let suite_id = [
+            b"HPKE".as_slice(),
+            &self.kem.id().to_be_bytes(),
+            &self.kdf.id().to_be_bytes(),
+            &self.aead.id().to_be_bytes(),
+        ]
+        .concat();
+        Labeled::new(self.kdf.hash(), suite_id)
+    }
+
+    /// Generates a key pair for the KEM of the suite
+    ///
+    /// # Returns
+    /// Result containing the key pair or an HpkeError
+    ///
+    /// # Errors
+    /// - `HpkeError::Generate` if the browser doesn't support the curve
+    pub async fn generate_key_pair(&self) -> Result<HpkeKeyPair, HpkeError> {
+        Ok(match self.kem.curve() {
+            Some(curve) => HpkeKeyPair::from_ecdh(EcdhKeyPair::generate(curve).await?).await?,
+            None => HpkeKeyPair::from_x25519(X25519KeyPair::generate().await?).await?,
+        })
+    }
+
+    fn check_key_pair(&self, pair: &HpkeKeyPair) -> Result<(), HpkeError> {
+        if pair.private_key.matches(self.kem) {
+            Ok(())
+        } else {
+            Err(HpkeError::InvalidKey)
+        }
+    }
+
+    /// Generates an ephemeral key pair and computes the shared secret with the
+    /// receiver, returning the shared secret and the encapsulated key
+    async fn encap(
+        &self,
+        pk_r: &[u8],
+        sender: Option<&HpkeKeyPair>,
+    ) -> Result<(crate::SecretBytes, Vec<u8>), HpkeError> {
+        let public_key = self.kem.import_public_key(pk_r).await?;
+        let ephemeral = self.generate_key_pair().await?;
+        let mut dh =
+            crate::SecretBytes::from(ephemeral.private_key.diffie_hellman(&public_key).await?);
+        let mut context = [ephemeral.public_key.as_slice(), pk_r].concat();
+        if let Some(sender) = sender {
+            self.check_key_pair(sender)?;
+            dh.extend(sender.private_key.diffie_hellman(&public_key).await?);
+            context.extend_from_slice(&sender.public_key);
+        }
+        let shared_secret = self.kem.extract_and_expand(&dh, &context).await?;
+        Ok((shared_secret.into(), ephemeral.public_key))
+    }
+
+    /// Computes the shared secret from the encapsulated key
+    async fn decap(
+        &self,
+        enc: &[u8],
+        receiver: &HpkeKeyPair,
+        pk_s: Option<&[u8]>,
+    ) -> Result<crate::SecretBytes, HpkeError> {
+        self.check_key_pair(receiver)?;
+        let ephemeral = self.kem.import_public_key(enc).await?;
+        let mut dh =
+            crate::SecretBytes::from(receiver.private_key.diffie_hellman(&ephemeral).await?);
+        let mut context = [enc, &receiver.public_key].concat();
+        if let Some(pk_s) = pk_s {
+            let sender = self.kem.import_public_key(pk_s).await?;
+            dh.extend(receiver.private_key.diffie_hellman(&sender).await?);
+            context.extend_from_slice(pk_s);
+        }
+        Ok(self.kem.extract_and_expand(&dh, &context).await?.into())
+    }
+
+    async fn key_schedule(
+        &self,
+        mode: u8,
+        shared_secret: &[u8],
+        info: &[u8],
+    ) -> Result<Context, HpkeError> {
+        let kdf = self.labeled();
+        // no pre-shared key in the base and auth modes
+        let psk_id_hash = kdf.extract(&[], b"psk_id_hash", &[]).await?;
+        let info_hash = kdf.extract(&[], b"info_hash", info).await?;
+        let context = [&[mode], psk_id_hash.as_slice(), &info_hash].concat();
+
+        let secret = crate::SecretBytes::from(kdf.extract(shared_secret, b"secret", &[]).await?);
+        let key = crate::SecretBytes::from(
+            kdf.expand(&secret, b"key", &context, self.aead.key_size())
+                .await?,
+        );
+        let base_nonce = kdf
+            .expand(&secret, b"base_nonce", &context, NONCE_SIZE)
+            .await?
+            .into();
+        let exporter_secret = kdf
+            .expand(&secret, b"exp", &context, self.kdf.hash().output_size())
+            .await?
+            .into();
+
+        let cipher = match self.aead {
+            Aead::Aes128Gcm => Cipher::Aes128(Aes128Gcm::from_key(key.as_slice()).await?),
+            Aead::Aes256Gcm => Cipher::Aes256(Aes256Gcm::from_key(key.as_slice()).await?),
+        };
+        Ok(Context {
+            kdf,
+            cipher,
+            base_nonce,
+            sequence: 0,
+            exporter_secret,
+        })
+    }
+
+    /// Sets up a context to encrypt messages for a receiver, in the base mode
+    ///
+    /// # Arguments
+    /// * `pk_r` - Serialized public key of the receiver
+    /// * `info` - Application specific information, shared with the receiver
+    ///
+    /// # Returns
+    /// Result containing the encapsulated key, to send to the receiver, and the
+    /// sender context, or an HpkeError
+    ///
+    /// # Errors
+    /// - `HpkeError::Import` if the public key is not valid for the KEM
+    pub async fn setup_base_sender(
+        &self,
+        pk_r: &[u8],
+        info: &[u8],
+    ) -> Result<(Vec<u8>, SenderContext), HpkeError> {
+        let (shared_secret, enc) = self.encap(pk_r, None).await?;
+        let context = self.key_schedule(MODE_BASE, &shared_secret, info).await?;
+        Ok((enc, SenderContext(context)))
+    }
+
+    /// Sets up a context to decrypt messages from a sender, in the base mode
+    ///
+    /// # Arguments
+    /// * `enc` - Encapsulated key, received from the sender
+    /// * `receiver` - Key pair of the receiver
+    /// * `info` - Application specific information, shared with the sender
+    ///
+    /// # Returns
+    /// Result containing the receiver context or an HpkeError
+    ///
+    /// # Errors
+    /// - `HpkeError::InvalidKey` if the key pair doesn't match the KEM
+    /// - `HpkeError::Import` if the encapsulated key is not valid for the KEM
+    pub async fn setup_base_receiver(
+        &self,
+        enc: &[u8],
+        receiver: &HpkeKeyPair,
+        info: &[u8],
+    ) -> Result<ReceiverContext, HpkeError> {
+        let shared_secret = self.decap(enc, receiver, None).await?;
+        let context = self.key_schedule(MODE_BASE, &shared_secret, info).await?;
+        Ok(ReceiverContext(context))
+    }
+
+    /// Sets up a context to encrypt messages for a receiver, in the
+    /// authenticated mode
+    ///
+    /// # Arguments
+    /// * `pk_r` - Serialized public key of the receiver
+    /// * `info` - Application specific information, shared with the receiver
+    /// * `sender` - Key pair of the sender, its public key must be known by the
+    ///   receiver
+    ///
+    /// # Returns
+    /// Result containing the encapsulated key, to send to the receiver, and the
+    /// sender context, or an HpkeError
+    ///
+    /// # Errors
+    /// - `HpkeError::InvalidKey` if the key pair doesn't match the KEM
+    /// - `HpkeError::Import` if the public key is not valid for the KEM
+    pub async fn setup_auth_sender(
+        &self,
+        pk_r: &[u8],
+        info: &[u8],
+        sender: &HpkeKeyPair,
+    ) -> Result<(Vec<u8>, SenderContext), HpkeError> {
+        let (shared_secret, enc) = self.encap(pk_r, Some(sender)).await?;
+        let context = self.key_schedule(MODE_AUTH, &shared_secret, info).await?;
+        Ok((enc, SenderContext(context)))
+    }
+
+    /// Sets up a context to decrypt messages from an authenticated sender
+    ///
+    /// # Arguments
+    /// * `enc` - Encapsulated key, received from the sender
+    /// * `receiver` - Key pair of the receiver
+    /// * `info` - Application specific information, shared with the sender
+    /// * `pk_s` - Serialized public key of the sender
+    ///
+    /// # Returns
+    /// Result containing the receiver context or an HpkeError
+    ///
+    /// # Errors
+    /// - `HpkeError::InvalidKey` if the key pair doesn't match the KEM
+    /// - `HpkeError::Import` if a public key is not valid for the KEM
+    pub async fn setup_auth_receiver(
+        &self,
+        enc: &[u8],
+        receiver: &HpkeKeyPair,
+        info: &[u8],
+        pk_s: &[u8],
+    ) -> Result<ReceiverContext, HpkeError> {
+        let shared_secret = self.decap(enc, receiver, Some(pk_s)).await?;
+        let context = self.key_schedule(MODE_AUTH, &shared_secret, info).await?;
+        Ok(ReceiverContext(context))
+    }
+
+    /// Encrypts a single message for a receiver, in the base mode
+    ///
+    /// # Arguments
+    /// * `pk_r` - Serialized public key of the receiver
+    /// * `info` - Application specific information, shared with the receiver
+    /// * `aad` - Additional data, authenticated but not encrypted
+    /// * `plaintext` - Message to encrypt
+    ///
+    /// # Returns
+    /// Result containing the encapsulated key and the ciphertext, or an
+    /// HpkeError
+    pub async fn seal(
+        &self,
+        pk_r: &[u8],
+        info: &[u8],
+        aad: &[u8],
+        plaintext: &[u8],
+    ) -> Result<(Vec<u8>, Vec<u8>), HpkeError> {
+        let (enc, mut context) = self.setup_base_sender(pk_r, info).await?;
+        let ciphertext = context.seal(aad, plaintext).await?;
+        Ok((enc, ciphertext))
+    }
+
+    /// Decrypts a single message from a sender, in the base mode
+    ///
+    /// # Arguments
+    /// * `enc` - Encapsulated key, received from the sender
+    /// * `receiver` - Key pair of the receiver
+    /// * `info` - Application specific information, shared with the sender
+    /// * `aad` - Additional data used for encryption
+    /// * `ciphertext` - Message to decrypt
+    ///
+    /// # Returns
+    /// Result containing the plaintext or an HpkeError
+    ///
+    /// # Errors
+    /// - `HpkeError::Decryption` if the ciphertext, the additional data or the
+    ///   info don't match
+    pub async fn open(
+        &self,
+        enc: &[u8],
+        receiver: &HpkeKeyPair,
+        info: &[u8],
+        aad: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, HpkeError> {
+        let mut context = self.setup_base_receiver(enc, receiver, info).await?;
+        context.open(aad, ciphertext).await
+    }
+
+    /// Encrypts a single message for a receiver, in the authenticated mode
+    ///
+    /// See [`Suite::seal`] and [`Suite::setup_auth_sender`].
+    pub async fn seal_auth(
+        &self,
+        pk_r: &[u8],
+        info: &[u8],
+        aad: &[u8],
+        plaintext: &[u8],
+        sender: &HpkeKeyPair,
+    ) -> Result<(Vec<u8>, Vec<u8>), HpkeError> {
+        let (enc, mut context) = self.setup_auth_sender(pk_r, info, sender).await?;
+        let ciphertext = context.seal(aad, plaintext).await?;
+        Ok((enc, ciphertext))
+    }
+
+    /// Decrypts a single message from an authenticated sender
+    ///
+    /// See [`Suite::open`] and [`Suite::setup_auth_receiver`].
+    pub async fn open_auth(
+        &self,
+        enc: &[u8],
+        receiver: &HpkeKeyPair,
+        info: &[u8],
+        aad: &[u8],
+        ciphertext: &[u8],
+        pk_s: &[u8],
+    ) -> Result<Vec<u8>, HpkeError> {
+        let mut context = self.setup_auth_receiver(enc, receiver, info, pk_s).await?;
+        context.open(aad, ciphertext).await
+    }
+
+    /// Exports a secret shared with a receiver, in the base mode, without
+    /// encrypting any message
+    ///
+    /// # Arguments
+    /// * `pk_r` - Serialized public key of the receiver
+    /// * `info` - Application specific information, shared with the receiver
+    /// * `exporter_context` - Context of the exported secret
+    /// * `length` - Size of the exported secret in bytes
+    ///
+    /// # Returns
+    /// Result containing the encapsulated key and the secret, or an HpkeError
+    pub async fn send_export(
+        &self,
+        pk_r: &[u8],
+        info: &[u8],
+        exporter_context: &[u8],
+        length: usize,
+    ) -> Result<(Vec<u8>, Vec<u8>), HpkeError> {
+        let (enc, context) = self.setup_base_sender(pk_r, info).await?;
+        let secret = context.export(exporter_context, length).await?;
+        Ok((enc, secret))
+    }
+
+    /// Exports a secret shared with a sender, in the base mode
+    ///
+    /// See [`Suite::send_export`].
+    pub async fn receive_export(
+        &self,
+        enc: &[u8],
+        receiver: &HpkeKeyPair,
+        info: &[u8],
+        exporter_context: &[u8],
+        length: usize,
+    ) -> Result<Vec<u8>, HpkeError> {
+        let context = self.setup_base_receiver(enc, receiver, info).await?;
+        context.export(exporter_context, length).await
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Cipher {
+    Aes128(Aes128Gcm),
+    Aes256(Aes256Gcm),
+}
+
+/// Encryption context shared by the sender and the receiver
+struct Context {
+    kdf: Labeled,
+    cipher: Cipher,
+    base_nonce: crate::SecretBytes,
+    sequence: u64,
+    exporter_secret: crate::SecretBytes,
+}
+
+impl Context {
+    /// Computes the nonce of the current message and increments the sequence
+    /// number
+    fn next_nonce(&mut self) -> Result<Vec<u8>, HpkeError> {
+        let next = self
+            .sequence
+            .checked_add(1)
+            .ok_or(HpkeError::MessageLimitReached)?;
+        let mut nonce = self.base_nonce.to_vec();
+        let sequence = self.sequence.to_be_bytes();
+        for (byte, seq) in nonce[NONCE_SIZE - sequence.len()..]
+            .iter_mut()
+            .zip(sequence)
+        {
+            *byte ^= seq;
+        }
+        self.sequence = next;
+        Ok(nonce)
+    }
+
+    async fn seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, HpkeError> {
+        let nonce = self.next_nonce()?;
+        Ok(match &self.cipher {
+            Cipher::Aes128(cipher) => {
+                let nonce = Nonce::from_slice(&nonce)?;
+                cipher.encrypt_with_aad(&nonce, aad, plaintext).await?
+            }
+            Cipher::Aes256(cipher) => {
+                let nonce = Nonce::from_slice(&nonce)?;
+                cipher.encrypt_with_aad(&nonce, aad, plaintext).await?
+            }
+        })
+    }
+
+    async fn open(&mut self, aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, HpkeError> {
+        let nonce = self.next_nonce()?;
+        let plaintext = match &self.cipher {
+            Cipher::Aes128(cipher) => {
+                let nonce = Nonce::from_slice(&nonce)?;
+                cipher.decrypt_with_aad(&nonce, aad, ciphertext).await
+            }
+            Cipher::Aes256(cipher) => {
+                let nonce = Nonce::from_slice(&nonce)?;
+                cipher.decrypt_with_aad(&nonce, aad, ciphertext).await
+            }
+        };
+        if plaintext.is_err() {
+            // the sequence number only moves forward on success
+            self.sequence -= 1;
+        }
+        Ok(plaintext?)
+    }
+
+    async fn export(&self, exporter_context: &[u8], length: usize) -> Result<Vec<u8>, HpkeError> {
+        self.kdf
+            .expand(&self.exporter_secret, b"sec", exporter_context, length)
+            .await
+    }
+}
+
+/// Context of a sender, encrypting messages for a single receiver
+///
+/// Messages must be decrypted in the order they were encrypted.
+pub struct SenderContext(Context);
+
+impl SenderContext {
+    /// Encrypts the next message
+    ///
+    /// # Arguments
+    /// * `aad` - Additional data, authenticated but not encrypted
+    /// * `plaintext` - Message to encrypt
+    ///
+    /// # Returns
+    /// Result containing the ciphertext or an HpkeError
+    ///
+    /// # Errors
+    /// - `HpkeError::MessageLimitReached` if the sequence number overflows
+    pub async fn seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, HpkeError> {
+        self.0.seal(aad, plaintext).await
+    }
+
+    /// Exports a secret, derived from the context
+    ///
+    /// # Arguments
+    /// * `exporter_context` - Context of the exported secret
+    /// * `length` - Size of the exported secret in bytes
+    ///
+    /// # Errors
+    /// - `HpkeError::InvalidExportLength` if the length is larger than 255
+    ///   times the KDF output size
+    pub async fn export(
+        &self,
+        exporter_context: &[u8],
+        length: usize,
+    ) -> Result<Vec<u8>, HpkeError> {
+        self.0.export(exporter_context, length).await
+    }
+}
+
+impl std::fmt::Debug for SenderContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SenderContext")
+            .field("sequence", &self.0.sequence)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Context of a receiver, decrypting messages from a single sender
+pub struct ReceiverContext(Context);
+
+impl ReceiverContext {
+    /// Decrypts the next message
+    ///
+    /// A failed decryption doesn't move to the next message.
+    ///
+    /// # Arguments
+    /// * `aad` - Additional data used for encryption
+    /// * `ciphertext` - Message to decrypt
+    ///
+    /// # Returns
+    /// Result containing the plaintext or an HpkeError
+    ///
+    /// # Errors
+    /// - `HpkeError::Decryption` if the message has been altered, or is not the
+    ///   next one
+    pub async fn open(&mut self, aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, HpkeError> {
+        self.0.open(aad, ciphertext).await
+    }
+
+    /// Exports a secret, derived from the context
+    ///
+    /// The secret matches the one exported by the sender with the same
+    /// arguments.
+    ///
+    /// # Arguments
+    /// * `exporter_context` - Context of the exported secret
+    /// * `length` - Size of the exported secret in bytes
+    ///
+    /// # Errors
+    /// - `HpkeError::InvalidExportLength` if the length is larger than 255
+    ///   times the KDF output size
+    pub async fn export(
+        &self,
+        exporter_context: &[u8],
+        length: usize,
+    ) -> Result<Vec<u8>, HpkeError> {
+        self.0.export(exporter_context, length).await
+    }
+}
+
+impl std::fmt::Debug for ReceiverContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReceiverContext")
+            .field("sequence", &self.0.sequence)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The base nonce and the exporter secret are overwritten when the context is
+/// dropped, the AEAD key being held by the browser.
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for SenderContext {}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for ReceiverContext {}
@@ -0,0 +1,203 @@
+//! Inputs of the cryptographic operations
+//!
+//! [`CryptoInput`] accepts either Rust bytes or JavaScript buffers, like the
+//! body of a `fetch` response, the result of a `FileReader` or a message
+//! received with `postMessage`, so they don't have to be copied into a
+//! `Vec<u8>` first. The buffers shared between threads, with a
+//! `SharedArrayBuffer`, are accepted too, and copied before being passed to
+//! the Web Crypto API, which rejects them.
+//!
+//! Keys and passphrases are accepted through [`SecretInput`], as bytes or
+//! strings. With the `secrecy` feature enabled, they can also be passed in a
+//! `secrecy::SecretBox`, like a `SecretSlice<u8>` or a `SecretString`, so they
+//! stay wrapped in a type redacting them and zeroizing them on drop until they
+//! reach the Web Crypto API.
+//!
+//! ```rust,no_run
+//! use browser_crypto::aes_gcm::Aes256Gcm;
+//! use browser_crypto::algorithm::Algorithm;
+//!
+//! async fn encrypt(buffer: js_sys::ArrayBuffer) -> Result<(), Box<dyn std::error::Error>> {
+//!     let cipher = Aes256Gcm::from_key(&[0u8; 32]).await?;
+//!     let nonce = Aes256Gcm::generate_nonce()?;
+//!     let encrypted = cipher.encrypt_js(&nonce, &buffer).await?;
+//!     let decrypted = cipher.decrypt_js(&nonce, &encrypted).await?;
+//!     assert_eq!(decrypted.length(), buffer.byte_length());
+//!     Ok(())
+//! }
+//! ```
+
+use js_sys::{ArrayBuffer, DataView, SharedArrayBuffer, Uint8Array};
+use wasm_bindgen::JsCast;
+
+/// Bytes passed to a cryptographic operation, held by Rust or by JavaScript
+#[derive(Debug, Clone)]
+pub enum CryptoInput<'a> {
+    /// Bytes held by Rust
+    Bytes(&'a [u8]),
+    /// Bytes held by JavaScript
+    Array(Uint8Array),
+}
+
+impl CryptoInput<'_> {
+    /// Number of bytes
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Bytes(bytes) => bytes.len(),
+            Self::Array(array) => array.length() as usize,
+        }
+    }
+
+    /// Whether there are no bytes
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Copies the bytes into a vector
+    pub fn to_vec(&self) -> Vec<u8> {
+        match self {
+            Self::Bytes(bytes) => bytes.to_vec(),
+            Self::Array(array) => crate::array_to_vec(array),
+        }
+    }
+
+    /// Passes the bytes to a Web Crypto function, see [`crate::with_bytes`]
+    ///
+    /// The Web Crypto API rejects the views of a `SharedArrayBuffer`, those
+    /// are copied into a new buffer first.
+    pub(crate) fn with_array<R>(&self, call: impl FnOnce(&Uint8Array) -> R) -> R {
+        match self {
+            Self::Bytes(bytes) => crate::with_bytes(bytes, call),
+            Self::Array(array) if array.buffer().is_instance_of::<ArrayBuffer>() => call(array),
+            Self::Array(array) => {
+                let copy = Uint8Array::new_with_length(array.length());
+                copy.set(array, 0);
+                call(&copy)
+            }
+        }
+    }
+}
+
+impl<'a> From<&'a [u8]> for CryptoInput<'a> {
+    fn from(value: &'a [u8]) -> Self {
+        Self::Bytes(value)
+    }
+}
+
+impl<'a, const N: usize> From<&'a [u8; N]> for CryptoInput<'a> {
+    fn from(value: &'a [u8; N]) -> Self {
+        Self::Bytes(value.as_slice())
+    }
+}
+
+impl<'a> From<&'a Vec<u8>> for CryptoInput<'a> {
+    fn from(value: &'a Vec<u8>) -> Self {
+        Self::Bytes(value.as_slice())
+    }
+}
+
+impl From<Uint8Array> for CryptoInput<'_> {
+    fn from(value: Uint8Array) -> Self {
+        Self::Array(value)
+    }
+}
+
+impl From<&Uint8Array> for CryptoInput<'_> {
+    fn from(value: &Uint8Array) -> Self {
+        Self::Array(value.clone())
+    }
+}
+
+impl From<&ArrayBuffer> for CryptoInput<'_> {
+    fn from(value: &ArrayBuffer) -> Self {
+        Self::Array(Uint8Array::new(value))
+    }
+}
+
+impl From<ArrayBuffer> for CryptoInput<'_> {
+    fn from(value: ArrayBuffer) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&SharedArrayBuffer> for CryptoInput<'_> {
+    fn from(value: &SharedArrayBuffer) -> Self {
+        Self::Array(Uint8Array::new(value))
+    }
+}
+
+impl From<&DataView> for CryptoInput<'_> {
+    fn from(value: &DataView) -> Self {
+        Self::Array(Uint8Array::new_with_byte_offset_and_length(
+            &value.buffer(),
+            value.byte_offset() as u32,
+            value.byte_length() as u32,
+        ))
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<'a> From<&'a bytes::Bytes> for CryptoInput<'a> {
+    fn from(value: &'a bytes::Bytes) -> Self {
+        Self::Bytes(value.as_ref())
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<'a> From<&'a bytes::BytesMut> for CryptoInput<'a> {
+    fn from(value: &'a bytes::BytesMut) -> Self {
+        Self::Bytes(value.as_ref())
+    }
+}
+
+/// Secret bytes passed to a key import or a password-based operation
+pub trait SecretInput {
+    /// Exposes the secret bytes, to pass them to the Web Crypto API
+    fn expose_bytes(&self) -> &[u8];
+}
+
+impl<T: SecretInput + ?Sized> SecretInput for &T {
+    fn expose_bytes(&self) -> &[u8] {
+        (**self).expose_bytes()
+    }
+}
+
+impl SecretInput for [u8] {
+    fn expose_bytes(&self) -> &[u8] {
+        self
+    }
+}
+
+impl<const N: usize> SecretInput for [u8; N] {
+    fn expose_bytes(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl SecretInput for Vec<u8> {
+    fn expose_bytes(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl SecretInput for str {
+    fn expose_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl SecretInput for String {
+    fn expose_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+#[cfg(feature = "secrecy")]
+impl<S> SecretInput for secrecy::SecretBox<S>
+where
+    S: SecretInput + secrecy::zeroize::Zeroize + ?Sized,
+{
+    fn expose_bytes(&self) -> &[u8] {
+        secrecy::ExposeSecret::expose_secret(self).expose_bytes()
+    }
+}
@@ -0,0 +1,145 @@
+//! Instrumentation of the Web Crypto operations
+//!
+//! With the `tracing` feature enabled, the Web Crypto operations run in a span
+//! recording the operation, the algorithm and the size of the input, and end
+//! with an event recording their duration and, on failure, the code of the
+//! error. Neither the data nor the key material is ever recorded.
+//!
+//! With the `metrics` feature enabled, the same operations are timed and
+//! handed to the recorder registered with [`crate::metrics::set_metrics`].
+//!
+//! With the `audit` feature enabled, their outcome is handed to the sink
+//! registered with [`crate::audit::set_audit_sink`].
+//!
+//! With the `mock` feature enabled, the backend registered with
+//! [`crate::mock::set_backend`] can fail them before they run.
+
+use crate::context::Operation;
+use crate::ErrorCode;
+
+/// Runs a Web Crypto operation, observing its outcome
+///
+/// # Arguments
+/// * `operation` - Operation being run
+/// * `algorithm` - Name of the algorithm, like "AES-GCM"
+/// * `size` - Size of the input in bytes, when relevant
+/// * `future` - Operation itself
+pub(crate) async fn observe<T, E, F>(
+    operation: Operation,
+    algorithm: &str,
+    size: Option<usize>,
+    future: F,
+) -> Result<T, E>
+where
+    F: std::future::Future<Output = Result<T, E>>,
+    E: ErrorCode + From<wasm_bindgen::JsValue> + From<crate::Error>,
+{
+    #[cfg(feature = "mock")]
+    let future = async {
+        match crate::mock::failure(operation, algorithm) {
+            Some(err) => Err(err),
+            None => future.await,
+        }
+    };
+    #[cfg(feature = "audit")]
+    let kid = crate::audit::current_kid();
+    #[cfg(any(feature = "tracing", feature = "metrics"))]
+    let start = now();
+    #[cfg(feature = "tracing")]
+    let span = tracing::debug_span!("webcrypto", operation = operation.name(), algorithm, size);
+    #[cfg(feature = "tracing")]
+    let result = tracing::Instrument::instrument(future, span.clone()).await;
+    #[cfg(not(feature = "tracing"))]
+    let result = future.await;
+
+    #[cfg(any(feature = "tracing", feature = "metrics"))]
+    let duration_ms = now() - start;
+    #[cfg(feature = "tracing")]
+    {
+        let _entered = span.enter();
+        match &result {
+            Ok(_) => tracing::debug!(duration_ms, "operation succeeded"),
+            Err(err) => tracing::warn!(duration_ms, error.code = err.code(), "operation failed"),
+        }
+    }
+    #[cfg(feature = "metrics")]
+    crate::metrics::record(&crate::metrics::Measurement {
+        operation,
+        algorithm,
+        size,
+        duration_ms,
+        error_code: result.as_ref().err().map(ErrorCode::code),
+    });
+    #[cfg(feature = "audit")]
+    crate::audit::record(&crate::audit::AuditRecord {
+        operation,
+        algorithm,
+        kid: kid.as_deref(),
+        payload_len: size,
+        outcome: match &result {
+            Ok(_) => crate::audit::Outcome::Success,
+            Err(err) => crate::audit::Outcome::Failure { code: err.code() },
+        },
+    });
+    #[cfg(not(any(feature = "tracing", feature = "metrics", feature = "audit")))]
+    let _ = (operation, algorithm, size);
+    result
+}
+
+/// Attributes the key ID of a tagged key, if any, to the operations of a
+/// future
+#[cfg(feature = "audit")]
+pub(crate) fn tagged<F: std::future::Future>(
+    kid: Option<&str>,
+    future: F,
+) -> impl std::future::Future<Output = F::Output> {
+    crate::audit::with_optional_kid(kid, future)
+}
+
+/// Attributes the key ID of a tagged key, if any, to the operations of a
+/// future
+#[cfg(not(feature = "audit"))]
+pub(crate) fn tagged<F: std::future::Future>(
+    _kid: Option<&str>,
+    future: F,
+) -> impl std::future::Future<Output = F::Output> {
+    future
+}
+
+/// Name of the algorithm described by Web Crypto parameters
+pub(crate) fn algorithm_name(algorithm: &js_sys::Object) -> String {
+    js_sys::Reflect::get(algorithm, &"name".into())
+        .ok()
+        .and_then(|name| name.as_string())
+        .unwrap_or_default()
+}
+
+/// Name of the algorithm of a key
+pub(crate) fn key_algorithm_name(key: &web_sys::CryptoKey) -> String {
+    key.algorithm()
+        .map(|algorithm| algorithm_name(&algorithm))
+        .unwrap_or_default()
+}
+
+/// Milliseconds elapsed since the origin of the page or the worker, with
+/// `performance.now()`, or since the epoch when it's not available
+#[cfg(all(any(feature = "tracing", feature = "metrics"), target_arch = "wasm32"))]
+pub(crate) fn now() -> f64 {
+    use wasm_bindgen::JsCast;
+
+    js_sys::Reflect::get(&js_sys::global(), &"performance".into())
+        .ok()
+        .and_then(|value| value.dyn_into::<web_sys::Performance>().ok())
+        .map_or_else(js_sys::Date::now, |performance| performance.now())
+}
+
+/// Milliseconds elapsed since the epoch, outside of WebAssembly
+#[cfg(all(
+    any(feature = "tracing", feature = "metrics"),
+    not(target_arch = "wasm32")
+))]
+pub(crate) fn now() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0.0, |elapsed| elapsed.as_secs_f64() * 1000.0)
+}
@@ -0,0 +1,52 @@
+//! Concurrent execution of futures
+//!
+//! The Web Crypto operations run in the background once started, awaiting
+//! them one after the other leaves the browser idle between them. This polls a
+//! set of futures together, like `Promise.all`, without depending on an async
+//! runtime.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Poll;
+
+/// Runs the futures concurrently, at most `limit` at a time, returning their
+/// outputs in order
+///
+/// A limit of 0 is considered as 1. The futures are only started once a
+/// previous one is done, so a large batch doesn't start all its operations at
+/// once.
+pub(crate) async fn join_all<F: Future>(
+    futures: impl IntoIterator<Item = F>,
+    limit: usize,
+) -> Vec<F::Output> {
+    let limit = limit.max(1);
+    let mut queue = futures.into_iter().enumerate();
+    let mut running: Vec<(usize, Pin<Box<F>>)> = Vec::with_capacity(limit);
+    let mut outputs: Vec<Option<F::Output>> = Vec::new();
+    std::future::poll_fn(|cx| loop {
+        while running.len() < limit {
+            let Some((index, fut)) = queue.next() else {
+                break;
+            };
+            outputs.push(None);
+            running.push((index, Box::pin(fut)));
+        }
+        if running.is_empty() {
+            return Poll::Ready(());
+        }
+        let before = running.len();
+        running.retain_mut(|(index, fut)| match fut.as_mut().poll(cx) {
+            Poll::Ready(value) => {
+                outputs[*index] = Some(value);
+                false
+            }
+            Poll::Pending => true,
+        });
+        // the freed slots are filled with the next futures, polled right away
+        if running.len() == before {
+            return Poll::Pending;
+        }
+    })
+    .await;
+    outputs.into_iter().flatten().collect()
+}
@@ -0,0 +1,630 @@
+//! JSON Web Key handling
+//!
+//! Keys received from JOSE based backends can be loaded with the `from_jwk`
+//! constructor of each algorithm, without decoding the base64url encoded
+//! members manually.
+//!
+//! With the `serde` feature enabled, [`Jwk`] can be serialized and
+//! deserialized, following RFC 7517.
+//!
+//! ```rust,no_run
+//! use browser_crypto::aes_gcm::Aes256Gcm;
+//! use browser_crypto::jwk::{Jwk, OctJwk};
+//!
+//! async fn load(k: &str) -> Result<Aes256Gcm, Box<dyn std::error::Error>> {
+//!     let jwk = Jwk::from(OctJwk::new(k)).with_alg("A256GCM");
+//!     Ok(Aes256Gcm::from_jwk(&jwk).await?)
+//! }
+//! ```
+
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::curve::NamedCurve;
+use crate::ErrorCode;
+
+/// Errors that can occur when validating or converting a JSON Web Key.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum JwkError {
+    /// Indicates that the `kty` member is not one of "oct", "EC", "RSA" or
+    /// "OKP".
+    #[error("unsupported key type {0:?}")]
+    UnsupportedKeyType(String),
+    /// Indicates that the `crv` member is not a curve supported by the Web
+    /// Crypto API.
+    #[error("unsupported curve {0:?}")]
+    UnsupportedCurve(String),
+    /// Indicates that a member required by the key type is missing.
+    #[error("missing member {0:?}")]
+    MissingMember(&'static str),
+    /// Indicates that a member is not properly base64url encoded or doesn't
+    /// have the expected size.
+    #[error("invalid member {0:?}")]
+    InvalidMember(&'static str),
+}
+
+impl ErrorCode for JwkError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::UnsupportedKeyType(..) => "jwk.unsupported_key_type",
+            Self::UnsupportedCurve(..) => "jwk.unsupported_curve",
+            Self::MissingMember(..) => "jwk.missing_member",
+            Self::InvalidMember(..) => "jwk.invalid_member",
+        }
+    }
+}
+
+impl From<JwkError> for JsValue {
+    fn from(value: JwkError) -> Self {
+        crate::js_error("JwkError", &value)
+    }
+}
+
+/// Members shared by all the key types
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JwkParams {
+    /// Key identifier, used to pick a key in a JWK Set
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub kid: Option<String>,
+    /// Intended use of the public key: "sig" or "enc"
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "use", default, skip_serializing_if = "Option::is_none")
+    )]
+    pub key_use: Option<String>,
+    /// Operations the key can be used for, like "encrypt" or "sign"
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub key_ops: Option<Vec<String>>,
+    /// Algorithm the key is meant to be used with, like "A256GCM" or "ES256"
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub alg: Option<String>,
+    /// Whether the key can be extracted, a Web Crypto API extension
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub ext: Option<bool>,
+}
+
+/// Symmetric key, of type "oct"
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OctJwk {
+    /// Shared members
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub params: JwkParams,
+    /// Secret key
+    pub k: String,
+}
+
+impl OctJwk {
+    /// Creates a symmetric key from its base64url encoded secret
+    pub fn new(k: impl Into<String>) -> Self {
+        Self {
+            params: JwkParams::default(),
+            k: k.into(),
+        }
+    }
+}
+
+/// Elliptic curve key, of type "EC"
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EcJwk {
+    /// Shared members
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub params: JwkParams,
+    /// Curve, like "P-256"
+    pub crv: String,
+    /// X coordinate
+    pub x: String,
+    /// Y coordinate
+    pub y: String,
+    /// Private key, only defined for private keys
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub d: Option<String>,
+}
+
+impl EcJwk {
+    /// Creates a public key from its base64url encoded coordinates
+    pub fn new(crv: impl Into<String>, x: impl Into<String>, y: impl Into<String>) -> Self {
+        Self {
+            params: JwkParams::default(),
+            crv: crv.into(),
+            x: x.into(),
+            y: y.into(),
+            d: None,
+        }
+    }
+}
+
+/// RSA key, of type "RSA"
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RsaJwk {
+    /// Shared members
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub params: JwkParams,
+    /// Modulus
+    pub n: String,
+    /// Public exponent
+    pub e: String,
+    /// Private exponent, only defined for private keys
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub d: Option<String>,
+    /// First prime factor
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub p: Option<String>,
+    /// Second prime factor
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub q: Option<String>,
+    /// First factor CRT exponent
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub dp: Option<String>,
+    /// Second factor CRT exponent
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub dq: Option<String>,
+    /// First CRT coefficient
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub qi: Option<String>,
+}
+
+impl RsaJwk {
+    /// Creates a public key from its base64url encoded modulus and exponent
+    pub fn new(n: impl Into<String>, e: impl Into<String>) -> Self {
+        Self {
+            params: JwkParams::default(),
+            n: n.into(),
+            e: e.into(),
+            d: None,
+            p: None,
+            q: None,
+            dp: None,
+            dq: None,
+            qi: None,
+        }
+    }
+}
+
+/// Octet key pair, of type "OKP", used by Ed25519 and X25519
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OkpJwk {
+    /// Shared members
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub params: JwkParams,
+    /// Curve, like "Ed25519"
+    pub crv: String,
+    /// Public key
+    pub x: String,
+    /// Private key, only defined for private keys
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub d: Option<String>,
+}
+
+impl OkpJwk {
+    /// Creates a public key from its base64url encoded value
+    pub fn new(crv: impl Into<String>, x: impl Into<String>) -> Self {
+        Self {
+            params: JwkParams::default(),
+            crv: crv.into(),
+            x: x.into(),
+            d: None,
+        }
+    }
+}
+
+/// JSON Web Key, as defined in RFC 7517
+///
+/// The key specific members are base64url encoded, without padding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "kty")
+)]
+pub enum Jwk {
+    /// Symmetric key
+    #[cfg_attr(feature = "serde", serde(rename = "oct"))]
+    Oct(OctJwk),
+    /// Elliptic curve key
+    #[cfg_attr(feature = "serde", serde(rename = "EC"))]
+    Ec(EcJwk),
+    /// RSA key
+    #[cfg_attr(feature = "serde", serde(rename = "RSA"))]
+    Rsa(RsaJwk),
+    /// Octet key pair
+    #[cfg_attr(feature = "serde", serde(rename = "OKP"))]
+    Okp(OkpJwk),
+}
+
+impl From<OctJwk> for Jwk {
+    fn from(value: OctJwk) -> Self {
+        Self::Oct(value)
+    }
+}
+
+impl From<EcJwk> for Jwk {
+    fn from(value: EcJwk) -> Self {
+        Self::Ec(value)
+    }
+}
+
+impl From<RsaJwk> for Jwk {
+    fn from(value: RsaJwk) -> Self {
+        Self::Rsa(value)
+    }
+}
+
+impl From<OkpJwk> for Jwk {
+    fn from(value: OkpJwk) -> Self {
+        Self::Okp(value)
+    }
+}
+
+impl Jwk {
+    /// Key type, as found in the `kty` member
+    pub fn kty(&self) -> &'static str {
+        match self {
+            Self::Oct(_) => "oct",
+            Self::Ec(_) => "EC",
+            Self::Rsa(_) => "RSA",
+            Self::Okp(_) => "OKP",
+        }
+    }
+
+    /// Members shared by all the key types
+    pub fn params(&self) -> &JwkParams {
+        match self {
+            Self::Oct(inner) => &inner.params,
+            Self::Ec(inner) => &inner.params,
+            Self::Rsa(inner) => &inner.params,
+            Self::Okp(inner) => &inner.params,
+        }
+    }
+
+    /// Mutable access to the members shared by all the key types
+    pub fn params_mut(&mut self) -> &mut JwkParams {
+        match self {
+            Self::Oct(inner) => &mut inner.params,
+            Self::Ec(inner) => &mut inner.params,
+            Self::Rsa(inner) => &mut inner.params,
+            Self::Okp(inner) => &mut inner.params,
+        }
+    }
+
+    /// Key identifier, used to pick a key in a JWK Set
+    pub fn kid(&self) -> Option<&str> {
+        self.params().kid.as_deref()
+    }
+
+    /// Sets the key identifier
+    pub fn with_kid(mut self, kid: impl Into<String>) -> Self {
+        self.params_mut().kid = Some(kid.into());
+        self
+    }
+
+    /// Sets the algorithm the key is meant to be used with
+    pub fn with_alg(mut self, alg: impl Into<String>) -> Self {
+        self.params_mut().alg = Some(alg.into());
+        self
+    }
+
+    /// Checks that the key is consistent with its type
+    ///
+    /// # Errors
+    /// - `JwkError::UnsupportedCurve` if the curve is not supported by the Web
+    ///   Crypto API
+    /// - `JwkError::MissingMember` if an RSA private key is missing some of its
+    ///   factors
+    /// - `JwkError::InvalidMember` if a member is not properly base64url
+    ///   encoded or doesn't match the size expected by the curve
+    pub fn validate(&self) -> Result<(), JwkError> {
+        match self {
+            Self::Oct(inner) => {
+                check_encoded("k", &inner.k, None)?;
+            }
+            Self::Ec(inner) => {
+                let size = [NamedCurve::P256, NamedCurve::P384, NamedCurve::P521]
+                    .into_iter()
+                    .find(|curve| curve.name() == inner.crv)
+                    .map(|curve| curve.field_size())
+                    .ok_or_else(|| JwkError::UnsupportedCurve(inner.crv.clone()))?;
+                check_encoded("x", &inner.x, Some(size))?;
+                check_encoded("y", &inner.y, Some(size))?;
+                if let Some(ref d) = inner.d {
+                    check_encoded("d", d, Some(size))?;
+                }
+            }
+            Self::Rsa(inner) => {
+                check_encoded("n", &inner.n, None)?;
+                check_encoded("e", &inner.e, None)?;
+                let factors = [
+                    ("p", &inner.p),
+                    ("q", &inner.q),
+                    ("dp", &inner.dp),
+                    ("dq", &inner.dq),
+                    ("qi", &inner.qi),
+                ];
+                if let Some(ref d) = inner.d {
+                    check_encoded("d", d, None)?;
+                    // the factors are optional, but all or none must be present
+                    if factors.iter().any(|(_, value)| value.is_some()) {
+                        for (name, value) in factors {
+                            let value = value.as_deref().ok_or(JwkError::MissingMember(name))?;
+                            check_encoded(name, value, None)?;
+                        }
+                    }
+                } else if let Some((name, _)) = factors.iter().find(|(_, value)| value.is_some()) {
+                    return Err(JwkError::InvalidMember(name));
+                }
+            }
+            Self::Okp(inner) => {
+                let size = match inner.crv.as_str() {
+                    "Ed25519" | "X25519" => 32,
+                    "X448" => 56,
+                    "Ed448" => 57,
+                    _ => return Err(JwkError::UnsupportedCurve(inner.crv.clone())),
+                };
+                check_encoded("x", &inner.x, Some(size))?;
+                if let Some(ref d) = inner.d {
+                    check_encoded("d", d, Some(size))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks that a member is base64url encoded, without padding, and optionally
+/// that it decodes to `size` bytes
+fn check_encoded(name: &'static str, value: &str, size: Option<usize>) -> Result<(), JwkError> {
+    if value.is_empty() || value.ends_with('=') {
+        return Err(JwkError::InvalidMember(name));
+    }
+    let decoded =
+        crate::encoding::base64url_decode(value).map_err(|_| JwkError::InvalidMember(name))?;
+    match size {
+        Some(size) if decoded.len() != size => Err(JwkError::InvalidMember(name)),
+        _ => Ok(()),
+    }
+}
+
+impl From<&Jwk> for web_sys::JsonWebKey {
+    fn from(value: &Jwk) -> Self {
+        let jwk = web_sys::JsonWebKey::new(value.kty());
+        let params = value.params();
+        if let Some(ref kid) = params.kid {
+            // not part of the JsonWebKey dictionary, the Web Crypto API ignores it
+            let _ = js_sys::Reflect::set(&jwk, &"kid".into(), &kid.into());
+        }
+        if let Some(ref key_use) = params.key_use {
+            jwk.set_use(key_use);
+        }
+        if let Some(ref key_ops) = params.key_ops {
+            let key_ops = key_ops
+                .iter()
+                .map(|op| JsValue::from_str(op))
+                .collect::<js_sys::Array>();
+            jwk.set_key_ops(&key_ops);
+        }
+        if let Some(ref alg) = params.alg {
+            jwk.set_alg(alg);
+        }
+        if let Some(ext) = params.ext {
+            jwk.set_ext(ext);
+        }
+        match value {
+            Jwk::Oct(inner) => {
+                jwk.set_k(&inner.k);
+            }
+            Jwk::Ec(inner) => {
+                jwk.set_crv(&inner.crv);
+                jwk.set_x(&inner.x);
+                jwk.set_y(&inner.y);
+                if let Some(ref d) = inner.d {
+                    jwk.set_d(d);
+                }
+            }
+            Jwk::Rsa(inner) => {
+                jwk.set_n(&inner.n);
+                jwk.set_e(&inner.e);
+                if let Some(ref d) = inner.d {
+                    jwk.set_d(d);
+                }
+                if let Some(ref p) = inner.p {
+                    jwk.set_p(p);
+                }
+                if let Some(ref q) = inner.q {
+                    jwk.set_q(q);
+                }
+                if let Some(ref dp) = inner.dp {
+                    jwk.set_dp(dp);
+                }
+                if let Some(ref dq) = inner.dq {
+                    jwk.set_dq(dq);
+                }
+                if let Some(ref qi) = inner.qi {
+                    jwk.set_qi(qi);
+                }
+            }
+            Jwk::Okp(inner) => {
+                jwk.set_crv(&inner.crv);
+                jwk.set_x(&inner.x);
+                if let Some(ref d) = inner.d {
+                    jwk.set_d(d);
+                }
+            }
+        }
+        jwk
+    }
+}
+
+impl From<&Jwk> for JsValue {
+    fn from(value: &Jwk) -> Self {
+        web_sys::JsonWebKey::from(value).into()
+    }
+}
+
+fn optional_member(value: &JsValue, name: &str) -> Option<String> {
+    js_sys::Reflect::get(value, &name.into())
+        .ok()
+        .and_then(|value| value.as_string())
+}
+
+fn required_member(value: &JsValue, name: &'static str) -> Result<String, JwkError> {
+    optional_member(value, name).ok_or(JwkError::MissingMember(name))
+}
+
+impl TryFrom<&JsValue> for Jwk {
+    type Error = JwkError;
+
+    /// Reads a JSON Web Key from a JavaScript object, like the ones returned
+    /// by `exportKey`
+    fn try_from(value: &JsValue) -> Result<Self, Self::Error> {
+        if !value.is_object() {
+            return Err(JwkError::MissingMember("kty"));
+        }
+        let params = JwkParams {
+            kid: optional_member(value, "kid"),
+            key_use: optional_member(value, "use"),
+            key_ops: js_sys::Reflect::get(value, &"key_ops".into())
+                .ok()
+                .and_then(|ops| ops.dyn_into::<js_sys::Array>().ok())
+                .map(|ops| ops.iter().filter_map(|op| op.as_string()).collect()),
+            alg: optional_member(value, "alg"),
+            ext: js_sys::Reflect::get(value, &"ext".into())
+                .ok()
+                .and_then(|ext| ext.as_bool()),
+        };
+        match required_member(value, "kty")?.as_str() {
+            "oct" => Ok(Self::Oct(OctJwk {
+                params,
+                k: required_member(value, "k")?,
+            })),
+            "EC" => Ok(Self::Ec(EcJwk {
+                params,
+                crv: required_member(value, "crv")?,
+                x: required_member(value, "x")?,
+                y: required_member(value, "y")?,
+                d: optional_member(value, "d"),
+            })),
+            "RSA" => Ok(Self::Rsa(RsaJwk {
+                params,
+                n: required_member(value, "n")?,
+                e: required_member(value, "e")?,
+                d: optional_member(value, "d"),
+                p: optional_member(value, "p"),
+                q: optional_member(value, "q"),
+                dp: optional_member(value, "dp"),
+                dq: optional_member(value, "dq"),
+                qi: optional_member(value, "qi"),
+            })),
+            "OKP" => Ok(Self::Okp(OkpJwk {
+                params,
+                crv: required_member(value, "crv")?,
+                x: required_member(value, "x")?,
+                d: optional_member(value, "d"),
+            })),
+            other => Err(JwkError::UnsupportedKeyType(other.to_string())),
+        }
+    }
+}
+
+impl TryFrom<&web_sys::JsonWebKey> for Jwk {
+    type Error = JwkError;
+
+    fn try_from(value: &web_sys::JsonWebKey) -> Result<Self, Self::Error> {
+        Self::try_from(value.unchecked_ref::<JsValue>())
+    }
+}
+
+/// Overwrites the key members, the shared members being left untouched.
+///
+/// The JSON Web Keys don't implement `ZeroizeOnDrop`, so their members can
+/// still be moved out, they can be wrapped in `zeroize::Zeroizing` instead.
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for OctJwk {
+    fn zeroize(&mut self) {
+        self.k.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for EcJwk {
+    fn zeroize(&mut self) {
+        self.crv.zeroize();
+        self.x.zeroize();
+        self.y.zeroize();
+        self.d.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for RsaJwk {
+    fn zeroize(&mut self) {
+        self.n.zeroize();
+        self.e.zeroize();
+        self.d.zeroize();
+        self.p.zeroize();
+        self.q.zeroize();
+        self.dp.zeroize();
+        self.dq.zeroize();
+        self.qi.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for OkpJwk {
+    fn zeroize(&mut self) {
+        self.crv.zeroize();
+        self.x.zeroize();
+        self.d.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Jwk {
+    fn zeroize(&mut self) {
+        match self {
+            Self::Oct(inner) => inner.zeroize(),
+            Self::Ec(inner) => inner.zeroize(),
+            Self::Rsa(inner) => inner.zeroize(),
+            Self::Okp(inner) => inner.zeroize(),
+        }
+    }
+}
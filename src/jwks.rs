@@ -0,0 +1,259 @@
+//! JSON Web Key Sets
+//!
+//! Identity providers publish the public keys verifying their tokens as a JWK
+//! Set. With the `fetch` feature enabled, `Jwks::fetch` downloads it with the
+//! Fetch API.
+//!
+//! ```rust,no_run
+//! use browser_crypto::jwks::Jwks;
+//!
+//! async fn check(
+//!     content: &str,
+//!     kid: &str,
+//!     signature: &[u8],
+//!     payload: &[u8],
+//! ) -> Result<bool, Box<dyn std::error::Error>> {
+//!     let value = js_sys::JSON::parse(content).unwrap();
+//!     let jwks = Jwks::try_from(&value)?;
+//!     let key = jwks.verifying_key(kid).await?;
+//!     Ok(key.verify(signature, payload).await?)
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::curve::NamedCurve;
+use crate::ecdsa::EcdsaPublicKey;
+use crate::ed25519::Ed25519PublicKey;
+use crate::hash::Hash;
+use crate::jwk::Jwk;
+use crate::key::ImportKeyError;
+use crate::rsassa_pkcs1::RsassaPkcs1PublicKey;
+use crate::signature::VerifyError;
+use crate::ErrorCode;
+
+/// Errors that can occur when loading a JWK Set or picking one of its keys.
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum JwksError {
+    /// Indicates that the server answered with an unsuccessful status code.
+    #[error("unexpected response status {0}")]
+    InvalidStatus(u16),
+    /// Indicates that the content is not a JWK Set, an object with a `keys`
+    /// array.
+    #[error("invalid JWK Set")]
+    InvalidContent,
+    /// Indicates that no key of the set has the requested identifier.
+    #[error("no key found with the identifier {0:?}")]
+    KeyNotFound(String),
+    /// Indicates that the key couldn't be imported.
+    #[error(transparent)]
+    Import(#[from] ImportKeyError),
+    /// A wrapper for other types of errors, like network errors
+    #[error(transparent)]
+    Generic(#[from] crate::Error),
+}
+
+impl ErrorCode for JwksError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidStatus(..) => "jwks.invalid_status",
+            Self::InvalidContent => "jwks.invalid_content",
+            Self::KeyNotFound(..) => "jwks.key_not_found",
+            Self::Import(inner) => inner.code(),
+            Self::Generic(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<JwksError> for JsValue {
+    fn from(value: JwksError) -> Self {
+        crate::js_error("JwksError", &value)
+    }
+}
+
+impl From<JsValue> for JwksError {
+    fn from(value: JsValue) -> Self {
+        Self::Generic(crate::Error::from(value))
+    }
+}
+
+/// Public key verifying signatures, for the algorithms found in JWK Sets
+#[derive(Debug, Clone)]
+pub enum VerifyingKey {
+    /// RS256, RS384 or RS512
+    RsassaPkcs1(RsassaPkcs1PublicKey),
+    /// ES256, ES384 or ES512
+    Ecdsa(EcdsaPublicKey),
+    /// EdDSA over Ed25519
+    Ed25519(Ed25519PublicKey),
+}
+
+impl VerifyingKey {
+    /// Imports a public key in the JSON Web Key format, the algorithm being
+    /// picked from its `alg` member, or its curve when not defined.
+    ///
+    /// # Arguments
+    /// * `jwk` - JSON Web Key
+    ///
+    /// # Returns
+    /// Result containing the public key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::NotSupported` if the algorithm is not supported, like
+    ///   RSA-PSS, or can't be guessed
+    /// - `ImportKeyError::InvalidKeyFormat` if the JWK is not a valid public
+    ///   key
+    pub async fn from_jwk(jwk: &Jwk) -> Result<Self, ImportKeyError> {
+        match (jwk, jwk.params().alg.as_deref()) {
+            (Jwk::Rsa(_), Some("RS256")) => RsassaPkcs1PublicKey::from_jwk(jwk, Hash::Sha256)
+                .await
+                .map(Self::RsassaPkcs1),
+            (Jwk::Rsa(_), Some("RS384")) => RsassaPkcs1PublicKey::from_jwk(jwk, Hash::Sha384)
+                .await
+                .map(Self::RsassaPkcs1),
+            (Jwk::Rsa(_), Some("RS512")) => RsassaPkcs1PublicKey::from_jwk(jwk, Hash::Sha512)
+                .await
+                .map(Self::RsassaPkcs1),
+            (Jwk::Ec(inner), None | Some("ES256" | "ES384" | "ES512")) => {
+                let (curve, hash) = match inner.crv.as_str() {
+                    "P-256" => (NamedCurve::P256, Hash::Sha256),
+                    "P-384" => (NamedCurve::P384, Hash::Sha384),
+                    "P-521" => (NamedCurve::P521, Hash::Sha512),
+                    _ => return Err(ImportKeyError::NotSupported),
+                };
+                EcdsaPublicKey::from_jwk(jwk, curve, hash)
+                    .await
+                    .map(Self::Ecdsa)
+            }
+            (Jwk::Okp(inner), None | Some("EdDSA" | "Ed25519")) if inner.crv == "Ed25519" => {
+                Ed25519PublicKey::from_jwk(jwk).await.map(Self::Ed25519)
+            }
+            _ => Err(ImportKeyError::NotSupported),
+        }
+    }
+
+    /// Verifies the signature of a payload
+    ///
+    /// # Arguments
+    /// * `signature` - Signature to verify, in the JWS format
+    /// * `payload` - Data that was signed
+    ///
+    /// # Returns
+    /// Result containing whether the signature is valid or a VerifyError
+    pub async fn verify(&self, signature: &[u8], payload: &[u8]) -> Result<bool, VerifyError> {
+        match self {
+            Self::RsassaPkcs1(key) => key.verify(signature, payload).await,
+            Self::Ecdsa(key) => key.verify(signature, payload).await,
+            Self::Ed25519(key) => key.verify(signature, payload).await,
+        }
+    }
+}
+
+/// JSON Web Key Set, as defined in RFC 7517
+///
+/// The keys are imported when first requested, then cached.
+#[derive(Debug, Default)]
+pub struct Jwks {
+    keys: Vec<Jwk>,
+    cache: RefCell<HashMap<usize, VerifyingKey>>,
+}
+
+impl Jwks {
+    /// Creates a JWK Set from its keys
+    pub fn new(keys: Vec<Jwk>) -> Self {
+        Self {
+            keys,
+            cache: RefCell::default(),
+        }
+    }
+
+    /// Downloads a JWK Set
+    ///
+    /// # Arguments
+    /// * `url` - Location of the JWK Set
+    ///
+    /// # Returns
+    /// Result containing the JWK Set or a JwksError
+    ///
+    /// # Errors
+    /// - `JwksError::InvalidStatus` if the server doesn't answer with a
+    ///   successful status
+    /// - `JwksError::InvalidContent` if the content is not a JWK Set
+    /// - `JwksError::Generic` if the request fails
+    ///
+    /// ```rust,no_run
+    /// use browser_crypto::jwks::Jwks;
+    ///
+    /// async fn load() -> Result<Jwks, Box<dyn std::error::Error>> {
+    ///     Ok(Jwks::fetch("https://example.com/.well-known/jwks.json").await?)
+    /// }
+    /// ```
+    #[cfg(feature = "fetch")]
+    pub async fn fetch(url: &str) -> Result<Self, JwksError> {
+        let promise = crate::fetch(url)?;
+        let response = crate::resolve::<web_sys::Response, JwksError>(promise).await?;
+        if !response.ok() {
+            return Err(JwksError::InvalidStatus(response.status()));
+        }
+        let content = crate::resolve::<JsValue, JwksError>(response.json()?).await?;
+        Self::try_from(&content)
+    }
+
+    /// Keys of the set
+    pub fn keys(&self) -> &[Jwk] {
+        &self.keys
+    }
+
+    /// Finds a key by its identifier
+    pub fn find(&self, kid: &str) -> Option<&Jwk> {
+        self.keys.iter().find(|jwk| jwk.kid() == Some(kid))
+    }
+
+    /// Imports the key with the given identifier, the imported key is cached
+    /// for the next calls.
+    ///
+    /// # Arguments
+    /// * `kid` - Key identifier
+    ///
+    /// # Returns
+    /// Result containing the public key or a JwksError
+    ///
+    /// # Errors
+    /// - `JwksError::KeyNotFound` if no key has this identifier
+    /// - `JwksError::Import` if the key can't be imported
+    pub async fn verifying_key(&self, kid: &str) -> Result<VerifyingKey, JwksError> {
+        let index = self
+            .keys
+            .iter()
+            .position(|jwk| jwk.kid() == Some(kid))
+            .ok_or_else(|| JwksError::KeyNotFound(kid.to_string()))?;
+        if let Some(key) = self.cache.borrow().get(&index) {
+            return Ok(key.clone());
+        }
+        let key = VerifyingKey::from_jwk(&self.keys[index]).await?;
+        self.cache.borrow_mut().insert(index, key.clone());
+        Ok(key)
+    }
+}
+
+impl TryFrom<&JsValue> for Jwks {
+    type Error = JwksError;
+
+    /// Reads a JWK Set from a JavaScript object, keys of unsupported types are
+    /// ignored, as recommended by RFC 7517.
+    fn try_from(value: &JsValue) -> Result<Self, Self::Error> {
+        let keys = js_sys::Reflect::get(value, &"keys".into())
+            .ok()
+            .and_then(|keys| keys.dyn_into::<js_sys::Array>().ok())
+            .ok_or(JwksError::InvalidContent)?;
+        let keys = keys
+            .iter()
+            .filter_map(|key| Jwk::try_from(&key).ok())
+            .collect();
+        Ok(Self::new(keys))
+    }
+}
@@ -0,0 +1,1028 @@
+//! Cryptographic key handling
+
+use js_sys::SyntaxError;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::DomException;
+
+use crate::context::Operation;
+use crate::der::KeyAlgorithm;
+use crate::jwk::Jwk;
+use crate::ErrorCode;
+
+/// Binary formats of cryptographic keys
+///
+/// Keys can also be imported and exported as JSON Web Keys, see
+/// [`crate::jwk`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum KeyFormat {
+    /// Raw bytes, for secret keys and some public keys
+    Raw,
+    /// DER encoded SubjectPublicKeyInfo, for public keys
+    Spki,
+    /// DER encoded PKCS#8, for private keys
+    Pkcs8,
+}
+
+impl KeyFormat {
+    /// Name of the format, as expected by the Web Crypto API
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Raw => "raw",
+            Self::Spki => "spki",
+            Self::Pkcs8 => "pkcs8",
+        }
+    }
+}
+
+impl std::fmt::Display for KeyFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Errors that can occur when importing cryptographic keys.
+///
+/// These errors map to the exceptions defined in the Web Crypto API
+/// specification for key import operations.
+///
+/// See [MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto/importKey#exceptions)
+#[derive(Debug, Clone, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum ImportKeyError {
+    /// Indicates that the key usage array is empty for a secret or private key.
+    ///
+    /// This error occurs when:
+    /// - No key usages are specified during import
+    /// - The key type requires at least one usage to be specified
+    ///
+    /// Key usages typically include operations like "encrypt", "decrypt",
+    /// "sign", or "verify".
+    #[error("keyUsages is empty but the unwrapped key is of type secret or private")]
+    Syntax,
+    /// Indicates that the key data is not suitable for the specified format.
+    ///
+    /// This error occurs when:
+    /// - The key data is malformed
+    /// - The key data doesn't match the expected format
+    /// - The key data is invalid for the specified algorithm
+    ///
+    /// For example, trying to import non-AES data as an AES key would trigger
+    /// this error.
+    #[error("invalid format or keyData not suited for that format")]
+    Type,
+    /// Indicates that an invalid key format was specified during import.
+    ///
+    /// This error occurs when:
+    /// - The specified format (e.g., "raw", "pkcs8", "spki", "jwk") is not
+    ///   supported
+    /// - The specified format is not appropriate for the key type
+    ///
+    /// For example, trying to import a symmetric key using "spki" format would
+    /// trigger this error.
+    #[error("invalid key format provided")]
+    InvalidKeyFormat,
+    /// Indicates that the DER structure of a SPKI or PKCS#8 key can't be
+    /// read.
+    ///
+    /// This error occurs when the data is truncated, has trailing bytes or is
+    /// not DER at all, like a PEM encoded key.
+    #[error("malformed DER encoded {0} key")]
+    MalformedDer(KeyFormat),
+    /// Indicates that a SPKI or PKCS#8 key is well formed but of another
+    /// algorithm, like an RSA key imported as an ECDSA key, or on another
+    /// curve.
+    ///
+    /// # Fields
+    /// * `0` - The object identifier found in the key, in dotted notation
+    #[error("unsupported key algorithm {0}")]
+    UnsupportedAlgorithm(String),
+    /// Indicates that the algorithm of the key is not supported by the
+    /// browser.
+    ///
+    /// This error occurs with recent algorithms, like Ed25519, on older
+    /// browsers.
+    #[error("the algorithm is not supported")]
+    NotSupported,
+    /// Indicates that an encoded raw key couldn't be decoded or doesn't have
+    /// the size expected by the algorithm, before reaching the browser.
+    #[error(transparent)]
+    RawKey(#[from] crate::algorithm::KeyError),
+    /// A wrapper for other types of errors that may occur during key import.
+    ///
+    /// This includes general Web Crypto API errors and other unexpected
+    /// failures.
+    #[error(transparent)]
+    Generic(#[from] crate::Error),
+}
+
+impl ErrorCode for ImportKeyError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Syntax => "import_key.syntax",
+            Self::Type => "import_key.type",
+            Self::InvalidKeyFormat => "import_key.invalid_key_format",
+            Self::MalformedDer(..) => "import_key.malformed_der",
+            Self::UnsupportedAlgorithm(..) => "import_key.unsupported_algorithm",
+            Self::NotSupported => "import_key.not_supported",
+            Self::RawKey(inner) => inner.code(),
+            Self::Generic(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<ImportKeyError> for JsValue {
+    fn from(value: ImportKeyError) -> Self {
+        crate::js_error("ImportKeyError", &value)
+    }
+}
+
+impl From<JsValue> for ImportKeyError {
+    /// Converts a JavaScript value into an ImportKeyError.
+    ///
+    /// Maps specific DOM exceptions to their corresponding ImportKeyError
+    /// variants:
+    /// - `SyntaxError` → `ImportKeyError::Syntax`
+    /// - `DataError` → `ImportKeyError::InvalidKeyFormat`
+    /// - `NotSupportedError` → `ImportKeyError::NotSupported`
+    /// - JavaScript `SyntaxError` → `ImportKeyError::Type`
+    /// - Other errors → `ImportKeyError::Generic`
+    ///
+    /// # Arguments
+    /// * `value` - The JavaScript value to convert
+    ///
+    /// # Returns
+    /// The corresponding ImportKeyError variant
+    fn from(value: JsValue) -> Self {
+        if let Some(exception) = value.dyn_ref::<DomException>() {
+            if exception.name() == "SyntaxError" {
+                return Self::Syntax;
+            }
+            if exception.name() == "DataError" {
+                return Self::InvalidKeyFormat;
+            }
+            if exception.name() == "NotSupportedError" {
+                return Self::NotSupported;
+            }
+        }
+        if value.dyn_ref::<SyntaxError>().is_some() {
+            return Self::Type;
+        }
+        Self::Generic(crate::Error::from(value))
+    }
+}
+
+/// Imports a key as a CryptoKey
+///
+/// # Arguments
+/// * `format` - Format of the key data
+/// * `algorithm` - Algorithm details the key will be used with
+/// * `data` - Key bytes, in the given format
+/// * `usages` - Allowed usages of the key
+///
+/// # Returns
+/// Result containing the imported CryptoKey or an ImportKeyError
+pub(crate) async fn import_key(
+    format: KeyFormat,
+    algorithm: &js_sys::Object,
+    data: &[u8],
+    usages: &[&str],
+) -> Result<web_sys::CryptoKey, ImportKeyError> {
+    // Convert Rust array to Uint8Array
+    let js_key_data = js_sys::Uint8Array::from(data);
+    let result = import_key_object(format.name(), algorithm, &js_key_data, true, usages).await;
+    crate::wipe_array(&js_key_data);
+    result
+}
+
+/// Imports a DER encoded SPKI or PKCS#8 key as a CryptoKey, after checking
+/// its algorithm
+///
+/// # Arguments
+/// * `format` - Format of the key data, `KeyFormat::Spki` or `KeyFormat::Pkcs8`
+/// * `algorithm` - Algorithm details the key will be used with
+/// * `data` - DER encoded key
+/// * `expected` - Algorithm the key is expected to be of
+/// * `usages` - Allowed usages of the key
+///
+/// # Errors
+/// - `ImportKeyError::MalformedDer` if the DER structure can't be read
+/// - `ImportKeyError::UnsupportedAlgorithm` if the key is of another algorithm
+/// - Any error returned by [`import_key`]
+pub(crate) async fn import_der(
+    format: KeyFormat,
+    algorithm: &js_sys::Object,
+    data: &[u8],
+    expected: KeyAlgorithm,
+    usages: &[&str],
+) -> Result<web_sys::CryptoKey, ImportKeyError> {
+    crate::der::check(format, data, expected)?;
+    import_key(format, algorithm, data, usages).await
+}
+
+/// Imports raw key material as a non extractable CryptoKey, usable to derive
+/// other keys or bits
+///
+/// # Arguments
+/// * `name` - Name of the derivation algorithm ("PBKDF2" or "HKDF")
+/// * `data` - Raw key material
+///
+/// # Returns
+/// Result containing the imported CryptoKey or an ImportKeyError
+pub(crate) async fn import_base_key(
+    name: &str,
+    data: &[u8],
+) -> Result<web_sys::CryptoKey, ImportKeyError> {
+    crate::check_target()?;
+    let algorithm = js_sys::Object::new();
+    js_sys::Reflect::set(&algorithm, &"name".into(), &name.into())?;
+    let js_key_data = js_sys::Uint8Array::from(data);
+    // derivation keys can't be exported
+    let result = import_key_object(
+        "raw",
+        &algorithm,
+        &js_key_data,
+        false,
+        &["deriveBits", "deriveKey"],
+    )
+    .await;
+    crate::wipe_array(&js_key_data);
+    result
+}
+
+/// Restricts usages to the `key_ops` member of a JSON Web Key, when defined,
+/// as the browser refuses to import a key with usages it doesn't list
+pub(crate) fn jwk_usages<'a>(jwk: &Jwk, usages: &[&'a str]) -> Vec<&'a str> {
+    match jwk.params().key_ops.as_deref() {
+        Some(key_ops) => usages
+            .iter()
+            .filter(|usage| key_ops.iter().any(|op| op == *usage))
+            .copied()
+            .collect(),
+        None => usages.to_vec(),
+    }
+}
+
+/// Imports a key in the JSON Web Key format as a CryptoKey
+///
+/// The key is extractable, unless its `ext` member is `false`.
+///
+/// # Arguments
+/// * `algorithm` - Algorithm details the key will be used with
+/// * `jwk` - The JSON Web Key
+/// * `usages` - Allowed usages of the key
+///
+/// # Returns
+/// Result containing the imported CryptoKey or an ImportKeyError
+///
+/// # Errors
+/// - `ImportKeyError::InvalidKeyFormat` if the JWK is not valid
+pub(crate) async fn import_jwk(
+    algorithm: &js_sys::Object,
+    jwk: &Jwk,
+    usages: &[&str],
+) -> Result<web_sys::CryptoKey, ImportKeyError> {
+    jwk.validate()
+        .map_err(|_| ImportKeyError::InvalidKeyFormat)?;
+    let extractable = jwk.params().ext.unwrap_or(true);
+    let jwk = web_sys::JsonWebKey::from(jwk);
+    import_key_object("jwk", algorithm, &jwk, extractable, usages).await
+}
+
+async fn import_key_object(
+    format: &str,
+    algorithm: &js_sys::Object,
+    data: &js_sys::Object,
+    extractable: bool,
+    usages: &[&str],
+) -> Result<web_sys::CryptoKey, ImportKeyError> {
+    let name = crate::instrument::algorithm_name(algorithm);
+    crate::instrument::observe(Operation::ImportKey, &name, None, async {
+        let subtle = crate::subtle()?;
+        let promise: js_sys::Promise = subtle.import_key_with_object(
+            format,      // Import format
+            data,        // Key material
+            algorithm,   // Algorithm details
+            extractable, // Extractable (true allows exporting later)
+            &usages_array(usages),
+        )?;
+
+        crate::resolve::<web_sys::CryptoKey, ImportKeyError>(promise).await
+    })
+    .await
+}
+
+/// Exports a CryptoKey
+///
+/// # Arguments
+/// * `format` - Format of the exported key
+/// * `key` - The key to export, must be extractable
+///
+/// # Returns
+/// Result containing the exported key bytes or an ExportKeyError
+pub(crate) async fn export_key(
+    format: KeyFormat,
+    key: &web_sys::CryptoKey,
+) -> Result<Vec<u8>, ExportKeyError> {
+    let name = crate::instrument::key_algorithm_name(key);
+    let exported = crate::instrument::observe(Operation::ExportKey, &name, None, async {
+        let subtle = crate::subtle()?;
+        let promise: js_sys::Promise = subtle.export_key(format.name(), key)?;
+        crate::resolve::<js_sys::ArrayBuffer, ExportKeyError>(promise).await
+    })
+    .await?;
+
+    Ok(crate::secret_array_to_vec(&js_sys::Uint8Array::new(
+        &exported,
+    )))
+}
+
+/// Exports a CryptoKey in the JSON Web Key format
+///
+/// # Arguments
+/// * `key` - The key to export, must be extractable
+///
+/// # Returns
+/// Result containing the JSON Web Key or an ExportKeyError
+pub(crate) async fn export_jwk(key: &web_sys::CryptoKey) -> Result<Jwk, ExportKeyError> {
+    let name = crate::instrument::key_algorithm_name(key);
+    let exported = crate::instrument::observe(Operation::ExportKey, &name, None, async {
+        let subtle = crate::subtle()?;
+        let promise: js_sys::Promise = subtle.export_key("jwk", key)?;
+        // JsonWebKey is a dictionary, it can't be checked with instanceof
+        crate::resolve::<JsValue, ExportKeyError>(promise).await
+    })
+    .await?;
+
+    // the browser only exports valid keys
+    Jwk::try_from(&exported).map_err(|_| ExportKeyError::Generic(crate::Error::Unknown))
+}
+
+/// Generates a new secret key
+///
+/// # Arguments
+/// * `algorithm` - Algorithm details of the key
+/// * `extractable` - Whether the key can be exported or wrapped
+/// * `usages` - Allowed usages of the key
+///
+/// # Returns
+/// Result containing the generated CryptoKey or a GenerateKeyError
+pub(crate) async fn generate_key(
+    algorithm: &js_sys::Object,
+    extractable: bool,
+    usages: &[&str],
+) -> Result<web_sys::CryptoKey, GenerateKeyError> {
+    let name = crate::instrument::algorithm_name(algorithm);
+    crate::instrument::observe(Operation::GenerateKey, &name, None, async {
+        let subtle = crate::subtle()?;
+        let promise: js_sys::Promise =
+            subtle.generate_key_with_object(algorithm, extractable, &usages_array(usages))?;
+        crate::resolve::<web_sys::CryptoKey, GenerateKeyError>(promise).await
+    })
+    .await
+}
+
+/// Generates a new key pair
+///
+/// # Arguments
+/// * `algorithm` - Algorithm details of the key pair
+/// * `usages` - Allowed usages of the keys, the browser dispatches them to the
+///   public and private keys
+///
+/// # Returns
+/// Result containing the public and private CryptoKey or a GenerateKeyError
+pub(crate) async fn generate_key_pair(
+    algorithm: &js_sys::Object,
+    usages: &[&str],
+) -> Result<(web_sys::CryptoKey, web_sys::CryptoKey), GenerateKeyError> {
+    let name = crate::instrument::algorithm_name(algorithm);
+    let pair = crate::instrument::observe(Operation::GenerateKey, &name, None, async {
+        let subtle = crate::subtle()?;
+        let promise: js_sys::Promise =
+            subtle.generate_key_with_object(algorithm, true, &usages_array(usages))?;
+        // the pair is not always an instance of Object, Node.js creates it
+        // without prototype
+        crate::resolve::<JsValue, GenerateKeyError>(promise).await
+    })
+    .await?;
+
+    let public_key = js_sys::Reflect::get(&pair, &"publicKey".into())?;
+    let private_key = js_sys::Reflect::get(&pair, &"privateKey".into())?;
+    Ok((public_key.dyn_into()?, private_key.dyn_into()?))
+}
+
+pub(crate) fn usages_array(usages: &[&str]) -> js_sys::Array {
+    usages
+        .iter()
+        .map(|usage| JsValue::from_str(usage))
+        .collect::<js_sys::Array>()
+}
+
+/// Imports a raw AES key of `KEY_BITS` bits as a CryptoKey
+///
+/// The browser infers the key size from the data, so this makes sure it
+/// matches the requested one.
+///
+/// # Errors
+/// - `ImportKeyError::InvalidKeyFormat` if the key size doesn't match
+///   `KEY_BITS`
+/// - Any error returned by [`import_key`]
+pub(crate) async fn import_raw_aes<const KEY_BITS: usize>(
+    name: &str,
+    data: &[u8],
+    usages: &[&str],
+) -> Result<web_sys::CryptoKey, ImportKeyError> {
+    crate::check_target()?;
+    if data.len() * 8 != KEY_BITS {
+        return Err(ImportKeyError::InvalidKeyFormat);
+    }
+
+    // Define AES import parameters
+    let algorithm = js_sys::Object::new();
+    js_sys::Reflect::set(&algorithm, &"name".into(), &name.into())?;
+
+    import_key(KeyFormat::Raw, &algorithm, data, usages).await
+}
+
+/// Errors that can occur when exporting cryptographic keys.
+///
+/// These errors map to the exceptions defined in the Web Crypto API
+/// specification for key export operations.
+///
+/// See [MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto/exportKey#exceptions)
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum ExportKeyError {
+    /// Indicates that the key is not extractable.
+    #[error("the key is not extractable")]
+    InvalidAccess,
+    /// Indicates that the key can't be exported in the requested format.
+    #[error("the key can't be exported in the requested format")]
+    NotSupported,
+    /// A wrapper for other types of errors that may occur during key export.
+    #[error(transparent)]
+    Generic(#[from] crate::Error),
+}
+
+impl ErrorCode for ExportKeyError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidAccess => "export_key.invalid_access",
+            Self::NotSupported => "export_key.not_supported",
+            Self::Generic(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<ExportKeyError> for JsValue {
+    fn from(value: ExportKeyError) -> Self {
+        crate::js_error("ExportKeyError", &value)
+    }
+}
+
+impl From<JsValue> for ExportKeyError {
+    fn from(value: JsValue) -> Self {
+        if let Some(exception) = value.dyn_ref::<DomException>() {
+            match exception.name().as_str() {
+                "InvalidAccessError" => {
+                    return Self::InvalidAccess;
+                }
+                "NotSupportedError" => {
+                    return Self::NotSupported;
+                }
+                _ => {}
+            }
+        }
+        Self::Generic(crate::Error::from(value))
+    }
+}
+
+/// Errors that can occur when generating cryptographic keys.
+///
+/// These errors map to the exceptions defined in the Web Crypto API
+/// specification for key generation operations.
+///
+/// See [MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto/generateKey#exceptions)
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum GenerateKeyError {
+    /// Indicates that the key usage array is empty for a secret or private key.
+    #[error("keyUsages is empty but the generated key is of type secret or private")]
+    Syntax,
+    /// Indicates that the algorithm or its parameters are not supported by the
+    /// browser.
+    #[error("the algorithm is not supported")]
+    NotSupported,
+    /// Indicates that the operation failed for an algorithm-specific reason,
+    /// like an invalid modulus length.
+    #[error("operation failed for an operation-specific reason")]
+    Operation,
+    /// A wrapper for other types of errors that may occur during key
+    /// generation.
+    #[error(transparent)]
+    Generic(#[from] crate::Error),
+}
+
+impl ErrorCode for GenerateKeyError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Syntax => "generate_key.syntax",
+            Self::NotSupported => "generate_key.not_supported",
+            Self::Operation => "generate_key.operation",
+            Self::Generic(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<GenerateKeyError> for JsValue {
+    fn from(value: GenerateKeyError) -> Self {
+        crate::js_error("GenerateKeyError", &value)
+    }
+}
+
+impl From<JsValue> for GenerateKeyError {
+    fn from(value: JsValue) -> Self {
+        if let Some(exception) = value.dyn_ref::<DomException>() {
+            match exception.name().as_str() {
+                "SyntaxError" => {
+                    return Self::Syntax;
+                }
+                "NotSupportedError" => {
+                    return Self::NotSupported;
+                }
+                "OperationError" => {
+                    return Self::Operation;
+                }
+                _ => {}
+            }
+        }
+        Self::Generic(crate::Error::from(value))
+    }
+}
+
+/// Errors that can occur when wrapping cryptographic keys.
+///
+/// These errors map to the exceptions defined in the Web Crypto API
+/// specification for key wrapping operations.
+///
+/// See [MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto/wrapKey#exceptions)
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum WrapKeyError {
+    /// Indicates that the wrapping key is not valid for the operation.
+    ///
+    /// This error occurs when:
+    /// - The wrapping key doesn't have the "wrapKey" usage
+    /// - The key to wrap is not extractable
+    #[error("requested operation is not valid for the provided key")]
+    InvalidAccess,
+    /// Indicates that the wrapping algorithm can't be used for wrapping keys.
+    #[error("the algorithm is not supported for wrapping keys")]
+    NotSupported,
+    /// Indicates that the operation failed for an algorithm-specific reason.
+    ///
+    /// For example, AES-KW requires the wrapped key to be a multiple of 8
+    /// bytes.
+    #[error("operation failed for an operation-specific reason")]
+    Operation,
+    /// A wrapper for other types of errors that may occur during key wrapping.
+    #[error(transparent)]
+    Generic(#[from] crate::Error),
+}
+
+impl ErrorCode for WrapKeyError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidAccess => "wrap_key.invalid_access",
+            Self::NotSupported => "wrap_key.not_supported",
+            Self::Operation => "wrap_key.operation",
+            Self::Generic(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<WrapKeyError> for JsValue {
+    fn from(value: WrapKeyError) -> Self {
+        crate::js_error("WrapKeyError", &value)
+    }
+}
+
+impl From<JsValue> for WrapKeyError {
+    fn from(value: JsValue) -> Self {
+        if let Some(exception) = value.dyn_ref::<DomException>() {
+            match exception.name().as_str() {
+                "InvalidAccessError" => {
+                    return Self::InvalidAccess;
+                }
+                "NotSupportedError" => {
+                    return Self::NotSupported;
+                }
+                "OperationError" => {
+                    return Self::Operation;
+                }
+                _ => {}
+            }
+        }
+        Self::Generic(crate::Error::from(value))
+    }
+}
+
+/// Errors that can occur when unwrapping cryptographic keys.
+///
+/// These errors map to the exceptions defined in the Web Crypto API
+/// specification for key unwrapping operations.
+///
+/// See [MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto/unwrapKey#exceptions)
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum UnwrapKeyError {
+    /// Indicates that the unwrapping key is not valid for the operation.
+    ///
+    /// This error occurs when the unwrapping key doesn't have the "unwrapKey"
+    /// usage or doesn't match the unwrapping algorithm.
+    #[error("requested operation is not valid for the provided key")]
+    InvalidAccess,
+    /// Indicates that the key usage array is empty for a secret or private key.
+    #[error("keyUsages is empty but the unwrapped key is of type secret or private")]
+    Syntax,
+    /// Indicates that the operation failed for an algorithm-specific reason.
+    ///
+    /// For example, AES-KW fails the integrity check when the wrapped key has
+    /// been altered or was wrapped with another key.
+    #[error("operation failed for an operation-specific reason")]
+    Operation,
+    /// Indicates that the unwrapped key doesn't match the expected algorithm
+    /// or size.
+    #[error("the unwrapped key doesn't match the expected algorithm")]
+    InvalidKey,
+    /// A wrapper for other types of errors that may occur during key
+    /// unwrapping.
+    #[error(transparent)]
+    Generic(#[from] crate::Error),
+}
+
+impl ErrorCode for UnwrapKeyError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidAccess => "unwrap_key.invalid_access",
+            Self::Syntax => "unwrap_key.syntax",
+            Self::Operation => "unwrap_key.operation",
+            Self::InvalidKey => "unwrap_key.invalid_key",
+            Self::Generic(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<UnwrapKeyError> for JsValue {
+    fn from(value: UnwrapKeyError) -> Self {
+        crate::js_error("UnwrapKeyError", &value)
+    }
+}
+
+impl From<JsValue> for UnwrapKeyError {
+    fn from(value: JsValue) -> Self {
+        if let Some(exception) = value.dyn_ref::<DomException>() {
+            match exception.name().as_str() {
+                "InvalidAccessError" => {
+                    return Self::InvalidAccess;
+                }
+                "SyntaxError" => {
+                    return Self::Syntax;
+                }
+                "OperationError" => {
+                    return Self::Operation;
+                }
+                _ => {}
+            }
+        }
+        Self::Generic(crate::Error::from(value))
+    }
+}
+
+pub(crate) mod private {
+    /// Plumbing to move secret keys in and out of the Web Crypto API, not
+    /// meant to be implemented outside of this crate.
+    pub trait Sealed: Sized {
+        /// Algorithm details used to import the key
+        fn import_algorithm() -> Result<js_sys::Object, wasm_bindgen::JsValue>;
+        /// Usages allowed for the key
+        fn usages() -> &'static [&'static str];
+        /// Underlying CryptoKey, `Error::Unsupported` when the key is held by
+        /// the native backend
+        fn crypto_key(&self) -> Result<&web_sys::CryptoKey, crate::Error>;
+        /// Wraps an existing CryptoKey, returns `None` if it doesn't match the
+        /// expected algorithm
+        fn from_crypto_key(key: web_sys::CryptoKey) -> Option<Self>;
+    }
+
+    /// Plumbing to wrap and unwrap keys, not meant to be implemented outside
+    /// of this crate.
+    pub trait Wrapper {
+        /// Algorithm details used to wrap or unwrap keys
+        fn wrapping_algorithm(&self) -> Result<js_sys::Object, wasm_bindgen::JsValue>;
+        /// Underlying CryptoKey, with the "wrapKey" or "unwrapKey" usage
+        fn wrapping_key(&self) -> &web_sys::CryptoKey;
+        /// Key ID attached with [`crate::kid::Tagged`]
+        fn kid(&self) -> Option<&str> {
+            None
+        }
+    }
+}
+
+/// Secret keys that can be wrapped and unwrapped by a key wrapping algorithm,
+/// like [`crate::aes_kw`].
+pub trait WrappableKey: private::Sealed {}
+
+/// Key encryption keys able to wrap secret keys, like [`crate::aes_kw::AesKw`]
+/// or [`crate::rsa_oaep::RsaOaepPublicKey`]
+///
+/// Keys are wrapped in the raw format, without their bytes going through Rust
+/// or JavaScript.
+pub trait KeyWrapper: private::Wrapper {
+    /// Wraps a secret key
+    ///
+    /// # Arguments
+    /// * `key` - The key to wrap, must be extractable
+    ///
+    /// # Returns
+    /// Result containing the wrapped key bytes or a WrapKeyError
+    ///
+    /// # Errors
+    /// - `WrapKeyError::InvalidAccess` if the key to wrap is not extractable
+    /// - `WrapKeyError::Operation` if the key can't be wrapped with this
+    ///   algorithm, like a key size that is not a multiple of 8 bytes with
+    ///   AES-KW
+    fn wrap_key<K: WrappableKey>(
+        &self,
+        key: &K,
+    ) -> impl std::future::Future<Output = Result<Vec<u8>, WrapKeyError>> {
+        wrap_key(self, key)
+    }
+}
+
+/// Key encryption keys able to unwrap secret keys, like
+/// [`crate::aes_kw::AesKw`] or [`crate::rsa_oaep::RsaOaepPrivateKey`]
+pub trait KeyUnwrapper: private::Wrapper {
+    /// Unwraps a secret key, as a non extractable key
+    ///
+    /// # Arguments
+    /// * `wrapped` - The wrapped key bytes
+    ///
+    /// # Returns
+    /// Result containing the unwrapped key or an UnwrapKeyError
+    ///
+    /// # Errors
+    /// - `UnwrapKeyError::Operation` if the wrapped key has been altered or was
+    ///   wrapped using another key
+    /// - `UnwrapKeyError::InvalidKey` if the unwrapped key doesn't match the
+    ///   requested key type, like a 128-bit key unwrapped as AES-256-GCM
+    fn unwrap_key<K: WrappableKey>(
+        &self,
+        wrapped: &[u8],
+    ) -> impl std::future::Future<Output = Result<K, UnwrapKeyError>> {
+        unwrap_key(self, wrapped, false)
+    }
+
+    /// Unwraps a secret key, as an extractable key so it can be wrapped again,
+    /// with another key encryption key for example
+    ///
+    /// # Errors
+    /// Same as [`KeyUnwrapper::unwrap_key`]
+    fn unwrap_extractable_key<K: WrappableKey>(
+        &self,
+        wrapped: &[u8],
+    ) -> impl std::future::Future<Output = Result<K, UnwrapKeyError>> {
+        unwrap_key(self, wrapped, true)
+    }
+}
+
+async fn wrap_key<W, K>(wrapper: &W, key: &K) -> Result<Vec<u8>, WrapKeyError>
+where
+    W: private::Wrapper + ?Sized,
+    K: WrappableKey,
+{
+    let algorithm = wrapper.wrapping_algorithm()?;
+    let name = crate::instrument::algorithm_name(&algorithm);
+    let wrapped = crate::instrument::tagged(
+        wrapper.kid(),
+        crate::instrument::observe(Operation::WrapKey, &name, None, async {
+            let subtle = crate::subtle()?;
+            let promise: js_sys::Promise = subtle.wrap_key_with_object(
+                KeyFormat::Raw.name(),
+                key.crypto_key()?,
+                wrapper.wrapping_key(),
+                &algorithm,
+            )?;
+            crate::resolve::<js_sys::ArrayBuffer, WrapKeyError>(promise).await
+        }),
+    )
+    .await?;
+
+    Ok(crate::array_to_vec(&js_sys::Uint8Array::new(&wrapped)))
+}
+
+async fn unwrap_key<W, K>(
+    wrapper: &W,
+    wrapped: &[u8],
+    extractable: bool,
+) -> Result<K, UnwrapKeyError>
+where
+    W: private::Wrapper + ?Sized,
+    K: WrappableKey,
+{
+    let algorithm = wrapper.wrapping_algorithm()?;
+    let name = crate::instrument::algorithm_name(&algorithm);
+    let key = crate::instrument::tagged(
+        wrapper.kid(),
+        crate::instrument::observe(Operation::UnwrapKey, &name, Some(wrapped.len()), async {
+            let subtle = crate::subtle()?;
+            let promise: js_sys::Promise = subtle.unwrap_key_with_u8_array_and_object_and_object(
+                KeyFormat::Raw.name(),
+                wrapped,
+                wrapper.wrapping_key(),
+                &algorithm,
+                &K::import_algorithm()?,
+                extractable,
+                &usages_array(K::usages()),
+            )?;
+            crate::resolve::<web_sys::CryptoKey, UnwrapKeyError>(promise).await
+        }),
+    )
+    .await?;
+
+    K::from_crypto_key(key).ok_or(UnwrapKeyError::InvalidKey)
+}
+
+/// Imports an AES key of `KEY_BITS` bits in the JSON Web Key format as a
+/// CryptoKey
+///
+/// # Errors
+/// - `ImportKeyError::InvalidKeyFormat` if the key size doesn't match
+///   `KEY_BITS`
+/// - Any error returned by [`import_jwk`]
+pub(crate) async fn import_jwk_aes<const KEY_BITS: usize>(
+    name: &'static str,
+    jwk: &Jwk,
+    usages: &[&str],
+) -> Result<web_sys::CryptoKey, ImportKeyError> {
+    crate::check_target()?;
+    let algorithm = aes_algorithm(name, KEY_BITS)?;
+    let key = import_jwk(&algorithm, jwk, usages).await?;
+    if !is_aes_key(&key, name, KEY_BITS) {
+        return Err(ImportKeyError::InvalidKeyFormat);
+    }
+    Ok(key)
+}
+
+/// Reads a member of the algorithm of a CryptoKey
+pub(crate) fn key_algorithm_member(key: &web_sys::CryptoKey, member: &str) -> Option<JsValue> {
+    key.algorithm()
+        .and_then(|algorithm| js_sys::Reflect::get(&algorithm, &member.into()))
+        .ok()
+}
+
+/// Checks that a CryptoKey is an AES key with the given algorithm name and
+/// size
+pub(crate) fn is_aes_key(key: &web_sys::CryptoKey, name: &'static str, bits: usize) -> bool {
+    check_aes_key(key, name, bits, &[]).is_ok()
+}
+
+/// Checks that a CryptoKey is an AES secret key with the given algorithm name,
+/// size and usages
+///
+/// # Errors
+/// - `CryptoKeyError::InvalidType` if the key is not a secret key
+/// - `CryptoKeyError::InvalidAlgorithm` if the algorithm name doesn't match
+/// - `CryptoKeyError::InvalidLength` if the key size doesn't match
+/// - `CryptoKeyError::MissingUsage` if one of the usages is not allowed
+pub(crate) fn check_aes_key(
+    key: &web_sys::CryptoKey,
+    name: &'static str,
+    bits: usize,
+    usages: &[&'static str],
+) -> Result<(), CryptoKeyError> {
+    check_key_algorithm(key, "secret", name)?;
+    let key_length = key_algorithm_member(key, "length")
+        .and_then(|value| value.as_f64())
+        .unwrap_or_default() as usize;
+    if key_length != bits {
+        return Err(CryptoKeyError::InvalidLength {
+            expected: bits,
+            found: key_length,
+        });
+    }
+    check_key_usages(key, usages)
+}
+
+/// Checks that a CryptoKey has the given type, like "secret" or "private", and
+/// algorithm name
+///
+/// # Errors
+/// - `CryptoKeyError::InvalidType` if the key type doesn't match
+/// - `CryptoKeyError::InvalidAlgorithm` if the algorithm name doesn't match
+pub(crate) fn check_key_algorithm(
+    key: &web_sys::CryptoKey,
+    key_type: &str,
+    name: &'static str,
+) -> Result<(), CryptoKeyError> {
+    let found = key.type_();
+    if found != key_type {
+        return Err(CryptoKeyError::InvalidType(found));
+    }
+    let key_name = key_algorithm_member(key, "name")
+        .and_then(|value| value.as_string())
+        .unwrap_or_default();
+    if key_name != name {
+        return Err(CryptoKeyError::InvalidAlgorithm {
+            expected: name,
+            found: key_name,
+        });
+    }
+    Ok(())
+}
+
+/// Checks that a CryptoKey allows all the given usages
+///
+/// # Errors
+/// - `CryptoKeyError::MissingUsage` if one of the usages is not allowed
+pub(crate) fn check_key_usages(
+    key: &web_sys::CryptoKey,
+    usages: &[&'static str],
+) -> Result<(), CryptoKeyError> {
+    let key_usages = key.usages();
+    for usage in usages {
+        if !key_usages.includes(&JsValue::from_str(usage), 0) {
+            return Err(CryptoKeyError::MissingUsage(usage));
+        }
+    }
+    Ok(())
+}
+
+/// Errors that can occur when using a CryptoKey created outside of this crate,
+/// when it doesn't match the expected algorithm.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum CryptoKeyError {
+    /// Indicates that the key is not of the expected type, like a public key
+    /// provided instead of a secret key.
+    ///
+    /// # Fields
+    /// * `0` - The type of the key
+    #[error("unexpected key type {0:?}")]
+    InvalidType(String),
+    /// Indicates that the key is meant for another algorithm.
+    #[error("unexpected key algorithm {found:?}, expected {expected:?}")]
+    InvalidAlgorithm {
+        /// Name of the expected algorithm
+        expected: &'static str,
+        /// Name of the algorithm of the key
+        found: String,
+    },
+    /// Indicates that the key size doesn't match, like a 128-bit key provided
+    /// for AES-256-GCM.
+    #[error("unexpected key length {found}, expected {expected}")]
+    InvalidLength {
+        /// Expected size of the key, in bits
+        expected: usize,
+        /// Size of the key, in bits
+        found: usize,
+    },
+    /// Indicates that the key doesn't allow one of the required usages.
+    ///
+    /// # Fields
+    /// * `0` - The missing usage
+    #[error("the key doesn't allow the {0:?} usage")]
+    MissingUsage(&'static str),
+    /// Indicates that the cipher can't hold a CryptoKey, like with the native
+    /// backend which holds its keys in memory.
+    #[error("the key can't be used by the backend")]
+    NotSupported,
+}
+
+impl ErrorCode for CryptoKeyError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidType(..) => "crypto_key.invalid_type",
+            Self::InvalidAlgorithm { .. } => "crypto_key.invalid_algorithm",
+            Self::InvalidLength { .. } => "crypto_key.invalid_length",
+            Self::MissingUsage(..) => "crypto_key.missing_usage",
+            Self::NotSupported => "crypto_key.not_supported",
+        }
+    }
+}
+
+impl From<CryptoKeyError> for JsValue {
+    fn from(value: CryptoKeyError) -> Self {
+        crate::js_error("CryptoKeyError", &value)
+    }
+}
+
+/// Builds the algorithm details to import an AES key
+pub(crate) fn aes_algorithm(name: &str, bits: usize) -> Result<js_sys::Object, JsValue> {
+    let algorithm = js_sys::Object::new();
+    js_sys::Reflect::set(&algorithm, &"name".into(), &name.into())?;
+    js_sys::Reflect::set(&algorithm, &"length".into(), &(bits as u32).into())?;
+    Ok(algorithm)
+}
@@ -0,0 +1,118 @@
+//! Asymmetric key pairs
+//!
+//! Every asymmetric algorithm, like [`crate::rsa_oaep`] or [`crate::ecdsa`],
+//! exposes a marker type implementing [`AsymmetricAlgorithm`], so its key pairs
+//! are handled the same way.
+//!
+//! ```rust,no_run
+//! use browser_crypto::curve::NamedCurve;
+//! use browser_crypto::ecdsa::EcdsaKeyPair;
+//! use browser_crypto::hash::Hash;
+//!
+//! async fn export() -> Result<(Vec<u8>, Vec<u8>), Box<dyn std::error::Error>> {
+//!     let pair = EcdsaKeyPair::generate(NamedCurve::P256, Hash::Sha256).await?;
+//!     let public_key = pair.export_public_key().await?;
+//!     let private_key = pair.export_private_key().await?;
+//!     Ok((public_key, private_key))
+//! }
+//! ```
+
+use self::private::Sealed;
+use crate::key::{ExportKeyError, GenerateKeyError, KeyFormat};
+
+pub(crate) mod private {
+    /// Plumbing to access the CryptoKey of a public or private key, not meant
+    /// to be implemented outside of this crate.
+    pub trait Sealed {
+        /// Underlying CryptoKey
+        fn crypto_key(&self) -> &web_sys::CryptoKey;
+    }
+}
+
+/// Public or private half of a key pair
+pub trait AsymmetricKey: private::Sealed {}
+
+/// Asymmetric algorithms, defining the types of their public and private keys
+pub trait AsymmetricAlgorithm {
+    /// Public key type
+    type PublicKey: AsymmetricKey + std::fmt::Debug + Clone;
+    /// Private key type
+    type PrivateKey: AsymmetricKey + std::fmt::Debug + Clone;
+
+    /// Usages allowed for the public key
+    const PUBLIC_USAGES: &'static [&'static str];
+    /// Usages allowed for the private key
+    const PRIVATE_USAGES: &'static [&'static str];
+}
+
+/// Key pair of an asymmetric algorithm
+#[derive(Debug, Clone)]
+pub struct KeyPair<A: AsymmetricAlgorithm> {
+    /// Public key, can be shared
+    pub public_key: A::PublicKey,
+    /// Private key, must be kept secret
+    pub private_key: A::PrivateKey,
+}
+
+impl<A: AsymmetricAlgorithm> KeyPair<A> {
+    /// Builds a key pair from its halves
+    ///
+    /// Nothing checks that both keys match.
+    pub fn new(public_key: A::PublicKey, private_key: A::PrivateKey) -> Self {
+        Self {
+            public_key,
+            private_key,
+        }
+    }
+
+    /// Splits the key pair into its public and private keys
+    pub fn into_parts(self) -> (A::PublicKey, A::PrivateKey) {
+        (self.public_key, self.private_key)
+    }
+
+    /// Usages allowed for the public key
+    pub fn public_usages(&self) -> &'static [&'static str] {
+        A::PUBLIC_USAGES
+    }
+
+    /// Usages allowed for the private key
+    pub fn private_usages(&self) -> &'static [&'static str] {
+        A::PRIVATE_USAGES
+    }
+
+    /// Exports the public key in the SubjectPublicKeyInfo format.
+    ///
+    /// # Returns
+    /// Result containing the DER encoded SubjectPublicKeyInfo or an
+    /// ExportKeyError
+    pub async fn export_public_key(&self) -> Result<Vec<u8>, ExportKeyError> {
+        crate::key::export_key(KeyFormat::Spki, self.public_key.crypto_key()).await
+    }
+
+    /// Exports the private key in the PKCS#8 format.
+    ///
+    /// # Returns
+    /// Result containing the DER encoded PKCS#8 private key or an
+    /// ExportKeyError
+    pub async fn export_private_key(&self) -> Result<Vec<u8>, ExportKeyError> {
+        crate::key::export_key(KeyFormat::Pkcs8, self.private_key.crypto_key()).await
+    }
+}
+
+/// Generates the public and private CryptoKey of an algorithm
+///
+/// # Arguments
+/// * `algorithm` - Algorithm details of the key pair
+///
+/// # Returns
+/// Result containing the public and private CryptoKey or a GenerateKeyError
+pub(crate) async fn generate<A: AsymmetricAlgorithm>(
+    algorithm: &js_sys::Object,
+) -> Result<(web_sys::CryptoKey, web_sys::CryptoKey), GenerateKeyError> {
+    let usages = A::PUBLIC_USAGES
+        .iter()
+        .chain(A::PRIVATE_USAGES.iter())
+        .copied()
+        .collect::<Vec<_>>();
+    crate::key::generate_key_pair(algorithm, &usages).await
+}
@@ -0,0 +1,262 @@
+//! Keyring, to rotate encryption keys
+//!
+//! A keyring holds several ciphers, each one identified by a key ID. Payloads
+//! are encrypted with the primary cipher, and the ciphertext embeds the key ID
+//! so it can be decrypted after the primary cipher changed.
+//!
+//! The ciphertext is made of the length of the key ID on one byte, the key ID,
+//! the nonce and the encrypted payload.
+//!
+//! ```rust,no_run
+//! use browser_crypto::aes_gcm::Aes256Gcm;
+//! use browser_crypto::keyring::Keyring;
+//!
+//! async fn rotate() -> Result<(), Box<dyn std::error::Error>> {
+//!     let mut keyring = Keyring::new("2024", Aes256Gcm::from_key(&[0u8; 32]).await?)?;
+//!     let encrypted = keyring.encrypt(b"Hello World!").await?;
+//!
+//!     keyring.rotate("2025", Aes256Gcm::from_key(&[1u8; 32]).await?)?;
+//!     assert_eq!(keyring.decrypt(&encrypted).await?, b"Hello World!");
+//!     Ok(())
+//! }
+//! ```
+
+use wasm_bindgen::JsValue;
+
+use crate::algorithm::{Algorithm, DecryptionError, EncryptionError, Nonce, NonceError};
+use crate::ErrorCode;
+
+/// Errors that can occur when using a keyring.
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum KeyringError {
+    /// Indicates that a key ID is empty or longer than 255 bytes.
+    #[error("key ID must be between 1 and 255 bytes")]
+    InvalidKid,
+    /// Indicates that no cipher of the keyring has the key ID.
+    ///
+    /// # Fields
+    /// * `0` - The requested key ID
+    #[error("no key found with the identifier {0:?}")]
+    UnknownKid(String),
+    /// Indicates that the primary cipher can't be removed from the keyring.
+    #[error("the primary key can't be removed")]
+    PrimaryKid,
+    /// Indicates that the ciphertext is too short or its key ID is not valid
+    /// UTF-8.
+    #[error("invalid ciphertext envelope")]
+    InvalidEnvelope,
+    /// Indicates that the nonce couldn't be generated.
+    #[error(transparent)]
+    Nonce(#[from] NonceError),
+    /// Indicates that the encryption failed.
+    #[error(transparent)]
+    Encryption(#[from] EncryptionError),
+    /// Indicates that the decryption failed.
+    #[error(transparent)]
+    Decryption(#[from] DecryptionError),
+}
+
+impl ErrorCode for KeyringError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidKid => "keyring.invalid_kid",
+            Self::UnknownKid(..) => "keyring.unknown_kid",
+            Self::PrimaryKid => "keyring.primary_kid",
+            Self::InvalidEnvelope => "keyring.invalid_envelope",
+            Self::Nonce(inner) => inner.code(),
+            Self::Encryption(inner) => inner.code(),
+            Self::Decryption(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<KeyringError> for JsValue {
+    fn from(value: KeyringError) -> Self {
+        crate::js_error("KeyringError", &value)
+    }
+}
+
+/// Set of ciphers identified by their key ID, with a primary one used for
+/// encryption
+///
+/// The ciphers have to return the errors of this crate, wrapped by
+/// [`KeyringError`].
+#[derive(Debug, Clone)]
+pub struct Keyring<A> {
+    ciphers: Vec<(String, A)>,
+    primary: usize,
+}
+
+fn check_kid(kid: &str) -> Result<(), KeyringError> {
+    if kid.is_empty() || kid.len() > u8::MAX as usize {
+        return Err(KeyringError::InvalidKid);
+    }
+    Ok(())
+}
+
+impl<A> Keyring<A>
+where
+    A: Algorithm<EncryptError = EncryptionError, DecryptError = DecryptionError>,
+{
+    /// Creates a keyring with a primary cipher
+    ///
+    /// # Errors
+    /// - `KeyringError::InvalidKid` if the key ID is empty or longer than 255
+    ///   bytes
+    pub fn new(kid: impl Into<String>, cipher: A) -> Result<Self, KeyringError> {
+        let kid = kid.into();
+        check_kid(&kid)?;
+        Ok(Self {
+            ciphers: vec![(kid, cipher)],
+            primary: 0,
+        })
+    }
+
+    fn position(&self, kid: &str) -> Option<usize> {
+        self.ciphers.iter().position(|(id, _)| id == kid)
+    }
+
+    /// Adds a cipher, used to decrypt payloads embedding its key ID
+    ///
+    /// A cipher with the same key ID is replaced.
+    ///
+    /// # Errors
+    /// - `KeyringError::InvalidKid` if the key ID is empty or longer than 255
+    ///   bytes
+    pub fn insert(&mut self, kid: impl Into<String>, cipher: A) -> Result<(), KeyringError> {
+        let kid = kid.into();
+        check_kid(&kid)?;
+        match self.position(&kid) {
+            Some(index) => self.ciphers[index].1 = cipher,
+            None => self.ciphers.push((kid, cipher)),
+        }
+        Ok(())
+    }
+
+    /// Sets the cipher used to encrypt payloads
+    ///
+    /// # Errors
+    /// - `KeyringError::UnknownKid` if no cipher has the key ID
+    pub fn set_primary(&mut self, kid: &str) -> Result<(), KeyringError> {
+        self.primary = self
+            .position(kid)
+            .ok_or_else(|| KeyringError::UnknownKid(kid.to_string()))?;
+        Ok(())
+    }
+
+    /// Adds a cipher and makes it the primary one
+    ///
+    /// # Errors
+    /// - `KeyringError::InvalidKid` if the key ID is empty or longer than 255
+    ///   bytes
+    pub fn rotate(&mut self, kid: impl Into<String>, cipher: A) -> Result<(), KeyringError> {
+        let kid = kid.into();
+        self.insert(kid.clone(), cipher)?;
+        self.set_primary(&kid)
+    }
+
+    /// Removes a cipher, payloads embedding its key ID can't be decrypted
+    /// anymore
+    ///
+    /// # Errors
+    /// - `KeyringError::UnknownKid` if no cipher has the key ID
+    /// - `KeyringError::PrimaryKid` if the cipher is the primary one
+    pub fn remove(&mut self, kid: &str) -> Result<A, KeyringError> {
+        let index = self
+            .position(kid)
+            .ok_or_else(|| KeyringError::UnknownKid(kid.to_string()))?;
+        if index == self.primary {
+            return Err(KeyringError::PrimaryKid);
+        }
+        if index < self.primary {
+            self.primary -= 1;
+        }
+        Ok(self.ciphers.remove(index).1)
+    }
+
+    /// Key ID of the primary cipher
+    pub fn primary_kid(&self) -> &str {
+        &self.ciphers[self.primary].0
+    }
+
+    /// Key IDs of the ciphers, in insertion order
+    pub fn kids(&self) -> impl Iterator<Item = &str> {
+        self.ciphers.iter().map(|(kid, _)| kid.as_str())
+    }
+
+    /// Finds a cipher by its key ID
+    pub fn get(&self, kid: &str) -> Option<&A> {
+        self.position(kid).map(|index| &self.ciphers[index].1)
+    }
+
+    /// Encrypts a payload with the primary cipher and a random nonce
+    ///
+    /// # Arguments
+    /// * `payload` - Data to encrypt
+    ///
+    /// # Returns
+    /// Result containing the ciphertext, embedding the key ID and the nonce,
+    /// or a KeyringError
+    pub async fn encrypt(&self, payload: &[u8]) -> Result<Vec<u8>, KeyringError> {
+        let (kid, cipher) = &self.ciphers[self.primary];
+        let nonce = Nonce::<A>::generate()?;
+        let encrypted = cipher.encrypt(&nonce, payload).await?;
+
+        let mut output =
+            Vec::with_capacity(1 + kid.len() + A::NONCE_SIZE as usize + encrypted.len());
+        output.push(kid.len() as u8);
+        output.extend_from_slice(kid.as_bytes());
+        output.extend(nonce.iter());
+        output.extend_from_slice(&encrypted);
+        Ok(output)
+    }
+
+    /// Decrypts a payload with the cipher matching its key ID
+    ///
+    /// # Arguments
+    /// * `ciphertext` - Data returned by [`Keyring::encrypt`]
+    ///
+    /// # Returns
+    /// Result containing the decrypted payload or a KeyringError
+    ///
+    /// # Errors
+    /// - `KeyringError::InvalidEnvelope` if the ciphertext is malformed
+    /// - `KeyringError::UnknownKid` if no cipher has the embedded key ID
+    /// - `KeyringError::Decryption` if the decryption fails
+    pub async fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, KeyringError> {
+        let (kid, rest) = split_envelope(ciphertext)?;
+        let cipher = self
+            .get(kid)
+            .ok_or_else(|| KeyringError::UnknownKid(kid.to_string()))?;
+        let nonce_size = A::NONCE_SIZE as usize;
+        if rest.len() < nonce_size {
+            return Err(KeyringError::InvalidEnvelope);
+        }
+        let (nonce, encrypted) = rest.split_at(nonce_size);
+        let nonce = Nonce::<A>::from_slice(nonce)?;
+        Ok(cipher.decrypt(&nonce, encrypted).await?)
+    }
+}
+
+/// Reads the key ID embedded in a ciphertext produced by [`Keyring::encrypt`]
+///
+/// # Errors
+/// - `KeyringError::InvalidEnvelope` if the ciphertext is malformed
+pub fn kid_of(ciphertext: &[u8]) -> Result<&str, KeyringError> {
+    split_envelope(ciphertext).map(|(kid, _)| kid)
+}
+
+/// Splits a ciphertext into its key ID and the remaining bytes
+fn split_envelope(ciphertext: &[u8]) -> Result<(&str, &[u8]), KeyringError> {
+    let (&length, rest) = ciphertext
+        .split_first()
+        .ok_or(KeyringError::InvalidEnvelope)?;
+    let length = length as usize;
+    if length == 0 || rest.len() < length {
+        return Err(KeyringError::InvalidEnvelope);
+    }
+    let (kid, rest) = rest.split_at(length);
+    let kid = std::str::from_utf8(kid).map_err(|_| KeyringError::InvalidEnvelope)?;
+    Ok((kid, rest))
+}
@@ -0,0 +1,445 @@
+//! Persistence of keys in IndexedDB
+//!
+//! IndexedDB stores CryptoKey objects with the structured clone algorithm, so
+//! non extractable keys survive page reloads without their material ever
+//! being exposed to JavaScript. Each key is stored under an identifier, in
+//! the `keys` object store of the database.
+//!
+//! The keys are loaded back into the types of this crate, checking that the
+//! stored CryptoKey matches the expected algorithm. Signing keys also keep
+//! their hash function.
+//!
+//! Requires the `keystore` feature.
+//!
+//! ```rust,no_run
+//! use browser_crypto::aes_gcm::Aes256Gcm;
+//! use browser_crypto::keystore::Keystore;
+//!
+//! async fn persist() -> Result<(), Box<dyn std::error::Error>> {
+//!     let keystore = Keystore::open("my-app").await?;
+//!     let cipher = match keystore.get::<Aes256Gcm>("main").await? {
+//!         Some(cipher) => cipher,
+//!         None => {
+//!             let cipher = Aes256Gcm::generate(false).await?;
+//!             keystore.put("main", &cipher).await?;
+//!             cipher
+//!         }
+//!     };
+//!     Ok(())
+//! }
+//! ```
+
+use js_sys::Promise;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{DomException, IdbDatabase, IdbRequest, IdbTransaction, IdbTransactionMode};
+
+use crate::hash::Hash;
+use crate::key::CryptoKeyError;
+use crate::key_pair::{AsymmetricAlgorithm, KeyPair};
+use crate::ErrorCode;
+
+/// Version of the database schema
+const DATABASE_VERSION: u32 = 2;
+
+/// Name of the object store holding the keys
+const STORE_NAME: &str = "keys";
+
+/// Name of the object store holding the checkpoints of the nonce managers,
+/// added with the version 2 of the schema
+pub(crate) const NONCES_STORE_NAME: &str = "nonces";
+
+/// Errors that can occur when storing or loading keys.
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum KeystoreError {
+    /// Indicates that IndexedDB is not available, like in Node.js or in some
+    /// private browsing modes.
+    #[error("IndexedDB is not available")]
+    Unavailable,
+    /// Indicates that the browser ran out of storage space.
+    #[error("the storage quota has been exceeded")]
+    QuotaExceeded,
+    /// Indicates that the key couldn't be cloned into the database.
+    #[error("the key can't be stored by the browser")]
+    DataClone,
+    /// Indicates that the database has been closed or deleted.
+    #[error("the database is not usable anymore")]
+    InvalidState,
+    /// Indicates that the transaction has been aborted without error.
+    #[error("the transaction has been aborted")]
+    Aborted,
+    /// Indicates that the stored entry was not written by this module.
+    #[error("invalid stored entry")]
+    InvalidEntry,
+    /// Indicates that the stored key doesn't match the requested type.
+    #[error(transparent)]
+    CryptoKey(#[from] CryptoKeyError),
+    /// A wrapper for other types of errors that may occur while accessing the
+    /// database.
+    #[error(transparent)]
+    Generic(#[from] crate::Error),
+}
+
+impl ErrorCode for KeystoreError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Unavailable => "keystore.unavailable",
+            Self::QuotaExceeded => "keystore.quota_exceeded",
+            Self::DataClone => "keystore.data_clone",
+            Self::InvalidState => "keystore.invalid_state",
+            Self::Aborted => "keystore.aborted",
+            Self::InvalidEntry => "keystore.invalid_entry",
+            Self::CryptoKey(inner) => inner.code(),
+            Self::Generic(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<KeystoreError> for JsValue {
+    fn from(value: KeystoreError) -> Self {
+        crate::js_error("KeystoreError", &value)
+    }
+}
+
+impl From<JsValue> for KeystoreError {
+    fn from(value: JsValue) -> Self {
+        if let Some(exception) = value.dyn_ref::<DomException>() {
+            match exception.name().as_str() {
+                "QuotaExceededError" => {
+                    return Self::QuotaExceeded;
+                }
+                "DataCloneError" => {
+                    return Self::DataClone;
+                }
+                "InvalidStateError" => {
+                    return Self::InvalidState;
+                }
+                _ => {}
+            }
+        }
+        Self::Generic(crate::Error::from(value))
+    }
+}
+
+pub(crate) mod private {
+    /// Plumbing to turn keys into IndexedDB entries and back, not meant to be
+    /// implemented outside of this crate.
+    pub trait Sealed: Sized {
+        /// Builds the stored entry, holding the CryptoKey and its parameters
+        fn to_entry(&self) -> Result<js_sys::Object, wasm_bindgen::JsValue>;
+        /// Rebuilds the key from a stored entry
+        fn from_entry(entry: &wasm_bindgen::JsValue) -> Result<Self, super::KeystoreError>;
+    }
+}
+
+/// Keys that can be stored in a [`Keystore`]
+pub trait StorableKey: private::Sealed {}
+
+/// Builds an entry holding a CryptoKey
+pub(crate) fn key_entry(key: &web_sys::CryptoKey) -> Result<js_sys::Object, JsValue> {
+    let entry = js_sys::Object::new();
+    js_sys::Reflect::set(&entry, &"key".into(), key)?;
+    Ok(entry)
+}
+
+/// Builds an entry holding a CryptoKey and the hash function used with it
+pub(crate) fn key_hash_entry(
+    key: &web_sys::CryptoKey,
+    hash: Hash,
+) -> Result<js_sys::Object, JsValue> {
+    let entry = key_entry(key)?;
+    js_sys::Reflect::set(&entry, &"hash".into(), &hash.name().into())?;
+    Ok(entry)
+}
+
+/// Reads the CryptoKey of an entry
+pub(crate) fn entry_key(entry: &JsValue) -> Result<web_sys::CryptoKey, KeystoreError> {
+    js_sys::Reflect::get(entry, &"key".into())
+        .ok()
+        .and_then(|value| value.dyn_into().ok())
+        .ok_or(KeystoreError::InvalidEntry)
+}
+
+/// Reads the hash function of an entry
+pub(crate) fn entry_hash(entry: &JsValue) -> Result<Hash, KeystoreError> {
+    js_sys::Reflect::get(entry, &"hash".into())
+        .ok()
+        .and_then(|value| value.as_string())
+        .and_then(|name| Hash::from_name(&name))
+        .ok_or(KeystoreError::InvalidEntry)
+}
+
+/// Checks that the CryptoKey of a stored entry has the expected type,
+/// algorithm and usages
+pub(crate) fn check_entry_key(
+    entry: &JsValue,
+    key_type: &str,
+    name: &'static str,
+    usages: &[&'static str],
+) -> Result<web_sys::CryptoKey, KeystoreError> {
+    let key = entry_key(entry)?;
+    crate::key::check_key_algorithm(&key, key_type, name)?;
+    crate::key::check_key_usages(&key, usages)?;
+    Ok(key)
+}
+
+/// Both halves of a key pair are stored in the same entry.
+impl<A> private::Sealed for KeyPair<A>
+where
+    A: AsymmetricAlgorithm,
+    A::PublicKey: StorableKey,
+    A::PrivateKey: StorableKey,
+{
+    fn to_entry(&self) -> Result<js_sys::Object, JsValue> {
+        let public_key = self.public_key.to_entry()?;
+        let private_key = self.private_key.to_entry()?;
+        let entry = js_sys::Object::new();
+        js_sys::Reflect::set(&entry, &"publicKey".into(), &public_key)?;
+        js_sys::Reflect::set(&entry, &"privateKey".into(), &private_key)?;
+        Ok(entry)
+    }
+
+    fn from_entry(entry: &JsValue) -> Result<Self, KeystoreError> {
+        let public_key = js_sys::Reflect::get(entry, &"publicKey".into())
+            .map_err(|_| KeystoreError::InvalidEntry)?;
+        let private_key = js_sys::Reflect::get(entry, &"privateKey".into())
+            .map_err(|_| KeystoreError::InvalidEntry)?;
+        Ok(Self::new(
+            A::PublicKey::from_entry(&public_key)?,
+            A::PrivateKey::from_entry(&private_key)?,
+        ))
+    }
+}
+
+impl<A> StorableKey for KeyPair<A>
+where
+    A: AsymmetricAlgorithm,
+    A::PublicKey: StorableKey,
+    A::PrivateKey: StorableKey,
+{
+}
+
+/// Checks if IndexedDB is available in the current environment
+pub fn is_supported() -> bool {
+    matches!(crate::indexed_db(), Ok(Some(_)))
+}
+
+/// Callback listening to an IndexedDB event
+type Listener = Closure<dyn FnMut()>;
+
+/// Waits for one of the events of an IndexedDB request or transaction
+///
+/// # Returns
+/// Result containing `true` if the `success` event fired first, `false` if one
+/// of the `failures` events did
+async fn wait(
+    target: &web_sys::EventTarget,
+    success: &str,
+    failures: &[&str],
+) -> Result<bool, JsValue> {
+    let mut listeners: Vec<(&str, Listener)> = Vec::with_capacity(1 + failures.len());
+    let promise = Promise::new(&mut |resolve, _reject| {
+        let listener = |outcome: bool| {
+            let resolve = resolve.clone();
+            Listener::new(move || {
+                let _ = resolve.call1(&JsValue::UNDEFINED, &outcome.into());
+            })
+        };
+        listeners.push((success, listener(true)));
+        listeners.extend(failures.iter().map(|name| (*name, listener(false))));
+    });
+    for (name, listener) in listeners.iter() {
+        target.add_event_listener_with_callback(name, listener.as_ref().unchecked_ref())?;
+    }
+    let outcome = JsFuture::from(promise).await;
+    for (name, listener) in listeners.iter() {
+        target.remove_event_listener_with_callback(name, listener.as_ref().unchecked_ref())?;
+    }
+    Ok(outcome?.is_truthy())
+}
+
+/// Waits for a request to complete
+///
+/// # Returns
+/// Result containing the result of the request or a KeystoreError
+async fn request(request: &IdbRequest) -> Result<JsValue, KeystoreError> {
+    if wait(request, "success", &["error"]).await? {
+        return Ok(request.result()?);
+    }
+    match request.error()? {
+        Some(exception) => Err(KeystoreError::from(JsValue::from(exception))),
+        None => Err(KeystoreError::Aborted),
+    }
+}
+
+/// Waits for a transaction to be committed
+async fn commit(transaction: &IdbTransaction) -> Result<(), KeystoreError> {
+    if wait(transaction, "complete", &["abort"]).await? {
+        return Ok(());
+    }
+    match transaction.error() {
+        Some(exception) => Err(KeystoreError::from(JsValue::from(exception))),
+        None => Err(KeystoreError::Aborted),
+    }
+}
+
+/// Deletes a database and all the keys it contains
+///
+/// # Arguments
+/// * `name` - Name of the database
+///
+/// # Errors
+/// - `KeystoreError::Unavailable` if IndexedDB is not available
+pub async fn delete_database(name: &str) -> Result<(), KeystoreError> {
+    let factory = crate::indexed_db()?.ok_or(KeystoreError::Unavailable)?;
+    let delete_request = factory.delete_database(name)?;
+    request(&delete_request).await?;
+    Ok(())
+}
+
+/// Keys stored in an IndexedDB database
+#[derive(Debug, Clone)]
+pub struct Keystore {
+    database: IdbDatabase,
+}
+
+impl Keystore {
+    /// Opens a database, creating it when it doesn't exist.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the database, not shared with other IndexedDB users
+    ///
+    /// # Returns
+    /// Result containing the Keystore or a KeystoreError
+    ///
+    /// # Errors
+    /// - `KeystoreError::Unavailable` if IndexedDB is not available
+    pub async fn open(name: &str) -> Result<Self, KeystoreError> {
+        let factory = crate::indexed_db()?.ok_or(KeystoreError::Unavailable)?;
+        let open_request = factory.open_with_u32(name, DATABASE_VERSION)?;
+
+        let upgrade = {
+            let open_request = open_request.clone();
+            Closure::<dyn FnMut()>::new(move || {
+                if let Ok(database) = open_request.result().and_then(|value| value.dyn_into()) {
+                    let database: IdbDatabase = database;
+                    // creating an existing store fails, upgrading from the
+                    // previous version only adds the missing ones
+                    let _ = database.create_object_store(STORE_NAME);
+                    let _ = database.create_object_store(NONCES_STORE_NAME);
+                }
+            })
+        };
+        open_request.set_onupgradeneeded(Some(upgrade.as_ref().unchecked_ref()));
+        let result = request(&open_request).await;
+        open_request.set_onupgradeneeded(None);
+
+        let database = result?.dyn_into::<IdbDatabase>()?;
+        Ok(Self { database })
+    }
+
+    /// Name of the database
+    pub fn name(&self) -> String {
+        self.database.name()
+    }
+
+    /// Stores a key, replacing any key with the same identifier.
+    ///
+    /// The key doesn't need to be extractable.
+    ///
+    /// # Arguments
+    /// * `id` - Identifier of the key
+    /// * `key` - Key to store
+    ///
+    /// # Errors
+    /// - `KeystoreError::QuotaExceeded` if the browser ran out of storage space
+    /// - `KeystoreError::DataClone` if the browser can't store the key
+    pub async fn put<K: StorableKey>(&self, id: &str, key: &K) -> Result<(), KeystoreError> {
+        let entry = key.to_entry()?;
+        self.put_value(STORE_NAME, id, &entry).await
+    }
+
+    /// Loads a key.
+    ///
+    /// # Arguments
+    /// * `id` - Identifier of the key
+    ///
+    /// # Returns
+    /// Result containing the key, `None` if no key has this identifier, or a
+    /// KeystoreError
+    ///
+    /// # Errors
+    /// - `KeystoreError::InvalidEntry` if the entry was not written by this
+    ///   module
+    /// - `KeystoreError::CryptoKey` if the stored key doesn't match the
+    ///   requested type
+    pub async fn get<K: StorableKey>(&self, id: &str) -> Result<Option<K>, KeystoreError> {
+        let entry = self.get_value(STORE_NAME, id).await?;
+        if entry.is_undefined() {
+            return Ok(None);
+        }
+        K::from_entry(&entry).map(Some)
+    }
+
+    /// Deletes a key, if it exists.
+    ///
+    /// # Arguments
+    /// * `id` - Identifier of the key
+    pub async fn delete(&self, id: &str) -> Result<(), KeystoreError> {
+        self.delete_value(STORE_NAME, id).await
+    }
+
+    /// Lists the identifiers of the stored keys.
+    ///
+    /// # Returns
+    /// Result containing the identifiers, in ascending order, or a
+    /// KeystoreError
+    pub async fn ids(&self) -> Result<Vec<String>, KeystoreError> {
+        let transaction = self.database.transaction_with_str(STORE_NAME)?;
+        let ids = request(&transaction.object_store(STORE_NAME)?.get_all_keys()?).await?;
+        Ok(js_sys::Array::from(&ids)
+            .iter()
+            .filter_map(|id| id.as_string())
+            .collect())
+    }
+
+    /// Stores a value in an object store, replacing any value with the same
+    /// identifier, once the transaction is committed
+    pub(crate) async fn put_value(
+        &self,
+        store: &str,
+        id: &str,
+        value: &JsValue,
+    ) -> Result<(), KeystoreError> {
+        let transaction = self
+            .database
+            .transaction_with_str_and_mode(store, IdbTransactionMode::Readwrite)?;
+        transaction
+            .object_store(store)?
+            .put_with_key(value, &id.into())?;
+        commit(&transaction).await
+    }
+
+    /// Loads a value from an object store, `undefined` if there's none
+    pub(crate) async fn get_value(&self, store: &str, id: &str) -> Result<JsValue, KeystoreError> {
+        let transaction = self.database.transaction_with_str(store)?;
+        request(&transaction.object_store(store)?.get(&id.into())?).await
+    }
+
+    /// Deletes a value from an object store, if it exists
+    pub(crate) async fn delete_value(&self, store: &str, id: &str) -> Result<(), KeystoreError> {
+        let transaction = self
+            .database
+            .transaction_with_str_and_mode(store, IdbTransactionMode::Readwrite)?;
+        transaction.object_store(store)?.delete(&id.into())?;
+        commit(&transaction).await
+    }
+
+    /// Closes the connection to the database, once the pending transactions
+    /// are completed.
+    pub fn close(self) {
+        self.database.close();
+    }
+}
@@ -0,0 +1,178 @@
+//! Key identifiers
+//!
+//! In deployments with several keys, a key ID (`kid`) identifies which key
+//! produced a ciphertext. [`Tagged`] attaches a key ID to a cipher, a signer or
+//! a key encryption key when it's built, and the ID is carried automatically:
+//! - [`crate::envelope::Envelope`] records the key ID of its key encryption key
+//! - [`crate::sealed::Sealed`] records the key ID of its cipher
+//! - [`Tagged::to_jwk`] and [`Tagged::public_jwk`] set the `kid` member of the
+//!   exported JSON Web Keys, so they can be published in a JWK Set
+//!
+//! ```rust,no_run
+//! use browser_crypto::aes_kw::Aes256Kw;
+//! use browser_crypto::envelope::Envelope;
+//! use browser_crypto::kid::Tagged;
+//!
+//! async fn seal() -> Result<(), Box<dyn std::error::Error>> {
+//!     let kek = Tagged::new("2024-01", Aes256Kw::from_key(&[0u8; 32]).await?);
+//!     let envelope = Envelope::seal(&kek, b"Hello World!").await?;
+//!     assert_eq!(envelope.kid(), Some("2024-01"));
+//!     Ok(())
+//! }
+//! ```
+
+use crate::algorithm::{Algorithm, DecryptionError, EncryptionError, Nonce, VariableNonceSize};
+use crate::jwk::Jwk;
+use crate::key::{ExportKeyError, KeyUnwrapper, KeyWrapper, WrappableKey};
+use crate::key_pair::private::Sealed as _;
+use crate::key_pair::{AsymmetricAlgorithm, KeyPair};
+use crate::sealed::SealingCipher;
+
+/// Key along with its key ID
+///
+/// The methods of the key are available through `Deref`, like `sign` for a
+/// signing key.
+#[derive(Debug, Clone)]
+pub struct Tagged<K> {
+    kid: String,
+    key: K,
+}
+
+impl<K> Tagged<K> {
+    /// Attaches a key ID to a key
+    pub fn new(kid: impl Into<String>, key: K) -> Self {
+        Self {
+            kid: kid.into(),
+            key,
+        }
+    }
+
+    /// Key ID
+    pub fn kid(&self) -> &str {
+        &self.kid
+    }
+
+    /// Underlying key
+    pub fn inner(&self) -> &K {
+        &self.key
+    }
+
+    /// Splits into the key ID and the key
+    pub fn into_parts(self) -> (String, K) {
+        (self.kid, self.key)
+    }
+}
+
+impl<K> std::ops::Deref for Tagged<K> {
+    type Target = K;
+
+    fn deref(&self) -> &Self::Target {
+        &self.key
+    }
+}
+
+impl<K: WrappableKey> Tagged<K> {
+    /// Exports the key in the JSON Web Key format, with the key ID.
+    ///
+    /// # Returns
+    /// Result containing the JSON Web Key, of type "oct", or an ExportKeyError
+    pub async fn to_jwk(&self) -> Result<Jwk, ExportKeyError> {
+        let jwk = crate::key::export_jwk(self.key.crypto_key()?).await?;
+        Ok(jwk.with_kid(self.kid.as_str()))
+    }
+}
+
+impl<A: AsymmetricAlgorithm> Tagged<KeyPair<A>> {
+    /// Exports the public key in the JSON Web Key format, with the key ID, to
+    /// publish it in a JWK Set.
+    ///
+    /// # Returns
+    /// Result containing the JSON Web Key or an ExportKeyError
+    pub async fn public_jwk(&self) -> Result<Jwk, ExportKeyError> {
+        let jwk = crate::key::export_jwk(self.key.public_key.crypto_key()).await?;
+        Ok(jwk.with_kid(self.kid.as_str()))
+    }
+}
+
+/// The key ID is not part of the ciphertext, see [`crate::keyring`] to embed
+/// it.
+impl<A: Algorithm> Algorithm for Tagged<A> {
+    const NAME: &'static str = A::NAME;
+    const NONCE_SIZE: u32 = A::NONCE_SIZE;
+    const KEY_SIZE: u32 = A::KEY_SIZE;
+    type EncryptError = A::EncryptError;
+    type DecryptError = A::DecryptError;
+
+    async fn encrypt(
+        &self,
+        nonce: &Nonce<Self>,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, Self::EncryptError> {
+        crate::instrument::tagged(Some(&self.kid), self.key.encrypt(&nonce.cast(), payload)).await
+    }
+
+    async fn decrypt(
+        &self,
+        nonce: &Nonce<Self>,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, Self::DecryptError> {
+        crate::instrument::tagged(Some(&self.kid), self.key.decrypt(&nonce.cast(), payload)).await
+    }
+}
+
+impl<A: VariableNonceSize> VariableNonceSize for Tagged<A> {}
+
+impl<A: crate::nonce_sequence::SequentialNonce> crate::nonce_sequence::SequentialNonce
+    for Tagged<A>
+{
+}
+
+impl<W: crate::key::private::Wrapper> crate::key::private::Wrapper for Tagged<W> {
+    fn wrapping_algorithm(&self) -> Result<js_sys::Object, wasm_bindgen::JsValue> {
+        self.key.wrapping_algorithm()
+    }
+
+    fn wrapping_key(&self) -> &web_sys::CryptoKey {
+        self.key.wrapping_key()
+    }
+
+    fn kid(&self) -> Option<&str> {
+        Some(&self.kid)
+    }
+}
+
+impl<W: KeyWrapper> KeyWrapper for Tagged<W> {}
+
+impl<U: KeyUnwrapper> KeyUnwrapper for Tagged<U> {}
+
+impl<C: crate::sealed::private::Cipher> crate::sealed::private::Cipher for Tagged<C> {
+    const ALGORITHM: crate::sealed::SealedAlgorithm = C::ALGORITHM;
+
+    fn kid(&self) -> Option<&str> {
+        Some(&self.kid)
+    }
+
+    async fn encrypt_with_aad(
+        &self,
+        nonce: &Nonce<Self>,
+        aad: &[u8],
+        payload: &[u8],
+    ) -> Result<Vec<u8>, EncryptionError> {
+        let nonce = nonce.cast();
+        let future = self.key.encrypt_with_aad(&nonce, aad, payload);
+        crate::instrument::tagged(Some(&self.kid), future).await
+    }
+
+    async fn decrypt_with_aad(
+        &self,
+        nonce: &Nonce<Self>,
+        aad: &[u8],
+        payload: &[u8],
+    ) -> Result<Vec<u8>, DecryptionError> {
+        let nonce = nonce.cast();
+        let future = self.key.decrypt_with_aad(&nonce, aad, payload);
+        crate::instrument::tagged(Some(&self.kid), future).await
+    }
+}
+
+impl<C: SealingCipher> SealingCipher for Tagged<C> {}
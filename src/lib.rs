@@ -8,8 +8,50 @@
 //! # Features
 //!
 //! - Type-safe cryptographic algorithm implementations
-//! - Secure nonce generation and handling
-//! - AES-256-GCM encryption and decryption
+//! - Secure nonce generation and handling, with the nonce prepended to the
+//!   ciphertext in a single call
+//! - Typed raw keys, from bytes, hexadecimal or base64, checked against the key
+//!   size of the algorithm before their import
+//! - Secure random bytes, unbiased random integers and UUIDs, without depending
+//!   on another crate
+//! - Base64, base64url and hexadecimal encoders, with typed decoding errors
+//! - Counter-based nonce sequences, never repeating nor wrapping around, and
+//!   checkpointed to IndexedDB or `localStorage` to survive page reloads
+//! - Deterministic nonces derived with HKDF from unique message identifiers
+//! - Concurrent batch encryption and decryption of many small payloads
+//! - Key import and export in the SPKI and PKCS#8 formats, checking the key
+//!   algorithm, with PEM encoding helpers
+//! - Key import and export as JSON Web Keys, and JWK Sets
+//! - AES-GCM encryption and decryption, with 128, 192 or 256-bit keys, and GMAC
+//!   authentication
+//! - Key-committing AES-GCM, against partitioning oracle attacks
+//! - Padmé and block padding, hiding the size of the encrypted payloads
+//! - Chunked encryption of large payloads, with the STREAM construction, and
+//!   its Web Streams and Blob integrations
+//! - AES-CBC and AES-CTR encryption and decryption, for legacy interoperability
+//! - AES-KW key wrapping, keys being unwrapped as non extractable
+//! - RSA-OAEP asymmetric encryption and key wrapping
+//! - RSASSA-PKCS1-v1_5 signatures
+//! - ECDSA signatures, over the P-256, P-384 and P-521 curves
+//! - Ed25519 signatures, on browsers supporting it
+//! - ECDH and X25519 key agreement
+//! - ECIES, encrypting with an ephemeral ECDH key, HKDF and AES-256-GCM
+//! - HPKE hybrid public key encryption (RFC 9180), in the base and
+//!   authenticated modes
+//! - HMAC message authentication
+//! - SHA digests, with incremental hashing of large files
+//! - Keyrings, to rotate encryption keys
+//! - Key IDs attached to keys, recorded in envelopes and exported JSON Web Keys
+//! - Envelope encryption, wrapping a new data encryption key for each payload
+//! - Versioned ciphertexts with a stable layout, recording the algorithm, the
+//!   key ID and the hash of the additional data
+//! - HKDF and PBKDF2 key derivation, directly into non extractable AES-GCM
+//!   keys, with typed salts
+//! - Password-based encryption in a single call, with PBKDF2 and AES-256-GCM,
+//!   passphrase-protected key backups and passphrase hashing for local unlock
+//! - Persistence of non extractable keys in IndexedDB, or of wrapped keys in
+//!   `localStorage`
+//! - Constant-time comparison of MACs, tokens and fingerprints
 //! - Proper error handling and conversion from Web API exceptions
 //!
 //! # Examples
@@ -56,8 +98,57 @@
 //!
 //! # Features Flags
 //!
-//! - `log-error`: Enables console logging of unknown errors (useful for
-//!   debugging)
+//! - `log-error`: Enables console logging of the JavaScript exceptions other
+//!   than DOMExceptions, with their stack trace (useful for debugging)
+//! - `redact-errors`: Replaces the messages of the JavaScript exceptions, which
+//!   may contain sensitive details, with `[redacted]` in the errors, the names
+//!   of the exceptions being kept. Combined with `log-error`, all the
+//!   exceptions are logged to the console with their details
+//! - `fetch`: Enables downloading JWK Sets with the Fetch API
+//! - `serde`: Implements `Serialize` and `Deserialize` for JSON Web Keys,
+//!   sealed payloads, nonces, salts and the main error types, to post them to
+//!   another worker or report them to a telemetry backend
+//! - `keystore`: Enables storing keys and nonce checkpoints in IndexedDB or
+//!   `localStorage`
+//! - `streams`: Enables encrypting and decrypting Web Streams and Blobs
+//! - `incremental-digest`: Enables hashing large inputs chunk by chunk, in
+//!   software
+//! - `bytes`: Enables encrypting from and to `bytes::Bytes` buffers
+//! - `rand_core`: Implements the `rand_core` traits for the browser's random
+//!   number generator
+//! - `getrandom`: Registers the browser's random number generator as the custom
+//!   backend of `getrandom`
+//! - `secrecy`: Accepts keys and passphrases wrapped in `secrecy::SecretBox`
+//! - `zeroize`: Overwrites the intermediate copies of key material with zeros,
+//!   implements `Zeroize` for JSON Web Keys and `ZeroizeOnDrop` for the types
+//!   holding secrets
+//! - `tracing`: Emits `tracing` spans and events for the Web Crypto operations,
+//!   like the key imports, encryptions and signatures, with their algorithm,
+//!   input size, duration and error code, never the data nor the key material
+//! - `metrics`: Times the same operations with `performance.now()` and hands
+//!   the durations to a registered `Metrics` recorder
+//! - `audit`: Hands a record of each Web Crypto operation, with its key ID,
+//!   payload length and outcome, to a registered `AuditSink`, to build
+//!   client-side audit trails
+//! - `native`: Runs AES-GCM, the digests and the random generation with the
+//!   RustCrypto implementations outside of WebAssembly, so the code sharing
+//!   them with a browser application can be unit tested or run on a server
+//! - `mock`: Lets the tests register a backend with deterministic random values
+//!   and canned failures, like a `QuotaExceededError`, to exercise their error
+//!   handling
+//! - `test-util`: Provides deterministic keys, fixed-nonce ciphers and
+//!   round-trip assertions for the tests of the crates using this one
+//! - `test-vectors`: Runs AES-GCM test vectors, embedded or parsed from the
+//!   NIST CAVP and Wycheproof files, to validate the implementation of a
+//!   platform
+//! - `signature`: Provides async analogues of the `signature` crate's `Signer`
+//!   and `Verifier` traits, implemented for the signing keys
+//! - `digest`: Provides SHA hash functions mirroring the `digest` crate's
+//!   interface, buffering their input and computing the digest with the Web
+//!   Crypto API
+//! - `aead`: Provides an async analogue of the `aead` crate's traits,
+//!   implemented for AES-GCM, to move code written against the RustCrypto
+//!   ciphers to the browser
 //!
 //! # Browser Compatibility
 //!
@@ -69,6 +160,17 @@
 //!
 //! Most modern browsers (Chrome, Firefox, Safari, Edge) support these features.
 //!
+//! The crypto interface is resolved from the current global scope, which can be
+//! either the main browser thread (`Window`), a service worker
+//! (`ServiceWorkerGlobalScope`) or any other worker (`WorkerGlobalScope`).
+//! In any other environment, like Node.js, the crypto interface is read from
+//! `globalThis.crypto`.
+//!
+//! The crate also builds for the other targets, so a workspace can check or
+//! test its host crates without gating every use site, but the operations
+//! relying on the Web Crypto API fail there with `Error::Unsupported`, unless
+//! the `native` feature provides them.
+//!
 //! # Error Handling
 //!
 //! The crate provides detailed error types that map directly to Web Crypto API
@@ -79,6 +181,21 @@
 //! - `DecryptionError`: Decryption-specific errors
 //! - `NonceError`: Nonce generation and validation errors
 //! - `ImportKeyError`: Key import and format errors
+//! - `ExportKeyError`/`GenerateKeyError`: Key export and generation errors
+//! - `WrapKeyError`/`UnwrapKeyError`: Key wrapping errors
+//! - `SignError`/`VerifyError`: Signature errors
+//! - `DeriveError`: Key and bits derivation errors
+//! - `CryptoKeyError`: Mismatching `CryptoKey` created outside of the crate
+//! - `ContextError`: Any of those errors, with the operation and the algorithm
+//!   that failed
+//!
+//! The error enums are `#[non_exhaustive]`, new exceptions being mapped to new
+//! variants, and implement [`ErrorCode`], which returns a stable identifier,
+//! like `"decryption.operation"`, to match on.
+//!
+//! All of them convert into a `JsValue`, a JavaScript `Error` named after the
+//! error type with a `code` property, and into a `JsError`, so the functions
+//! exported with `wasm-bindgen` can use `?`.
 //!
 //! # Implementation Details
 //!
@@ -96,10 +213,84 @@
 use js_sys::Promise;
 use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::JsFuture;
-use web_sys::{DomException, WorkerGlobalScope};
+use web_sys::{DomException, ServiceWorkerGlobalScope, Window, WorkerGlobalScope};
 
+#[cfg(feature = "aead")]
+pub mod aead;
 pub mod aes256gcm;
+pub mod aes_cbc;
+pub mod aes_ctr;
+pub mod aes_gcm;
+pub mod aes_kw;
 pub mod algorithm;
+pub mod any_cipher;
+#[cfg(feature = "audit")]
+pub mod audit;
+#[cfg(feature = "streams")]
+pub mod blob;
+pub mod committing;
+pub mod context;
+pub mod curve;
+mod der;
+pub mod derive;
+#[cfg(feature = "digest")]
+pub mod digest;
+pub mod dyn_algorithm;
+pub mod ecdh;
+pub mod ecdsa;
+pub mod ecies;
+pub mod ed25519;
+pub mod encoding;
+pub mod envelope;
+pub mod hash;
+#[cfg(feature = "incremental-digest")]
+pub mod hasher;
+pub mod hkdf;
+pub mod hmac;
+pub mod hpke;
+pub mod input;
+mod instrument;
+mod join;
+pub mod jwk;
+pub mod jwks;
+pub mod key;
+pub mod key_pair;
+pub mod keyring;
+#[cfg(feature = "keystore")]
+pub mod keystore;
+pub mod kid;
+#[cfg(feature = "keystore")]
+pub mod local_keystore;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "mock")]
+pub mod mock;
+#[cfg(native_backend)]
+mod native;
+pub mod nonce_derivation;
+#[cfg(feature = "keystore")]
+pub mod nonce_manager;
+pub mod nonce_sequence;
+pub mod padding;
+pub mod password;
+pub mod pbkdf2;
+pub mod pem;
+pub mod random;
+pub mod registry;
+pub mod rsa_oaep;
+pub mod rsassa_pkcs1;
+pub mod salt;
+pub mod sealed;
+pub mod signature;
+pub mod stream;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+pub mod util;
+#[cfg(feature = "test-vectors")]
+pub mod vectors;
+#[cfg(feature = "streams")]
+pub mod web_stream;
+pub mod x25519;
 
 /// Utility functions
 /// Resolves a JavaScript Promise to a Rust Result
@@ -123,6 +314,8 @@ where
 
 /// General errors that can occur when interacting with the Web Crypto API.
 #[derive(Debug, Clone, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum Error {
     /// Indicates that the global scope (window or worker context) could not be
     /// accessed. This might occur in environments where the Web API is not
@@ -142,41 +335,260 @@ pub enum Error {
     /// * `1` - The error message
     #[error("DOMException {0}: {1}")]
     DomException(String, String),
-    /// Represents an unknown or unexpected error that couldn't be classified.
+    /// Represents a JavaScript error which is not a DOM exception, like a
+    /// `TypeError` thrown by the browser for invalid arguments.
+    ///
+    /// # Fields
+    /// * `name` - The name of the error, like "TypeError"
+    /// * `message` - The error message
+    #[error("{name}: {message}")]
+    JsError { name: String, message: String },
+    /// Represents a thrown value which is not an error object, like a string
+    /// or a number thrown by other JavaScript code.
+    ///
+    /// # Fields
+    /// * `0` - The value, as a string or in JSON
+    #[error("exception thrown: {0}")]
+    Thrown(String),
+    /// Represents an unknown or unexpected error that couldn't be classified,
+    /// like `undefined` or a value that can't be converted to JSON.
     /// When the `log-error` feature is enabled, these errors will be logged
     /// to the console.
     #[error("unknown exception")]
     Unknown,
+    /// Indicates that the crate runs outside of WebAssembly, where the Web
+    /// Crypto API can't be reached, like in the host tests of a workspace.
+    #[error("the Web Crypto API is not available on this target")]
+    Unsupported,
+}
+
+/// Stable identifiers of the errors
+///
+/// Implemented by all the error types of the crate. The codes are made of the
+/// error type and its variant, like `"decryption.operation"`, and don't change
+/// between versions, unlike the messages, so they can be matched by the
+/// application or aggregated by a telemetry backend. The errors wrapping
+/// another one, displayed transparently, report the code of the wrapped error.
+pub trait ErrorCode {
+    /// Stable identifier of the error
+    fn code(&self) -> &'static str;
+}
+
+/// Converts an error into a JavaScript `Error`, named after its type, with its
+/// message and its [`ErrorCode`] in the `code` property
+///
+/// Backs the `From` implementations turning the errors of the crate into a
+/// `JsValue`, so the functions exported with `wasm-bindgen` can use `?`.
+fn js_error(name: &str, error: &(impl std::fmt::Display + ErrorCode)) -> JsValue {
+    let output = js_sys::Error::new(&error.to_string());
+    output.set_name(name);
+    let _ = js_sys::Reflect::set(&output, &"code".into(), &error.code().into());
+    output.into()
+}
+
+impl ErrorCode for Error {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::GlobalScopeNotFound => "generic.global_scope_not_found",
+            Self::CryptoUnreachable => "generic.crypto_unreachable",
+            Self::DomException(..) => "generic.dom_exception",
+            Self::JsError { .. } => "generic.js_error",
+            Self::Thrown(..) => "generic.thrown",
+            Self::Unknown => "generic.unknown",
+            Self::Unsupported => "generic.unsupported",
+        }
+    }
+}
+
+impl From<Error> for JsValue {
+    fn from(value: Error) -> Self {
+        crate::js_error("Error", &value)
+    }
 }
 
 impl From<JsValue> for Error {
     /// Converts a JavaScript value into a Rust Error.
     ///
     /// If the JavaScript value is a DOMException, it will be converted into
-    /// a `Error::DomException` with the appropriate name and message.
-    /// Otherwise, it will be converted into `Error::Unknown`.
+    /// a `Error::DomException` with the appropriate name and message. Other
+    /// error objects are converted into `Error::JsError`, strings, numbers or
+    /// any value with a JSON representation into `Error::Thrown`. Otherwise,
+    /// it will be converted into `Error::Unknown`.
     ///
-    /// When the `log-error` feature is enabled, unknown errors will be logged
-    /// to the console for debugging purposes.
+    /// When the `log-error` feature is enabled, all the values but the
+    /// DOMExceptions will be logged to the console for debugging purposes,
+    /// with their stack trace when available.
+    ///
+    /// When the `redact-errors` feature is enabled, the messages and the
+    /// thrown values are replaced by [`REDACTED`], only the names of the
+    /// exceptions being kept. Combined with `log-error`, the DOMExceptions are
+    /// logged as well, the console being the only place with the details.
     fn from(value: JsValue) -> Self {
         if let Some(exception) = value.dyn_ref::<DomException>() {
-            Self::DomException(exception.name(), exception.message())
-        } else {
-            #[cfg(feature = "log-error")]
+            #[cfg(all(feature = "log-error", feature = "redact-errors"))]
             web_sys::console::error_1(&value);
-            Self::Unknown
+            return Self::DomException(exception.name(), redact(exception.message()));
+        }
+        #[cfg(feature = "log-error")]
+        web_sys::console::error_1(&value);
+        if let Some(error) = value.dyn_ref::<js_sys::Error>() {
+            return Self::JsError {
+                name: error.name().into(),
+                message: redact(error.message().into()),
+            };
+        }
+        value
+            .as_string()
+            .or_else(|| {
+                js_sys::JSON::stringify(&value)
+                    .ok()
+                    .and_then(|json| json.as_string())
+            })
+            .map_or(Self::Unknown, |value| Self::Thrown(redact(value)))
+    }
+}
+
+/// Replacement of the messages of the JavaScript exceptions, with the
+/// `redact-errors` feature
+pub const REDACTED: &str = "[redacted]";
+
+/// Replaces a message by [`REDACTED`] when the `redact-errors` feature is
+/// enabled
+fn redact(message: String) -> String {
+    if cfg!(feature = "redact-errors") {
+        REDACTED.to_string()
+    } else {
+        message
+    }
+}
+
+/// Global scopes exposing the Web Crypto API
+enum Scope {
+    /// Main browser thread
+    Window(Window),
+    /// Service worker
+    ServiceWorker(ServiceWorkerGlobalScope),
+    /// Dedicated or shared worker
+    Worker(WorkerGlobalScope),
+    /// Any other environment exposing `globalThis.crypto`, like Node.js
+    Global(js_sys::Object),
+}
+
+impl Scope {
+    fn crypto(&self) -> Result<web_sys::Crypto, Error> {
+        match self {
+            Self::Window(window) => window.crypto(),
+            Self::ServiceWorker(worker) => worker.crypto(),
+            Self::Worker(worker) => worker.crypto(),
+            Self::Global(global) => js_sys::Reflect::get(global, &"crypto".into())
+                .and_then(|value| value.dyn_into::<web_sys::Crypto>()),
+        }
+        .map_err(|_| Error::CryptoUnreachable)
+    }
+
+    #[cfg(feature = "fetch")]
+    fn fetch(&self, url: &str) -> Result<Promise, Error> {
+        match self {
+            Self::Window(window) => Ok(window.fetch_with_str(url)),
+            Self::ServiceWorker(worker) => Ok(worker.fetch_with_str(url)),
+            Self::Worker(worker) => Ok(worker.fetch_with_str(url)),
+            Self::Global(global) => js_sys::Reflect::get(global, &"fetch".into())
+                .and_then(|value| value.dyn_into::<js_sys::Function>())
+                .and_then(|fetch| fetch.call1(global, &url.into()))
+                .and_then(|value| value.dyn_into::<Promise>())
+                .map_err(Error::from),
         }
     }
+
+    #[cfg(feature = "keystore")]
+    fn indexed_db(&self) -> Result<Option<web_sys::IdbFactory>, Error> {
+        match self {
+            Self::Window(window) => window.indexed_db(),
+            Self::ServiceWorker(worker) => worker.indexed_db(),
+            Self::Worker(worker) => worker.indexed_db(),
+            Self::Global(global) => js_sys::Reflect::get(global, &"indexedDB".into())
+                .map(|value| value.dyn_into::<web_sys::IdbFactory>().ok()),
+        }
+        .map_err(Error::from)
+    }
+
+    /// Web Storage of the origin, only exposed to the main browser thread
+    #[cfg(feature = "keystore")]
+    fn local_storage(&self) -> Result<Option<web_sys::Storage>, Error> {
+        match self {
+            Self::Window(window) => window.local_storage(),
+            Self::Global(global) => js_sys::Reflect::get(global, &"localStorage".into())
+                .map(|value| value.dyn_into::<web_sys::Storage>().ok()),
+            Self::ServiceWorker(_) | Self::Worker(_) => Ok(None),
+        }
+        .map_err(Error::from)
+    }
 }
 
-fn scope() -> Result<web_sys::WorkerGlobalScope, Error> {
-    js_sys::global()
-        .dyn_into::<WorkerGlobalScope>()
-        .map_err(|_| Error::GlobalScopeNotFound)
+/// Detects the global scope the code is running in
+///
+/// Workers are checked first, then the main browser thread and finally any
+/// other global object, reading its `crypto` property directly.
+fn scope() -> Result<Scope, Error> {
+    check_target()?;
+    let global = js_sys::global();
+    if let Some(worker) = global.dyn_ref::<ServiceWorkerGlobalScope>() {
+        return Ok(Scope::ServiceWorker(worker.clone()));
+    }
+    if let Some(worker) = global.dyn_ref::<WorkerGlobalScope>() {
+        return Ok(Scope::Worker(worker.clone()));
+    }
+    if let Some(window) = global.dyn_ref::<Window>() {
+        return Ok(Scope::Window(window.clone()));
+    }
+    if global.is_undefined() || global.is_null() {
+        return Err(Error::GlobalScopeNotFound);
+    }
+    Ok(Scope::Global(global))
+}
+
+/// Fails outside of WebAssembly, before calling the JavaScript bindings, which
+/// panic on the other targets
+///
+/// # Errors
+/// - `Error::Unsupported` if the target is not `wasm32`
+fn check_target() -> Result<(), Error> {
+    if cfg!(target_arch = "wasm32") {
+        Ok(())
+    } else {
+        Err(Error::Unsupported)
+    }
 }
 
 fn crypto() -> Result<web_sys::Crypto, Error> {
-    scope().and_then(|scope| scope.crypto().map_err(|_| Error::CryptoUnreachable))
+    scope().and_then(|scope| scope.crypto())
+}
+
+/// Sends a GET request with the Fetch API
+///
+/// # Returns
+/// Result containing the Promise of the Response or Error
+#[cfg(feature = "fetch")]
+fn fetch(url: &str) -> Result<Promise, Error> {
+    scope().and_then(|scope| scope.fetch(url))
+}
+
+/// Gets the IndexedDB factory
+///
+/// # Returns
+/// Result containing the IdbFactory, if the environment exposes one, or Error
+#[cfg(feature = "keystore")]
+fn indexed_db() -> Result<Option<web_sys::IdbFactory>, Error> {
+    scope().and_then(|scope| scope.indexed_db())
+}
+
+/// Gets the `localStorage` of the origin
+///
+/// # Returns
+/// Result containing the Storage, if the environment exposes one, or Error
+#[cfg(feature = "keystore")]
+fn local_storage() -> Result<Option<web_sys::Storage>, Error> {
+    scope().and_then(|scope| scope.local_storage())
 }
 
 /// Gets the Web Crypto API interface
@@ -192,3 +604,97 @@ fn array_to_vec(input: &js_sys::Uint8Array) -> Vec<u8> {
     input.copy_to(&mut output);
     output
 }
+
+/// Copies the content of an array holding key material into a new buffer,
+/// overwriting the array with zeros when the `zeroize` feature is enabled
+fn secret_array_to_vec(input: &js_sys::Uint8Array) -> Vec<u8> {
+    let output = array_to_vec(input);
+    wipe_array(input);
+    output
+}
+
+/// Overwrites an array holding key material with zeros, when the `zeroize`
+/// feature is enabled
+fn wipe_array(input: &js_sys::Uint8Array) {
+    #[cfg(feature = "zeroize")]
+    input.fill(0, 0, input.length());
+    #[cfg(not(feature = "zeroize"))]
+    let _ = input;
+}
+
+/// Buffer holding key material, overwritten with zeros when dropped if the
+/// `zeroize` feature is enabled
+#[derive(Clone, Default)]
+struct SecretBytes(Vec<u8>);
+
+impl From<Vec<u8>> for SecretBytes {
+    fn from(value: Vec<u8>) -> Self {
+        Self(value)
+    }
+}
+
+impl std::ops::Deref for SecretBytes {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl std::ops::DerefMut for SecretBytes {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SecretBytes {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
+
+/// Whether the WebAssembly memory is shared between threads
+fn shared_memory() -> bool {
+    thread_local! {
+        static SHARED: bool = !wasm_bindgen::memory()
+            .unchecked_into::<js_sys::WebAssembly::Memory>()
+            .buffer()
+            .is_instance_of::<js_sys::ArrayBuffer>();
+    }
+    SHARED.with(|shared| *shared)
+}
+
+/// Passes some bytes to a Web Crypto function, without copying them when
+/// possible
+///
+/// The Web Crypto functions copy their input before returning their promise,
+/// so a view over the WebAssembly memory is enough. A shared memory is copied,
+/// as the Web Crypto API rejects shared buffers.
+///
+/// The view is invalidated by any allocation, so `call` must pass it directly
+/// to the Web Crypto function.
+fn with_bytes<R>(data: &[u8], call: impl FnOnce(&js_sys::Uint8Array) -> R) -> R {
+    if shared_memory() {
+        return call(&js_sys::Uint8Array::from(data));
+    }
+    // SAFETY: the view doesn't outlive `data` and is copied by the Web Crypto
+    // function before anything can allocate and grow the memory
+    let view = unsafe { js_sys::Uint8Array::view(data) };
+    call(&view)
+}
+
+/// Copies the content of an array into a new buffer
+#[cfg(feature = "bytes")]
+fn array_to_bytes(input: &js_sys::Uint8Array) -> bytes::Bytes {
+    let mut output = bytes::BytesMut::zeroed(input.length() as usize);
+    input.copy_to(&mut output);
+    output.freeze()
+}
+
+/// Appends the content of an array to a buffer, reusing its capacity
+fn extend_from_array(output: &mut Vec<u8>, input: &js_sys::Uint8Array) {
+    let start = output.len();
+    output.resize(start + input.length() as usize, 0);
+    input.copy_to(&mut output[start..]);
+}
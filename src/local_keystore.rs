@@ -0,0 +1,364 @@
+//! Persistence of wrapped keys in `localStorage`
+//!
+//! A fallback for [`crate::keystore`], when IndexedDB is not available.
+//! `localStorage` only stores strings, so keys can't be kept as non
+//! extractable CryptoKey objects: they're wrapped before being stored, either
+//! with an AES-KW key derived from a passphrase or with a key encryption key
+//! provided by the application. A store can't be created without one of them.
+//!
+//! Security caveats:
+//! - any script running on the origin can read and delete the stored entries,
+//!   the protection of the keys only relies on the passphrase or the key
+//!   encryption key, which must not be stored next to them
+//! - the keys must be extractable to be wrapped
+//! - `localStorage` is only exposed to the main browser thread
+//!
+//! Each entry is stored as the format version, the protection scheme and the
+//! base64 encoded wrapped key, separated by dots.
+//!
+//! Requires the `keystore` feature.
+//!
+//! ```rust,no_run
+//! use browser_crypto::aes_gcm::Aes256Gcm;
+//! use browser_crypto::local_keystore::LocalKeystore;
+//!
+//! async fn persist(passphrase: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+//!     let keystore = LocalKeystore::with_passphrase("my-app.", passphrase)?;
+//!     keystore
+//!         .put("main", &Aes256Gcm::generate(true).await?)
+//!         .await?;
+//!     let cipher: Option<Aes256Gcm> = keystore.get("main").await?;
+//!     Ok(())
+//! }
+//! ```
+
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{DomException, Storage};
+
+use crate::aes_kw::Aes256Kw;
+use crate::encoding::{base64_decode, base64_encode};
+use crate::input::SecretInput;
+use crate::key::{KeyUnwrapper, KeyWrapper, UnwrapKeyError, WrapKeyError, WrappableKey};
+use crate::password::PasswordError;
+use crate::pbkdf2::Pbkdf2;
+use crate::ErrorCode;
+
+/// Version of the entry format
+pub const VERSION: u8 = 1;
+
+/// Scheme of the entries wrapped with a passphrase
+const PASSPHRASE_SCHEME: &str = "pbkdf2";
+
+/// Scheme of the entries wrapped with a key encryption key
+const WRAPPING_KEY_SCHEME: &str = "kw";
+
+/// Errors that can occur when storing or loading wrapped keys.
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum LocalKeystoreError {
+    /// Indicates that `localStorage` is not available, like in workers or when
+    /// the user disabled it.
+    #[error("localStorage is not available")]
+    Unavailable,
+    /// Indicates that the browser ran out of storage space.
+    #[error("the storage quota has been exceeded")]
+    QuotaExceeded,
+    /// Indicates that the stored entry was not written by this module.
+    #[error("invalid stored entry")]
+    InvalidEntry,
+    /// Indicates that the stored entry was written by an unknown version of
+    /// the format.
+    ///
+    /// # Fields
+    /// * `0` - The version found in the entry
+    #[error("unsupported entry version {0}")]
+    UnsupportedVersion(u8),
+    /// Indicates that the stored entry is protected with another scheme, like
+    /// a passphrase when the store uses a key encryption key.
+    ///
+    /// # Fields
+    /// * `0` - The scheme found in the entry
+    #[error("entry protected with the {0:?} scheme")]
+    ProtectionMismatch(String),
+    /// Indicates that the key couldn't be wrapped or unwrapped with the
+    /// passphrase.
+    #[error(transparent)]
+    Password(#[from] PasswordError),
+    /// Indicates that the key couldn't be wrapped, like when it's not
+    /// extractable.
+    #[error(transparent)]
+    Wrap(#[from] WrapKeyError),
+    /// Indicates that the key couldn't be unwrapped, like when the key
+    /// encryption key doesn't match.
+    #[error(transparent)]
+    Unwrap(#[from] UnwrapKeyError),
+    /// A wrapper for other types of errors that may occur while accessing the
+    /// storage.
+    #[error(transparent)]
+    Generic(#[from] crate::Error),
+}
+
+impl ErrorCode for LocalKeystoreError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Unavailable => "local_keystore.unavailable",
+            Self::QuotaExceeded => "local_keystore.quota_exceeded",
+            Self::InvalidEntry => "local_keystore.invalid_entry",
+            Self::UnsupportedVersion(..) => "local_keystore.unsupported_version",
+            Self::ProtectionMismatch(..) => "local_keystore.protection_mismatch",
+            Self::Password(inner) => inner.code(),
+            Self::Wrap(inner) => inner.code(),
+            Self::Unwrap(inner) => inner.code(),
+            Self::Generic(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<LocalKeystoreError> for JsValue {
+    fn from(value: LocalKeystoreError) -> Self {
+        crate::js_error("LocalKeystoreError", &value)
+    }
+}
+
+impl From<JsValue> for LocalKeystoreError {
+    fn from(value: JsValue) -> Self {
+        if let Some(exception) = value.dyn_ref::<DomException>() {
+            if exception.name() == "QuotaExceededError" {
+                return Self::QuotaExceeded;
+            }
+        }
+        Self::Generic(crate::Error::from(value))
+    }
+}
+
+/// Protection of the stored keys
+#[derive(Clone)]
+enum Protection {
+    /// Wrapped with an AES-KW key derived from the passphrase, with a random
+    /// salt for each entry
+    Passphrase {
+        passphrase: crate::SecretBytes,
+        iterations: u32,
+    },
+    /// Wrapped with a key encryption key
+    WrappingKey(Aes256Kw),
+}
+
+impl Protection {
+    fn scheme(&self) -> &'static str {
+        match self {
+            Self::Passphrase { .. } => PASSPHRASE_SCHEME,
+            Self::WrappingKey(_) => WRAPPING_KEY_SCHEME,
+        }
+    }
+}
+
+/// Wrapped keys stored in `localStorage`, or any other Web Storage
+#[derive(Clone)]
+pub struct LocalKeystore {
+    storage: Storage,
+    prefix: String,
+    protection: Protection,
+}
+
+impl LocalKeystore {
+    /// Creates a store in `localStorage`, wrapping the keys with a passphrase
+    /// and [`Pbkdf2::DEFAULT_ITERATIONS`] iterations.
+    ///
+    /// # Arguments
+    /// * `prefix` - Prefix of the storage entries, to share the storage with
+    ///   other data
+    /// * `passphrase` - Passphrase protecting the keys
+    ///
+    /// # Returns
+    /// Result containing the LocalKeystore or a LocalKeystoreError
+    ///
+    /// # Errors
+    /// - `LocalKeystoreError::Unavailable` if `localStorage` is not available
+    pub fn with_passphrase(
+        prefix: &str,
+        passphrase: &(impl SecretInput + ?Sized),
+    ) -> Result<Self, LocalKeystoreError> {
+        Self::with_passphrase_and_iterations(prefix, passphrase, Pbkdf2::DEFAULT_ITERATIONS)
+    }
+
+    /// Creates a store in `localStorage`, wrapping the keys with a passphrase
+    /// and a custom number of iterations.
+    ///
+    /// The number of iterations is stored in each entry, changing it doesn't
+    /// prevent loading the existing keys.
+    ///
+    /// # Errors
+    /// - `LocalKeystoreError::Unavailable` if `localStorage` is not available
+    pub fn with_passphrase_and_iterations(
+        prefix: &str,
+        passphrase: &(impl SecretInput + ?Sized),
+        iterations: u32,
+    ) -> Result<Self, LocalKeystoreError> {
+        let protection = Protection::Passphrase {
+            passphrase: passphrase.expose_bytes().to_vec().into(),
+            iterations,
+        };
+        Ok(Self::new(local_storage()?, prefix, protection))
+    }
+
+    /// Creates a store in `localStorage`, wrapping the keys with a key
+    /// encryption key.
+    ///
+    /// # Arguments
+    /// * `prefix` - Prefix of the storage entries
+    /// * `wrapping_key` - Key encryption key, kept outside of the storage
+    ///
+    /// # Errors
+    /// - `LocalKeystoreError::Unavailable` if `localStorage` is not available
+    pub fn with_wrapping_key(
+        prefix: &str,
+        wrapping_key: Aes256Kw,
+    ) -> Result<Self, LocalKeystoreError> {
+        let protection = Protection::WrappingKey(wrapping_key);
+        Ok(Self::new(local_storage()?, prefix, protection))
+    }
+
+    /// Uses another Web Storage, like `sessionStorage`, keeping the prefix
+    /// and the protection of the keys.
+    pub fn with_storage(self, storage: Storage) -> Self {
+        Self { storage, ..self }
+    }
+
+    fn new(storage: Storage, prefix: &str, protection: Protection) -> Self {
+        Self {
+            storage,
+            prefix: prefix.to_string(),
+            protection,
+        }
+    }
+
+    fn entry_name(&self, id: &str) -> String {
+        format!("{}{id}", self.prefix)
+    }
+
+    /// Wraps and stores a key, replacing any key with the same identifier.
+    ///
+    /// # Arguments
+    /// * `id` - Identifier of the key
+    /// * `key` - Key to store, must be extractable
+    ///
+    /// # Errors
+    /// - `LocalKeystoreError::Wrap` or `LocalKeystoreError::Password` if the
+    ///   key is not extractable
+    /// - `LocalKeystoreError::QuotaExceeded` if the browser ran out of storage
+    ///   space
+    pub async fn put<K: WrappableKey>(&self, id: &str, key: &K) -> Result<(), LocalKeystoreError> {
+        let wrapped = match &self.protection {
+            Protection::Passphrase {
+                passphrase,
+                iterations,
+            } => crate::password::wrap_key(passphrase, key, *iterations).await?,
+            Protection::WrappingKey(wrapping_key) => wrapping_key.wrap_key(key).await?,
+        };
+        let entry = format!(
+            "{VERSION}.{}.{}",
+            self.protection.scheme(),
+            base64_encode(&wrapped)
+        );
+        self.storage.set_item(&self.entry_name(id), &entry)?;
+        Ok(())
+    }
+
+    /// Loads and unwraps a key, as a non extractable key.
+    ///
+    /// # Arguments
+    /// * `id` - Identifier of the key
+    ///
+    /// # Returns
+    /// Result containing the key, `None` if no key has this identifier, or a
+    /// LocalKeystoreError
+    ///
+    /// # Errors
+    /// - `LocalKeystoreError::InvalidEntry` if the entry was not written by
+    ///   this module
+    /// - `LocalKeystoreError::UnsupportedVersion` if the entry has an unknown
+    ///   version
+    /// - `LocalKeystoreError::ProtectionMismatch` if the entry is protected
+    ///   with another scheme
+    /// - `LocalKeystoreError::Password` or `LocalKeystoreError::Unwrap` if the
+    ///   passphrase or the key encryption key is wrong, or if the stored key
+    ///   doesn't match the requested type
+    pub async fn get<K: WrappableKey>(&self, id: &str) -> Result<Option<K>, LocalKeystoreError> {
+        let Some(entry) = self.storage.get_item(&self.entry_name(id))? else {
+            return Ok(None);
+        };
+        let mut parts = entry.splitn(3, '.');
+        let (Some(version), Some(scheme), Some(wrapped)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(LocalKeystoreError::InvalidEntry);
+        };
+        let version = version
+            .parse::<u8>()
+            .map_err(|_| LocalKeystoreError::InvalidEntry)?;
+        if version != VERSION {
+            return Err(LocalKeystoreError::UnsupportedVersion(version));
+        }
+        if scheme != self.protection.scheme() {
+            return Err(LocalKeystoreError::ProtectionMismatch(scheme.to_string()));
+        }
+        let wrapped = base64_decode(wrapped).map_err(|_| LocalKeystoreError::InvalidEntry)?;
+        let key = match &self.protection {
+            Protection::Passphrase { passphrase, .. } => {
+                crate::password::unwrap_key(passphrase, &wrapped, false).await?
+            }
+            Protection::WrappingKey(wrapping_key) => wrapping_key.unwrap_key(&wrapped).await?,
+        };
+        Ok(Some(key))
+    }
+
+    /// Deletes a key, if it exists.
+    ///
+    /// # Arguments
+    /// * `id` - Identifier of the key
+    pub fn delete(&self, id: &str) -> Result<(), LocalKeystoreError> {
+        self.storage.remove_item(&self.entry_name(id))?;
+        Ok(())
+    }
+
+    /// Lists the identifiers of the stored keys, the storage entries starting
+    /// with the prefix.
+    pub fn ids(&self) -> Result<Vec<String>, LocalKeystoreError> {
+        let mut ids = Vec::new();
+        for index in 0..self.storage.length()? {
+            if let Some(id) = self
+                .storage
+                .key(index)?
+                .and_then(|name| name.strip_prefix(&self.prefix).map(String::from))
+            {
+                ids.push(id);
+            }
+        }
+        ids.sort();
+        Ok(ids)
+    }
+}
+
+/// The passphrase and the key encryption key are not displayed.
+impl std::fmt::Debug for LocalKeystore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalKeystore")
+            .field("prefix", &self.prefix)
+            .field("scheme", &self.protection.scheme())
+            .finish_non_exhaustive()
+    }
+}
+
+/// The passphrase is overwritten when the store is dropped.
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for LocalKeystore {}
+
+/// Checks if `localStorage` is available in the current environment
+pub fn is_supported() -> bool {
+    local_storage().is_ok()
+}
+
+fn local_storage() -> Result<Storage, LocalKeystoreError> {
+    crate::local_storage()?.ok_or(LocalKeystoreError::Unavailable)
+}
@@ -0,0 +1,91 @@
+//! Latency metrics of the Web Crypto operations
+//!
+//! The duration of the Web Crypto operations varies a lot between browsers and
+//! versions. An application can register a [`Metrics`] recorder, or a closure,
+//! with [`set_metrics`] to receive a [`Measurement`] after each Web Crypto
+//! operation, timed with `performance.now()`, and track the regressions.
+//!
+//! The recorder is registered for the current thread only: each worker
+//! registers its own.
+//!
+//! Requires the `metrics` feature.
+//!
+//! ```rust,no_run
+//! use browser_crypto::metrics::{self, Measurement};
+//!
+//! metrics::set_metrics(|measurement: &Measurement<'_>| {
+//!     web_sys::console::log_1(
+//!         &format!(
+//!             "{} {} took {}ms",
+//!             measurement.operation, measurement.algorithm, measurement.duration_ms
+//!         )
+//!         .into(),
+//!     );
+//! });
+//! ```
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::context::Operation;
+
+/// Duration and outcome of a Web Crypto operation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement<'a> {
+    /// Operation that was run
+    pub operation: Operation,
+    /// Name of the algorithm, like "AES-GCM"
+    pub algorithm: &'a str,
+    /// Size of the input in bytes, for the operations processing data
+    pub size: Option<usize>,
+    /// Duration of the operation in milliseconds, from `performance.now()`
+    pub duration_ms: f64,
+    /// Code of the error, see [`crate::ErrorCode`], if the operation failed
+    pub error_code: Option<&'static str>,
+}
+
+impl Measurement<'_> {
+    /// Whether the operation succeeded
+    pub fn is_success(&self) -> bool {
+        self.error_code.is_none()
+    }
+}
+
+/// Recorder of the measurements
+///
+/// Implemented for the closures taking a [`Measurement`].
+pub trait Metrics {
+    /// Records the measurement of an operation
+    fn record(&self, measurement: &Measurement<'_>);
+}
+
+impl<F: Fn(&Measurement<'_>)> Metrics for F {
+    fn record(&self, measurement: &Measurement<'_>) {
+        self(measurement)
+    }
+}
+
+thread_local! {
+    static METRICS: RefCell<Option<Rc<dyn Metrics>>> = const { RefCell::new(None) };
+}
+
+/// Registers the recorder of the current thread, replacing the previous one
+pub fn set_metrics(metrics: impl Metrics + 'static) {
+    METRICS.with(|current| *current.borrow_mut() = Some(Rc::new(metrics)));
+}
+
+/// Unregisters the recorder of the current thread
+pub fn clear_metrics() {
+    METRICS.with(|current| *current.borrow_mut() = None);
+}
+
+/// Hands a measurement to the registered recorder, if any
+///
+/// The recorder is cloned out of the cell first, so it can register another
+/// one while recording.
+pub(crate) fn record(measurement: &Measurement<'_>) {
+    let metrics = METRICS.with(|current| current.borrow().clone());
+    if let Some(metrics) = metrics {
+        metrics.record(measurement);
+    }
+}
@@ -0,0 +1,272 @@
+//! Injectable backend for the tests
+//!
+//! The error handling of an application is hard to test against the real Web
+//! Crypto API: the random values can't be predicted and failures like a
+//! `QuotaExceededError` can't be triggered on demand. A test can register a
+//! [`Backend`] with [`set_backend`] to take over the random values and to
+//! fail the Web Crypto operations with canned errors. [`MockBackend`]
+//! implements it with deterministic random values and a queue of failures.
+//!
+//! The failures are converted like the exceptions of the Web Crypto API, so a
+//! `DOMException` named `"QuotaExceededError"` becomes
+//! `NonceError::QuotaExceeded`, and they go through the instrumentation of
+//! the `tracing`, `metrics` and `audit` features. Outside of WebAssembly, where
+//! no `DOMException` can be created, they end up in the `Generic` variant of
+//! the errors.
+//!
+//! The random values don't require a JavaScript runtime, so the code drawing
+//! nonces, salts or raw keys can be tested natively, and the operations run
+//! with the software backend of the `native` feature can be failed as well.
+//!
+//! The backend is registered for the current thread only and must never be
+//! registered in production.
+//!
+//! Requires the `mock` feature.
+//!
+//! ```rust,no_run
+//! use browser_crypto::algorithm::NonceError;
+//! use browser_crypto::mock::{self, MockBackend};
+//!
+//! let backend = MockBackend::with_fixed_random(&[0x42]);
+//! backend.fail_next_random(browser_crypto::Error::DomException(
+//!     "QuotaExceededError".into(),
+//!     "quota exceeded".into(),
+//! ));
+//! mock::set_backend(backend);
+//! assert!(matches!(
+//!     browser_crypto::random::random_bytes(12),
+//!     Err(NonceError::QuotaExceeded)
+//! ));
+//! assert_eq!(
+//!     browser_crypto::random::random_bytes(2).unwrap(),
+//!     [0x42, 0x42]
+//! );
+//! mock::clear_backend();
+//! ```
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use wasm_bindgen::JsValue;
+
+use crate::context::Operation;
+
+/// Backend taking over the random values and the outcome of the Web Crypto
+/// operations
+///
+/// Both methods default to the real implementation.
+pub trait Backend {
+    /// Fills a buffer with "random" bytes
+    ///
+    /// # Returns
+    /// `None` to use the platform's random number generator, or the outcome
+    /// of the generation
+    fn fill_random(&self, buffer: &mut [u8]) -> Option<Result<(), crate::Error>> {
+        let _ = buffer;
+        None
+    }
+
+    /// Called before each Web Crypto operation
+    ///
+    /// # Arguments
+    /// * `operation` - Operation about to run
+    /// * `algorithm` - Name of the algorithm, like "AES-GCM"
+    ///
+    /// # Returns
+    /// `None` to run the operation, or the error it fails with instead
+    fn fail(&self, operation: Operation, algorithm: &str) -> Option<crate::Error> {
+        let _ = (operation, algorithm);
+        None
+    }
+}
+
+thread_local! {
+    static BACKEND: RefCell<Option<Rc<dyn Backend>>> = const { RefCell::new(None) };
+}
+
+/// Registers the backend of the current thread, replacing the previous one
+pub fn set_backend(backend: impl Backend + 'static) {
+    BACKEND.with(|current| *current.borrow_mut() = Some(Rc::new(backend)));
+}
+
+/// Unregisters the backend of the current thread
+pub fn clear_backend() {
+    BACKEND.with(|current| *current.borrow_mut() = None);
+}
+
+fn current() -> Option<Rc<dyn Backend>> {
+    BACKEND.with(|current| current.borrow().clone())
+}
+
+/// Fills a buffer with the registered backend, if it takes over the random
+/// values
+pub(crate) fn fill_random<E>(buffer: &mut [u8]) -> Option<Result<(), E>>
+where
+    E: From<JsValue> + From<crate::Error>,
+{
+    let result = current()?.fill_random(buffer)?;
+    Some(result.map_err(into_error))
+}
+
+/// Error the registered backend fails an operation with, if any
+pub(crate) fn failure<E>(operation: Operation, algorithm: &str) -> Option<E>
+where
+    E: From<JsValue> + From<crate::Error>,
+{
+    current()?.fail(operation, algorithm).map(into_error)
+}
+
+/// Converts a canned error like an exception of the Web Crypto API, going
+/// through a real `DOMException` when possible
+fn into_error<E>(error: crate::Error) -> E
+where
+    E: From<JsValue> + From<crate::Error>,
+{
+    #[cfg(target_arch = "wasm32")]
+    if let crate::Error::DomException(name, message) = &error {
+        if let Ok(exception) = web_sys::DomException::new_with_message_and_name(message, name) {
+            return E::from(JsValue::from(exception));
+        }
+    }
+    E::from(error)
+}
+
+/// Source of the random values of a [`MockBackend`]
+#[derive(Debug)]
+enum Random {
+    /// Repeats the same bytes
+    Fixed(Vec<u8>),
+    /// SplitMix64 sequence, reproducible from its seed
+    Seeded(u64),
+}
+
+impl Random {
+    fn fill(&mut self, buffer: &mut [u8]) {
+        match self {
+            Self::Fixed(bytes) => {
+                for (target, source) in buffer.iter_mut().zip(bytes.iter().cycle()) {
+                    *target = *source;
+                }
+            }
+            Self::Seeded(state) => {
+                for chunk in buffer.chunks_mut(8) {
+                    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+                    let mut value = *state;
+                    value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+                    value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+                    value ^= value >> 31;
+                    chunk.copy_from_slice(&value.to_le_bytes()[..chunk.len()]);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    random: Option<Random>,
+    random_failures: VecDeque<crate::Error>,
+    failures: VecDeque<(Option<Operation>, crate::Error)>,
+}
+
+/// Deterministic backend, with a queue of canned failures
+///
+/// The clones share their state, so a test can keep one to queue failures
+/// after registering another with [`set_backend`].
+#[derive(Debug, Clone, Default)]
+pub struct MockBackend {
+    state: Rc<RefCell<State>>,
+}
+
+impl MockBackend {
+    /// Creates a backend keeping the platform's random values and running
+    /// every operation
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a backend filling every random buffer by repeating the same
+    /// bytes, so every nonce is the same
+    ///
+    /// # Panics
+    /// If `bytes` is empty
+    pub fn with_fixed_random(bytes: &[u8]) -> Self {
+        assert!(!bytes.is_empty(), "the random bytes cannot be empty");
+        Self::with_random(Random::Fixed(bytes.to_vec()))
+    }
+
+    /// Creates a backend drawing distinct random values from a sequence
+    /// seeded with `seed`, the same for every run
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_random(Random::Seeded(seed))
+    }
+
+    fn with_random(random: Random) -> Self {
+        let backend = Self::default();
+        backend.state.borrow_mut().random = Some(random);
+        backend
+    }
+
+    /// Fails the next generation of random values
+    ///
+    /// # Arguments
+    /// * `error` - Error thrown by the Web Crypto API, like
+    ///   `Error::DomException("QuotaExceededError".into(), "...".into())`
+    pub fn fail_next_random(&self, error: crate::Error) -> &Self {
+        self.state.borrow_mut().random_failures.push_back(error);
+        self
+    }
+
+    /// Fails the next run of an operation, whatever its algorithm
+    ///
+    /// The failures of the same operation are used in the order they were
+    /// queued.
+    ///
+    /// # Arguments
+    /// * `operation` - Operation to fail
+    /// * `error` - Error thrown by the Web Crypto API, like
+    ///   `Error::DomException("OperationError".into(), "...".into())`
+    pub fn fail_next(&self, operation: Operation, error: crate::Error) -> &Self {
+        self.state
+            .borrow_mut()
+            .failures
+            .push_back((Some(operation), error));
+        self
+    }
+
+    /// Fails the next operation, whatever it is
+    ///
+    /// # Arguments
+    /// * `error` - Error thrown by the Web Crypto API
+    pub fn fail_next_any(&self, error: crate::Error) -> &Self {
+        self.state.borrow_mut().failures.push_back((None, error));
+        self
+    }
+
+    /// Number of failures queued and not used yet, random ones included
+    pub fn pending_failures(&self) -> usize {
+        let state = self.state.borrow();
+        state.failures.len() + state.random_failures.len()
+    }
+}
+
+impl Backend for MockBackend {
+    fn fill_random(&self, buffer: &mut [u8]) -> Option<Result<(), crate::Error>> {
+        let mut state = self.state.borrow_mut();
+        if let Some(error) = state.random_failures.pop_front() {
+            return Some(Err(error));
+        }
+        state.random.as_mut()?.fill(buffer);
+        Some(Ok(()))
+    }
+
+    fn fail(&self, operation: Operation, _algorithm: &str) -> Option<crate::Error> {
+        let mut state = self.state.borrow_mut();
+        let index = state
+            .failures
+            .iter()
+            .position(|(expected, _)| expected.is_none_or(|expected| expected == operation))?;
+        state.failures.remove(index).map(|(_, error)| error)
+    }
+}
@@ -0,0 +1,138 @@
+//! Software backend of the native targets
+//!
+//! With the `native` feature enabled, outside of WebAssembly, the ciphers
+//! supporting it hold their key in memory and run the RustCrypto
+//! implementations instead of the Web Crypto API, so the code sharing them
+//! with a browser application can be unit tested or run on a server. The
+//! random values come from the random number generator of the operating
+//! system.
+//!
+//! Only [`crate::aes_gcm::AesGcm`], with 96-bit nonces, and the digests of
+//! [`crate::hash::Hash`] are supported. The methods relying on a `CryptoKey`,
+//! like the JSON Web Key import or the key derivation, are still available but
+//! fail with `Error::Unsupported`.
+
+use aes_gcm::aead::consts::U12;
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+
+use crate::algorithm::{DecryptionError, EncryptionError};
+use crate::hash::Hash;
+use crate::key::{GenerateKeyError, ImportKeyError};
+
+/// AES-GCM key, for one of the supported key sizes
+#[derive(Clone)]
+pub(crate) enum AesGcmKey {
+    Aes128(Box<aes_gcm::AesGcm<aes_gcm::aes::Aes128, U12>>),
+    Aes192(Box<aes_gcm::AesGcm<aes_gcm::aes::Aes192, U12>>),
+    Aes256(Box<aes_gcm::AesGcm<aes_gcm::aes::Aes256, U12>>),
+}
+
+/// The key material is never displayed.
+impl std::fmt::Debug for AesGcmKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("AesGcmKey")
+    }
+}
+
+impl AesGcmKey {
+    /// Creates a key from its raw bytes
+    ///
+    /// # Errors
+    /// - `ImportKeyError::InvalidKeyFormat` if the key size doesn't match
+    ///   `key_bits`
+    pub(crate) fn new(data: &[u8], key_bits: usize) -> Result<Self, ImportKeyError> {
+        if data.len() * 8 != key_bits {
+            return Err(ImportKeyError::InvalidKeyFormat);
+        }
+        let invalid = |_| ImportKeyError::InvalidKeyFormat;
+        Ok(match key_bits {
+            128 => Self::Aes128(Box::new(KeyInit::new_from_slice(data).map_err(invalid)?)),
+            192 => Self::Aes192(Box::new(KeyInit::new_from_slice(data).map_err(invalid)?)),
+            256 => Self::Aes256(Box::new(KeyInit::new_from_slice(data).map_err(invalid)?)),
+            _ => return Err(ImportKeyError::InvalidKeyFormat),
+        })
+    }
+
+    /// Generates a random key
+    ///
+    /// # Errors
+    /// - `GenerateKeyError::NotSupported` if the key size is not supported
+    /// - `GenerateKeyError::Generic` if the random number generator is not
+    ///   available
+    pub(crate) fn generate(key_bits: usize) -> Result<Self, GenerateKeyError> {
+        let mut data = crate::SecretBytes::from(vec![0; key_bits / 8]);
+        fill_random(&mut data)?;
+        Self::new(&data, key_bits).map_err(|_| GenerateKeyError::NotSupported)
+    }
+
+    /// Encrypts data, authenticating some additional data
+    ///
+    /// # Errors
+    /// - `EncryptionError::Operation` if the nonce is not 96 bits long
+    pub(crate) fn encrypt(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        payload: &[u8],
+    ) -> Result<Vec<u8>, EncryptionError> {
+        if nonce.len() != 12 {
+            return Err(EncryptionError::Operation);
+        }
+        let nonce = aes_gcm::Nonce::from_slice(nonce);
+        let payload = Payload { msg: payload, aad };
+        match self {
+            Self::Aes128(cipher) => cipher.encrypt(nonce, payload),
+            Self::Aes192(cipher) => cipher.encrypt(nonce, payload),
+            Self::Aes256(cipher) => cipher.encrypt(nonce, payload),
+        }
+        .map_err(|_| EncryptionError::Operation)
+    }
+
+    /// Decrypts data, checking the additional data
+    ///
+    /// # Errors
+    /// - `DecryptionError::Operation` if the nonce is not 96 bits long or if
+    ///   the data or the additional data have been altered
+    pub(crate) fn decrypt(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        payload: &[u8],
+    ) -> Result<Vec<u8>, DecryptionError> {
+        if nonce.len() != 12 {
+            return Err(DecryptionError::Operation);
+        }
+        let nonce = aes_gcm::Nonce::from_slice(nonce);
+        let payload = Payload { msg: payload, aad };
+        match self {
+            Self::Aes128(cipher) => cipher.decrypt(nonce, payload),
+            Self::Aes192(cipher) => cipher.decrypt(nonce, payload),
+            Self::Aes256(cipher) => cipher.decrypt(nonce, payload),
+        }
+        .map_err(|_| DecryptionError::Operation)
+    }
+}
+
+/// Computes the digest of some data
+pub(crate) fn digest(hash: Hash, data: &[u8]) -> Vec<u8> {
+    use sha2::Digest;
+
+    match hash {
+        Hash::Sha1 => sha1::Sha1::digest(data).to_vec(),
+        Hash::Sha256 => sha2::Sha256::digest(data).to_vec(),
+        Hash::Sha384 => sha2::Sha384::digest(data).to_vec(),
+        Hash::Sha512 => sha2::Sha512::digest(data).to_vec(),
+    }
+}
+
+/// Fills a buffer with random bytes from the operating system
+///
+/// # Errors
+/// - `Error::Thrown` if the random number generator is not available
+pub(crate) fn fill_random(buffer: &mut [u8]) -> Result<(), crate::Error> {
+    use aes_gcm::aead::rand_core::RngCore;
+
+    aes_gcm::aead::OsRng
+        .try_fill_bytes(buffer)
+        .map_err(|err| crate::Error::Thrown(err.to_string()))
+}
@@ -0,0 +1,171 @@
+//! Deterministic nonces derived from message identifiers
+//!
+//! Systems already assigning a unique identifier to each message, like a
+//! database row ID or a sequence number, can derive the nonces from those
+//! identifiers with [`Nonce::derive`] instead of storing them next to the
+//! ciphertexts. The nonces are derived with HKDF-SHA-256 from a secret
+//! dedicated to the derivation, the domain of the [`NonceContext`] and the
+//! [`MessageId`].
+//!
+//! The derived nonces are only unique if:
+//! - a message identifier is never used twice within the same context,
+//!   including after a message has been modified
+//! - each encryption key has its own context, either with its own secret or
+//!   with its own domain
+//!
+//! The domain is a `&'static str`, so it's fixed when writing the
+//! application, like `"my-app/messages/v1"`, and can't be built from user
+//! input.
+//!
+//! ```rust,no_run
+//! use browser_crypto::aes_gcm::Aes256Gcm;
+//! use browser_crypto::algorithm::{Algorithm, Nonce};
+//! use browser_crypto::nonce_derivation::NonceContext;
+//!
+//! async fn encrypt(
+//!     cipher: &Aes256Gcm,
+//!     secret: &[u8],
+//!     row_id: u64,
+//!     message: &[u8],
+//! ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+//!     let context = NonceContext::new(secret, "my-app/messages/v1").await?;
+//!     let nonce = Nonce::<Aes256Gcm>::derive(&context, row_id).await?;
+//!     Ok(cipher.encrypt(&nonce, message).await?)
+//! }
+//! ```
+
+use crate::algorithm::Nonce;
+use crate::derive::DeriveError;
+use crate::hash::Hash;
+use crate::hkdf::Hkdf;
+use crate::input::SecretInput;
+use crate::key::ImportKeyError;
+use crate::nonce_sequence::SequentialNonce;
+
+/// Label prepended to the HKDF info, separating the nonce derivation from the
+/// other uses of the same secret
+const LABEL: &[u8] = b"browser-crypto/nonce/v1";
+
+/// Context of the nonce derivation, for a single encryption key
+///
+/// The secret must be dedicated to the nonce derivation and never be used as
+/// an encryption key. Two keys sharing a secret need distinct domains.
+#[derive(Debug, Clone)]
+pub struct NonceContext {
+    hkdf: Hkdf,
+    domain: &'static str,
+}
+
+impl NonceContext {
+    /// Creates a context from a secret dedicated to the nonce derivation
+    ///
+    /// The secret is imported as a non extractable key.
+    ///
+    /// # Arguments
+    /// * `secret` - Secret with a high entropy, like 32 random bytes
+    /// * `domain` - Constant label of the context, distinct for each key
+    ///
+    /// # Returns
+    /// Result containing the NonceContext or an ImportKeyError
+    pub async fn new(
+        secret: &(impl SecretInput + ?Sized),
+        domain: &'static str,
+    ) -> Result<Self, ImportKeyError> {
+        let hkdf = Hkdf::from_key(secret, Hash::Sha256, &[], &[]).await?;
+        Ok(Self { hkdf, domain })
+    }
+
+    /// Creates a context from an existing HKDF input key, keeping its hash
+    /// function and its salt
+    ///
+    /// The info of the HKDF instance is replaced by the one of the derivation.
+    ///
+    /// # Arguments
+    /// * `hkdf` - HKDF instance of the input key
+    /// * `domain` - Constant label of the context, distinct for each key
+    pub fn from_hkdf(hkdf: &Hkdf, domain: &'static str) -> Self {
+        Self {
+            hkdf: hkdf.with_info(&[]),
+            domain,
+        }
+    }
+
+    /// Label of the context
+    pub fn domain(&self) -> &'static str {
+        self.domain
+    }
+
+    /// Builds the HKDF info of a nonce
+    ///
+    /// The domain is prefixed with its length, so a domain can't be extended
+    /// with the beginning of a message identifier.
+    fn info(&self, nonce_size: u32, message_id: &MessageId) -> Vec<u8> {
+        let mut info = Vec::with_capacity(LABEL.len() + 8 + self.domain.len() + message_id.0.len());
+        info.extend_from_slice(LABEL);
+        info.extend_from_slice(&nonce_size.to_be_bytes());
+        info.extend_from_slice(&(self.domain.len() as u32).to_be_bytes());
+        info.extend_from_slice(self.domain.as_bytes());
+        info.extend_from_slice(&message_id.0);
+        info
+    }
+}
+
+/// Identifier of a message, which must never be reused within a
+/// [`NonceContext`]
+///
+/// Integers are encoded as 8 big endian bytes. Identifiers of different types
+/// must not be mixed in the same context, as they could have the same bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageId(Vec<u8>);
+
+impl MessageId {
+    /// Bytes of the identifier
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<u64> for MessageId {
+    fn from(value: u64) -> Self {
+        Self(value.to_be_bytes().to_vec())
+    }
+}
+
+impl From<&[u8]> for MessageId {
+    fn from(value: &[u8]) -> Self {
+        Self(value.to_vec())
+    }
+}
+
+impl From<&str> for MessageId {
+    fn from(value: &str) -> Self {
+        Self(value.as_bytes().to_vec())
+    }
+}
+
+impl<A: SequentialNonce> Nonce<A> {
+    /// Derives the nonce of a message from its identifier
+    ///
+    /// The same context and identifier always produce the same nonce, see the
+    /// [module documentation](crate::nonce_derivation) for the conditions of
+    /// uniqueness.
+    ///
+    /// # Arguments
+    /// * `context` - Context of the encryption key
+    /// * `message_id` - Unique identifier of the message
+    ///
+    /// # Returns
+    /// Result containing the Nonce or a DeriveError
+    pub async fn derive(
+        context: &NonceContext,
+        message_id: impl Into<MessageId>,
+    ) -> Result<Self, DeriveError> {
+        let info = context.info(A::NONCE_SIZE, &message_id.into());
+        let bytes = context
+            .hkdf
+            .with_info(&info)
+            .derive_bits(A::NONCE_SIZE * 8)
+            .await?;
+        Ok(Self::from_vec(bytes))
+    }
+}
@@ -0,0 +1,304 @@
+//! Counter-based nonces surviving page reloads
+//!
+//! A [`NonceSequence`] only guarantees unique nonces while it lives, a page
+//! reload or a crash starts it over and reuses its nonces, which breaks the
+//! confidentiality and the authenticity of AES-GCM. [`NonceManager`]
+//! checkpoints the counter of the sequence in IndexedDB or in `localStorage`
+//! before handing out the nonces, reserving a range of counter values with each
+//! write, so a reloaded manager resumes after the last reserved value. The
+//! values reserved but not used before a reload are skipped.
+//!
+//! A manager must be the only one producing nonces for its key: two tabs
+//! opening the same identifier at the same time would reserve the same values.
+//!
+//! Each checkpoint is stored as the format version, the size of the counter,
+//! the base64 encoded prefix and the first value not reserved yet, separated by
+//! dots.
+//!
+//! Requires the `keystore` feature.
+//!
+//! ```rust,no_run
+//! use browser_crypto::aes_gcm::Aes256Gcm;
+//! use browser_crypto::algorithm::Algorithm;
+//! use browser_crypto::keystore::Keystore;
+//! use browser_crypto::nonce_manager::{NonceManager, NonceStorage};
+//! use browser_crypto::nonce_sequence::CounterSize;
+//!
+//! async fn encrypt(cipher: &Aes256Gcm, message: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+//!     let keystore = Keystore::open("my-app").await?;
+//!     let storage = NonceStorage::IndexedDb(keystore);
+//!     let mut nonces = NonceManager::<Aes256Gcm>::open(storage, "main", CounterSize::U32).await?;
+//!     let nonce = nonces.next_nonce().await?;
+//!     let _encrypted = cipher.encrypt(&nonce, message).await?;
+//!     Ok(())
+//! }
+//! ```
+
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{DomException, Storage};
+
+use crate::algorithm::{Nonce, NonceError};
+use crate::encoding::{base64_decode, base64_encode};
+use crate::keystore::{Keystore, KeystoreError, NONCES_STORE_NAME};
+use crate::nonce_sequence::{CounterSize, NonceSequence, SequentialNonce};
+use crate::ErrorCode;
+
+/// Version of the checkpoint format
+pub const VERSION: u8 = 1;
+
+/// Number of counter values reserved with each checkpoint, by default
+pub const DEFAULT_RESERVATION: u64 = 1024;
+
+/// Errors that can occur when producing nonces with a [`NonceManager`].
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum NonceManagerError {
+    /// Indicates that `localStorage` is not available, like in workers or when
+    /// the user disabled it.
+    #[error("localStorage is not available")]
+    Unavailable,
+    /// Indicates that the browser ran out of storage space.
+    #[error("the storage quota has been exceeded")]
+    QuotaExceeded,
+    /// Indicates that the stored checkpoint was not written by this module.
+    #[error("invalid stored checkpoint")]
+    InvalidEntry,
+    /// Indicates that the stored checkpoint uses another counter size.
+    ///
+    /// # Fields
+    /// * `expected` - The counter size requested when opening the manager
+    /// * `stored` - The counter size of the stored checkpoint
+    #[error("expected a {expected:?} counter, the checkpoint has a {stored:?} counter")]
+    CounterSizeMismatch {
+        expected: CounterSize,
+        stored: CounterSize,
+    },
+    /// Indicates that the nonces couldn't be produced, like when the sequence
+    /// is exhausted.
+    #[error(transparent)]
+    Nonce(#[from] NonceError),
+    /// Indicates that the checkpoint couldn't be read or written in IndexedDB.
+    #[error(transparent)]
+    Keystore(#[from] KeystoreError),
+    /// A wrapper for other types of errors that may occur while accessing the
+    /// storage.
+    #[error(transparent)]
+    Generic(#[from] crate::Error),
+}
+
+impl ErrorCode for NonceManagerError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Unavailable => "nonce_manager.unavailable",
+            Self::QuotaExceeded => "nonce_manager.quota_exceeded",
+            Self::InvalidEntry => "nonce_manager.invalid_entry",
+            Self::CounterSizeMismatch { .. } => "nonce_manager.counter_size_mismatch",
+            Self::Nonce(inner) => inner.code(),
+            Self::Keystore(inner) => inner.code(),
+            Self::Generic(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<NonceManagerError> for JsValue {
+    fn from(value: NonceManagerError) -> Self {
+        crate::js_error("NonceManagerError", &value)
+    }
+}
+
+impl From<JsValue> for NonceManagerError {
+    fn from(value: JsValue) -> Self {
+        if let Some(exception) = value.dyn_ref::<DomException>() {
+            if exception.name() == "QuotaExceededError" {
+                return Self::QuotaExceeded;
+            }
+        }
+        Self::Generic(crate::Error::from(value))
+    }
+}
+
+/// Storage of the checkpoints
+#[derive(Debug, Clone)]
+pub enum NonceStorage {
+    /// The `nonces` object store of an IndexedDB [`Keystore`]
+    IndexedDb(Keystore),
+    /// A Web Storage, like `localStorage`, the identifiers of the managers
+    /// being used as entry names
+    Web(Storage),
+}
+
+impl NonceStorage {
+    /// Uses the `localStorage` of the origin
+    ///
+    /// # Errors
+    /// - `NonceManagerError::Unavailable` if `localStorage` is not available
+    pub fn local_storage() -> Result<Self, NonceManagerError> {
+        crate::local_storage()?
+            .map(Self::Web)
+            .ok_or(NonceManagerError::Unavailable)
+    }
+
+    async fn load(&self, id: &str) -> Result<Option<String>, NonceManagerError> {
+        match self {
+            Self::IndexedDb(keystore) => {
+                Ok(keystore.get_value(NONCES_STORE_NAME, id).await?.as_string())
+            }
+            Self::Web(storage) => Ok(storage.get_item(id)?),
+        }
+    }
+
+    async fn save(&self, id: &str, entry: &str) -> Result<(), NonceManagerError> {
+        match self {
+            Self::IndexedDb(keystore) => Ok(keystore
+                .put_value(NONCES_STORE_NAME, id, &entry.into())
+                .await?),
+            Self::Web(storage) => Ok(storage.set_item(id, entry)?),
+        }
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), NonceManagerError> {
+        match self {
+            Self::IndexedDb(keystore) => Ok(keystore.delete_value(NONCES_STORE_NAME, id).await?),
+            Self::Web(storage) => Ok(storage.remove_item(id)?),
+        }
+    }
+}
+
+/// Sequence of unique nonces, checkpointed to a storage
+#[derive(Debug)]
+pub struct NonceManager<A> {
+    storage: NonceStorage,
+    id: String,
+    sequence: NonceSequence<A>,
+    /// First counter value not covered by the stored checkpoint
+    reserved: u128,
+    reservation: u64,
+}
+
+impl<A: SequentialNonce> NonceManager<A> {
+    /// Resumes the sequence stored under an identifier, or starts a new one
+    /// with a random prefix.
+    ///
+    /// # Arguments
+    /// * `storage` - Storage of the checkpoints
+    /// * `id` - Identifier of the sequence, one for each key
+    /// * `counter_size` - Size of the counter
+    ///
+    /// # Returns
+    /// Result containing the NonceManager or a NonceManagerError
+    ///
+    /// # Errors
+    /// - `NonceManagerError::InvalidEntry` if the checkpoint was not written by
+    ///   this module
+    /// - `NonceManagerError::CounterSizeMismatch` if the checkpoint uses
+    ///   another counter size
+    /// - `NonceManagerError::Nonce` if the stored sequence is exhausted
+    pub async fn open(
+        storage: NonceStorage,
+        id: &str,
+        counter_size: CounterSize,
+    ) -> Result<Self, NonceManagerError> {
+        let (sequence, reserved) = match storage.load(id).await? {
+            Some(entry) => Self::parse(&entry, counter_size)?,
+            None => (NonceSequence::new(counter_size)?, 0),
+        };
+        Ok(Self {
+            storage,
+            id: id.to_string(),
+            sequence,
+            reserved,
+            reservation: DEFAULT_RESERVATION,
+        })
+    }
+
+    fn parse(
+        entry: &str,
+        counter_size: CounterSize,
+    ) -> Result<(NonceSequence<A>, u128), NonceManagerError> {
+        let mut parts = entry.splitn(4, '.');
+        let (Some(version), Some(size), Some(prefix), Some(reserved)) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(NonceManagerError::InvalidEntry);
+        };
+        if version != VERSION.to_string() {
+            return Err(NonceManagerError::InvalidEntry);
+        }
+        let stored = match size {
+            "4" => CounterSize::U32,
+            "8" => CounterSize::U64,
+            _ => return Err(NonceManagerError::InvalidEntry),
+        };
+        if stored != counter_size {
+            return Err(NonceManagerError::CounterSizeMismatch {
+                expected: counter_size,
+                stored,
+            });
+        }
+        let prefix = base64_decode(prefix).map_err(|_| NonceManagerError::InvalidEntry)?;
+        let reserved = reserved
+            .parse::<u128>()
+            .map_err(|_| NonceManagerError::InvalidEntry)?;
+        let position = u64::try_from(reserved)
+            .ok()
+            .filter(|position| *position <= counter_size.max())
+            .ok_or(NonceError::Exhausted)?;
+        let sequence = NonceSequence::from_parts(&prefix, position, counter_size)
+            .map_err(|_| NonceManagerError::InvalidEntry)?;
+        Ok((sequence, reserved))
+    }
+
+    /// Sets the number of counter values reserved with each checkpoint,
+    /// [`DEFAULT_RESERVATION`] by default.
+    ///
+    /// A larger reservation writes less often, but skips more values after a
+    /// reload. A reservation of 0 is considered as 1.
+    pub fn with_reservation(mut self, reservation: u64) -> Self {
+        self.reservation = reservation.max(1);
+        self
+    }
+
+    /// Identifier of the sequence
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Next value of the counter, or `None` if the sequence is exhausted
+    pub fn position(&self) -> Option<u64> {
+        self.sequence.position()
+    }
+
+    /// Produces the next nonce, writing a checkpoint first when the reserved
+    /// values are all used.
+    ///
+    /// # Returns
+    /// Result containing the Nonce or a NonceManagerError
+    ///
+    /// # Errors
+    /// - `NonceManagerError::Nonce` if the sequence is exhausted
+    /// - `NonceManagerError::QuotaExceeded` or `NonceManagerError::Keystore` if
+    ///   the checkpoint couldn't be written, no nonce being produced
+    pub async fn next_nonce(&mut self) -> Result<Nonce<A>, NonceManagerError> {
+        let position = self.sequence.position().ok_or(NonceError::Exhausted)? as u128;
+        if position >= self.reserved {
+            let end = self.sequence.counter_size().max() as u128 + 1;
+            let reserved = (position + self.reservation as u128).min(end);
+            let entry = format!(
+                "{VERSION}.{}.{}.{reserved}",
+                self.sequence.counter_size().bytes(),
+                base64_encode(self.sequence.prefix())
+            );
+            self.storage.save(&self.id, &entry).await?;
+            self.reserved = reserved;
+        }
+        Ok(self.sequence.next_nonce()?)
+    }
+
+    /// Deletes the stored checkpoint, once the key has been replaced.
+    ///
+    /// The nonces of a deleted sequence can be produced again, it must not be
+    /// done while the key is still in use.
+    pub async fn delete(self) -> Result<(), NonceManagerError> {
+        self.storage.delete(&self.id).await
+    }
+}
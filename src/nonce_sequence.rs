@@ -0,0 +1,182 @@
+//! Counter-based nonces
+//!
+//! Random 96-bit nonces can be safely used for about 2^32 messages under the
+//! same key. [`NonceSequence`] produces nonces made of a random prefix followed
+//! by a big endian counter, so they never repeat for the lifetime of the
+//! sequence, and refuses to wrap around once the counter is exhausted.
+//!
+//! The sequence only guarantees uniqueness while it lives: restarting from the
+//! same prefix and counter reuses the nonces. Only one sequence should exist
+//! per key.
+//!
+//! ```rust,no_run
+//! use browser_crypto::aes_gcm::Aes256Gcm;
+//! use browser_crypto::algorithm::Algorithm;
+//! use browser_crypto::nonce_sequence::{CounterSize, NonceSequence};
+//!
+//! async fn encrypt(messages: &[&[u8]]) -> Result<(), Box<dyn std::error::Error>> {
+//!     let cipher = Aes256Gcm::generate(false).await?;
+//!     let mut nonces = NonceSequence::<Aes256Gcm>::new(CounterSize::U32)?;
+//!     for message in messages {
+//!         let nonce = nonces.next_nonce()?;
+//!         let _encrypted = cipher.encrypt(&nonce, message).await?;
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use std::marker::PhantomData;
+
+use crate::algorithm::{Algorithm, Nonce, NonceError};
+
+/// Marker trait for algorithms accepting counter-based nonces
+///
+/// A nonce only has to be unique for those algorithms, unlike AES-CBC, which
+/// requires unpredictable initialization vectors, or AES-CTR, which increments
+/// the counter block itself.
+pub trait SequentialNonce: Algorithm {}
+
+/// Size of the counter of a [`NonceSequence`], the rest of the nonce being the
+/// random prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CounterSize {
+    /// 32-bit counter, with a 64-bit prefix for 96-bit nonces
+    U32,
+    /// 64-bit counter, with a 32-bit prefix for 96-bit nonces
+    U64,
+}
+
+impl CounterSize {
+    /// Size of the counter in bytes
+    pub fn bytes(&self) -> usize {
+        match self {
+            Self::U32 => 4,
+            Self::U64 => 8,
+        }
+    }
+
+    /// Last value of the counter
+    pub fn max(&self) -> u64 {
+        match self {
+            Self::U32 => u32::MAX as u64,
+            Self::U64 => u64::MAX,
+        }
+    }
+}
+
+/// Sequence of unique nonces, made of a random prefix and a counter
+///
+/// Not `Clone`, a copy would produce the same nonces again. A sequence is
+/// resumed with [`NonceSequence::from_parts`] instead.
+#[derive(Debug)]
+pub struct NonceSequence<A> {
+    algo: PhantomData<fn() -> A>,
+    prefix: Vec<u8>,
+    counter_size: CounterSize,
+    /// Next value of the counter, `None` once exhausted
+    counter: Option<u64>,
+}
+
+impl<A: SequentialNonce> NonceSequence<A> {
+    /// Size of the prefix for a counter size
+    fn prefix_size(counter_size: CounterSize) -> Result<usize, NonceError> {
+        (A::NONCE_SIZE as usize)
+            .checked_sub(counter_size.bytes())
+            .ok_or(NonceError::InvalidSize {
+                expected: counter_size.bytes() as u32,
+                received: A::NONCE_SIZE,
+            })
+    }
+
+    /// Starts a sequence with a random prefix
+    ///
+    /// # Arguments
+    /// * `counter_size` - Size of the counter
+    ///
+    /// # Returns
+    /// Result containing the NonceSequence or a NonceError
+    ///
+    /// # Errors
+    /// - `NonceError::InvalidSize` if the nonces are shorter than the counter
+    pub fn new(counter_size: CounterSize) -> Result<Self, NonceError> {
+        let prefix = crate::random::random_bytes(Self::prefix_size(counter_size)?)?;
+        Ok(Self {
+            algo: PhantomData,
+            prefix,
+            counter_size,
+            counter: Some(0),
+        })
+    }
+
+    /// Resumes a sequence from its prefix and the next value of its counter
+    ///
+    /// The counter must be past any value already used with this prefix.
+    ///
+    /// # Arguments
+    /// * `prefix` - Prefix of the sequence, see [`NonceSequence::prefix`]
+    /// * `counter` - Next value of the counter, see [`NonceSequence::position`]
+    /// * `counter_size` - Size of the counter
+    ///
+    /// # Returns
+    /// Result containing the NonceSequence or a NonceError
+    ///
+    /// # Errors
+    /// - `NonceError::InvalidSize` if the prefix doesn't fill the nonce with
+    ///   the counter
+    /// - `NonceError::Exhausted` if the counter exceeds its size
+    pub fn from_parts(
+        prefix: &[u8],
+        counter: u64,
+        counter_size: CounterSize,
+    ) -> Result<Self, NonceError> {
+        let prefix_size = Self::prefix_size(counter_size)?;
+        if prefix.len() != prefix_size {
+            return Err(NonceError::InvalidSize {
+                expected: prefix_size as u32,
+                received: prefix.len() as u32,
+            });
+        }
+        if counter > counter_size.max() {
+            return Err(NonceError::Exhausted);
+        }
+        Ok(Self {
+            algo: PhantomData,
+            prefix: prefix.to_vec(),
+            counter_size,
+            counter: Some(counter),
+        })
+    }
+
+    /// Random prefix shared by the nonces of the sequence
+    pub fn prefix(&self) -> &[u8] {
+        &self.prefix
+    }
+
+    /// Size of the counter
+    pub fn counter_size(&self) -> CounterSize {
+        self.counter_size
+    }
+
+    /// Next value of the counter, or `None` if the sequence is exhausted
+    pub fn position(&self) -> Option<u64> {
+        self.counter
+    }
+
+    /// Produces the next nonce of the sequence
+    ///
+    /// # Returns
+    /// Result containing the Nonce or a NonceError
+    ///
+    /// # Errors
+    /// - `NonceError::Exhausted` if the counter has reached its last value
+    pub fn next_nonce(&mut self) -> Result<Nonce<A>, NonceError> {
+        let counter = self.counter.ok_or(NonceError::Exhausted)?;
+        let mut bytes = Vec::with_capacity(A::NONCE_SIZE as usize);
+        bytes.extend_from_slice(&self.prefix);
+        bytes.extend_from_slice(&counter.to_be_bytes()[8 - self.counter_size.bytes()..]);
+        self.counter = counter
+            .checked_add(1)
+            .filter(|next| *next <= self.counter_size.max());
+        Nonce::from_slice(&bytes)
+    }
+}
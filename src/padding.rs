@@ -0,0 +1,191 @@
+//! Length-hiding padding
+//!
+//! Authenticated encryption reveals the size of the payload, which can be
+//! enough to guess a short message. Padding the payload before encrypting it
+//! hides its exact size:
+//! - [`Padding::Padme`] pads to the Padmé length, with an overhead of at most
+//!   12%, only leaking `O(log log n)` bits of the size
+//! - [`Padding::Block`] pads to a multiple of a block size
+//!
+//! The payload is padded with a `0x80` byte followed by zeros (ISO/IEC 7816-4),
+//! so the padding can be stripped without knowing the original size.
+//!
+//! [`Padded`] wraps a cipher to pad the payloads before encrypting them and
+//! strip the padding after decrypting them.
+//!
+//! ```rust,no_run
+//! use browser_crypto::aes_gcm::Aes256Gcm;
+//! use browser_crypto::algorithm::Algorithm;
+//! use browser_crypto::padding::{Padded, Padding};
+//!
+//! async fn encrypt() -> Result<(), Box<dyn std::error::Error>> {
+//!     let cipher = Padded::new(Aes256Gcm::from_key(&[0u8; 32]).await?, Padding::Padme);
+//!     let encrypted = cipher.seal(b"Hello World!").await?;
+//!     assert_eq!(cipher.open(&encrypted).await?, b"Hello World!");
+//!     Ok(())
+//! }
+//! ```
+
+use wasm_bindgen::JsValue;
+
+use crate::algorithm::{Algorithm, DecryptionError, Nonce};
+use crate::ErrorCode;
+
+/// Marker of the beginning of the padding
+const MARKER: u8 = 0x80;
+
+/// Errors that can occur when stripping the padding.
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum PaddingError {
+    /// Indicates that the data doesn't end with a valid padding.
+    #[error("invalid padding")]
+    Invalid,
+}
+
+impl ErrorCode for PaddingError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Invalid => "padding.invalid",
+        }
+    }
+}
+
+impl From<PaddingError> for JsValue {
+    fn from(value: PaddingError) -> Self {
+        crate::js_error("PaddingError", &value)
+    }
+}
+
+/// Padding schemes, hiding the size of the payloads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Padding {
+    /// Padmé padding, rounding the size to a length with a limited number of
+    /// significant bits
+    Padme,
+    /// Padding to a multiple of a block size in bytes, a block size of 0 being
+    /// considered as 1
+    Block(usize),
+}
+
+/// Padmé length of a payload of `length` bytes
+fn padme_length(length: usize) -> usize {
+    if length < 2 {
+        return length;
+    }
+    let exponent = length.ilog2();
+    let significant_bits = exponent.ilog2() + 1;
+    let mask = (1usize << (exponent - significant_bits)) - 1;
+    (length + mask) & !mask
+}
+
+impl Padding {
+    /// Size of a payload of `length` bytes, once padded
+    pub fn padded_length(&self, length: usize) -> usize {
+        // the marker is always added
+        let length = length + 1;
+        match self {
+            Self::Padme => padme_length(length),
+            Self::Block(size) => length.next_multiple_of((*size).max(1)),
+        }
+    }
+
+    /// Pads a payload
+    ///
+    /// # Arguments
+    /// * `data` - Payload to pad
+    ///
+    /// # Returns
+    /// The padded payload, of [`Padding::padded_length`] bytes
+    pub fn pad(&self, data: &[u8]) -> Vec<u8> {
+        let length = self.padded_length(data.len());
+        let mut output = Vec::with_capacity(length);
+        output.extend_from_slice(data);
+        output.push(MARKER);
+        output.resize(length, 0);
+        output
+    }
+
+    /// Strips the padding of a payload
+    ///
+    /// # Arguments
+    /// * `data` - Padded payload
+    ///
+    /// # Returns
+    /// Result containing the payload or a PaddingError
+    ///
+    /// # Errors
+    /// - `PaddingError::Invalid` if the data doesn't end with a marker followed
+    ///   by zeros
+    pub fn unpad(&self, data: &[u8]) -> Result<Vec<u8>, PaddingError> {
+        let end = data
+            .iter()
+            .rposition(|byte| *byte != 0)
+            .filter(|end| data[*end] == MARKER)
+            .ok_or(PaddingError::Invalid)?;
+        Ok(data[..end].to_vec())
+    }
+}
+
+/// Cipher padding the payloads before encrypting them
+///
+/// The payloads encrypted with another padding scheme can still be decrypted.
+#[derive(Debug, Clone)]
+pub struct Padded<A> {
+    cipher: A,
+    padding: Padding,
+}
+
+impl<A> Padded<A> {
+    /// Wraps a cipher with a padding scheme
+    pub fn new(cipher: A, padding: Padding) -> Self {
+        Self { cipher, padding }
+    }
+
+    /// Underlying cipher
+    pub fn inner(&self) -> &A {
+        &self.cipher
+    }
+
+    /// Padding scheme
+    pub fn padding(&self) -> Padding {
+        self.padding
+    }
+}
+
+impl<A: Algorithm> Algorithm for Padded<A> {
+    const NAME: &'static str = A::NAME;
+    const NONCE_SIZE: u32 = A::NONCE_SIZE;
+    const KEY_SIZE: u32 = A::KEY_SIZE;
+    type EncryptError = A::EncryptError;
+    type DecryptError = A::DecryptError;
+
+    async fn encrypt(
+        &self,
+        nonce: &Nonce<Self>,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, Self::EncryptError> {
+        let payload = self.padding.pad(payload);
+        self.cipher.encrypt(&nonce.cast(), &payload).await
+    }
+
+    /// Decrypts data, then strips its padding
+    ///
+    /// # Errors
+    /// - `DecryptionError::InvalidPadding` if the decrypted data is not padded
+    async fn decrypt(
+        &self,
+        nonce: &Nonce<Self>,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, Self::DecryptError> {
+        let payload = self.cipher.decrypt(&nonce.cast(), payload).await?;
+        self.padding
+            .unpad(&payload)
+            .map_err(|_| DecryptionError::InvalidPadding.into())
+    }
+}
+
+impl<A: crate::nonce_sequence::SequentialNonce> crate::nonce_sequence::SequentialNonce
+    for Padded<A>
+{
+}
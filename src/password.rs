@@ -0,0 +1,400 @@
+//! Password-based encryption
+//!
+//! Encrypts a payload with a passphrase in a single call: a key is derived with
+//! PBKDF2-HMAC-SHA256 from the passphrase and a random salt, then the payload
+//! is encrypted with AES-256-GCM and a random nonce.
+//!
+//! The output is self-describing, so it can be decrypted with the passphrase
+//! only: a version byte, the number of iterations (4 bytes, big endian), the
+//! salt, the nonce and the encrypted payload. The header is authenticated as
+//! additional data.
+//!
+//! Keys are backed up the same way, with
+//! [`crate::aes_gcm::AesGcm::export_protected`]: the header is followed by the
+//! key, wrapped with an AES-KW key derived from the passphrase.
+//!
+//! Passphrases can also be checked without encrypting anything, like to unlock
+//! a local vault, with [`hash`] and [`verify`]. The hash is stored as a string
+//! in the PHC format, embedding the derivation parameters:
+//! `$pbkdf2-sha256$i=600000$<salt>$<hash>`, the salt and the hash being base64
+//! encoded without padding.
+//!
+//! ```rust,no_run
+//! async fn protect() -> Result<(), Box<dyn std::error::Error>> {
+//!     let encrypted = browser_crypto::password::encrypt(b"passphrase", b"Hello World!").await?;
+//!     let decrypted = browser_crypto::password::decrypt(b"passphrase", &encrypted).await?;
+//!     assert_eq!(decrypted, b"Hello World!");
+//!     Ok(())
+//! }
+//! ```
+
+use wasm_bindgen::JsValue;
+
+use crate::aes_gcm::Aes256Gcm;
+use crate::aes_kw::Aes256Kw;
+use crate::algorithm::{Algorithm, DecryptionError, EncryptionError, Nonce, NonceError};
+use crate::derive::private::Sealed;
+use crate::derive::DeriveError;
+use crate::hash::Hash;
+use crate::input::SecretInput;
+use crate::key::{
+    ImportKeyError, KeyUnwrapper, KeyWrapper, UnwrapKeyError, WrapKeyError, WrappableKey,
+};
+use crate::pbkdf2::Pbkdf2;
+use crate::ErrorCode;
+
+/// Version of the output format
+pub const VERSION: u8 = 1;
+
+/// Size of the random salt in bytes
+pub const SALT_SIZE: usize = 16;
+
+/// Size of the header: version, iterations and salt
+const HEADER_SIZE: usize = 1 + 4 + SALT_SIZE;
+
+/// Errors that can occur during password-based encryption or decryption.
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum PasswordError {
+    /// Indicates that the encrypted data is truncated.
+    #[error("invalid encrypted data")]
+    InvalidFormat,
+    /// Indicates that the encrypted data was produced by an unknown version of
+    /// the format.
+    ///
+    /// # Fields
+    /// * `0` - The version found in the encrypted data
+    #[error("unsupported format version {0}")]
+    UnsupportedVersion(u8),
+    /// Indicates that the passphrase couldn't be imported.
+    #[error(transparent)]
+    Import(#[from] ImportKeyError),
+    /// Indicates that the key couldn't be derived, like with zero iterations.
+    #[error(transparent)]
+    Derive(#[from] DeriveError),
+    /// Indicates that the salt or the nonce couldn't be generated.
+    #[error(transparent)]
+    Nonce(#[from] NonceError),
+    /// Indicates that the encryption failed.
+    #[error(transparent)]
+    Encryption(#[from] EncryptionError),
+    /// Indicates that the decryption failed, like when the passphrase is wrong
+    /// or the data has been altered.
+    #[error(transparent)]
+    Decryption(#[from] DecryptionError),
+    /// Indicates that the key couldn't be wrapped, like when it's not
+    /// extractable.
+    #[error(transparent)]
+    Wrap(#[from] WrapKeyError),
+    /// Indicates that the key couldn't be unwrapped, like when the passphrase
+    /// is wrong or the data has been altered.
+    #[error(transparent)]
+    Unwrap(#[from] UnwrapKeyError),
+    /// Indicates that a stored password hash is not a PBKDF2 hash in the PHC
+    /// format.
+    #[error("invalid password hash")]
+    InvalidHash,
+}
+
+impl ErrorCode for PasswordError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidFormat => "password.invalid_format",
+            Self::UnsupportedVersion(..) => "password.unsupported_version",
+            Self::Import(inner) => inner.code(),
+            Self::Derive(inner) => inner.code(),
+            Self::Nonce(inner) => inner.code(),
+            Self::Encryption(inner) => inner.code(),
+            Self::Decryption(inner) => inner.code(),
+            Self::Wrap(inner) => inner.code(),
+            Self::Unwrap(inner) => inner.code(),
+            Self::InvalidHash => "password.invalid_hash",
+        }
+    }
+}
+
+impl From<PasswordError> for JsValue {
+    fn from(value: PasswordError) -> Self {
+        crate::js_error("PasswordError", &value)
+    }
+}
+
+/// Writes the header: version, iterations and salt
+fn write_header(iterations: u32, salt: &[u8], capacity: usize) -> Vec<u8> {
+    let mut output = Vec::with_capacity(HEADER_SIZE + capacity);
+    output.push(VERSION);
+    output.extend_from_slice(&iterations.to_be_bytes());
+    output.extend_from_slice(salt);
+    output
+}
+
+/// Reads the header, returning the iterations, the salt and the remaining
+/// bytes
+fn read_header(data: &[u8]) -> Result<(u32, &[u8], &[u8]), PasswordError> {
+    let (&version, rest) = data.split_first().ok_or(PasswordError::InvalidFormat)?;
+    if version != VERSION {
+        return Err(PasswordError::UnsupportedVersion(version));
+    }
+    let (iterations, rest) = rest
+        .split_first_chunk::<4>()
+        .ok_or(PasswordError::InvalidFormat)?;
+    if rest.len() < SALT_SIZE {
+        return Err(PasswordError::InvalidFormat);
+    }
+    let (salt, rest) = rest.split_at(SALT_SIZE);
+    Ok((u32::from_be_bytes(*iterations), salt, rest))
+}
+
+async fn derive_cipher(
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+) -> Result<Aes256Gcm, PasswordError> {
+    let pbkdf2 = Pbkdf2::from_password(password, Hash::Sha256, salt, iterations).await?;
+    Ok(pbkdf2.derive_aes_gcm::<256>().await?)
+}
+
+/// Encrypts a payload with a passphrase, using
+/// [`Pbkdf2::DEFAULT_ITERATIONS`] iterations
+///
+/// # Arguments
+/// * `password` - Passphrase
+/// * `plaintext` - Data to encrypt
+///
+/// # Returns
+/// Result containing the encrypted data, embedding the derivation parameters
+/// and the nonce, or a PasswordError
+pub async fn encrypt(
+    password: &(impl SecretInput + ?Sized),
+    plaintext: &[u8],
+) -> Result<Vec<u8>, PasswordError> {
+    encrypt_with_iterations(password, plaintext, Pbkdf2::DEFAULT_ITERATIONS).await
+}
+
+/// Encrypts a payload with a passphrase, using a custom number of iterations
+///
+/// # Arguments
+/// * `password` - Passphrase
+/// * `plaintext` - Data to encrypt
+/// * `iterations` - Number of PBKDF2 iterations, stored in the output
+///
+/// # Returns
+/// Result containing the encrypted data or a PasswordError
+///
+/// # Errors
+/// - `PasswordError::Derive` if the number of iterations is zero
+pub async fn encrypt_with_iterations(
+    password: &(impl SecretInput + ?Sized),
+    plaintext: &[u8],
+    iterations: u32,
+) -> Result<Vec<u8>, PasswordError> {
+    let salt = crate::random::random_bytes(SALT_SIZE)?;
+    let cipher = derive_cipher(password.expose_bytes(), &salt, iterations).await?;
+
+    let mut output = write_header(
+        iterations,
+        &salt,
+        Aes256Gcm::NONCE_SIZE as usize + plaintext.len() + 16,
+    );
+
+    let nonce = Aes256Gcm::generate_nonce()?;
+    let encrypted = cipher.encrypt_with_aad(&nonce, &output, plaintext).await?;
+    output.extend(nonce.iter());
+    output.extend_from_slice(&encrypted);
+    Ok(output)
+}
+
+/// Decrypts a payload encrypted with [`encrypt`]
+///
+/// The derivation runs with the number of iterations stored in the encrypted
+/// data, untrusted data can make it arbitrarily slow.
+///
+/// # Arguments
+/// * `password` - Passphrase
+/// * `encrypted` - Data returned by [`encrypt`]
+///
+/// # Returns
+/// Result containing the decrypted payload or a PasswordError
+///
+/// # Errors
+/// - `PasswordError::InvalidFormat` if the data is truncated
+/// - `PasswordError::UnsupportedVersion` if the data has an unknown version
+/// - `PasswordError::Decryption` if the passphrase is wrong or the data has
+///   been altered
+pub async fn decrypt(
+    password: &(impl SecretInput + ?Sized),
+    encrypted: &[u8],
+) -> Result<Vec<u8>, PasswordError> {
+    let (iterations, salt, rest) = read_header(encrypted)?;
+    let nonce_size = Aes256Gcm::NONCE_SIZE as usize;
+    if rest.len() < nonce_size {
+        return Err(PasswordError::InvalidFormat);
+    }
+    let (nonce, ciphertext) = rest.split_at(nonce_size);
+    let header = &encrypted[..HEADER_SIZE];
+
+    let cipher = derive_cipher(password.expose_bytes(), salt, iterations).await?;
+    let nonce = Nonce::<Aes256Gcm>::from_slice(nonce)?;
+    Ok(cipher.decrypt_with_aad(&nonce, header, ciphertext).await?)
+}
+
+async fn derive_kek(
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+) -> Result<Aes256Kw, PasswordError> {
+    let pbkdf2 = Pbkdf2::from_password(password, Hash::Sha256, salt, iterations).await?;
+    let params = pbkdf2.derive_params().map_err(DeriveError::from)?;
+    Ok(crate::derive::derive_key(&params, pbkdf2.base_key(), false).await?)
+}
+
+/// Wraps a key with an AES-KW key derived from a passphrase, with a random
+/// salt, prefixed by the header
+pub(crate) async fn wrap_key<K: WrappableKey>(
+    password: &[u8],
+    key: &K,
+    iterations: u32,
+) -> Result<Vec<u8>, PasswordError> {
+    let salt = crate::random::random_bytes(SALT_SIZE)?;
+    let kek = derive_kek(password, &salt, iterations).await?;
+    let wrapped = kek.wrap_key(key).await?;
+
+    let mut output = write_header(iterations, &salt, wrapped.len());
+    output.extend_from_slice(&wrapped);
+    Ok(output)
+}
+
+/// Unwraps a key wrapped by [`wrap_key`]
+pub(crate) async fn unwrap_key<K: WrappableKey>(
+    password: &[u8],
+    data: &[u8],
+    extractable: bool,
+) -> Result<K, PasswordError> {
+    let (iterations, salt, wrapped) = read_header(data)?;
+    if wrapped.is_empty() {
+        return Err(PasswordError::InvalidFormat);
+    }
+    let kek = derive_kek(password, salt, iterations).await?;
+    if extractable {
+        Ok(kek.unwrap_extractable_key(wrapped).await?)
+    } else {
+        Ok(kek.unwrap_key(wrapped).await?)
+    }
+}
+
+/// Identifier of the hash function in the PHC format
+fn phc_id(hash: Hash) -> &'static str {
+    match hash {
+        Hash::Sha1 => "pbkdf2",
+        Hash::Sha256 => "pbkdf2-sha256",
+        Hash::Sha384 => "pbkdf2-sha384",
+        Hash::Sha512 => "pbkdf2-sha512",
+    }
+}
+
+/// Encodes bytes in base64 without padding, as in the PHC format
+fn phc_base64(data: &[u8]) -> String {
+    crate::encoding::base64_encode(data)
+        .trim_end_matches('=')
+        .to_string()
+}
+
+async fn derive_hash(
+    password: &[u8],
+    hash: Hash,
+    salt: &[u8],
+    iterations: u32,
+) -> Result<crate::SecretBytes, PasswordError> {
+    let pbkdf2 = Pbkdf2::from_password(password, hash, salt, iterations).await?;
+    Ok(pbkdf2
+        .derive_bits(hash.output_size() as u32 * 8)
+        .await?
+        .into())
+}
+
+/// Hashes a passphrase with PBKDF2-HMAC-SHA256, using
+/// [`Pbkdf2::DEFAULT_ITERATIONS`] iterations and a random salt
+///
+/// # Arguments
+/// * `password` - Passphrase
+///
+/// # Returns
+/// Result containing the hash in the PHC format, to store and check with
+/// [`verify`], or a PasswordError
+pub async fn hash(password: &(impl SecretInput + ?Sized)) -> Result<String, PasswordError> {
+    hash_with_iterations(password, Pbkdf2::DEFAULT_ITERATIONS).await
+}
+
+/// Hashes a passphrase with PBKDF2-HMAC-SHA256, using a custom number of
+/// iterations
+///
+/// # Arguments
+/// * `password` - Passphrase
+/// * `iterations` - Number of PBKDF2 iterations, stored in the hash
+///
+/// # Returns
+/// Result containing the hash in the PHC format or a PasswordError
+///
+/// # Errors
+/// - `PasswordError::Derive` if the number of iterations is zero
+pub async fn hash_with_iterations(
+    password: &(impl SecretInput + ?Sized),
+    iterations: u32,
+) -> Result<String, PasswordError> {
+    let salt = crate::random::random_bytes(SALT_SIZE)?;
+    let derived = derive_hash(password.expose_bytes(), Hash::Sha256, &salt, iterations).await?;
+    Ok(format!(
+        "${}$i={iterations}${}${}",
+        phc_id(Hash::Sha256),
+        phc_base64(&salt),
+        phc_base64(&derived)
+    ))
+}
+
+/// Checks a passphrase against a hash produced by [`hash`]
+///
+/// The PBKDF2 hashes using another hash function, like `pbkdf2-sha512`, are
+/// accepted too. The derivation runs with the number of iterations stored in
+/// the hash, and the hashes are compared in constant time.
+///
+/// # Arguments
+/// * `password` - Passphrase to check
+/// * `stored` - Hash in the PHC format
+///
+/// # Returns
+/// Result containing whether the passphrase matches, or a PasswordError
+///
+/// # Errors
+/// - `PasswordError::InvalidHash` if the stored hash can't be parsed
+pub async fn verify(
+    password: &(impl SecretInput + ?Sized),
+    stored: &str,
+) -> Result<bool, PasswordError> {
+    let mut parts = stored.split('$');
+    let (Some(""), Some(id), Some(params), Some(salt), Some(expected), None) = (
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+    ) else {
+        return Err(PasswordError::InvalidHash);
+    };
+    let hash = [Hash::Sha1, Hash::Sha256, Hash::Sha384, Hash::Sha512]
+        .into_iter()
+        .find(|hash| phc_id(*hash) == id)
+        .ok_or(PasswordError::InvalidHash)?;
+    let iterations = params
+        .strip_prefix("i=")
+        .and_then(|value| value.parse::<u32>().ok())
+        .filter(|value| *value > 0)
+        .ok_or(PasswordError::InvalidHash)?;
+    let salt = crate::encoding::base64_decode(salt).map_err(|_| PasswordError::InvalidHash)?;
+    let expected = crate::encoding::base64_decode(expected)
+        .ok()
+        .filter(|expected| expected.len() == hash.output_size())
+        .ok_or(PasswordError::InvalidHash)?;
+    let derived = derive_hash(password.expose_bytes(), hash, &salt, iterations).await?;
+    Ok(crate::util::ct_eq(&derived, &expected))
+}
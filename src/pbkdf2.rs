@@ -0,0 +1,139 @@
+//! PBKDF2 key derivation implementation
+//!
+//! Derives keys from passwords, using a salt and a number of iterations to slow
+//! down brute force attacks.
+//!
+//! ```rust,no_run
+//! use browser_crypto::aes_gcm::Aes256Gcm;
+//! use browser_crypto::hash::Hash;
+//! use browser_crypto::pbkdf2::Pbkdf2;
+//!
+//! async fn from_password(salt: &[u8]) -> Result<Aes256Gcm, Box<dyn std::error::Error>> {
+//!     let pbkdf2 = Pbkdf2::from_password(
+//!         b"correct horse battery staple",
+//!         Hash::Sha256,
+//!         salt,
+//!         Pbkdf2::DEFAULT_ITERATIONS,
+//!     )
+//!     .await?;
+//!     Ok(pbkdf2.derive_aes_gcm::<256>().await?)
+//! }
+//! ```
+
+use crate::aes_gcm::AesGcm;
+use crate::derive::{DeriveError, KeyDerivation};
+use crate::hash::Hash;
+use crate::input::SecretInput;
+use crate::key::ImportKeyError;
+
+const NAME: &str = "PBKDF2";
+
+/// PBKDF2 key derivation, from a password with a given salt and iteration
+/// count
+#[derive(Debug, Clone)]
+pub struct Pbkdf2 {
+    key: web_sys::CryptoKey,
+    hash: Hash,
+    salt: Vec<u8>,
+    iterations: u32,
+}
+
+impl Pbkdf2 {
+    /// Number of iterations recommended by OWASP for PBKDF2-HMAC-SHA256
+    pub const DEFAULT_ITERATIONS: u32 = 600_000;
+
+    /// Creates a new PBKDF2 instance from a password.
+    ///
+    /// The password is imported as a non extractable key.
+    ///
+    /// # Arguments
+    /// * `password` - Password bytes
+    /// * `hash` - Hash function used by the underlying HMAC
+    /// * `salt` - Random salt, at least 16 bytes are recommended, like a
+    ///   [`crate::salt::Salt`]
+    /// * `iterations` - Number of iterations
+    ///
+    /// # Returns
+    /// Result containing the Pbkdf2 instance or an ImportKeyError
+    pub async fn from_password(
+        password: &(impl SecretInput + ?Sized),
+        hash: Hash,
+        salt: impl AsRef<[u8]>,
+        iterations: u32,
+    ) -> Result<Self, ImportKeyError> {
+        let key = crate::key::import_base_key(NAME, password.expose_bytes()).await?;
+        Ok(Self {
+            key,
+            hash,
+            salt: salt.as_ref().to_vec(),
+            iterations,
+        })
+    }
+
+    /// Hash function used by the underlying HMAC
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    /// Salt used for the derivation
+    pub fn salt(&self) -> &[u8] {
+        &self.salt
+    }
+
+    /// Number of iterations
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    fn params(&self) -> web_sys::Pbkdf2Params {
+        web_sys::Pbkdf2Params::new(
+            NAME,
+            &self.hash.name().into(),
+            self.iterations,
+            &js_sys::Uint8Array::from(self.salt.as_slice()),
+        )
+    }
+
+    /// Derives raw bits from the password
+    ///
+    /// # Arguments
+    /// * `length` - Number of bits to derive, should be a multiple of 8
+    ///
+    /// # Returns
+    /// Result containing the derived bytes or a DeriveError
+    ///
+    /// # Errors
+    /// - `DeriveError::Operation` if the length is not a multiple of 8 or the
+    ///   iteration count is zero
+    pub async fn derive_bits(&self, length: u32) -> Result<Vec<u8>, DeriveError> {
+        crate::derive::derive_bits(&self.params(), &self.key, length).await
+    }
+
+    /// Derives an AES-GCM key of `KEY_BITS` bits from the password
+    ///
+    /// This is a shortcut to [`AesGcm::derive_from`], the derived key is not
+    /// extractable.
+    ///
+    /// # Returns
+    /// Result containing the AesGcm instance or a DeriveError
+    ///
+    /// # Errors
+    /// - `DeriveError::Operation` if the iteration count is zero
+    pub async fn derive_aes_gcm<const KEY_BITS: usize>(
+        &self,
+    ) -> Result<AesGcm<KEY_BITS>, DeriveError> {
+        AesGcm::derive_from(self).await
+    }
+}
+
+impl crate::derive::private::Sealed for Pbkdf2 {
+    fn derive_params(&self) -> Result<js_sys::Object, wasm_bindgen::JsValue> {
+        Ok(self.params().into())
+    }
+
+    fn base_key(&self) -> &web_sys::CryptoKey {
+        &self.key
+    }
+}
+
+impl KeyDerivation for Pbkdf2 {}
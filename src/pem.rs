@@ -0,0 +1,150 @@
+//! PEM encoding of SPKI and PKCS#8 keys
+//!
+//! Keys are commonly shared in the textual encoding defined by RFC 7468, the
+//! base64 encoded DER surrounded by `-----BEGIN PUBLIC KEY-----` and
+//! `-----END PUBLIC KEY-----` lines. This module converts them from and to the
+//! DER encoding expected by the `from_spki` and `from_pkcs8` functions.
+//!
+//! ```rust,no_run
+//! use browser_crypto::curve::NamedCurve;
+//! use browser_crypto::ecdsa::EcdsaPublicKey;
+//! use browser_crypto::hash::Hash;
+//!
+//! async fn load(pem: &str) -> Result<EcdsaPublicKey, Box<dyn std::error::Error>> {
+//!     let spki = browser_crypto::pem::decode_spki(pem)?;
+//!     Ok(EcdsaPublicKey::from_spki(&spki, NamedCurve::P256, Hash::Sha256).await?)
+//! }
+//! ```
+
+use wasm_bindgen::JsValue;
+
+use crate::encoding::{base64_decode, base64_encode};
+use crate::ErrorCode;
+
+/// Label of SubjectPublicKeyInfo public keys
+pub const PUBLIC_KEY_LABEL: &str = "PUBLIC KEY";
+/// Label of PKCS#8 private keys
+pub const PRIVATE_KEY_LABEL: &str = "PRIVATE KEY";
+
+const LINE_LENGTH: usize = 64;
+
+/// Errors that can occur when decoding PEM content.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum PemError {
+    /// Indicates that the content doesn't have matching `-----BEGIN` and
+    /// `-----END` lines.
+    #[error("missing or mismatching PEM boundaries")]
+    InvalidBoundary,
+    /// Indicates that the content is not of the expected type, like a PKCS#1
+    /// `RSA PUBLIC KEY` provided instead of a `PUBLIC KEY`.
+    ///
+    /// # Fields
+    /// * `0` - The label found in the content
+    #[error("unexpected PEM label {0:?}")]
+    UnexpectedLabel(String),
+    /// Indicates that the content between the boundaries is not valid base64.
+    #[error("invalid base64 content")]
+    InvalidBase64,
+}
+
+impl ErrorCode for PemError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidBoundary => "pem.invalid_boundary",
+            Self::UnexpectedLabel(..) => "pem.unexpected_label",
+            Self::InvalidBase64 => "pem.invalid_base64",
+        }
+    }
+}
+
+impl From<PemError> for JsValue {
+    fn from(value: PemError) -> Self {
+        crate::js_error("PemError", &value)
+    }
+}
+
+/// Decodes the first PEM block of a text, any text before or after it being
+/// ignored.
+///
+/// # Arguments
+/// * `pem` - PEM encoded content
+///
+/// # Returns
+/// Result containing the label and the decoded bytes, or a PemError
+///
+/// # Errors
+/// - `PemError::InvalidBoundary` if no complete PEM block is found
+/// - `PemError::InvalidBase64` if the content is not valid base64
+pub fn decode(pem: &str) -> Result<(String, Vec<u8>), PemError> {
+    let (_, rest) = pem
+        .split_once("-----BEGIN ")
+        .ok_or(PemError::InvalidBoundary)?;
+    let (label, rest) = rest.split_once("-----").ok_or(PemError::InvalidBoundary)?;
+    let footer = format!("-----END {label}-----");
+    let (content, _) = rest
+        .split_once(footer.as_str())
+        .ok_or(PemError::InvalidBoundary)?;
+    let content: String = content
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace())
+        .collect();
+    let data = base64_decode(&content).map_err(|_| PemError::InvalidBase64)?;
+    Ok((label.to_string(), data))
+}
+
+/// Encodes bytes as a PEM block, with lines of 64 characters.
+///
+/// # Arguments
+/// * `label` - Type of the content, like `PUBLIC KEY`
+/// * `data` - Bytes to encode
+///
+/// # Returns
+/// The PEM block, ending with a new line
+pub fn encode(label: &str, data: &[u8]) -> String {
+    let content = base64_encode(data);
+    let mut output = format!("-----BEGIN {label}-----\n");
+    // base64 is ASCII, so the lines can be split on any byte
+    for line in content.as_bytes().chunks(LINE_LENGTH) {
+        output.push_str(std::str::from_utf8(line).unwrap_or_default());
+        output.push('\n');
+    }
+    output.push_str(&format!("-----END {label}-----\n"));
+    output
+}
+
+fn decode_with_label(expected: &str, pem: &str) -> Result<Vec<u8>, PemError> {
+    let (label, data) = decode(pem)?;
+    if label != expected {
+        return Err(PemError::UnexpectedLabel(label));
+    }
+    Ok(data)
+}
+
+/// Decodes a `PUBLIC KEY` PEM block into a DER encoded SubjectPublicKeyInfo
+///
+/// # Errors
+/// - `PemError::UnexpectedLabel` if the block is not a `PUBLIC KEY`
+/// - Any error returned by [`decode`]
+pub fn decode_spki(pem: &str) -> Result<Vec<u8>, PemError> {
+    decode_with_label(PUBLIC_KEY_LABEL, pem)
+}
+
+/// Decodes a `PRIVATE KEY` PEM block into a DER encoded PKCS#8 private key
+///
+/// # Errors
+/// - `PemError::UnexpectedLabel` if the block is not a `PRIVATE KEY`
+/// - Any error returned by [`decode`]
+pub fn decode_pkcs8(pem: &str) -> Result<Vec<u8>, PemError> {
+    decode_with_label(PRIVATE_KEY_LABEL, pem)
+}
+
+/// Encodes a DER encoded SubjectPublicKeyInfo as a `PUBLIC KEY` PEM block
+pub fn encode_spki(der: &[u8]) -> String {
+    encode(PUBLIC_KEY_LABEL, der)
+}
+
+/// Encodes a DER encoded PKCS#8 private key as a `PRIVATE KEY` PEM block
+pub fn encode_pkcs8(der: &[u8]) -> String {
+    encode(PRIVATE_KEY_LABEL, der)
+}
@@ -0,0 +1,280 @@
+//! Secure random values
+//!
+//! Random bytes from the platform's secure random number generator, through
+//! `crypto.getRandomValues()`, for salts, tokens or identifiers, without
+//! depending on another crate. The Web Crypto API limits each call to 65536
+//! bytes, larger requests are split into several calls.
+//!
+//! Random integers are also provided, with [`gen_range`] picking a value in a
+//! range without the bias of a plain modulo, as well as version 4 UUIDs with
+//! [`uuid`].
+//!
+//! With the `rand_core` feature enabled, [`BrowserRng`] exposes the same
+//! generator through the `RngCore` and `CryptoRng` traits, for the crates
+//! expecting one.
+//!
+//! With the `getrandom` feature enabled, the crate registers
+//! [`getrandom_backend`] as the custom backend of `getrandom` 0.2 on
+//! `wasm32-unknown-unknown`, so the dependencies relying on `getrandom` build
+//! without enabling its `js` feature. When the `js` feature is enabled anyway,
+//! `getrandom` uses it instead. No other crate of the build may register a
+//! custom backend.
+//!
+//! ```rust,no_run
+//! use browser_crypto::random;
+//!
+//! fn session_token() -> Result<Vec<u8>, browser_crypto::algorithm::NonceError> {
+//!     random::random_bytes(32)
+//! }
+//!
+//! fn retry_delay_ms() -> Result<u64, browser_crypto::algorithm::NonceError> {
+//!     Ok(1000 + random::gen_range(0..500)?)
+//! }
+//! ```
+
+#[cfg(not(native_backend))]
+use wasm_bindgen::JsCast;
+
+use crate::algorithm::NonceError;
+
+/// Maximum number of bytes filled by a single call to `getRandomValues()`
+pub const MAX_CHUNK_SIZE: usize = 65536;
+
+/// Generates random bytes
+///
+/// # Arguments
+/// * `len` - Number of bytes to generate
+///
+/// # Returns
+/// Result containing the random bytes or a NonceError
+///
+/// # Errors
+/// - `NonceError::Generic` if the Web Crypto API is not available
+pub fn random_bytes(len: usize) -> Result<Vec<u8>, NonceError> {
+    let mut output = vec![0; len];
+    fill_random(&mut output)?;
+    Ok(output)
+}
+
+/// Fills a buffer with random bytes
+///
+/// With the `native` feature enabled, outside of WebAssembly, the bytes come
+/// from the random number generator of the operating system. With the `mock`
+/// feature enabled, the backend registered with
+/// [`crate::mock::set_backend`] can take over.
+///
+/// # Arguments
+/// * `buffer` - Buffer to overwrite
+///
+/// # Errors
+/// - `NonceError::Generic` if the Web Crypto API is not available
+pub fn fill_random(buffer: &mut [u8]) -> Result<(), NonceError> {
+    #[cfg(feature = "mock")]
+    if let Some(result) = crate::mock::fill_random(buffer) {
+        return result;
+    }
+    #[cfg(native_backend)]
+    crate::native::fill_random(buffer)?;
+    #[cfg(not(native_backend))]
+    {
+        let crypto = crate::crypto()?;
+        let array = js_sys::Uint8Array::new_with_length(buffer.len().min(MAX_CHUNK_SIZE) as u32);
+        for chunk in buffer.chunks_mut(MAX_CHUNK_SIZE) {
+            let view = array.subarray(0, chunk.len() as u32);
+            crypto.get_random_values_with_js_u8_array(&view)?;
+            view.copy_to(chunk);
+        }
+    }
+    Ok(())
+}
+
+/// Generates a random 32-bit integer
+///
+/// # Errors
+/// - `NonceError::Generic` if the Web Crypto API is not available
+pub fn u32() -> Result<u32, NonceError> {
+    let mut bytes = [0; 4];
+    fill_random(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+/// Generates a random 64-bit integer
+///
+/// # Errors
+/// - `NonceError::Generic` if the Web Crypto API is not available
+pub fn u64() -> Result<u64, NonceError> {
+    let mut bytes = [0; 8];
+    fill_random(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Picks a random integer in a range, each value having the same probability
+///
+/// The random integers are drawn again while they fall in the incomplete cycle
+/// of the modulo, so the lower values of the range are not favored.
+///
+/// # Arguments
+/// * `range` - Range of the values, like `0..n`
+///
+/// # Errors
+/// - `NonceError::Generic` if the Web Crypto API is not available
+///
+/// # Panics
+/// If the range is empty
+pub fn gen_range(range: std::ops::Range<u64>) -> Result<u64, NonceError> {
+    assert!(!range.is_empty(), "cannot pick a value in an empty range");
+    let size = range.end - range.start;
+    // 2^64 modulo the size, the number of values to reject
+    let threshold = size.wrapping_neg() % size;
+    loop {
+        let value = u64()?;
+        if value >= threshold {
+            return Ok(range.start + value % size);
+        }
+    }
+}
+
+/// Random UUID, as defined by RFC 9562 version 4
+///
+/// Displayed in its canonical form, lowercase hexadecimal digits separated by
+/// hyphens, like `"36b8f84d-df4e-4d49-b662-bcde71a8764f"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Uuid([u8; 16]);
+
+impl Uuid {
+    /// Builds a version 4 UUID from random bytes, overwriting the version and
+    /// variant bits
+    fn from_random_bytes(mut bytes: [u8; 16]) -> Self {
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+        Self(bytes)
+    }
+
+    #[cfg(not(native_backend))]
+    /// Parses the canonical form of a UUID
+    fn parse(input: &str) -> Option<Self> {
+        let digits = input.replace('-', "");
+        if input.len() != 36 || digits.len() != 32 {
+            return None;
+        }
+        let bytes = crate::encoding::hex_decode(&digits).ok()?;
+        bytes.try_into().ok().map(Self)
+    }
+
+    /// Bytes of the UUID
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+
+    /// Consumes the UUID, returning its bytes
+    pub fn into_bytes(self) -> [u8; 16] {
+        self.0
+    }
+}
+
+impl std::fmt::Display for Uuid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let hex = crate::encoding::hex_encode(&self.0);
+        write!(
+            f,
+            "{}-{}-{}-{}-{}",
+            &hex[..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..]
+        )
+    }
+}
+
+impl From<Uuid> for [u8; 16] {
+    fn from(value: Uuid) -> Self {
+        value.0
+    }
+}
+
+/// Generates a random UUID
+///
+/// Relies on `crypto.randomUUID()`, only exposed to secure contexts, and falls
+/// back to `crypto.getRandomValues()` when it's missing.
+///
+/// # Errors
+/// - `NonceError::Generic` if the Web Crypto API is not available
+pub fn uuid() -> Result<Uuid, NonceError> {
+    #[cfg(not(native_backend))]
+    {
+        let crypto = crate::crypto()?;
+        let native = js_sys::Reflect::get(&crypto, &"randomUUID".into())
+            .ok()
+            .filter(|value| value.is_instance_of::<js_sys::Function>());
+        if native.is_some() {
+            if let Some(uuid) = Uuid::parse(&crypto.random_uuid()) {
+                return Ok(uuid);
+            }
+        }
+    }
+    let mut bytes = [0; 16];
+    fill_random(&mut bytes)?;
+    Ok(Uuid::from_random_bytes(bytes))
+}
+
+/// Random number generator backed by `crypto.getRandomValues()`
+///
+/// Implements the `rand_core` traits, so other crates, like the RustCrypto
+/// ones, can draw from the platform's secure random number generator. It holds
+/// no state: every call goes to the Web Crypto API.
+///
+/// Requires the `rand_core` feature.
+#[cfg(feature = "rand_core")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BrowserRng;
+
+/// The infallible methods panic if the Web Crypto API is not available, use
+/// `try_fill_bytes` to handle it.
+#[cfg(feature = "rand_core")]
+impl rand_core::RngCore for BrowserRng {
+    fn next_u32(&mut self) -> u32 {
+        u32().expect("unable to generate random values")
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        u64().expect("unable to generate random values")
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        fill_random(dest).expect("unable to generate random values")
+    }
+
+    /// Failures are reported with the [`rand_core::Error::CUSTOM_START`] code,
+    /// the `rand_core` errors only holding a code without the `std` feature.
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        fill_random(dest).map_err(|_| {
+            rand_core::Error::from(
+                std::num::NonZeroU32::new(rand_core::Error::CUSTOM_START)
+                    .expect("the custom error code is not zero"),
+            )
+        })
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl rand_core::CryptoRng for BrowserRng {}
+
+/// Fills a buffer for `getrandom`, registered as its custom backend on
+/// `wasm32-unknown-unknown`
+///
+/// Failures are reported with the `getrandom::Error::CUSTOM_START` code.
+///
+/// Requires the `getrandom` feature.
+#[cfg(feature = "getrandom")]
+pub fn getrandom_backend(dest: &mut [u8]) -> Result<(), getrandom::Error> {
+    fill_random(dest).map_err(|_| {
+        getrandom::Error::from(
+            std::num::NonZeroU32::new(getrandom::Error::CUSTOM_START)
+                .expect("the custom error code is not zero"),
+        )
+    })
+}
+
+#[cfg(all(feature = "getrandom", target_arch = "wasm32", target_os = "unknown"))]
+getrandom::register_custom_getrandom!(getrandom_backend);
@@ -0,0 +1,238 @@
+//! Algorithms selected by name
+//!
+//! A [`Registry`] maps identifiers, like `"AES-GCM-256"` or `"ECDH-P256"`, to
+//! the constructors importing a key for the algorithm, so the algorithm can
+//! come from a configuration file or a plugin instead of the code. The
+//! registries start with the built-in algorithms, [`Registry::ciphers`] and
+//! [`Registry::key_agreements`], and the applications can register their own
+//! entries or replace the built-in ones.
+//!
+//! The names are case sensitive.
+//!
+//! ```rust,no_run
+//! use browser_crypto::registry::Registry;
+//!
+//! async fn encrypt(name: &str, key: &[u8]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+//!     let registry = Registry::ciphers();
+//!     let cipher = registry.create(name, key).await?;
+//!     Ok(cipher.seal(b"Hello World!").await?)
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use wasm_bindgen::JsValue;
+
+use crate::aes_cbc::AesCbc;
+use crate::aes_gcm::AesGcm;
+use crate::curve::NamedCurve;
+use crate::dyn_algorithm::{BoxFuture, DynAlgorithm};
+use crate::ecdh::EcdhPrivateKey;
+use crate::key::ImportKeyError;
+use crate::ErrorCode;
+
+/// Errors that can occur when creating an algorithm from a registry.
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum RegistryError {
+    /// Indicates that no algorithm is registered with the name.
+    ///
+    /// # Fields
+    /// * `0` - The requested name
+    #[error("unknown algorithm {0:?}")]
+    UnknownAlgorithm(String),
+    /// Indicates that the key couldn't be imported.
+    #[error(transparent)]
+    Import(#[from] ImportKeyError),
+}
+
+impl ErrorCode for RegistryError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::UnknownAlgorithm(..) => "registry.unknown_algorithm",
+            Self::Import(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<RegistryError> for JsValue {
+    fn from(value: RegistryError) -> Self {
+        crate::js_error("RegistryError", &value)
+    }
+}
+
+type Constructor<T> = Rc<dyn for<'a> Fn(&'a [u8]) -> BoxFuture<'a, Result<T, ImportKeyError>>>;
+
+/// Constructors of algorithms, identified by their name
+///
+/// Cloning a registry is cheap, the constructors being shared.
+pub struct Registry<T> {
+    entries: BTreeMap<String, Constructor<T>>,
+}
+
+impl<T> Default for Registry<T> {
+    fn default() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T> Clone for Registry<T> {
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries.clone(),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for Registry<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.entries.keys()).finish()
+    }
+}
+
+impl<T> Registry<T> {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a constructor, replacing the one registered with the same
+    /// name
+    ///
+    /// # Arguments
+    /// * `name` - Identifier of the algorithm
+    /// * `constructor` - Function importing the key material of the algorithm
+    ///
+    /// # Returns
+    /// Whether a constructor was already registered with the name
+    ///
+    /// ```rust,no_run
+    /// use browser_crypto::aes_gcm::Aes256Gcm;
+    /// use browser_crypto::dyn_algorithm::DynAlgorithm;
+    /// use browser_crypto::padding::{Padded, Padding};
+    /// use browser_crypto::registry::Registry;
+    ///
+    /// let mut registry = Registry::ciphers();
+    /// registry.register("PADME-AES-GCM-256", |key| {
+    ///     Box::pin(async move {
+    ///         let cipher = Aes256Gcm::from_key(key).await?;
+    ///         Ok(Box::new(Padded::new(cipher, Padding::Padme)) as Box<dyn DynAlgorithm>)
+    ///     })
+    /// });
+    /// ```
+    pub fn register<F>(&mut self, name: impl Into<String>, constructor: F) -> bool
+    where
+        F: for<'a> Fn(&'a [u8]) -> BoxFuture<'a, Result<T, ImportKeyError>> + 'static,
+    {
+        self.entries
+            .insert(name.into(), Rc::new(constructor))
+            .is_some()
+    }
+
+    /// Removes the constructor registered with a name
+    ///
+    /// # Returns
+    /// Whether a constructor was registered with the name
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.entries.remove(name).is_some()
+    }
+
+    /// Whether a constructor is registered with a name
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+
+    /// Names of the registered algorithms, in alphabetical order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    /// Creates an algorithm from its name and its key material
+    ///
+    /// # Arguments
+    /// * `name` - Identifier of the algorithm
+    /// * `key` - Key material, in the format expected by the constructor
+    ///
+    /// # Returns
+    /// Result containing the algorithm or a RegistryError
+    ///
+    /// # Errors
+    /// - `RegistryError::UnknownAlgorithm` if no algorithm is registered with
+    ///   the name
+    /// - `RegistryError::Import` if the constructor fails to import the key
+    pub async fn create(&self, name: &str, key: &[u8]) -> Result<T, RegistryError> {
+        let constructor = self
+            .entries
+            .get(name)
+            .ok_or_else(|| RegistryError::UnknownAlgorithm(name.to_string()))?;
+        Ok(constructor(key).await?)
+    }
+}
+
+fn aes_gcm<const KEY_BITS: usize>(
+    key: &[u8],
+) -> BoxFuture<'_, Result<Box<dyn DynAlgorithm>, ImportKeyError>> {
+    Box::pin(async move {
+        let cipher = AesGcm::<KEY_BITS>::from_key(key).await?;
+        Ok(Box::new(cipher) as Box<dyn DynAlgorithm>)
+    })
+}
+
+fn aes_cbc<const KEY_BITS: usize>(
+    key: &[u8],
+) -> BoxFuture<'_, Result<Box<dyn DynAlgorithm>, ImportKeyError>> {
+    Box::pin(async move {
+        let cipher = AesCbc::<KEY_BITS>::from_key(key).await?;
+        Ok(Box::new(cipher) as Box<dyn DynAlgorithm>)
+    })
+}
+
+impl Registry<Box<dyn DynAlgorithm>> {
+    /// Registry of the built-in ciphers, created from raw keys
+    ///
+    /// | Name          | Cipher                                  |
+    /// |---------------|-----------------------------------------|
+    /// | `AES-GCM-128` | [`crate::aes_gcm::Aes128Gcm`]           |
+    /// | `AES-GCM-192` | [`crate::aes_gcm::Aes192Gcm`]           |
+    /// | `AES-GCM-256` | [`crate::aes_gcm::Aes256Gcm`]           |
+    /// | `AES-CBC-128` | [`crate::aes_cbc::Aes128Cbc`]           |
+    /// | `AES-CBC-192` | [`crate::aes_cbc::Aes192Cbc`]           |
+    /// | `AES-CBC-256` | [`crate::aes_cbc::Aes256Cbc`]           |
+    pub fn ciphers() -> Self {
+        let mut registry = Self::new();
+        registry.register("AES-GCM-128", aes_gcm::<128>);
+        registry.register("AES-GCM-192", aes_gcm::<192>);
+        registry.register("AES-GCM-256", aes_gcm::<256>);
+        registry.register("AES-CBC-128", aes_cbc::<128>);
+        registry.register("AES-CBC-192", aes_cbc::<192>);
+        registry.register("AES-CBC-256", aes_cbc::<256>);
+        registry
+    }
+}
+
+impl Registry<EcdhPrivateKey> {
+    /// Registry of the built-in key agreements, created from DER encoded
+    /// PKCS#8 private keys
+    ///
+    /// | Name        | Key agreement                    |
+    /// |-------------|----------------------------------|
+    /// | `ECDH-P256` | ECDH on [`NamedCurve::P256`]     |
+    /// | `ECDH-P384` | ECDH on [`NamedCurve::P384`]     |
+    /// | `ECDH-P521` | ECDH on [`NamedCurve::P521`]     |
+    pub fn key_agreements() -> Self {
+        let mut registry = Self::new();
+        for (name, curve) in [
+            ("ECDH-P256", NamedCurve::P256),
+            ("ECDH-P384", NamedCurve::P384),
+            ("ECDH-P521", NamedCurve::P521),
+        ] {
+            registry.register(name, move |key| {
+                Box::pin(EcdhPrivateKey::from_pkcs8(key, curve))
+            });
+        }
+        registry
+    }
+}
@@ -0,0 +1,351 @@
+//! RSA-OAEP encryption implementation
+//!
+//! RSA-OAEP is an asymmetric algorithm, the public key encrypts small payloads,
+//! like session keys, that only the private key can decrypt.
+//!
+//! ```rust,no_run
+//! use browser_crypto::hash::Hash;
+//! use browser_crypto::rsa_oaep::RsaOaepPublicKey;
+//!
+//! async fn encrypt(spki: &[u8]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+//!     let public_key = RsaOaepPublicKey::from_spki(spki, Hash::Sha256).await?;
+//!     Ok(public_key.encrypt(b"session key").await?)
+//! }
+//! ```
+
+use crate::algorithm::{DecryptionError, EncryptionError};
+use crate::context::Operation;
+use crate::der::KeyAlgorithm;
+use crate::hash::Hash;
+use crate::input::SecretInput;
+use crate::jwk::Jwk;
+use crate::key::{
+    ExportKeyError, GenerateKeyError, ImportKeyError, KeyFormat, KeyUnwrapper, KeyWrapper,
+};
+use crate::key_pair::{AsymmetricAlgorithm, AsymmetricKey, KeyPair};
+
+const NAME: &str = "RSA-OAEP";
+
+/// Public exponent used when generating keys, 65537
+const PUBLIC_EXPONENT: [u8; 3] = [0x01, 0x00, 0x01];
+
+fn import_algorithm(hash: Hash) -> Result<js_sys::Object, wasm_bindgen::JsValue> {
+    let algorithm = js_sys::Object::new();
+    js_sys::Reflect::set(&algorithm, &"name".into(), &NAME.into())?;
+    js_sys::Reflect::set(&algorithm, &"hash".into(), &hash.name().into())?;
+    Ok(algorithm)
+}
+
+/// RSA-OAEP algorithm, see [`RsaOaepKeyPair`]
+#[derive(Debug, Clone, Copy)]
+pub struct RsaOaep;
+
+impl AsymmetricAlgorithm for RsaOaep {
+    type PublicKey = RsaOaepPublicKey;
+    type PrivateKey = RsaOaepPrivateKey;
+
+    const PUBLIC_USAGES: &'static [&'static str] = &["encrypt", "wrapKey"];
+    const PRIVATE_USAGES: &'static [&'static str] = &["decrypt", "unwrapKey"];
+}
+
+/// RSA-OAEP public key, used for encryption and key wrapping
+#[derive(Debug, Clone)]
+pub struct RsaOaepPublicKey {
+    key: web_sys::CryptoKey,
+}
+
+impl RsaOaepPublicKey {
+    /// Imports a public key in the SubjectPublicKeyInfo format.
+    ///
+    /// # Arguments
+    /// * `data` - DER encoded SubjectPublicKeyInfo
+    /// * `hash` - Hash function used by OAEP
+    ///
+    /// # Returns
+    /// Result containing the public key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::MalformedDer` if the data is not a DER encoded
+    ///   SubjectPublicKeyInfo
+    /// - `ImportKeyError::UnsupportedAlgorithm` if the key is not an RSA key
+    /// - `ImportKeyError::InvalidKeyFormat` if the data is not a valid RSA
+    ///   public key
+    pub async fn from_spki(data: &[u8], hash: Hash) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let algorithm = import_algorithm(hash)?;
+        let key = crate::key::import_der(
+            KeyFormat::Spki,
+            &algorithm,
+            data,
+            KeyAlgorithm::Rsa,
+            RsaOaep::PUBLIC_USAGES,
+        )
+        .await?;
+        Ok(Self { key })
+    }
+
+    /// Imports a public key in the JSON Web Key format.
+    ///
+    /// # Arguments
+    /// * `jwk` - JSON Web Key, with `kty` set to `RSA`
+    /// * `hash` - Hash function used by OAEP, must match the `alg` of the JWK
+    ///   when defined (`RSA-OAEP-256` for SHA-256)
+    ///
+    /// # Returns
+    /// Result containing the public key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::InvalidKeyFormat` if the JWK is not a valid RSA
+    ///   public key or doesn't match the hash function
+    pub async fn from_jwk(jwk: &Jwk, hash: Hash) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let algorithm = import_algorithm(hash)?;
+        let usages = crate::key::jwk_usages(jwk, RsaOaep::PUBLIC_USAGES);
+        let key = crate::key::import_jwk(&algorithm, jwk, &usages).await?;
+        Ok(Self { key })
+    }
+
+    /// Exports the public key in the SubjectPublicKeyInfo format.
+    ///
+    /// # Returns
+    /// Result containing the DER encoded SubjectPublicKeyInfo or an
+    /// ExportKeyError
+    pub async fn to_spki(&self) -> Result<Vec<u8>, ExportKeyError> {
+        crate::key::export_key(KeyFormat::Spki, &self.key).await
+    }
+
+    /// Exports the public key in the JSON Web Key format.
+    ///
+    /// # Returns
+    /// Result containing the JSON Web Key or an ExportKeyError
+    pub async fn to_jwk(&self) -> Result<Jwk, ExportKeyError> {
+        crate::key::export_jwk(&self.key).await
+    }
+
+    /// Encrypts data using this public key
+    ///
+    /// # Arguments
+    /// * `payload` - Data to encrypt, its maximum size depends on the modulus
+    ///   length and the hash function (190 bytes for a 2048-bit key with
+    ///   SHA-256)
+    ///
+    /// # Returns
+    /// Result containing encrypted bytes or an EncryptionError
+    ///
+    /// # Errors
+    /// - `EncryptionError::Operation` if the payload is too large
+    pub async fn encrypt(&self, payload: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let ciphertext =
+            crate::instrument::observe(Operation::Encrypt, NAME, Some(payload.len()), async {
+                let subtle = crate::subtle()?;
+                let params = web_sys::RsaOaepParams::new(NAME);
+                let promise: js_sys::Promise = crate::with_bytes(payload, |plaintext| {
+                    subtle.encrypt_with_object_and_js_u8_array(&params, &self.key, plaintext)
+                })?;
+                crate::resolve::<js_sys::ArrayBuffer, EncryptionError>(promise).await
+            })
+            .await?;
+
+        Ok(crate::array_to_vec(&js_sys::Uint8Array::new(&ciphertext)))
+    }
+}
+
+impl crate::key_pair::private::Sealed for RsaOaepPublicKey {
+    fn crypto_key(&self) -> &web_sys::CryptoKey {
+        &self.key
+    }
+}
+
+impl AsymmetricKey for RsaOaepPublicKey {}
+
+impl crate::key::private::Wrapper for RsaOaepPublicKey {
+    fn wrapping_algorithm(&self) -> Result<js_sys::Object, wasm_bindgen::JsValue> {
+        Ok(web_sys::RsaOaepParams::new(NAME).into())
+    }
+
+    fn wrapping_key(&self) -> &web_sys::CryptoKey {
+        &self.key
+    }
+}
+
+/// The public key wraps secret keys, like AES keys, that only the private key
+/// can unwrap.
+impl KeyWrapper for RsaOaepPublicKey {}
+
+/// RSA-OAEP private key, used for decryption and key unwrapping
+#[derive(Debug, Clone)]
+pub struct RsaOaepPrivateKey {
+    key: web_sys::CryptoKey,
+}
+
+impl RsaOaepPrivateKey {
+    /// Imports a private key in the PKCS#8 format.
+    ///
+    /// # Arguments
+    /// * `data` - DER encoded PKCS#8 private key
+    /// * `hash` - Hash function used by OAEP
+    ///
+    /// # Returns
+    /// Result containing the private key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::MalformedDer` if the data is not a DER encoded PKCS#8
+    ///   key
+    /// - `ImportKeyError::UnsupportedAlgorithm` if the key is not an RSA key
+    /// - `ImportKeyError::InvalidKeyFormat` if the data is not a valid RSA
+    ///   private key
+    pub async fn from_pkcs8(
+        data: &(impl SecretInput + ?Sized),
+        hash: Hash,
+    ) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let algorithm = import_algorithm(hash)?;
+        let key = crate::key::import_der(
+            KeyFormat::Pkcs8,
+            &algorithm,
+            data.expose_bytes(),
+            KeyAlgorithm::Rsa,
+            RsaOaep::PRIVATE_USAGES,
+        )
+        .await?;
+        Ok(Self { key })
+    }
+
+    /// Imports a private key in the JSON Web Key format.
+    ///
+    /// # Arguments
+    /// * `jwk` - JSON Web Key, with `kty` set to `RSA`
+    /// * `hash` - Hash function used by OAEP, must match the `alg` of the JWK
+    ///   when defined (`RSA-OAEP-256` for SHA-256)
+    ///
+    /// # Returns
+    /// Result containing the private key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::InvalidKeyFormat` if the JWK is not a valid RSA
+    ///   private key or doesn't match the hash function
+    pub async fn from_jwk(jwk: &Jwk, hash: Hash) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let algorithm = import_algorithm(hash)?;
+        let usages = crate::key::jwk_usages(jwk, RsaOaep::PRIVATE_USAGES);
+        let key = crate::key::import_jwk(&algorithm, jwk, &usages).await?;
+        Ok(Self { key })
+    }
+
+    /// Exports the private key in the PKCS#8 format.
+    ///
+    /// # Returns
+    /// Result containing the DER encoded PKCS#8 private key or an
+    /// ExportKeyError
+    pub async fn to_pkcs8(&self) -> Result<Vec<u8>, ExportKeyError> {
+        crate::key::export_key(KeyFormat::Pkcs8, &self.key).await
+    }
+
+    /// Exports the private key in the JSON Web Key format.
+    ///
+    /// # Returns
+    /// Result containing the JSON Web Key or an ExportKeyError
+    pub async fn to_jwk(&self) -> Result<Jwk, ExportKeyError> {
+        crate::key::export_jwk(&self.key).await
+    }
+
+    /// Decrypts data using this private key
+    ///
+    /// # Arguments
+    /// * `payload` - Data encrypted with the matching public key
+    ///
+    /// # Returns
+    /// Result containing decrypted bytes or a DecryptionError
+    ///
+    /// # Errors
+    /// - `DecryptionError::Operation` if the payload was encrypted with another
+    ///   key or has been altered
+    pub async fn decrypt(&self, payload: &[u8]) -> Result<Vec<u8>, DecryptionError> {
+        let clear = self.decrypt_buffer(payload).await?;
+        Ok(crate::array_to_vec(&js_sys::Uint8Array::new(&clear)))
+    }
+
+    /// Decrypts data in its buffer, copying the decrypted data from the
+    /// browser directly over the encrypted data, which is truncated
+    ///
+    /// The buffer is left unchanged on error.
+    ///
+    /// # Arguments
+    /// * `buffer` - Data encrypted with the matching public key, replaced by
+    ///   the decrypted data
+    ///
+    /// # Returns
+    /// Result containing nothing or a DecryptionError
+    ///
+    /// # Errors
+    /// Same as [`RsaOaepPrivateKey::decrypt`]
+    pub async fn decrypt_in_place(&self, buffer: &mut Vec<u8>) -> Result<(), DecryptionError> {
+        let clear = self.decrypt_buffer(buffer).await?;
+        buffer.clear();
+        crate::extend_from_array(buffer, &js_sys::Uint8Array::new(&clear));
+        Ok(())
+    }
+
+    async fn decrypt_buffer(&self, payload: &[u8]) -> Result<js_sys::ArrayBuffer, DecryptionError> {
+        crate::instrument::observe(Operation::Decrypt, NAME, Some(payload.len()), async {
+            let subtle = crate::subtle()?;
+            let params = web_sys::RsaOaepParams::new(NAME);
+            let promise: js_sys::Promise = crate::with_bytes(payload, |payload| {
+                subtle.decrypt_with_object_and_js_u8_array(&params, &self.key, payload)
+            })?;
+            crate::resolve::<js_sys::ArrayBuffer, DecryptionError>(promise).await
+        })
+        .await
+    }
+}
+
+impl crate::key_pair::private::Sealed for RsaOaepPrivateKey {
+    fn crypto_key(&self) -> &web_sys::CryptoKey {
+        &self.key
+    }
+}
+
+impl AsymmetricKey for RsaOaepPrivateKey {}
+
+impl crate::key::private::Wrapper for RsaOaepPrivateKey {
+    fn wrapping_algorithm(&self) -> Result<js_sys::Object, wasm_bindgen::JsValue> {
+        Ok(web_sys::RsaOaepParams::new(NAME).into())
+    }
+
+    fn wrapping_key(&self) -> &web_sys::CryptoKey {
+        &self.key
+    }
+}
+
+impl KeyUnwrapper for RsaOaepPrivateKey {}
+
+/// RSA-OAEP key pair, the public key encrypts and the private key decrypts
+pub type RsaOaepKeyPair = KeyPair<RsaOaep>;
+
+impl KeyPair<RsaOaep> {
+    /// Generates a new key pair, with a public exponent of 65537.
+    ///
+    /// # Arguments
+    /// * `modulus_length` - Size of the modulus in bits, at least 2048 is
+    ///   recommended
+    /// * `hash` - Hash function used by OAEP
+    ///
+    /// # Returns
+    /// Result containing the key pair or a GenerateKeyError
+    pub async fn generate(modulus_length: u32, hash: Hash) -> Result<Self, GenerateKeyError> {
+        crate::check_target()?;
+        let algorithm = import_algorithm(hash)?;
+        js_sys::Reflect::set(&algorithm, &"modulusLength".into(), &modulus_length.into())?;
+        js_sys::Reflect::set(
+            &algorithm,
+            &"publicExponent".into(),
+            &js_sys::Uint8Array::from(PUBLIC_EXPONENT.as_slice()),
+        )?;
+
+        let (public_key, private_key) = crate::key_pair::generate::<RsaOaep>(&algorithm).await?;
+        Ok(Self::new(
+            RsaOaepPublicKey { key: public_key },
+            RsaOaepPrivateKey { key: private_key },
+        ))
+    }
+}
@@ -0,0 +1,297 @@
+//! RSASSA-PKCS1-v1_5 signature implementation
+//!
+//! This legacy algorithm is mostly used for interoperability, like verifying
+//! JWTs signed with RS256 (RSASSA-PKCS1-v1_5 with SHA-256).
+//!
+//! ```rust,no_run
+//! use browser_crypto::hash::Hash;
+//! use browser_crypto::rsassa_pkcs1::RsassaPkcs1PublicKey;
+//!
+//! async fn verify(
+//!     spki: &[u8],
+//!     signature: &[u8],
+//!     payload: &[u8],
+//! ) -> Result<bool, Box<dyn std::error::Error>> {
+//!     let public_key = RsassaPkcs1PublicKey::from_spki(spki, Hash::Sha256).await?;
+//!     Ok(public_key.verify(signature, payload).await?)
+//! }
+//! ```
+
+use crate::der::KeyAlgorithm;
+use crate::hash::Hash;
+use crate::input::SecretInput;
+use crate::jwk::Jwk;
+use crate::key::{ExportKeyError, GenerateKeyError, ImportKeyError, KeyFormat};
+use crate::key_pair::{AsymmetricAlgorithm, AsymmetricKey, KeyPair};
+use crate::signature::{SignError, SignatureAlgorithm, VerifyError};
+
+const NAME: &str = "RSASSA-PKCS1-v1_5";
+
+/// Public exponent used when generating keys, 65537
+const PUBLIC_EXPONENT: [u8; 3] = [0x01, 0x00, 0x01];
+
+fn import_algorithm(hash: Hash) -> Result<js_sys::Object, wasm_bindgen::JsValue> {
+    let algorithm = js_sys::Object::new();
+    js_sys::Reflect::set(&algorithm, &"name".into(), &NAME.into())?;
+    js_sys::Reflect::set(&algorithm, &"hash".into(), &hash.name().into())?;
+    Ok(algorithm)
+}
+
+fn signature_algorithm() -> Result<js_sys::Object, wasm_bindgen::JsValue> {
+    let algorithm = js_sys::Object::new();
+    js_sys::Reflect::set(&algorithm, &"name".into(), &NAME.into())?;
+    Ok(algorithm)
+}
+
+/// RSASSA-PKCS1-v1_5 algorithm, see [`RsassaPkcs1KeyPair`]
+#[derive(Debug, Clone, Copy)]
+pub struct RsassaPkcs1;
+
+impl AsymmetricAlgorithm for RsassaPkcs1 {
+    type PublicKey = RsassaPkcs1PublicKey;
+    type PrivateKey = RsassaPkcs1PrivateKey;
+
+    const PUBLIC_USAGES: &'static [&'static str] = &["verify"];
+    const PRIVATE_USAGES: &'static [&'static str] = &["sign"];
+}
+
+impl SignatureAlgorithm for RsassaPkcs1 {
+    const NAME: &'static str = NAME;
+    const SIGNATURE_SIZE: Option<usize> = None;
+    type SigningKey = RsassaPkcs1PrivateKey;
+    type VerifyingKey = RsassaPkcs1PublicKey;
+
+    async fn sign(key: &RsassaPkcs1PrivateKey, payload: &[u8]) -> Result<Vec<u8>, SignError> {
+        key.sign(payload).await
+    }
+
+    async fn verify(
+        key: &RsassaPkcs1PublicKey,
+        signature: &[u8],
+        payload: &[u8],
+    ) -> Result<bool, VerifyError> {
+        key.verify(signature, payload).await
+    }
+}
+
+/// RSASSA-PKCS1-v1_5 public key, used for verification
+#[derive(Debug, Clone)]
+pub struct RsassaPkcs1PublicKey {
+    key: web_sys::CryptoKey,
+}
+
+impl RsassaPkcs1PublicKey {
+    /// Imports a public key in the SubjectPublicKeyInfo format.
+    ///
+    /// # Arguments
+    /// * `data` - DER encoded SubjectPublicKeyInfo
+    /// * `hash` - Hash function used for signing
+    ///
+    /// # Returns
+    /// Result containing the public key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::MalformedDer` if the data is not a DER encoded
+    ///   SubjectPublicKeyInfo
+    /// - `ImportKeyError::UnsupportedAlgorithm` if the key is not an RSA key
+    /// - `ImportKeyError::InvalidKeyFormat` if the data is not a valid RSA
+    ///   public key
+    pub async fn from_spki(data: &[u8], hash: Hash) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let algorithm = import_algorithm(hash)?;
+        let key = crate::key::import_der(
+            KeyFormat::Spki,
+            &algorithm,
+            data,
+            KeyAlgorithm::Rsa,
+            &["verify"],
+        )
+        .await?;
+        Ok(Self { key })
+    }
+
+    /// Imports a public key in the JSON Web Key format.
+    ///
+    /// # Arguments
+    /// * `jwk` - JSON Web Key, with `kty` set to `RSA`
+    /// * `hash` - Hash function used for signing, must match the `alg` of the
+    ///   JWK when defined (`RS256` for SHA-256)
+    ///
+    /// # Returns
+    /// Result containing the public key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::InvalidKeyFormat` if the JWK is not a valid RSA
+    ///   public key or doesn't match the hash function
+    pub async fn from_jwk(jwk: &Jwk, hash: Hash) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let algorithm = import_algorithm(hash)?;
+        let key = crate::key::import_jwk(&algorithm, jwk, &["verify"]).await?;
+        Ok(Self { key })
+    }
+
+    /// Exports the public key in the SubjectPublicKeyInfo format.
+    ///
+    /// # Returns
+    /// Result containing the DER encoded SubjectPublicKeyInfo or an
+    /// ExportKeyError
+    pub async fn to_spki(&self) -> Result<Vec<u8>, ExportKeyError> {
+        crate::key::export_key(KeyFormat::Spki, &self.key).await
+    }
+
+    /// Exports the public key in the JSON Web Key format.
+    ///
+    /// # Returns
+    /// Result containing the JSON Web Key or an ExportKeyError
+    pub async fn to_jwk(&self) -> Result<Jwk, ExportKeyError> {
+        crate::key::export_jwk(&self.key).await
+    }
+
+    /// Verifies the signature of a payload
+    ///
+    /// # Arguments
+    /// * `signature` - Signature to verify
+    /// * `payload` - Data that was signed
+    ///
+    /// # Returns
+    /// Result containing whether the signature is valid or a VerifyError
+    pub async fn verify(&self, signature: &[u8], payload: &[u8]) -> Result<bool, VerifyError> {
+        let algorithm = signature_algorithm()?;
+        crate::signature::verify(&algorithm, &self.key, signature, payload).await
+    }
+}
+
+impl crate::key_pair::private::Sealed for RsassaPkcs1PublicKey {
+    fn crypto_key(&self) -> &web_sys::CryptoKey {
+        &self.key
+    }
+}
+
+impl AsymmetricKey for RsassaPkcs1PublicKey {}
+
+/// RSASSA-PKCS1-v1_5 private key, used for signing
+#[derive(Debug, Clone)]
+pub struct RsassaPkcs1PrivateKey {
+    key: web_sys::CryptoKey,
+}
+
+impl RsassaPkcs1PrivateKey {
+    /// Imports a private key in the PKCS#8 format.
+    ///
+    /// # Arguments
+    /// * `data` - DER encoded PKCS#8 private key
+    /// * `hash` - Hash function used for signing
+    ///
+    /// # Returns
+    /// Result containing the private key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::MalformedDer` if the data is not a DER encoded PKCS#8
+    ///   key
+    /// - `ImportKeyError::UnsupportedAlgorithm` if the key is not an RSA key
+    /// - `ImportKeyError::InvalidKeyFormat` if the data is not a valid RSA
+    ///   private key
+    pub async fn from_pkcs8(
+        data: &(impl SecretInput + ?Sized),
+        hash: Hash,
+    ) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let algorithm = import_algorithm(hash)?;
+        let key = crate::key::import_der(
+            KeyFormat::Pkcs8,
+            &algorithm,
+            data.expose_bytes(),
+            KeyAlgorithm::Rsa,
+            &["sign"],
+        )
+        .await?;
+        Ok(Self { key })
+    }
+
+    /// Imports a private key in the JSON Web Key format.
+    ///
+    /// # Arguments
+    /// * `jwk` - JSON Web Key, with `kty` set to `RSA` and the private
+    ///   parameters defined
+    /// * `hash` - Hash function used for signing, must match the `alg` of the
+    ///   JWK when defined (`RS256` for SHA-256)
+    ///
+    /// # Returns
+    /// Result containing the private key or an ImportKeyError
+    pub async fn from_jwk(jwk: &Jwk, hash: Hash) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let algorithm = import_algorithm(hash)?;
+        let key = crate::key::import_jwk(&algorithm, jwk, &["sign"]).await?;
+        Ok(Self { key })
+    }
+
+    /// Exports the private key in the PKCS#8 format.
+    ///
+    /// # Returns
+    /// Result containing the DER encoded PKCS#8 private key or an
+    /// ExportKeyError
+    pub async fn to_pkcs8(&self) -> Result<Vec<u8>, ExportKeyError> {
+        crate::key::export_key(KeyFormat::Pkcs8, &self.key).await
+    }
+
+    /// Exports the private key in the JSON Web Key format.
+    ///
+    /// # Returns
+    /// Result containing the JSON Web Key or an ExportKeyError
+    pub async fn to_jwk(&self) -> Result<Jwk, ExportKeyError> {
+        crate::key::export_jwk(&self.key).await
+    }
+
+    /// Signs a payload
+    ///
+    /// # Arguments
+    /// * `payload` - Data to sign
+    ///
+    /// # Returns
+    /// Result containing the signature or a SignError
+    pub async fn sign(&self, payload: &[u8]) -> Result<Vec<u8>, SignError> {
+        let algorithm = signature_algorithm()?;
+        crate::signature::sign(&algorithm, &self.key, payload).await
+    }
+}
+
+impl crate::key_pair::private::Sealed for RsassaPkcs1PrivateKey {
+    fn crypto_key(&self) -> &web_sys::CryptoKey {
+        &self.key
+    }
+}
+
+impl AsymmetricKey for RsassaPkcs1PrivateKey {}
+
+/// RSASSA-PKCS1-v1_5 key pair, the private key signs and the public key
+/// verifies
+pub type RsassaPkcs1KeyPair = KeyPair<RsassaPkcs1>;
+
+impl KeyPair<RsassaPkcs1> {
+    /// Generates a new key pair, with a public exponent of 65537.
+    ///
+    /// # Arguments
+    /// * `modulus_length` - Size of the modulus in bits, at least 2048 is
+    ///   recommended
+    /// * `hash` - Hash function used for signing
+    ///
+    /// # Returns
+    /// Result containing the key pair or a GenerateKeyError
+    pub async fn generate(modulus_length: u32, hash: Hash) -> Result<Self, GenerateKeyError> {
+        crate::check_target()?;
+        let algorithm = import_algorithm(hash)?;
+        js_sys::Reflect::set(&algorithm, &"modulusLength".into(), &modulus_length.into())?;
+        js_sys::Reflect::set(
+            &algorithm,
+            &"publicExponent".into(),
+            &js_sys::Uint8Array::from(PUBLIC_EXPONENT.as_slice()),
+        )?;
+
+        let (public_key, private_key) =
+            crate::key_pair::generate::<RsassaPkcs1>(&algorithm).await?;
+        Ok(Self::new(
+            RsassaPkcs1PublicKey { key: public_key },
+            RsassaPkcs1PrivateKey { key: private_key },
+        ))
+    }
+}
@@ -0,0 +1,258 @@
+//! Salts of the key derivation functions
+//!
+//! [`Salt`] mirrors [`crate::algorithm::Nonce`]: it's generated with the
+//! platform's secure random number generator, checked when created from
+//! existing bytes and encoded with the same helpers, so salts aren't passed
+//! around as bare vectors. [`crate::pbkdf2::Pbkdf2`] and [`crate::hkdf::Hkdf`]
+//! accept it wherever they accept bytes.
+//!
+//! With the `serde` feature enabled, [`Salt`] is serialized as bytes or, for
+//! human readable formats like JSON, as a base64 string.
+//!
+//! ```rust,no_run
+//! use browser_crypto::aes_gcm::Aes256Gcm;
+//! use browser_crypto::hash::Hash;
+//! use browser_crypto::pbkdf2::Pbkdf2;
+//! use browser_crypto::salt::Salt;
+//!
+//! async fn from_password(
+//!     password: &[u8],
+//! ) -> Result<(Salt, Aes256Gcm), Box<dyn std::error::Error>> {
+//!     let salt = Salt::generate()?;
+//!     let pbkdf2 =
+//!         Pbkdf2::from_password(password, Hash::Sha256, &salt, Pbkdf2::DEFAULT_ITERATIONS)
+//!             .await?;
+//!     Ok((salt, pbkdf2.derive_aes_gcm::<256>().await?))
+//! }
+//! ```
+
+use wasm_bindgen::JsValue;
+
+use crate::algorithm::NonceError;
+use crate::encoding::EncodingError;
+use crate::ErrorCode;
+
+/// Errors that can occur when generating or parsing a salt.
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum SaltError {
+    /// Indicates that the salt is shorter than [`Salt::MIN_SIZE`].
+    ///
+    /// # Fields
+    /// * `minimum` - The minimum size in bytes
+    /// * `received` - The size of the provided salt
+    #[error("salt too short, expected at least {minimum} bytes, received {received}")]
+    TooShort { minimum: usize, received: usize },
+    /// Indicates that a salt couldn't be parsed, the text not being properly
+    /// hex or base64url encoded.
+    ///
+    /// # Fields
+    /// * `0` - The decoding error
+    #[error("invalid salt encoding: {0}")]
+    InvalidEncoding(#[from] EncodingError),
+    /// Indicates that the random bytes couldn't be generated.
+    #[error(transparent)]
+    Random(#[from] NonceError),
+}
+
+impl ErrorCode for SaltError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::TooShort { .. } => "salt.too_short",
+            Self::InvalidEncoding(..) => "salt.invalid_encoding",
+            Self::Random(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<SaltError> for JsValue {
+    fn from(value: SaltError) -> Self {
+        crate::js_error("SaltError", &value)
+    }
+}
+
+/// Salt of a key derivation
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Salt {
+    inner: Vec<u8>,
+}
+
+impl AsRef<[u8]> for Salt {
+    fn as_ref(&self) -> &[u8] {
+        &self.inner
+    }
+}
+
+impl Salt {
+    /// Size of the generated salts in bytes, as recommended by NIST SP
+    /// 800-132
+    pub const DEFAULT_SIZE: usize = 16;
+
+    /// Minimum size of the salts created from existing bytes
+    pub const MIN_SIZE: usize = 16;
+
+    /// Generates a new random salt of [`Salt::DEFAULT_SIZE`] bytes
+    ///
+    /// # Returns
+    /// Result containing the generated Salt or a SaltError
+    pub fn generate() -> Result<Self, SaltError> {
+        Self::generate_with_size(Self::DEFAULT_SIZE)
+    }
+
+    /// Generates a new random salt of a custom size
+    ///
+    /// # Arguments
+    /// * `size` - Size of the salt in bytes, at least [`Salt::MIN_SIZE`]
+    ///
+    /// # Errors
+    /// - `SaltError::TooShort` if the size is below [`Salt::MIN_SIZE`]
+    pub fn generate_with_size(size: usize) -> Result<Self, SaltError> {
+        Self::check_size(size)?;
+        Ok(Self {
+            inner: crate::random::random_bytes(size)?,
+        })
+    }
+
+    fn check_size(size: usize) -> Result<(), SaltError> {
+        if size < Self::MIN_SIZE {
+            return Err(SaltError::TooShort {
+                minimum: Self::MIN_SIZE,
+                received: size,
+            });
+        }
+        Ok(())
+    }
+
+    /// Creates a salt from existing bytes
+    ///
+    /// # Arguments
+    /// * `data` - Byte slice containing the salt
+    ///
+    /// # Errors
+    /// - `SaltError::TooShort` if the salt is shorter than [`Salt::MIN_SIZE`]
+    pub fn from_slice(data: &[u8]) -> Result<Self, SaltError> {
+        Self::check_size(data.len())?;
+        Ok(Self {
+            inner: data.to_vec(),
+        })
+    }
+
+    /// Creates a salt from existing bytes, whatever their length
+    ///
+    /// This is meant to derive keys produced by legacy systems with shorter
+    /// salts, new salts should rely on [`Salt::generate`].
+    pub fn from_slice_unchecked_len(data: &[u8]) -> Self {
+        Self {
+            inner: data.to_vec(),
+        }
+    }
+
+    /// Number of bytes
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Whether the salt is empty, only possible with
+    /// [`Salt::from_slice_unchecked_len`]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Returns the salt bytes as a vector
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.inner.clone()
+    }
+
+    /// Encodes the salt as lowercase hexadecimal
+    pub fn to_hex(&self) -> String {
+        crate::encoding::hex_encode(&self.inner)
+    }
+
+    /// Parses a salt encoded as hexadecimal, in lowercase or uppercase
+    ///
+    /// # Errors
+    /// - `SaltError::InvalidEncoding` if the text is not hexadecimal
+    /// - `SaltError::TooShort` if the salt is shorter than [`Salt::MIN_SIZE`]
+    pub fn from_hex(input: &str) -> Result<Self, SaltError> {
+        let bytes = crate::encoding::hex_decode(input)?;
+        Self::from_slice(&bytes)
+    }
+
+    /// Encodes the salt with the URL and filename safe base64 alphabet,
+    /// without padding
+    pub fn to_base64url(&self) -> String {
+        crate::encoding::base64url_encode(&self.inner)
+    }
+
+    /// Parses a salt encoded with the URL and filename safe base64 alphabet,
+    /// padding being optional
+    ///
+    /// # Errors
+    /// - `SaltError::InvalidEncoding` if the text is not base64url encoded
+    /// - `SaltError::TooShort` if the salt is shorter than [`Salt::MIN_SIZE`]
+    pub fn from_base64url(input: &str) -> Result<Self, SaltError> {
+        let bytes = crate::encoding::base64url_decode(input)?;
+        Self::from_slice(&bytes)
+    }
+}
+
+impl TryFrom<&[u8]> for Salt {
+    type Error = SaltError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_slice(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Salt {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&crate::encoding::base64_encode(&self.inner))
+        } else {
+            serializer.serialize_bytes(&self.inner)
+        }
+    }
+}
+
+/// Checks the size of the salt, like [`Salt::from_slice`].
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Salt {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = Salt;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a salt, as bytes or a base64 string")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                let bytes = crate::encoding::base64_decode(value).map_err(E::custom)?;
+                Salt::from_slice(&bytes).map_err(E::custom)
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+                Salt::from_slice(value).map_err(E::custom)
+            }
+
+            fn visit_seq<S: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: S,
+            ) -> Result<Self::Value, S::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(byte) = seq.next_element::<u8>()? {
+                    bytes.push(byte);
+                }
+                Salt::from_slice(&bytes).map_err(serde::de::Error::custom)
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(Visitor)
+        } else {
+            deserializer.deserialize_bytes(Visitor)
+        }
+    }
+}
@@ -0,0 +1,482 @@
+//! Versioned ciphertexts
+//!
+//! [`Sealed`] keeps everything needed to decrypt a payload next to its
+//! ciphertext: the version of the format, the algorithm, the key ID of the
+//! cipher when it has one, the nonce and the SHA-256 hash of the additional
+//! data. The layout is stable, so stored ciphertexts remain decodable by later
+//! versions of the crate.
+//!
+//! The header, everything but the ciphertext, is authenticated as additional
+//! data of AES-GCM: altering it makes the decryption fail. The additional data
+//! itself is not stored, it has to be provided again to open the payload.
+//!
+//! With the `serde` feature enabled, [`Sealed`] is serialized with the same
+//! layout, as bytes or, for human readable formats like JSON, as a base64
+//! string.
+//!
+//! ```rust,no_run
+//! use browser_crypto::aes_gcm::Aes256Gcm;
+//! use browser_crypto::kid::Tagged;
+//! use browser_crypto::sealed::Sealed;
+//!
+//! async fn seal() -> Result<(), Box<dyn std::error::Error>> {
+//!     let cipher = Tagged::new("2024-01", Aes256Gcm::from_key(&[0u8; 32]).await?);
+//!     let sealed = Sealed::seal(&cipher, b"user-42", b"Hello World!").await?;
+//!     let bytes = sealed.to_bytes();
+//!
+//!     let sealed = Sealed::from_bytes(&bytes)?;
+//!     assert_eq!(sealed.kid(), Some("2024-01"));
+//!     assert_eq!(sealed.open(&cipher, b"user-42").await?, b"Hello World!");
+//!     Ok(())
+//! }
+//! ```
+
+use wasm_bindgen::JsValue;
+
+use crate::algorithm::{DecryptionError, EncryptionError, Nonce, NonceError};
+use crate::any_cipher::AnyCipher;
+use crate::hash::Hash;
+use crate::ErrorCode;
+
+/// Version of the layout
+pub const VERSION: u8 = 1;
+
+/// Size of the hash of the additional data, with SHA-256
+const AAD_HASH_SIZE: usize = 32;
+
+/// Errors that can occur when sealing or opening a payload.
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum SealedError {
+    /// Indicates that the serialized payload is truncated or its key ID is not
+    /// valid UTF-8.
+    #[error("invalid sealed payload")]
+    InvalidFormat,
+    /// Indicates that the payload was sealed with an unknown version of the
+    /// layout.
+    ///
+    /// # Fields
+    /// * `0` - The version found in the payload
+    #[error("unsupported sealed payload version {0}")]
+    UnsupportedVersion(u8),
+    /// Indicates that the payload was sealed with an unknown algorithm.
+    ///
+    /// # Fields
+    /// * `0` - The identifier found in the payload
+    #[error("unknown algorithm identifier {0}")]
+    UnknownAlgorithm(u8),
+    /// Indicates that the key ID of the cipher is empty or longer than 255
+    /// bytes.
+    #[error("key ID must be between 1 and 255 bytes")]
+    InvalidKid,
+    /// Indicates that the payload was sealed with another algorithm.
+    ///
+    /// # Fields
+    /// * `0` - The algorithm recorded in the payload
+    #[error("payload sealed with {0:?}")]
+    AlgorithmMismatch(SealedAlgorithm),
+    /// Indicates that the payload was sealed with another cipher, both having
+    /// a key ID.
+    ///
+    /// # Fields
+    /// * `0` - The key ID recorded in the payload
+    #[error("payload sealed with the key {0:?}")]
+    KidMismatch(String),
+    /// Indicates that the additional data doesn't match the one used to seal
+    /// the payload.
+    #[error("additional data mismatch")]
+    AadMismatch,
+    /// Indicates that the nonce couldn't be generated or doesn't match the
+    /// algorithm.
+    #[error(transparent)]
+    Nonce(#[from] NonceError),
+    /// Indicates that the payload couldn't be encrypted.
+    #[error(transparent)]
+    Encryption(#[from] EncryptionError),
+    /// Indicates that the payload couldn't be decrypted, like when the cipher
+    /// doesn't match or the payload has been altered.
+    #[error(transparent)]
+    Decryption(#[from] DecryptionError),
+    /// A wrapper for other types of errors that may occur while hashing the
+    /// additional data.
+    #[error(transparent)]
+    Generic(#[from] crate::Error),
+}
+
+impl ErrorCode for SealedError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidFormat => "sealed.invalid_format",
+            Self::UnsupportedVersion(..) => "sealed.unsupported_version",
+            Self::UnknownAlgorithm(..) => "sealed.unknown_algorithm",
+            Self::InvalidKid => "sealed.invalid_kid",
+            Self::AlgorithmMismatch(..) => "sealed.algorithm_mismatch",
+            Self::KidMismatch(..) => "sealed.kid_mismatch",
+            Self::AadMismatch => "sealed.aad_mismatch",
+            Self::Nonce(inner) => inner.code(),
+            Self::Encryption(inner) => inner.code(),
+            Self::Decryption(inner) => inner.code(),
+            Self::Generic(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<SealedError> for JsValue {
+    fn from(value: SealedError) -> Self {
+        crate::js_error("SealedError", &value)
+    }
+}
+
+/// Algorithms of the sealed payloads, each one having a stable identifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SealedAlgorithm {
+    /// AES-128-GCM
+    Aes128Gcm,
+    /// AES-192-GCM
+    Aes192Gcm,
+    /// AES-256-GCM
+    Aes256Gcm,
+}
+
+impl SealedAlgorithm {
+    /// Identifier of the algorithm in the layout
+    pub fn id(&self) -> u8 {
+        match self {
+            Self::Aes128Gcm => 1,
+            Self::Aes192Gcm => 2,
+            Self::Aes256Gcm => 3,
+        }
+    }
+
+    /// Finds an algorithm from its identifier in the layout
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(Self::Aes128Gcm),
+            2 => Some(Self::Aes192Gcm),
+            3 => Some(Self::Aes256Gcm),
+            _ => None,
+        }
+    }
+
+    /// AES-GCM algorithm for a key size, failing to compile for an unsupported
+    /// one
+    pub(crate) const fn aes_gcm(key_bits: usize) -> Self {
+        match key_bits {
+            128 => Self::Aes128Gcm,
+            192 => Self::Aes192Gcm,
+            256 => Self::Aes256Gcm,
+            _ => panic!("unsupported AES key size"),
+        }
+    }
+}
+
+pub(crate) mod private {
+    use crate::algorithm::{Algorithm, DecryptionError, EncryptionError, Nonce};
+
+    /// Plumbing to authenticate the header of sealed payloads, not meant to be
+    /// implemented outside of this crate.
+    pub trait Cipher: Algorithm {
+        /// Algorithm recorded in the sealed payloads
+        const ALGORITHM: super::SealedAlgorithm;
+
+        /// Key ID attached with [`crate::kid::Tagged`]
+        fn kid(&self) -> Option<&str> {
+            None
+        }
+
+        /// Encrypts data, authenticating some additional data
+        fn encrypt_with_aad(
+            &self,
+            nonce: &Nonce<Self>,
+            aad: &[u8],
+            payload: &[u8],
+        ) -> impl std::future::Future<Output = Result<Vec<u8>, EncryptionError>>;
+
+        /// Decrypts data, checking the additional data
+        fn decrypt_with_aad(
+            &self,
+            nonce: &Nonce<Self>,
+            aad: &[u8],
+            payload: &[u8],
+        ) -> impl std::future::Future<Output = Result<Vec<u8>, DecryptionError>>;
+    }
+}
+
+/// Ciphers able to seal payloads, like [`crate::aes_gcm::AesGcm`] or a tagged
+/// one
+pub trait SealingCipher: private::Cipher {}
+
+/// Encrypted payload, along with what's needed to decrypt it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sealed {
+    algorithm: SealedAlgorithm,
+    kid: Option<String>,
+    nonce: Vec<u8>,
+    aad_hash: [u8; AAD_HASH_SIZE],
+    ciphertext: Vec<u8>,
+}
+
+async fn hash_aad(aad: &[u8]) -> Result<[u8; AAD_HASH_SIZE], SealedError> {
+    let digest = Hash::Sha256.digest(aad).await?;
+    digest.try_into().map_err(|_| SealedError::InvalidFormat)
+}
+
+impl Sealed {
+    /// Encrypts a payload with a random nonce, binding it to some additional
+    /// data.
+    ///
+    /// # Arguments
+    /// * `cipher` - Cipher encrypting the payload
+    /// * `aad` - Additional data, authenticated but not stored, can be empty
+    /// * `payload` - Data to encrypt
+    ///
+    /// # Returns
+    /// Result containing the sealed payload or a SealedError
+    ///
+    /// # Errors
+    /// - `SealedError::InvalidKid` if the key ID of the cipher is empty or
+    ///   longer than 255 bytes
+    /// - `SealedError::Encryption` if the encryption fails
+    pub async fn seal<C: SealingCipher>(
+        cipher: &C,
+        aad: &[u8],
+        payload: &[u8],
+    ) -> Result<Self, SealedError> {
+        let kid = cipher.kid().map(String::from);
+        if kid
+            .as_ref()
+            .is_some_and(|kid| kid.is_empty() || kid.len() > u8::MAX as usize)
+        {
+            return Err(SealedError::InvalidKid);
+        }
+        let nonce = C::generate_nonce()?;
+        let mut sealed = Self {
+            algorithm: C::ALGORITHM,
+            kid,
+            nonce: nonce.to_vec(),
+            aad_hash: hash_aad(aad).await?,
+            ciphertext: Vec::new(),
+        };
+        sealed.ciphertext = cipher
+            .encrypt_with_aad(&nonce, &sealed.header(), payload)
+            .await?;
+        Ok(sealed)
+    }
+
+    /// Decrypts the payload.
+    ///
+    /// # Arguments
+    /// * `cipher` - Cipher used to seal the payload
+    /// * `aad` - Additional data used to seal the payload
+    ///
+    /// # Returns
+    /// Result containing the decrypted payload or a SealedError
+    ///
+    /// # Errors
+    /// - `SealedError::AlgorithmMismatch` if the cipher uses another algorithm
+    /// - `SealedError::KidMismatch` if the payload and the cipher have
+    ///   different key IDs
+    /// - `SealedError::AadMismatch` if the additional data doesn't match
+    /// - `SealedError::Decryption` if the cipher doesn't match or the payload
+    ///   has been altered
+    pub async fn open<C: SealingCipher>(
+        &self,
+        cipher: &C,
+        aad: &[u8],
+    ) -> Result<Vec<u8>, SealedError> {
+        if self.algorithm != C::ALGORITHM {
+            return Err(SealedError::AlgorithmMismatch(self.algorithm));
+        }
+        if let (Some(kid), Some(expected)) = (self.kid.as_deref(), cipher.kid()) {
+            if kid != expected {
+                return Err(SealedError::KidMismatch(kid.to_string()));
+            }
+        }
+        if !crate::util::ct_eq(&hash_aad(aad).await?, &self.aad_hash) {
+            return Err(SealedError::AadMismatch);
+        }
+        let nonce = Nonce::<C>::from_slice(&self.nonce)?;
+        Ok(cipher
+            .decrypt_with_aad(&nonce, &self.header(), &self.ciphertext)
+            .await?)
+    }
+
+    /// Encrypts a payload with a cipher chosen at runtime, see
+    /// [`Sealed::seal`].
+    ///
+    /// # Errors
+    /// Same as [`Sealed::seal`]
+    pub async fn seal_any(
+        cipher: &AnyCipher,
+        aad: &[u8],
+        payload: &[u8],
+    ) -> Result<Self, SealedError> {
+        match cipher {
+            AnyCipher::Aes128Gcm(inner) => Self::seal(inner, aad, payload).await,
+            AnyCipher::Aes192Gcm(inner) => Self::seal(inner, aad, payload).await,
+            AnyCipher::Aes256Gcm(inner) => Self::seal(inner, aad, payload).await,
+        }
+    }
+
+    /// Decrypts the payload with a cipher chosen at runtime, usually created
+    /// for [`Sealed::algorithm`], see [`Sealed::open`].
+    ///
+    /// # Errors
+    /// Same as [`Sealed::open`]
+    pub async fn open_any(&self, cipher: &AnyCipher, aad: &[u8]) -> Result<Vec<u8>, SealedError> {
+        match cipher {
+            AnyCipher::Aes128Gcm(inner) => self.open(inner, aad).await,
+            AnyCipher::Aes192Gcm(inner) => self.open(inner, aad).await,
+            AnyCipher::Aes256Gcm(inner) => self.open(inner, aad).await,
+        }
+    }
+
+    /// Algorithm used to seal the payload
+    pub fn algorithm(&self) -> SealedAlgorithm {
+        self.algorithm
+    }
+
+    /// Key ID of the cipher, if it had one
+    pub fn kid(&self) -> Option<&str> {
+        self.kid.as_deref()
+    }
+
+    /// Nonce used to encrypt the payload
+    pub fn nonce(&self) -> &[u8] {
+        &self.nonce
+    }
+
+    /// SHA-256 hash of the additional data
+    pub fn aad_hash(&self) -> &[u8] {
+        &self.aad_hash
+    }
+
+    /// Encrypted payload, with its authentication tag
+    pub fn ciphertext(&self) -> &[u8] {
+        &self.ciphertext
+    }
+
+    /// Header of the layout, authenticated as additional data
+    fn header(&self) -> Vec<u8> {
+        let kid = self.kid.as_deref().unwrap_or_default();
+        let mut output = Vec::with_capacity(4 + kid.len() + self.nonce.len() + AAD_HASH_SIZE);
+        output.push(VERSION);
+        output.push(self.algorithm.id());
+        output.push(kid.len() as u8);
+        output.extend_from_slice(kid.as_bytes());
+        output.push(self.nonce.len() as u8);
+        output.extend_from_slice(&self.nonce);
+        output.extend_from_slice(&self.aad_hash);
+        output
+    }
+
+    /// Serializes the sealed payload: the version and the algorithm
+    /// identifier on one byte each, the length of the key ID on one byte (0
+    /// without key ID) and the key ID, the length of the nonce on one byte and
+    /// the nonce, the hash of the additional data on 32 bytes, and the
+    /// encrypted payload.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut output = self.header();
+        output.extend_from_slice(&self.ciphertext);
+        output
+    }
+
+    /// Deserializes a sealed payload produced by [`Sealed::to_bytes`]
+    ///
+    /// # Errors
+    /// - `SealedError::InvalidFormat` if the data is truncated
+    /// - `SealedError::UnsupportedVersion` if the data has an unknown version
+    /// - `SealedError::UnknownAlgorithm` if the data has an unknown algorithm
+    ///   identifier
+    pub fn from_bytes(data: &[u8]) -> Result<Self, SealedError> {
+        let (&[version, algorithm], rest) = data
+            .split_first_chunk::<2>()
+            .ok_or(SealedError::InvalidFormat)?;
+        if version != VERSION {
+            return Err(SealedError::UnsupportedVersion(version));
+        }
+        let algorithm =
+            SealedAlgorithm::from_id(algorithm).ok_or(SealedError::UnknownAlgorithm(algorithm))?;
+        let (kid, rest) = split_prefixed(rest)?;
+        let kid = if kid.is_empty() {
+            None
+        } else {
+            let kid = std::str::from_utf8(kid).map_err(|_| SealedError::InvalidFormat)?;
+            Some(kid.to_string())
+        };
+        let (nonce, rest) = split_prefixed(rest)?;
+        let (aad_hash, ciphertext) = rest
+            .split_first_chunk::<AAD_HASH_SIZE>()
+            .ok_or(SealedError::InvalidFormat)?;
+        Ok(Self {
+            algorithm,
+            kid,
+            nonce: nonce.to_vec(),
+            aad_hash: *aad_hash,
+            ciphertext: ciphertext.to_vec(),
+        })
+    }
+}
+
+/// Splits a field prefixed with its length on one byte
+fn split_prefixed(data: &[u8]) -> Result<(&[u8], &[u8]), SealedError> {
+    let (&length, rest) = data.split_first().ok_or(SealedError::InvalidFormat)?;
+    let length = length as usize;
+    if rest.len() < length {
+        return Err(SealedError::InvalidFormat);
+    }
+    Ok(rest.split_at(length))
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Sealed {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = self.to_bytes();
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&crate::encoding::base64_encode(&bytes))
+        } else {
+            serializer.serialize_bytes(&bytes)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Sealed {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = Sealed;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a sealed payload, as bytes or a base64 string")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                let bytes = crate::encoding::base64_decode(value).map_err(E::custom)?;
+                Sealed::from_bytes(&bytes).map_err(E::custom)
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+                Sealed::from_bytes(value).map_err(E::custom)
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(byte) = seq.next_element::<u8>()? {
+                    bytes.push(byte);
+                }
+                Sealed::from_bytes(&bytes).map_err(serde::de::Error::custom)
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(Visitor)
+        } else {
+            deserializer.deserialize_bytes(Visitor)
+        }
+    }
+}
@@ -0,0 +1,416 @@
+//! Digital signature errors and helpers
+//!
+//! These are shared by the signing algorithms, like
+//! [`crate::rsassa_pkcs1`], which implement [`SignatureAlgorithm`] to be used
+//! in generic code.
+//!
+//! With the `signature` feature enabled, the signing and verifying keys also
+//! implement [`AsyncSigner`] and [`AsyncVerifier`], the async analogues of the
+//! `signature` crate's traits, with the same [`Signature`] type and the same
+//! errors, so the libraries built on them, like JWT signers, can use the
+//! browser keys.
+
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::DomException;
+
+use crate::context::Operation;
+use crate::ErrorCode;
+
+/// Errors that can occur during signing operations.
+///
+/// These errors map to the exceptions defined in the Web Crypto API
+/// specification for signing operations.
+///
+/// See [MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto/sign#exceptions)
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum SignError {
+    /// Indicates that the requested operation is not valid for the provided
+    /// key. This typically occurs when:
+    /// - The key is not a signing key
+    /// - The key's usages don't include "sign"
+    #[error("requested operation is not valid for the provided key")]
+    InvalidAccess,
+    /// A wrapper for other types of errors that may occur during signing
+    #[error(transparent)]
+    Generic(#[from] crate::Error),
+}
+
+impl ErrorCode for SignError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidAccess => "sign.invalid_access",
+            Self::Generic(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<SignError> for JsValue {
+    fn from(value: SignError) -> Self {
+        crate::js_error("SignError", &value)
+    }
+}
+
+impl From<JsValue> for SignError {
+    fn from(value: JsValue) -> Self {
+        if let Some(exception) = value.dyn_ref::<DomException>() {
+            if exception.name() == "InvalidAccessError" {
+                return Self::InvalidAccess;
+            }
+        }
+        Self::Generic(crate::Error::from(value))
+    }
+}
+
+/// Errors that can occur during signature verification operations.
+///
+/// An invalid signature is not an error, the verification returns `false`.
+///
+/// See [MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto/verify#exceptions)
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum VerifyError {
+    /// Indicates that the requested operation is not valid for the provided
+    /// key. This typically occurs when:
+    /// - The key is not a verification key
+    /// - The key's usages don't include "verify"
+    #[error("requested operation is not valid for the provided key")]
+    InvalidAccess,
+    /// A wrapper for other types of errors that may occur during verification
+    #[error(transparent)]
+    Generic(#[from] crate::Error),
+}
+
+impl ErrorCode for VerifyError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidAccess => "verify.invalid_access",
+            Self::Generic(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<VerifyError> for JsValue {
+    fn from(value: VerifyError) -> Self {
+        crate::js_error("VerifyError", &value)
+    }
+}
+
+impl From<JsValue> for VerifyError {
+    fn from(value: JsValue) -> Self {
+        if let Some(exception) = value.dyn_ref::<DomException>() {
+            if exception.name() == "InvalidAccessError" {
+                return Self::InvalidAccess;
+            }
+        }
+        Self::Generic(crate::Error::from(value))
+    }
+}
+
+/// Signs a payload
+///
+/// # Arguments
+/// * `algorithm` - Signature algorithm details
+/// * `key` - Signing key
+/// * `payload` - Data to sign
+///
+/// # Returns
+/// Result containing the signature or a SignError
+pub(crate) async fn sign(
+    algorithm: &js_sys::Object,
+    key: &web_sys::CryptoKey,
+    payload: &[u8],
+) -> Result<Vec<u8>, SignError> {
+    let name = crate::instrument::algorithm_name(algorithm);
+    let signature =
+        crate::instrument::observe(Operation::Sign, &name, Some(payload.len()), async {
+            let subtle = crate::subtle()?;
+            let promise: js_sys::Promise = crate::with_bytes(payload, |payload| {
+                subtle.sign_with_object_and_js_u8_array(algorithm, key, payload)
+            })?;
+            crate::resolve::<js_sys::ArrayBuffer, SignError>(promise).await
+        })
+        .await?;
+
+    Ok(crate::array_to_vec(&js_sys::Uint8Array::new(&signature)))
+}
+
+/// Verifies the signature of a payload
+///
+/// # Arguments
+/// * `algorithm` - Signature algorithm details
+/// * `key` - Verification key
+/// * `signature` - Signature to verify
+/// * `payload` - Data that was signed
+///
+/// # Returns
+/// Result containing whether the signature is valid or a VerifyError
+pub(crate) async fn verify(
+    algorithm: &js_sys::Object,
+    key: &web_sys::CryptoKey,
+    signature: &[u8],
+    payload: &[u8],
+) -> Result<bool, VerifyError> {
+    let name = crate::instrument::algorithm_name(algorithm);
+    let valid = crate::instrument::observe(Operation::Verify, &name, Some(payload.len()), async {
+        let subtle = crate::subtle()?;
+        let signature = js_sys::Uint8Array::from(signature);
+        let promise: js_sys::Promise = crate::with_bytes(payload, |payload| {
+            subtle.verify_with_object_and_js_u8_array_and_js_u8_array(
+                algorithm, key, &signature, payload,
+            )
+        })?;
+        crate::resolve::<js_sys::Boolean, VerifyError>(promise).await
+    })
+    .await?;
+
+    Ok(valid.value_of())
+}
+
+/// Core signature algorithm trait, the counterpart of
+/// [`crate::algorithm::Algorithm`] for the algorithms that sign
+///
+/// It is implemented by [`crate::ecdsa::Ecdsa`], [`crate::ed25519::Ed25519`],
+/// [`crate::rsassa_pkcs1::RsassaPkcs1`] and [`crate::hmac::Hmac`], so code
+/// can be generic over the algorithm signing its tokens.
+///
+/// ```rust,no_run
+/// use browser_crypto::signature::{SignError, SignatureAlgorithm};
+///
+/// async fn sign_token<S: SignatureAlgorithm>(
+///     key: &S::SigningKey,
+///     payload: &str,
+/// ) -> Result<Vec<u8>, SignError> {
+///     S::sign(key, payload.as_bytes()).await
+/// }
+/// ```
+pub trait SignatureAlgorithm {
+    /// Name of the algorithm, like "ECDSA"
+    const NAME: &'static str;
+
+    /// Size of the signatures in bytes, `None` when it depends on the key,
+    /// like the curve of ECDSA or the hash function of HMAC
+    const SIGNATURE_SIZE: Option<usize>;
+
+    /// Key signing the payloads
+    type SigningKey: std::fmt::Debug + Clone;
+
+    /// Key verifying the signatures, the same as the signing key for the
+    /// symmetric algorithms
+    type VerifyingKey: std::fmt::Debug + Clone;
+
+    /// Signs a payload
+    ///
+    /// # Arguments
+    /// * `key` - Signing key
+    /// * `payload` - Data to sign
+    ///
+    /// # Returns
+    /// Result containing the signature or a SignError
+    fn sign(
+        key: &Self::SigningKey,
+        payload: &[u8],
+    ) -> impl std::future::Future<Output = Result<Vec<u8>, SignError>>;
+
+    /// Verifies the signature of a payload
+    ///
+    /// # Arguments
+    /// * `key` - Verification key
+    /// * `signature` - Signature to verify
+    /// * `payload` - Data that was signed
+    ///
+    /// # Returns
+    /// Result containing whether the signature is valid or a VerifyError
+    fn verify(
+        key: &Self::VerifyingKey,
+        signature: &[u8],
+        payload: &[u8],
+    ) -> impl std::future::Future<Output = Result<bool, VerifyError>>;
+}
+
+/// Bytes of a signature, encoded like the keys producing it, for the
+/// [`AsyncSigner`] and [`AsyncVerifier`] traits
+///
+/// Implements `signature::SignatureEncoding`, so it can be handed to the
+/// libraries built on the `signature` crate.
+///
+/// Requires the `signature` feature.
+#[cfg(feature = "signature")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Signature(Vec<u8>);
+
+#[cfg(feature = "signature")]
+impl Signature {
+    /// Wraps the bytes of a signature
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
+    /// Consumes the signature, returning its bytes
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+#[cfg(feature = "signature")]
+impl AsRef<[u8]> for Signature {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(feature = "signature")]
+impl TryFrom<&[u8]> for Signature {
+    type Error = ::signature::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self(value.to_vec()))
+    }
+}
+
+#[cfg(feature = "signature")]
+impl From<Signature> for Vec<u8> {
+    fn from(value: Signature) -> Self {
+        value.0
+    }
+}
+
+#[cfg(feature = "signature")]
+impl ::signature::SignatureEncoding for Signature {
+    type Repr = Vec<u8>;
+}
+
+/// Async analogue of `signature::Signer`
+///
+/// Requires the `signature` feature.
+///
+/// ```rust,no_run
+/// use browser_crypto::ecdsa::EcdsaPrivateKey;
+/// use browser_crypto::signature::{AsyncSigner, Signature};
+///
+/// async fn sign(key: &EcdsaPrivateKey) -> Result<Signature, signature::Error> {
+///     key.sign_async(b"header.payload").await
+/// }
+/// ```
+#[cfg(feature = "signature")]
+pub trait AsyncSigner<S> {
+    /// Signs a message, the async analogue of `signature::Signer::try_sign`
+    ///
+    /// # Errors
+    /// `signature::Error` if the message can't be signed, with the
+    /// [`SignError`] as source
+    fn sign_async(
+        &self,
+        msg: &[u8],
+    ) -> impl std::future::Future<Output = Result<S, ::signature::Error>>;
+}
+
+/// Async analogue of `signature::Verifier`
+///
+/// Requires the `signature` feature.
+#[cfg(feature = "signature")]
+pub trait AsyncVerifier<S> {
+    /// Verifies the signature of a message, the async analogue of
+    /// `signature::Verifier::verify`
+    ///
+    /// # Errors
+    /// `signature::Error` if the signature is not valid, or with the
+    /// [`VerifyError`] as source if it can't be verified
+    fn verify_async(
+        &self,
+        msg: &[u8],
+        signature: &S,
+    ) -> impl std::future::Future<Output = Result<(), ::signature::Error>>;
+}
+
+/// Converts the result of a signature to the conventions of the `signature`
+/// crate
+#[cfg(feature = "signature")]
+fn signed(result: Result<Vec<u8>, SignError>) -> Result<Signature, ::signature::Error> {
+    result
+        .map(Signature)
+        .map_err(::signature::Error::from_source)
+}
+
+/// Converts the result of a verification to the conventions of the
+/// `signature` crate, an invalid signature being an error
+#[cfg(feature = "signature")]
+fn verified(result: Result<bool, VerifyError>) -> Result<(), ::signature::Error> {
+    match result {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(::signature::Error::new()),
+        Err(error) => Err(::signature::Error::from_source(error)),
+    }
+}
+
+#[cfg(feature = "signature")]
+impl AsyncSigner<Signature> for crate::ecdsa::EcdsaPrivateKey {
+    async fn sign_async(&self, msg: &[u8]) -> Result<Signature, ::signature::Error> {
+        signed(self.sign(msg).await)
+    }
+}
+
+#[cfg(feature = "signature")]
+impl AsyncVerifier<Signature> for crate::ecdsa::EcdsaPublicKey {
+    async fn verify_async(
+        &self,
+        msg: &[u8],
+        signature: &Signature,
+    ) -> Result<(), ::signature::Error> {
+        verified(self.verify(signature.as_ref(), msg).await)
+    }
+}
+
+#[cfg(feature = "signature")]
+impl AsyncSigner<Signature> for crate::ed25519::Ed25519PrivateKey {
+    async fn sign_async(&self, msg: &[u8]) -> Result<Signature, ::signature::Error> {
+        signed(self.sign(msg).await)
+    }
+}
+
+#[cfg(feature = "signature")]
+impl AsyncVerifier<Signature> for crate::ed25519::Ed25519PublicKey {
+    async fn verify_async(
+        &self,
+        msg: &[u8],
+        signature: &Signature,
+    ) -> Result<(), ::signature::Error> {
+        verified(self.verify(signature.as_ref(), msg).await)
+    }
+}
+
+#[cfg(feature = "signature")]
+impl AsyncSigner<Signature> for crate::rsassa_pkcs1::RsassaPkcs1PrivateKey {
+    async fn sign_async(&self, msg: &[u8]) -> Result<Signature, ::signature::Error> {
+        signed(self.sign(msg).await)
+    }
+}
+
+#[cfg(feature = "signature")]
+impl AsyncVerifier<Signature> for crate::rsassa_pkcs1::RsassaPkcs1PublicKey {
+    async fn verify_async(
+        &self,
+        msg: &[u8],
+        signature: &Signature,
+    ) -> Result<(), ::signature::Error> {
+        verified(self.verify(signature.as_ref(), msg).await)
+    }
+}
+
+#[cfg(feature = "signature")]
+impl AsyncSigner<Signature> for crate::hmac::Hmac {
+    async fn sign_async(&self, msg: &[u8]) -> Result<Signature, ::signature::Error> {
+        signed(self.sign(msg).await)
+    }
+}
+
+#[cfg(feature = "signature")]
+impl AsyncVerifier<Signature> for crate::hmac::Hmac {
+    async fn verify_async(
+        &self,
+        msg: &[u8],
+        signature: &Signature,
+    ) -> Result<(), ::signature::Error> {
+        verified(self.verify(signature.as_ref(), msg).await)
+    }
+}
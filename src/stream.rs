@@ -0,0 +1,273 @@
+//! Chunked encryption of large payloads
+//!
+//! The Web Crypto API only encrypts payloads held entirely in memory. This
+//! module splits a payload in chunks, each one encrypted with AES-256-GCM,
+//! following the STREAM construction: the nonce of a chunk is made of a random
+//! prefix of 7 bytes shared by the stream, the index of the chunk on 4 bytes
+//! (big endian) and a flag set on the last chunk. Chunks can't be reordered,
+//! dropped or duplicated, and a truncated stream fails at the last chunk.
+//!
+//! The encrypted stream is made of the nonce prefix followed by the encrypted
+//! chunks, each one being 16 bytes longer than its clear chunk. All chunks but
+//! the last one should have the same size, like [`CHUNK_SIZE`], so the reader
+//! can split the encrypted stream.
+//!
+//! ```rust,no_run
+//! use browser_crypto::aes_gcm::Aes256Gcm;
+//! use browser_crypto::stream::{StreamDecryptor, StreamEncryptor};
+//!
+//! async fn encrypt() -> Result<(), Box<dyn std::error::Error>> {
+//!     let cipher = Aes256Gcm::from_key(&[0u8; 32]).await?;
+//!     let mut encryptor = StreamEncryptor::new(cipher.clone())?;
+//!     let first = encryptor.encrypt_chunk(b"Hello ").await?;
+//!     let last = encryptor.finish(b"World!").await?;
+//!
+//!     let mut decryptor = StreamDecryptor::new(cipher, encryptor.prefix())?;
+//!     assert_eq!(decryptor.decrypt_chunk(&first).await?, b"Hello ");
+//!     assert_eq!(decryptor.finish(&last).await?, b"World!");
+//!     Ok(())
+//! }
+//! ```
+
+use wasm_bindgen::JsValue;
+
+use crate::aes_gcm::Aes256Gcm;
+use crate::algorithm::{Algorithm, DecryptionError, EncryptionError, Nonce, NonceError};
+use crate::ErrorCode;
+
+/// Size of the nonce prefix, shared by all the chunks of a stream
+pub const PREFIX_SIZE: usize = 7;
+
+/// Recommended size of the clear chunks, 64 KiB
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Size of the authentication tag added to each chunk
+pub const TAG_SIZE: usize = 16;
+
+/// Errors that can occur when encrypting or decrypting a stream.
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum StreamError {
+    /// Indicates that the nonce prefix doesn't have [`PREFIX_SIZE`] bytes.
+    #[error("the nonce prefix must have {PREFIX_SIZE} bytes")]
+    InvalidPrefix,
+    /// Indicates that the stream has more chunks than the counter can
+    /// represent.
+    #[error("too many chunks in the stream")]
+    TooManyChunks,
+    /// Indicates that the last chunk has already been processed.
+    #[error("the stream is already finished")]
+    Finished,
+    /// Indicates that a Web Stream chunk is not an `ArrayBuffer` or an
+    /// `ArrayBufferView`.
+    #[error("chunks must be ArrayBuffer or ArrayBufferView")]
+    InvalidChunk,
+    /// Indicates that the operation has been aborted by its `AbortSignal`.
+    #[error("the operation has been aborted")]
+    Aborted,
+    /// Indicates that a Web Stream is already locked by a reader.
+    #[error("the stream is locked")]
+    Locked,
+    /// Indicates that the nonce prefix couldn't be generated.
+    #[error(transparent)]
+    Nonce(#[from] NonceError),
+    /// Indicates that a chunk couldn't be encrypted.
+    #[error(transparent)]
+    Encryption(#[from] EncryptionError),
+    /// Indicates that a chunk couldn't be decrypted, like when it has been
+    /// altered, reordered or when the stream is truncated.
+    #[error(transparent)]
+    Decryption(#[from] DecryptionError),
+    /// A wrapper for other types of errors that may occur while creating the
+    /// Web Streams or reading a Blob.
+    #[error(transparent)]
+    Generic(#[from] crate::Error),
+}
+
+impl ErrorCode for StreamError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidPrefix => "stream.invalid_prefix",
+            Self::TooManyChunks => "stream.too_many_chunks",
+            Self::Finished => "stream.finished",
+            Self::InvalidChunk => "stream.invalid_chunk",
+            Self::Aborted => "stream.aborted",
+            Self::Locked => "stream.locked",
+            Self::Nonce(inner) => inner.code(),
+            Self::Encryption(inner) => inner.code(),
+            Self::Decryption(inner) => inner.code(),
+            Self::Generic(inner) => inner.code(),
+        }
+    }
+}
+
+impl From<StreamError> for JsValue {
+    fn from(value: StreamError) -> Self {
+        crate::js_error("StreamError", &value)
+    }
+}
+
+impl From<JsValue> for StreamError {
+    fn from(value: JsValue) -> Self {
+        Self::Generic(crate::Error::from(value))
+    }
+}
+
+/// Nonces of the chunks of a stream
+///
+/// Not `Clone`, two copies of the sequence would hand out the same nonces.
+#[derive(Debug)]
+struct NonceSequence {
+    prefix: [u8; PREFIX_SIZE],
+    counter: Option<u32>,
+}
+
+impl NonceSequence {
+    fn new(prefix: [u8; PREFIX_SIZE]) -> Self {
+        Self {
+            prefix,
+            counter: Some(0),
+        }
+    }
+
+    /// Nonce of the next chunk, the sequence being finished after the last
+    /// chunk
+    fn next(&mut self, last: bool) -> Result<Nonce<Aes256Gcm>, StreamError> {
+        let counter = self.counter.ok_or(StreamError::Finished)?;
+        let mut nonce = [0u8; 12];
+        nonce[..PREFIX_SIZE].copy_from_slice(&self.prefix);
+        nonce[PREFIX_SIZE..11].copy_from_slice(&counter.to_be_bytes());
+        nonce[11] = last as u8;
+        self.counter = if last {
+            None
+        } else {
+            Some(counter.checked_add(1).ok_or(StreamError::TooManyChunks)?)
+        };
+        Ok(Nonce::from_slice(&nonce)?)
+    }
+}
+
+/// Encrypts a stream, chunk by chunk
+///
+/// Not `Clone`, a copy would encrypt the next chunks with the same nonces.
+#[derive(Debug)]
+pub struct StreamEncryptor {
+    cipher: Aes256Gcm,
+    nonces: NonceSequence,
+}
+
+impl StreamEncryptor {
+    /// Starts a stream with a random nonce prefix
+    ///
+    /// # Errors
+    /// - `StreamError::Nonce` if the random prefix couldn't be generated
+    pub fn new(cipher: Aes256Gcm) -> Result<Self, StreamError> {
+        let prefix = crate::random::random_bytes(PREFIX_SIZE)?;
+        let prefix = prefix.try_into().map_err(|_| StreamError::InvalidPrefix)?;
+        Ok(Self {
+            cipher,
+            nonces: NonceSequence::new(prefix),
+        })
+    }
+
+    /// Nonce prefix of the stream, to write before the encrypted chunks
+    pub fn prefix(&self) -> &[u8] {
+        &self.nonces.prefix
+    }
+
+    /// Encrypts a chunk that is not the last one
+    ///
+    /// # Arguments
+    /// * `chunk` - Clear chunk, usually of [`CHUNK_SIZE`] bytes
+    ///
+    /// # Returns
+    /// Result containing the encrypted chunk, [`TAG_SIZE`] bytes longer, or a
+    /// StreamError
+    ///
+    /// # Errors
+    /// - `StreamError::Finished` if the last chunk was already encrypted
+    /// - `StreamError::TooManyChunks` if the stream has more than 2^32 chunks
+    pub async fn encrypt_chunk(&mut self, chunk: &[u8]) -> Result<Vec<u8>, StreamError> {
+        let nonce = self.nonces.next(false)?;
+        Ok(self.cipher.encrypt(&nonce, chunk).await?)
+    }
+
+    /// Encrypts the last chunk, which can be empty, finishing the stream
+    ///
+    /// # Errors
+    /// - `StreamError::Finished` if the last chunk was already encrypted
+    pub async fn finish(&mut self, chunk: &[u8]) -> Result<Vec<u8>, StreamError> {
+        let nonce = self.nonces.next(true)?;
+        Ok(self.cipher.encrypt(&nonce, chunk).await?)
+    }
+
+    /// Whether the last chunk has been encrypted
+    pub fn is_finished(&self) -> bool {
+        self.nonces.counter.is_none()
+    }
+}
+
+/// Decrypts a stream, chunk by chunk
+#[derive(Debug)]
+pub struct StreamDecryptor {
+    cipher: Aes256Gcm,
+    nonces: NonceSequence,
+}
+
+/// Decrypting the same chunks twice doesn't reuse any nonce, unlike
+/// encrypting them.
+impl Clone for StreamDecryptor {
+    fn clone(&self) -> Self {
+        Self {
+            cipher: self.cipher.clone(),
+            nonces: NonceSequence {
+                prefix: self.nonces.prefix,
+                counter: self.nonces.counter,
+            },
+        }
+    }
+}
+
+impl StreamDecryptor {
+    /// Starts decrypting a stream
+    ///
+    /// # Arguments
+    /// * `cipher` - Cipher used to encrypt the stream
+    /// * `prefix` - Nonce prefix written before the encrypted chunks
+    ///
+    /// # Errors
+    /// - `StreamError::InvalidPrefix` if the prefix doesn't have
+    ///   [`PREFIX_SIZE`] bytes
+    pub fn new(cipher: Aes256Gcm, prefix: &[u8]) -> Result<Self, StreamError> {
+        let prefix = prefix.try_into().map_err(|_| StreamError::InvalidPrefix)?;
+        Ok(Self {
+            cipher,
+            nonces: NonceSequence::new(prefix),
+        })
+    }
+
+    /// Decrypts a chunk that is not the last one
+    ///
+    /// # Errors
+    /// - `StreamError::Decryption` if the chunk has been altered or is not at
+    ///   its position, including the last chunk
+    pub async fn decrypt_chunk(&mut self, chunk: &[u8]) -> Result<Vec<u8>, StreamError> {
+        let nonce = self.nonces.next(false)?;
+        Ok(self.cipher.decrypt(&nonce, chunk).await?)
+    }
+
+    /// Decrypts the last chunk, finishing the stream
+    ///
+    /// # Errors
+    /// - `StreamError::Decryption` if the chunk has been altered or is not the
+    ///   last one, like when the stream is truncated
+    pub async fn finish(&mut self, chunk: &[u8]) -> Result<Vec<u8>, StreamError> {
+        let nonce = self.nonces.next(true)?;
+        Ok(self.cipher.decrypt(&nonce, chunk).await?)
+    }
+
+    /// Whether the last chunk has been decrypted
+    pub fn is_finished(&self) -> bool {
+        self.nonces.counter.is_none()
+    }
+}
@@ -0,0 +1,187 @@
+//! Helpers for the tests of the crates using this one
+//!
+//! The tests of an application encrypting with this crate usually need the
+//! same scaffolding as the crate's own tests: keys and nonces that don't
+//! change between runs, ciphers producing the same output for the same input
+//! and assertions that a payload survives a round trip or that tampering is
+//! detected. This module ships them, so they don't have to be copied around.
+//!
+//! The keys and nonces are predictable by design, they must never leave the
+//! tests. The helpers panic on failure, like assertions.
+//!
+//! Requires the `test-util` feature, usually enabled in `[dev-dependencies]`.
+//!
+//! ```rust,no_run
+//! use browser_crypto::aes_gcm::Aes256Gcm;
+//! use browser_crypto::test_util;
+//!
+//! async fn should_store_documents() {
+//!     let cipher = test_util::aes_gcm::<256>(1).await;
+//!     let sealed = browser_crypto::assert_round_trip!(cipher, b"document");
+//!     browser_crypto::assert_tampering_detected!(cipher, sealed);
+//!
+//!     // the same bytes for every run, so they can be compared with a fixture
+//!     let fixed = test_util::FixedNonce::new(cipher, test_util::nonce::<Aes256Gcm>(0));
+//!     assert_eq!(
+//!         fixed.seal(b"document").await.unwrap(),
+//!         fixed.seal(b"document").await.unwrap()
+//!     );
+//! }
+//! ```
+
+use crate::aes_gcm::AesGcm;
+use crate::algorithm::{Algorithm, Key, Nonce};
+
+/// Bytes counting up from a seed, wrapping around after 255
+fn counting_bytes(seed: u8, len: u32) -> Vec<u8> {
+    (0..len)
+        .map(|index| seed.wrapping_add(index as u8))
+        .collect()
+}
+
+/// Deterministic raw key of an algorithm, its bytes counting up from `seed`
+///
+/// # Arguments
+/// * `seed` - First byte of the key, different seeds giving different keys
+pub fn key<A: Algorithm>(seed: u8) -> Key<A> {
+    Key::from_slice(&counting_bytes(seed, A::KEY_SIZE))
+        .expect("the key has the size of the algorithm")
+}
+
+/// Deterministic nonce of an algorithm, its bytes counting up from `seed`
+///
+/// # Arguments
+/// * `seed` - First byte of the nonce, different seeds giving different nonces
+pub fn nonce<A: Algorithm>(seed: u8) -> Nonce<A> {
+    Nonce::from_slice(&counting_bytes(seed, A::NONCE_SIZE))
+        .expect("the nonce has the size of the algorithm")
+}
+
+/// Imports the deterministic AES-GCM key of a seed, see [`key`]
+///
+/// # Panics
+/// If the key can't be imported
+pub async fn aes_gcm<const KEY_BITS: usize>(seed: u8) -> AesGcm<KEY_BITS> {
+    AesGcm::from_key(&key::<AesGcm<KEY_BITS>>(seed))
+        .await
+        .expect("unable to import the test key")
+}
+
+/// Cipher encrypting with the same nonce every time
+///
+/// Reusing a nonce breaks the security of most algorithms, it's only meant to
+/// get the same output for the same input, like to compare it with a fixture.
+/// The sealed payloads have the layout of [`Algorithm::seal`], so they can be
+/// opened by the wrapped cipher.
+#[derive(Debug)]
+pub struct FixedNonce<A> {
+    cipher: A,
+    nonce: Nonce<A>,
+}
+
+impl<A: Algorithm> FixedNonce<A> {
+    /// Wraps a cipher with the nonce it will always use
+    pub fn new(cipher: A, nonce: Nonce<A>) -> Self {
+        Self { cipher, nonce }
+    }
+
+    /// Wrapped cipher
+    pub fn cipher(&self) -> &A {
+        &self.cipher
+    }
+
+    /// Nonce used by every encryption
+    pub fn nonce(&self) -> &Nonce<A> {
+        &self.nonce
+    }
+
+    /// Consumes the wrapper, returning the cipher
+    pub fn into_inner(self) -> A {
+        self.cipher
+    }
+
+    /// Encrypts data with the fixed nonce
+    ///
+    /// # Errors
+    /// Same as [`Algorithm::encrypt`]
+    pub async fn encrypt(&self, payload: &[u8]) -> Result<Vec<u8>, A::EncryptError> {
+        self.cipher.encrypt(&self.nonce, payload).await
+    }
+
+    /// Encrypts data with the fixed nonce, prepended to the encrypted data
+    /// like [`Algorithm::seal`]
+    ///
+    /// # Errors
+    /// Same as [`Algorithm::encrypt`]
+    pub async fn seal(&self, payload: &[u8]) -> Result<Vec<u8>, A::EncryptError> {
+        let mut output = self.nonce.to_vec();
+        self.cipher
+            .encrypt_into(&self.nonce, payload, &mut output)
+            .await?;
+        Ok(output)
+    }
+
+    /// Decrypts data produced by [`FixedNonce::seal`] or [`Algorithm::seal`]
+    ///
+    /// # Errors
+    /// Same as [`Algorithm::open`]
+    pub async fn open(&self, sealed: &[u8]) -> Result<Vec<u8>, A::DecryptError> {
+        self.cipher.open(sealed).await
+    }
+}
+
+/// Asserts that a payload sealed by a cipher opens to the same payload,
+/// evaluating to the sealed bytes
+///
+/// Must be used in an async function, like a `wasm_bindgen_test`.
+///
+/// ```rust,no_run
+/// # async fn test(cipher: browser_crypto::aes_gcm::Aes256Gcm) {
+/// let sealed = browser_crypto::assert_round_trip!(cipher, b"hello");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! assert_round_trip {
+    ($cipher:expr, $payload:expr $(,)?) => {{
+        let cipher = &$cipher;
+        let payload = &$payload;
+        let payload: &[u8] = ::core::convert::AsRef::<[u8]>::as_ref(payload);
+        let sealed = $crate::algorithm::Algorithm::seal(cipher, payload)
+            .await
+            .expect("unable to seal the payload");
+        let opened = $crate::algorithm::Algorithm::open(cipher, &sealed)
+            .await
+            .expect("unable to open the sealed payload");
+        assert_eq!(opened, payload, "the payload changed after a round trip");
+        sealed
+    }};
+}
+
+/// Asserts that a cipher refuses to open a sealed payload once altered, each
+/// byte being flipped in turn
+///
+/// Must be used in an async function, like a `wasm_bindgen_test`.
+///
+/// ```rust,no_run
+/// # async fn test(cipher: browser_crypto::aes_gcm::Aes256Gcm, sealed: Vec<u8>) {
+/// browser_crypto::assert_tampering_detected!(cipher, sealed);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! assert_tampering_detected {
+    ($cipher:expr, $sealed:expr $(,)?) => {{
+        let cipher = &$cipher;
+        let sealed = &$sealed;
+        let sealed: &[u8] = ::core::convert::AsRef::<[u8]>::as_ref(sealed);
+        for index in 0..sealed.len() {
+            let mut altered = sealed.to_vec();
+            altered[index] ^= 0x01;
+            assert!(
+                $crate::algorithm::Algorithm::open(cipher, &altered)
+                    .await
+                    .is_err(),
+                "altering the byte {index} was not detected"
+            );
+        }
+    }};
+}
@@ -0,0 +1,38 @@
+//! Helpers for the code handling secrets
+//!
+//! Comparing a MAC, a token or a fingerprint with `==` returns as soon as a
+//! byte differs, so the time of the comparison tells how many leading bytes
+//! were right. [`ct_eq`] always goes through all the bytes.
+//!
+//! ```rust
+//! use browser_crypto::util::ct_eq;
+//!
+//! fn check_token(expected: &[u8], received: &[u8]) -> bool {
+//!     ct_eq(expected, received)
+//! }
+//! ```
+
+/// Compares two byte slices in constant time
+///
+/// The time only depends on the length of the slices, which is not considered
+/// secret: slices of different lengths are different, without comparing their
+/// bytes.
+///
+/// # Arguments
+/// * `a` - First slice
+/// * `b` - Second slice
+///
+/// # Returns
+/// Whether the slices have the same bytes
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    // the accumulator goes through black_box at every byte, so the compiler
+    // can't tell that it stays non zero once a byte differs and stop the loop
+    // there; black_box is only a hint, not a guarantee
+    let difference = a.iter().zip(b.iter()).fold(0u8, |acc, (left, right)| {
+        std::hint::black_box(acc | (left ^ right))
+    });
+    difference == 0
+}
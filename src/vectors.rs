@@ -0,0 +1,456 @@
+//! Test vectors of the AES-GCM implementation
+//!
+//! The Web Crypto API is implemented by each browser, and the crate can't
+//! check every version of every browser an application runs on. This module
+//! runs known answer tests against the implementation of the current
+//! platform, through [`crate::aes_gcm::AesGcm`], so an application can
+//! validate its targets, like in a `wasm_bindgen_test` run by each browser of
+//! its CI.
+//!
+//! [`embedded_vectors`] holds the test cases of the GCM specification, by
+//! McGrew and Viega, and altered copies of them that must be rejected. More
+//! vectors can be parsed with [`parse_cavp`], from the response files of the
+//! NIST Cryptographic Algorithm Validation Program like
+//! `gcmEncryptExtIV256.rsp` or `gcmDecrypt256.rsp`, and with
+//! [`parse_wycheproof`], from the `aes_gcm_test.json` file of Project
+//! Wycheproof. Any other source can build the [`AeadVector`]s itself.
+//!
+//! Only the vectors with a 96-bit nonce and a 128-bit tag, the ones supported
+//! by [`crate::aes_gcm::AesGcm`], are run, the others being counted as
+//! skipped.
+//!
+//! Requires the `test-vectors` feature.
+//!
+//! ```rust,no_run
+//! use browser_crypto::vectors;
+//!
+//! async fn should_match_the_test_vectors(wycheproof: &str) {
+//!     let report = vectors::run_aes_gcm(&vectors::embedded_vectors()).await;
+//!     report.assert_success();
+//!
+//!     let custom = vectors::parse_wycheproof(wycheproof).unwrap();
+//!     vectors::run_aes_gcm(&custom).await.assert_success();
+//! }
+//! ```
+
+use wasm_bindgen::JsValue;
+
+use crate::aes_gcm::AesGcm;
+use crate::algorithm::Nonce;
+use crate::encoding::EncodingError;
+use crate::ErrorCode;
+
+/// Test cases of the GCM specification, in the layout of the CAVP files
+const GCM_SPEC: &str = include_str!("../vectors/aes-gcm-spec.rsp");
+
+/// Errors that can occur when parsing test vectors.
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum VectorError {
+    /// Indicates that a CAVP file doesn't have the expected layout.
+    ///
+    /// # Fields
+    /// * `line` - The line of the error, starting at 1
+    /// * `reason` - What was expected
+    #[error("malformed CAVP file at line {line}: {reason}")]
+    Malformed { line: usize, reason: &'static str },
+    /// Indicates that a Wycheproof file is not valid JSON or doesn't have the
+    /// expected structure.
+    ///
+    /// # Fields
+    /// * `0` - The parsing error
+    #[error("invalid Wycheproof file: {0}")]
+    InvalidJson(String),
+    /// Indicates that a Wycheproof file holds the vectors of another
+    /// algorithm.
+    ///
+    /// # Fields
+    /// * `0` - The name of the algorithm
+    #[error("unsupported algorithm {0}")]
+    UnsupportedAlgorithm(String),
+    /// Indicates that a member of a vector is not hexadecimal.
+    ///
+    /// # Fields
+    /// * `id` - The identifier of the vector
+    /// * `field` - The name of the member
+    /// * `source` - The decoding error
+    #[error("invalid {field} in vector {id}: {source}")]
+    InvalidEncoding {
+        id: String,
+        field: &'static str,
+        #[source]
+        source: EncodingError,
+    },
+}
+
+impl ErrorCode for VectorError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Malformed { .. } => "vector.malformed",
+            Self::InvalidJson(..) => "vector.invalid_json",
+            Self::UnsupportedAlgorithm(..) => "vector.unsupported_algorithm",
+            Self::InvalidEncoding { .. } => "vector.invalid_encoding",
+        }
+    }
+}
+
+impl From<VectorError> for JsValue {
+    fn from(value: VectorError) -> Self {
+        crate::js_error("VectorError", &value)
+    }
+}
+
+/// Expected outcome of a test vector
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expected {
+    /// The ciphertext must be produced and accepted
+    Valid,
+    /// The ciphertext must be rejected
+    Invalid,
+    /// The implementation may accept or reject the ciphertext, like for the
+    /// legal but weak parameters of Wycheproof
+    Acceptable,
+}
+
+/// Test vector of an authenticated encryption
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AeadVector {
+    /// Identifier of the vector, to find it in its source
+    pub id: String,
+    /// Raw key
+    pub key: Vec<u8>,
+    /// Nonce, or initialization vector
+    pub nonce: Vec<u8>,
+    /// Additional authenticated data
+    pub aad: Vec<u8>,
+    /// Plaintext, empty for the invalid vectors of the CAVP files
+    pub plaintext: Vec<u8>,
+    /// Ciphertext, without the tag
+    pub ciphertext: Vec<u8>,
+    /// Authentication tag
+    pub tag: Vec<u8>,
+    /// Expected outcome
+    pub expected: Expected,
+}
+
+/// Test vectors embedded in the crate, see the module documentation
+pub fn embedded_vectors() -> Vec<AeadVector> {
+    parse_cavp(GCM_SPEC).expect("the embedded vectors are valid")
+}
+
+fn decode(id: &str, field: &'static str, value: &str) -> Result<Vec<u8>, VectorError> {
+    crate::encoding::hex_decode(value).map_err(|source| VectorError::InvalidEncoding {
+        id: id.to_string(),
+        field,
+        source,
+    })
+}
+
+/// Vector of a CAVP file being parsed
+#[derive(Default)]
+struct CavpEntry {
+    line: usize,
+    count: String,
+    fields: Vec<(&'static str, String)>,
+    fail: bool,
+}
+
+impl CavpEntry {
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn into_vector(self) -> Result<AeadVector, VectorError> {
+        let id = format!("Count = {} (line {})", self.count, self.line);
+        let required = |name: &'static str, reason: &'static str| {
+            self.field(name).ok_or(VectorError::Malformed {
+                line: self.line,
+                reason,
+            })
+        };
+        let key = decode(&id, "Key", required("Key", "missing Key")?)?;
+        let nonce = decode(&id, "IV", required("IV", "missing IV")?)?;
+        let ciphertext = decode(&id, "CT", required("CT", "missing CT")?)?;
+        let tag = decode(&id, "Tag", required("Tag", "missing Tag")?)?;
+        let aad = decode(&id, "AAD", self.field("AAD").unwrap_or_default())?;
+        let (plaintext, expected) = match (self.field("PT"), self.fail) {
+            (Some(plaintext), false) => (decode(&id, "PT", plaintext)?, Expected::Valid),
+            (None, true) => (Vec::new(), Expected::Invalid),
+            _ => {
+                return Err(VectorError::Malformed {
+                    line: self.line,
+                    reason: "expected either PT or FAIL",
+                })
+            }
+        };
+        Ok(AeadVector {
+            id,
+            key,
+            nonce,
+            aad,
+            plaintext,
+            ciphertext,
+            tag,
+            expected,
+        })
+    }
+}
+
+/// Parses the AES-GCM vectors of a NIST CAVP response file
+///
+/// Both the encryption files, giving the plaintext, and the decryption files,
+/// where `FAIL` marks the vectors to reject, are supported. The comments and
+/// the bracketed headers are ignored, the sizes being read from the values.
+///
+/// # Arguments
+/// * `input` - Content of the `.rsp` file
+///
+/// # Errors
+/// - `VectorError::Malformed` if a line or a vector can't be read
+/// - `VectorError::InvalidEncoding` if a value is not hexadecimal
+pub fn parse_cavp(input: &str) -> Result<Vec<AeadVector>, VectorError> {
+    let mut vectors = Vec::new();
+    let mut current: Option<CavpEntry> = None;
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('[') {
+            continue;
+        }
+        if line == "FAIL" {
+            let entry = current.as_mut().ok_or(VectorError::Malformed {
+                line: index + 1,
+                reason: "FAIL outside of a vector",
+            })?;
+            entry.fail = true;
+            continue;
+        }
+        let (name, value) = line.split_once('=').ok_or(VectorError::Malformed {
+            line: index + 1,
+            reason: "expected a name and a value",
+        })?;
+        let (name, value) = (name.trim(), value.trim());
+        if name == "Count" {
+            if let Some(entry) = current.take() {
+                vectors.push(entry.into_vector()?);
+            }
+            current = Some(CavpEntry {
+                line: index + 1,
+                count: value.to_string(),
+                ..Default::default()
+            });
+            continue;
+        }
+        let field = match name {
+            "Key" => "Key",
+            "IV" => "IV",
+            "PT" => "PT",
+            "AAD" => "AAD",
+            "CT" => "CT",
+            "Tag" => "Tag",
+            _ => continue,
+        };
+        let entry = current.as_mut().ok_or(VectorError::Malformed {
+            line: index + 1,
+            reason: "value outside of a vector",
+        })?;
+        entry.fields.push((field, value.to_string()));
+    }
+    if let Some(entry) = current {
+        vectors.push(entry.into_vector()?);
+    }
+    Ok(vectors)
+}
+
+#[derive(serde::Deserialize)]
+struct WycheproofFile {
+    algorithm: String,
+    #[serde(rename = "testGroups")]
+    test_groups: Vec<WycheproofGroup>,
+}
+
+#[derive(serde::Deserialize)]
+struct WycheproofGroup {
+    tests: Vec<WycheproofTest>,
+}
+
+#[derive(serde::Deserialize)]
+struct WycheproofTest {
+    #[serde(rename = "tcId")]
+    tc_id: u64,
+    #[serde(default)]
+    comment: String,
+    key: String,
+    iv: String,
+    #[serde(default)]
+    aad: String,
+    msg: String,
+    ct: String,
+    tag: String,
+    result: String,
+}
+
+/// Parses the vectors of a Project Wycheproof AES-GCM file
+///
+/// # Arguments
+/// * `input` - Content of the JSON file, like `aes_gcm_test.json`
+///
+/// # Errors
+/// - `VectorError::InvalidJson` if the file is not valid JSON or doesn't have
+///   the structure of the Wycheproof files
+/// - `VectorError::UnsupportedAlgorithm` if the file is not for AES-GCM
+/// - `VectorError::InvalidEncoding` if a value is not hexadecimal
+pub fn parse_wycheproof(input: &str) -> Result<Vec<AeadVector>, VectorError> {
+    let file: WycheproofFile =
+        serde_json::from_str(input).map_err(|err| VectorError::InvalidJson(err.to_string()))?;
+    if file.algorithm != "AES-GCM" {
+        return Err(VectorError::UnsupportedAlgorithm(file.algorithm));
+    }
+    file.test_groups
+        .into_iter()
+        .flat_map(|group| group.tests)
+        .map(|test| {
+            let id = match test.comment.as_str() {
+                "" => format!("tcId {}", test.tc_id),
+                comment => format!("tcId {} ({comment})", test.tc_id),
+            };
+            let expected = match test.result.as_str() {
+                "valid" => Expected::Valid,
+                "acceptable" => Expected::Acceptable,
+                _ => Expected::Invalid,
+            };
+            Ok(AeadVector {
+                key: decode(&id, "key", &test.key)?,
+                nonce: decode(&id, "iv", &test.iv)?,
+                aad: decode(&id, "aad", &test.aad)?,
+                plaintext: decode(&id, "msg", &test.msg)?,
+                ciphertext: decode(&id, "ct", &test.ct)?,
+                tag: decode(&id, "tag", &test.tag)?,
+                id,
+                expected,
+            })
+        })
+        .collect()
+}
+
+/// Vector the implementation didn't handle as expected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VectorFailure {
+    /// Identifier of the vector
+    pub id: String,
+    /// What went wrong
+    pub reason: String,
+}
+
+impl std::fmt::Display for VectorFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.id, self.reason)
+    }
+}
+
+/// Outcome of a run of test vectors
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    /// Number of vectors handled as expected
+    pub passed: usize,
+    /// Number of vectors with parameters the cipher doesn't support
+    pub skipped: usize,
+    /// Vectors that weren't handled as expected
+    pub failures: Vec<VectorFailure>,
+}
+
+impl Report {
+    /// Whether every vector run was handled as expected
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Asserts that every vector run was handled as expected
+    ///
+    /// # Panics
+    /// If any vector failed, listing them
+    pub fn assert_success(&self) {
+        if self.is_success() {
+            return;
+        }
+        let failures = self
+            .failures
+            .iter()
+            .map(VectorFailure::to_string)
+            .collect::<Vec<_>>();
+        panic!(
+            "{} test vectors failed:\n{}",
+            failures.len(),
+            failures.join("\n")
+        );
+    }
+}
+
+/// Runs AES-GCM test vectors with the implementation of the current platform
+///
+/// The valid vectors must decrypt to their plaintext and encrypt to their
+/// ciphertext, the invalid ones must be rejected, and the acceptable ones
+/// may be either.
+///
+/// # Arguments
+/// * `vectors` - Vectors to run, in order
+///
+/// # Returns
+/// The report of the run
+pub async fn run_aes_gcm(vectors: &[AeadVector]) -> Report {
+    let mut report = Report::default();
+    for vector in vectors {
+        // the only sizes supported by AesGcm
+        if vector.nonce.len() != 12 || vector.tag.len() != 16 {
+            report.skipped += 1;
+            continue;
+        }
+        let result = match vector.key.len() {
+            16 => check::<128>(vector).await,
+            24 => check::<192>(vector).await,
+            32 => check::<256>(vector).await,
+            _ if vector.expected == Expected::Valid => Err("invalid key size".to_string()),
+            _ => Ok(()),
+        };
+        match result {
+            Ok(()) => report.passed += 1,
+            Err(reason) => report.failures.push(VectorFailure {
+                id: vector.id.clone(),
+                reason,
+            }),
+        }
+    }
+    report
+}
+
+async fn check<const KEY_BITS: usize>(vector: &AeadVector) -> Result<(), String> {
+    let cipher = match AesGcm::<KEY_BITS>::from_key(&vector.key).await {
+        Ok(cipher) => cipher,
+        Err(_) if vector.expected != Expected::Valid => return Ok(()),
+        Err(err) => return Err(format!("unable to import the key: {err}")),
+    };
+    let nonce = Nonce::<AesGcm<KEY_BITS>>::from_slice(&vector.nonce)
+        .map_err(|err| format!("invalid nonce: {err}"))?;
+    let mut sealed = vector.ciphertext.clone();
+    sealed.extend_from_slice(&vector.tag);
+
+    let decrypted = cipher.decrypt_with_aad(&nonce, &vector.aad, &sealed).await;
+    match (vector.expected, decrypted) {
+        (Expected::Valid, Ok(plaintext)) if plaintext == vector.plaintext => {}
+        (Expected::Valid, Ok(_)) => return Err("decrypted to another plaintext".into()),
+        (Expected::Valid, Err(err)) => return Err(format!("unable to decrypt: {err}")),
+        (Expected::Invalid, Ok(_)) => return Err("accepted an invalid ciphertext".into()),
+        (Expected::Invalid, Err(_)) | (Expected::Acceptable, _) => return Ok(()),
+    }
+
+    let encrypted = cipher
+        .encrypt_with_aad(&nonce, &vector.aad, &vector.plaintext)
+        .await
+        .map_err(|err| format!("unable to encrypt: {err}"))?;
+    if encrypted != sealed {
+        return Err("encrypted to another ciphertext".into());
+    }
+    Ok(())
+}
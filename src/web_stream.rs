@@ -0,0 +1,580 @@
+//! Web Streams integration of the chunked encryption
+//!
+//! [`encrypt_transform`] and [`decrypt_transform`] expose the chunked
+//! encryption of [`crate::stream`] as `TransformStream`s, so a `fetch` response
+//! body or the stream of a `File` can be piped through them in JavaScript.
+//!
+//! The written chunks can be any `ArrayBuffer` or `ArrayBufferView`, of any
+//! size: they're split in chunks of [`CHUNK_SIZE`] bytes. The encrypted stream
+//! starts with the nonce prefix, so the decrypting stream only needs the key.
+//!
+//! An encrypted `ReadableStream`, like a large media being downloaded, can be
+//! decrypted progressively with [`decrypt_readable`], into another
+//! `ReadableStream`, or with [`DecryptStream`], a Rust `Stream` of the
+//! decrypted chunks. Both can be aborted with an `AbortSignal`, like the
+//! transforms when piped with the `signal` option of `pipeThrough`.
+//!
+//! Requires the `streams` feature.
+//!
+//! ```rust,no_run
+//! use browser_crypto::aes_gcm::Aes256Gcm;
+//! use browser_crypto::web_stream::encrypt_transform;
+//!
+//! async fn encrypting_stream() -> Result<web_sys::TransformStream, Box<dyn std::error::Error>> {
+//!     let cipher = Aes256Gcm::from_key(&[0u8; 32]).await?;
+//!     // to use with `response.body.pipeThrough(stream)` in JavaScript
+//!     Ok(encrypt_transform(cipher)?)
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+use js_sys::{Promise, Uint8Array};
+use wasm_bindgen::prelude::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    AbortSignal, ReadableStream, ReadableStreamDefaultReader, ReadableWritablePair,
+    StreamPipeOptions, TransformStream, TransformStreamDefaultController,
+};
+
+use crate::aes_gcm::Aes256Gcm;
+use crate::stream::{
+    StreamDecryptor, StreamEncryptor, StreamError, CHUNK_SIZE, PREFIX_SIZE, TAG_SIZE,
+};
+
+/// Reads the bytes of a chunk written to a stream
+///
+/// Returns `None` if the chunk is not an `ArrayBuffer`, a `SharedArrayBuffer`
+/// or an `ArrayBufferView`.
+pub(crate) fn chunk_bytes(chunk: &JsValue) -> Option<Vec<u8>> {
+    if chunk.is_instance_of::<js_sys::ArrayBuffer>()
+        || chunk.is_instance_of::<js_sys::SharedArrayBuffer>()
+    {
+        return Some(crate::array_to_vec(&Uint8Array::new(chunk)));
+    }
+    if !js_sys::ArrayBuffer::is_view(chunk) {
+        return None;
+    }
+    // buffer, byteOffset and byteLength are shared by all the views
+    let view = chunk.unchecked_ref::<js_sys::DataView>();
+    let bytes = Uint8Array::new_with_byte_offset_and_length(
+        &view.buffer(),
+        view.byte_offset() as u32,
+        view.byte_length() as u32,
+    );
+    Some(crate::array_to_vec(&bytes))
+}
+
+/// Converts an error into a JavaScript error, rejecting the stream
+pub(crate) fn stream_error(err: StreamError) -> JsValue {
+    js_sys::Error::new(&err.to_string()).into()
+}
+
+/// Chunked processing of a stream
+pub(crate) trait Transform: 'static {
+    /// Processes some input data, returning the completed output chunks
+    async fn push(&mut self, data: &[u8]) -> Result<Vec<Vec<u8>>, StreamError>;
+
+    /// Processes the remaining data at the end of the input
+    async fn flush(&mut self) -> Result<Vec<Vec<u8>>, StreamError>;
+}
+
+/// Encrypts the input, starting with the nonce prefix
+pub(crate) struct EncryptTransform {
+    encryptor: StreamEncryptor,
+    buffer: Vec<u8>,
+    started: bool,
+}
+
+impl EncryptTransform {
+    pub(crate) fn new(cipher: Aes256Gcm) -> Result<Self, StreamError> {
+        Ok(Self {
+            encryptor: StreamEncryptor::new(cipher)?,
+            buffer: Vec::new(),
+            started: false,
+        })
+    }
+
+    fn start(&mut self, output: &mut Vec<Vec<u8>>) {
+        if !self.started {
+            self.started = true;
+            output.push(self.encryptor.prefix().to_vec());
+        }
+    }
+}
+
+impl Transform for EncryptTransform {
+    async fn push(&mut self, data: &[u8]) -> Result<Vec<Vec<u8>>, StreamError> {
+        let mut output = Vec::new();
+        self.start(&mut output);
+        self.buffer.extend_from_slice(data);
+        // the last chunk is kept until the end of the input
+        while self.buffer.len() > CHUNK_SIZE {
+            let rest = self.buffer.split_off(CHUNK_SIZE);
+            let chunk = std::mem::replace(&mut self.buffer, rest);
+            output.push(self.encryptor.encrypt_chunk(&chunk).await?);
+        }
+        Ok(output)
+    }
+
+    async fn flush(&mut self) -> Result<Vec<Vec<u8>>, StreamError> {
+        let mut output = Vec::new();
+        self.start(&mut output);
+        let chunk = std::mem::take(&mut self.buffer);
+        output.push(self.encryptor.finish(&chunk).await?);
+        Ok(output)
+    }
+}
+
+/// Decrypts the input, starting with the nonce prefix
+pub(crate) struct DecryptTransform {
+    cipher: Aes256Gcm,
+    decryptor: Option<StreamDecryptor>,
+    buffer: Vec<u8>,
+}
+
+impl DecryptTransform {
+    pub(crate) fn new(cipher: Aes256Gcm) -> Self {
+        Self {
+            cipher,
+            decryptor: None,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Decryptor of the stream, once the nonce prefix has been read
+    fn decryptor(&mut self) -> Result<Option<&mut StreamDecryptor>, StreamError> {
+        if self.decryptor.is_none() && self.buffer.len() >= PREFIX_SIZE {
+            let rest = self.buffer.split_off(PREFIX_SIZE);
+            let prefix = std::mem::replace(&mut self.buffer, rest);
+            self.decryptor = Some(StreamDecryptor::new(self.cipher.clone(), &prefix)?);
+        }
+        Ok(self.decryptor.as_mut())
+    }
+}
+
+impl Transform for DecryptTransform {
+    async fn push(&mut self, data: &[u8]) -> Result<Vec<Vec<u8>>, StreamError> {
+        let mut output = Vec::new();
+        self.buffer.extend_from_slice(data);
+        if self.decryptor()?.is_none() {
+            return Ok(output);
+        }
+        // the last chunk is kept until the end of the input
+        while self.buffer.len() > CHUNK_SIZE + TAG_SIZE {
+            let rest = self.buffer.split_off(CHUNK_SIZE + TAG_SIZE);
+            let chunk = std::mem::replace(&mut self.buffer, rest);
+            if let Some(decryptor) = self.decryptor.as_mut() {
+                output.push(decryptor.decrypt_chunk(&chunk).await?);
+            }
+        }
+        Ok(output)
+    }
+
+    async fn flush(&mut self) -> Result<Vec<Vec<u8>>, StreamError> {
+        let chunk = std::mem::take(&mut self.buffer);
+        let decryptor = self.decryptor.as_mut().ok_or(StreamError::InvalidPrefix)?;
+        Ok(vec![decryptor.finish(&chunk).await?])
+    }
+}
+
+/// Reports the number of input bytes processed by a transform
+pub(crate) struct WithProgress<T, F> {
+    inner: T,
+    done: u64,
+    total: Option<u64>,
+    on_progress: F,
+}
+
+impl<T, F> WithProgress<T, F> {
+    pub(crate) fn new(inner: T, total: Option<u64>, on_progress: F) -> Self {
+        Self {
+            inner,
+            done: 0,
+            total,
+            on_progress,
+        }
+    }
+}
+
+impl<T: Transform, F: FnMut(u64, Option<u64>) + 'static> Transform for WithProgress<T, F> {
+    async fn push(&mut self, data: &[u8]) -> Result<Vec<Vec<u8>>, StreamError> {
+        let output = self.inner.push(data).await?;
+        self.done += data.len() as u64;
+        (self.on_progress)(self.done, self.total);
+        Ok(output)
+    }
+
+    async fn flush(&mut self) -> Result<Vec<Vec<u8>>, StreamError> {
+        self.inner.flush().await
+    }
+}
+
+/// Runs a transform on a chunk, the transform being taken out of its cell
+/// while running
+pub(crate) async fn run<T: Transform>(
+    state: &RefCell<Option<T>>,
+    data: Option<&[u8]>,
+) -> Result<Vec<Vec<u8>>, StreamError> {
+    let Some(mut transform) = state.borrow_mut().take() else {
+        return Err(StreamError::Finished);
+    };
+    let result = match data {
+        Some(data) => transform.push(data).await,
+        None => transform.flush().await,
+    };
+    // a failed transform errors the stream, it's not restored
+    if result.is_ok() {
+        *state.borrow_mut() = Some(transform);
+    }
+    result
+}
+
+fn enqueue(
+    controller: &TransformStreamDefaultController,
+    chunks: Vec<Vec<u8>>,
+) -> Result<(), JsValue> {
+    for chunk in chunks.iter().filter(|chunk| !chunk.is_empty()) {
+        controller.enqueue_with_chunk(&Uint8Array::from(chunk.as_slice()))?;
+    }
+    Ok(())
+}
+
+type TransformCallback = dyn FnMut(JsValue, TransformStreamDefaultController) -> Promise;
+type FlushCallback = dyn FnMut(TransformStreamDefaultController) -> Promise;
+
+fn transform_stream<T: Transform>(transform: T) -> Result<TransformStream, StreamError> {
+    let state = Rc::new(RefCell::new(Some(transform)));
+
+    let transform_state = state.clone();
+    let on_transform = Closure::<TransformCallback>::new(
+        move |chunk: JsValue, controller: TransformStreamDefaultController| {
+            let state = transform_state.clone();
+            wasm_bindgen_futures::future_to_promise(async move {
+                let data = chunk_bytes(&chunk).ok_or_else(|| {
+                    js_sys::TypeError::new("chunks must be ArrayBuffer or ArrayBufferView")
+                })?;
+                let chunks = run(&state, Some(&data)).await.map_err(stream_error)?;
+                enqueue(&controller, chunks)?;
+                Ok(JsValue::UNDEFINED)
+            })
+        },
+    );
+    let on_flush =
+        Closure::<FlushCallback>::new(move |controller: TransformStreamDefaultController| {
+            let state = state.clone();
+            wasm_bindgen_futures::future_to_promise(async move {
+                let chunks = run(&state, None).await.map_err(stream_error)?;
+                enqueue(&controller, chunks)?;
+                Ok(JsValue::UNDEFINED)
+            })
+        });
+
+    let transformer = web_sys::Transformer::new();
+    // the closures are released along with the stream
+    transformer.set_transform(on_transform.into_js_value().unchecked_ref());
+    transformer.set_flush(on_flush.into_js_value().unchecked_ref());
+    Ok(TransformStream::new_with_transformer(&transformer)?)
+}
+
+/// Creates a `TransformStream` encrypting the written bytes
+///
+/// The readable side starts with the nonce prefix, followed by the encrypted
+/// chunks.
+///
+/// # Arguments
+/// * `cipher` - Cipher encrypting the stream
+///
+/// # Returns
+/// Result containing the TransformStream or a StreamError
+///
+/// # Errors
+/// - `StreamError::Nonce` if the nonce prefix couldn't be generated
+pub fn encrypt_transform(cipher: Aes256Gcm) -> Result<TransformStream, StreamError> {
+    transform_stream(EncryptTransform::new(cipher)?)
+}
+
+/// Creates a `TransformStream` decrypting the bytes of a stream produced by
+/// [`encrypt_transform`]
+///
+/// The readable side errors if a chunk has been altered or if the stream is
+/// truncated, the chunks read before being already authenticated.
+///
+/// # Arguments
+/// * `cipher` - Cipher used to encrypt the stream
+///
+/// # Returns
+/// Result containing the TransformStream or a StreamError
+pub fn decrypt_transform(cipher: Aes256Gcm) -> Result<TransformStream, StreamError> {
+    transform_stream(DecryptTransform::new(cipher))
+}
+
+/// Creates a `TransformStream` encrypting the written bytes, reporting the
+/// progress after each written chunk
+///
+/// # Arguments
+/// * `cipher` - Cipher encrypting the stream
+/// * `total` - Expected number of bytes to encrypt, like the size of a file, if
+///   known
+/// * `on_progress` - Called with the number of bytes written so far and the
+///   expected total
+///
+/// # Returns
+/// Result containing the TransformStream or a StreamError
+///
+/// # Errors
+/// - `StreamError::Nonce` if the nonce prefix couldn't be generated
+pub fn encrypt_transform_with_progress(
+    cipher: Aes256Gcm,
+    total: Option<u64>,
+    on_progress: impl FnMut(u64, Option<u64>) + 'static,
+) -> Result<TransformStream, StreamError> {
+    let transform = EncryptTransform::new(cipher)?;
+    transform_stream(WithProgress::new(transform, total, on_progress))
+}
+
+/// Creates a `TransformStream` decrypting the bytes of a stream produced by
+/// [`encrypt_transform`], reporting the progress after each written chunk
+///
+/// # Arguments
+/// * `cipher` - Cipher used to encrypt the stream
+/// * `total` - Expected number of encrypted bytes, like the `Content-Length` of
+///   a response, if known
+/// * `on_progress` - Called with the number of encrypted bytes written so far
+///   and the expected total
+///
+/// # Returns
+/// Result containing the TransformStream or a StreamError
+pub fn decrypt_transform_with_progress(
+    cipher: Aes256Gcm,
+    total: Option<u64>,
+    on_progress: impl FnMut(u64, Option<u64>) + 'static,
+) -> Result<TransformStream, StreamError> {
+    let transform = DecryptTransform::new(cipher);
+    transform_stream(WithProgress::new(transform, total, on_progress))
+}
+
+/// Decrypts a `ReadableStream` produced by [`encrypt_transform`], like the body
+/// of a `fetch` response
+///
+/// The returned stream can be consumed by JavaScript, or by a media element
+/// through a `MediaSource`, while the encrypted stream is downloaded.
+///
+/// # Arguments
+/// * `cipher` - Cipher used to encrypt the stream
+/// * `stream` - Encrypted stream
+///
+/// # Returns
+/// Result containing the decrypted ReadableStream or a StreamError
+///
+/// # Errors
+/// - `StreamError::Locked` if the encrypted stream is already being read
+pub fn decrypt_readable(
+    cipher: Aes256Gcm,
+    stream: &ReadableStream,
+) -> Result<ReadableStream, StreamError> {
+    if stream.locked() {
+        return Err(StreamError::Locked);
+    }
+    let transform = decrypt_transform(cipher)?;
+    let pair = ReadableWritablePair::new(&transform.readable(), &transform.writable());
+    Ok(stream.pipe_through(&pair))
+}
+
+/// Decrypts a `ReadableStream` produced by [`encrypt_transform`], until the
+/// signal is aborted
+///
+/// Once aborted, the encrypted stream is cancelled and the decrypted stream
+/// errors with the reason of the signal.
+///
+/// # Arguments
+/// * `cipher` - Cipher used to encrypt the stream
+/// * `stream` - Encrypted stream
+/// * `signal` - Signal of an `AbortController`
+///
+/// # Returns
+/// Result containing the decrypted ReadableStream or a StreamError
+///
+/// # Errors
+/// - `StreamError::Locked` if the encrypted stream is already being read
+pub fn decrypt_readable_abortable(
+    cipher: Aes256Gcm,
+    stream: &ReadableStream,
+    signal: &AbortSignal,
+) -> Result<ReadableStream, StreamError> {
+    if stream.locked() {
+        return Err(StreamError::Locked);
+    }
+    let transform = decrypt_transform(cipher)?;
+    let pair = ReadableWritablePair::new(&transform.readable(), &transform.writable());
+    let options = StreamPipeOptions::new();
+    options.set_signal(signal);
+    Ok(stream.pipe_through_with_options(&pair, &options))
+}
+
+/// State of a [`DecryptStream`], moved into the pending read
+struct DecryptState {
+    reader: ReadableStreamDefaultReader,
+    transform: DecryptTransform,
+    output: VecDeque<Vec<u8>>,
+    signal: Option<AbortSignal>,
+    done: bool,
+}
+
+impl DecryptState {
+    fn check_signal(&self) -> Result<(), StreamError> {
+        match self.signal {
+            Some(ref signal) if signal.aborted() => Err(StreamError::Aborted),
+            _ => Ok(()),
+        }
+    }
+
+    /// Cancels the encrypted stream, releasing its source
+    fn cancel(&mut self) {
+        self.done = true;
+        let promise = self.reader.cancel();
+        // the cancellation is rejected when the stream has errored
+        wasm_bindgen_futures::spawn_local(async move {
+            let _ = JsFuture::from(promise).await;
+        });
+    }
+
+    /// Reads the next chunk of the encrypted stream
+    async fn read(&self) -> Result<Option<Vec<u8>>, StreamError> {
+        let result = JsFuture::from(self.reader.read()).await?;
+        if js_sys::Reflect::get(&result, &"done".into())?.is_truthy() {
+            return Ok(None);
+        }
+        let value = js_sys::Reflect::get(&result, &"value".into())?;
+        chunk_bytes(&value)
+            .map(Some)
+            .ok_or(StreamError::InvalidChunk)
+    }
+
+    async fn fill(&mut self) -> Result<(), StreamError> {
+        self.check_signal()?;
+        let data = self.read().await?;
+        self.check_signal()?;
+        let chunks = match data {
+            Some(data) => self.transform.push(&data).await?,
+            None => {
+                self.done = true;
+                self.transform.flush().await?
+            }
+        };
+        self.output
+            .extend(chunks.into_iter().filter(|chunk| !chunk.is_empty()));
+        Ok(())
+    }
+
+    async fn next(mut self) -> (Self, Option<Result<Vec<u8>, StreamError>>) {
+        loop {
+            if let Some(chunk) = self.output.pop_front() {
+                return (self, Some(Ok(chunk)));
+            }
+            if self.done {
+                return (self, None);
+            }
+            if let Err(err) = self.fill().await {
+                // the stream ends with its first error
+                self.cancel();
+                return (self, Some(Err(err)));
+            }
+        }
+    }
+}
+
+impl Drop for DecryptState {
+    fn drop(&mut self) {
+        if !self.done {
+            self.cancel();
+        }
+    }
+}
+
+type NextFuture = dyn Future<Output = (DecryptState, Option<Result<Vec<u8>, StreamError>>)>;
+
+/// Stream of the decrypted chunks of a `ReadableStream` produced by
+/// [`encrypt_transform`]
+///
+/// The chunks are yielded as soon as they're authenticated: when the
+/// encrypted stream is truncated or altered, the previous chunks are already
+/// yielded and the stream ends with an error.
+///
+/// Dropping the stream before its end cancels the encrypted stream.
+pub struct DecryptStream {
+    state: Option<DecryptState>,
+    pending: Option<Pin<Box<NextFuture>>>,
+}
+
+impl DecryptStream {
+    /// Starts reading an encrypted stream
+    ///
+    /// # Arguments
+    /// * `cipher` - Cipher used to encrypt the stream
+    /// * `stream` - Encrypted stream, locked until the end of the decryption
+    ///
+    /// # Returns
+    /// Result containing the DecryptStream or a StreamError
+    ///
+    /// # Errors
+    /// - `StreamError::Locked` if the encrypted stream is already being read
+    pub fn new(cipher: Aes256Gcm, stream: &ReadableStream) -> Result<Self, StreamError> {
+        if stream.locked() {
+            return Err(StreamError::Locked);
+        }
+        Ok(Self {
+            state: Some(DecryptState {
+                reader: ReadableStreamDefaultReader::new(stream)?,
+                transform: DecryptTransform::new(cipher),
+                output: VecDeque::new(),
+                signal: None,
+                done: false,
+            }),
+            pending: None,
+        })
+    }
+
+    /// Aborts the decryption when the signal is aborted, the stream ending
+    /// with `StreamError::Aborted` before reading its next chunk
+    ///
+    /// # Arguments
+    /// * `signal` - Signal of an `AbortController`, set before polling the
+    ///   stream
+    pub fn with_signal(mut self, signal: AbortSignal) -> Self {
+        if let Some(state) = self.state.as_mut() {
+            state.signal = Some(signal);
+        }
+        self
+    }
+}
+
+impl futures_core::Stream for DecryptStream {
+    type Item = Result<Vec<u8>, StreamError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.pending.is_none() {
+            let Some(state) = self.state.take() else {
+                return Poll::Ready(None);
+            };
+            self.pending = Some(Box::pin(state.next()));
+        }
+        let Some(pending) = self.pending.as_mut() else {
+            return Poll::Ready(None);
+        };
+        match pending.as_mut().poll(cx) {
+            Poll::Ready((state, item)) => {
+                self.pending = None;
+                self.state = Some(state);
+                Poll::Ready(item)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
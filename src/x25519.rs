@@ -0,0 +1,270 @@
+//! X25519 key agreement implementation
+//!
+//! X25519 is only supported by recent browsers, use [`is_supported`] to check
+//! its availability. On older browsers, the operations fail with a
+//! `NotSupported` error.
+//!
+//! ```rust,no_run
+//! use browser_crypto::x25519::X25519KeyPair;
+//!
+//! async fn agree() -> Result<(), Box<dyn std::error::Error>> {
+//!     if !browser_crypto::x25519::is_supported().await {
+//!         return Ok(());
+//!     }
+//!     let alice = X25519KeyPair::generate().await?;
+//!     let bob = X25519KeyPair::generate().await?;
+//!     let left = alice.private_key.diffie_hellman(&bob.public_key).await?;
+//!     let right = bob.private_key.diffie_hellman(&alice.public_key).await?;
+//!     assert_eq!(left, right);
+//!     Ok(())
+//! }
+//! ```
+
+use crate::der::KeyAlgorithm;
+use crate::derive::DeriveError;
+use crate::input::SecretInput;
+use crate::jwk::Jwk;
+use crate::key::{ExportKeyError, GenerateKeyError, ImportKeyError, KeyFormat};
+use crate::key_pair::{AsymmetricAlgorithm, AsymmetricKey, KeyPair};
+
+const NAME: &str = "X25519";
+
+/// Size of a public key in bytes
+pub const PUBLIC_KEY_SIZE: usize = 32;
+
+/// Size of a shared secret in bytes
+pub const SHARED_SECRET_SIZE: usize = 32;
+
+/// Public key from RFC 7748, used to check the browser support
+const PROBE_PUBLIC_KEY: [u8; PUBLIC_KEY_SIZE] = [
+    0x85, 0x20, 0xf0, 0x09, 0x89, 0x30, 0xa7, 0x54, 0x74, 0x8b, 0x7d, 0xdc, 0xb4, 0x3e, 0xf7, 0x5a,
+    0x0d, 0xbf, 0x3a, 0x0d, 0x26, 0x38, 0x1a, 0xf4, 0xeb, 0xa4, 0xa9, 0x8e, 0xaa, 0x9b, 0x4e, 0x6a,
+];
+
+fn algorithm() -> Result<js_sys::Object, wasm_bindgen::JsValue> {
+    let algorithm = js_sys::Object::new();
+    js_sys::Reflect::set(&algorithm, &"name".into(), &NAME.into())?;
+    Ok(algorithm)
+}
+
+/// Checks if the browser supports X25519
+///
+/// # Returns
+/// `true` if a public key could be imported, `false` otherwise
+pub async fn is_supported() -> bool {
+    X25519PublicKey::from_raw(&PROBE_PUBLIC_KEY).await.is_ok()
+}
+
+/// X25519 algorithm, see [`X25519KeyPair`]
+#[derive(Debug, Clone, Copy)]
+pub struct X25519;
+
+impl AsymmetricAlgorithm for X25519 {
+    type PublicKey = X25519PublicKey;
+    type PrivateKey = X25519PrivateKey;
+
+    const PUBLIC_USAGES: &'static [&'static str] = &[];
+    const PRIVATE_USAGES: &'static [&'static str] = &["deriveBits"];
+}
+
+/// X25519 public key, shared with the peer
+#[derive(Debug, Clone)]
+pub struct X25519PublicKey {
+    key: web_sys::CryptoKey,
+}
+
+impl X25519PublicKey {
+    /// Imports a public key from its raw bytes.
+    ///
+    /// # Arguments
+    /// * `data` - Raw public key, 32 bytes
+    ///
+    /// # Returns
+    /// Result containing the public key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::InvalidKeyFormat` if the data is not a valid public
+    ///   key
+    /// - `ImportKeyError::NotSupported` if the browser doesn't support X25519
+    pub async fn from_raw(data: &[u8]) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let key = crate::key::import_key(KeyFormat::Raw, &algorithm()?, data, &[]).await?;
+        Ok(Self { key })
+    }
+
+    /// Imports a public key in the SubjectPublicKeyInfo format.
+    ///
+    /// # Arguments
+    /// * `data` - DER encoded SubjectPublicKeyInfo
+    ///
+    /// # Returns
+    /// Result containing the public key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::MalformedDer` if the data is not a DER encoded
+    ///   SubjectPublicKeyInfo
+    /// - `ImportKeyError::UnsupportedAlgorithm` if the key is not an X25519 key
+    /// - `ImportKeyError::NotSupported` if the browser doesn't support X25519
+    pub async fn from_spki(data: &[u8]) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let key = crate::key::import_der(
+            KeyFormat::Spki,
+            &algorithm()?,
+            data,
+            KeyAlgorithm::X25519,
+            &[],
+        )
+        .await?;
+        Ok(Self { key })
+    }
+
+    /// Imports a public key in the JSON Web Key format.
+    ///
+    /// # Arguments
+    /// * `jwk` - JSON Web Key, with `kty` set to `OKP` and `crv` to `X25519`
+    ///
+    /// # Returns
+    /// Result containing the public key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::NotSupported` if the browser doesn't support X25519
+    pub async fn from_jwk(jwk: &Jwk) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let key = crate::key::import_jwk(&algorithm()?, jwk, &[]).await?;
+        Ok(Self { key })
+    }
+
+    /// Exports the public key as raw bytes.
+    pub async fn to_raw(&self) -> Result<Vec<u8>, ExportKeyError> {
+        crate::key::export_key(KeyFormat::Raw, &self.key).await
+    }
+
+    /// Exports the public key in the SubjectPublicKeyInfo format.
+    pub async fn to_spki(&self) -> Result<Vec<u8>, ExportKeyError> {
+        crate::key::export_key(KeyFormat::Spki, &self.key).await
+    }
+
+    /// Exports the public key in the JSON Web Key format.
+    pub async fn to_jwk(&self) -> Result<Jwk, ExportKeyError> {
+        crate::key::export_jwk(&self.key).await
+    }
+}
+
+impl crate::key_pair::private::Sealed for X25519PublicKey {
+    fn crypto_key(&self) -> &web_sys::CryptoKey {
+        &self.key
+    }
+}
+
+impl AsymmetricKey for X25519PublicKey {}
+
+/// X25519 private key, used to compute shared secrets
+#[derive(Debug, Clone)]
+pub struct X25519PrivateKey {
+    key: web_sys::CryptoKey,
+}
+
+impl X25519PrivateKey {
+    /// Imports a private key in the PKCS#8 format.
+    ///
+    /// The Web Crypto API doesn't support importing raw X25519 private keys.
+    ///
+    /// # Arguments
+    /// * `data` - DER encoded PKCS#8 private key
+    ///
+    /// # Returns
+    /// Result containing the private key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::MalformedDer` if the data is not a DER encoded PKCS#8
+    ///   key
+    /// - `ImportKeyError::UnsupportedAlgorithm` if the key is not an X25519 key
+    /// - `ImportKeyError::NotSupported` if the browser doesn't support X25519
+    pub async fn from_pkcs8(data: &(impl SecretInput + ?Sized)) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let key = crate::key::import_der(
+            KeyFormat::Pkcs8,
+            &algorithm()?,
+            data.expose_bytes(),
+            KeyAlgorithm::X25519,
+            X25519::PRIVATE_USAGES,
+        )
+        .await?;
+        Ok(Self { key })
+    }
+
+    /// Imports a private key in the JSON Web Key format.
+    ///
+    /// # Arguments
+    /// * `jwk` - JSON Web Key, with `kty` set to `OKP`, `crv` to `X25519` and
+    ///   `d` defined
+    ///
+    /// # Returns
+    /// Result containing the private key or an ImportKeyError
+    ///
+    /// # Errors
+    /// - `ImportKeyError::NotSupported` if the browser doesn't support X25519
+    pub async fn from_jwk(jwk: &Jwk) -> Result<Self, ImportKeyError> {
+        crate::check_target()?;
+        let key = crate::key::import_jwk(&algorithm()?, jwk, X25519::PRIVATE_USAGES).await?;
+        Ok(Self { key })
+    }
+
+    /// Exports the private key in the PKCS#8 format.
+    pub async fn to_pkcs8(&self) -> Result<Vec<u8>, ExportKeyError> {
+        crate::key::export_key(KeyFormat::Pkcs8, &self.key).await
+    }
+
+    /// Exports the private key in the JSON Web Key format.
+    pub async fn to_jwk(&self) -> Result<Jwk, ExportKeyError> {
+        crate::key::export_jwk(&self.key).await
+    }
+
+    /// Computes the shared secret with the public key of the peer
+    ///
+    /// # Arguments
+    /// * `public_key` - Public key of the peer
+    ///
+    /// # Returns
+    /// Result containing the 32 bytes shared secret or a DeriveError
+    ///
+    /// # Errors
+    /// - `DeriveError::Operation` if the shared secret is all zeros, because
+    ///   the public key is a low order point
+    pub async fn diffie_hellman(
+        &self,
+        public_key: &X25519PublicKey,
+    ) -> Result<Vec<u8>, DeriveError> {
+        let length = (SHARED_SECRET_SIZE * 8) as u32;
+        crate::derive::key_agreement(NAME, &self.key, &public_key.key, length).await
+    }
+}
+
+impl crate::key_pair::private::Sealed for X25519PrivateKey {
+    fn crypto_key(&self) -> &web_sys::CryptoKey {
+        &self.key
+    }
+}
+
+impl AsymmetricKey for X25519PrivateKey {}
+
+/// X25519 key pair
+pub type X25519KeyPair = KeyPair<X25519>;
+
+impl KeyPair<X25519> {
+    /// Generates a new key pair.
+    ///
+    /// # Returns
+    /// Result containing the key pair or a GenerateKeyError
+    ///
+    /// # Errors
+    /// - `GenerateKeyError::NotSupported` if the browser doesn't support X25519
+    pub async fn generate() -> Result<Self, GenerateKeyError> {
+        crate::check_target()?;
+        let (public_key, private_key) = crate::key_pair::generate::<X25519>(&algorithm()?).await?;
+        Ok(Self::new(
+            X25519PublicKey { key: public_key },
+            X25519PrivateKey { key: private_key },
+        ))
+    }
+}
@@ -0,0 +1,123 @@
+//! Test suite for the compatibility with the aead crate.
+
+#![cfg(all(target_arch = "wasm32", feature = "aead"))]
+
+extern crate wasm_bindgen_test;
+
+use aes_gcm::aead::{Aead, AeadCore, AeadInPlace, KeyInit, Payload};
+use browser_crypto::aead::{Aes128GcmAdapter, Aes256GcmAdapter, AsyncAead};
+use browser_crypto::aes_gcm::Aes256Gcm;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+const KEY: [u8; 32] = [42; 32];
+
+/// Encrypts with any cipher following the conventions of the aead crate
+async fn encrypt<C: AsyncAead>(cipher: &C, nonce: &aes_gcm::aead::Nonce<C>) -> Vec<u8> {
+    let payload = Payload {
+        msg: b"Hello World!",
+        aad: b"user-42",
+    };
+    cipher.encrypt(nonce, payload).await.unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn should_match_the_rust_crypto_cipher() {
+    console_error_panic_hook::set_once();
+
+    let browser = Aes256GcmAdapter::from_key(&KEY.into()).await.unwrap();
+    let pure = aes_gcm::Aes256Gcm::new(&KEY.into());
+    let nonce = aes_gcm::Nonce::from([7u8; 12]);
+
+    let encrypted = encrypt(&browser, &nonce).await;
+    assert_eq!(
+        encrypted,
+        pure.encrypt(
+            &nonce,
+            Payload {
+                msg: b"Hello World!",
+                aad: b"user-42"
+            }
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        browser
+            .decrypt(
+                &nonce,
+                Payload {
+                    msg: &encrypted,
+                    aad: b"user-42"
+                }
+            )
+            .await
+            .unwrap(),
+        b"Hello World!"
+    );
+
+    // without additional data
+    let encrypted = browser.encrypt(&nonce, b"hello".as_ref()).await.unwrap();
+    assert_eq!(encrypted, pure.encrypt(&nonce, b"hello".as_ref()).unwrap());
+    assert_eq!(
+        browser.decrypt(&nonce, encrypted.as_ref()).await.unwrap(),
+        b"hello"
+    );
+}
+
+#[wasm_bindgen_test]
+async fn should_fail_with_the_opaque_error() {
+    console_error_panic_hook::set_once();
+
+    let browser = Aes256GcmAdapter::from(Aes256Gcm::from_key(&KEY).await.unwrap());
+    let nonce = Aes256GcmAdapter::generate_nonce(aes_gcm::aead::OsRng);
+    let mut encrypted = encrypt(&browser, &nonce).await;
+    assert!(browser.decrypt(&nonce, encrypted.as_ref()).await.is_err());
+    encrypted[0] ^= 1;
+    assert_eq!(
+        browser
+            .decrypt(
+                &nonce,
+                Payload {
+                    msg: &encrypted,
+                    aad: b"user-42"
+                }
+            )
+            .await,
+        Err(aes_gcm::aead::Error)
+    );
+    assert!(Aes128GcmAdapter::from_key(&[0u8; 16].into()).await.is_ok());
+}
+
+#[wasm_bindgen_test]
+async fn should_encrypt_in_place() {
+    console_error_panic_hook::set_once();
+
+    let browser = Aes256GcmAdapter::from_key(&KEY.into()).await.unwrap();
+    let pure = aes_gcm::Aes256Gcm::new(&KEY.into());
+    let nonce = aes_gcm::Nonce::from([1u8; 12]);
+
+    let mut buffer = b"Hello World!".to_vec();
+    browser
+        .encrypt_in_place(&nonce, b"aad", &mut buffer)
+        .await
+        .unwrap();
+    let mut expected = b"Hello World!".to_vec();
+    pure.encrypt_in_place(&nonce, b"aad", &mut expected)
+        .unwrap();
+    assert_eq!(buffer, expected);
+
+    browser
+        .decrypt_in_place(&nonce, b"aad", &mut buffer)
+        .await
+        .unwrap();
+    assert_eq!(buffer, b"Hello World!");
+
+    let mut altered = expected.clone();
+    altered[3] ^= 1;
+    assert!(browser
+        .decrypt_in_place(&nonce, b"aad", &mut altered)
+        .await
+        .is_err());
+    assert_eq!(altered[3], expected[3] ^ 1);
+}
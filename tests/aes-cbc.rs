@@ -0,0 +1,89 @@
+//! Test suite for the AES-CBC implementation.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_cbc::{Aes256Cbc, Iv};
+use browser_crypto::algorithm::{Algorithm, DecryptionError};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+pub const DEFAULT_KEY: [u8; 32] = [42; 32];
+
+#[wasm_bindgen_test]
+async fn should_encrypt_and_decrypt() {
+    console_error_panic_hook::set_once();
+
+    let clear_msg = b"Hello World!";
+    let crypto = Aes256Cbc::from_key(&DEFAULT_KEY).await.unwrap();
+    let iv = Aes256Cbc::generate_nonce().unwrap();
+    let encrypted = crypto.encrypt(&iv, clear_msg).await.unwrap();
+    // padded to the block size
+    assert_eq!(encrypted.len(), 16);
+
+    let decrypted = crypto.decrypt(&iv, &encrypted).await.unwrap();
+
+    assert_eq!(clear_msg, decrypted.as_slice());
+}
+
+#[wasm_bindgen_test]
+async fn should_decrypt_in_place() {
+    console_error_panic_hook::set_once();
+
+    let crypto = Aes256Cbc::from_key(&DEFAULT_KEY).await.unwrap();
+    let iv = Aes256Cbc::generate_nonce().unwrap();
+    let mut buffer = crypto.encrypt(&iv, b"Hello World!").await.unwrap();
+    crypto.decrypt_in_place(&iv, &mut buffer).await.unwrap();
+    assert_eq!(buffer, b"Hello World!");
+
+    // not a multiple of the block size
+    let mut buffer = vec![1; 15];
+    let err = crypto.decrypt_in_place(&iv, &mut buffer).await.unwrap_err();
+    assert!(matches!(err, DecryptionError::Operation));
+    assert_eq!(buffer, [1; 15]);
+}
+
+#[wasm_bindgen_test]
+async fn should_match_nist_vector() {
+    // NIST SP 800-38A, F.2.5 CBC-AES256.Encrypt, first block
+    console_error_panic_hook::set_once();
+
+    let key = [
+        0x60, 0x3d, 0xeb, 0x10, 0x15, 0xca, 0x71, 0xbe, 0x2b, 0x73, 0xae, 0xf0, 0x85, 0x7d, 0x77,
+        0x81, 0x1f, 0x35, 0x2c, 0x07, 0x3b, 0x61, 0x08, 0xd7, 0x2d, 0x98, 0x10, 0xa3, 0x09, 0x14,
+        0xdf, 0xf4,
+    ];
+    let iv = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f,
+    ];
+    let plaintext = [
+        0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17,
+        0x2a,
+    ];
+    let expected = [
+        0xf5, 0x8c, 0x4c, 0x04, 0xd6, 0xe5, 0xf1, 0xba, 0x77, 0x9e, 0xab, 0xfb, 0x5f, 0x7b, 0xfb,
+        0xd6,
+    ];
+
+    let crypto = Aes256Cbc::from_key(&key).await.unwrap();
+    let iv = Iv::from_slice(&iv).unwrap();
+    let encrypted = crypto.encrypt(&iv, &plaintext).await.unwrap();
+
+    // the second block only contains the padding
+    assert_eq!(encrypted.len(), 32);
+    assert_eq!(&encrypted[..16], &expected);
+}
+
+#[wasm_bindgen_test]
+async fn should_fail_decrypting_with_invalid_padding() {
+    console_error_panic_hook::set_once();
+
+    let crypto = Aes256Cbc::from_key(&DEFAULT_KEY).await.unwrap();
+    let iv = Aes256Cbc::generate_nonce().unwrap();
+    let err = crypto.decrypt(&iv, &[0; 15]).await.unwrap_err();
+
+    assert!(matches!(err, DecryptionError::Operation));
+}
@@ -0,0 +1,86 @@
+//! Test suite for the AES-CTR implementation.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_ctr::{Aes256Ctr, Counter};
+use browser_crypto::algorithm::{Algorithm, EncryptionError};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+pub const DEFAULT_KEY: [u8; 32] = [42; 32];
+
+#[wasm_bindgen_test]
+async fn should_encrypt_and_decrypt() {
+    console_error_panic_hook::set_once();
+
+    let clear_msg = b"Hello World!";
+    let crypto = Aes256Ctr::from_key(&DEFAULT_KEY).await.unwrap();
+    let counter = Aes256Ctr::generate_nonce().unwrap();
+    let encrypted = crypto.encrypt(&counter, clear_msg).await.unwrap();
+    assert_eq!(encrypted.len(), clear_msg.len());
+
+    let decrypted = crypto.decrypt(&counter, &encrypted).await.unwrap();
+
+    assert_eq!(clear_msg, decrypted.as_slice());
+}
+
+#[wasm_bindgen_test]
+async fn should_decrypt_in_place() {
+    console_error_panic_hook::set_once();
+
+    let crypto = Aes256Ctr::from_key(&DEFAULT_KEY).await.unwrap();
+    let counter = Aes256Ctr::generate_nonce().unwrap();
+    let mut buffer = crypto.encrypt(&counter, b"Hello World!").await.unwrap();
+    crypto
+        .decrypt_in_place(&counter, &mut buffer)
+        .await
+        .unwrap();
+    assert_eq!(buffer, b"Hello World!");
+}
+
+#[wasm_bindgen_test]
+async fn should_match_nist_vector() {
+    // NIST SP 800-38A, F.5.5 CTR-AES256.Encrypt, first block
+    console_error_panic_hook::set_once();
+
+    let key = [
+        0x60, 0x3d, 0xeb, 0x10, 0x15, 0xca, 0x71, 0xbe, 0x2b, 0x73, 0xae, 0xf0, 0x85, 0x7d, 0x77,
+        0x81, 0x1f, 0x35, 0x2c, 0x07, 0x3b, 0x61, 0x08, 0xd7, 0x2d, 0x98, 0x10, 0xa3, 0x09, 0x14,
+        0xdf, 0xf4,
+    ];
+    let counter = [
+        0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd, 0xfe,
+        0xff,
+    ];
+    let plaintext = [
+        0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17,
+        0x2a,
+    ];
+    let expected = [
+        0x60, 0x1e, 0xc3, 0x13, 0x77, 0x57, 0x89, 0xa5, 0xb7, 0xa7, 0xf5, 0x04, 0xbb, 0xf3, 0xd2,
+        0x28,
+    ];
+
+    let crypto = Aes256Ctr::from_key(&key).await.unwrap();
+    let counter = Counter::from_slice(&counter).unwrap();
+    let encrypted = crypto.encrypt(&counter, &plaintext).await.unwrap();
+
+    assert_eq!(encrypted, expected);
+}
+
+#[wasm_bindgen_test]
+async fn should_reject_invalid_counter_length() {
+    console_error_panic_hook::set_once();
+
+    let crypto = Aes256Ctr::from_key(&DEFAULT_KEY)
+        .await
+        .unwrap()
+        .with_counter_length(0);
+    let counter = Aes256Ctr::generate_nonce().unwrap();
+    let err = crypto.encrypt(&counter, b"Hello World!").await.unwrap_err();
+
+    assert!(matches!(err, EncryptionError::Operation));
+}
@@ -0,0 +1,100 @@
+//! Test suite for the AES-KW implementation.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::{Aes128Gcm, Aes256Gcm};
+use browser_crypto::aes_kw::Aes256Kw;
+use browser_crypto::algorithm::Algorithm;
+use browser_crypto::key::{KeyUnwrapper, KeyWrapper, UnwrapKeyError, WrapKeyError};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_wrap_and_unwrap() {
+    console_error_panic_hook::set_once();
+
+    let kek = Aes256Kw::from_key(&[1; 32]).await.unwrap();
+    let dek = Aes256Gcm::from_key(&[42; 32]).await.unwrap();
+    let wrapped = kek.wrap_key(&dek).await.unwrap();
+    // 8 bytes of integrity check
+    assert_eq!(wrapped.len(), 40);
+
+    let nonce = Aes256Gcm::generate_nonce().unwrap();
+    let encrypted = dek.encrypt(&nonce, b"Hello World!").await.unwrap();
+
+    let unwrapped: Aes256Gcm = kek.unwrap_key(&wrapped).await.unwrap();
+    let decrypted = unwrapped.decrypt(&nonce, &encrypted).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+}
+
+#[wasm_bindgen_test]
+async fn should_match_rfc_vector() {
+    // RFC 3394, 4.6 Wrap 256 bits of Key Data with a 256-bit KEK
+    console_error_panic_hook::set_once();
+
+    let kek: Vec<u8> = (0u8..32).collect();
+    let key = [
+        0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee,
+        0xff, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d,
+        0x0e, 0x0f,
+    ];
+    let expected = [
+        0x28, 0xc9, 0xf4, 0x04, 0xc4, 0xb8, 0x10, 0xf4, 0xcb, 0xcc, 0xb3, 0x5c, 0xfb, 0x87, 0xf8,
+        0x26, 0x3f, 0x57, 0x86, 0xe2, 0xd8, 0x0e, 0xd3, 0x26, 0xcb, 0xc7, 0xf0, 0xe7, 0x1a, 0x99,
+        0xf4, 0x3b, 0xfb, 0x98, 0x8b, 0x9b, 0x7a, 0x02, 0xdd, 0x21,
+    ];
+
+    let kek = Aes256Kw::from_key(&kek).await.unwrap();
+    let dek = Aes256Gcm::from_key(&key).await.unwrap();
+    let wrapped = kek.wrap_key(&dek).await.unwrap();
+
+    assert_eq!(wrapped, expected);
+}
+
+#[wasm_bindgen_test]
+async fn should_fail_unwrapping_with_another_key() {
+    console_error_panic_hook::set_once();
+
+    let kek = Aes256Kw::from_key(&[1; 32]).await.unwrap();
+    let dek = Aes256Gcm::from_key(&[42; 32]).await.unwrap();
+    let wrapped = kek.wrap_key(&dek).await.unwrap();
+
+    let other = Aes256Kw::from_key(&[2; 32]).await.unwrap();
+    let err = other.unwrap_key::<Aes256Gcm>(&wrapped).await.unwrap_err();
+    assert!(matches!(err, UnwrapKeyError::Operation));
+}
+
+#[wasm_bindgen_test]
+async fn should_fail_unwrapping_with_another_key_size() {
+    console_error_panic_hook::set_once();
+
+    let kek = Aes256Kw::from_key(&[1; 32]).await.unwrap();
+    let dek = Aes128Gcm::from_key(&[42; 16]).await.unwrap();
+    let wrapped = kek.wrap_key(&dek).await.unwrap();
+
+    let err = kek.unwrap_key::<Aes256Gcm>(&wrapped).await.unwrap_err();
+    assert!(matches!(err, UnwrapKeyError::InvalidKey));
+}
+
+#[wasm_bindgen_test]
+async fn should_unwrap_non_extractable_keys() {
+    console_error_panic_hook::set_once();
+
+    let kek = Aes256Kw::from_key(&[1; 32]).await.unwrap();
+    let dek = Aes256Gcm::from_key(&[42; 32]).await.unwrap();
+    let wrapped = kek.wrap_key(&dek).await.unwrap();
+
+    let unwrapped: Aes256Gcm = kek.unwrap_key(&wrapped).await.unwrap();
+    let err = kek.wrap_key(&unwrapped).await.unwrap_err();
+    // Node.js names the exception InvalidAccessException
+    assert!(
+        matches!(err, WrapKeyError::InvalidAccess | WrapKeyError::Generic(_)),
+        "{err:?}"
+    );
+
+    let unwrapped: Aes256Gcm = kek.unwrap_extractable_key(&wrapped).await.unwrap();
+    assert_eq!(kek.wrap_key(&unwrapped).await.unwrap(), wrapped);
+}
@@ -0,0 +1,89 @@
+//! Test suite for the built-in ciphers chosen at runtime.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::{Aes128Gcm, Aes256Gcm};
+use browser_crypto::algorithm::{Algorithm, EncryptionError};
+use browser_crypto::any_cipher::AnyCipher;
+use browser_crypto::sealed::{Sealed, SealedAlgorithm, SealedError};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_create_the_cipher_of_an_algorithm() {
+    console_error_panic_hook::set_once();
+
+    let cipher = AnyCipher::from_key(SealedAlgorithm::Aes128Gcm, &[1u8; 16])
+        .await
+        .unwrap();
+    assert!(matches!(cipher, AnyCipher::Aes128Gcm(_)));
+    assert_eq!(cipher.algorithm(), SealedAlgorithm::Aes128Gcm);
+    assert_eq!(
+        (cipher.name(), cipher.key_size(), cipher.nonce_size()),
+        ("AES-GCM", 16, 12)
+    );
+    assert!(AnyCipher::from_key(SealedAlgorithm::Aes256Gcm, &[1u8; 16])
+        .await
+        .is_err());
+
+    let cipher = AnyCipher::generate(SealedAlgorithm::Aes256Gcm, false)
+        .await
+        .unwrap();
+    assert_eq!(cipher.algorithm(), SealedAlgorithm::Aes256Gcm);
+}
+
+#[wasm_bindgen_test]
+async fn should_match_the_concrete_cipher() {
+    console_error_panic_hook::set_once();
+
+    let concrete = Aes256Gcm::from_key(&[2u8; 32]).await.unwrap();
+    let cipher = AnyCipher::from(concrete.clone());
+
+    let nonce = cipher.generate_nonce().unwrap();
+    assert_eq!(nonce.len(), 12);
+    let encrypted = cipher.encrypt(&nonce, b"hello").await.unwrap();
+    assert_eq!(cipher.decrypt(&nonce, &encrypted).await.unwrap(), b"hello");
+
+    let sealed = cipher.seal(b"hello").await.unwrap();
+    assert_eq!(concrete.open(&sealed).await.unwrap(), b"hello");
+    let sealed = concrete.seal(b"world").await.unwrap();
+    assert_eq!(cipher.open(&sealed).await.unwrap(), b"world");
+
+    assert!(matches!(
+        cipher.encrypt(&nonce[..8], b"hello").await,
+        Err(EncryptionError::Nonce(_))
+    ));
+}
+
+#[wasm_bindgen_test]
+async fn should_open_sealed_payloads_of_any_algorithm() {
+    console_error_panic_hook::set_once();
+
+    let sealed = Sealed::seal(
+        &Aes128Gcm::from_key(&[3u8; 16]).await.unwrap(),
+        b"aad",
+        b"hello",
+    )
+    .await
+    .unwrap();
+    let sealed = Sealed::from_bytes(&sealed.to_bytes()).unwrap();
+    let cipher = AnyCipher::from_key(sealed.algorithm(), &[3u8; 16])
+        .await
+        .unwrap();
+    assert_eq!(sealed.open_any(&cipher, b"aad").await.unwrap(), b"hello");
+
+    let sealed = Sealed::seal_any(&cipher, b"aad", b"world").await.unwrap();
+    assert_eq!(sealed.algorithm(), SealedAlgorithm::Aes128Gcm);
+    assert_eq!(sealed.open_any(&cipher, b"aad").await.unwrap(), b"world");
+
+    let other = AnyCipher::from_key(SealedAlgorithm::Aes256Gcm, &[3u8; 32])
+        .await
+        .unwrap();
+    assert!(matches!(
+        sealed.open_any(&other, b"aad").await,
+        Err(SealedError::AlgorithmMismatch(SealedAlgorithm::Aes128Gcm))
+    ));
+}
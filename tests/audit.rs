@@ -0,0 +1,122 @@
+//! Test suite for the audit records.
+
+#![cfg(all(target_arch = "wasm32", feature = "audit"))]
+
+extern crate wasm_bindgen_test;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::aes_kw::Aes256Kw;
+use browser_crypto::algorithm::{Algorithm, Nonce};
+use browser_crypto::audit::{self, AuditRecord, Outcome};
+use browser_crypto::context::Operation;
+use browser_crypto::hash::Hash;
+use browser_crypto::hmac::Hmac;
+use browser_crypto::key::{KeyUnwrapper, KeyWrapper};
+use browser_crypto::kid::Tagged;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+type Entry = (Operation, String, Option<String>, Option<usize>, Outcome);
+
+fn record_into(recorded: &Rc<RefCell<Vec<Entry>>>) {
+    let sink = recorded.clone();
+    audit::set_audit_sink(move |record: &AuditRecord<'_>| {
+        sink.borrow_mut().push((
+            record.operation,
+            record.algorithm.to_string(),
+            record.kid.map(String::from),
+            record.payload_len,
+            record.outcome,
+        ));
+    });
+}
+
+#[wasm_bindgen_test]
+async fn should_record_operations() {
+    console_error_panic_hook::set_once();
+
+    let recorded = Rc::new(RefCell::new(Vec::new()));
+    record_into(&recorded);
+
+    let cipher = Aes256Gcm::from_key(&[0x42; 32]).await.unwrap();
+    let nonce = Nonce::<Aes256Gcm>::generate().unwrap();
+    let encrypted = cipher.encrypt(&nonce, b"hello").await.unwrap();
+    cipher.decrypt(&nonce, &encrypted[1..]).await.unwrap_err();
+    let hmac = Hmac::generate(Hash::Sha256).await.unwrap();
+    let signature = hmac.sign(b"hello world").await.unwrap();
+    assert!(!hmac.verify(&signature, b"hello").await.unwrap());
+    Hash::Sha256.digest(b"abc").await.unwrap();
+    audit::clear_audit_sink();
+    cipher.encrypt(&nonce, b"hello").await.unwrap();
+
+    let recorded = recorded.borrow();
+    let operations = recorded.iter().map(|entry| entry.0).collect::<Vec<_>>();
+    assert_eq!(
+        operations,
+        [
+            Operation::ImportKey,
+            Operation::Encrypt,
+            Operation::Decrypt,
+            Operation::GenerateKey,
+            Operation::Sign,
+            Operation::Verify,
+            Operation::Digest,
+        ]
+    );
+    assert_eq!(recorded[0].1, "AES-GCM");
+    assert_eq!(recorded[1].3, Some(5));
+    assert_eq!(recorded[1].4, Outcome::Success);
+    assert_eq!(
+        recorded[2].4,
+        Outcome::Failure {
+            code: "decryption.operation"
+        }
+    );
+    assert_eq!(recorded[3].1, "HMAC");
+    assert_eq!(recorded[4].3, Some(11));
+    assert_eq!(recorded[5].4, Outcome::Success);
+    assert_eq!(recorded[6].1, "SHA-256");
+    assert!(recorded.iter().all(|entry| entry.2.is_none()));
+}
+
+#[wasm_bindgen_test]
+async fn should_record_key_ids() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Tagged::new("data-1", Aes256Gcm::from_key(&[0x42; 32]).await.unwrap());
+    let kek = Tagged::new("kek-1", Aes256Kw::from_key(&[0x24; 32]).await.unwrap());
+    let hmac = Hmac::generate(Hash::Sha256).await.unwrap();
+
+    let recorded = Rc::new(RefCell::new(Vec::new()));
+    record_into(&recorded);
+
+    let nonce = Nonce::generate().unwrap();
+    cipher.encrypt(&nonce, b"hello").await.unwrap();
+    let wrapped = kek.wrap_key(cipher.inner()).await.unwrap();
+    let _: Aes256Gcm = kek.unwrap_key(&wrapped).await.unwrap();
+    audit::with_kid("mac-1", hmac.sign(b"hello")).await.unwrap();
+    hmac.sign(b"hello").await.unwrap();
+    audit::clear_audit_sink();
+
+    let recorded = recorded.borrow();
+    let kids = recorded
+        .iter()
+        .map(|entry| (entry.0, entry.2.as_deref()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kids,
+        [
+            (Operation::Encrypt, Some("data-1")),
+            (Operation::WrapKey, Some("kek-1")),
+            (Operation::UnwrapKey, Some("kek-1")),
+            (Operation::Sign, Some("mac-1")),
+            (Operation::Sign, None),
+        ]
+    );
+    assert_eq!(recorded[1].1, "AES-KW");
+    assert_eq!(recorded[2].3, Some(wrapped.len()));
+}
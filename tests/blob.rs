@@ -0,0 +1,186 @@
+//! Test suite for the Blob encryption helpers.
+
+#![cfg(all(target_arch = "wasm32", feature = "streams"))]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::blob::{
+    decrypt_blob, decrypt_blob_abortable, decrypt_blob_with_progress, encrypt_blob,
+    encrypt_blob_abortable, encrypt_blob_with_progress,
+};
+#[cfg(feature = "incremental-digest")]
+use browser_crypto::hash::Hash;
+use browser_crypto::stream::{StreamError, CHUNK_SIZE, PREFIX_SIZE, TAG_SIZE};
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::*;
+use web_sys::{AbortController, Blob};
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+fn to_blob(data: &[u8]) -> Blob {
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(data));
+    Blob::new_with_u8_array_sequence(&parts).unwrap()
+}
+
+async fn read_blob(blob: &Blob) -> Vec<u8> {
+    let buffer = JsFuture::from(blob.array_buffer()).await.unwrap();
+    js_sys::Uint8Array::new(&buffer).to_vec()
+}
+
+#[wasm_bindgen_test]
+async fn should_encrypt_and_decrypt_blob() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let payload: Vec<u8> = (0..CHUNK_SIZE * 2 + 100).map(|idx| idx as u8).collect();
+
+    let encrypted = encrypt_blob(&cipher, &to_blob(&payload)).await.unwrap();
+    assert_eq!(encrypted.type_(), "application/octet-stream");
+    assert_eq!(
+        encrypted.size() as usize,
+        PREFIX_SIZE + payload.len() + 3 * TAG_SIZE
+    );
+
+    let decrypted = decrypt_blob(&cipher, &encrypted, "text/plain")
+        .await
+        .unwrap();
+    assert_eq!(decrypted.type_(), "text/plain");
+    assert_eq!(read_blob(&decrypted).await, payload);
+}
+
+#[wasm_bindgen_test]
+async fn should_encrypt_and_decrypt_exact_chunks() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let payload = vec![42u8; CHUNK_SIZE * 2];
+
+    let encrypted = encrypt_blob(&cipher, &to_blob(&payload)).await.unwrap();
+    assert_eq!(
+        encrypted.size() as usize,
+        PREFIX_SIZE + payload.len() + 2 * TAG_SIZE
+    );
+    let decrypted = decrypt_blob(&cipher, &encrypted, "").await.unwrap();
+    assert_eq!(read_blob(&decrypted).await, payload);
+}
+
+#[wasm_bindgen_test]
+async fn should_encrypt_and_decrypt_empty_blob() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let encrypted = encrypt_blob(&cipher, &to_blob(&[])).await.unwrap();
+    assert_eq!(encrypted.size() as usize, PREFIX_SIZE + TAG_SIZE);
+
+    let decrypted = decrypt_blob(&cipher, &encrypted, "").await.unwrap();
+    assert_eq!(decrypted.size(), 0.0);
+}
+
+#[wasm_bindgen_test]
+async fn should_fail_with_truncated_blob() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let payload = vec![42u8; CHUNK_SIZE + 10];
+    let encrypted = read_blob(&encrypt_blob(&cipher, &to_blob(&payload)).await.unwrap()).await;
+
+    // without the last chunk, the first one is decrypted as the last one
+    let truncated = to_blob(&encrypted[..PREFIX_SIZE + CHUNK_SIZE + TAG_SIZE]);
+    let err = decrypt_blob(&cipher, &truncated, "").await.unwrap_err();
+    assert!(matches!(err, StreamError::Decryption(_)));
+
+    let err = decrypt_blob(&cipher, &to_blob(&encrypted[..3]), "")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, StreamError::InvalidPrefix));
+}
+
+#[wasm_bindgen_test]
+async fn should_fail_with_another_key() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let other = Aes256Gcm::from_key(&[2; 32]).await.unwrap();
+    let encrypted = encrypt_blob(&cipher, &to_blob(b"Hello World!"))
+        .await
+        .unwrap();
+    let err = decrypt_blob(&other, &encrypted, "").await.unwrap_err();
+    assert!(matches!(err, StreamError::Decryption(_)));
+}
+
+#[wasm_bindgen_test]
+async fn should_report_blob_progress() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let payload = vec![42u8; CHUNK_SIZE * 2 + 100];
+    let total = payload.len() as u64;
+
+    let mut progress = Vec::new();
+    let encrypted = encrypt_blob_with_progress(&cipher, &to_blob(&payload), |done, total| {
+        progress.push((done, total))
+    })
+    .await
+    .unwrap();
+    let chunk = CHUNK_SIZE as u64;
+    assert_eq!(
+        progress,
+        vec![(chunk, total), (2 * chunk, total), (total, total)]
+    );
+
+    let mut progress = Vec::new();
+    decrypt_blob_with_progress(&cipher, &encrypted, "", |done, total| {
+        progress.push((done, total))
+    })
+    .await
+    .unwrap();
+    let size = encrypted.size() as u64;
+    assert_eq!(progress.len(), 3);
+    assert_eq!(progress.last(), Some(&(size, size)));
+}
+
+#[wasm_bindgen_test]
+async fn should_abort_blob_encryption() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let payload = vec![42u8; CHUNK_SIZE * 3];
+    let blob = to_blob(&payload);
+
+    let controller = AbortController::new().unwrap();
+    let mut calls = 0;
+    let err = encrypt_blob_abortable(&cipher, &blob, &controller.signal(), |_, _| {
+        calls += 1;
+        controller.abort();
+    })
+    .await
+    .unwrap_err();
+    assert!(matches!(err, StreamError::Aborted));
+    assert_eq!(calls, 1);
+
+    let encrypted = encrypt_blob(&cipher, &blob).await.unwrap();
+    let err = decrypt_blob_abortable(&cipher, &encrypted, "", &controller.signal(), |_, _| {})
+        .await
+        .unwrap_err();
+    assert!(matches!(err, StreamError::Aborted));
+
+    let controller = AbortController::new().unwrap();
+    let decrypted =
+        decrypt_blob_abortable(&cipher, &encrypted, "", &controller.signal(), |_, _| {})
+            .await
+            .unwrap();
+    assert_eq!(read_blob(&decrypted).await, payload);
+}
+
+#[cfg(feature = "incremental-digest")]
+#[wasm_bindgen_test]
+async fn should_digest_blob() {
+    console_error_panic_hook::set_once();
+
+    let payload: Vec<u8> = (0..3 * 1024 * 1024 + 10).map(|idx| idx as u8).collect();
+    let digest = browser_crypto::blob::digest_blob(Hash::Sha256, &to_blob(&payload))
+        .await
+        .unwrap();
+    assert_eq!(digest, Hash::Sha256.digest(&payload).await.unwrap());
+}
@@ -0,0 +1,34 @@
+//! Test suite for the `bytes` integration.
+
+#![cfg(all(target_arch = "wasm32", feature = "bytes"))]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::Algorithm;
+use browser_crypto::hash::Hash;
+use bytes::{Bytes, BytesMut};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_encrypt_and_decrypt_bytes() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let nonce = Aes256Gcm::generate_nonce().unwrap();
+    let payload = Bytes::from_static(b"Hello World!");
+
+    let encrypted = cipher.encrypt_bytes(&nonce, &payload).await.unwrap();
+    assert_eq!(encrypted, cipher.encrypt(&nonce, &payload).await.unwrap());
+
+    let mut buffer = BytesMut::new();
+    buffer.extend_from_slice(&encrypted);
+    let decrypted = cipher.decrypt_bytes(&nonce, &buffer).await.unwrap();
+    assert_eq!(decrypted, payload);
+    assert_eq!(
+        Hash::Sha256.digest(&decrypted).await.unwrap(),
+        Hash::Sha256.digest(b"Hello World!").await.unwrap()
+    );
+}
@@ -0,0 +1,84 @@
+//! Test suite for the key-committing AES-GCM.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::algorithm::{Algorithm, DecryptionError, Nonce};
+use browser_crypto::committing::{CommittingAes128Gcm, CommittingAes256Gcm};
+use browser_crypto::hash::Hash;
+use browser_crypto::hkdf::Hkdf;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+async fn hkdf(key: &[u8]) -> Hkdf {
+    Hkdf::from_key(key, Hash::Sha256, b"salt", b"")
+        .await
+        .unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn should_encrypt_and_decrypt() {
+    console_error_panic_hook::set_once();
+
+    let cipher = CommittingAes256Gcm::derive_from(&hkdf(&[1; 32]).await)
+        .await
+        .unwrap();
+    let nonce = CommittingAes256Gcm::generate_nonce().unwrap();
+    let encrypted = cipher.encrypt(&nonce, b"Hello World!").await.unwrap();
+    assert_eq!(encrypted.len(), 12 + 16 + 32);
+    let decrypted = cipher.decrypt(&nonce, &encrypted).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+
+    // the underlying cipher decrypts the payload without its commitment
+    let aes_nonce = Nonce::from_slice(&nonce.to_vec()).unwrap();
+    let decrypted = cipher
+        .cipher()
+        .decrypt(&aes_nonce, &encrypted[..28])
+        .await
+        .unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+
+    let sealed = cipher.seal(b"Hello World!").await.unwrap();
+    assert_eq!(cipher.open(&sealed).await.unwrap(), b"Hello World!");
+}
+
+#[wasm_bindgen_test]
+async fn should_reject_another_key() {
+    console_error_panic_hook::set_once();
+
+    let cipher = CommittingAes128Gcm::derive_from(&hkdf(&[1; 32]).await)
+        .await
+        .unwrap();
+    let other = CommittingAes128Gcm::derive_from(&hkdf(&[2; 32]).await)
+        .await
+        .unwrap();
+    let sealed = cipher.seal(b"Hello World!").await.unwrap();
+    let err = other.open(&sealed).await.unwrap_err();
+    assert!(matches!(err, DecryptionError::KeyCommitment));
+}
+
+#[wasm_bindgen_test]
+async fn should_reject_altered_payload() {
+    console_error_panic_hook::set_once();
+
+    let cipher = CommittingAes256Gcm::derive_from(&hkdf(&[1; 32]).await)
+        .await
+        .unwrap();
+    let nonce = CommittingAes256Gcm::generate_nonce().unwrap();
+    let mut encrypted = cipher.encrypt(&nonce, b"Hello World!").await.unwrap();
+
+    let err = cipher.decrypt(&nonce, &encrypted[..31]).await.unwrap_err();
+    assert!(matches!(err, DecryptionError::Truncated));
+
+    let last = encrypted.len() - 1;
+    encrypted[last] ^= 1;
+    let err = cipher.decrypt(&nonce, &encrypted).await.unwrap_err();
+    assert!(matches!(err, DecryptionError::KeyCommitment));
+
+    encrypted[last] ^= 1;
+    encrypted[0] ^= 1;
+    let err = cipher.decrypt(&nonce, &encrypted).await.unwrap_err();
+    assert!(matches!(err, DecryptionError::Operation));
+}
@@ -74,3 +74,122 @@ async fn webcrypto_should_decrypt_pure() {
 
     assert_eq!(decrypted, clear_msg);
 }
+
+#[wasm_bindgen_test]
+async fn webcrypto_should_decrypt_pure_with_long_nonce() {
+    use aes_gcm::aead::consts::U16;
+
+    console_error_panic_hook::set_once();
+
+    let clear_msg = b"Hello World!";
+    let nonce = [7u8; 16];
+
+    let pure = aes_gcm::AesGcm::<aes_gcm::aes::Aes256, U16>::new_from_slice(&DEFAULT_KEY).unwrap();
+    let pure_nonce = aes_gcm::Nonce::<U16>::from_slice(&nonce);
+    let encrypted = pure.encrypt(pure_nonce, clear_msg.as_ref()).unwrap();
+
+    let browser = browser_crypto::aes256gcm::Aes256Gcm::from_key(&DEFAULT_KEY)
+        .await
+        .unwrap();
+    let browser_nonce = browser_crypto::algorithm::Nonce::from_slice_unchecked_len(&nonce).unwrap();
+    assert!(matches!(
+        browser_crypto::algorithm::Nonce::<browser_crypto::aes256gcm::Aes256Gcm>::from_slice_unchecked_len(&[]),
+        Err(browser_crypto::algorithm::NonceError::Empty)
+    ));
+    let decrypted = browser
+        .decrypt(&browser_nonce, encrypted.as_ref())
+        .await
+        .unwrap();
+
+    assert_eq!(decrypted, clear_msg);
+}
+
+#[wasm_bindgen_test]
+async fn should_have_the_same_encrypted_output_with_128_bits_key() {
+    console_error_panic_hook::set_once();
+
+    let key = [42u8; 16];
+    let clear_msg = b"Hello World!";
+    let browser = browser_crypto::aes_gcm::Aes128Gcm::from_key(&key)
+        .await
+        .unwrap();
+    let browser_nonce = browser_crypto::algorithm::Nonce::from_slice(&[0; 12]).unwrap();
+    let browser_encrypted = browser.encrypt(&browser_nonce, clear_msg).await.unwrap();
+
+    let pure = aes_gcm::Aes128Gcm::new_from_slice(&key).unwrap();
+    let pure_nonce = aes_gcm::Nonce::default();
+    let pure_encrypted = pure.encrypt(&pure_nonce, clear_msg.as_ref()).unwrap();
+
+    assert_eq!(browser_encrypted, pure_encrypted);
+}
+
+#[wasm_bindgen_test]
+async fn should_have_the_same_gmac_tag() {
+    console_error_panic_hook::set_once();
+
+    let metadata = b"{\"name\":\"file.txt\"}";
+    let browser = browser_crypto::aes256gcm::Aes256Gcm::from_key(&DEFAULT_KEY)
+        .await
+        .unwrap();
+    let browser_nonce = browser_crypto::algorithm::Nonce::from_slice(&[0; 12]).unwrap();
+    let browser_tag = browser
+        .authenticate(&browser_nonce, metadata)
+        .await
+        .unwrap();
+
+    let pure = aes_gcm::Aes256Gcm::new_from_slice(&DEFAULT_KEY).unwrap();
+    let pure_nonce = aes_gcm::Nonce::default();
+    let payload = aes_gcm::aead::Payload {
+        msg: &[],
+        aad: metadata,
+    };
+    let pure_tag = pure.encrypt(&pure_nonce, payload).unwrap();
+
+    assert_eq!(browser_tag, pure_tag);
+}
+
+#[wasm_bindgen_test]
+async fn should_have_the_same_output_with_additional_data() {
+    console_error_panic_hook::set_once();
+
+    let clear_msg = b"Hello World!";
+    let browser = browser_crypto::aes256gcm::Aes256Gcm::from_key(&DEFAULT_KEY)
+        .await
+        .unwrap();
+    let browser_nonce = browser_crypto::algorithm::Nonce::from_slice(&[0; 12]).unwrap();
+    let browser_encrypted = browser
+        .encrypt_payload(
+            &browser_nonce,
+            browser_crypto::algorithm::Payload {
+                msg: clear_msg,
+                aad: b"user-42",
+            },
+        )
+        .await
+        .unwrap();
+
+    let pure = aes_gcm::Aes256Gcm::new_from_slice(&DEFAULT_KEY).unwrap();
+    let pure_nonce = aes_gcm::Nonce::default();
+    let payload = aes_gcm::aead::Payload {
+        msg: clear_msg,
+        aad: b"user-42",
+    };
+    let pure_encrypted = pure.encrypt(&pure_nonce, payload).unwrap();
+    assert_eq!(browser_encrypted, pure_encrypted);
+
+    let decrypted = browser
+        .decrypt_payload(
+            &browser_nonce,
+            browser_crypto::algorithm::Payload {
+                msg: &browser_encrypted,
+                aad: b"user-42",
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(decrypted, clear_msg);
+    assert!(browser
+        .decrypt(&browser_nonce, &browser_encrypted)
+        .await
+        .is_err());
+}
@@ -0,0 +1,44 @@
+//! Test suite for the operation context of the errors.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use std::error::Error as _;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::{Algorithm, DecryptionError};
+use browser_crypto::context::{ErrorContext, Operation};
+use browser_crypto::padding::Padded;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+fn should_expose_algorithm_name() {
+    assert_eq!(Aes256Gcm::NAME, "AES-GCM");
+    assert_eq!(Padded::<Aes256Gcm>::NAME, "AES-GCM");
+}
+
+#[wasm_bindgen_test]
+async fn should_add_operation_context() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::generate(false).await.unwrap();
+    let err = cipher
+        .open(&[0; 4])
+        .await
+        .context(Operation::Decrypt, Aes256Gcm::NAME)
+        .unwrap_err();
+    assert_eq!(err.operation, Operation::Decrypt);
+    assert_eq!(err.algorithm, "AES-GCM");
+    assert_eq!(
+        err.to_string(),
+        "decrypt with AES-GCM failed: the sealed payload is truncated"
+    );
+    assert!(err.source().is_some());
+    assert!(matches!(err.into_inner(), DecryptionError::Truncated));
+
+    let ok: Result<u8, DecryptionError> = Ok(1);
+    assert_eq!(ok.context(Operation::Decrypt, "AES-GCM").unwrap(), 1);
+}
@@ -0,0 +1,94 @@
+//! Test suite for the use of CryptoKeys created outside of the crate.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::{Aes128Gcm, Aes256Gcm};
+use browser_crypto::algorithm::Algorithm;
+use browser_crypto::key::CryptoKeyError;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+fn subtle() -> web_sys::SubtleCrypto {
+    js_sys::Reflect::get(&js_sys::global(), &"crypto".into())
+        .unwrap()
+        .unchecked_into::<web_sys::Crypto>()
+        .subtle()
+}
+
+async fn generate_key(name: &str, length: u32, usages: &[&str]) -> web_sys::CryptoKey {
+    let algorithm = js_sys::Object::new();
+    js_sys::Reflect::set(&algorithm, &"name".into(), &name.into()).unwrap();
+    js_sys::Reflect::set(&algorithm, &"length".into(), &length.into()).unwrap();
+    let usages = usages
+        .iter()
+        .map(|usage| JsValue::from_str(usage))
+        .collect::<js_sys::Array>();
+    let promise = subtle()
+        .generate_key_with_object(&algorithm, false, &usages)
+        .unwrap();
+    JsFuture::from(promise).await.unwrap().unchecked_into()
+}
+
+#[wasm_bindgen_test]
+async fn should_use_existing_crypto_key() {
+    console_error_panic_hook::set_once();
+
+    let key = generate_key("AES-GCM", 256, &["encrypt", "decrypt"]).await;
+    let cipher = Aes256Gcm::from_crypto_key(key).unwrap();
+
+    let nonce = Aes256Gcm::generate_nonce().unwrap();
+    let encrypted = cipher.encrypt(&nonce, b"Hello World!").await.unwrap();
+    let decrypted = cipher.decrypt(&nonce, &encrypted).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+}
+
+#[wasm_bindgen_test]
+async fn should_reject_mismatching_crypto_key() {
+    console_error_panic_hook::set_once();
+
+    let key = generate_key("AES-CBC", 256, &["encrypt", "decrypt"]).await;
+    assert_eq!(
+        Aes256Gcm::from_crypto_key(key).unwrap_err(),
+        CryptoKeyError::InvalidAlgorithm {
+            expected: "AES-GCM",
+            found: "AES-CBC".into(),
+        }
+    );
+
+    let key = generate_key("AES-GCM", 128, &["encrypt", "decrypt"]).await;
+    assert_eq!(
+        Aes256Gcm::from_crypto_key(key.clone()).unwrap_err(),
+        CryptoKeyError::InvalidLength {
+            expected: 256,
+            found: 128,
+        }
+    );
+    assert!(Aes128Gcm::from_crypto_key(key).is_ok());
+
+    let key = generate_key("AES-GCM", 256, &["encrypt"]).await;
+    assert_eq!(
+        Aes256Gcm::from_crypto_key(key).unwrap_err(),
+        CryptoKeyError::MissingUsage("decrypt")
+    );
+}
+
+#[wasm_bindgen_test]
+async fn should_expose_crypto_key() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::generate(false).await.unwrap();
+    let key = cipher.as_crypto_key().unwrap();
+    assert_eq!(key.type_(), "secret");
+    assert!(!key.extractable());
+
+    let nonce = Aes256Gcm::generate_nonce().unwrap();
+    let encrypted = cipher.encrypt(&nonce, b"Hello World!").await.unwrap();
+    let other = Aes256Gcm::from_crypto_key(key.clone()).unwrap();
+    let decrypted = other.decrypt(&nonce, &encrypted).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+}
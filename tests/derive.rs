@@ -0,0 +1,79 @@
+//! Test suite for the generic derivation functions.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::derive::{derive_bits, import_base_key, DeriveError};
+use browser_crypto::hash::Hash;
+use browser_crypto::pbkdf2::Pbkdf2;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+fn hkdf_params(salt: &[u8], info: &[u8]) -> js_sys::Object {
+    let params = js_sys::Object::new();
+    js_sys::Reflect::set(&params, &"name".into(), &"HKDF".into()).unwrap();
+    js_sys::Reflect::set(&params, &"hash".into(), &"SHA-256".into()).unwrap();
+    js_sys::Reflect::set(&params, &"salt".into(), &js_sys::Uint8Array::from(salt)).unwrap();
+    js_sys::Reflect::set(&params, &"info".into(), &js_sys::Uint8Array::from(info)).unwrap();
+    params
+}
+
+#[wasm_bindgen_test]
+async fn should_derive_hkdf_rfc_vector() {
+    // RFC 5869, A.1 Test Case 1
+    console_error_panic_hook::set_once();
+
+    let base_key = import_base_key("HKDF", &[0x0b; 22]).await.unwrap();
+    let params = hkdf_params(
+        &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c,
+        ],
+        &[0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9],
+    );
+    let derived = derive_bits(&params, &base_key, 42 * 8).await.unwrap();
+    assert_eq!(
+        derived,
+        [
+            0x3c, 0xb2, 0x5f, 0x25, 0xfa, 0xac, 0xd5, 0x7a, 0x90, 0x43, 0x4f, 0x64, 0xd0, 0x36,
+            0x2f, 0x2a, 0x2d, 0x2d, 0x0a, 0x90, 0xcf, 0x1a, 0x5a, 0x4c, 0x5d, 0xb0, 0x2d, 0x56,
+            0xec, 0xc4, 0xc5, 0xbf, 0x34, 0x00, 0x72, 0x08, 0xd5, 0xb8, 0x87, 0x18, 0x58, 0x65
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn should_match_pbkdf2_module() {
+    console_error_panic_hook::set_once();
+
+    let pbkdf2 = Pbkdf2::from_password(b"password", Hash::Sha256, b"salt", 10)
+        .await
+        .unwrap();
+    let expected = pbkdf2.derive_bits(256).await.unwrap();
+
+    let base_key = import_base_key("PBKDF2", b"password").await.unwrap();
+    let params = js_sys::Object::new();
+    js_sys::Reflect::set(&params, &"name".into(), &"PBKDF2".into()).unwrap();
+    js_sys::Reflect::set(&params, &"hash".into(), &"SHA-256".into()).unwrap();
+    js_sys::Reflect::set(
+        &params,
+        &"salt".into(),
+        &js_sys::Uint8Array::from(&b"salt"[..]),
+    )
+    .unwrap();
+    js_sys::Reflect::set(&params, &"iterations".into(), &10.into()).unwrap();
+    let derived = derive_bits(&params, &base_key, 256).await.unwrap();
+    assert_eq!(derived, expected);
+}
+
+#[wasm_bindgen_test]
+async fn should_fail_with_mismatching_algorithm() {
+    console_error_panic_hook::set_once();
+
+    let base_key = import_base_key("PBKDF2", b"password").await.unwrap();
+    let err = derive_bits(&hkdf_params(b"salt", b""), &base_key, 256)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DeriveError::InvalidAccess), "{err:?}");
+}
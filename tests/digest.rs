@@ -0,0 +1,67 @@
+//! Test suite for the compatibility with the digest crate.
+
+#![cfg(all(target_arch = "wasm32", feature = "digest"))]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::digest::{Sha1, Sha256, Sha512};
+use browser_crypto::encoding::hex_encode;
+use browser_crypto::hash::Hash;
+use digest::{OutputSizeUser, Update};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+/// Feeds a message to any hash function following the digest crate traits
+fn feed(hasher: &mut impl Update) {
+    hasher.update(b"Hello ");
+    hasher.update(b"World!");
+}
+
+#[wasm_bindgen_test]
+async fn should_compute_known_digests() {
+    console_error_panic_hook::set_once();
+
+    let digest = Sha256::digest(b"abc").await.unwrap();
+    assert_eq!(
+        hex_encode(&digest),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    let digest = Sha1::new_with_prefix("abc").finalize().await.unwrap();
+    assert_eq!(
+        hex_encode(&digest),
+        "a9993e364706816aba3e25717850c26c9cd0d89d"
+    );
+    assert_eq!(Sha512::output_size(), 64);
+    assert_eq!(<Sha512 as OutputSizeUser>::output_size(), 64);
+}
+
+#[wasm_bindgen_test]
+async fn should_buffer_the_updates() {
+    console_error_panic_hook::set_once();
+
+    let expected = Hash::Sha256.digest(b"Hello World!").await.unwrap();
+
+    let mut hasher = Sha256::new();
+    feed(&mut hasher);
+    assert_eq!(
+        hasher.clone().finalize().await.unwrap().as_slice(),
+        expected
+    );
+
+    let digest = Sha256::new()
+        .chain_update(b"Hello ")
+        .chain_update("World!")
+        .finalize()
+        .await
+        .unwrap();
+    assert_eq!(digest.as_slice(), expected);
+
+    let mut hasher = Sha256::default();
+    std::io::copy(&mut &b"Hello World!"[..], &mut hasher).unwrap();
+    assert_eq!(hasher.finalize_reset().await.unwrap().as_slice(), expected);
+    assert_eq!(
+        hasher.finalize().await.unwrap(),
+        Sha256::digest([]).await.unwrap()
+    );
+}
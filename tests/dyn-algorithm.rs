@@ -0,0 +1,78 @@
+//! Test suite for the ciphers chosen at runtime.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_cbc::Aes256Cbc;
+use browser_crypto::aes_gcm::{Aes128Gcm, Aes256Gcm};
+use browser_crypto::algorithm::{DecryptionError, EncryptionError};
+use browser_crypto::dyn_algorithm::DynAlgorithm;
+use browser_crypto::padding::{Padded, Padding};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+async fn ciphers() -> Vec<Box<dyn DynAlgorithm>> {
+    vec![
+        Box::new(Aes128Gcm::from_key(&[1u8; 16]).await.unwrap()),
+        Box::new(Aes256Gcm::from_key(&[2u8; 32]).await.unwrap()),
+        Box::new(Aes256Cbc::from_key(&[3u8; 32]).await.unwrap()),
+        Box::new(Padded::new(
+            Aes256Gcm::from_key(&[4u8; 32]).await.unwrap(),
+            Padding::Block(32),
+        )),
+    ]
+}
+
+#[wasm_bindgen_test]
+async fn should_describe_the_ciphers() {
+    console_error_panic_hook::set_once();
+
+    let ciphers = ciphers().await;
+    let described = ciphers
+        .iter()
+        .map(|cipher| (cipher.name(), cipher.key_size(), cipher.nonce_size()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        described,
+        [
+            ("AES-GCM", 16, 12),
+            ("AES-GCM", 32, 12),
+            ("AES-CBC", 32, 16),
+            ("AES-GCM", 32, 12),
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn should_seal_and_open_with_every_cipher() {
+    console_error_panic_hook::set_once();
+
+    for cipher in ciphers().await {
+        let sealed = cipher.seal(b"hello world").await.unwrap();
+        assert_eq!(cipher.open(&sealed).await.unwrap(), b"hello world");
+
+        let nonce = vec![7u8; cipher.nonce_size() as usize];
+        let encrypted = cipher.encrypt(&nonce, b"hello world").await.unwrap();
+        assert_eq!(
+            cipher.decrypt(&nonce, &encrypted).await.unwrap(),
+            b"hello world"
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+async fn should_reject_nonces_of_the_wrong_size() {
+    console_error_panic_hook::set_once();
+
+    let cipher: Box<dyn DynAlgorithm> = Box::new(Aes256Gcm::from_key(&[2u8; 32]).await.unwrap());
+    assert!(matches!(
+        cipher.encrypt(&[0u8; 8], b"hello").await,
+        Err(EncryptionError::Nonce(_))
+    ));
+    assert!(matches!(
+        cipher.decrypt(&[0u8; 8], b"hello").await,
+        Err(DecryptionError::Operation)
+    ));
+}
@@ -0,0 +1,133 @@
+//! Test suite for the ECDH implementation.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::curve::NamedCurve;
+use browser_crypto::derive::DeriveError;
+use browser_crypto::ecdh::{EcdhKeyPair, EcdhPrivateKey, EcdhPublicKey};
+use browser_crypto::key::ImportKeyError;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+// generated with the python cryptography package
+const PRIVATE_KEY: [u8; 138] = [
+    0x30, 0x81, 0x87, 0x02, 0x01, 0x00, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02,
+    0x01, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x04, 0x6d, 0x30, 0x6b, 0x02,
+    0x01, 0x01, 0x04, 0x20, 0x51, 0xa5, 0xef, 0x3f, 0xf1, 0x7a, 0x2f, 0xfc, 0x6f, 0xaf, 0xd4, 0xbb,
+    0x5a, 0x92, 0x3c, 0x39, 0x83, 0x87, 0xcf, 0xd3, 0x66, 0x63, 0x82, 0xdc, 0x0d, 0xbe, 0xf7, 0xd5,
+    0xd6, 0xfa, 0xdd, 0x2b, 0xa1, 0x44, 0x03, 0x42, 0x00, 0x04, 0x59, 0x3d, 0x82, 0x1c, 0xb8, 0xde,
+    0x5f, 0xe7, 0xc0, 0x41, 0xc3, 0x55, 0xa7, 0xb6, 0x4f, 0x41, 0x1e, 0xc5, 0x63, 0xac, 0x7d, 0x7e,
+    0x2c, 0xa4, 0xab, 0xe4, 0x0b, 0xe6, 0xe1, 0x76, 0x7e, 0x7c, 0x96, 0x93, 0xd1, 0x4d, 0xf3, 0x6f,
+    0xe8, 0x39, 0x14, 0x0a, 0x6f, 0xb4, 0xb6, 0xa4, 0xa8, 0xc9, 0x74, 0xa2, 0x54, 0xd3, 0x91, 0x82,
+    0xa6, 0x56, 0xcd, 0x16, 0x81, 0xe5, 0x15, 0xfa, 0x7f, 0x85,
+];
+const PEER_PUBLIC_KEY: [u8; 65] = [
+    0x04, 0x54, 0x2c, 0x8a, 0x69, 0x81, 0x20, 0xf7, 0x18, 0x33, 0x7c, 0xbe, 0x71, 0x96, 0x74, 0xc9,
+    0x1c, 0xa4, 0xa4, 0xeb, 0xf1, 0x1e, 0xcd, 0xb1, 0xda, 0x63, 0x4b, 0xeb, 0x80, 0xc2, 0xaa, 0xea,
+    0xbd, 0xe7, 0xc7, 0x9b, 0xa4, 0x8c, 0x81, 0xdd, 0x9f, 0xf3, 0xca, 0x2e, 0x1f, 0x83, 0x43, 0xc5,
+    0xfc, 0xec, 0x79, 0x35, 0x90, 0xda, 0xc9, 0xbe, 0x28, 0x73, 0xac, 0x81, 0xf5, 0x9f, 0x97, 0x54,
+    0x3f,
+];
+const SHARED_SECRET: [u8; 32] = [
+    0x1e, 0x77, 0x18, 0x15, 0xd2, 0xeb, 0x92, 0xaa, 0xd8, 0xc0, 0x03, 0x23, 0xa1, 0xd0, 0x5f, 0x2c,
+    0xb0, 0x3e, 0xc0, 0xe1, 0x8e, 0x15, 0x7f, 0xb2, 0x57, 0x82, 0x93, 0xc0, 0xa6, 0x55, 0xd2, 0xf4,
+];
+
+#[wasm_bindgen_test]
+async fn should_agree_on_shared_secret() {
+    console_error_panic_hook::set_once();
+
+    for curve in [NamedCurve::P256, NamedCurve::P384, NamedCurve::P521] {
+        let alice = EcdhKeyPair::generate(curve).await.unwrap();
+        let bob = EcdhKeyPair::generate(curve).await.unwrap();
+
+        let left = alice
+            .private_key
+            .diffie_hellman(&bob.public_key)
+            .await
+            .unwrap();
+        let right = bob
+            .private_key
+            .diffie_hellman(&alice.public_key)
+            .await
+            .unwrap();
+        assert_eq!(left.len(), curve.field_size());
+        assert_eq!(left, right);
+    }
+}
+
+#[wasm_bindgen_test]
+async fn should_match_reference_secret() {
+    console_error_panic_hook::set_once();
+
+    let private_key = EcdhPrivateKey::from_pkcs8(&PRIVATE_KEY, NamedCurve::P256)
+        .await
+        .unwrap();
+    let public_key = EcdhPublicKey::from_raw(&PEER_PUBLIC_KEY, NamedCurve::P256)
+        .await
+        .unwrap();
+    let secret = private_key.diffie_hellman(&public_key).await.unwrap();
+    assert_eq!(secret, SHARED_SECRET);
+}
+
+#[wasm_bindgen_test]
+async fn should_export_and_import_keys() {
+    console_error_panic_hook::set_once();
+
+    let alice = EcdhKeyPair::generate(NamedCurve::P384).await.unwrap();
+    let bob = EcdhKeyPair::generate(NamedCurve::P384).await.unwrap();
+    let expected = alice
+        .private_key
+        .diffie_hellman(&bob.public_key)
+        .await
+        .unwrap();
+
+    let spki = bob.public_key.to_spki().await.unwrap();
+    let public_key = EcdhPublicKey::from_spki(&spki, NamedCurve::P384)
+        .await
+        .unwrap();
+    let jwk = alice.private_key.to_jwk().await.unwrap();
+    let private_key = EcdhPrivateKey::from_jwk(&jwk, NamedCurve::P384)
+        .await
+        .unwrap();
+    assert_eq!(
+        private_key.diffie_hellman(&public_key).await.unwrap(),
+        expected
+    );
+
+    let pkcs8 = alice.private_key.to_pkcs8().await.unwrap();
+    let private_key = EcdhPrivateKey::from_pkcs8(&pkcs8, NamedCurve::P384)
+        .await
+        .unwrap();
+    assert_eq!(
+        private_key.diffie_hellman(&public_key).await.unwrap(),
+        expected
+    );
+
+    assert!(matches!(
+        EcdhPublicKey::from_spki(&spki, NamedCurve::P256)
+            .await
+            .unwrap_err(),
+        ImportKeyError::UnsupportedAlgorithm(_)
+    ));
+}
+
+#[wasm_bindgen_test]
+async fn should_reject_mismatching_curves() {
+    console_error_panic_hook::set_once();
+
+    let alice = EcdhKeyPair::generate(NamedCurve::P256).await.unwrap();
+    let bob = EcdhKeyPair::generate(NamedCurve::P384).await.unwrap();
+    // Node.js reports an InvalidAccessException, not known by the crate
+    assert!(matches!(
+        alice
+            .private_key
+            .diffie_hellman(&bob.public_key)
+            .await
+            .unwrap_err(),
+        DeriveError::InvalidAccess | DeriveError::Generic(_)
+    ));
+}
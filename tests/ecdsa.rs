@@ -0,0 +1,114 @@
+//! Test suite for the ECDSA implementation.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::curve::NamedCurve;
+use browser_crypto::ecdsa::{EcdsaKeyPair, EcdsaPrivateKey, EcdsaPublicKey};
+use browser_crypto::hash::Hash;
+use browser_crypto::jwk::{EcJwk, Jwk};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+// P-256 public key generated with Node.js
+const JWK_X: &str = "qhqa58szR4UIWmc0muIE9pdcorgLKlmN4Wzy3cve2oQ";
+const JWK_Y: &str = "fE1iyw_fEpol_c6YOdkVIOqK_4-gtJ8kvbx-S1YqozA";
+// ES256 signature of "Hello World!" generated with Node.js
+const SIGNATURE: [u8; 64] = [
+    0x13, 0x86, 0xad, 0x1a, 0xa0, 0xef, 0x0f, 0xbb, 0xbd, 0x7d, 0xbd, 0xc7, 0x14, 0x8e, 0x68, 0x67,
+    0xf2, 0xfc, 0xce, 0xd5, 0xb5, 0xab, 0x42, 0xf3, 0xf0, 0x71, 0xfb, 0x4c, 0x3b, 0x12, 0xa7, 0x60,
+    0xa4, 0xbc, 0x39, 0x50, 0xb3, 0x04, 0x92, 0x55, 0xe8, 0x04, 0x05, 0xfe, 0x07, 0x41, 0x86, 0xdd,
+    0xed, 0x6b, 0x72, 0xf1, 0x90, 0xcf, 0xc7, 0x29, 0x7f, 0xa0, 0x76, 0xe6, 0xfd, 0xc9, 0xc8, 0x9b,
+];
+
+#[wasm_bindgen_test]
+async fn should_sign_and_verify_on_all_curves() {
+    console_error_panic_hook::set_once();
+
+    for (curve, hash) in [
+        (NamedCurve::P256, Hash::Sha256),
+        (NamedCurve::P384, Hash::Sha384),
+        (NamedCurve::P521, Hash::Sha512),
+    ] {
+        let pair = EcdsaKeyPair::generate(curve, hash).await.unwrap();
+        let signature = pair.private_key.sign(b"Hello World!").await.unwrap();
+        assert_eq!(signature.len(), curve.field_size() * 2);
+
+        assert!(pair
+            .public_key
+            .verify(&signature, b"Hello World!")
+            .await
+            .unwrap());
+        assert!(!pair
+            .public_key
+            .verify(&signature, b"Hello World?")
+            .await
+            .unwrap());
+    }
+}
+
+#[wasm_bindgen_test]
+async fn should_import_exported_keys() {
+    console_error_panic_hook::set_once();
+
+    let pair = EcdsaKeyPair::generate(NamedCurve::P256, Hash::Sha256)
+        .await
+        .unwrap();
+    let raw = pair.public_key.to_raw().await.unwrap();
+    assert_eq!(raw.len(), 65);
+    let spki = pair.public_key.to_spki().await.unwrap();
+    let pkcs8 = pair.private_key.to_pkcs8().await.unwrap();
+
+    let private_key = EcdsaPrivateKey::from_pkcs8(&pkcs8, NamedCurve::P256, Hash::Sha256)
+        .await
+        .unwrap();
+    let signature = private_key.sign(b"Hello World!").await.unwrap();
+
+    let public_key = EcdsaPublicKey::from_spki(&spki, NamedCurve::P256, Hash::Sha256)
+        .await
+        .unwrap();
+    assert!(public_key
+        .verify(&signature, b"Hello World!")
+        .await
+        .unwrap());
+    let public_key = EcdsaPublicKey::from_raw(&raw, NamedCurve::P256, Hash::Sha256)
+        .await
+        .unwrap();
+    assert!(public_key
+        .verify(&signature, b"Hello World!")
+        .await
+        .unwrap());
+}
+
+#[wasm_bindgen_test]
+async fn should_verify_with_jwk() {
+    console_error_panic_hook::set_once();
+
+    let jwk = Jwk::from(EcJwk::new("P-256", JWK_X, JWK_Y));
+
+    let public_key = EcdsaPublicKey::from_jwk(&jwk, NamedCurve::P256, Hash::Sha256)
+        .await
+        .unwrap();
+    assert!(public_key
+        .verify(&SIGNATURE, b"Hello World!")
+        .await
+        .unwrap());
+}
+
+#[wasm_bindgen_test]
+async fn should_fail_importing_key_on_another_curve() {
+    console_error_panic_hook::set_once();
+
+    let pair = EcdsaKeyPair::generate(NamedCurve::P256, Hash::Sha256)
+        .await
+        .unwrap();
+    let spki = pair.public_key.to_spki().await.unwrap();
+
+    assert!(
+        EcdsaPublicKey::from_spki(&spki, NamedCurve::P384, Hash::Sha384)
+            .await
+            .is_err()
+    );
+}
@@ -0,0 +1,101 @@
+//! Test suite for the ECIES implementation.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::curve::NamedCurve;
+use browser_crypto::ecdh::{EcdhKeyPair, EcdhPrivateKey};
+use browser_crypto::ecies::{self, EciesError};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_encrypt_and_decrypt() {
+    console_error_panic_hook::set_once();
+
+    for curve in [NamedCurve::P256, NamedCurve::P384, NamedCurve::P521] {
+        let pair = EcdhKeyPair::generate(curve).await.unwrap();
+        let ciphertext = ecies::encrypt(&pair.public_key, b"Hello World!")
+            .await
+            .unwrap();
+        // ephemeral public key, nonce, payload and tag
+        assert_eq!(ciphertext.len(), 1 + 2 * curve.field_size() + 12 + 12 + 16);
+        let plaintext = ecies::decrypt(&pair.private_key, &ciphertext)
+            .await
+            .unwrap();
+        assert_eq!(plaintext, b"Hello World!");
+    }
+}
+
+#[wasm_bindgen_test]
+async fn should_use_new_ephemeral_key() {
+    console_error_panic_hook::set_once();
+
+    let pair = EcdhKeyPair::generate(NamedCurve::P256).await.unwrap();
+    let first = ecies::encrypt(&pair.public_key, b"Hello World!")
+        .await
+        .unwrap();
+    let second = ecies::encrypt(&pair.public_key, b"Hello World!")
+        .await
+        .unwrap();
+    assert_ne!(first[..65], second[..65]);
+}
+
+#[wasm_bindgen_test]
+async fn should_decrypt_with_imported_key() {
+    console_error_panic_hook::set_once();
+
+    let pair = EcdhKeyPair::generate(NamedCurve::P256).await.unwrap();
+    let ciphertext = ecies::encrypt(&pair.public_key, b"Hello World!")
+        .await
+        .unwrap();
+    let pkcs8 = pair.private_key.to_pkcs8().await.unwrap();
+    let private_key = EcdhPrivateKey::from_pkcs8(&pkcs8, NamedCurve::P256)
+        .await
+        .unwrap();
+    let plaintext = ecies::decrypt(&private_key, &ciphertext).await.unwrap();
+    assert_eq!(plaintext, b"Hello World!");
+}
+
+#[wasm_bindgen_test]
+async fn should_reject_invalid_ciphertext() {
+    console_error_panic_hook::set_once();
+
+    let pair = EcdhKeyPair::generate(NamedCurve::P256).await.unwrap();
+    let other = EcdhKeyPair::generate(NamedCurve::P256).await.unwrap();
+    let mut ciphertext = ecies::encrypt(&pair.public_key, b"Hello World!")
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        ecies::decrypt(&other.private_key, &ciphertext)
+            .await
+            .unwrap_err(),
+        EciesError::Decryption(_)
+    ));
+    assert!(matches!(
+        ecies::decrypt(&pair.private_key, &ciphertext[..70])
+            .await
+            .unwrap_err(),
+        EciesError::InvalidCiphertext
+    ));
+
+    let last = ciphertext.len() - 1;
+    ciphertext[last] ^= 1;
+    assert!(matches!(
+        ecies::decrypt(&pair.private_key, &ciphertext)
+            .await
+            .unwrap_err(),
+        EciesError::Decryption(_)
+    ));
+
+    let p384 = EcdhKeyPair::generate(NamedCurve::P384).await.unwrap();
+    let ciphertext = ecies::encrypt(&p384.public_key, b"Hello World!")
+        .await
+        .unwrap();
+    assert!(ecies::decrypt(&pair.private_key, &ciphertext)
+        .await
+        .is_err());
+}
@@ -0,0 +1,77 @@
+//! Test suite for the Ed25519 implementation.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::ed25519::{Ed25519KeyPair, Ed25519PrivateKey, Ed25519PublicKey};
+use browser_crypto::jwk::{Jwk, OkpJwk};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+// RFC 8032, 7.1 TEST 1
+const JWK_D: &str = "nWGxne_9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A";
+const JWK_X: &str = "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo";
+const SIGNATURE: [u8; 64] = [
+    0xe5, 0x56, 0x43, 0x00, 0xc3, 0x60, 0xac, 0x72, 0x90, 0x86, 0xe2, 0xcc, 0x80, 0x6e, 0x82, 0x8a,
+    0x84, 0x87, 0x7f, 0x1e, 0xb8, 0xe5, 0xd9, 0x74, 0xd8, 0x73, 0xe0, 0x65, 0x22, 0x49, 0x01, 0x55,
+    0x5f, 0xb8, 0x82, 0x15, 0x90, 0xa3, 0x3b, 0xac, 0xc6, 0x1e, 0x39, 0x70, 0x1c, 0xf9, 0xb4, 0x6b,
+    0xd2, 0x5b, 0xf5, 0xf0, 0x59, 0x5b, 0xbe, 0x24, 0x65, 0x51, 0x41, 0x43, 0x8e, 0x7a, 0x10, 0x0b,
+];
+
+#[wasm_bindgen_test]
+async fn should_sign_and_verify() {
+    console_error_panic_hook::set_once();
+    if !browser_crypto::ed25519::is_supported().await {
+        return;
+    }
+
+    let pair = Ed25519KeyPair::generate().await.unwrap();
+    let signature = pair.private_key.sign(b"Hello World!").await.unwrap();
+    assert_eq!(signature.len(), 64);
+
+    assert!(pair
+        .public_key
+        .verify(&signature, b"Hello World!")
+        .await
+        .unwrap());
+    assert!(!pair
+        .public_key
+        .verify(&signature, b"Hello World?")
+        .await
+        .unwrap());
+
+    let raw = pair.public_key.to_raw().await.unwrap();
+    let public_key = Ed25519PublicKey::from_raw(&raw).await.unwrap();
+    assert!(public_key
+        .verify(&signature, b"Hello World!")
+        .await
+        .unwrap());
+}
+
+#[wasm_bindgen_test]
+async fn should_match_rfc_vector() {
+    console_error_panic_hook::set_once();
+    if !browser_crypto::ed25519::is_supported().await {
+        return;
+    }
+
+    let jwk = Jwk::from(OkpJwk {
+        d: Some(JWK_D.into()),
+        ..OkpJwk::new("Ed25519", JWK_X)
+    });
+
+    let private_key = Ed25519PrivateKey::from_jwk(&jwk).await.unwrap();
+    let signature = private_key.sign(&[]).await.unwrap();
+    assert_eq!(signature, SIGNATURE);
+
+    let exported = private_key.to_jwk().await.unwrap();
+    let Jwk::Okp(exported) = exported else {
+        panic!("expected an OKP key");
+    };
+    assert_eq!(exported.x, JWK_X);
+    let public_jwk = Jwk::from(OkpJwk::new("Ed25519", exported.x));
+    let public_key = Ed25519PublicKey::from_jwk(&public_jwk).await.unwrap();
+    assert!(public_key.verify(&SIGNATURE, &[]).await.unwrap());
+}
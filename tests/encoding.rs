@@ -0,0 +1,80 @@
+//! Test suite for the text encodings.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::encoding::{self, EncodingError};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+fn should_encode_as_base64() {
+    console_error_panic_hook::set_once();
+
+    // RFC 4648 test vectors
+    let vectors = [
+        ("", "", ""),
+        ("f", "Zg==", "Zg"),
+        ("fo", "Zm8=", "Zm8"),
+        ("foo", "Zm9v", "Zm9v"),
+        ("foob", "Zm9vYg==", "Zm9vYg"),
+        ("fooba", "Zm9vYmE=", "Zm9vYmE"),
+        ("foobar", "Zm9vYmFy", "Zm9vYmFy"),
+    ];
+    for (data, padded, unpadded) in vectors {
+        assert_eq!(encoding::base64_encode(data.as_bytes()), padded);
+        assert_eq!(encoding::base64url_encode(data.as_bytes()), unpadded);
+        assert_eq!(encoding::base64_decode(padded).unwrap(), data.as_bytes());
+        assert_eq!(encoding::base64_decode(unpadded).unwrap(), data.as_bytes());
+        assert_eq!(
+            encoding::base64url_decode(unpadded).unwrap(),
+            data.as_bytes()
+        );
+    }
+
+    assert_eq!(encoding::base64_encode(&[0xfb, 0xff]), "+/8=");
+    assert_eq!(encoding::base64url_encode(&[0xfb, 0xff]), "-_8");
+}
+
+#[wasm_bindgen_test]
+fn should_reject_invalid_base64() {
+    console_error_panic_hook::set_once();
+
+    assert_eq!(
+        encoding::base64_decode("Zm9v-_8"),
+        Err(EncodingError::InvalidCharacter { position: 4 })
+    );
+    assert_eq!(
+        encoding::base64url_decode("Zm9v+/8"),
+        Err(EncodingError::InvalidCharacter { position: 4 })
+    );
+    assert_eq!(
+        encoding::base64_decode("Zg=a"),
+        Err(EncodingError::InvalidCharacter { position: 2 })
+    );
+    assert_eq!(
+        encoding::base64_decode("Zm9vY"),
+        Err(EncodingError::InvalidLength { length: 5 })
+    );
+}
+
+#[wasm_bindgen_test]
+fn should_encode_as_hex() {
+    console_error_panic_hook::set_once();
+
+    assert_eq!(encoding::hex_encode(&[0x00, 0x7f, 0xab, 0xff]), "007fabff");
+    assert_eq!(
+        encoding::hex_decode("007FabfF").unwrap(),
+        [0x00, 0x7f, 0xab, 0xff]
+    );
+    assert_eq!(
+        encoding::hex_decode("007g"),
+        Err(EncodingError::InvalidCharacter { position: 3 })
+    );
+    assert_eq!(
+        encoding::hex_decode("007"),
+        Err(EncodingError::InvalidLength { length: 3 })
+    );
+}
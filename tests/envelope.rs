@@ -0,0 +1,112 @@
+//! Test suite for the envelope encryption.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_kw::Aes256Kw;
+use browser_crypto::envelope::{Envelope, EnvelopeError};
+use browser_crypto::hash::Hash;
+use browser_crypto::key::UnwrapKeyError;
+use browser_crypto::kid::Tagged;
+use browser_crypto::rsa_oaep::RsaOaepKeyPair;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_seal_and_open_with_aes_kw() {
+    console_error_panic_hook::set_once();
+
+    let kek = Aes256Kw::from_key(&[1; 32]).await.unwrap();
+    let envelope = Envelope::seal(&kek, b"Hello World!").await.unwrap();
+    assert_eq!(envelope.wrapped_key().len(), 40);
+    assert_eq!(envelope.ciphertext().len(), 12 + 16);
+
+    assert_eq!(envelope.kid(), None);
+
+    let bytes = envelope.to_bytes();
+    assert_eq!(bytes.len(), 2 + 40 + 12 + 12 + 16);
+    assert_eq!(bytes[..2], [0, 40]);
+    let envelope = Envelope::from_bytes(&bytes).unwrap();
+    assert_eq!(envelope.open(&kek).await.unwrap(), b"Hello World!");
+
+    let other = Aes256Kw::from_key(&[2; 32]).await.unwrap();
+    let err = envelope.open(&other).await.unwrap_err();
+    assert!(matches!(
+        err,
+        EnvelopeError::Unwrap(UnwrapKeyError::Operation)
+    ));
+}
+
+#[wasm_bindgen_test]
+async fn should_seal_and_open_with_rsa_oaep() {
+    console_error_panic_hook::set_once();
+
+    let pair = RsaOaepKeyPair::generate(2048, Hash::Sha256).await.unwrap();
+    let envelope = Envelope::seal(&pair.public_key, b"Hello World!")
+        .await
+        .unwrap();
+    assert_eq!(envelope.wrapped_key().len(), 256);
+
+    let envelope = Envelope::from_bytes(&envelope.to_bytes()).unwrap();
+    assert_eq!(
+        envelope.open(&pair.private_key).await.unwrap(),
+        b"Hello World!"
+    );
+}
+
+#[wasm_bindgen_test]
+async fn should_use_a_new_key_per_envelope() {
+    console_error_panic_hook::set_once();
+
+    let kek = Aes256Kw::from_key(&[1; 32]).await.unwrap();
+    let first = Envelope::seal(&kek, b"Hello World!").await.unwrap();
+    let second = Envelope::seal(&kek, b"Hello World!").await.unwrap();
+    assert_ne!(first.wrapped_key(), second.wrapped_key());
+}
+
+#[wasm_bindgen_test]
+async fn should_record_the_kid_of_the_kek() {
+    console_error_panic_hook::set_once();
+
+    let kek = Tagged::new("2024", Aes256Kw::from_key(&[1; 32]).await.unwrap());
+    let envelope = Envelope::seal(&kek, b"Hello World!").await.unwrap();
+    assert_eq!(envelope.kid(), Some("2024"));
+
+    let bytes = envelope.to_bytes();
+    assert_eq!(bytes.len(), 2 + 1 + 4 + 40 + 12 + 12 + 16);
+    assert_eq!(bytes[..7], [0x80, 40, 4, b'2', b'0', b'2', b'4']);
+    let envelope = Envelope::from_bytes(&bytes).unwrap();
+    assert_eq!(envelope.kid(), Some("2024"));
+    assert_eq!(envelope.open(&kek).await.unwrap(), b"Hello World!");
+    // the key ID is only checked when both have one
+    assert_eq!(envelope.open(kek.inner()).await.unwrap(), b"Hello World!");
+
+    let other = Tagged::new("2025", Aes256Kw::from_key(&[1; 32]).await.unwrap());
+    let err = envelope.open(&other).await.unwrap_err();
+    assert!(matches!(err, EnvelopeError::KidMismatch(kid) if kid == "2024"));
+
+    let empty = Tagged::new("", Aes256Kw::from_key(&[1; 32]).await.unwrap());
+    let err = Envelope::seal(&empty, b"Hello World!").await.unwrap_err();
+    assert!(matches!(err, EnvelopeError::InvalidKid));
+}
+
+#[wasm_bindgen_test]
+fn should_reject_truncated_envelopes() {
+    for invalid in [
+        &[][..],
+        &[0],
+        &[0, 40, 1, 2, 3],
+        &[0, 0, 1, 2, 3],
+        &[0x80, 0],
+        &[0x80, 0, 0, 1, 2],
+        &[0x80, 0, 4, b'k', b'i', b'd'],
+        &[0x80, 0, 1, 0xff, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12],
+    ] {
+        assert!(matches!(
+            Envelope::from_bytes(invalid).unwrap_err(),
+            EnvelopeError::InvalidEnvelope
+        ));
+    }
+}
@@ -0,0 +1,43 @@
+//! Test suite for the stable codes of the errors.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::{Algorithm, DecryptionError, EncryptionError, NonceError};
+use browser_crypto::context::{ErrorContext, Operation};
+use browser_crypto::key::ImportKeyError;
+use browser_crypto::{Error, ErrorCode};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_report_error_codes() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::generate(false).await.unwrap();
+    let err = cipher.open(&[0; 32]).await.unwrap_err();
+    assert_eq!(err.code(), "decryption.operation");
+
+    let err = Aes256Gcm::from_key(&[0; 3]).await.unwrap_err();
+    assert_eq!(err.code(), "import_key.invalid_key_format");
+
+    let err = Aes256Gcm::from_hex_key("zz").await.unwrap_err();
+    assert_eq!(err.code(), "key.invalid_encoding");
+
+    let err = Err::<(), _>(DecryptionError::Truncated)
+        .context(Operation::Decrypt, Aes256Gcm::NAME)
+        .unwrap_err();
+    assert_eq!(err.code(), "decryption.truncated");
+}
+
+#[wasm_bindgen_test]
+fn should_report_wrapped_error_codes() {
+    let err = EncryptionError::from(NonceError::QuotaExceeded);
+    assert_eq!(err.code(), "nonce.quota_exceeded");
+
+    let err = ImportKeyError::from(Error::Unknown);
+    assert_eq!(err.code(), "generic.unknown");
+}
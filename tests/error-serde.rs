@@ -0,0 +1,59 @@
+//! Test suite for the serialization of errors.
+
+#![cfg(all(target_arch = "wasm32", feature = "serde"))]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::{Algorithm, DecryptionError, KeyError, Nonce, NonceError};
+use browser_crypto::encoding::EncodingError;
+use browser_crypto::key::ImportKeyError;
+use browser_crypto::Error;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_serialize_decryption_error() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::generate(false).await.unwrap();
+    let nonce = Nonce::<Aes256Gcm>::generate().unwrap();
+    let err = cipher.decrypt(&nonce, &[0; 32]).await.unwrap_err();
+    let json = serde_json::to_string(&err).unwrap();
+    assert_eq!(json, "\"Operation\"");
+
+    let decoded: DecryptionError = serde_json::from_str(&json).unwrap();
+    assert!(matches!(decoded, DecryptionError::Operation));
+}
+
+#[wasm_bindgen_test]
+fn should_serialize_nested_errors() {
+    console_error_panic_hook::set_once();
+
+    let err = ImportKeyError::from(KeyError::InvalidEncoding(EncodingError::InvalidCharacter {
+        position: 3,
+    }));
+    let json = serde_json::to_string(&err).unwrap();
+    assert_eq!(
+        json,
+        r#"{"RawKey":{"InvalidEncoding":{"InvalidCharacter":{"position":3}}}}"#
+    );
+    let decoded: ImportKeyError = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded.to_string(), err.to_string());
+
+    let err = NonceError::Generic(Error::DomException(
+        "NotSupportedError".into(),
+        "not supported".into(),
+    ));
+    let json = serde_json::to_string(&err).unwrap();
+    assert_eq!(
+        json,
+        r#"{"Generic":{"DomException":["NotSupportedError","not supported"]}}"#
+    );
+    let decoded: NonceError = serde_json::from_str(&json).unwrap();
+    assert!(matches!(
+        decoded,
+        NonceError::Generic(Error::DomException(name, _)) if name == "NotSupportedError"
+    ));
+}
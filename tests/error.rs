@@ -0,0 +1,92 @@
+//! Test suite for the conversion of JavaScript exceptions, and of the errors
+//! into JavaScript values.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::{Algorithm, DecryptionError};
+use browser_crypto::context::{ErrorContext, Operation};
+#[cfg(not(feature = "redact-errors"))]
+use browser_crypto::Error;
+use wasm_bindgen::{JsCast, JsError, JsValue};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+#[cfg(not(feature = "redact-errors"))]
+fn should_keep_exception_details() {
+    console_error_panic_hook::set_once();
+
+    let exception =
+        web_sys::DomException::new_with_message_and_name("not supported", "NotSupportedError")
+            .unwrap();
+    assert!(matches!(
+        Error::from(JsValue::from(exception)),
+        Error::DomException(name, message) if name == "NotSupportedError" && message == "not supported"
+    ));
+
+    let err = Error::from(JsValue::from(js_sys::TypeError::new("invalid argument")));
+    assert!(matches!(
+        &err,
+        Error::JsError { name, message } if name == "TypeError" && message == "invalid argument"
+    ));
+    assert_eq!(err.to_string(), "TypeError: invalid argument");
+}
+
+#[wasm_bindgen_test]
+#[cfg(not(feature = "redact-errors"))]
+fn should_keep_thrown_values() {
+    console_error_panic_hook::set_once();
+
+    assert!(matches!(
+        Error::from(JsValue::from_str("boom")),
+        Error::Thrown(value) if value == "boom"
+    ));
+    assert!(matches!(
+        Error::from(JsValue::from(42)),
+        Error::Thrown(value) if value == "42"
+    ));
+    assert!(matches!(Error::from(JsValue::UNDEFINED), Error::Unknown));
+}
+
+#[wasm_bindgen_test]
+async fn should_convert_into_js_error() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::generate(false).await.unwrap();
+    let err = cipher.open(&[0; 4]).await.unwrap_err();
+    let value = JsValue::from(err.clone());
+    let error = value.dyn_ref::<js_sys::Error>().unwrap();
+    assert_eq!(String::from(error.name()), "DecryptionError");
+    assert_eq!(
+        String::from(error.message()),
+        "the sealed payload is truncated"
+    );
+    let code = js_sys::Reflect::get(&value, &"code".into()).unwrap();
+    assert_eq!(code.as_string().unwrap(), "decryption.truncated");
+
+    let value = JsValue::from(
+        Err::<(), _>(err)
+            .context(Operation::Decrypt, Aes256Gcm::NAME)
+            .unwrap_err(),
+    );
+    let algorithm = js_sys::Reflect::get(&value, &"algorithm".into()).unwrap();
+    assert_eq!(algorithm.as_string().unwrap(), "AES-GCM");
+    let error = value.dyn_ref::<js_sys::Error>().unwrap();
+    assert_eq!(
+        String::from(error.message()),
+        "decrypt with AES-GCM failed: the sealed payload is truncated"
+    );
+}
+
+#[wasm_bindgen_test]
+fn should_convert_into_wasm_bindgen_error() {
+    fn exported() -> Result<(), JsError> {
+        Err(DecryptionError::Truncated)?;
+        Ok(())
+    }
+    assert!(exported().is_err());
+}
@@ -0,0 +1,39 @@
+//! Test suite for the getrandom backend.
+
+#![cfg(all(target_arch = "wasm32", feature = "getrandom"))]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::random;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+fn should_fill_buffer_for_getrandom() {
+    console_error_panic_hook::set_once();
+
+    let mut first = [0u8; 32];
+    let mut second = [0u8; 32];
+    random::getrandom_backend(&mut first).unwrap();
+    random::getrandom_backend(&mut second).unwrap();
+    assert_ne!(first, second);
+
+    let mut large = vec![0u8; random::MAX_CHUNK_SIZE + 1];
+    random::getrandom_backend(&mut large).unwrap();
+    assert!(
+        large[random::MAX_CHUNK_SIZE..]
+            .iter()
+            .any(|byte| *byte != 0)
+            || large[0] != 0
+    );
+}
+
+#[wasm_bindgen_test]
+fn should_generate_through_getrandom() {
+    console_error_panic_hook::set_once();
+
+    let mut buffer = [0u8; 32];
+    getrandom::getrandom(&mut buffer).unwrap();
+    assert!(buffer.iter().any(|byte| *byte != 0));
+}
@@ -0,0 +1,46 @@
+//! Test suite for the GMAC authentication with AES-GCM keys.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::{Aes128Gcm, Aes256Gcm};
+use browser_crypto::algorithm::Algorithm;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_authenticate_and_verify() {
+    console_error_panic_hook::set_once();
+
+    let key = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let nonce = Aes256Gcm::generate_nonce().unwrap();
+    let tag = key.authenticate(&nonce, b"metadata").await.unwrap();
+    assert_eq!(tag.len(), 16);
+    assert!(key.verify_tag(&nonce, &tag, b"metadata").await.unwrap());
+
+    assert!(!key.verify_tag(&nonce, &tag, b"altered").await.unwrap());
+    let other_nonce = Aes256Gcm::generate_nonce().unwrap();
+    assert!(!key
+        .verify_tag(&other_nonce, &tag, b"metadata")
+        .await
+        .unwrap());
+    assert!(!key
+        .verify_tag(&nonce, &tag[..8], b"metadata")
+        .await
+        .unwrap());
+    let other = Aes256Gcm::from_key(&[2; 32]).await.unwrap();
+    assert!(!other.verify_tag(&nonce, &tag, b"metadata").await.unwrap());
+}
+
+#[wasm_bindgen_test]
+async fn should_authenticate_empty_data() {
+    console_error_panic_hook::set_once();
+
+    let key = Aes128Gcm::generate(false).await.unwrap();
+    let nonce = Aes128Gcm::generate_nonce().unwrap();
+    let tag = key.authenticate(&nonce, b"").await.unwrap();
+    assert_eq!(tag.len(), 16);
+    assert!(key.verify_tag(&nonce, &tag, b"").await.unwrap());
+}
@@ -0,0 +1,36 @@
+//! Test suite for the incremental hashing.
+
+#![cfg(all(target_arch = "wasm32", feature = "incremental-digest"))]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::hash::Hash;
+use browser_crypto::hasher::Hasher;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_match_subtle_digest() {
+    console_error_panic_hook::set_once();
+
+    let data: Vec<u8> = (0..10_000).map(|idx| idx as u8).collect();
+    for hash in [Hash::Sha1, Hash::Sha256, Hash::Sha384, Hash::Sha512] {
+        let mut hasher = hash.hasher();
+        for chunk in data.chunks(333) {
+            hasher.update(chunk);
+        }
+        let digest = hasher.finalize();
+        assert_eq!(digest.len(), hash.output_size());
+        assert_eq!(digest, hash.digest(&data).await.unwrap());
+    }
+}
+
+#[wasm_bindgen_test]
+async fn should_hash_empty_input() {
+    console_error_panic_hook::set_once();
+
+    let hasher = Hasher::new(Hash::Sha256);
+    assert_eq!(hasher.hash(), Hash::Sha256);
+    assert_eq!(hasher.finalize(), Hash::Sha256.digest(&[]).await.unwrap());
+}
@@ -0,0 +1,100 @@
+//! Test suite for the HKDF implementation.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::aes_kw::Aes256Kw;
+use browser_crypto::algorithm::Algorithm;
+use browser_crypto::hash::Hash;
+use browser_crypto::hkdf::Hkdf;
+use browser_crypto::hmac::Hmac;
+use browser_crypto::key::{KeyWrapper, WrapKeyError};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_match_rfc_vector() {
+    // RFC 5869, A.1 Test Case 1
+    console_error_panic_hook::set_once();
+
+    let hkdf = Hkdf::from_key(
+        &[0x0b; 22],
+        Hash::Sha256,
+        &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c,
+        ],
+        &[0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9],
+    )
+    .await
+    .unwrap();
+    let derived = hkdf.derive_bits(42 * 8).await.unwrap();
+    assert_eq!(
+        derived,
+        [
+            0x3c, 0xb2, 0x5f, 0x25, 0xfa, 0xac, 0xd5, 0x7a, 0x90, 0x43, 0x4f, 0x64, 0xd0, 0x36,
+            0x2f, 0x2a, 0x2d, 0x2d, 0x0a, 0x90, 0xcf, 0x1a, 0x5a, 0x4c, 0x5d, 0xb0, 0x2d, 0x56,
+            0xec, 0xc4, 0xc5, 0xbf, 0x34, 0x00, 0x72, 0x08, 0xd5, 0xb8, 0x87, 0x18, 0x58, 0x65
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn should_derive_aes_gcm_key_matching_bits() {
+    console_error_panic_hook::set_once();
+
+    let hkdf = Hkdf::from_key(&[42; 32], Hash::Sha256, b"salt", b"")
+        .await
+        .unwrap()
+        .with_info(b"encryption");
+    let cipher = Aes256Gcm::derive_from(&hkdf).await.unwrap();
+    let bits = hkdf.derive_bits(256).await.unwrap();
+    let imported = Aes256Gcm::from_key(&bits).await.unwrap();
+
+    let nonce = Aes256Gcm::generate_nonce().unwrap();
+    let encrypted = cipher.encrypt(&nonce, b"Hello World!").await.unwrap();
+    let decrypted = imported.decrypt(&nonce, &encrypted).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+
+    // another info gives another key
+    let other = Aes256Gcm::derive_from(&hkdf.with_info(b"other"))
+        .await
+        .unwrap();
+    assert!(other.decrypt(&nonce, &encrypted).await.is_err());
+}
+
+#[wasm_bindgen_test]
+async fn should_derive_hmac_key_matching_bits() {
+    console_error_panic_hook::set_once();
+
+    let hkdf = Hkdf::from_key(&[42; 32], Hash::Sha256, b"salt", b"authentication")
+        .await
+        .unwrap();
+    let hmac = Hmac::derive_from(&hkdf, Hash::Sha256).await.unwrap();
+    // the derived key has the size of the hash block
+    let bits = hkdf.derive_bits(512).await.unwrap();
+    let imported = Hmac::from_key(&bits, Hash::Sha256).await.unwrap();
+
+    let tag = hmac.sign(b"Hello World!").await.unwrap();
+    assert_eq!(imported.sign(b"Hello World!").await.unwrap(), tag);
+}
+
+#[wasm_bindgen_test]
+async fn should_derive_non_extractable_key() {
+    console_error_panic_hook::set_once();
+
+    let hkdf = Hkdf::from_key(&[42; 32], Hash::Sha256, b"salt", b"info")
+        .await
+        .unwrap();
+    let cipher = Aes256Gcm::derive_from(&hkdf).await.unwrap();
+
+    let kek = Aes256Kw::from_key(&[1; 32]).await.unwrap();
+    let err = kek.wrap_key(&cipher).await.unwrap_err();
+    // Node.js names the exception InvalidAccessException
+    assert!(
+        matches!(err, WrapKeyError::InvalidAccess | WrapKeyError::Generic(_)),
+        "{err:?}"
+    );
+}
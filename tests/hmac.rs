@@ -0,0 +1,43 @@
+//! Test suite for the HMAC implementation.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::hash::Hash;
+use browser_crypto::hmac::Hmac;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_match_rfc_vector() {
+    // RFC 4231, 4.2 Test Case 1
+    console_error_panic_hook::set_once();
+
+    let expected = [
+        0xb0, 0x34, 0x4c, 0x61, 0xd8, 0xdb, 0x38, 0x53, 0x5c, 0xa8, 0xaf, 0xce, 0xaf, 0x0b, 0xf1,
+        0x2b, 0x88, 0x1d, 0xc2, 0x00, 0xc9, 0x83, 0x3d, 0xa7, 0x26, 0xe9, 0x37, 0x6c, 0x2e, 0x32,
+        0xcf, 0xf7,
+    ];
+
+    let hmac = Hmac::from_key(&[0x0b; 20], Hash::Sha256).await.unwrap();
+    let tag = hmac.sign(b"Hi There").await.unwrap();
+    assert_eq!(tag, expected);
+    assert!(hmac.verify(&expected, b"Hi There").await.unwrap());
+}
+
+#[wasm_bindgen_test]
+async fn should_sign_and_verify_with_generated_key() {
+    console_error_panic_hook::set_once();
+
+    for hash in [Hash::Sha256, Hash::Sha384, Hash::Sha512] {
+        let hmac = Hmac::generate(hash).await.unwrap();
+        let tag = hmac.sign(b"Hello World!").await.unwrap();
+        assert_eq!(tag.len(), hash.output_size());
+
+        assert!(hmac.verify(&tag, b"Hello World!").await.unwrap());
+        assert!(!hmac.verify(&tag, b"Hello World?").await.unwrap());
+        assert!(!hmac.verify(&tag[1..], b"Hello World!").await.unwrap());
+    }
+}
@@ -0,0 +1,258 @@
+//! Test suite for the HPKE implementation.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::curve::NamedCurve;
+use browser_crypto::ecdh::EcdhPrivateKey;
+use browser_crypto::hpke::{Aead, HpkeError, HpkeKeyPair, Kdf, Kem, Suite};
+use browser_crypto::x25519::X25519PrivateKey;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+// Messages encrypted with the python cryptography package, in the base mode,
+// with the info "browser-crypto" and no additional data. A message is the
+// encapsulated key followed by the ciphertext.
+const INFO: &[u8] = b"browser-crypto";
+const P256_PRIVATE_KEY: [u8; 138] = [
+    0x30, 0x81, 0x87, 0x02, 0x01, 0x00, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02,
+    0x01, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x04, 0x6d, 0x30, 0x6b, 0x02,
+    0x01, 0x01, 0x04, 0x20, 0x81, 0x7d, 0x72, 0xb0, 0xcf, 0xef, 0x9d, 0xe1, 0x22, 0x5e, 0x83, 0x37,
+    0xd3, 0x9c, 0xad, 0x39, 0x75, 0x8e, 0x7c, 0x2a, 0x4f, 0x99, 0x9e, 0xb5, 0xd8, 0x29, 0xe8, 0x50,
+    0x98, 0x20, 0x6f, 0x46, 0xa1, 0x44, 0x03, 0x42, 0x00, 0x04, 0xee, 0xe2, 0x82, 0x78, 0x6d, 0x1a,
+    0xa8, 0x62, 0xe7, 0x90, 0x98, 0xbc, 0x08, 0xa0, 0xb4, 0xd4, 0x17, 0x4f, 0x2b, 0xed, 0xb1, 0x07,
+    0x7c, 0x0f, 0x83, 0x06, 0x5f, 0x2b, 0xf6, 0xd8, 0x3d, 0xc7, 0x22, 0x75, 0xfe, 0xb5, 0x87, 0xa0,
+    0x7f, 0x32, 0x85, 0xa6, 0xd2, 0x73, 0x49, 0x6e, 0x86, 0x79, 0x13, 0x10, 0x42, 0x4b, 0x1f, 0x51,
+    0x93, 0x20, 0xf0, 0x4e, 0xf6, 0x2c, 0x64, 0x3d, 0xbb, 0x86,
+];
+const P256_PUBLIC_KEY: [u8; 65] = [
+    0x04, 0xee, 0xe2, 0x82, 0x78, 0x6d, 0x1a, 0xa8, 0x62, 0xe7, 0x90, 0x98, 0xbc, 0x08, 0xa0, 0xb4,
+    0xd4, 0x17, 0x4f, 0x2b, 0xed, 0xb1, 0x07, 0x7c, 0x0f, 0x83, 0x06, 0x5f, 0x2b, 0xf6, 0xd8, 0x3d,
+    0xc7, 0x22, 0x75, 0xfe, 0xb5, 0x87, 0xa0, 0x7f, 0x32, 0x85, 0xa6, 0xd2, 0x73, 0x49, 0x6e, 0x86,
+    0x79, 0x13, 0x10, 0x42, 0x4b, 0x1f, 0x51, 0x93, 0x20, 0xf0, 0x4e, 0xf6, 0x2c, 0x64, 0x3d, 0xbb,
+    0x86,
+];
+const P256_MESSAGE: [u8; 93] = [
+    0x04, 0x79, 0xb1, 0x18, 0x54, 0x82, 0x38, 0x21, 0xdb, 0xcd, 0xbe, 0xc6, 0x99, 0x63, 0x7a, 0x39,
+    0xea, 0x64, 0xb3, 0xd1, 0xc1, 0x0e, 0xad, 0x5b, 0x80, 0x17, 0xbf, 0xcf, 0xd0, 0x2a, 0x13, 0xa9,
+    0x50, 0x39, 0x9c, 0x3b, 0xec, 0x3f, 0x9c, 0x86, 0xa4, 0xc1, 0x58, 0x51, 0xe4, 0x24, 0x2d, 0xf9,
+    0x77, 0x58, 0xdf, 0x28, 0x83, 0x54, 0xae, 0x97, 0x37, 0xc8, 0xc3, 0x3d, 0x45, 0xc7, 0xd8, 0x4a,
+    0xcc, 0x04, 0x48, 0xe4, 0xf9, 0x41, 0x49, 0xb2, 0xb8, 0x9b, 0x53, 0x01, 0x7d, 0x08, 0x7b, 0xd6,
+    0x30, 0x12, 0x50, 0xe3, 0x81, 0x0d, 0x0c, 0x8e, 0x91, 0x08, 0x1f, 0xab, 0xb6,
+];
+const X25519_PRIVATE_KEY: [u8; 48] = [
+    0x30, 0x2e, 0x02, 0x01, 0x00, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x6e, 0x04, 0x22, 0x04, 0x20,
+    0xb0, 0x66, 0x6e, 0x63, 0xce, 0x8d, 0x10, 0x9e, 0x7a, 0x35, 0x3f, 0xcb, 0x1f, 0xa6, 0xb2, 0xe7,
+    0x01, 0xed, 0x2f, 0xdf, 0xdb, 0x41, 0xa3, 0x1a, 0x88, 0xf3, 0x61, 0x14, 0x75, 0xc3, 0xf3, 0x7d,
+];
+const X25519_PUBLIC_KEY: [u8; 32] = [
+    0x47, 0xc3, 0x82, 0x13, 0xe3, 0x67, 0x09, 0x3a, 0xed, 0x19, 0x2f, 0xff, 0xea, 0xda, 0x14, 0x1f,
+    0xdc, 0xb8, 0x5e, 0xb2, 0x17, 0xa1, 0xd6, 0x53, 0xe6, 0xdf, 0x9d, 0x34, 0x42, 0x01, 0x5d, 0x74,
+];
+const X25519_MESSAGE: [u8; 60] = [
+    0xf1, 0x75, 0x27, 0xec, 0x59, 0x61, 0x0d, 0x35, 0x2a, 0xcd, 0x69, 0x63, 0xad, 0x2c, 0x02, 0xd2,
+    0xdf, 0x90, 0x39, 0xe5, 0x50, 0x4b, 0xba, 0x9d, 0x61, 0xd2, 0x60, 0x1d, 0xa5, 0x2c, 0xed, 0x42,
+    0x92, 0x27, 0x7b, 0x88, 0xaf, 0x30, 0x8f, 0x17, 0x25, 0x55, 0x55, 0xe6, 0x5c, 0x7b, 0x6a, 0x2c,
+    0x9f, 0xa3, 0x1f, 0x83, 0x91, 0xf1, 0x39, 0x8e, 0xb6, 0xf2, 0x6c, 0x51,
+];
+
+const P256_SUITE: Suite = Suite::new(Kem::DhkemP256HkdfSha256, Kdf::HkdfSha256, Aead::Aes128Gcm);
+
+#[wasm_bindgen_test]
+async fn should_open_reference_messages() {
+    console_error_panic_hook::set_once();
+
+    let private_key = EcdhPrivateKey::from_pkcs8(&P256_PRIVATE_KEY, NamedCurve::P256)
+        .await
+        .unwrap();
+    let receiver = HpkeKeyPair::new(private_key, P256_PUBLIC_KEY.to_vec());
+    let (enc, ciphertext) = P256_MESSAGE.split_at(Kem::DhkemP256HkdfSha256.public_key_size());
+    let plaintext = P256_SUITE
+        .open(enc, &receiver, INFO, b"", ciphertext)
+        .await
+        .unwrap();
+    assert_eq!(plaintext, b"Hello World!");
+
+    if !browser_crypto::x25519::is_supported().await {
+        return;
+    }
+
+    let suite = Suite::new(Kem::DhkemX25519HkdfSha256, Kdf::HkdfSha256, Aead::Aes256Gcm);
+    let private_key = X25519PrivateKey::from_pkcs8(&X25519_PRIVATE_KEY)
+        .await
+        .unwrap();
+    let receiver = HpkeKeyPair::new(private_key, X25519_PUBLIC_KEY.to_vec());
+    let (enc, ciphertext) = X25519_MESSAGE.split_at(Kem::DhkemX25519HkdfSha256.public_key_size());
+    let plaintext = suite
+        .open(enc, &receiver, INFO, b"", ciphertext)
+        .await
+        .unwrap();
+    assert_eq!(plaintext, b"Hello World!");
+}
+
+#[wasm_bindgen_test]
+async fn should_seal_and_open_with_every_suite() {
+    console_error_panic_hook::set_once();
+
+    let x25519 = browser_crypto::x25519::is_supported().await;
+    for kem in [
+        Kem::DhkemP256HkdfSha256,
+        Kem::DhkemP384HkdfSha384,
+        Kem::DhkemP521HkdfSha512,
+        Kem::DhkemX25519HkdfSha256,
+    ] {
+        if kem == Kem::DhkemX25519HkdfSha256 && !x25519 {
+            continue;
+        }
+        for kdf in [Kdf::HkdfSha256, Kdf::HkdfSha384, Kdf::HkdfSha512] {
+            for aead in [Aead::Aes128Gcm, Aead::Aes256Gcm] {
+                let suite = Suite::new(kem, kdf, aead);
+                let receiver = suite.generate_key_pair().await.unwrap();
+                assert_eq!(receiver.public_key().len(), kem.public_key_size());
+
+                let (enc, ciphertext) = suite
+                    .seal(receiver.public_key(), INFO, b"aad", b"Hello World!")
+                    .await
+                    .unwrap();
+                assert_eq!(enc.len(), kem.public_key_size());
+                assert_eq!(ciphertext.len(), 12 + 16);
+                let plaintext = suite
+                    .open(&enc, &receiver, INFO, b"aad", &ciphertext)
+                    .await
+                    .unwrap();
+                assert_eq!(plaintext, b"Hello World!");
+            }
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+async fn should_encrypt_several_messages() {
+    console_error_panic_hook::set_once();
+
+    let receiver = P256_SUITE.generate_key_pair().await.unwrap();
+    let (enc, mut sender) = P256_SUITE
+        .setup_base_sender(receiver.public_key(), INFO)
+        .await
+        .unwrap();
+    let first = sender.seal(b"", b"first").await.unwrap();
+    let second = sender.seal(b"", b"second").await.unwrap();
+
+    let mut context = P256_SUITE
+        .setup_base_receiver(&enc, &receiver, INFO)
+        .await
+        .unwrap();
+    // messages must be opened in order, a failure doesn't move forward
+    assert!(matches!(
+        context.open(b"", &second).await.unwrap_err(),
+        HpkeError::Decryption(_)
+    ));
+    assert_eq!(context.open(b"", &first).await.unwrap(), b"first");
+    assert_eq!(context.open(b"", &second).await.unwrap(), b"second");
+}
+
+#[wasm_bindgen_test]
+async fn should_authenticate_sender() {
+    console_error_panic_hook::set_once();
+
+    let receiver = P256_SUITE.generate_key_pair().await.unwrap();
+    let sender = P256_SUITE.generate_key_pair().await.unwrap();
+    let other = P256_SUITE.generate_key_pair().await.unwrap();
+
+    let (enc, ciphertext) = P256_SUITE
+        .seal_auth(receiver.public_key(), INFO, b"", b"Hello World!", &sender)
+        .await
+        .unwrap();
+    let plaintext = P256_SUITE
+        .open_auth(&enc, &receiver, INFO, b"", &ciphertext, sender.public_key())
+        .await
+        .unwrap();
+    assert_eq!(plaintext, b"Hello World!");
+
+    assert!(matches!(
+        P256_SUITE
+            .open_auth(&enc, &receiver, INFO, b"", &ciphertext, other.public_key())
+            .await
+            .unwrap_err(),
+        HpkeError::Decryption(_)
+    ));
+    assert!(matches!(
+        P256_SUITE
+            .open(&enc, &receiver, INFO, b"", &ciphertext)
+            .await
+            .unwrap_err(),
+        HpkeError::Decryption(_)
+    ));
+}
+
+#[wasm_bindgen_test]
+async fn should_export_same_secrets() {
+    console_error_panic_hook::set_once();
+
+    let receiver = P256_SUITE.generate_key_pair().await.unwrap();
+    let (enc, secret) = P256_SUITE
+        .send_export(receiver.public_key(), INFO, b"context", 100)
+        .await
+        .unwrap();
+    assert_eq!(secret.len(), 100);
+    let received = P256_SUITE
+        .receive_export(&enc, &receiver, INFO, b"context", 100)
+        .await
+        .unwrap();
+    assert_eq!(secret, received);
+
+    let context = P256_SUITE
+        .setup_base_receiver(&enc, &receiver, INFO)
+        .await
+        .unwrap();
+    let other = context.export(b"other context", 100).await.unwrap();
+    assert_ne!(secret, other);
+    assert!(matches!(
+        context.export(b"context", 255 * 32 + 1).await.unwrap_err(),
+        HpkeError::InvalidExportLength
+    ));
+}
+
+#[wasm_bindgen_test]
+async fn should_reject_invalid_inputs() {
+    console_error_panic_hook::set_once();
+
+    let receiver = P256_SUITE.generate_key_pair().await.unwrap();
+    let (enc, ciphertext) = P256_SUITE
+        .seal(receiver.public_key(), INFO, b"aad", b"Hello World!")
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        P256_SUITE
+            .open(&enc, &receiver, b"other info", b"aad", &ciphertext)
+            .await
+            .unwrap_err(),
+        HpkeError::Decryption(_)
+    ));
+    assert!(matches!(
+        P256_SUITE
+            .open(&enc, &receiver, INFO, b"other aad", &ciphertext)
+            .await
+            .unwrap_err(),
+        HpkeError::Decryption(_)
+    ));
+    assert!(matches!(
+        P256_SUITE
+            .open(&enc[1..], &receiver, INFO, b"aad", &ciphertext)
+            .await
+            .unwrap_err(),
+        HpkeError::Import(_)
+    ));
+
+    let suite = Suite::new(Kem::DhkemP384HkdfSha384, Kdf::HkdfSha256, Aead::Aes128Gcm);
+    assert!(matches!(
+        suite
+            .open(&enc, &receiver, INFO, b"aad", &ciphertext)
+            .await
+            .unwrap_err(),
+        HpkeError::InvalidKey
+    ));
+}
@@ -0,0 +1,81 @@
+//! Test suite for the inputs held by JavaScript.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::Algorithm;
+use browser_crypto::hash::Hash;
+use browser_crypto::input::CryptoInput;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_encrypt_javascript_buffers() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let nonce = Aes256Gcm::generate_nonce().unwrap();
+    let expected = cipher.encrypt(&nonce, b"Hello World!").await.unwrap();
+
+    let array = js_sys::Uint8Array::from(b"--Hello World!--".as_slice());
+    let view = array.subarray(2, 14);
+    let encrypted = cipher.encrypt_js(&nonce, &view).await.unwrap();
+    assert_eq!(encrypted.to_vec(), expected);
+
+    // views are restricted to their range of the buffer
+    let data_view = js_sys::DataView::new(&array.buffer(), 2, 12);
+    let encrypted = cipher.encrypt_js(&nonce, &data_view).await.unwrap();
+    assert_eq!(encrypted.to_vec(), expected);
+
+    let decrypted = cipher.decrypt_js(&nonce, encrypted.buffer()).await.unwrap();
+    assert_eq!(decrypted.to_vec(), b"Hello World!");
+}
+
+#[wasm_bindgen_test]
+async fn should_digest_javascript_buffers() {
+    console_error_panic_hook::set_once();
+
+    let array = js_sys::Uint8Array::from(b"Hello World!".as_slice());
+    assert_eq!(
+        Hash::Sha256.digest(&array.buffer()).await.unwrap(),
+        Hash::Sha256.digest(b"Hello World!").await.unwrap()
+    );
+}
+
+#[wasm_bindgen_test]
+fn should_read_inputs() {
+    let input = CryptoInput::from(&js_sys::Uint8Array::from(b"Hello".as_slice()));
+    assert_eq!(input.len(), 5);
+    assert_eq!(input.to_vec(), b"Hello");
+    assert!(CryptoInput::from(&[]).is_empty());
+}
+
+#[wasm_bindgen_test]
+async fn should_encrypt_shared_buffers() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let nonce = Aes256Gcm::generate_nonce().unwrap();
+    let expected = cipher.encrypt(&nonce, b"Hello World!").await.unwrap();
+
+    let shared = js_sys::SharedArrayBuffer::new(16);
+    let array = js_sys::Uint8Array::new(&shared);
+    array.copy_from(b"--Hello World!--");
+    let encrypted = cipher
+        .encrypt_js(&nonce, &array.subarray(2, 14))
+        .await
+        .unwrap();
+    assert_eq!(encrypted.to_vec(), expected);
+
+    let shared = js_sys::SharedArrayBuffer::new(expected.len() as u32);
+    js_sys::Uint8Array::new(&shared).copy_from(&expected);
+    let decrypted = cipher.decrypt_js(&nonce, &shared).await.unwrap();
+    assert_eq!(decrypted.to_vec(), b"Hello World!");
+    assert_eq!(
+        Hash::Sha256.digest(&shared).await.unwrap(),
+        Hash::Sha256.digest(&expected).await.unwrap()
+    );
+}
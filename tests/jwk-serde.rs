@@ -0,0 +1,77 @@
+//! Test suite for the JSON Web Key serialization.
+
+#![cfg(all(target_arch = "wasm32", feature = "serde"))]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::Algorithm;
+use browser_crypto::jwk::{EcJwk, Jwk, OctJwk};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+fn should_deserialize_rfc_example() {
+    // RFC 7517, A.1
+    let jwk: Jwk = serde_json::from_str(
+        r#"{
+            "kty": "EC",
+            "crv": "P-256",
+            "x": "MKBCTNIcKUSDii11ySs3526iDZ8AiTo7Tu6KPAqv7D4",
+            "y": "4Etl6SRW2YiLUrN5vfvVHuhp7x8PxltmWWlbbM4IFyM",
+            "use": "enc",
+            "kid": "1"
+        }"#,
+    )
+    .unwrap();
+    let Jwk::Ec(ref inner) = jwk else {
+        panic!("expected an EC key");
+    };
+    assert_eq!(inner.crv, "P-256");
+    assert_eq!(inner.params.key_use.as_deref(), Some("enc"));
+    assert!(inner.d.is_none());
+    jwk.validate().unwrap();
+}
+
+#[wasm_bindgen_test]
+fn should_serialize_without_empty_members() {
+    let jwk = Jwk::from(EcJwk::new("P-256", "AA", "AA")).with_alg("ES256");
+    assert_eq!(
+        serde_json::to_value(&jwk).unwrap(),
+        serde_json::json!({
+            "kty": "EC",
+            "alg": "ES256",
+            "crv": "P-256",
+            "x": "AA",
+            "y": "AA",
+        })
+    );
+}
+
+#[wasm_bindgen_test]
+async fn should_import_deserialized_key() {
+    console_error_panic_hook::set_once();
+
+    let jwk: Jwk = serde_json::from_str(
+        r#"{"kty":"oct","alg":"A256GCM","k":"KioqKioqKioqKioqKioqKioqKioqKioqKioqKioqKio"}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        jwk,
+        Jwk::from(OctJwk::new("KioqKioqKioqKioqKioqKioqKioqKioqKioqKioqKio")).with_alg("A256GCM")
+    );
+
+    let cipher = Aes256Gcm::from_jwk(&jwk).await.unwrap();
+    let nonce = Aes256Gcm::generate_nonce().unwrap();
+    let encrypted = cipher.encrypt(&nonce, b"Hello World!").await.unwrap();
+    let exported = cipher.to_jwk().await.unwrap();
+    let json = serde_json::to_string(&exported).unwrap();
+    let imported = Aes256Gcm::from_jwk(&serde_json::from_str(&json).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(
+        imported.decrypt(&nonce, &encrypted).await.unwrap(),
+        b"Hello World!"
+    );
+}
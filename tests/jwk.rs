@@ -0,0 +1,250 @@
+//! Test suite for the JSON Web Key import and export.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::{Aes128Gcm, Aes256Gcm};
+use browser_crypto::aes_kw::Aes128Kw;
+use browser_crypto::algorithm::Algorithm;
+use browser_crypto::curve::NamedCurve;
+use browser_crypto::ecdsa::{EcdsaKeyPair, EcdsaPrivateKey, EcdsaPublicKey};
+use browser_crypto::hash::Hash;
+use browser_crypto::hkdf::Hkdf;
+use browser_crypto::hmac::Hmac;
+use browser_crypto::jwk::{EcJwk, Jwk, JwkError, OctJwk, RsaJwk};
+use browser_crypto::key::{ExportKeyError, ImportKeyError, KeyWrapper};
+use browser_crypto::rsa_oaep::{RsaOaepKeyPair, RsaOaepPrivateKey, RsaOaepPublicKey};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+/// Base64url encoding of 32 bytes set to 42
+const K_256: &str = "KioqKioqKioqKioqKioqKioqKioqKioqKioqKioqKio";
+
+/// Key of RFC 3394, 4.1
+const KEK_128: &str = "AAECAwQFBgcICQoLDA0ODw";
+
+#[wasm_bindgen_test]
+async fn should_import_aes_gcm_key() {
+    console_error_panic_hook::set_once();
+
+    let jwk = Jwk::from(OctJwk::new(K_256)).with_alg("A256GCM");
+    let from_jwk = Aes256Gcm::from_jwk(&jwk).await.unwrap();
+    let from_raw = Aes256Gcm::from_key(&[42; 32]).await.unwrap();
+
+    let nonce = Aes256Gcm::generate_nonce().unwrap();
+    let encrypted = from_jwk.encrypt(&nonce, b"Hello World!").await.unwrap();
+    let decrypted = from_raw.decrypt(&nonce, &encrypted).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+}
+
+#[wasm_bindgen_test]
+async fn should_reject_aes_key_of_another_size() {
+    console_error_panic_hook::set_once();
+
+    let jwk = Jwk::from(OctJwk::new(K_256));
+    let err = Aes128Gcm::from_jwk(&jwk).await.unwrap_err();
+    assert!(matches!(err, ImportKeyError::InvalidKeyFormat), "{err:?}");
+}
+
+#[wasm_bindgen_test]
+async fn should_reject_aes_key_of_another_algorithm() {
+    console_error_panic_hook::set_once();
+
+    let jwk = Jwk::from(OctJwk::new(K_256)).with_alg("A256KW");
+    assert!(Aes256Gcm::from_jwk(&jwk).await.is_err());
+}
+
+#[wasm_bindgen_test]
+async fn should_import_aes_kw_key() {
+    console_error_panic_hook::set_once();
+
+    let jwk = Jwk::from(OctJwk::new(KEK_128)).with_alg("A128KW");
+    let kek = Aes128Kw::from_jwk(&jwk).await.unwrap();
+    let key = Aes128Gcm::from_key(&[
+        0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee,
+        0xff,
+    ])
+    .await
+    .unwrap();
+    let wrapped = kek.wrap_key(&key).await.unwrap();
+    assert_eq!(
+        wrapped,
+        [
+            0x1f, 0xa6, 0x8b, 0x0a, 0x81, 0x12, 0xb4, 0x47, 0xae, 0xf3, 0x4b, 0xd8, 0xfb, 0x5a,
+            0x7b, 0x82, 0x9d, 0x3e, 0x86, 0x23, 0x71, 0xd2, 0xcf, 0xe5
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn should_import_hmac_key() {
+    console_error_panic_hook::set_once();
+
+    let jwk = Jwk::from(OctJwk::new(K_256)).with_alg("HS256");
+    let from_jwk = Hmac::from_jwk(&jwk, Hash::Sha256).await.unwrap();
+    let from_raw = Hmac::from_key(&[42; 32], Hash::Sha256).await.unwrap();
+
+    let tag = from_jwk.sign(b"Hello World!").await.unwrap();
+    assert!(from_raw.verify(&tag, b"Hello World!").await.unwrap());
+
+    // the hash function doesn't match the algorithm
+    assert!(Hmac::from_jwk(&jwk, Hash::Sha512).await.is_err());
+}
+
+#[wasm_bindgen_test]
+async fn should_export_aes_gcm_key() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[42; 32]).await.unwrap();
+    let jwk = cipher.to_jwk().await.unwrap();
+    assert_eq!(jwk.kty(), "oct");
+    assert_eq!(jwk.params().alg.as_deref(), Some("A256GCM"));
+    assert_eq!(jwk.params().ext, Some(true));
+    assert_eq!(
+        jwk.params().key_ops,
+        Some(vec!["encrypt".to_string(), "decrypt".to_string()])
+    );
+    let Jwk::Oct(jwk) = jwk else {
+        panic!("expected an oct key");
+    };
+    assert_eq!(jwk.k, K_256);
+}
+
+#[wasm_bindgen_test]
+async fn should_not_export_derived_key() {
+    console_error_panic_hook::set_once();
+
+    let hkdf = Hkdf::from_key(&[42; 32], Hash::Sha256, b"salt", b"info")
+        .await
+        .unwrap();
+    let cipher = Aes256Gcm::derive_from(&hkdf).await.unwrap();
+    let err = cipher.to_jwk().await.unwrap_err();
+    // Node.js names the exception InvalidAccessException
+    assert!(
+        matches!(
+            err,
+            ExportKeyError::InvalidAccess | ExportKeyError::Generic(_)
+        ),
+        "{err:?}"
+    );
+}
+
+#[wasm_bindgen_test]
+async fn should_export_and_import_hmac_key() {
+    console_error_panic_hook::set_once();
+
+    let hmac = Hmac::generate(Hash::Sha384).await.unwrap();
+    let jwk = hmac.to_jwk().await.unwrap();
+    assert_eq!(jwk.params().alg.as_deref(), Some("HS384"));
+
+    let imported = Hmac::from_jwk(&jwk, Hash::Sha384).await.unwrap();
+    let tag = hmac.sign(b"Hello World!").await.unwrap();
+    assert!(imported.verify(&tag, b"Hello World!").await.unwrap());
+}
+
+#[wasm_bindgen_test]
+async fn should_export_and_import_ecdsa_keys() {
+    console_error_panic_hook::set_once();
+
+    let pair = EcdsaKeyPair::generate(NamedCurve::P384, Hash::Sha384)
+        .await
+        .unwrap();
+    let public_jwk = pair.public_key.to_jwk().await.unwrap();
+    let Jwk::Ec(ref public_ec) = public_jwk else {
+        panic!("expected an EC key");
+    };
+    assert_eq!(public_ec.crv, "P-384");
+    assert!(public_ec.d.is_none());
+    let private_jwk = pair.private_key.to_jwk().await.unwrap();
+    assert!(matches!(private_jwk, Jwk::Ec(EcJwk { d: Some(_), .. })));
+
+    let public_key = EcdsaPublicKey::from_jwk(&public_jwk, NamedCurve::P384, Hash::Sha384)
+        .await
+        .unwrap();
+    let private_key = EcdsaPrivateKey::from_jwk(&private_jwk, NamedCurve::P384, Hash::Sha384)
+        .await
+        .unwrap();
+    let signature = private_key.sign(b"Hello World!").await.unwrap();
+    assert!(public_key
+        .verify(&signature, b"Hello World!")
+        .await
+        .unwrap());
+}
+
+#[wasm_bindgen_test]
+async fn should_export_and_import_rsa_oaep_keys() {
+    console_error_panic_hook::set_once();
+
+    let pair = RsaOaepKeyPair::generate(2048, Hash::Sha256).await.unwrap();
+    let public_jwk = pair.public_key.to_jwk().await.unwrap();
+    assert_eq!(public_jwk.kty(), "RSA");
+    assert_eq!(public_jwk.params().alg.as_deref(), Some("RSA-OAEP-256"));
+    let private_jwk = pair.private_key.to_jwk().await.unwrap();
+
+    let public_key = RsaOaepPublicKey::from_jwk(&public_jwk, Hash::Sha256)
+        .await
+        .unwrap();
+    let private_key = RsaOaepPrivateKey::from_jwk(&private_jwk, Hash::Sha256)
+        .await
+        .unwrap();
+    let encrypted = public_key.encrypt(b"Hello World!").await.unwrap();
+    assert_eq!(
+        private_key.decrypt(&encrypted).await.unwrap(),
+        b"Hello World!"
+    );
+}
+
+#[wasm_bindgen_test]
+async fn should_reject_invalid_keys() {
+    console_error_panic_hook::set_once();
+
+    let jwk = Jwk::from(OctJwk::new("not base64url!"));
+    assert_eq!(jwk.validate(), Err(JwkError::InvalidMember("k")));
+    let err = Aes256Gcm::from_jwk(&jwk).await.unwrap_err();
+    assert!(matches!(err, ImportKeyError::InvalidKeyFormat), "{err:?}");
+
+    // coordinates of a P-256 key on P-384
+    let jwk = Jwk::from(EcJwk::new(
+        "P-384",
+        "f83OJ3D2xF1Bg8vub9tLe1gHMzV76e8Tus9uPHvRVEU",
+        "x_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0",
+    ));
+    assert_eq!(jwk.validate(), Err(JwkError::InvalidMember("x")));
+
+    let jwk = Jwk::from(EcJwk::new("secp256k1", "AA", "AA"));
+    assert_eq!(
+        jwk.validate(),
+        Err(JwkError::UnsupportedCurve("secp256k1".into()))
+    );
+
+    let jwk = Jwk::from(RsaJwk {
+        d: Some("AQAB".into()),
+        p: Some("AQAB".into()),
+        ..RsaJwk::new("AQAB", "AQAB")
+    });
+    assert_eq!(jwk.validate(), Err(JwkError::MissingMember("q")));
+}
+
+#[wasm_bindgen_test]
+async fn should_convert_from_and_to_js_value() {
+    console_error_panic_hook::set_once();
+
+    let jwk = Jwk::from(EcJwk::new(
+        "P-256",
+        "f83OJ3D2xF1Bg8vub9tLe1gHMzV76e8Tus9uPHvRVEU",
+        "x_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0",
+    ))
+    .with_alg("ES256");
+    let value = wasm_bindgen::JsValue::from(&jwk);
+    assert_eq!(js_sys::Reflect::get(&value, &"kty".into()).unwrap(), "EC");
+    assert_eq!(Jwk::try_from(&value).unwrap(), jwk);
+
+    let value = js_sys::Object::new();
+    js_sys::Reflect::set(&value, &"kty".into(), &"foo".into()).unwrap();
+    assert_eq!(
+        Jwk::try_from(&wasm_bindgen::JsValue::from(value)),
+        Err(JwkError::UnsupportedKeyType("foo".into()))
+    );
+}
@@ -0,0 +1,102 @@
+//! Test suite for the JSON Web Key Sets.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::curve::NamedCurve;
+use browser_crypto::ecdsa::EcdsaKeyPair;
+use browser_crypto::hash::Hash;
+use browser_crypto::jwk::Jwk;
+use browser_crypto::jwks::{Jwks, JwksError, VerifyingKey};
+use browser_crypto::key::ImportKeyError;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+/// RFC 7517, A.1, with the EC key used for signatures
+const JWKS: &str = r#"{"keys":[
+    {"kty":"EC","crv":"P-256","x":"MKBCTNIcKUSDii11ySs3526iDZ8AiTo7Tu6KPAqv7D4","y":"4Etl6SRW2YiLUrN5vfvVHuhp7x8PxltmWWlbbM4IFyM","use":"sig","kid":"1"},
+    {"kty":"RSA","n":"0vx7agoebGcQSuuPiLJXZptN9nndrQmbXEps2aiAFbWhM78LhWx4cbbfAAtVT86zwu1RK7aPFFxuhDR1L6tSoc_BJECPebWKRXjBZCiFV4n3oknjhMstn64tZ_2W-5JsGY4Hc5n9yBXArwl93lqt7_RN5w6Cf0h4QyQ5v-65YGjQR0_FDW2QvzqY368QQMicAtaSqzs8KJZgnYb9c7d0zgdAZHzu6qMQvRL5hajrn1n91CbOpbISD08qNLyrdkt-bFTWhAI4vMQFh6WeZu0fM4lFd2NcRwr3XPksINHaQ-G_xBniIqbw0Ls1jF44-csFCur-kEgU8awapJzKnqDKgw","e":"AQAB","alg":"RS256","kid":"2011-04-29"},
+    {"kty":"unknown","kid":"ignored"}
+]}"#;
+
+fn parse(content: &str) -> Jwks {
+    let value = js_sys::JSON::parse(content).unwrap();
+    Jwks::try_from(&value).unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn should_parse_and_import_keys() {
+    console_error_panic_hook::set_once();
+
+    let jwks = parse(JWKS);
+    assert_eq!(jwks.keys().len(), 2);
+    assert!(jwks.find("ignored").is_none());
+
+    let key = jwks.verifying_key("1").await.unwrap();
+    assert!(matches!(key, VerifyingKey::Ecdsa(_)));
+    let key = jwks.verifying_key("2011-04-29").await.unwrap();
+    assert!(matches!(key, VerifyingKey::RsassaPkcs1(_)));
+    // from the cache
+    let key = jwks.verifying_key("2011-04-29").await.unwrap();
+    assert!(matches!(key, VerifyingKey::RsassaPkcs1(_)));
+
+    let err = jwks.verifying_key("unknown").await.unwrap_err();
+    assert!(matches!(err, JwksError::KeyNotFound(_)), "{err:?}");
+}
+
+#[wasm_bindgen_test]
+async fn should_verify_with_generated_key() {
+    console_error_panic_hook::set_once();
+
+    let pair = EcdsaKeyPair::generate(NamedCurve::P384, Hash::Sha384)
+        .await
+        .unwrap();
+    let jwk = pair
+        .public_key
+        .to_jwk()
+        .await
+        .unwrap()
+        .with_kid("generated");
+    let jwks = Jwks::new(vec![jwk]);
+
+    let signature = pair.private_key.sign(b"Hello World!").await.unwrap();
+    let key = jwks.verifying_key("generated").await.unwrap();
+    assert!(key.verify(&signature, b"Hello World!").await.unwrap());
+    assert!(!key.verify(&signature, b"Hello World?").await.unwrap());
+}
+
+#[wasm_bindgen_test]
+async fn should_reject_unsupported_algorithm() {
+    console_error_panic_hook::set_once();
+
+    let value = js_sys::JSON::parse(
+        r#"{"kty":"RSA","n":"0vx7agoebGcQSuuPiLJXZptN9nndrQmbXEps2aiAFbWhM78LhWx4cbbfAAtVT86zwu1RK7aPFFxuhDR1L6tSoc_BJECPebWKRXjBZCiFV4n3oknjhMstn64tZ_2W-5JsGY4Hc5n9yBXArwl93lqt7_RN5w6Cf0h4QyQ5v-65YGjQR0_FDW2QvzqY368QQMicAtaSqzs8KJZgnYb9c7d0zgdAZHzu6qMQvRL5hajrn1n91CbOpbISD08qNLyrdkt-bFTWhAI4vMQFh6WeZu0fM4lFd2NcRwr3XPksINHaQ-G_xBniIqbw0Ls1jF44-csFCur-kEgU8awapJzKnqDKgw","e":"AQAB","alg":"PS256"}"#,
+    )
+    .unwrap();
+    let jwk = Jwk::try_from(&value).unwrap();
+    let err = VerifyingKey::from_jwk(&jwk).await.unwrap_err();
+    assert!(matches!(err, ImportKeyError::NotSupported), "{err:?}");
+}
+
+#[wasm_bindgen_test]
+fn should_reject_invalid_content() {
+    let value = js_sys::JSON::parse(r#"{"foo":[]}"#).unwrap();
+    let err = Jwks::try_from(&value).unwrap_err();
+    assert!(matches!(err, JwksError::InvalidContent), "{err:?}");
+}
+
+#[cfg(feature = "fetch")]
+#[wasm_bindgen_test]
+async fn should_fetch_data_url() {
+    console_error_panic_hook::set_once();
+
+    let url = format!(
+        "data:application/json,{}",
+        String::from(js_sys::encode_uri_component(JWKS))
+    );
+    let jwks = Jwks::fetch(&url).await.unwrap();
+    assert_eq!(jwks.keys().len(), 2);
+    assert!(jwks.verifying_key("1").await.is_ok());
+}
@@ -0,0 +1,129 @@
+//! Test suite for the SPKI and PKCS#8 key import.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::curve::NamedCurve;
+use browser_crypto::ecdsa::{EcdsaKeyPair, EcdsaPrivateKey, EcdsaPublicKey};
+use browser_crypto::ed25519::{Ed25519KeyPair, Ed25519PrivateKey, Ed25519PublicKey};
+use browser_crypto::hash::Hash;
+use browser_crypto::key::{ImportKeyError, KeyFormat};
+use browser_crypto::rsassa_pkcs1::{RsassaPkcs1KeyPair, RsassaPkcs1PublicKey};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+fn should_name_formats() {
+    assert_eq!(KeyFormat::Raw.name(), "raw");
+    assert_eq!(KeyFormat::Spki.name(), "spki");
+    assert_eq!(KeyFormat::Pkcs8.to_string(), "pkcs8");
+}
+
+#[wasm_bindgen_test]
+async fn should_reject_malformed_der() {
+    console_error_panic_hook::set_once();
+
+    let pair = EcdsaKeyPair::generate(NamedCurve::P256, Hash::Sha256)
+        .await
+        .unwrap();
+    let spki = pair.export_public_key().await.unwrap();
+    let pkcs8 = pair.export_private_key().await.unwrap();
+
+    let truncated = &spki[..spki.len() - 1];
+    let err = EcdsaPublicKey::from_spki(truncated, NamedCurve::P256, Hash::Sha256)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ImportKeyError::MalformedDer(KeyFormat::Spki)));
+
+    let mut trailing = pkcs8.clone();
+    trailing.push(0);
+    let err = EcdsaPrivateKey::from_pkcs8(&trailing, NamedCurve::P256, Hash::Sha256)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ImportKeyError::MalformedDer(KeyFormat::Pkcs8)
+    ));
+
+    // a PKCS#8 key is not a SubjectPublicKeyInfo
+    let err = EcdsaPublicKey::from_spki(&pkcs8, NamedCurve::P256, Hash::Sha256)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ImportKeyError::MalformedDer(KeyFormat::Spki)));
+
+    let err = RsassaPkcs1PublicKey::from_spki(b"-----BEGIN PUBLIC KEY-----", Hash::Sha256)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ImportKeyError::MalformedDer(KeyFormat::Spki)));
+}
+
+#[wasm_bindgen_test]
+async fn should_reject_keys_of_other_algorithms() {
+    console_error_panic_hook::set_once();
+
+    let pair = RsassaPkcs1KeyPair::generate(2048, Hash::Sha256)
+        .await
+        .unwrap();
+    let spki = pair.export_public_key().await.unwrap();
+    let err = EcdsaPublicKey::from_spki(&spki, NamedCurve::P256, Hash::Sha256)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, ImportKeyError::UnsupportedAlgorithm(oid) if oid == "1.2.840.113549.1.1.1"),
+        "{err:?}"
+    );
+
+    let pair = EcdsaKeyPair::generate(NamedCurve::P256, Hash::Sha256)
+        .await
+        .unwrap();
+    let pkcs8 = pair.export_private_key().await.unwrap();
+    let err = Ed25519PrivateKey::from_pkcs8(&pkcs8).await.unwrap_err();
+    assert!(
+        matches!(&err, ImportKeyError::UnsupportedAlgorithm(oid) if oid == "1.2.840.10045.2.1"),
+        "{err:?}"
+    );
+}
+
+#[wasm_bindgen_test]
+async fn should_reject_keys_on_other_curves() {
+    console_error_panic_hook::set_once();
+
+    let pair = EcdsaKeyPair::generate(NamedCurve::P384, Hash::Sha384)
+        .await
+        .unwrap();
+    let spki = pair.export_public_key().await.unwrap();
+    let err = EcdsaPublicKey::from_spki(&spki, NamedCurve::P256, Hash::Sha256)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, ImportKeyError::UnsupportedAlgorithm(oid) if oid == "1.3.132.0.34"),
+        "{err:?}"
+    );
+
+    let public_key = EcdsaPublicKey::from_spki(&spki, NamedCurve::P384, Hash::Sha384)
+        .await
+        .unwrap();
+    assert_eq!(public_key.to_spki().await.unwrap(), spki);
+}
+
+#[wasm_bindgen_test]
+async fn should_import_ed25519_spki() {
+    console_error_panic_hook::set_once();
+
+    if !browser_crypto::ed25519::is_supported().await {
+        return;
+    }
+
+    let pair = Ed25519KeyPair::generate().await.unwrap();
+    let spki = pair.public_key.to_spki().await.unwrap();
+    assert_eq!(spki, pair.export_public_key().await.unwrap());
+
+    let public_key = Ed25519PublicKey::from_spki(&spki).await.unwrap();
+    let signature = pair.private_key.sign(b"Hello World!").await.unwrap();
+    assert!(public_key
+        .verify(&signature, b"Hello World!")
+        .await
+        .unwrap());
+}
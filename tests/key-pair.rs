@@ -0,0 +1,66 @@
+//! Test suite for the key pair abstraction.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::curve::NamedCurve;
+use browser_crypto::ecdsa::{EcdsaKeyPair, EcdsaPrivateKey, EcdsaPublicKey};
+use browser_crypto::hash::Hash;
+use browser_crypto::rsa_oaep::{RsaOaepKeyPair, RsaOaepPrivateKey, RsaOaepPublicKey};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_export_and_import_ecdsa_key_pair() {
+    console_error_panic_hook::set_once();
+
+    let pair = EcdsaKeyPair::generate(NamedCurve::P256, Hash::Sha256)
+        .await
+        .unwrap();
+    assert_eq!(pair.public_usages(), &["verify"]);
+    assert_eq!(pair.private_usages(), &["sign"]);
+
+    let spki = pair.export_public_key().await.unwrap();
+    let pkcs8 = pair.export_private_key().await.unwrap();
+    assert_eq!(spki, pair.public_key.to_spki().await.unwrap());
+
+    let public_key = EcdsaPublicKey::from_spki(&spki, NamedCurve::P256, Hash::Sha256)
+        .await
+        .unwrap();
+    let private_key = EcdsaPrivateKey::from_pkcs8(&pkcs8, NamedCurve::P256, Hash::Sha256)
+        .await
+        .unwrap();
+    let (public_key, _) = EcdsaKeyPair::new(public_key, private_key).into_parts();
+
+    let signature = pair.private_key.sign(b"Hello World!").await.unwrap();
+    assert!(public_key
+        .verify(&signature, b"Hello World!")
+        .await
+        .unwrap());
+}
+
+#[wasm_bindgen_test]
+async fn should_export_and_import_rsa_oaep_key_pair() {
+    console_error_panic_hook::set_once();
+
+    let pair = RsaOaepKeyPair::generate(2048, Hash::Sha256).await.unwrap();
+    assert_eq!(pair.public_usages(), &["encrypt", "wrapKey"]);
+    assert_eq!(pair.private_usages(), &["decrypt", "unwrapKey"]);
+
+    let public_key =
+        RsaOaepPublicKey::from_spki(&pair.export_public_key().await.unwrap(), Hash::Sha256)
+            .await
+            .unwrap();
+    let private_key =
+        RsaOaepPrivateKey::from_pkcs8(&pair.export_private_key().await.unwrap(), Hash::Sha256)
+            .await
+            .unwrap();
+
+    let encrypted = public_key.encrypt(b"Hello World!").await.unwrap();
+    let decrypted = pair.private_key.decrypt(&encrypted).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+    let decrypted = private_key.decrypt(&encrypted).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+}
@@ -0,0 +1,124 @@
+//! Test suite for the raw keys.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_cbc::Aes128Cbc;
+use browser_crypto::aes_gcm::{Aes128Gcm, Aes256Gcm};
+use browser_crypto::algorithm::{Algorithm, Key, KeyError, Nonce};
+use browser_crypto::input::SecretInput;
+use browser_crypto::key::ImportKeyError;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+fn should_expose_key_size() {
+    assert_eq!(<Aes128Gcm as Algorithm>::KEY_SIZE, 16);
+    assert_eq!(<Aes256Gcm as Algorithm>::KEY_SIZE, 32);
+    assert_eq!(<Aes128Cbc as Algorithm>::KEY_SIZE, 16);
+}
+
+#[wasm_bindgen_test]
+async fn should_generate_and_import_key() {
+    console_error_panic_hook::set_once();
+
+    let key = Key::<Aes256Gcm>::generate().unwrap();
+    assert_eq!(key.expose_bytes().len(), 32);
+    assert_ne!(key, Key::<Aes256Gcm>::generate().unwrap());
+
+    let cipher = Aes256Gcm::from_key(&key).await.unwrap();
+    let nonce = Nonce::<Aes256Gcm>::from_slice(&[1u8; 12]).unwrap();
+    let encrypted = cipher.encrypt(&nonce, b"Hello World!").await.unwrap();
+
+    let cipher = Aes256Gcm::from_key(key.expose_bytes()).await.unwrap();
+    let decrypted = cipher.decrypt(&nonce, &encrypted).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+}
+
+#[wasm_bindgen_test]
+fn should_check_key_size() {
+    console_error_panic_hook::set_once();
+
+    assert!(Key::<Aes128Gcm>::from_slice(&[0u8; 16]).is_ok());
+    let err = Key::<Aes128Gcm>::from_slice(&[0u8; 32]).unwrap_err();
+    assert!(matches!(
+        err,
+        KeyError::InvalidSize {
+            expected: 16,
+            received: 32
+        }
+    ));
+    assert!(Key::<Aes256Gcm>::try_from([0u8; 31].as_slice()).is_err());
+}
+
+#[wasm_bindgen_test]
+fn should_encode_key() {
+    console_error_panic_hook::set_once();
+
+    let bytes: Vec<u8> = (0..16).collect();
+    let key = Key::<Aes128Gcm>::from_slice(&bytes).unwrap();
+    assert_eq!(key.to_hex(), "000102030405060708090a0b0c0d0e0f");
+    assert_eq!(key.to_base64(), "AAECAwQFBgcICQoLDA0ODw==");
+    assert_eq!(key.to_base64url(), "AAECAwQFBgcICQoLDA0ODw");
+    assert_eq!(
+        Key::<Aes128Gcm>::from_hex("000102030405060708090A0B0C0D0E0F").unwrap(),
+        key
+    );
+    assert_eq!(
+        Key::<Aes128Gcm>::from_base64url("AAECAwQFBgcICQoLDA0ODw").unwrap(),
+        key
+    );
+
+    assert_eq!(
+        Key::<Aes128Gcm>::from_base64("AAECAwQFBgcICQoLDA0ODw").unwrap(),
+        key
+    );
+
+    assert!(matches!(
+        Key::<Aes128Gcm>::from_hex("not hex"),
+        Err(KeyError::InvalidEncoding(_))
+    ));
+    assert!(matches!(
+        Key::<Aes128Gcm>::from_base64url("AAECAwQFBgcICQoLDA0O"),
+        Err(KeyError::InvalidSize { .. })
+    ));
+}
+
+#[wasm_bindgen_test]
+fn should_redact_key() {
+    let key = Key::<Aes128Gcm>::from_slice(&[0x42; 16]).unwrap();
+    assert_eq!(format!("{key:?}"), "Key([REDACTED; 16 bytes])");
+}
+
+#[wasm_bindgen_test]
+async fn should_import_encoded_key() {
+    console_error_panic_hook::set_once();
+
+    let key = Key::<Aes256Gcm>::generate().unwrap();
+    let nonce = Nonce::<Aes256Gcm>::generate().unwrap();
+    let encrypted = Aes256Gcm::from_key(&key)
+        .await
+        .unwrap()
+        .encrypt(&nonce, b"hello")
+        .await
+        .unwrap();
+
+    let cipher = Aes256Gcm::from_base64_key(&key.to_base64()).await.unwrap();
+    assert_eq!(cipher.decrypt(&nonce, &encrypted).await.unwrap(), b"hello");
+    let cipher = Aes256Gcm::from_hex_key(&key.to_hex()).await.unwrap();
+    assert_eq!(cipher.decrypt(&nonce, &encrypted).await.unwrap(), b"hello");
+
+    assert!(matches!(
+        Aes256Gcm::from_base64_key("not base64!").await,
+        Err(ImportKeyError::RawKey(KeyError::InvalidEncoding(_)))
+    ));
+    assert!(matches!(
+        Aes256Gcm::from_hex_key("000102030405060708090a0b0c0d0e0f").await,
+        Err(ImportKeyError::RawKey(KeyError::InvalidSize {
+            expected: 32,
+            received: 16
+        }))
+    ));
+}
@@ -0,0 +1,88 @@
+//! Test suite for the keyring.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::DecryptionError;
+use browser_crypto::keyring::{self, Keyring, KeyringError};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_decrypt_after_rotation() {
+    console_error_panic_hook::set_once();
+
+    let mut ring = Keyring::new("first", Aes256Gcm::from_key(&[1; 32]).await.unwrap()).unwrap();
+    let first = ring.encrypt(b"Hello World!").await.unwrap();
+    // key ID, nonce and tag
+    assert_eq!(first.len(), 1 + 5 + 12 + 12 + 16);
+    assert_eq!(keyring::kid_of(&first).unwrap(), "first");
+
+    ring.rotate("second", Aes256Gcm::from_key(&[2; 32]).await.unwrap())
+        .unwrap();
+    assert_eq!(ring.primary_kid(), "second");
+    let second = ring.encrypt(b"Hello World!").await.unwrap();
+    assert_eq!(keyring::kid_of(&second).unwrap(), "second");
+
+    assert_eq!(ring.decrypt(&first).await.unwrap(), b"Hello World!");
+    assert_eq!(ring.decrypt(&second).await.unwrap(), b"Hello World!");
+
+    ring.remove("first").unwrap();
+    assert_eq!(ring.kids().collect::<Vec<_>>(), vec!["second"]);
+    let err = ring.decrypt(&first).await.unwrap_err();
+    assert!(matches!(err, KeyringError::UnknownKid(kid) if kid == "first"));
+}
+
+#[wasm_bindgen_test]
+async fn should_manage_ciphers() {
+    console_error_panic_hook::set_once();
+
+    let mut ring = Keyring::new("first", Aes256Gcm::from_key(&[1; 32]).await.unwrap()).unwrap();
+    ring.insert("second", Aes256Gcm::from_key(&[2; 32]).await.unwrap())
+        .unwrap();
+    assert_eq!(ring.primary_kid(), "first");
+    assert!(ring.get("second").is_some());
+
+    assert!(matches!(
+        ring.remove("first").unwrap_err(),
+        KeyringError::PrimaryKid
+    ));
+    assert!(matches!(
+        ring.set_primary("third").unwrap_err(),
+        KeyringError::UnknownKid(_)
+    ));
+    assert!(matches!(
+        ring.insert("", Aes256Gcm::from_key(&[3; 32]).await.unwrap())
+            .unwrap_err(),
+        KeyringError::InvalidKid
+    ));
+
+    ring.set_primary("second").unwrap();
+    ring.remove("first").unwrap();
+    assert_eq!(ring.primary_kid(), "second");
+}
+
+#[wasm_bindgen_test]
+async fn should_reject_invalid_ciphertexts() {
+    console_error_panic_hook::set_once();
+
+    let ring = Keyring::new("first", Aes256Gcm::from_key(&[1; 32]).await.unwrap()).unwrap();
+    let encrypted = ring.encrypt(b"Hello World!").await.unwrap();
+
+    for invalid in [&[][..], &[0], &[6, b'f'], &encrypted[..10]] {
+        let err = ring.decrypt(invalid).await.unwrap_err();
+        assert!(matches!(err, KeyringError::InvalidEnvelope), "{err:?}");
+    }
+
+    let mut altered = encrypted.clone();
+    let last = altered.len() - 1;
+    altered[last] ^= 1;
+    let err = ring.decrypt(&altered).await.unwrap_err();
+    assert!(matches!(
+        err,
+        KeyringError::Decryption(DecryptionError::Operation)
+    ));
+}
@@ -0,0 +1,154 @@
+//! Test suite for the IndexedDB keystore.
+
+#![cfg(all(target_arch = "wasm32", feature = "keystore"))]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::{Aes128Gcm, Aes256Gcm};
+use browser_crypto::algorithm::Algorithm;
+use browser_crypto::curve::NamedCurve;
+use browser_crypto::ecdsa::EcdsaKeyPair;
+use browser_crypto::hash::Hash;
+use browser_crypto::hmac::Hmac;
+use browser_crypto::key::CryptoKeyError;
+use browser_crypto::keystore::{self, Keystore, KeystoreError};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+/// Opens an empty database, or returns `None` when IndexedDB is not available
+async fn open(name: &str) -> Option<Keystore> {
+    if !keystore::is_supported() {
+        return None;
+    }
+    keystore::delete_database(name).await.unwrap();
+    Some(Keystore::open(name).await.unwrap())
+}
+
+#[wasm_bindgen_test]
+async fn should_fail_without_indexed_db() {
+    console_error_panic_hook::set_once();
+
+    if keystore::is_supported() {
+        return;
+    }
+    let err = Keystore::open("keystore-unavailable").await.unwrap_err();
+    assert!(matches!(err, KeystoreError::Unavailable));
+}
+
+#[wasm_bindgen_test]
+async fn should_store_non_extractable_key() {
+    console_error_panic_hook::set_once();
+
+    let Some(store) = open("keystore-aes-gcm").await else {
+        return;
+    };
+    let cipher = Aes256Gcm::generate(false).await.unwrap();
+    let nonce = Aes256Gcm::generate_nonce().unwrap();
+    let encrypted = cipher.encrypt(&nonce, b"Hello World!").await.unwrap();
+    store.put("main", &cipher).await.unwrap();
+    store.close();
+
+    // reopening the database, like after a page reload
+    let store = Keystore::open("keystore-aes-gcm").await.unwrap();
+    let loaded = store.get::<Aes256Gcm>("main").await.unwrap().unwrap();
+    assert!(!loaded.as_crypto_key().unwrap().extractable());
+    let decrypted = loaded.decrypt(&nonce, &encrypted).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+}
+
+#[wasm_bindgen_test]
+async fn should_list_replace_and_delete_keys() {
+    console_error_panic_hook::set_once();
+
+    let Some(store) = open("keystore-ids").await else {
+        return;
+    };
+    assert!(store.get::<Aes256Gcm>("missing").await.unwrap().is_none());
+
+    let first = Aes256Gcm::generate(false).await.unwrap();
+    let second = Aes256Gcm::generate(false).await.unwrap();
+    store.put("b", &first).await.unwrap();
+    store.put("a", &first).await.unwrap();
+    store.put("a", &second).await.unwrap();
+    assert_eq!(store.ids().await.unwrap(), vec!["a", "b"]);
+
+    // the replaced key decrypts what the second cipher encrypted
+    let nonce = Aes256Gcm::generate_nonce().unwrap();
+    let encrypted = second.encrypt(&nonce, b"Hello World!").await.unwrap();
+    let loaded = store.get::<Aes256Gcm>("a").await.unwrap().unwrap();
+    assert!(loaded.decrypt(&nonce, &encrypted).await.is_ok());
+
+    store.delete("a").await.unwrap();
+    store.delete("unknown").await.unwrap();
+    assert_eq!(store.ids().await.unwrap(), vec!["b"]);
+    assert!(store.get::<Aes256Gcm>("a").await.unwrap().is_none());
+}
+
+#[wasm_bindgen_test]
+async fn should_store_signing_keys() {
+    console_error_panic_hook::set_once();
+
+    let Some(store) = open("keystore-signing").await else {
+        return;
+    };
+    let pair = EcdsaKeyPair::generate(NamedCurve::P384, Hash::Sha384)
+        .await
+        .unwrap();
+    let hmac = Hmac::generate(Hash::Sha512).await.unwrap();
+    store.put("ecdsa", &pair).await.unwrap();
+    store.put("ecdsa-public", &pair.public_key).await.unwrap();
+    store.put("hmac", &hmac).await.unwrap();
+
+    let loaded = store.get::<EcdsaKeyPair>("ecdsa").await.unwrap().unwrap();
+    assert_eq!(loaded.private_key.curve(), NamedCurve::P384);
+    assert_eq!(loaded.private_key.hash(), Hash::Sha384);
+    let signature = loaded.private_key.sign(b"Hello World!").await.unwrap();
+    assert!(pair
+        .public_key
+        .verify(&signature, b"Hello World!")
+        .await
+        .unwrap());
+
+    let public_key = store
+        .get::<browser_crypto::ecdsa::EcdsaPublicKey>("ecdsa-public")
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(public_key
+        .verify(&signature, b"Hello World!")
+        .await
+        .unwrap());
+
+    let loaded = store.get::<Hmac>("hmac").await.unwrap().unwrap();
+    assert_eq!(loaded.hash(), Hash::Sha512);
+    let tag = hmac.sign(b"Hello World!").await.unwrap();
+    assert!(loaded.verify(&tag, b"Hello World!").await.unwrap());
+}
+
+#[wasm_bindgen_test]
+async fn should_fail_loading_another_key_type() {
+    console_error_panic_hook::set_once();
+
+    let Some(store) = open("keystore-mismatch").await else {
+        return;
+    };
+    let cipher = Aes256Gcm::generate(false).await.unwrap();
+    store.put("main", &cipher).await.unwrap();
+
+    let err = store.get::<Aes128Gcm>("main").await.unwrap_err();
+    assert!(matches!(
+        err,
+        KeystoreError::CryptoKey(CryptoKeyError::InvalidLength {
+            expected: 128,
+            found: 256
+        })
+    ));
+    let err = store.get::<Hmac>("main").await.unwrap_err();
+    assert!(matches!(
+        err,
+        KeystoreError::CryptoKey(CryptoKeyError::InvalidAlgorithm { .. })
+    ));
+    let err = store.get::<EcdsaKeyPair>("main").await.unwrap_err();
+    assert!(matches!(err, KeystoreError::InvalidEntry));
+}
@@ -0,0 +1,66 @@
+//! Test suite for the key identifiers.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::Algorithm;
+use browser_crypto::curve::NamedCurve;
+use browser_crypto::ecdsa::EcdsaKeyPair;
+use browser_crypto::hash::Hash;
+use browser_crypto::jwk::Jwk;
+use browser_crypto::keyring::Keyring;
+use browser_crypto::kid::Tagged;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_encrypt_like_the_inner_cipher() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Tagged::new("2024", Aes256Gcm::from_key(&[1; 32]).await.unwrap());
+    assert_eq!(cipher.kid(), "2024");
+
+    let nonce = Tagged::<Aes256Gcm>::generate_nonce().unwrap();
+    let encrypted = cipher.encrypt(&nonce, b"Hello World!").await.unwrap();
+    let inner_nonce = browser_crypto::algorithm::Nonce::from_slice(&nonce.to_vec()).unwrap();
+    let decrypted = cipher
+        .inner()
+        .decrypt(&inner_nonce, &encrypted)
+        .await
+        .unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+
+    // tagged ciphers can be used wherever a cipher is expected
+    let keyring = Keyring::new(cipher.kid().to_string(), cipher.clone()).unwrap();
+    let encrypted = keyring.encrypt(b"Hello World!").await.unwrap();
+    assert_eq!(keyring.decrypt(&encrypted).await.unwrap(), b"Hello World!");
+}
+
+#[wasm_bindgen_test]
+async fn should_export_jwk_with_kid() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Tagged::new("secret-1", Aes256Gcm::generate(true).await.unwrap());
+    let jwk = cipher.to_jwk().await.unwrap();
+    assert!(matches!(jwk, Jwk::Oct(_)));
+    assert_eq!(jwk.kid(), Some("secret-1"));
+
+    let pair = EcdsaKeyPair::generate(NamedCurve::P256, Hash::Sha256)
+        .await
+        .unwrap();
+    let pair = Tagged::new("signing-1", pair);
+    let jwk = pair.public_jwk().await.unwrap();
+    assert!(matches!(&jwk, Jwk::Ec(inner) if inner.d.is_none()));
+    assert_eq!(jwk.kid(), Some("signing-1"));
+
+    // the methods of the key are available on the tagged key
+    let signature = pair.private_key.sign(b"Hello World!").await.unwrap();
+    assert!(pair
+        .public_key
+        .verify(&signature, b"Hello World!")
+        .await
+        .unwrap());
+}
@@ -0,0 +1,140 @@
+//! Test suite for the localStorage keystore, only available on the main
+//! browser thread.
+
+#![cfg(all(target_arch = "wasm32", feature = "keystore"))]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::{Aes128Gcm, Aes256Gcm};
+use browser_crypto::aes_kw::Aes256Kw;
+use browser_crypto::algorithm::Algorithm;
+use browser_crypto::key::UnwrapKeyError;
+use browser_crypto::local_keystore::{self, LocalKeystore, LocalKeystoreError};
+use browser_crypto::password::PasswordError;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Creates an empty store, or returns `None` when localStorage is not
+/// available
+fn open(prefix: &str, passphrase: &[u8]) -> Option<LocalKeystore> {
+    if !local_keystore::is_supported() {
+        return None;
+    }
+    let store = LocalKeystore::with_passphrase_and_iterations(prefix, passphrase, 1000).unwrap();
+    for id in store.ids().unwrap() {
+        store.delete(&id).unwrap();
+    }
+    Some(store)
+}
+
+#[wasm_bindgen_test]
+async fn should_fail_without_local_storage() {
+    console_error_panic_hook::set_once();
+
+    if local_keystore::is_supported() {
+        return;
+    }
+    let err = LocalKeystore::with_passphrase("local-keystore.", b"passphrase").unwrap_err();
+    assert!(matches!(err, LocalKeystoreError::Unavailable));
+}
+
+#[wasm_bindgen_test]
+async fn should_store_key_with_passphrase() {
+    console_error_panic_hook::set_once();
+
+    let Some(store) = open("local-keystore-passphrase.", b"passphrase") else {
+        return;
+    };
+    let cipher = Aes256Gcm::generate(true).await.unwrap();
+    let nonce = Aes256Gcm::generate_nonce().unwrap();
+    let encrypted = cipher.encrypt(&nonce, b"Hello World!").await.unwrap();
+    store.put("main", &cipher).await.unwrap();
+    store.put("other", &cipher).await.unwrap();
+    assert_eq!(store.ids().unwrap(), vec!["main", "other"]);
+
+    let loaded = store.get::<Aes256Gcm>("main").await.unwrap().unwrap();
+    assert!(!loaded.as_crypto_key().unwrap().extractable());
+    let decrypted = loaded.decrypt(&nonce, &encrypted).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+
+    store.delete("other").unwrap();
+    assert_eq!(store.ids().unwrap(), vec!["main"]);
+    assert!(store.get::<Aes256Gcm>("other").await.unwrap().is_none());
+}
+
+#[wasm_bindgen_test]
+async fn should_store_key_with_wrapping_key() {
+    console_error_panic_hook::set_once();
+
+    if open("local-keystore-kek.", b"passphrase").is_none() {
+        return;
+    }
+    let wrapping_key = Aes256Kw::from_key(&[1u8; 32]).await.unwrap();
+    let store = LocalKeystore::with_wrapping_key("local-keystore-kek.", wrapping_key).unwrap();
+    let cipher = Aes256Gcm::generate(true).await.unwrap();
+    store.put("main", &cipher).await.unwrap();
+
+    let loaded = store.get::<Aes256Gcm>("main").await.unwrap().unwrap();
+    let nonce = Aes256Gcm::generate_nonce().unwrap();
+    let encrypted = cipher.encrypt(&nonce, b"Hello World!").await.unwrap();
+    assert!(loaded.decrypt(&nonce, &encrypted).await.is_ok());
+
+    let other_key = Aes256Kw::from_key(&[2u8; 32]).await.unwrap();
+    let other = LocalKeystore::with_wrapping_key("local-keystore-kek.", other_key).unwrap();
+    let err = other.get::<Aes256Gcm>("main").await.unwrap_err();
+    assert!(matches!(
+        err,
+        LocalKeystoreError::Unwrap(UnwrapKeyError::Operation)
+    ));
+}
+
+#[wasm_bindgen_test]
+async fn should_fail_with_wrong_protection() {
+    console_error_panic_hook::set_once();
+
+    let Some(store) = open("local-keystore-wrong.", b"passphrase") else {
+        return;
+    };
+    let cipher = Aes256Gcm::generate(true).await.unwrap();
+    store.put("main", &cipher).await.unwrap();
+
+    let wrong = LocalKeystore::with_passphrase_and_iterations(
+        "local-keystore-wrong.",
+        b"wrong passphrase",
+        1000,
+    )
+    .unwrap();
+    let err = wrong.get::<Aes256Gcm>("main").await.unwrap_err();
+    assert!(matches!(
+        err,
+        LocalKeystoreError::Password(PasswordError::Unwrap(UnwrapKeyError::Operation))
+    ));
+
+    let err = store.get::<Aes128Gcm>("main").await.unwrap_err();
+    assert!(matches!(
+        err,
+        LocalKeystoreError::Password(PasswordError::Unwrap(UnwrapKeyError::InvalidKey))
+    ));
+
+    let wrapping_key = Aes256Kw::from_key(&[1u8; 32]).await.unwrap();
+    let kek = LocalKeystore::with_wrapping_key("local-keystore-wrong.", wrapping_key).unwrap();
+    let err = kek.get::<Aes256Gcm>("main").await.unwrap_err();
+    assert!(matches!(err, LocalKeystoreError::ProtectionMismatch(scheme) if scheme == "pbkdf2"));
+}
+
+#[wasm_bindgen_test]
+async fn should_fail_storing_non_extractable_key() {
+    console_error_panic_hook::set_once();
+
+    let Some(store) = open("local-keystore-extractable.", b"passphrase") else {
+        return;
+    };
+    let cipher = Aes256Gcm::generate(false).await.unwrap();
+    let err = store.put("main", &cipher).await.unwrap_err();
+    assert!(matches!(
+        err,
+        LocalKeystoreError::Password(PasswordError::Wrap(_))
+    ));
+    assert!(store.ids().unwrap().is_empty());
+}
@@ -0,0 +1,51 @@
+//! Test suite for the latency metrics.
+
+#![cfg(all(target_arch = "wasm32", feature = "metrics"))]
+
+extern crate wasm_bindgen_test;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::{Algorithm, Nonce};
+use browser_crypto::context::Operation;
+use browser_crypto::metrics::{self, Measurement};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_record_measurements() {
+    console_error_panic_hook::set_once();
+
+    let recorded = Rc::new(RefCell::new(Vec::new()));
+    let sink = recorded.clone();
+    metrics::set_metrics(move |measurement: &Measurement<'_>| {
+        sink.borrow_mut().push((
+            measurement.operation,
+            measurement.algorithm.to_string(),
+            measurement.size,
+            measurement.duration_ms,
+            measurement.error_code,
+        ));
+    });
+
+    let cipher = Aes256Gcm::from_key(&[0x42; 32]).await.unwrap();
+    let nonce = Nonce::<Aes256Gcm>::generate().unwrap();
+    let encrypted = cipher.encrypt(&nonce, b"hello").await.unwrap();
+    cipher.decrypt(&nonce, &encrypted[1..]).await.unwrap_err();
+    metrics::clear_metrics();
+    cipher.encrypt(&nonce, b"hello").await.unwrap();
+
+    let recorded = recorded.borrow();
+    assert_eq!(recorded.len(), 3);
+    assert_eq!(recorded[0].0, Operation::ImportKey);
+    assert_eq!(recorded[0].1, "AES-GCM");
+    assert_eq!(recorded[1].0, Operation::Encrypt);
+    assert_eq!(recorded[1].2, Some(5));
+    assert_eq!(recorded[1].4, None);
+    assert_eq!(recorded[2].0, Operation::Decrypt);
+    assert_eq!(recorded[2].4, Some("decryption.operation"));
+    assert!(recorded.iter().all(|measurement| measurement.3 >= 0.0));
+}
@@ -0,0 +1,88 @@
+//! Test suite for the injectable backend.
+
+#![cfg(all(target_arch = "wasm32", feature = "mock"))]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::{Algorithm, DecryptionError, EncryptionError, Nonce, NonceError};
+use browser_crypto::context::Operation;
+use browser_crypto::mock::{self, MockBackend};
+use browser_crypto::Error;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+fn exception(name: &str) -> Error {
+    Error::DomException(name.into(), "injected by the test".into())
+}
+
+#[wasm_bindgen_test]
+fn should_generate_deterministic_nonces() {
+    console_error_panic_hook::set_once();
+
+    mock::set_backend(MockBackend::with_fixed_random(&[0x42]));
+    let nonce = Nonce::<Aes256Gcm>::generate().unwrap();
+    assert_eq!(nonce.as_ref(), &[0x42; 12]);
+
+    mock::set_backend(MockBackend::with_seed(7));
+    let first = Nonce::<Aes256Gcm>::generate().unwrap().to_vec();
+    let second = Nonce::<Aes256Gcm>::generate().unwrap().to_vec();
+    assert_ne!(first, second);
+    mock::set_backend(MockBackend::with_seed(7));
+    assert_eq!(Nonce::<Aes256Gcm>::generate().unwrap().to_vec(), first);
+    assert_eq!(Nonce::<Aes256Gcm>::generate().unwrap().to_vec(), second);
+
+    mock::clear_backend();
+    assert_ne!(
+        Nonce::<Aes256Gcm>::generate().unwrap().as_ref(),
+        &[0x42; 12]
+    );
+}
+
+#[wasm_bindgen_test]
+fn should_fail_random_generation() {
+    console_error_panic_hook::set_once();
+
+    let backend = MockBackend::new();
+    backend.fail_next_random(exception("QuotaExceededError"));
+    mock::set_backend(backend.clone());
+    assert!(matches!(
+        Nonce::<Aes256Gcm>::generate(),
+        Err(NonceError::QuotaExceeded)
+    ));
+    assert_eq!(backend.pending_failures(), 0);
+    Nonce::<Aes256Gcm>::generate().unwrap();
+    mock::clear_backend();
+}
+
+#[wasm_bindgen_test]
+async fn should_fail_operations() {
+    console_error_panic_hook::set_once();
+
+    let backend = MockBackend::with_fixed_random(&[0]);
+    mock::set_backend(backend.clone());
+    let cipher = Aes256Gcm::from_key(&[0x42; 32]).await.unwrap();
+    let nonce = Nonce::<Aes256Gcm>::generate().unwrap();
+    let encrypted = cipher.encrypt(&nonce, b"hello").await.unwrap();
+
+    backend
+        .fail_next(Operation::Decrypt, exception("OperationError"))
+        .fail_next(Operation::Encrypt, exception("InvalidAccessError"));
+    assert!(matches!(
+        cipher.encrypt(&nonce, b"hello").await,
+        Err(EncryptionError::InvalidAccess)
+    ));
+    assert!(matches!(
+        cipher.decrypt(&nonce, &encrypted).await,
+        Err(DecryptionError::Operation)
+    ));
+    assert_eq!(cipher.decrypt(&nonce, &encrypted).await.unwrap(), b"hello");
+
+    backend.fail_next_any(Error::Thrown("unexpected".into()));
+    assert!(matches!(
+        cipher.encrypt(&nonce, b"hello").await,
+        Err(EncryptionError::Generic(Error::Thrown(_)))
+    ));
+    mock::clear_backend();
+}
@@ -0,0 +1,123 @@
+//! Test suite for the software backend of the native targets.
+
+#![cfg(all(not(target_arch = "wasm32"), feature = "native"))]
+
+use std::future::Future;
+use std::task::{Context, Poll, Waker};
+
+use browser_crypto::aes_gcm::{Aes128Gcm, Aes256Gcm};
+use browser_crypto::algorithm::{Algorithm, DecryptionError, EncryptionError, Nonce};
+use browser_crypto::hash::Hash;
+use browser_crypto::key::{ExportKeyError, ImportKeyError};
+use browser_crypto::sealed::Sealed;
+use browser_crypto::{Error, ErrorCode};
+
+/// Runs a future to completion, the native operations never being pending
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+#[test]
+fn should_match_known_answers() {
+    // test cases 1 and 13 of the GCM specification, an empty payload with a
+    // key and a nonce made of zeros
+    let nonce = Nonce::from([0; 12]);
+    let cipher = block_on(Aes128Gcm::from_key(&[0; 16])).unwrap();
+    let tag = block_on(cipher.encrypt(&nonce, &[])).unwrap();
+    assert_eq!(
+        browser_crypto::encoding::hex_encode(&tag),
+        "58e2fccefa7e3061367f1d57a4e7455a"
+    );
+    let cipher = block_on(Aes256Gcm::from_key(&[0; 32])).unwrap();
+    let tag = block_on(cipher.encrypt(&Nonce::from([0; 12]), &[])).unwrap();
+    assert_eq!(
+        browser_crypto::encoding::hex_encode(&tag),
+        "530f8afbc74536b9a963b4f1c4cb738b"
+    );
+}
+
+#[test]
+fn should_encrypt_and_decrypt() {
+    let cipher = block_on(Aes256Gcm::generate(false)).unwrap();
+    let sealed = block_on(cipher.seal(b"Hello World!")).unwrap();
+    assert_eq!(block_on(cipher.open(&sealed)).unwrap(), b"Hello World!");
+
+    let mut altered = sealed.clone();
+    altered[20] ^= 1;
+    assert!(matches!(
+        block_on(cipher.open(&altered)),
+        Err(DecryptionError::Operation)
+    ));
+    let other = block_on(Aes256Gcm::generate(false)).unwrap();
+    assert!(matches!(
+        block_on(other.open(&sealed)),
+        Err(DecryptionError::Operation)
+    ));
+}
+
+#[test]
+fn should_reject_invalid_keys_and_nonces() {
+    assert!(matches!(
+        block_on(Aes256Gcm::from_key(&[0; 16])),
+        Err(ImportKeyError::InvalidKeyFormat)
+    ));
+    let cipher = block_on(Aes256Gcm::from_hex_key(&"42".repeat(32))).unwrap();
+    let nonce = Nonce::<Aes256Gcm>::from_slice_unchecked_len(&[0; 16]).unwrap();
+    assert!(matches!(
+        block_on(cipher.encrypt(&nonce, b"hello")),
+        Err(EncryptionError::Operation)
+    ));
+}
+
+#[test]
+fn should_fail_methods_relying_on_a_crypto_key() {
+    let cipher = block_on(Aes256Gcm::generate(true)).unwrap();
+    assert!(matches!(cipher.as_crypto_key(), Err(Error::Unsupported)));
+    assert!(matches!(
+        block_on(cipher.to_jwk()),
+        Err(ExportKeyError::Generic(Error::Unsupported))
+    ));
+    assert!(matches!(
+        block_on(cipher.encrypt_js(&Nonce::from([0; 12]), b"hello".as_slice())),
+        Err(EncryptionError::Generic(Error::Unsupported))
+    ));
+    let err = block_on(cipher.export_protected("passphrase")).unwrap_err();
+    assert_eq!(err.code(), "generic.unsupported");
+}
+
+#[test]
+fn should_seal_with_additional_data() {
+    let cipher = block_on(Aes256Gcm::generate(false)).unwrap();
+    let sealed = block_on(Sealed::seal(&cipher, b"header", b"Hello World!")).unwrap();
+    let sealed = Sealed::from_bytes(&sealed.to_bytes()).unwrap();
+    assert_eq!(
+        block_on(sealed.open(&cipher, b"header")).unwrap(),
+        b"Hello World!"
+    );
+    assert!(block_on(sealed.open(&cipher, b"other")).is_err());
+}
+
+#[test]
+fn should_compute_digests() {
+    let digest = block_on(Hash::Sha256.digest(b"abc")).unwrap();
+    assert_eq!(
+        browser_crypto::encoding::hex_encode(&digest),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(block_on(Hash::Sha384.digest(b"abc")).unwrap().len(), 48);
+}
+
+#[cfg(feature = "test-vectors")]
+#[test]
+fn should_pass_the_embedded_vectors() {
+    let vectors = browser_crypto::vectors::embedded_vectors();
+    let report = block_on(browser_crypto::vectors::run_aes_gcm(&vectors));
+    report.assert_success();
+    assert_eq!(report.passed, vectors.len());
+}
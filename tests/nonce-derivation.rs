@@ -0,0 +1,107 @@
+//! Test suite for the nonces derived from message identifiers.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::{Aes128Gcm, Aes256Gcm};
+use browser_crypto::algorithm::{Algorithm, Nonce};
+use browser_crypto::hash::Hash;
+use browser_crypto::hkdf::Hkdf;
+use browser_crypto::nonce_derivation::NonceContext;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+const SECRET: &[u8] = &[7; 32];
+
+#[wasm_bindgen_test]
+async fn should_derive_same_nonce() {
+    console_error_panic_hook::set_once();
+
+    let context = NonceContext::new(SECRET, "tests/messages").await.unwrap();
+    let first = Nonce::<Aes256Gcm>::derive(&context, 42).await.unwrap();
+    let second = Nonce::<Aes256Gcm>::derive(&context, 42).await.unwrap();
+    assert_eq!(first.as_ref().len(), 12);
+    assert_eq!(first.as_ref(), second.as_ref());
+
+    // the info is the label, the nonce size, the length of the domain, the
+    // domain and the message identifier
+    let mut info = b"browser-crypto/nonce/v1".to_vec();
+    info.extend_from_slice(&[0, 0, 0, 12, 0, 0, 0, 14]);
+    info.extend_from_slice(b"tests/messages");
+    info.extend_from_slice(&42u64.to_be_bytes());
+    let expected = Hkdf::from_key(SECRET, Hash::Sha256, &[], &info)
+        .await
+        .unwrap()
+        .derive_bits(96)
+        .await
+        .unwrap();
+    assert_eq!(first.as_ref(), expected.as_slice());
+}
+
+#[wasm_bindgen_test]
+async fn should_separate_messages_and_domains() {
+    console_error_panic_hook::set_once();
+
+    let context = NonceContext::new(SECRET, "tests/messages").await.unwrap();
+    let other_domain = NonceContext::new(SECRET, "tests/attachments")
+        .await
+        .unwrap();
+    let other_secret = NonceContext::new(&[8; 32], "tests/messages").await.unwrap();
+
+    let nonce = Nonce::<Aes256Gcm>::derive(&context, 1).await.unwrap();
+    for other in [
+        Nonce::<Aes256Gcm>::derive(&context, 2).await.unwrap(),
+        Nonce::<Aes256Gcm>::derive(&other_domain, 1).await.unwrap(),
+        Nonce::<Aes256Gcm>::derive(&other_secret, 1).await.unwrap(),
+    ] {
+        assert_ne!(nonce.as_ref(), other.as_ref());
+    }
+
+    let by_name = Nonce::<Aes256Gcm>::derive(&context, "message-1")
+        .await
+        .unwrap();
+    let by_bytes = Nonce::<Aes256Gcm>::derive(&context, b"message-1".as_slice())
+        .await
+        .unwrap();
+    assert_eq!(by_name.as_ref(), by_bytes.as_ref());
+}
+
+#[wasm_bindgen_test]
+async fn should_derive_from_hkdf() {
+    console_error_panic_hook::set_once();
+
+    let hkdf = Hkdf::from_key(SECRET, Hash::Sha256, b"", b"ignored")
+        .await
+        .unwrap();
+    let from_hkdf = NonceContext::from_hkdf(&hkdf, "tests/messages");
+    let context = NonceContext::new(SECRET, "tests/messages").await.unwrap();
+    assert_eq!(from_hkdf.domain(), "tests/messages");
+    assert_eq!(
+        Nonce::<Aes256Gcm>::derive(&from_hkdf, 3)
+            .await
+            .unwrap()
+            .as_ref(),
+        Nonce::<Aes256Gcm>::derive(&context, 3)
+            .await
+            .unwrap()
+            .as_ref()
+    );
+}
+
+#[wasm_bindgen_test]
+async fn should_encrypt_with_derived_nonce() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes128Gcm::generate(false).await.unwrap();
+    let context = NonceContext::new(SECRET, "tests/messages").await.unwrap();
+    let encrypted = {
+        let nonce = Nonce::<Aes128Gcm>::derive(&context, 7).await.unwrap();
+        cipher.encrypt(&nonce, b"Hello World!").await.unwrap()
+    };
+    // the nonce is derived again instead of being stored
+    let nonce = Nonce::<Aes128Gcm>::derive(&context, 7).await.unwrap();
+    let decrypted = cipher.decrypt(&nonce, &encrypted).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+}
@@ -0,0 +1,196 @@
+//! Test suite for the checkpointed nonce sequences.
+
+#![cfg(all(target_arch = "wasm32", feature = "keystore"))]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::{Algorithm, NonceError};
+use browser_crypto::keystore::{self, Keystore};
+use browser_crypto::nonce_manager::{NonceManager, NonceManagerError, NonceStorage};
+use browser_crypto::nonce_sequence::CounterSize;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+/// Builds an in-memory object behaving like a Web Storage, available in any
+/// environment
+fn memory_storage() -> web_sys::Storage {
+    js_sys::Function::new_no_args(
+        "const items = new Map();
+        return {
+            getItem: (key) => (items.has(key) ? items.get(key) : null),
+            setItem: (key, value) => { items.set(key, String(value)); },
+            removeItem: (key) => { items.delete(key); },
+        };",
+    )
+    .call0(&wasm_bindgen::JsValue::UNDEFINED)
+    .unwrap()
+    .unchecked_into()
+}
+
+#[wasm_bindgen_test]
+async fn should_resume_after_reserved_values() {
+    console_error_panic_hook::set_once();
+
+    let storage = memory_storage();
+    let mut manager = NonceManager::<Aes256Gcm>::open(
+        NonceStorage::Web(storage.clone()),
+        "main",
+        CounterSize::U32,
+    )
+    .await
+    .unwrap()
+    .with_reservation(10);
+    let first = manager.next_nonce().await.unwrap();
+    let second = manager.next_nonce().await.unwrap();
+    assert_eq!(&first.as_ref()[8..], &[0, 0, 0, 0]);
+    assert_eq!(&second.as_ref()[8..], &[0, 0, 0, 1]);
+    let entry = storage.get_item("main").unwrap().unwrap();
+    assert!(entry.starts_with("1.4."), "{entry}");
+    assert!(entry.ends_with(".10"), "{entry}");
+
+    // reloading skips the values reserved but not used
+    let mut reloaded = NonceManager::<Aes256Gcm>::open(
+        NonceStorage::Web(storage.clone()),
+        "main",
+        CounterSize::U32,
+    )
+    .await
+    .unwrap();
+    assert_eq!(reloaded.position(), Some(10));
+    let resumed = reloaded.next_nonce().await.unwrap();
+    assert_eq!(&resumed.as_ref()[..8], &first.as_ref()[..8]);
+    assert_eq!(&resumed.as_ref()[8..], &[0, 0, 0, 10]);
+
+    reloaded.delete().await.unwrap();
+    assert!(storage.get_item("main").unwrap().is_none());
+}
+
+#[wasm_bindgen_test]
+async fn should_checkpoint_before_each_reservation() {
+    console_error_panic_hook::set_once();
+
+    let storage = memory_storage();
+    let mut manager = NonceManager::<Aes256Gcm>::open(
+        NonceStorage::Web(storage.clone()),
+        "main",
+        CounterSize::U64,
+    )
+    .await
+    .unwrap()
+    .with_reservation(2);
+    for _ in 0..5 {
+        manager.next_nonce().await.unwrap();
+    }
+    let entry = storage.get_item("main").unwrap().unwrap();
+    assert!(entry.starts_with("1.8."), "{entry}");
+    assert!(entry.ends_with(".6"), "{entry}");
+}
+
+#[wasm_bindgen_test]
+async fn should_encrypt_with_managed_nonces() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::generate(false).await.unwrap();
+    let mut manager = NonceManager::<Aes256Gcm>::open(
+        NonceStorage::Web(memory_storage()),
+        "main",
+        CounterSize::U32,
+    )
+    .await
+    .unwrap();
+    let nonce = manager.next_nonce().await.unwrap();
+    let encrypted = cipher.encrypt(&nonce, b"Hello World!").await.unwrap();
+    let decrypted = cipher.decrypt(&nonce, &encrypted).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+}
+
+#[wasm_bindgen_test]
+async fn should_stay_exhausted_after_reload() {
+    console_error_panic_hook::set_once();
+
+    let storage = memory_storage();
+    storage
+        .set_item("main", &format!("1.4.AAAAAAAAAAA=.{}", u32::MAX))
+        .unwrap();
+    let mut manager = NonceManager::<Aes256Gcm>::open(
+        NonceStorage::Web(storage.clone()),
+        "main",
+        CounterSize::U32,
+    )
+    .await
+    .unwrap();
+    manager.next_nonce().await.unwrap();
+    let err = manager.next_nonce().await.unwrap_err();
+    assert!(matches!(
+        err,
+        NonceManagerError::Nonce(NonceError::Exhausted)
+    ));
+
+    let err = NonceManager::<Aes256Gcm>::open(NonceStorage::Web(storage), "main", CounterSize::U32)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        NonceManagerError::Nonce(NonceError::Exhausted)
+    ));
+}
+
+#[wasm_bindgen_test]
+async fn should_reject_invalid_checkpoints() {
+    console_error_panic_hook::set_once();
+
+    let storage = memory_storage();
+    storage.set_item("invalid", "not a checkpoint").unwrap();
+    storage.set_item("version", "2.4.AAAAAAAAAAA=.0").unwrap();
+    storage.set_item("prefix", "1.4.AAAA.0").unwrap();
+    storage.set_item("size", "1.8.AAAAAA==.0").unwrap();
+    for id in ["invalid", "version", "prefix"] {
+        let err = NonceManager::<Aes256Gcm>::open(
+            NonceStorage::Web(storage.clone()),
+            id,
+            CounterSize::U32,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, NonceManagerError::InvalidEntry), "{id}");
+    }
+    let err = NonceManager::<Aes256Gcm>::open(NonceStorage::Web(storage), "size", CounterSize::U32)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        NonceManagerError::CounterSizeMismatch {
+            expected: CounterSize::U32,
+            stored: CounterSize::U64
+        }
+    ));
+}
+
+#[wasm_bindgen_test]
+async fn should_checkpoint_in_indexed_db() {
+    console_error_panic_hook::set_once();
+
+    if !keystore::is_supported() {
+        return;
+    }
+    keystore::delete_database("nonce-manager").await.unwrap();
+    let database = Keystore::open("nonce-manager").await.unwrap();
+    let storage = NonceStorage::IndexedDb(database.clone());
+    let mut manager = NonceManager::<Aes256Gcm>::open(storage.clone(), "main", CounterSize::U32)
+        .await
+        .unwrap()
+        .with_reservation(4);
+    manager.next_nonce().await.unwrap();
+
+    let mut reloaded = NonceManager::<Aes256Gcm>::open(storage, "main", CounterSize::U32)
+        .await
+        .unwrap();
+    assert_eq!(reloaded.position(), Some(4));
+    reloaded.next_nonce().await.unwrap();
+    // the checkpoints are not listed with the keys
+    assert!(database.ids().await.unwrap().is_empty());
+    database.close();
+}
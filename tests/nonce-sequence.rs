@@ -0,0 +1,122 @@
+//! Test suite for the counter-based nonce sequences.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::{Algorithm, NonceError};
+use browser_crypto::nonce_sequence::{CounterSize, NonceSequence};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+fn should_produce_increasing_nonces() {
+    console_error_panic_hook::set_once();
+
+    let mut sequence = NonceSequence::<Aes256Gcm>::new(CounterSize::U32).unwrap();
+    assert_eq!(sequence.prefix().len(), 8);
+    let first = sequence.next_nonce().unwrap();
+    let second = sequence.next_nonce().unwrap();
+    assert_eq!(&first.as_ref()[..8], sequence.prefix());
+    assert_eq!(&first.as_ref()[8..], &[0, 0, 0, 0]);
+    assert_eq!(&second.as_ref()[8..], &[0, 0, 0, 1]);
+    assert!(first.as_ref() < second.as_ref());
+    assert_eq!(sequence.position(), Some(2));
+
+    let mut sequence = NonceSequence::<Aes256Gcm>::new(CounterSize::U64).unwrap();
+    assert_eq!(sequence.prefix().len(), 4);
+    let nonce = sequence.next_nonce().unwrap();
+    assert_eq!(nonce.as_ref().len(), 12);
+}
+
+#[wasm_bindgen_test]
+async fn should_encrypt_with_sequence_nonces() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::generate(false).await.unwrap();
+    let mut sequence = NonceSequence::<Aes256Gcm>::new(CounterSize::U32).unwrap();
+    for index in 0u8..4 {
+        let nonce = sequence.next_nonce().unwrap();
+        let encrypted = cipher.encrypt(&nonce, &[index; 16]).await.unwrap();
+        let decrypted = cipher.decrypt(&nonce, &encrypted).await.unwrap();
+        assert_eq!(decrypted, vec![index; 16]);
+    }
+}
+
+#[wasm_bindgen_test]
+fn should_resume_sequence() {
+    console_error_panic_hook::set_once();
+
+    let mut sequence = NonceSequence::<Aes256Gcm>::new(CounterSize::U32).unwrap();
+    sequence.next_nonce().unwrap();
+    let mut resumed = NonceSequence::<Aes256Gcm>::from_parts(
+        sequence.prefix(),
+        sequence.position().unwrap(),
+        sequence.counter_size(),
+    )
+    .unwrap();
+    assert_eq!(
+        resumed.next_nonce().unwrap().as_ref(),
+        sequence.next_nonce().unwrap().as_ref()
+    );
+}
+
+#[wasm_bindgen_test]
+fn should_not_reuse_nonces_after_resume() {
+    console_error_panic_hook::set_once();
+
+    let mut sequence = NonceSequence::<Aes256Gcm>::new(CounterSize::U32).unwrap();
+    let used: Vec<_> = (0..3).map(|_| sequence.next_nonce().unwrap()).collect();
+    let mut resumed = NonceSequence::<Aes256Gcm>::from_parts(
+        sequence.prefix(),
+        sequence.position().unwrap(),
+        sequence.counter_size(),
+    )
+    .unwrap();
+    assert_eq!(resumed.position(), Some(3));
+    let mut previous = used.last().unwrap().as_ref().to_vec();
+    for _ in 0..3 {
+        let nonce = resumed.next_nonce().unwrap();
+        assert!(used.iter().all(|used| used.as_ref() != nonce.as_ref()));
+        assert!(nonce.as_ref() > previous.as_slice());
+        previous = nonce.as_ref().to_vec();
+    }
+}
+
+#[wasm_bindgen_test]
+fn should_refuse_to_wrap_around() {
+    console_error_panic_hook::set_once();
+
+    let mut sequence =
+        NonceSequence::<Aes256Gcm>::from_parts(&[1; 8], u32::MAX as u64, CounterSize::U32).unwrap();
+    let last = sequence.next_nonce().unwrap();
+    assert_eq!(&last.as_ref()[8..], &[0xff; 4]);
+    assert_eq!(sequence.position(), None);
+    assert!(matches!(sequence.next_nonce(), Err(NonceError::Exhausted)));
+    assert!(matches!(sequence.next_nonce(), Err(NonceError::Exhausted)));
+
+    let err =
+        NonceSequence::<Aes256Gcm>::from_parts(&[1; 8], 1 << 32, CounterSize::U32).unwrap_err();
+    assert!(matches!(err, NonceError::Exhausted));
+
+    let mut sequence =
+        NonceSequence::<Aes256Gcm>::from_parts(&[1; 4], u64::MAX, CounterSize::U64).unwrap();
+    sequence.next_nonce().unwrap();
+    assert!(matches!(sequence.next_nonce(), Err(NonceError::Exhausted)));
+}
+
+#[wasm_bindgen_test]
+fn should_reject_invalid_prefix() {
+    console_error_panic_hook::set_once();
+
+    let err = NonceSequence::<Aes256Gcm>::from_parts(&[1; 4], 0, CounterSize::U32).unwrap_err();
+    assert!(matches!(
+        err,
+        NonceError::InvalidSize {
+            expected: 8,
+            received: 4
+        }
+    ));
+}
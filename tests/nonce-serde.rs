@@ -0,0 +1,61 @@
+//! Test suite for the serialization of nonces and salts.
+
+#![cfg(all(target_arch = "wasm32", feature = "serde"))]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::{Algorithm, Nonce};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Envelope {
+    nonce: Nonce<Aes256Gcm>,
+    ciphertext: Vec<u8>,
+}
+
+#[wasm_bindgen_test]
+fn should_serialize_as_base64() {
+    console_error_panic_hook::set_once();
+
+    let nonce = Nonce::<Aes256Gcm>::from_slice(&[1; 12]).unwrap();
+    let json = serde_json::to_string(&nonce).unwrap();
+    assert_eq!(json, "\"AQEBAQEBAQEBAQEB\"");
+
+    let decoded: Nonce<Aes256Gcm> = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded.as_ref(), nonce.as_ref());
+
+    assert!(serde_json::from_str::<Nonce<Aes256Gcm>>("\"AQEB\"").is_err());
+    assert!(serde_json::from_str::<Nonce<Aes256Gcm>>("\"not base64\"").is_err());
+}
+
+#[wasm_bindgen_test]
+async fn should_serialize_envelope() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let nonce = Aes256Gcm::generate_nonce().unwrap();
+    let ciphertext = cipher.encrypt(&nonce, b"Hello World!").await.unwrap();
+    let json = serde_json::to_string(&Envelope { nonce, ciphertext }).unwrap();
+
+    let envelope: Envelope = serde_json::from_str(&json).unwrap();
+    let decrypted = cipher
+        .decrypt(&envelope.nonce, &envelope.ciphertext)
+        .await
+        .unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+}
+
+#[wasm_bindgen_test]
+fn should_serialize_salt_as_base64() {
+    console_error_panic_hook::set_once();
+
+    let salt = browser_crypto::salt::Salt::from_slice(&[1; 16]).unwrap();
+    let json = serde_json::to_string(&salt).unwrap();
+    assert_eq!(json, "\"AQEBAQEBAQEBAQEBAQEBAQ==\"");
+    let decoded: browser_crypto::salt::Salt = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, salt);
+    assert!(serde_json::from_str::<browser_crypto::salt::Salt>("\"AQEB\"").is_err());
+}
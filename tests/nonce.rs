@@ -0,0 +1,110 @@
+//! Test suite for the encodings and conversions of nonces.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::{Nonce, NonceError};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+const BYTES: [u8; 12] = [0, 1, 2, 3, 0xf8, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd, 0xfe, 0xff];
+
+#[wasm_bindgen_test]
+fn should_encode_as_hex() {
+    console_error_panic_hook::set_once();
+
+    let nonce = Nonce::<Aes256Gcm>::from_slice(&BYTES).unwrap();
+    assert_eq!(nonce.to_hex(), "00010203f8f9fafbfcfdfeff");
+
+    let decoded = Nonce::<Aes256Gcm>::from_hex("00010203F8F9FAFBFCFDFEFF").unwrap();
+    assert_eq!(decoded.as_ref(), &BYTES);
+
+    for input in ["0001020", "00010203f8f9fafbfcfdfeffzz", "not hex"] {
+        let err = Nonce::<Aes256Gcm>::from_hex(input).unwrap_err();
+        assert!(matches!(err, NonceError::InvalidEncoding(_)), "{input}");
+    }
+    let err = Nonce::<Aes256Gcm>::from_hex("0001").unwrap_err();
+    assert!(matches!(
+        err,
+        NonceError::InvalidSize {
+            expected: 12,
+            received: 2
+        }
+    ));
+}
+
+#[wasm_bindgen_test]
+fn should_encode_as_base64url() {
+    console_error_panic_hook::set_once();
+
+    let nonce = Nonce::<Aes256Gcm>::from_slice(&BYTES).unwrap();
+    assert_eq!(nonce.to_base64url(), "AAECA_j5-vv8_f7_");
+
+    let decoded = Nonce::<Aes256Gcm>::from_base64url("AAECA_j5-vv8_f7_").unwrap();
+    assert_eq!(decoded.as_ref(), &BYTES);
+
+    let err = Nonce::<Aes256Gcm>::from_base64url("AAECA/j5+vv8/f7/").unwrap_err();
+    assert!(matches!(err, NonceError::InvalidEncoding(_)));
+    let err = Nonce::<Aes256Gcm>::from_base64url("AAEC").unwrap_err();
+    assert!(matches!(err, NonceError::InvalidSize { .. }));
+}
+
+#[wasm_bindgen_test]
+fn should_redact_display() {
+    console_error_panic_hook::set_once();
+
+    let nonce = Nonce::<Aes256Gcm>::from_slice(&BYTES).unwrap();
+    let displayed = nonce.to_string();
+    assert_eq!(displayed, "Nonce([REDACTED; 12 bytes])");
+    assert!(!displayed.contains(&nonce.to_hex()));
+}
+
+#[wasm_bindgen_test]
+fn should_convert_from_bytes() {
+    console_error_panic_hook::set_once();
+
+    let nonce = Nonce::<Aes256Gcm>::from(BYTES);
+    assert_eq!(nonce.as_ref(), &BYTES);
+
+    let nonce = Nonce::<Aes256Gcm>::try_from(BYTES.as_slice()).unwrap();
+    assert_eq!(nonce.as_ref(), &BYTES);
+    let nonce = Nonce::<Aes256Gcm>::try_from(BYTES.to_vec()).unwrap();
+    assert_eq!(nonce.as_ref(), &BYTES);
+
+    let err = Nonce::<Aes256Gcm>::try_from(&BYTES[..8]).unwrap_err();
+    assert!(matches!(
+        err,
+        NonceError::InvalidSize {
+            expected: 12,
+            received: 8
+        }
+    ));
+    let err = Nonce::<Aes256Gcm>::try_from(vec![0; 16]).unwrap_err();
+    assert!(matches!(err, NonceError::InvalidSize { .. }));
+}
+
+#[wasm_bindgen_test]
+fn should_iterate_over_bytes() {
+    console_error_panic_hook::set_once();
+
+    let nonce = Nonce::<Aes256Gcm>::from(BYTES);
+    let mut borrowed = Vec::new();
+    for byte in &nonce {
+        borrowed.push(byte);
+    }
+    assert_eq!(borrowed, BYTES);
+    assert_eq!(nonce.into_iter().collect::<Vec<_>>(), BYTES);
+}
+
+#[wasm_bindgen_test]
+fn should_interoperate_with_aes_gcm_crate() {
+    console_error_panic_hook::set_once();
+
+    let nonce = Nonce::<Aes256Gcm>::from(BYTES);
+    let pure = aes_gcm::Nonce::<aes_gcm::aead::consts::U12>::from_slice(nonce.as_ref());
+    let back = Nonce::<Aes256Gcm>::try_from(pure.as_slice()).unwrap();
+    assert_eq!(back.as_ref(), nonce.as_ref());
+}
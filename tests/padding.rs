@@ -0,0 +1,77 @@
+//! Test suite for the length-hiding padding.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::{Algorithm, DecryptionError};
+use browser_crypto::padding::{Padded, Padding, PaddingError};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+fn should_compute_padme_length() {
+    console_error_panic_hook::set_once();
+
+    // the marker is added before rounding
+    assert_eq!(Padding::Padme.padded_length(0), 1);
+    assert_eq!(Padding::Padme.padded_length(1), 2);
+    assert_eq!(Padding::Padme.padded_length(8), 10);
+    assert_eq!(Padding::Padme.padded_length(999), 1024);
+    assert_eq!(Padding::Padme.padded_length(1024), 1088);
+    for length in 0..4096 {
+        let padded = Padding::Padme.padded_length(length);
+        assert!(padded > length);
+        assert!(padded <= (length + 1) * 112 / 100 + 1, "{length}");
+    }
+}
+
+#[wasm_bindgen_test]
+fn should_compute_block_length() {
+    console_error_panic_hook::set_once();
+
+    assert_eq!(Padding::Block(16).padded_length(0), 16);
+    assert_eq!(Padding::Block(16).padded_length(15), 16);
+    assert_eq!(Padding::Block(16).padded_length(16), 32);
+    assert_eq!(Padding::Block(0).padded_length(5), 6);
+}
+
+#[wasm_bindgen_test]
+fn should_pad_and_unpad() {
+    console_error_panic_hook::set_once();
+
+    for padding in [Padding::Padme, Padding::Block(32)] {
+        for payload in [&b""[..], b"Hello World!", &[0; 100], &[0x80; 33]] {
+            let padded = padding.pad(payload);
+            assert_eq!(padded.len(), padding.padded_length(payload.len()));
+            assert_eq!(padding.unpad(&padded).unwrap(), payload);
+        }
+    }
+    assert_eq!(Padding::Block(4).pad(b"ab"), [b'a', b'b', 0x80, 0]);
+
+    for invalid in [&b""[..], &[0; 4], b"ab\x80\x01", b"ab"] {
+        let err = Padding::Padme.unpad(invalid).unwrap_err();
+        assert!(matches!(err, PaddingError::Invalid));
+    }
+}
+
+#[wasm_bindgen_test]
+async fn should_hide_payload_size() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let padded = Padded::new(cipher.clone(), Padding::Block(64));
+    let short = padded.seal(b"Hi").await.unwrap();
+    let long = padded.seal(b"Hello World!").await.unwrap();
+    assert_eq!(short.len(), 12 + 64 + 16);
+    assert_eq!(short.len(), long.len());
+    assert_eq!(padded.open(&short).await.unwrap(), b"Hi");
+    assert_eq!(padded.open(&long).await.unwrap(), b"Hello World!");
+
+    // the payload encrypted without padding can't be unpadded
+    let encrypted = cipher.seal(b"Hello World!").await.unwrap();
+    let err = padded.open(&encrypted).await.unwrap_err();
+    assert!(matches!(err, DecryptionError::InvalidPadding));
+}
@@ -0,0 +1,195 @@
+//! Test suite for the password-based encryption.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::{Aes128Gcm, Aes256Gcm};
+use browser_crypto::algorithm::Algorithm;
+use browser_crypto::key::UnwrapKeyError;
+use browser_crypto::password::{self, PasswordError};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_encrypt_and_decrypt() {
+    console_error_panic_hook::set_once();
+
+    let encrypted = password::encrypt(b"passphrase", b"Hello World!")
+        .await
+        .unwrap();
+    assert_eq!(encrypted[0], password::VERSION);
+    assert_eq!(encrypted[1..5], 600_000u32.to_be_bytes());
+    assert_eq!(encrypted.len(), 1 + 4 + 16 + 12 + 12 + 16);
+
+    let decrypted = password::decrypt(b"passphrase", &encrypted).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+}
+
+#[wasm_bindgen_test]
+async fn should_use_random_salt_and_nonce() {
+    console_error_panic_hook::set_once();
+
+    let first = password::encrypt_with_iterations(b"passphrase", b"Hello World!", 1000)
+        .await
+        .unwrap();
+    let second = password::encrypt_with_iterations(b"passphrase", b"Hello World!", 1000)
+        .await
+        .unwrap();
+    assert_eq!(first[1..5], 1000u32.to_be_bytes());
+    assert_ne!(first[5..21], second[5..21]);
+    assert_ne!(first[21..], second[21..]);
+    assert_eq!(
+        password::decrypt(b"passphrase", &second).await.unwrap(),
+        b"Hello World!"
+    );
+}
+
+#[wasm_bindgen_test]
+async fn should_reject_invalid_data() {
+    console_error_panic_hook::set_once();
+
+    let encrypted = password::encrypt_with_iterations(b"passphrase", b"Hello World!", 1000)
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        password::decrypt(b"wrong", &encrypted).await.unwrap_err(),
+        PasswordError::Decryption(_)
+    ));
+    assert!(matches!(
+        password::decrypt(b"passphrase", &encrypted[..30])
+            .await
+            .unwrap_err(),
+        PasswordError::InvalidFormat
+    ));
+
+    let mut altered = encrypted.clone();
+    altered[0] = 2;
+    assert!(matches!(
+        password::decrypt(b"passphrase", &altered)
+            .await
+            .unwrap_err(),
+        PasswordError::UnsupportedVersion(2)
+    ));
+
+    // the header is authenticated
+    let mut altered = encrypted.clone();
+    altered[4] ^= 1;
+    assert!(matches!(
+        password::decrypt(b"passphrase", &altered)
+            .await
+            .unwrap_err(),
+        PasswordError::Decryption(_)
+    ));
+}
+
+#[wasm_bindgen_test]
+async fn should_export_and_import_protected_key() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::generate(true).await.unwrap();
+    let nonce = Aes256Gcm::generate_nonce().unwrap();
+    let encrypted = cipher.encrypt(&nonce, b"Hello World!").await.unwrap();
+
+    let backup = cipher.export_protected(b"passphrase").await.unwrap();
+    assert_eq!(backup[0], password::VERSION);
+    // header followed by the key wrapped with AES-KW
+    assert_eq!(backup.len(), 1 + 4 + 16 + 32 + 8);
+
+    let restored = Aes256Gcm::import_protected(b"passphrase", &backup, false)
+        .await
+        .unwrap();
+    assert!(!restored.as_crypto_key().unwrap().extractable());
+    let decrypted = restored.decrypt(&nonce, &encrypted).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+
+    // an extractable key can be backed up again
+    let restored = Aes256Gcm::import_protected(b"passphrase", &backup, true)
+        .await
+        .unwrap();
+    assert!(restored.export_protected(b"other").await.is_ok());
+}
+
+#[wasm_bindgen_test]
+async fn should_reject_invalid_protected_key() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::generate(false).await.unwrap();
+    assert!(matches!(
+        cipher.export_protected(b"passphrase").await.unwrap_err(),
+        PasswordError::Wrap(_)
+    ));
+
+    let cipher = Aes128Gcm::generate(true).await.unwrap();
+    let backup = cipher.export_protected(b"passphrase").await.unwrap();
+    assert!(matches!(
+        Aes128Gcm::import_protected(b"wrong", &backup, false)
+            .await
+            .unwrap_err(),
+        PasswordError::Unwrap(_)
+    ));
+    assert!(matches!(
+        Aes256Gcm::import_protected(b"passphrase", &backup, false)
+            .await
+            .unwrap_err(),
+        PasswordError::Unwrap(UnwrapKeyError::InvalidKey)
+    ));
+    assert!(matches!(
+        Aes128Gcm::import_protected(b"passphrase", &backup[..21], false)
+            .await
+            .unwrap_err(),
+        PasswordError::InvalidFormat
+    ));
+}
+
+#[wasm_bindgen_test]
+async fn should_hash_and_verify_password() {
+    console_error_panic_hook::set_once();
+
+    let stored = password::hash_with_iterations(b"passphrase", 1000)
+        .await
+        .unwrap();
+    assert!(stored.starts_with("$pbkdf2-sha256$i=1000$"), "{stored}");
+    // the salt and the hash are not padded
+    assert!(!stored.ends_with('='));
+    assert!(password::verify(b"passphrase", &stored).await.unwrap());
+    assert!(!password::verify(b"Passphrase", &stored).await.unwrap());
+
+    // the salt is random
+    let other = password::hash_with_iterations(b"passphrase", 1000)
+        .await
+        .unwrap();
+    assert_ne!(stored, other);
+}
+
+#[wasm_bindgen_test]
+async fn should_verify_other_pbkdf2_hashes() {
+    console_error_panic_hook::set_once();
+
+    // PBKDF2-HMAC-SHA512 of "password", with the salt "saltsaltsaltsalt" and 1
+    // iteration
+    let stored = "$pbkdf2-sha512$i=1$c2FsdHNhbHRzYWx0c2FsdA$zMa9LL9XW9NEyc9UKHf8bpNyy/Hx4d85LGz19gOLtXQ4NK7qbwImxKJXu7q+fBhdF5majSti2Ju4PSQnO4oyKQ";
+    assert!(password::verify(b"password", stored).await.unwrap());
+    assert!(!password::verify(b"wrong", stored).await.unwrap());
+}
+
+#[wasm_bindgen_test]
+async fn should_reject_invalid_password_hash() {
+    console_error_panic_hook::set_once();
+
+    for stored in [
+        "",
+        "pbkdf2-sha256$i=1000$c2FsdA$aGFzaA",
+        "$argon2id$v=19$m=65536,t=3,p=4$c2FsdA$aGFzaA",
+        "$pbkdf2-sha256$i=0$c2FsdHNhbHRzYWx0c2FsdA$aGFzaA",
+        "$pbkdf2-sha256$iterations=1000$c2FsdHNhbHRzYWx0c2FsdA$aGFzaA",
+        "$pbkdf2-sha256$i=1000$c2FsdHNhbHRzYWx0c2FsdA$aGFzaA",
+        "$pbkdf2-sha256$i=1000$not base64$aGFzaA",
+        "$pbkdf2-sha256$i=1000$c2FsdA$aGFzaA$extra",
+    ] {
+        let err = password::verify(b"passphrase", stored).await.unwrap_err();
+        assert!(matches!(err, PasswordError::InvalidHash), "{stored}");
+    }
+}
@@ -0,0 +1,72 @@
+//! Test suite for the PBKDF2 implementation.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::Algorithm;
+use browser_crypto::derive::DeriveError;
+use browser_crypto::hash::Hash;
+use browser_crypto::pbkdf2::Pbkdf2;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_match_rfc_vectors() {
+    // RFC 6070, test vectors 1 and 2
+    console_error_panic_hook::set_once();
+
+    let pbkdf2 = Pbkdf2::from_password(b"password", Hash::Sha1, b"salt", 1)
+        .await
+        .unwrap();
+    let derived = pbkdf2.derive_bits(160).await.unwrap();
+    assert_eq!(
+        derived,
+        [
+            0x0c, 0x60, 0xc8, 0x0f, 0x96, 0x1f, 0x0e, 0x71, 0xf3, 0xa9, 0xb5, 0x24, 0xaf, 0x60,
+            0x12, 0x06, 0x2f, 0xe0, 0x37, 0xa6
+        ]
+    );
+
+    let pbkdf2 = Pbkdf2::from_password(b"password", Hash::Sha1, b"salt", 2)
+        .await
+        .unwrap();
+    let derived = pbkdf2.derive_bits(160).await.unwrap();
+    assert_eq!(
+        derived,
+        [
+            0xea, 0x6c, 0x01, 0x4d, 0xc7, 0x2d, 0x6f, 0x8c, 0xcd, 0x1e, 0xd9, 0x2a, 0xce, 0x1d,
+            0x41, 0xf0, 0xd8, 0xde, 0x89, 0x57
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn should_derive_aes_gcm_key_matching_bits() {
+    console_error_panic_hook::set_once();
+
+    let pbkdf2 = Pbkdf2::from_password(b"password", Hash::Sha256, b"some random salt", 1000)
+        .await
+        .unwrap();
+    let cipher = pbkdf2.derive_aes_gcm::<256>().await.unwrap();
+    let bits = pbkdf2.derive_bits(256).await.unwrap();
+    let imported = Aes256Gcm::from_key(&bits).await.unwrap();
+
+    let nonce = Aes256Gcm::generate_nonce().unwrap();
+    let encrypted = cipher.encrypt(&nonce, b"Hello World!").await.unwrap();
+    let decrypted = imported.decrypt(&nonce, &encrypted).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+}
+
+#[wasm_bindgen_test]
+async fn should_fail_with_invalid_length() {
+    console_error_panic_hook::set_once();
+
+    let pbkdf2 = Pbkdf2::from_password(b"password", Hash::Sha256, b"salt", 1)
+        .await
+        .unwrap();
+    let err = pbkdf2.derive_bits(12).await.unwrap_err();
+    assert!(matches!(err, DeriveError::Operation), "{err:?}");
+}
@@ -0,0 +1,44 @@
+//! Test suite for the rand_core adapter.
+
+#![cfg(all(target_arch = "wasm32", feature = "rand_core"))]
+
+extern crate wasm_bindgen_test;
+
+use aes_gcm::aead::{Aead, AeadCore};
+use aes_gcm::KeyInit;
+use browser_crypto::algorithm::{Algorithm, Nonce};
+use browser_crypto::random::BrowserRng;
+use rand_core::RngCore;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+fn should_generate_random_values() {
+    console_error_panic_hook::set_once();
+
+    let mut rng = BrowserRng;
+    assert_ne!(rng.next_u64(), rng.next_u64());
+    let mut first = [0u8; 32];
+    let mut second = [0u8; 32];
+    rng.fill_bytes(&mut first);
+    rng.try_fill_bytes(&mut second).unwrap();
+    assert_ne!(first, second);
+}
+
+#[wasm_bindgen_test]
+async fn should_generate_keys_for_other_crates() {
+    console_error_panic_hook::set_once();
+
+    let key = aes_gcm::Aes256Gcm::generate_key(BrowserRng);
+    let nonce = aes_gcm::Aes256Gcm::generate_nonce(BrowserRng);
+    let pure = aes_gcm::Aes256Gcm::new(&key);
+    let encrypted = pure.encrypt(&nonce, b"Hello World!".as_ref()).unwrap();
+
+    let browser = browser_crypto::aes_gcm::Aes256Gcm::from_key(key.as_slice())
+        .await
+        .unwrap();
+    let nonce = Nonce::from_slice(nonce.as_slice()).unwrap();
+    let decrypted = browser.decrypt(&nonce, &encrypted).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+}
@@ -0,0 +1,116 @@
+//! Test suite for the secure random values.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::random;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+fn should_generate_random_bytes() {
+    console_error_panic_hook::set_once();
+
+    assert!(random::random_bytes(0).unwrap().is_empty());
+    let first = random::random_bytes(32).unwrap();
+    let second = random::random_bytes(32).unwrap();
+    assert_eq!(first.len(), 32);
+    assert_ne!(first, second);
+}
+
+#[wasm_bindgen_test]
+fn should_generate_more_than_quota() {
+    console_error_panic_hook::set_once();
+
+    let len = random::MAX_CHUNK_SIZE * 2 + 5;
+    let bytes = random::random_bytes(len).unwrap();
+    assert_eq!(bytes.len(), len);
+    // each chunk is filled, including the last partial one
+    for chunk in bytes.chunks(random::MAX_CHUNK_SIZE) {
+        assert!(chunk.iter().any(|byte| *byte != 0));
+    }
+    assert_ne!(
+        &bytes[..random::MAX_CHUNK_SIZE],
+        &bytes[random::MAX_CHUNK_SIZE..random::MAX_CHUNK_SIZE * 2]
+    );
+}
+
+#[wasm_bindgen_test]
+fn should_fill_buffer() {
+    console_error_panic_hook::set_once();
+
+    let mut buffer = [0u8; 64];
+    random::fill_random(&mut buffer[16..48]).unwrap();
+    assert_eq!(&buffer[..16], &[0; 16]);
+    assert_eq!(&buffer[48..], &[0; 16]);
+    assert!(buffer[16..48].iter().any(|byte| *byte != 0));
+}
+
+#[wasm_bindgen_test]
+fn should_generate_random_integers() {
+    console_error_panic_hook::set_once();
+
+    let values = (0..8).map(|_| random::u64().unwrap()).collect::<Vec<_>>();
+    assert!(values.iter().any(|value| *value != values[0]));
+    let values = (0..8).map(|_| random::u32().unwrap()).collect::<Vec<_>>();
+    assert!(values.iter().any(|value| *value != values[0]));
+}
+
+#[wasm_bindgen_test]
+fn should_pick_values_in_range() {
+    console_error_panic_hook::set_once();
+
+    let mut seen = [0u32; 6];
+    for _ in 0..600 {
+        let value = random::gen_range(10..16).unwrap();
+        assert!((10..16).contains(&value));
+        seen[(value - 10) as usize] += 1;
+    }
+    assert!(seen.iter().all(|count| *count > 0), "{seen:?}");
+
+    assert_eq!(random::gen_range(7..8).unwrap(), 7);
+    let value = random::gen_range(0..u64::MAX).unwrap();
+    assert!(value < u64::MAX);
+}
+
+#[wasm_bindgen_test]
+#[should_panic]
+fn should_panic_on_empty_range() {
+    let _ = random::gen_range(3..3);
+}
+
+#[wasm_bindgen_test]
+fn should_generate_uuid() {
+    console_error_panic_hook::set_once();
+
+    let first = random::uuid().unwrap();
+    let second = random::uuid().unwrap();
+    assert_ne!(first, second);
+
+    let text = first.to_string();
+    assert_eq!(text.len(), 36);
+    assert_eq!(text.matches('-').count(), 4);
+    assert_eq!(&text[14..15], "4");
+    assert!(matches!(&text[19..20], "8" | "9" | "a" | "b"), "{text}");
+    assert_eq!(first.as_bytes()[6] >> 4, 4);
+    assert_eq!(first.into_bytes()[8] >> 6, 2);
+}
+
+#[wasm_bindgen_test]
+fn should_generate_uuid_without_random_uuid() {
+    console_error_panic_hook::set_once();
+
+    let crypto = js_sys::Reflect::get(&js_sys::global(), &"crypto".into()).unwrap();
+    let prototype = js_sys::Object::get_prototype_of(&crypto);
+    let native = js_sys::Reflect::get(&prototype, &"randomUUID".into()).unwrap();
+    js_sys::Reflect::delete_property(&prototype, &"randomUUID".into()).unwrap();
+    assert!(!js_sys::Reflect::has(&crypto, &"randomUUID".into()).unwrap());
+    let result = random::uuid();
+    js_sys::Reflect::set(&prototype, &"randomUUID".into(), &native).unwrap();
+
+    let text = result.unwrap().to_string();
+    assert_eq!(&text[14..15], "4");
+    assert!(matches!(&text[19..20], "8" | "9" | "a" | "b"), "{text}");
+}
@@ -0,0 +1,35 @@
+//! Test suite for the redaction of the JavaScript exceptions.
+
+#![cfg(all(target_arch = "wasm32", feature = "redact-errors"))]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::{Error, REDACTED};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+fn should_redact_exception_messages() {
+    console_error_panic_hook::set_once();
+
+    let exception =
+        web_sys::DomException::new_with_message_and_name("secret detail", "OperationError")
+            .unwrap();
+    let err = Error::from(JsValue::from(exception));
+    assert!(matches!(
+        &err,
+        Error::DomException(name, message) if name == "OperationError" && message == REDACTED
+    ));
+    assert!(!err.to_string().contains("secret"));
+
+    let err = Error::from(JsValue::from(js_sys::TypeError::new("secret detail")));
+    assert!(matches!(
+        &err,
+        Error::JsError { name, message } if name == "TypeError" && message == REDACTED
+    ));
+
+    let err = Error::from(JsValue::from_str("secret detail"));
+    assert!(matches!(&err, Error::Thrown(value) if value == REDACTED));
+}
@@ -0,0 +1,114 @@
+//! Test suite for the algorithms selected by name.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::curve::NamedCurve;
+use browser_crypto::dyn_algorithm::DynAlgorithm;
+use browser_crypto::ecdh::EcdhKeyPair;
+use browser_crypto::key::ImportKeyError;
+use browser_crypto::padding::{Padded, Padding};
+use browser_crypto::registry::{Registry, RegistryError};
+use browser_crypto::ErrorCode;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_create_the_built_in_ciphers() {
+    console_error_panic_hook::set_once();
+
+    let registry = Registry::ciphers();
+    assert_eq!(
+        registry.names().collect::<Vec<_>>(),
+        [
+            "AES-CBC-128",
+            "AES-CBC-192",
+            "AES-CBC-256",
+            "AES-GCM-128",
+            "AES-GCM-192",
+            "AES-GCM-256"
+        ]
+    );
+
+    let cipher = registry.create("AES-GCM-256", &[1u8; 32]).await.unwrap();
+    assert_eq!((cipher.name(), cipher.key_size()), ("AES-GCM", 32));
+    let sealed = cipher.seal(b"hello").await.unwrap();
+    assert_eq!(cipher.open(&sealed).await.unwrap(), b"hello");
+
+    let cipher = registry.create("AES-CBC-128", &[1u8; 16]).await.unwrap();
+    assert_eq!((cipher.name(), cipher.key_size()), ("AES-CBC", 16));
+}
+
+#[wasm_bindgen_test]
+async fn should_fail_with_unknown_names_and_invalid_keys() {
+    console_error_panic_hook::set_once();
+
+    let registry = Registry::ciphers();
+    let Err(error) = registry.create("aes-gcm-256", &[1u8; 32]).await else {
+        panic!("the names are case sensitive");
+    };
+    assert!(matches!(&error, RegistryError::UnknownAlgorithm(name) if name == "aes-gcm-256"));
+    assert_eq!(error.code(), "registry.unknown_algorithm");
+    assert!(matches!(
+        registry.create("AES-GCM-256", &[1u8; 16]).await,
+        Err(RegistryError::Import(ImportKeyError::InvalidKeyFormat))
+    ));
+}
+
+#[wasm_bindgen_test]
+async fn should_register_custom_entries() {
+    console_error_panic_hook::set_once();
+
+    let mut registry = Registry::ciphers();
+    assert!(!registry.register("PADME-AES-GCM-256", |key| {
+        Box::pin(async move {
+            let cipher = Aes256Gcm::from_key(key).await?;
+            Ok(Box::new(Padded::new(cipher, Padding::Padme)) as Box<dyn DynAlgorithm>)
+        })
+    }));
+    assert!(registry.contains("PADME-AES-GCM-256"));
+    let cipher = registry
+        .create("PADME-AES-GCM-256", &[2u8; 32])
+        .await
+        .unwrap();
+    let sealed = cipher.seal(b"hello").await.unwrap();
+    assert_eq!(cipher.open(&sealed).await.unwrap(), b"hello");
+
+    assert!(registry.remove("AES-CBC-128"));
+    assert!(!registry.remove("AES-CBC-128"));
+    assert!(!registry.contains("AES-CBC-128"));
+    assert!(Registry::ciphers().contains("AES-CBC-128"));
+}
+
+#[wasm_bindgen_test]
+async fn should_create_the_built_in_key_agreements() {
+    console_error_panic_hook::set_once();
+
+    let registry = Registry::key_agreements();
+    assert_eq!(
+        registry.names().collect::<Vec<_>>(),
+        ["ECDH-P256", "ECDH-P384", "ECDH-P521"]
+    );
+
+    let alice = EcdhKeyPair::generate(NamedCurve::P256).await.unwrap();
+    let bob = EcdhKeyPair::generate(NamedCurve::P256).await.unwrap();
+    let pkcs8 = alice.private_key.to_pkcs8().await.unwrap();
+    let private_key = registry.create("ECDH-P256", &pkcs8).await.unwrap();
+    assert_eq!(private_key.curve(), NamedCurve::P256);
+    assert_eq!(
+        private_key.diffie_hellman(&bob.public_key).await.unwrap(),
+        alice
+            .private_key
+            .diffie_hellman(&bob.public_key)
+            .await
+            .unwrap()
+    );
+
+    assert!(matches!(
+        registry.create("ECDH-P384", &pkcs8).await,
+        Err(RegistryError::Import(_))
+    ));
+}
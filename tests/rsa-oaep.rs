@@ -0,0 +1,103 @@
+//! Test suite for the RSA-OAEP implementation.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::{Algorithm, EncryptionError};
+use browser_crypto::hash::Hash;
+use browser_crypto::key::{KeyUnwrapper, KeyWrapper, UnwrapKeyError};
+use browser_crypto::rsa_oaep::{RsaOaepKeyPair, RsaOaepPrivateKey, RsaOaepPublicKey};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_encrypt_and_decrypt() {
+    console_error_panic_hook::set_once();
+
+    let pair = RsaOaepKeyPair::generate(2048, Hash::Sha256).await.unwrap();
+    let encrypted = pair.public_key.encrypt(b"Hello World!").await.unwrap();
+    assert_eq!(encrypted.len(), 256);
+
+    let decrypted = pair.private_key.decrypt(&encrypted).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+}
+
+#[wasm_bindgen_test]
+async fn should_decrypt_in_place() {
+    console_error_panic_hook::set_once();
+
+    let pair = RsaOaepKeyPair::generate(2048, Hash::Sha256).await.unwrap();
+    let mut buffer = pair.public_key.encrypt(b"Hello World!").await.unwrap();
+    let encrypted = buffer.clone();
+    pair.private_key
+        .decrypt_in_place(&mut buffer)
+        .await
+        .unwrap();
+    assert_eq!(buffer, b"Hello World!");
+
+    let other = RsaOaepKeyPair::generate(2048, Hash::Sha256).await.unwrap();
+    let mut buffer = encrypted.clone();
+    assert!(other
+        .private_key
+        .decrypt_in_place(&mut buffer)
+        .await
+        .is_err());
+    assert_eq!(buffer, encrypted);
+}
+
+#[wasm_bindgen_test]
+async fn should_import_exported_keys() {
+    console_error_panic_hook::set_once();
+
+    let pair = RsaOaepKeyPair::generate(2048, Hash::Sha256).await.unwrap();
+    let spki = pair.public_key.to_spki().await.unwrap();
+    let pkcs8 = pair.private_key.to_pkcs8().await.unwrap();
+
+    let public_key = RsaOaepPublicKey::from_spki(&spki, Hash::Sha256)
+        .await
+        .unwrap();
+    let private_key = RsaOaepPrivateKey::from_pkcs8(&pkcs8, Hash::Sha256)
+        .await
+        .unwrap();
+
+    let encrypted = public_key.encrypt(b"Hello World!").await.unwrap();
+    let decrypted = private_key.decrypt(&encrypted).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+}
+
+#[wasm_bindgen_test]
+async fn should_fail_encrypting_large_payload() {
+    console_error_panic_hook::set_once();
+
+    let pair = RsaOaepKeyPair::generate(2048, Hash::Sha256).await.unwrap();
+    let err = pair.public_key.encrypt(&[0; 191]).await.unwrap_err();
+    assert!(matches!(err, EncryptionError::Operation));
+}
+
+#[wasm_bindgen_test]
+async fn should_wrap_and_unwrap_keys() {
+    console_error_panic_hook::set_once();
+
+    let pair = RsaOaepKeyPair::generate(2048, Hash::Sha256).await.unwrap();
+    let dek = Aes256Gcm::generate(true).await.unwrap();
+    let wrapped = pair.public_key.wrap_key(&dek).await.unwrap();
+    assert_eq!(wrapped.len(), 256);
+
+    let nonce = Aes256Gcm::generate_nonce().unwrap();
+    let encrypted = dek.encrypt(&nonce, b"Hello World!").await.unwrap();
+
+    let unwrapped: Aes256Gcm = pair.private_key.unwrap_key(&wrapped).await.unwrap();
+    let decrypted = unwrapped.decrypt(&nonce, &encrypted).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+
+    let other = RsaOaepKeyPair::generate(2048, Hash::Sha256).await.unwrap();
+    let err = other
+        .private_key
+        .unwrap_key::<Aes256Gcm>(&wrapped)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, UnwrapKeyError::Operation), "{err:?}");
+}
@@ -0,0 +1,98 @@
+//! Test suite for the RSASSA-PKCS1-v1_5 implementation.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::hash::Hash;
+use browser_crypto::jwk::{Jwk, RsaJwk};
+use browser_crypto::rsassa_pkcs1::{
+    RsassaPkcs1KeyPair, RsassaPkcs1PrivateKey, RsassaPkcs1PublicKey,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+// RSA public key generated with Node.js
+const JWK_N: &str = "rsXp_G1c48x-1KfNbZJYIGHMySJN7mvXhWGmMOtqgyBKwznFkfNV4g0E5O7tC7JnV8mrAPcm62DxPSoVRcRDIY5kx0tiOitepNOHuJHNXgpRGIGpyB2Jgx83n-42ix9JC0diTIaP1Zjrw7BseGcVAvHlwwLjHytMYBhzCD7AnXUlbNa6Y7xBepRvxfCRztgYz4R7RCj8V4MMiZ9iQ0kWmmqY94O_z9dQfIfNBvNWpqbxOlsjUsh6IRKQSZ4S80umzNnZFtGcQ9GDeqmUlSYvCuVthAQsxPPqt7UQB10ijttzpFtMxUUEUiEZ28VrWHY-seIzGWU4QdbvMwQKVK7W_w";
+const JWK_E: &str = "AQAB";
+// RS256 signature of "Hello World!" generated with Node.js
+const SIGNATURE: [u8; 256] = [
+    0x30, 0x96, 0x40, 0x9b, 0xd2, 0x9d, 0x50, 0xdb, 0xb9, 0x99, 0x1a, 0x52, 0x10, 0x5d, 0x15, 0x89,
+    0x81, 0x17, 0x84, 0x6d, 0x3b, 0xf0, 0xed, 0xe1, 0x69, 0x38, 0x91, 0xde, 0x61, 0x53, 0x9c, 0xf6,
+    0x06, 0xa9, 0x2e, 0xce, 0x0f, 0x8f, 0x33, 0xf8, 0x2b, 0xad, 0x39, 0x29, 0xe3, 0xb7, 0x1d, 0x2f,
+    0xdc, 0xd3, 0xc3, 0x61, 0x5d, 0xb1, 0xfb, 0xf4, 0x6f, 0x4e, 0xf6, 0x69, 0xfc, 0x85, 0x24, 0xd0,
+    0x62, 0x82, 0x2d, 0x2e, 0x99, 0x7d, 0x9f, 0xfc, 0x22, 0xd5, 0xfc, 0x78, 0x91, 0x63, 0xd0, 0x1a,
+    0x14, 0x87, 0x8a, 0xcd, 0xd7, 0x63, 0x8f, 0x5d, 0x87, 0xa7, 0xa1, 0xf1, 0x7d, 0xff, 0x2c, 0x14,
+    0xb9, 0x20, 0x9f, 0x0d, 0xf3, 0x94, 0xb8, 0x41, 0xd7, 0xe5, 0x4f, 0xc4, 0x89, 0xa3, 0xa5, 0x95,
+    0xf4, 0x42, 0x13, 0x76, 0x9f, 0xc6, 0x66, 0xc1, 0x76, 0x8b, 0x9e, 0x04, 0x1f, 0xa1, 0x54, 0x08,
+    0x00, 0xf6, 0x80, 0xc4, 0xb9, 0x7c, 0x18, 0xc9, 0x4d, 0x30, 0xe2, 0x9e, 0xc7, 0x18, 0x60, 0x0c,
+    0x14, 0xf1, 0xf6, 0xf6, 0xfd, 0xe9, 0xc4, 0xae, 0xed, 0xa8, 0x2c, 0xe7, 0x7e, 0x59, 0xf4, 0x32,
+    0x84, 0x2c, 0xcd, 0x92, 0xb5, 0x44, 0xaa, 0xbd, 0xba, 0x20, 0x13, 0x26, 0x04, 0x38, 0xea, 0xb8,
+    0x9f, 0x3e, 0x2e, 0x0c, 0xfb, 0x18, 0x6f, 0xf4, 0xc9, 0x4b, 0xa3, 0x0a, 0x4d, 0xa7, 0x9c, 0xec,
+    0x03, 0xcd, 0x25, 0x93, 0x72, 0xc2, 0x4d, 0x9c, 0xf8, 0x5b, 0x29, 0xc2, 0xdb, 0xa6, 0x7d, 0xa8,
+    0x42, 0x98, 0xf2, 0x72, 0x02, 0xb7, 0x41, 0x0c, 0xd9, 0xca, 0x47, 0x6d, 0x5b, 0x9e, 0x05, 0x28,
+    0x7e, 0x10, 0x17, 0x6d, 0xcc, 0xb7, 0xe9, 0x73, 0xc6, 0x08, 0x5c, 0x35, 0x44, 0x40, 0xd2, 0xd6,
+    0x31, 0xf0, 0x31, 0x3b, 0x69, 0x81, 0x99, 0x76, 0x86, 0xd6, 0x99, 0xf6, 0x38, 0x3f, 0xe5, 0x96,
+];
+
+#[wasm_bindgen_test]
+async fn should_sign_and_verify() {
+    console_error_panic_hook::set_once();
+
+    let pair = RsassaPkcs1KeyPair::generate(2048, Hash::Sha256)
+        .await
+        .unwrap();
+    let signature = pair.private_key.sign(b"Hello World!").await.unwrap();
+    assert_eq!(signature.len(), 256);
+
+    assert!(pair
+        .public_key
+        .verify(&signature, b"Hello World!")
+        .await
+        .unwrap());
+    assert!(!pair
+        .public_key
+        .verify(&signature, b"Hello World?")
+        .await
+        .unwrap());
+}
+
+#[wasm_bindgen_test]
+async fn should_import_exported_keys() {
+    console_error_panic_hook::set_once();
+
+    let pair = RsassaPkcs1KeyPair::generate(2048, Hash::Sha256)
+        .await
+        .unwrap();
+    let spki = pair.public_key.to_spki().await.unwrap();
+    let pkcs8 = pair.private_key.to_pkcs8().await.unwrap();
+
+    let public_key = RsassaPkcs1PublicKey::from_spki(&spki, Hash::Sha256)
+        .await
+        .unwrap();
+    let private_key = RsassaPkcs1PrivateKey::from_pkcs8(&pkcs8, Hash::Sha256)
+        .await
+        .unwrap();
+
+    let signature = private_key.sign(b"Hello World!").await.unwrap();
+    assert!(public_key
+        .verify(&signature, b"Hello World!")
+        .await
+        .unwrap());
+}
+
+#[wasm_bindgen_test]
+async fn should_verify_with_jwk() {
+    console_error_panic_hook::set_once();
+
+    let jwk = Jwk::from(RsaJwk::new(JWK_N, JWK_E)).with_alg("RS256");
+
+    let public_key = RsassaPkcs1PublicKey::from_jwk(&jwk, Hash::Sha256)
+        .await
+        .unwrap();
+    assert!(public_key
+        .verify(&SIGNATURE, b"Hello World!")
+        .await
+        .unwrap());
+}
@@ -0,0 +1,107 @@
+//! Test suite for the salts of the key derivations.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::Algorithm;
+use browser_crypto::hash::Hash;
+use browser_crypto::hkdf::Hkdf;
+use browser_crypto::pbkdf2::Pbkdf2;
+use browser_crypto::salt::{Salt, SaltError};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+fn should_generate_salts() {
+    console_error_panic_hook::set_once();
+
+    let first = Salt::generate().unwrap();
+    let second = Salt::generate().unwrap();
+    assert_eq!(first.len(), Salt::DEFAULT_SIZE);
+    assert_ne!(first, second);
+
+    let long = Salt::generate_with_size(32).unwrap();
+    assert_eq!(long.len(), 32);
+
+    let err = Salt::generate_with_size(8).unwrap_err();
+    assert!(matches!(
+        err,
+        SaltError::TooShort {
+            minimum: 16,
+            received: 8
+        }
+    ));
+}
+
+#[wasm_bindgen_test]
+fn should_check_salt_size() {
+    console_error_panic_hook::set_once();
+
+    let salt = Salt::from_slice(&[1; 16]).unwrap();
+    assert_eq!(salt.as_ref(), &[1; 16]);
+    assert!(matches!(
+        Salt::try_from([1u8; 15].as_slice()),
+        Err(SaltError::TooShort { .. })
+    ));
+
+    let legacy = Salt::from_slice_unchecked_len(&[1; 8]);
+    assert_eq!(legacy.len(), 8);
+    assert!(Salt::from_slice_unchecked_len(&[]).is_empty());
+}
+
+#[wasm_bindgen_test]
+fn should_encode_salts() {
+    console_error_panic_hook::set_once();
+
+    let salt = Salt::from_slice(&[0xfb; 16]).unwrap();
+    assert_eq!(salt.to_hex(), "fb".repeat(16));
+    assert_eq!(Salt::from_hex(&salt.to_hex()).unwrap(), salt);
+    assert_eq!(salt.to_base64url(), "-_v7-_v7-_v7-_v7-_v7-w");
+    assert_eq!(Salt::from_base64url(&salt.to_base64url()).unwrap(), salt);
+
+    assert!(matches!(
+        Salt::from_hex("zz"),
+        Err(SaltError::InvalidEncoding(_))
+    ));
+    assert!(matches!(
+        Salt::from_base64url("+/+/"),
+        Err(SaltError::InvalidEncoding(_))
+    ));
+    assert!(matches!(
+        Salt::from_hex("fbfb"),
+        Err(SaltError::TooShort { .. })
+    ));
+}
+
+#[wasm_bindgen_test]
+async fn should_derive_with_salt() {
+    console_error_panic_hook::set_once();
+
+    let salt = Salt::from_slice(&[3; 16]).unwrap();
+    let typed = Pbkdf2::from_password(b"password", Hash::Sha256, &salt, 1000)
+        .await
+        .unwrap();
+    let raw = Pbkdf2::from_password(b"password", Hash::Sha256, [3u8; 16], 1000)
+        .await
+        .unwrap();
+    assert_eq!(typed.salt(), salt.as_ref());
+    assert_eq!(
+        typed.derive_bits(256).await.unwrap(),
+        raw.derive_bits(256).await.unwrap()
+    );
+
+    let hkdf = Hkdf::from_key(&[4; 32], Hash::Sha256, &salt, b"encryption")
+        .await
+        .unwrap();
+    assert_eq!(hkdf.salt(), salt.as_ref());
+    let cipher = Aes256Gcm::derive_from(&hkdf).await.unwrap();
+    let nonce = Aes256Gcm::generate_nonce().unwrap();
+    let encrypted = cipher.encrypt(&nonce, b"Hello World!").await.unwrap();
+    assert_eq!(
+        cipher.decrypt(&nonce, &encrypted).await.unwrap(),
+        b"Hello World!"
+    );
+}
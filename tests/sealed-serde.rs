@@ -0,0 +1,28 @@
+//! Test suite for the serialization of versioned ciphertexts.
+
+#![cfg(all(target_arch = "wasm32", feature = "serde"))]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::sealed::Sealed;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_serialize_as_base64() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let sealed = Sealed::seal(&cipher, b"", b"Hello World!").await.unwrap();
+    let json = serde_json::to_string(&sealed).unwrap();
+    assert!(json.starts_with("\"AQMA"));
+
+    let decoded: Sealed = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, sealed);
+    assert_eq!(decoded.open(&cipher, b"").await.unwrap(), b"Hello World!");
+
+    assert!(serde_json::from_str::<Sealed>("\"AgMA\"").is_err());
+    assert!(serde_json::from_str::<Sealed>("\"not base64\"").is_err());
+}
@@ -0,0 +1,134 @@
+//! Test suite for the versioned ciphertexts.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::{Aes128Gcm, Aes256Gcm};
+use browser_crypto::algorithm::DecryptionError;
+use browser_crypto::hash::Hash;
+use browser_crypto::kid::Tagged;
+use browser_crypto::sealed::{Sealed, SealedAlgorithm, SealedError};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_seal_and_open() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let sealed = Sealed::seal(&cipher, b"", b"Hello World!").await.unwrap();
+    assert_eq!(sealed.algorithm(), SealedAlgorithm::Aes256Gcm);
+    assert_eq!(sealed.kid(), None);
+    assert_eq!(sealed.nonce().len(), 12);
+    assert_eq!(sealed.ciphertext().len(), 12 + 16);
+
+    let bytes = sealed.to_bytes();
+    assert_eq!(bytes.len(), 4 + 12 + 32 + 12 + 16);
+    assert_eq!(bytes[..3], [1, 3, 0]);
+    let sealed = Sealed::from_bytes(&bytes).unwrap();
+    assert_eq!(sealed.open(&cipher, b"").await.unwrap(), b"Hello World!");
+
+    let other = Aes256Gcm::from_key(&[2; 32]).await.unwrap();
+    let err = sealed.open(&other, b"").await.unwrap_err();
+    assert!(matches!(
+        err,
+        SealedError::Decryption(DecryptionError::Operation)
+    ));
+    let other = Aes128Gcm::from_key(&[1; 16]).await.unwrap();
+    let err = sealed.open(&other, b"").await.unwrap_err();
+    assert!(matches!(
+        err,
+        SealedError::AlgorithmMismatch(SealedAlgorithm::Aes256Gcm)
+    ));
+}
+
+#[wasm_bindgen_test]
+async fn should_check_additional_data() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes128Gcm::from_key(&[1; 16]).await.unwrap();
+    let sealed = Sealed::seal(&cipher, b"user-42", b"Hello World!")
+        .await
+        .unwrap();
+    let aad_hash = Hash::Sha256.digest(b"user-42").await.unwrap();
+    assert_eq!(sealed.aad_hash(), aad_hash);
+    assert_eq!(
+        sealed.open(&cipher, b"user-42").await.unwrap(),
+        b"Hello World!"
+    );
+    let err = sealed.open(&cipher, b"user-43").await.unwrap_err();
+    assert!(matches!(err, SealedError::AadMismatch));
+}
+
+#[wasm_bindgen_test]
+async fn should_record_key_id() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Tagged::new("2024", Aes256Gcm::from_key(&[1; 32]).await.unwrap());
+    let sealed = Sealed::seal(&cipher, b"", b"Hello World!").await.unwrap();
+    assert_eq!(sealed.kid(), Some("2024"));
+    let bytes = sealed.to_bytes();
+    assert_eq!(bytes[..7], [1, 3, 4, b'2', b'0', b'2', b'4']);
+
+    let sealed = Sealed::from_bytes(&bytes).unwrap();
+    assert_eq!(sealed.open(&cipher, b"").await.unwrap(), b"Hello World!");
+    // the key ID is only checked when both sides have one
+    assert!(sealed.open(cipher.inner(), b"").await.is_ok());
+
+    let other = Tagged::new("2025", Aes256Gcm::from_key(&[1; 32]).await.unwrap());
+    let err = sealed.open(&other, b"").await.unwrap_err();
+    assert!(matches!(err, SealedError::KidMismatch(kid) if kid == "2024"));
+
+    let cipher = Tagged::new("", Aes256Gcm::from_key(&[1; 32]).await.unwrap());
+    let err = Sealed::seal(&cipher, b"", b"Hello World!")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, SealedError::InvalidKid));
+}
+
+#[wasm_bindgen_test]
+async fn should_authenticate_header() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let sealed = Sealed::seal(&cipher, b"", b"Hello World!").await.unwrap();
+
+    // adding a key ID to the header
+    let mut bytes = vec![1, 3, 1, b'a'];
+    bytes.extend_from_slice(&sealed.to_bytes()[3..]);
+    let altered = Sealed::from_bytes(&bytes).unwrap();
+    let err = altered.open(&cipher, b"").await.unwrap_err();
+    assert!(matches!(
+        err,
+        SealedError::Decryption(DecryptionError::Operation)
+    ));
+}
+
+#[wasm_bindgen_test]
+fn should_reject_invalid_layout() {
+    console_error_panic_hook::set_once();
+
+    assert!(matches!(
+        Sealed::from_bytes(&[]).unwrap_err(),
+        SealedError::InvalidFormat
+    ));
+    assert!(matches!(
+        Sealed::from_bytes(&[2, 3, 0]).unwrap_err(),
+        SealedError::UnsupportedVersion(2)
+    ));
+    assert!(matches!(
+        Sealed::from_bytes(&[1, 9, 0]).unwrap_err(),
+        SealedError::UnknownAlgorithm(9)
+    ));
+    assert!(matches!(
+        Sealed::from_bytes(&[1, 3, 4, b'a']).unwrap_err(),
+        SealedError::InvalidFormat
+    ));
+    // missing the hash of the additional data
+    assert!(matches!(
+        Sealed::from_bytes(&[1, 3, 0, 12, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]).unwrap_err(),
+        SealedError::InvalidFormat
+    ));
+}
@@ -0,0 +1,74 @@
+//! Test suite for the secrecy integration.
+
+#![cfg(all(target_arch = "wasm32", feature = "secrecy"))]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::{Algorithm, Nonce};
+use browser_crypto::hash::Hash;
+use browser_crypto::hmac::Hmac;
+use browser_crypto::password;
+use browser_crypto::pbkdf2::Pbkdf2;
+use secrecy::{SecretBox, SecretSlice, SecretString};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+async fn should_import_secret_keys() {
+    console_error_panic_hook::set_once();
+
+    let key: SecretSlice<u8> = vec![7u8; 32].into();
+    let cipher = Aes256Gcm::from_key(&key).await.unwrap();
+    let nonce = Nonce::<Aes256Gcm>::from_slice(&[1u8; 12]).unwrap();
+    let encrypted = cipher.encrypt(&nonce, b"Hello World!").await.unwrap();
+
+    // the same key, as plain bytes
+    let cipher = Aes256Gcm::from_key(&[7u8; 32]).await.unwrap();
+    let decrypted = cipher.decrypt(&nonce, &encrypted).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+
+    let key = SecretBox::new(Box::new(vec![3u8; 32]));
+    let hmac = Hmac::from_key(&key, Hash::Sha256).await.unwrap();
+    let signature = hmac.sign(b"payload").await.unwrap();
+    let hmac = Hmac::from_key(&[3u8; 32], Hash::Sha256).await.unwrap();
+    assert!(hmac.verify(&signature, b"payload").await.unwrap());
+}
+
+#[wasm_bindgen_test]
+async fn should_accept_secret_passphrases() {
+    console_error_panic_hook::set_once();
+
+    let passphrase = SecretString::from("correct horse battery staple");
+    let encrypted = password::encrypt_with_iterations(&passphrase, b"Hello World!", 1000)
+        .await
+        .unwrap();
+    let decrypted = password::decrypt("correct horse battery staple", &encrypted)
+        .await
+        .unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+
+    let stored = password::hash_with_iterations(&passphrase, 1000)
+        .await
+        .unwrap();
+    assert!(password::verify(&passphrase, &stored).await.unwrap());
+    assert!(password::verify(b"correct horse battery staple", &stored)
+        .await
+        .unwrap());
+
+    let salt = [0u8; 16];
+    let left = Pbkdf2::from_password(&passphrase, Hash::Sha256, salt, 1000)
+        .await
+        .unwrap()
+        .derive_bits(256)
+        .await
+        .unwrap();
+    let right = Pbkdf2::from_password(b"correct horse battery staple", Hash::Sha256, salt, 1000)
+        .await
+        .unwrap()
+        .derive_bits(256)
+        .await
+        .unwrap();
+    assert_eq!(left, right);
+}
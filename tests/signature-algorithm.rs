@@ -0,0 +1,70 @@
+//! Test suite for the algorithms implementing the SignatureAlgorithm trait.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::curve::NamedCurve;
+use browser_crypto::ecdsa::{Ecdsa, EcdsaKeyPair};
+use browser_crypto::ed25519::{Ed25519, Ed25519KeyPair};
+use browser_crypto::hash::Hash;
+use browser_crypto::hmac::Hmac;
+use browser_crypto::rsassa_pkcs1::{RsassaPkcs1, RsassaPkcs1KeyPair};
+use browser_crypto::signature::SignatureAlgorithm;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+/// Signs and verifies a token with any signature algorithm, returning the
+/// size of the signature
+async fn sign_and_verify<S: SignatureAlgorithm>(
+    signing: &S::SigningKey,
+    verifying: &S::VerifyingKey,
+) -> usize {
+    let signature = S::sign(signing, b"header.payload").await.unwrap();
+    if let Some(size) = S::SIGNATURE_SIZE {
+        assert_eq!(signature.len(), size);
+    }
+    assert!(S::verify(verifying, &signature, b"header.payload")
+        .await
+        .unwrap());
+    assert!(!S::verify(verifying, &signature, b"header.other")
+        .await
+        .unwrap());
+    signature.len()
+}
+
+#[wasm_bindgen_test]
+async fn should_be_generic_over_asymmetric_algorithms() {
+    console_error_panic_hook::set_once();
+
+    let pair = EcdsaKeyPair::generate(NamedCurve::P384, Hash::Sha384)
+        .await
+        .unwrap();
+    assert_eq!(Ecdsa::NAME, "ECDSA");
+    assert_eq!(Ecdsa::SIGNATURE_SIZE, None);
+    let size = sign_and_verify::<Ecdsa>(&pair.private_key, &pair.public_key).await;
+    assert_eq!(size, 96);
+
+    let pair = RsassaPkcs1KeyPair::generate(2048, Hash::Sha256)
+        .await
+        .unwrap();
+    let size = sign_and_verify::<RsassaPkcs1>(&pair.private_key, &pair.public_key).await;
+    assert_eq!(size, 256);
+
+    if browser_crypto::ed25519::is_supported().await {
+        let pair = Ed25519KeyPair::generate().await.unwrap();
+        assert_eq!(Ed25519::SIGNATURE_SIZE, Some(64));
+        sign_and_verify::<Ed25519>(&pair.private_key, &pair.public_key).await;
+    }
+}
+
+#[wasm_bindgen_test]
+async fn should_sign_and_verify_with_the_same_hmac_key() {
+    console_error_panic_hook::set_once();
+
+    let key = Hmac::generate(Hash::Sha512).await.unwrap();
+    assert_eq!(Hmac::NAME, "HMAC");
+    let size = sign_and_verify::<Hmac>(&key, &key).await;
+    assert_eq!(size, 64);
+}
@@ -0,0 +1,78 @@
+//! Test suite for the compatibility with the signature crate.
+
+#![cfg(all(target_arch = "wasm32", feature = "signature"))]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::curve::NamedCurve;
+use browser_crypto::ecdsa::EcdsaKeyPair;
+use browser_crypto::hash::Hash;
+use browser_crypto::hmac::Hmac;
+use browser_crypto::signature::{AsyncSigner, AsyncVerifier, Signature};
+use signature::SignatureEncoding;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+/// Signs a token with any key following the conventions of the signature crate
+async fn sign_token<S: SignatureEncoding>(signer: &impl AsyncSigner<S>) -> Vec<u8> {
+    let signature = signer.sign_async(b"header.payload").await.unwrap();
+    signature.to_vec()
+}
+
+#[wasm_bindgen_test]
+async fn should_sign_and_verify_with_ecdsa() {
+    console_error_panic_hook::set_once();
+
+    let pair = EcdsaKeyPair::generate(NamedCurve::P256, Hash::Sha256)
+        .await
+        .unwrap();
+    let bytes = sign_token(&pair.private_key).await;
+    assert_eq!(bytes.len(), 64);
+
+    let signature = Signature::try_from(bytes.as_slice()).unwrap();
+    pair.public_key
+        .verify_async(b"header.payload", &signature)
+        .await
+        .unwrap();
+    assert!(pair
+        .public_key
+        .verify_async(b"header.other", &signature)
+        .await
+        .is_err());
+}
+
+#[wasm_bindgen_test]
+async fn should_sign_and_verify_with_hmac() {
+    console_error_panic_hook::set_once();
+
+    let key = Hmac::generate(Hash::Sha256).await.unwrap();
+    let signature: Signature = key.sign_async(b"header.payload").await.unwrap();
+    assert_eq!(signature.encoded_len(), 32);
+    assert_eq!(
+        signature.as_ref(),
+        key.sign(b"header.payload").await.unwrap()
+    );
+    key.verify_async(b"header.payload", &signature)
+        .await
+        .unwrap();
+
+    let altered = Signature::new(vec![0; 32]);
+    assert!(key.verify_async(b"header.payload", &altered).await.is_err());
+}
+
+#[wasm_bindgen_test]
+async fn should_reject_invalid_signatures_without_source() {
+    console_error_panic_hook::set_once();
+
+    let pair = EcdsaKeyPair::generate(NamedCurve::P256, Hash::Sha256)
+        .await
+        .unwrap();
+    let signature = Signature::new(vec![0; 64]);
+    let error = pair
+        .public_key
+        .verify_async(b"header.payload", &signature)
+        .await
+        .unwrap_err();
+    assert!(std::error::Error::source(&error).is_none());
+}
@@ -0,0 +1,110 @@
+//! Test suite for the chunked encryption.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::{Algorithm, DecryptionError, Nonce};
+use browser_crypto::stream::{StreamDecryptor, StreamEncryptor, StreamError, PREFIX_SIZE};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+async fn encrypt(cipher: &Aes256Gcm, chunks: &[&[u8]]) -> (Vec<u8>, Vec<Vec<u8>>) {
+    let mut encryptor = StreamEncryptor::new(cipher.clone()).unwrap();
+    let mut output = Vec::new();
+    let (last, chunks) = chunks.split_last().unwrap();
+    for chunk in chunks {
+        output.push(encryptor.encrypt_chunk(chunk).await.unwrap());
+    }
+    output.push(encryptor.finish(last).await.unwrap());
+    assert!(encryptor.is_finished());
+    (encryptor.prefix().to_vec(), output)
+}
+
+#[wasm_bindgen_test]
+async fn should_encrypt_and_decrypt_chunks() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let (prefix, chunks) = encrypt(&cipher, &[&[1; 1024], &[2; 1024], b"end"]).await;
+    assert_eq!(prefix.len(), PREFIX_SIZE);
+    assert_eq!(chunks[0].len(), 1024 + 16);
+    assert_eq!(chunks[2].len(), 3 + 16);
+
+    let mut decryptor = StreamDecryptor::new(cipher.clone(), &prefix).unwrap();
+    assert_eq!(
+        decryptor.decrypt_chunk(&chunks[0]).await.unwrap(),
+        [1; 1024]
+    );
+    assert_eq!(
+        decryptor.decrypt_chunk(&chunks[1]).await.unwrap(),
+        [2; 1024]
+    );
+    assert_eq!(decryptor.finish(&chunks[2]).await.unwrap(), b"end");
+    assert!(decryptor.is_finished());
+    let err = decryptor.finish(&chunks[2]).await.unwrap_err();
+    assert!(matches!(err, StreamError::Finished));
+
+    // the nonce of a chunk is the prefix, its index and the last chunk flag
+    let mut nonce = prefix.clone();
+    nonce.extend_from_slice(&[0, 0, 0, 2, 1]);
+    let nonce = Nonce::<Aes256Gcm>::from_slice(&nonce).unwrap();
+    assert_eq!(cipher.decrypt(&nonce, &chunks[2]).await.unwrap(), b"end");
+}
+
+#[wasm_bindgen_test]
+async fn should_encrypt_empty_stream() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let (prefix, chunks) = encrypt(&cipher, &[b""]).await;
+    assert_eq!(chunks[0].len(), 16);
+    let mut decryptor = StreamDecryptor::new(cipher, &prefix).unwrap();
+    assert!(decryptor.finish(&chunks[0]).await.unwrap().is_empty());
+}
+
+#[wasm_bindgen_test]
+async fn should_detect_truncation_and_reordering() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let (prefix, chunks) = encrypt(&cipher, &[b"first", b"second", b"last"]).await;
+
+    // truncated stream, the second chunk is not the last one
+    let mut decryptor = StreamDecryptor::new(cipher.clone(), &prefix).unwrap();
+    decryptor.decrypt_chunk(&chunks[0]).await.unwrap();
+    let err = decryptor.finish(&chunks[1]).await.unwrap_err();
+    assert!(matches!(
+        err,
+        StreamError::Decryption(DecryptionError::Operation)
+    ));
+
+    // reordered chunks
+    let mut decryptor = StreamDecryptor::new(cipher.clone(), &prefix).unwrap();
+    let err = decryptor.decrypt_chunk(&chunks[1]).await.unwrap_err();
+    assert!(matches!(
+        err,
+        StreamError::Decryption(DecryptionError::Operation)
+    ));
+
+    // the last chunk is not accepted as an intermediate one
+    let mut decryptor = StreamDecryptor::new(cipher.clone(), &prefix).unwrap();
+    decryptor.decrypt_chunk(&chunks[0]).await.unwrap();
+    decryptor.decrypt_chunk(&chunks[1]).await.unwrap();
+    assert!(decryptor.decrypt_chunk(&chunks[2]).await.is_err());
+
+    let err = StreamDecryptor::new(cipher, &prefix[1..]).unwrap_err();
+    assert!(matches!(err, StreamError::InvalidPrefix));
+}
+
+#[wasm_bindgen_test]
+async fn should_use_random_prefix() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let first = StreamEncryptor::new(cipher.clone()).unwrap();
+    let second = StreamEncryptor::new(cipher).unwrap();
+    assert_ne!(first.prefix(), second.prefix());
+}
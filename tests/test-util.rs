@@ -0,0 +1,56 @@
+//! Test suite for the helpers of the downstream tests.
+
+#![cfg(all(target_arch = "wasm32", feature = "test-util"))]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::{Aes128Gcm, Aes256Gcm};
+use browser_crypto::algorithm::{Algorithm, Key};
+use browser_crypto::input::SecretInput;
+use browser_crypto::test_util::{self, FixedNonce};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+fn should_build_deterministic_keys_and_nonces() {
+    console_error_panic_hook::set_once();
+
+    let key: Key<Aes128Gcm> = test_util::key(0xfe);
+    assert_eq!(key.to_hex(), "feff000102030405060708090a0b0c0d");
+    assert_eq!(
+        test_util::key::<Aes256Gcm>(1).expose_bytes(),
+        test_util::key::<Aes256Gcm>(1).expose_bytes()
+    );
+    assert_eq!(
+        test_util::nonce::<Aes256Gcm>(0).to_hex(),
+        "000102030405060708090a0b"
+    );
+}
+
+#[wasm_bindgen_test]
+async fn should_seal_with_a_fixed_nonce() {
+    console_error_panic_hook::set_once();
+
+    let cipher = test_util::aes_gcm::<256>(1).await;
+    let fixed = FixedNonce::new(cipher, test_util::nonce::<Aes256Gcm>(0));
+    let sealed = fixed.seal(b"hello").await.unwrap();
+    assert_eq!(sealed, fixed.seal(b"hello").await.unwrap());
+    assert_eq!(&sealed[..12], fixed.nonce().as_ref());
+    assert_eq!(&sealed[12..], fixed.encrypt(b"hello").await.unwrap());
+
+    let other = test_util::aes_gcm::<256>(1).await;
+    assert_eq!(other.open(&sealed).await.unwrap(), b"hello");
+    assert_eq!(fixed.open(&sealed).await.unwrap(), b"hello");
+}
+
+#[wasm_bindgen_test]
+async fn should_assert_round_trips() {
+    console_error_panic_hook::set_once();
+
+    let cipher = test_util::aes_gcm::<128>(7).await;
+    let sealed = browser_crypto::assert_round_trip!(cipher, b"hello world");
+    assert_eq!(sealed.len(), 12 + 11 + 16);
+    browser_crypto::assert_round_trip!(cipher, Vec::<u8>::new());
+    browser_crypto::assert_tampering_detected!(cipher, sealed);
+}
@@ -0,0 +1,88 @@
+//! Test suite for the tracing instrumentation.
+
+#![cfg(all(target_arch = "wasm32", feature = "tracing"))]
+
+extern crate wasm_bindgen_test;
+
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::{Algorithm, Nonce};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+/// Records the spans and the events as lines of fields
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<String>>>);
+
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        let _ = write!(self.0, " {}={:?}", field.name(), value);
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = Fields(format!("span {}", span.metadata().name()));
+        span.record(&mut fields);
+        let mut lines = self.0.lock().unwrap();
+        lines.push(fields.0);
+        Id::from_u64(lines.len() as u64)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(format!("event {}", event.metadata().level()));
+        event.record(&mut fields);
+        self.0.lock().unwrap().push(fields.0);
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[wasm_bindgen_test]
+async fn should_trace_operations() {
+    console_error_panic_hook::set_once();
+
+    let recorder = Recorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+
+    let cipher = Aes256Gcm::from_key(&[0x42; 32]).await.unwrap();
+    let nonce = Nonce::<Aes256Gcm>::generate().unwrap();
+    let encrypted = cipher.encrypt(&nonce, b"hello").await.unwrap();
+    cipher.decrypt(&nonce, &encrypted[1..]).await.unwrap_err();
+
+    let lines = recorder.0.lock().unwrap().clone();
+    assert_eq!(lines.len(), 6, "{lines:#?}");
+    assert_eq!(
+        lines[0],
+        "span webcrypto operation=\"importKey\" algorithm=\"AES-GCM\""
+    );
+    assert!(lines[1].starts_with("event DEBUG message=operation succeeded duration_ms="));
+    assert_eq!(
+        lines[2],
+        "span webcrypto operation=\"encrypt\" algorithm=\"AES-GCM\" size=5"
+    );
+    assert_eq!(
+        lines[4],
+        "span webcrypto operation=\"decrypt\" algorithm=\"AES-GCM\" size=20"
+    );
+    assert!(lines[5].starts_with("event WARN message=operation failed duration_ms="));
+    assert!(lines[5].ends_with("error.code=\"decryption.operation\""));
+}
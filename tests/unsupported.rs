@@ -0,0 +1,103 @@
+//! Test suite for the targets without the Web Crypto API.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::future::Future;
+use std::task::{Context, Poll, Waker};
+
+use browser_crypto::aes_kw::Aes256Kw;
+use browser_crypto::curve::NamedCurve;
+use browser_crypto::ecdsa::EcdsaKeyPair;
+use browser_crypto::ed25519::Ed25519PublicKey;
+use browser_crypto::hash::Hash;
+use browser_crypto::hkdf::Hkdf;
+use browser_crypto::hmac::Hmac;
+use browser_crypto::jwk::{Jwk, OctJwk};
+use browser_crypto::key::{GenerateKeyError, ImportKeyError};
+use browser_crypto::pbkdf2::Pbkdf2;
+use browser_crypto::rsa_oaep::RsaOaepPublicKey;
+use browser_crypto::x25519::X25519KeyPair;
+use browser_crypto::Error;
+
+/// Runs a future to completion, the unsupported operations failing before
+/// being pending
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+fn is_unsupported_import<T>(result: Result<T, ImportKeyError>) -> bool {
+    matches!(result, Err(ImportKeyError::Generic(Error::Unsupported)))
+}
+
+fn is_unsupported_generate<T>(result: Result<T, GenerateKeyError>) -> bool {
+    matches!(result, Err(GenerateKeyError::Generic(Error::Unsupported)))
+}
+
+#[test]
+fn should_fail_importing_keys() {
+    assert!(is_unsupported_import(block_on(Aes256Kw::from_key(
+        &[0; 32]
+    ))));
+    assert!(is_unsupported_import(block_on(Hmac::from_key(
+        &[0; 32],
+        Hash::Sha256
+    ))));
+    assert!(is_unsupported_import(block_on(Ed25519PublicKey::from_raw(
+        &[0; 32]
+    ))));
+    assert!(is_unsupported_import(block_on(
+        RsaOaepPublicKey::from_spki(&[0; 32], Hash::Sha256)
+    )));
+    assert!(is_unsupported_import(block_on(Hkdf::from_key(
+        &[0; 32],
+        Hash::Sha256,
+        b"salt",
+        b"info"
+    ))));
+    assert!(is_unsupported_import(block_on(Pbkdf2::from_password(
+        b"password",
+        Hash::Sha256,
+        b"salt",
+        Pbkdf2::DEFAULT_ITERATIONS
+    ))));
+    let jwk = Jwk::from(OctJwk::new("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA"));
+    assert!(is_unsupported_import(block_on(Aes256Kw::from_jwk(&jwk))));
+}
+
+#[test]
+fn should_fail_generating_keys() {
+    assert!(is_unsupported_generate(block_on(Hmac::generate(
+        Hash::Sha256
+    ))));
+    assert!(is_unsupported_generate(block_on(EcdsaKeyPair::generate(
+        NamedCurve::P256,
+        Hash::Sha256
+    ))));
+    assert!(is_unsupported_generate(block_on(X25519KeyPair::generate())));
+}
+
+#[cfg(not(feature = "native"))]
+#[test]
+fn should_fail_software_backed_operations() {
+    use browser_crypto::aes_gcm::Aes256Gcm;
+    use browser_crypto::algorithm::NonceError;
+    use browser_crypto::ErrorCode;
+
+    assert!(is_unsupported_import(block_on(Aes256Gcm::from_key(
+        &[0; 32]
+    ))));
+    assert!(is_unsupported_generate(block_on(Aes256Gcm::generate(true))));
+    let err = block_on(Hash::Sha256.digest(b"abc")).unwrap_err();
+    assert_eq!(err.code(), "generic.unsupported");
+    assert!(matches!(
+        browser_crypto::random::random_bytes(16),
+        Err(NonceError::Generic(Error::Unsupported))
+    ));
+    assert!(browser_crypto::random::uuid().is_err());
+}
@@ -7,6 +7,7 @@ extern crate wasm_bindgen_test;
 use std::assert_eq;
 
 use browser_crypto::algorithm::Algorithm;
+use browser_crypto::key::KeyWrapper;
 use wasm_bindgen_test::*;
 
 wasm_bindgen_test_configure!(run_in_dedicated_worker);
@@ -29,6 +30,340 @@ async fn should_encrypt_and_decrypt() {
     assert_eq!(clear_msg, decrypted.as_slice());
 }
 
+#[wasm_bindgen_test]
+async fn should_seal_and_open() {
+    console_error_panic_hook::set_once();
+
+    let crypto = browser_crypto::aes256gcm::Aes256Gcm::from_key(&DEFAULT_KEY)
+        .await
+        .unwrap();
+    let sealed = crypto.seal(b"Hello World!").await.unwrap();
+    assert_eq!(sealed.len(), 12 + 12 + 16);
+    assert_ne!(crypto.seal(b"Hello World!").await.unwrap(), sealed);
+
+    // the nonce is prepended to the encrypted data
+    let nonce = browser_crypto::algorithm::Nonce::from_slice(&sealed[..12]).unwrap();
+    let decrypted = crypto.decrypt(&nonce, &sealed[12..]).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+    assert_eq!(crypto.open(&sealed).await.unwrap(), b"Hello World!");
+
+    let err = crypto.open(&sealed[..11]).await.unwrap_err();
+    assert!(matches!(
+        err,
+        browser_crypto::algorithm::DecryptionError::Truncated
+    ));
+    let err = crypto.open(&sealed[..12]).await.unwrap_err();
+    assert!(matches!(
+        err,
+        browser_crypto::algorithm::DecryptionError::Operation
+    ));
+}
+
+#[wasm_bindgen_test]
+async fn should_encrypt_and_decrypt_into_buffer() {
+    console_error_panic_hook::set_once();
+
+    let crypto = browser_crypto::aes256gcm::Aes256Gcm::from_key(&DEFAULT_KEY)
+        .await
+        .unwrap();
+    let nonce = browser_crypto::aes256gcm::Aes256Gcm::generate_nonce().unwrap();
+    let expected = crypto.encrypt(&nonce, b"Hello World!").await.unwrap();
+
+    let mut buffer = b"header".to_vec();
+    crypto
+        .encrypt_into(&nonce, b"Hello World!", &mut buffer)
+        .await
+        .unwrap();
+    assert_eq!(&buffer[..6], b"header");
+    assert_eq!(buffer[6..], expected);
+
+    let mut output = Vec::with_capacity(64);
+    crypto
+        .decrypt_into(&nonce, &buffer[6..], &mut output)
+        .await
+        .unwrap();
+    assert_eq!(output, b"Hello World!");
+
+    // the buffer is left unchanged on error
+    let err = crypto
+        .decrypt_into(&nonce, &buffer[..10], &mut output)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        browser_crypto::algorithm::DecryptionError::Operation
+    ));
+    assert_eq!(output, b"Hello World!");
+}
+
+#[wasm_bindgen_test]
+async fn should_decrypt_in_place() {
+    console_error_panic_hook::set_once();
+
+    let crypto = browser_crypto::aes256gcm::Aes256Gcm::from_key(&DEFAULT_KEY)
+        .await
+        .unwrap();
+    let nonce = browser_crypto::aes256gcm::Aes256Gcm::generate_nonce().unwrap();
+    let mut buffer = crypto.encrypt(&nonce, b"Hello World!").await.unwrap();
+    let capacity = buffer.capacity();
+
+    crypto.decrypt_in_place(&nonce, &mut buffer).await.unwrap();
+    assert_eq!(buffer, b"Hello World!");
+    assert_eq!(buffer.capacity(), capacity);
+
+    // the buffer is left unchanged on error
+    let err = crypto
+        .decrypt_in_place(&nonce, &mut buffer)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        browser_crypto::algorithm::DecryptionError::Operation
+    ));
+    assert_eq!(buffer, b"Hello World!");
+}
+
+#[wasm_bindgen_test]
+async fn should_encrypt_large_payload() {
+    console_error_panic_hook::set_once();
+
+    let crypto = browser_crypto::aes256gcm::Aes256Gcm::from_key(&DEFAULT_KEY)
+        .await
+        .unwrap();
+    let payload: Vec<u8> = (0..4 * 1024 * 1024).map(|idx| (idx % 251) as u8).collect();
+    let sealed = crypto.seal(&payload).await.unwrap();
+    assert_eq!(sealed.len(), 12 + payload.len() + 16);
+    // the payload is left untouched
+    assert!(payload
+        .iter()
+        .enumerate()
+        .all(|(idx, byte)| *byte == (idx % 251) as u8));
+    assert_eq!(crypto.open(&sealed).await.unwrap(), payload);
+}
+
+#[wasm_bindgen_test]
+async fn should_encrypt_and_decrypt_js_arrays() {
+    console_error_panic_hook::set_once();
+
+    let crypto = browser_crypto::aes256gcm::Aes256Gcm::from_key(&DEFAULT_KEY)
+        .await
+        .unwrap();
+    let nonce = browser_crypto::aes256gcm::Aes256Gcm::generate_nonce().unwrap();
+    let encrypted = crypto.encrypt_js(&nonce, b"Hello World!").await.unwrap();
+    assert_eq!(
+        encrypted.to_vec(),
+        crypto.encrypt(&nonce, b"Hello World!").await.unwrap()
+    );
+
+    let decrypted = crypto
+        .decrypt_js(&nonce, &encrypted.to_vec())
+        .await
+        .unwrap();
+    assert_eq!(decrypted.to_vec(), b"Hello World!");
+
+    let err = crypto
+        .decrypt_js(&nonce, &encrypted.to_vec()[1..])
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        browser_crypto::algorithm::DecryptionError::Operation
+    ));
+}
+
+#[wasm_bindgen_test]
+async fn should_seal_and_open_batch() {
+    console_error_panic_hook::set_once();
+
+    let crypto = browser_crypto::aes256gcm::Aes256Gcm::from_key(&DEFAULT_KEY)
+        .await
+        .unwrap();
+    let payloads: Vec<Vec<u8>> = (0..50).map(|idx| vec![idx as u8; idx]).collect();
+    let payloads: Vec<&[u8]> = payloads.iter().map(Vec::as_slice).collect();
+    let sealed = crypto.seal_batch(&payloads).await.unwrap();
+    assert_eq!(sealed.len(), payloads.len());
+    for (sealed, payload) in sealed.iter().zip(payloads.iter()) {
+        assert_eq!(crypto.open(sealed).await.unwrap(), *payload);
+    }
+
+    let sealed: Vec<&[u8]> = sealed.iter().map(Vec::as_slice).collect();
+    assert_eq!(crypto.open_batch(&sealed).await.unwrap(), payloads);
+    assert!(crypto.seal_batch(&[]).await.unwrap().is_empty());
+
+    // a single invalid payload fails the batch
+    let mut invalid = sealed.clone();
+    invalid[10] = &sealed[10][..12];
+    let err = crypto.open_batch(&invalid).await.unwrap_err();
+    assert!(matches!(
+        err,
+        browser_crypto::algorithm::DecryptionError::Operation
+    ));
+}
+
+/// Cipher recording the number of concurrent encryptions
+struct Counting {
+    inner: browser_crypto::aes256gcm::Aes256Gcm,
+    current: std::cell::Cell<usize>,
+    max: std::cell::Cell<usize>,
+}
+
+impl Algorithm for Counting {
+    const NAME: &'static str = "Counting";
+    const NONCE_SIZE: u32 = 12;
+    const KEY_SIZE: u32 = 32;
+    type EncryptError = browser_crypto::algorithm::EncryptionError;
+    type DecryptError = browser_crypto::algorithm::DecryptionError;
+
+    async fn encrypt(
+        &self,
+        nonce: &browser_crypto::algorithm::Nonce<Self>,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, browser_crypto::algorithm::EncryptionError> {
+        self.current.set(self.current.get() + 1);
+        self.max.set(self.max.get().max(self.current.get()));
+        let nonce = browser_crypto::algorithm::Nonce::from_slice(&nonce.to_vec()).unwrap();
+        let result = self.inner.encrypt(&nonce, payload).await;
+        self.current.set(self.current.get() - 1);
+        result
+    }
+
+    async fn decrypt(
+        &self,
+        nonce: &browser_crypto::algorithm::Nonce<Self>,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, browser_crypto::algorithm::DecryptionError> {
+        let nonce = browser_crypto::algorithm::Nonce::from_slice(&nonce.to_vec()).unwrap();
+        self.inner.decrypt(&nonce, payload).await
+    }
+}
+
+#[wasm_bindgen_test]
+async fn should_limit_batch_concurrency() {
+    console_error_panic_hook::set_once();
+
+    let crypto = Counting {
+        inner: browser_crypto::aes256gcm::Aes256Gcm::from_key(&DEFAULT_KEY)
+            .await
+            .unwrap(),
+        current: Default::default(),
+        max: Default::default(),
+    };
+    let payloads: Vec<Vec<u8>> = (0..200).map(|idx| vec![idx as u8; idx]).collect();
+    let payloads: Vec<&[u8]> = payloads.iter().map(Vec::as_slice).collect();
+
+    let sealed = crypto.seal_batch_with_limit(&payloads, 8).await.unwrap();
+    assert_eq!(crypto.max.get(), 8);
+    let sealed: Vec<&[u8]> = sealed.iter().map(Vec::as_slice).collect();
+    assert_eq!(
+        crypto.open_batch_with_limit(&sealed, 3).await.unwrap(),
+        payloads
+    );
+
+    crypto.max.set(0);
+    crypto.seal_batch(&payloads).await.unwrap();
+    assert_eq!(
+        crypto.max.get(),
+        browser_crypto::algorithm::DEFAULT_MAX_IN_FLIGHT
+    );
+
+    crypto.max.set(0);
+    crypto.seal_batch_with_limit(&payloads, 0).await.unwrap();
+    assert_eq!(crypto.max.get(), 1);
+}
+
+/// Error of a cipher refusing empty payloads, kept apart from the errors of
+/// the browser
+#[derive(Debug)]
+enum NonEmptyError {
+    EmptyPayload,
+    Encryption(browser_crypto::algorithm::EncryptionError),
+    Decryption(browser_crypto::algorithm::DecryptionError),
+}
+
+impl std::fmt::Display for NonEmptyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyPayload => f.write_str("empty payload"),
+            Self::Encryption(inner) => inner.fmt(f),
+            Self::Decryption(inner) => inner.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for NonEmptyError {}
+
+impl From<browser_crypto::algorithm::EncryptionError> for NonEmptyError {
+    fn from(value: browser_crypto::algorithm::EncryptionError) -> Self {
+        Self::Encryption(value)
+    }
+}
+
+impl From<browser_crypto::algorithm::DecryptionError> for NonEmptyError {
+    fn from(value: browser_crypto::algorithm::DecryptionError) -> Self {
+        Self::Decryption(value)
+    }
+}
+
+/// Cipher with its own error type
+struct NonEmpty(browser_crypto::aes256gcm::Aes256Gcm);
+
+impl Algorithm for NonEmpty {
+    const NAME: &'static str = "NonEmpty";
+    const NONCE_SIZE: u32 = 12;
+    const KEY_SIZE: u32 = 32;
+    type EncryptError = NonEmptyError;
+    type DecryptError = NonEmptyError;
+
+    async fn encrypt(
+        &self,
+        nonce: &browser_crypto::algorithm::Nonce<Self>,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, NonEmptyError> {
+        if payload.is_empty() {
+            return Err(NonEmptyError::EmptyPayload);
+        }
+        let nonce = browser_crypto::algorithm::Nonce::from_slice(&nonce.to_vec()).unwrap();
+        Ok(self.0.encrypt(&nonce, payload).await?)
+    }
+
+    async fn decrypt(
+        &self,
+        nonce: &browser_crypto::algorithm::Nonce<Self>,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, NonEmptyError> {
+        let nonce = browser_crypto::algorithm::Nonce::from_slice(&nonce.to_vec()).unwrap();
+        Ok(self.0.decrypt(&nonce, payload).await?)
+    }
+}
+
+#[wasm_bindgen_test]
+async fn should_surface_custom_errors() {
+    console_error_panic_hook::set_once();
+
+    let crypto = NonEmpty(
+        browser_crypto::aes256gcm::Aes256Gcm::from_key(&DEFAULT_KEY)
+            .await
+            .unwrap(),
+    );
+    let sealed = crypto.seal(b"hello").await.unwrap();
+    assert_eq!(crypto.open(&sealed).await.unwrap(), b"hello");
+
+    assert!(matches!(
+        crypto.seal(b"").await,
+        Err(NonEmptyError::EmptyPayload)
+    ));
+    assert!(matches!(
+        crypto.seal_batch(&[b"hello", b""]).await,
+        Err(NonEmptyError::EmptyPayload)
+    ));
+    assert!(matches!(
+        crypto.open(&sealed[..4]).await,
+        Err(NonEmptyError::Decryption(
+            browser_crypto::algorithm::DecryptionError::Truncated
+        ))
+    ));
+}
+
 #[wasm_bindgen_test]
 async fn should_handle_invalid_keys() {
     console_error_panic_hook::set_once();
@@ -42,6 +377,12 @@ async fn should_handle_invalid_keys() {
         .await
         .unwrap_err();
     assert_eq!(err.to_string(), "invalid key format provided");
+
+    // valid AES key, but not for the requested size
+    let err = browser_crypto::aes256gcm::Aes256Gcm::from_key(&[0; 16])
+        .await
+        .unwrap_err();
+    assert_eq!(err.to_string(), "invalid key format provided");
 }
 
 #[wasm_bindgen_test]
@@ -60,3 +401,60 @@ async fn should_handle_invalid_nonce() {
         "invalid nonce size provided, expected 12, received 10"
     );
 }
+
+fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+#[wasm_bindgen_test]
+async fn should_hold_nonce_across_threads() {
+    console_error_panic_hook::set_once();
+
+    let crypto = browser_crypto::aes256gcm::Aes256Gcm::from_key(&DEFAULT_KEY)
+        .await
+        .unwrap();
+    let nonce = browser_crypto::aes256gcm::Aes256Gcm::generate_nonce().unwrap();
+    assert_send_sync(&nonce);
+    assert_eq!(nonce.as_ref().len(), 12);
+
+    let encrypted = crypto.encrypt(&nonce, b"Hello World!").await.unwrap();
+    let nonce = browser_crypto::algorithm::Nonce::from_slice(nonce.as_ref()).unwrap();
+    assert_eq!(
+        crypto.decrypt(&nonce, &encrypted).await.unwrap(),
+        b"Hello World!"
+    );
+}
+
+#[wasm_bindgen_test]
+async fn should_encrypt_and_decrypt_with_generated_key() {
+    console_error_panic_hook::set_once();
+
+    let clear_msg = b"Hello World!";
+    let crypto = browser_crypto::aes256gcm::Aes256Gcm::generate(false)
+        .await
+        .unwrap();
+    let nonce = browser_crypto::aes256gcm::Aes256Gcm::generate_nonce().unwrap();
+    let encrypted = crypto.encrypt(&nonce, clear_msg).await.unwrap();
+
+    let decrypted = crypto.decrypt(&nonce, &encrypted).await.unwrap();
+
+    assert_eq!(clear_msg, decrypted.as_slice());
+}
+
+#[wasm_bindgen_test]
+async fn should_only_wrap_extractable_generated_key() {
+    console_error_panic_hook::set_once();
+
+    let kek = browser_crypto::aes_kw::Aes256Kw::from_key(&DEFAULT_KEY)
+        .await
+        .unwrap();
+
+    let crypto = browser_crypto::aes256gcm::Aes256Gcm::generate(true)
+        .await
+        .unwrap();
+    let wrapped = kek.wrap_key(&crypto).await.unwrap();
+    assert_eq!(wrapped.len(), 40);
+
+    let crypto = browser_crypto::aes256gcm::Aes256Gcm::generate(false)
+        .await
+        .unwrap();
+    assert!(kek.wrap_key(&crypto).await.is_err());
+}
@@ -0,0 +1,23 @@
+//! Test suite for the helpers handling secrets.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::util::ct_eq;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+#[wasm_bindgen_test]
+fn should_compare_in_constant_time() {
+    console_error_panic_hook::set_once();
+
+    assert!(ct_eq(b"", b""));
+    assert!(ct_eq(b"token", b"token"));
+    assert!(!ct_eq(b"token", b"tokem"));
+    assert!(!ct_eq(b"token", b"Token"));
+    assert!(!ct_eq(b"token", b"token!"));
+    assert!(!ct_eq(b"", b"t"));
+    assert!(!ct_eq(&[0; 32], &[1; 32]));
+}
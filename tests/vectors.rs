@@ -0,0 +1,172 @@
+//! Test suite for the test vectors.
+
+#![cfg(all(target_arch = "wasm32", feature = "test-vectors"))]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::vectors::{self, Expected, VectorError};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+const CAVP: &str = "
+# CAVS 14.0
+# GCM Decrypt with keysize 128 test information
+
+[Keylen = 128]
+[IVlen = 96]
+[PTlen = 0]
+[AADlen = 0]
+[Taglen = 128]
+
+Count = 0
+Key = 00000000000000000000000000000000
+IV = 000000000000000000000000
+CT =
+AAD =
+Tag = 58e2fccefa7e3061367f1d57a4e7455a
+PT =
+
+Count = 1
+Key = 00000000000000000000000000000000
+IV = 000000000000000000000000
+CT =
+AAD =
+Tag = 58e2fccefa7e3061367f1d57a4e7455b
+FAIL
+
+[IVlen = 8]
+
+Count = 2
+Key = 00000000000000000000000000000000
+IV = 00
+CT =
+AAD =
+Tag = 00000000000000000000000000000000
+FAIL
+";
+
+const WYCHEPROOF: &str = r#"{
+  "algorithm": "AES-GCM",
+  "numberOfTests": 3,
+  "testGroups": [
+    {
+      "ivSize": 96,
+      "keySize": 256,
+      "tagSize": 128,
+      "type": "AeadTest",
+      "tests": [
+        {
+          "tcId": 1,
+          "comment": "",
+          "key": "0000000000000000000000000000000000000000000000000000000000000000",
+          "iv": "000000000000000000000000",
+          "aad": "",
+          "msg": "00000000000000000000000000000000",
+          "ct": "cea7403d4d606b6e074ec5d3baf39d18",
+          "tag": "d0d1c8a799996bf0265b98b5d48ab919",
+          "result": "valid",
+          "flags": []
+        },
+        {
+          "tcId": 2,
+          "comment": "Flipped bit 0 in tag",
+          "key": "0000000000000000000000000000000000000000000000000000000000000000",
+          "iv": "000000000000000000000000",
+          "aad": "",
+          "msg": "00000000000000000000000000000000",
+          "ct": "cea7403d4d606b6e074ec5d3baf39d18",
+          "tag": "d1d1c8a799996bf0265b98b5d48ab919",
+          "result": "invalid",
+          "flags": []
+        },
+        {
+          "tcId": 3,
+          "comment": "Wrong ciphertext",
+          "key": "0000000000000000000000000000000000000000000000000000000000000000",
+          "iv": "000000000000000000000000",
+          "aad": "",
+          "msg": "00000000000000000000000000000001",
+          "ct": "cea7403d4d606b6e074ec5d3baf39d18",
+          "tag": "d0d1c8a799996bf0265b98b5d48ab919",
+          "result": "valid",
+          "flags": []
+        }
+      ]
+    }
+  ]
+}"#;
+
+#[wasm_bindgen_test]
+async fn should_pass_the_embedded_vectors() {
+    console_error_panic_hook::set_once();
+
+    let vectors = vectors::embedded_vectors();
+    assert_eq!(vectors.len(), 15);
+    let report = vectors::run_aes_gcm(&vectors).await;
+    report.assert_success();
+    assert_eq!(report.passed, 15);
+    assert_eq!(report.skipped, 0);
+}
+
+#[wasm_bindgen_test]
+async fn should_run_cavp_files() {
+    console_error_panic_hook::set_once();
+
+    let vectors = vectors::parse_cavp(CAVP).unwrap();
+    let expected = vectors.iter().map(|v| v.expected).collect::<Vec<_>>();
+    assert_eq!(
+        expected,
+        [Expected::Valid, Expected::Invalid, Expected::Invalid]
+    );
+    assert_eq!(vectors[0].id, "Count = 0 (line 11)");
+    assert_eq!(vectors[2].nonce, [0]);
+
+    let report = vectors::run_aes_gcm(&vectors).await;
+    report.assert_success();
+    assert_eq!((report.passed, report.skipped), (2, 1));
+}
+
+#[wasm_bindgen_test]
+async fn should_report_failing_vectors() {
+    console_error_panic_hook::set_once();
+
+    let vectors = vectors::parse_wycheproof(WYCHEPROOF).unwrap();
+    assert_eq!(vectors.len(), 3);
+    assert_eq!(vectors[1].id, "tcId 2 (Flipped bit 0 in tag)");
+
+    let report = vectors::run_aes_gcm(&vectors).await;
+    assert!(!report.is_success());
+    assert_eq!(report.passed, 2);
+    assert_eq!(report.failures.len(), 1);
+    assert_eq!(
+        report.failures[0].to_string(),
+        "tcId 3 (Wrong ciphertext): decrypted to another plaintext"
+    );
+}
+
+#[wasm_bindgen_test]
+fn should_reject_malformed_files() {
+    console_error_panic_hook::set_once();
+
+    assert!(matches!(
+        vectors::parse_cavp("Key = 00"),
+        Err(VectorError::Malformed { line: 1, .. })
+    ));
+    assert!(matches!(
+        vectors::parse_cavp("Count = 0\nKey = 00\nIV = 00\nCT = \nTag = 00\n"),
+        Err(VectorError::Malformed { line: 1, .. })
+    ));
+    assert!(matches!(
+        vectors::parse_cavp("Count = 0\nKey = zz\nIV = 00\nCT = \nTag = 00\nPT = \n"),
+        Err(VectorError::InvalidEncoding { field: "Key", .. })
+    ));
+    assert!(matches!(
+        vectors::parse_wycheproof(r#"{"algorithm":"AES-CCM","testGroups":[]}"#),
+        Err(VectorError::UnsupportedAlgorithm(_))
+    ));
+    assert!(matches!(
+        vectors::parse_wycheproof("[]"),
+        Err(VectorError::InvalidJson(_))
+    ));
+}
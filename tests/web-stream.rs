@@ -0,0 +1,287 @@
+//! Test suite for the Web Streams integration of the chunked encryption.
+
+#![cfg(all(target_arch = "wasm32", feature = "streams"))]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::stream::{StreamDecryptor, StreamError, CHUNK_SIZE, PREFIX_SIZE};
+use browser_crypto::web_stream::{
+    decrypt_readable, decrypt_readable_abortable, decrypt_transform,
+    decrypt_transform_with_progress, encrypt_transform, encrypt_transform_with_progress,
+    DecryptStream,
+};
+use futures_core::Stream;
+use wasm_bindgen::prelude::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::*;
+use web_sys::{
+    AbortController, Blob, ReadableStream, ReadableStreamDefaultReader, TransformStream,
+};
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+/// Writes the chunks to the stream and reads everything it outputs
+async fn pipe(stream: &TransformStream, chunks: &[JsValue]) -> Result<Vec<Vec<u8>>, JsValue> {
+    let writer = stream.writable().get_writer()?;
+    // the writes are only resolved once the output is read, and rejected
+    // along with the reads
+    let ignore = Closure::<dyn FnMut(JsValue)>::new(|_| {});
+    for chunk in chunks {
+        let _ = writer.write_with_chunk(chunk).catch(&ignore);
+    }
+    let _ = writer.close().catch(&ignore);
+    ignore.forget();
+
+    read_all(&stream.readable()).await
+}
+
+/// Reads everything from a stream
+async fn read_all(stream: &ReadableStream) -> Result<Vec<Vec<u8>>, JsValue> {
+    let reader = ReadableStreamDefaultReader::new(stream)?;
+    let mut output = Vec::new();
+    loop {
+        let result = JsFuture::from(reader.read()).await?;
+        if js_sys::Reflect::get(&result, &"done".into())?.is_truthy() {
+            return Ok(output);
+        }
+        let value = js_sys::Reflect::get(&result, &"value".into())?;
+        output.push(js_sys::Uint8Array::new(&value).to_vec());
+    }
+}
+
+/// Next item of a Rust stream
+async fn next<S: Stream + Unpin>(stream: &mut S) -> Option<S::Item> {
+    std::future::poll_fn(|cx| std::pin::Pin::new(&mut *stream).poll_next(cx)).await
+}
+
+/// Collects the items of a Rust stream
+async fn collect<S: Stream + Unpin>(mut stream: S) -> Vec<S::Item> {
+    let mut output = Vec::new();
+    while let Some(item) = next(&mut stream).await {
+        output.push(item);
+    }
+    output
+}
+
+/// Readable stream of some bytes
+fn readable(data: &[u8]) -> ReadableStream {
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(data));
+    Blob::new_with_u8_array_sequence(&parts).unwrap().stream()
+}
+
+fn bytes(data: &[u8]) -> JsValue {
+    js_sys::Uint8Array::from(data).into()
+}
+
+#[wasm_bindgen_test]
+async fn should_encrypt_and_decrypt_stream() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let payload: Vec<u8> = (0..CHUNK_SIZE * 2 + 100).map(|idx| idx as u8).collect();
+    let shared = js_sys::SharedArrayBuffer::new(10);
+    js_sys::Uint8Array::new(&shared).copy_from(&payload[..10]);
+    let input = [
+        shared.into(),
+        bytes(&payload[10..CHUNK_SIZE + 5]),
+        js_sys::Uint8Array::from(&payload[CHUNK_SIZE + 5..])
+            .buffer()
+            .into(),
+    ];
+    let encrypted = pipe(&encrypt_transform(cipher.clone()).unwrap(), &input)
+        .await
+        .unwrap();
+    // the prefix, then the chunks
+    assert_eq!(encrypted.len(), 4);
+    assert_eq!(encrypted[0].len(), PREFIX_SIZE);
+    assert_eq!(encrypted[1].len(), CHUNK_SIZE + 16);
+    assert_eq!(encrypted[3].len(), 100 + 16);
+
+    // compatible with the chunked decryption
+    let mut decryptor = StreamDecryptor::new(cipher.clone(), &encrypted[0]).unwrap();
+    let first = decryptor.decrypt_chunk(&encrypted[1]).await.unwrap();
+    assert_eq!(first, payload[..CHUNK_SIZE]);
+
+    // written in small chunks, not aligned with the encrypted ones
+    let input: Vec<JsValue> = encrypted.concat().chunks(1000).map(bytes).collect();
+    let decrypted = pipe(&decrypt_transform(cipher).unwrap(), &input)
+        .await
+        .unwrap();
+    assert_eq!(decrypted.concat(), payload);
+}
+
+#[wasm_bindgen_test]
+async fn should_encrypt_empty_stream() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let encrypted = pipe(&encrypt_transform(cipher.clone()).unwrap(), &[])
+        .await
+        .unwrap();
+    assert_eq!(encrypted.concat().len(), PREFIX_SIZE + 16);
+
+    let input = [bytes(&encrypted.concat())];
+    let decrypted = pipe(&decrypt_transform(cipher).unwrap(), &input)
+        .await
+        .unwrap();
+    assert!(decrypted.concat().is_empty());
+}
+
+#[wasm_bindgen_test]
+async fn should_error_on_truncated_stream() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let payload = vec![42u8; CHUNK_SIZE + 10];
+    let encrypted = pipe(
+        &encrypt_transform(cipher.clone()).unwrap(),
+        &[bytes(&payload)],
+    )
+    .await
+    .unwrap()
+    .concat();
+
+    let input = [bytes(&encrypted[..PREFIX_SIZE + CHUNK_SIZE + 16])];
+    let err = pipe(&decrypt_transform(cipher.clone()).unwrap(), &input)
+        .await
+        .unwrap_err();
+    assert!(err.is_instance_of::<js_sys::Error>());
+
+    let err = pipe(
+        &decrypt_transform(cipher).unwrap(),
+        &[JsValue::from("text")],
+    )
+    .await
+    .unwrap_err();
+    assert!(err.is_instance_of::<js_sys::TypeError>());
+}
+
+#[wasm_bindgen_test]
+async fn should_decrypt_readable_stream() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let payload: Vec<u8> = (0..CHUNK_SIZE * 2 + 100).map(|idx| idx as u8).collect();
+    let encrypted = pipe(
+        &encrypt_transform(cipher.clone()).unwrap(),
+        &[bytes(&payload)],
+    )
+    .await
+    .unwrap()
+    .concat();
+
+    let stream = decrypt_readable(cipher.clone(), &readable(&encrypted)).unwrap();
+    assert_eq!(read_all(&stream).await.unwrap().concat(), payload);
+
+    let chunks = collect(DecryptStream::new(cipher, &readable(&encrypted)).unwrap()).await;
+    assert_eq!(chunks.len(), 3);
+    let chunks: Vec<Vec<u8>> = chunks.into_iter().map(Result::unwrap).collect();
+    assert_eq!(chunks[0].len(), CHUNK_SIZE);
+    assert_eq!(chunks.concat(), payload);
+}
+
+#[wasm_bindgen_test]
+async fn should_end_decrypt_stream_with_error() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let payload = vec![42u8; CHUNK_SIZE + 10];
+    let encrypted = pipe(
+        &encrypt_transform(cipher.clone()).unwrap(),
+        &[bytes(&payload)],
+    )
+    .await
+    .unwrap()
+    .concat();
+
+    // the first chunk is decrypted as the last one
+    let truncated = readable(&encrypted[..PREFIX_SIZE + CHUNK_SIZE + 16]);
+    let chunks = collect(DecryptStream::new(cipher.clone(), &truncated).unwrap()).await;
+    assert_eq!(chunks.len(), 1);
+    assert!(matches!(chunks[0], Err(StreamError::Decryption(_))));
+
+    let truncated = readable(&encrypted[..PREFIX_SIZE + CHUNK_SIZE + 16]);
+    let stream = decrypt_readable(cipher.clone(), &truncated).unwrap();
+    assert!(read_all(&stream).await.is_err());
+
+    // a stream can only be read once
+    let stream = readable(&encrypted);
+    let _decrypted = DecryptStream::new(cipher.clone(), &stream).unwrap();
+    assert!(matches!(
+        DecryptStream::new(cipher.clone(), &stream),
+        Err(StreamError::Locked)
+    ));
+    assert!(matches!(
+        decrypt_readable(cipher, &stream),
+        Err(StreamError::Locked)
+    ));
+}
+
+#[wasm_bindgen_test]
+async fn should_report_stream_progress() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let payload = vec![42u8; 3000];
+    let input: Vec<JsValue> = payload.chunks(1000).map(bytes).collect();
+
+    let progress = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+    let recorder = progress.clone();
+    let stream = encrypt_transform_with_progress(cipher.clone(), Some(3000), move |done, total| {
+        recorder.borrow_mut().push((done, total))
+    })
+    .unwrap();
+    let encrypted = pipe(&stream, &input).await.unwrap().concat();
+    assert_eq!(
+        *progress.borrow(),
+        vec![(1000, Some(3000)), (2000, Some(3000)), (3000, Some(3000))]
+    );
+
+    let progress = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+    let recorder = progress.clone();
+    let stream = decrypt_transform_with_progress(cipher, None, move |done, total| {
+        recorder.borrow_mut().push((done, total))
+    })
+    .unwrap();
+    let decrypted = pipe(&stream, &[bytes(&encrypted)]).await.unwrap();
+    assert_eq!(decrypted.concat(), payload);
+    assert_eq!(*progress.borrow(), vec![(encrypted.len() as u64, None)]);
+}
+
+#[wasm_bindgen_test]
+async fn should_abort_stream_decryption() {
+    console_error_panic_hook::set_once();
+
+    let cipher = Aes256Gcm::from_key(&[1; 32]).await.unwrap();
+    let payload = vec![42u8; CHUNK_SIZE * 3];
+    let encrypted = pipe(
+        &encrypt_transform(cipher.clone()).unwrap(),
+        &[bytes(&payload)],
+    )
+    .await
+    .unwrap()
+    .concat();
+
+    // a blob stream is read in chunks smaller than the whole payload
+    let controller = AbortController::new().unwrap();
+    let mut stream = DecryptStream::new(cipher.clone(), &readable(&encrypted))
+        .unwrap()
+        .with_signal(controller.signal());
+    let first = next(&mut stream).await.unwrap().unwrap();
+    assert_eq!(first.len(), CHUNK_SIZE);
+    controller.abort();
+    let mut rest = Vec::new();
+    while let Some(item) = next(&mut stream).await {
+        rest.push(item);
+    }
+    assert!(matches!(rest.last(), Some(Err(StreamError::Aborted))));
+    assert!(rest.len() < 3);
+
+    let controller = AbortController::new().unwrap();
+    controller.abort();
+    let stream =
+        decrypt_readable_abortable(cipher, &readable(&encrypted), &controller.signal()).unwrap();
+    assert!(read_all(&stream).await.is_err());
+}
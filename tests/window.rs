@@ -0,0 +1,28 @@
+//! Test suite for the main browser thread.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::algorithm::Algorithm;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+pub const DEFAULT_KEY: [u8; 32] = [42; 32];
+
+#[wasm_bindgen_test]
+async fn should_encrypt_and_decrypt_from_window() {
+    console_error_panic_hook::set_once();
+
+    let clear_msg = b"Hello World!";
+    let crypto = browser_crypto::aes256gcm::Aes256Gcm::from_key(&DEFAULT_KEY)
+        .await
+        .unwrap();
+    let nonce = browser_crypto::aes256gcm::Aes256Gcm::generate_nonce().unwrap();
+    let encrypted = crypto.encrypt(&nonce, clear_msg).await.unwrap();
+
+    let decrypted = crypto.decrypt(&nonce, &encrypted).await.unwrap();
+
+    assert_eq!(clear_msg, decrypted.as_slice());
+}
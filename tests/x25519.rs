@@ -0,0 +1,97 @@
+//! Test suite for the X25519 implementation.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::x25519::{X25519KeyPair, X25519PrivateKey, X25519PublicKey};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+// RFC 7748, 6.1, with the private key of Alice wrapped in PKCS#8
+const ALICE_PRIVATE_KEY: [u8; 48] = [
+    0x30, 0x2e, 0x02, 0x01, 0x00, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x6e, 0x04, 0x22, 0x04, 0x20,
+    0x77, 0x07, 0x6d, 0x0a, 0x73, 0x18, 0xa5, 0x7d, 0x3c, 0x16, 0xc1, 0x72, 0x51, 0xb2, 0x66, 0x45,
+    0xdf, 0x4c, 0x2f, 0x87, 0xeb, 0xc0, 0x99, 0x2a, 0xb1, 0x77, 0xfb, 0xa5, 0x1d, 0xb9, 0x2c, 0x2a,
+];
+const BOB_PUBLIC_KEY: [u8; 32] = [
+    0xde, 0x9e, 0xdb, 0x7d, 0x7b, 0x7d, 0xc1, 0xb4, 0xd3, 0x5b, 0x61, 0xc2, 0xec, 0xe4, 0x35, 0x37,
+    0x3f, 0x83, 0x43, 0xc8, 0x5b, 0x78, 0x67, 0x4d, 0xad, 0xfc, 0x7e, 0x14, 0x6f, 0x88, 0x2b, 0x4f,
+];
+const SHARED_SECRET: [u8; 32] = [
+    0x4a, 0x5d, 0x9d, 0x5b, 0xa4, 0xce, 0x2d, 0xe1, 0x72, 0x8e, 0x3b, 0xf4, 0x80, 0x35, 0x0f, 0x25,
+    0xe0, 0x7e, 0x21, 0xc9, 0x47, 0xd1, 0x9e, 0x33, 0x76, 0xf0, 0x9b, 0x3c, 0x1e, 0x16, 0x17, 0x42,
+];
+
+#[wasm_bindgen_test]
+async fn should_agree_on_shared_secret() {
+    console_error_panic_hook::set_once();
+    if !browser_crypto::x25519::is_supported().await {
+        return;
+    }
+
+    let alice = X25519KeyPair::generate().await.unwrap();
+    let bob = X25519KeyPair::generate().await.unwrap();
+    let left = alice
+        .private_key
+        .diffie_hellman(&bob.public_key)
+        .await
+        .unwrap();
+    let right = bob
+        .private_key
+        .diffie_hellman(&alice.public_key)
+        .await
+        .unwrap();
+    assert_eq!(left.len(), 32);
+    assert_eq!(left, right);
+
+    let raw = bob.public_key.to_raw().await.unwrap();
+    assert_eq!(raw.len(), browser_crypto::x25519::PUBLIC_KEY_SIZE);
+    let public_key = X25519PublicKey::from_raw(&raw).await.unwrap();
+    assert_eq!(
+        alice.private_key.diffie_hellman(&public_key).await.unwrap(),
+        left
+    );
+}
+
+#[wasm_bindgen_test]
+async fn should_match_rfc_vector() {
+    console_error_panic_hook::set_once();
+    if !browser_crypto::x25519::is_supported().await {
+        return;
+    }
+
+    let private_key = X25519PrivateKey::from_pkcs8(&ALICE_PRIVATE_KEY)
+        .await
+        .unwrap();
+    let public_key = X25519PublicKey::from_raw(&BOB_PUBLIC_KEY).await.unwrap();
+    let secret = private_key.diffie_hellman(&public_key).await.unwrap();
+    assert_eq!(secret, SHARED_SECRET);
+
+    let exported = private_key.to_pkcs8().await.unwrap();
+    let private_key = X25519PrivateKey::from_pkcs8(&exported).await.unwrap();
+    let spki = public_key.to_spki().await.unwrap();
+    let public_key = X25519PublicKey::from_spki(&spki).await.unwrap();
+    assert_eq!(
+        private_key.diffie_hellman(&public_key).await.unwrap(),
+        SHARED_SECRET
+    );
+}
+
+#[wasm_bindgen_test]
+async fn should_reject_low_order_point() {
+    console_error_panic_hook::set_once();
+    if !browser_crypto::x25519::is_supported().await {
+        return;
+    }
+
+    let private_key = X25519PrivateKey::from_pkcs8(&ALICE_PRIVATE_KEY)
+        .await
+        .unwrap();
+    let Ok(public_key) = X25519PublicKey::from_raw(&[0u8; 32]).await else {
+        // some browsers already reject the point on import
+        return;
+    };
+    assert!(private_key.diffie_hellman(&public_key).await.is_err());
+}
@@ -0,0 +1,98 @@
+//! Test suite for the zeroize integration.
+
+#![cfg(all(target_arch = "wasm32", feature = "zeroize"))]
+
+extern crate wasm_bindgen_test;
+
+use browser_crypto::aes_gcm::Aes256Gcm;
+use browser_crypto::algorithm::{Algorithm, Nonce};
+use browser_crypto::hash::Hash;
+use browser_crypto::hpke::{ReceiverContext, SenderContext};
+use browser_crypto::jwk::{EcJwk, Jwk, OctJwk, RsaJwk};
+use browser_crypto::pbkdf2::Pbkdf2;
+use wasm_bindgen_test::*;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+
+#[wasm_bindgen_test]
+async fn should_import_and_export_keys() {
+    console_error_panic_hook::set_once();
+
+    let key = [7u8; 32];
+    let cipher = Aes256Gcm::from_key(&key).await.unwrap();
+    let nonce = Nonce::<Aes256Gcm>::from_slice(&[1u8; 12]).unwrap();
+    let encrypted = cipher.encrypt(&nonce, b"Hello World!").await.unwrap();
+
+    // the exported key is copied before its buffer is overwritten
+    let Jwk::Oct(exported) = cipher.to_jwk().await.unwrap() else {
+        panic!("expected an oct key");
+    };
+    let imported = Aes256Gcm::from_jwk(&Jwk::from(exported)).await.unwrap();
+    let decrypted = imported.decrypt(&nonce, &encrypted).await.unwrap();
+    assert_eq!(decrypted, b"Hello World!");
+}
+
+#[wasm_bindgen_test]
+async fn should_derive_bits() {
+    console_error_panic_hook::set_once();
+
+    // RFC 6070 vector, with SHA-256
+    let pbkdf2 = Pbkdf2::from_password(b"password", Hash::Sha256, b"salt", 1)
+        .await
+        .unwrap();
+    let bits = pbkdf2.derive_bits(256).await.unwrap();
+    assert_eq!(
+        bits,
+        [
+            0x12, 0x0f, 0xb6, 0xcf, 0xfc, 0xf8, 0xb3, 0x2c, 0x43, 0xe7, 0x22, 0x52, 0x56, 0xc4,
+            0xf8, 0x37, 0xa8, 0x65, 0x48, 0xc9, 0x2c, 0xcc, 0x35, 0x48, 0x08, 0x05, 0x98, 0x7c,
+            0xb7, 0x0b, 0xe1, 0x7b
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+fn should_zeroize_jwk() {
+    console_error_panic_hook::set_once();
+
+    let mut jwk = Jwk::from(OctJwk::new("AAECAwQFBgcICQoLDA0ODw")).with_kid("main");
+    jwk.zeroize();
+    let Jwk::Oct(ref inner) = jwk else {
+        panic!("expected an oct key");
+    };
+    assert!(inner.k.is_empty());
+    assert_eq!(jwk.kid(), Some("main"));
+
+    let mut jwk = Jwk::from(EcJwk {
+        d: Some("private".into()),
+        ..EcJwk::new("P-256", "x", "y")
+    });
+    jwk.zeroize();
+    assert!(matches!(jwk, Jwk::Ec(EcJwk { d: None, .. })));
+
+    let mut jwk = Jwk::from(RsaJwk {
+        d: Some("private".into()),
+        p: Some("first".into()),
+        ..RsaJwk::new("n", "e")
+    });
+    jwk.zeroize();
+    assert!(matches!(
+        jwk,
+        Jwk::Rsa(RsaJwk {
+            d: None,
+            p: None,
+            ..
+        })
+    ));
+}
+
+#[wasm_bindgen_test]
+fn should_zeroize_secrets_on_drop() {
+    assert_zeroize_on_drop::<SenderContext>();
+    assert_zeroize_on_drop::<ReceiverContext>();
+    #[cfg(feature = "keystore")]
+    assert_zeroize_on_drop::<browser_crypto::local_keystore::LocalKeystore>();
+}